@@ -0,0 +1,312 @@
+//! Built-in [`Collector`](crate::audit::engine::Collector) implementations,
+//! ported from single-purpose commands so they can share a pass over the
+//! logs via [`crate::audit::engine::run_collectors`] instead of each
+//! re-reading the files on its own.
+
+use crate::audit::engine::Collector;
+use crate::audit::findings::{Finding, FindingCategory, Severity};
+use crate::audit::types::AuditEntry;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Counts successful Kubernetes/OpenShift logins and per-entity totals,
+/// mirroring [`crate::commands::k8s_auth`]'s core counter.
+#[derive(Debug, Default)]
+pub struct K8sLoginCollector {
+    k8s_logins: usize,
+    entities_seen: HashMap<String, usize>,
+}
+
+#[derive(Debug, Serialize)]
+struct K8sLoginReport {
+    k8s_logins: usize,
+    unique_entities: usize,
+    entities_seen: HashMap<String, usize>,
+}
+
+impl K8sLoginCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Collector for K8sLoginCollector {
+    fn observe(&mut self, entry: &AuditEntry) {
+        if entry.entry_type != "response" || entry.error.is_some() {
+            return;
+        }
+        let Some(request) = &entry.request else {
+            return;
+        };
+        let Some(path) = request.path.as_deref() else {
+            return;
+        };
+        if !path.ends_with("/login") {
+            return;
+        }
+
+        let is_k8s_by_path = path.contains("kubernetes") || path.contains("openshift");
+        let is_k8s_by_mount = request
+            .mount_type
+            .as_deref()
+            .is_some_and(|mt| mt == "kubernetes" || mt == "openshift");
+        if !(is_k8s_by_path || is_k8s_by_mount) {
+            return;
+        }
+
+        self.k8s_logins += 1;
+        if let Some(entity_id) = entry.auth.as_ref().and_then(|a| a.entity_id.as_deref()) {
+            *self.entities_seen.entry(entity_id.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    fn finalize(self: Box<Self>) -> serde_json::Value {
+        serde_json::to_value(K8sLoginReport {
+            k8s_logins: self.k8s_logins,
+            unique_entities: self.entities_seen.len(),
+            entities_seen: self.entities_seen,
+        })
+        .unwrap_or(serde_json::Value::Null)
+    }
+}
+
+/// Extracts entity-to-display-name mappings from login events, mirroring
+/// [`crate::commands::preprocess_entities`]'s extractor.
+#[derive(Debug, Default)]
+pub struct EntityMappingCollector {
+    mappings: HashMap<String, EntityMapping>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EntityMapping {
+    pub display_name: String,
+    pub mount_path: String,
+    pub mount_accessor: String,
+    pub username: Option<String>,
+    pub login_count: usize,
+    pub first_seen: String,
+    pub last_seen: String,
+}
+
+impl EntityMappingCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Collector for EntityMappingCollector {
+    fn observe(&mut self, entry: &AuditEntry) {
+        let Some(request) = &entry.request else {
+            return;
+        };
+        let Some(path) = &request.path else {
+            return;
+        };
+        if !path.starts_with("auth/") || !path.contains("/login") {
+            return;
+        }
+
+        let Some(auth) = &entry.auth else {
+            return;
+        };
+        let Some(entity_id) = auth.entity_id.as_ref().filter(|id| !id.is_empty()) else {
+            return;
+        };
+        let Some(display_name) = auth.display_name.as_ref().filter(|n| !n.is_empty()) else {
+            return;
+        };
+
+        let mount_path = path
+            .trim_end_matches("/login")
+            .trim_end_matches(&format!("/{}", display_name))
+            .to_string();
+        let mount_accessor = auth.accessor.clone().unwrap_or_default();
+        let username = auth
+            .metadata
+            .as_ref()
+            .and_then(|m| m.get("username"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        self.mappings
+            .entry(entity_id.clone())
+            .and_modify(|mapping| {
+                mapping.login_count += 1;
+                if entry.time > mapping.last_seen {
+                    mapping.display_name = display_name.clone();
+                    mapping.last_seen = entry.time.clone();
+                }
+            })
+            .or_insert_with(|| EntityMapping {
+                display_name: display_name.clone(),
+                mount_path,
+                mount_accessor,
+                username,
+                login_count: 1,
+                first_seen: entry.time.clone(),
+                last_seen: entry.time.clone(),
+            });
+    }
+
+    fn finalize(self: Box<Self>) -> serde_json::Value {
+        serde_json::to_value(self.mappings).unwrap_or(serde_json::Value::Null)
+    }
+}
+
+/// Normalizes secret-path fan-out, privileged token activity, and
+/// no-entity logins into one typed [`Finding`] stream, mirroring the
+/// detection each of `token-analysis`, `path-hotspots`, and
+/// `entity-analysis gaps` does today but reported as `Finding`s instead of
+/// a per-command table. See [`crate::commands::findings`].
+pub struct FindingsCollector {
+    secret_fanout_threshold: usize,
+    secret_ops_threshold: usize,
+    privileged_ops_threshold: usize,
+    secret_paths: HashMap<String, (HashSet<String>, usize)>,
+    privileged_entities: HashMap<String, (usize, Option<String>)>,
+    no_entity_paths: HashMap<String, usize>,
+}
+
+impl FindingsCollector {
+    pub fn new(
+        secret_fanout_threshold: usize,
+        secret_ops_threshold: usize,
+        privileged_ops_threshold: usize,
+    ) -> Self {
+        Self {
+            secret_fanout_threshold,
+            secret_ops_threshold,
+            privileged_ops_threshold,
+            secret_paths: HashMap::new(),
+            privileged_entities: HashMap::new(),
+            no_entity_paths: HashMap::new(),
+        }
+    }
+
+    fn is_secret_path(path: &str) -> bool {
+        path.starts_with("secret/data/") || path.starts_with("kv/data/")
+    }
+
+    /// A policy name is treated as privileged if it's exactly `root` or
+    /// contains `admin`, matching the coarse substring matching
+    /// [`crate::audit::indicators`] already uses for rule predicates
+    /// rather than pulling in a real policy-document parser.
+    fn is_privileged_policy(policy: &str) -> bool {
+        policy == "root" || policy.contains("admin")
+    }
+}
+
+impl Collector for FindingsCollector {
+    fn observe(&mut self, entry: &AuditEntry) {
+        let path = entry.request.as_ref().and_then(|r| r.path.clone());
+        let auth = entry.auth.as_ref();
+
+        if let Some(path) = &path {
+            if Self::is_secret_path(path) {
+                let (entities, operations) = self.secret_paths.entry(path.clone()).or_default();
+                *operations += 1;
+                if let Some(entity_id) = auth.and_then(|a| a.entity_id.clone()) {
+                    entities.insert(entity_id);
+                }
+            }
+        }
+
+        if let Some(auth) = auth {
+            let is_privileged = auth
+                .policies
+                .iter()
+                .chain(auth.token_policies.iter())
+                .flatten()
+                .any(|policy| Self::is_privileged_policy(policy));
+            if is_privileged {
+                let key = auth.entity_id.clone().unwrap_or_else(|| "unknown".to_string());
+                let record = self.privileged_entities.entry(key).or_insert((0, None));
+                record.0 += 1;
+                if record.1.is_none() {
+                    record.1 = auth.display_name.clone();
+                }
+            }
+
+            if auth.entity_id.is_none() {
+                if let Some(path) = &path {
+                    if path.starts_with("auth/") && path.contains("/login") {
+                        *self.no_entity_paths.entry(path.clone()).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    fn finalize(self: Box<Self>) -> serde_json::Value {
+        let mut findings = Vec::new();
+
+        for (path, (entities, operations)) in &self.secret_paths {
+            if entities.len() < self.secret_fanout_threshold && *operations < self.secret_ops_threshold {
+                continue;
+            }
+            let severity = if entities.len() >= self.secret_fanout_threshold * 2
+                || *operations >= self.secret_ops_threshold * 2
+            {
+                Severity::Critical
+            } else {
+                Severity::High
+            };
+            findings.push(Finding {
+                id: format!("secret-access:{}", path),
+                category: FindingCategory::SecretAccess,
+                severity,
+                resource_path: Some(path.clone()),
+                entity_id: None,
+                display_name: None,
+                description: format!(
+                    "{} distinct entities performed {} operations against {}",
+                    entities.len(),
+                    operations,
+                    path
+                ),
+                evidence_count: *operations,
+            });
+        }
+
+        for (entity_id, (operations, display_name)) in &self.privileged_entities {
+            if *operations < self.privileged_ops_threshold {
+                continue;
+            }
+            let severity = if *operations >= self.privileged_ops_threshold * 10 {
+                Severity::Critical
+            } else {
+                Severity::High
+            };
+            findings.push(Finding {
+                id: format!("privileged-auth:{}", entity_id),
+                category: FindingCategory::PrivilegedAuth,
+                severity,
+                resource_path: None,
+                entity_id: Some(entity_id.clone()),
+                display_name: display_name.clone(),
+                description: format!(
+                    "Entity {} performed {} operations with a root or admin-like policy",
+                    entity_id, operations
+                ),
+                evidence_count: *operations,
+            });
+        }
+
+        for (path, operations) in &self.no_entity_paths {
+            findings.push(Finding {
+                id: format!("policy-anomaly:{}", path),
+                category: FindingCategory::PolicyAnomaly,
+                severity: Severity::Medium,
+                resource_path: Some(path.clone()),
+                entity_id: None,
+                display_name: None,
+                description: format!("{} logins against {} resolved to no entity_id", operations, path),
+                evidence_count: *operations,
+            });
+        }
+
+        findings.sort_by(|a, b| a.severity.cmp(&b.severity).then_with(|| b.evidence_count.cmp(&a.evidence_count)));
+
+        serde_json::to_value(findings).unwrap_or(serde_json::Value::Null)
+    }
+}
@@ -0,0 +1,200 @@
+//! Unified streaming analysis engine with pluggable collectors.
+//!
+//! Several commands (`k8s-auth`, `preprocess-entities`, ...) each re-read the
+//! same log files end-to-end, repeating an identical file-size/progress/
+//! line-read/[`serde_json::from_str`] skeleton and differing only in what
+//! they accumulate per entry. [`run_collectors`] factors that skeleton out:
+//! it owns the progress bar, multi-file loop, byte accounting, and JSON
+//! decode, and feeds every parsed [`AuditEntry`] to each [`Collector`] in
+//! turn, so N analyses share one pass over the logs instead of each doing
+//! its own.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use vault_audit_tools::audit::engine::{run_collectors, Collector};
+//! use vault_audit_tools::audit::types::AuditEntry;
+//!
+//! struct CountAll(usize);
+//! impl Collector for CountAll {
+//!     fn observe(&mut self, _entry: &AuditEntry) {
+//!         self.0 += 1;
+//!     }
+//!     fn finalize(self: Box<Self>) -> serde_json::Value {
+//!         serde_json::json!({ "entries": self.0 })
+//!     }
+//! }
+//!
+//! let mut collectors: Vec<Box<dyn Collector>> = vec![Box::new(CountAll(0))];
+//! let (reports, stats) = run_collectors(&["audit.log".to_string()], &mut collectors).unwrap();
+//! println!("{} lines processed", stats.total_lines);
+//! ```
+
+use crate::audit::types::AuditEntry;
+use crate::utils::format::format_number;
+use crate::utils::reader::open_file;
+use anyhow::Result;
+use std::io::{BufRead, BufReader};
+
+/// One analysis accumulating state over a pass of [`AuditEntry`] values fed
+/// to it by [`run_collectors`]. Implementors mirror a single command's
+/// previous per-file accumulator (e.g. the K8s login counter, the entity
+/// mapping extractor), but without owning any of the file/progress plumbing
+/// - that's [`run_collectors`]'s job, so multiple collectors can share one
+/// pass over the same logs.
+pub trait Collector {
+    /// Fold one parsed entry into this collector's state. Called once per
+    /// successfully-decoded line, in file order.
+    fn observe(&mut self, entry: &AuditEntry);
+
+    /// Consume the collector and render its accumulated state as a
+    /// `serde_json::Value`, so [`run_collectors`] can return a uniform
+    /// `Vec` across collectors of different concrete types. Implementors
+    /// that want a typed report build their own struct internally and
+    /// `serde_json::to_value` it here.
+    fn finalize(self: Box<Self>) -> serde_json::Value;
+}
+
+/// Aggregate statistics for one [`run_collectors`] pass, analogous to
+/// [`crate::utils::processor::ProcessStats`] but scoped to this engine.
+#[derive(Debug, Default, Clone)]
+pub struct EngineStats {
+    pub total_lines: usize,
+    pub parsed_entries: usize,
+    pub bytes_read: u64,
+    pub files_processed: usize,
+}
+
+/// Read every file in `log_files` once, feeding each parsed [`AuditEntry`]
+/// to every collector in `collectors` via [`Collector::observe`], then
+/// finalize them all in order. Lines that fail to parse as [`AuditEntry`]
+/// are skipped (consistent with the rest of this crate's default
+/// [`crate::utils::processor::OnParseError::Skip`] behavior) rather than
+/// aborting the run.
+pub fn run_collectors(
+    log_files: &[String],
+    collectors: &mut [Box<dyn Collector>],
+) -> Result<(Vec<serde_json::Value>, EngineStats)> {
+    let mut stats = EngineStats::default();
+
+    for (file_idx, log_file) in log_files.iter().enumerate() {
+        eprintln!(
+            "[{}/{}] Processing: {}",
+            file_idx + 1,
+            log_files.len(),
+            log_file
+        );
+
+        let file_size = std::fs::metadata(log_file).ok().map(|m| m.len() as usize);
+        let mut progress = if let Some(size) = file_size {
+            crate::utils::progress::ProgressBar::new(size, "Processing")
+        } else {
+            crate::utils::progress::ProgressBar::new_spinner("Processing")
+        };
+
+        let file = open_file(log_file)?;
+        let reader = BufReader::new(file);
+
+        let mut file_lines = 0;
+        let mut bytes_read = 0;
+
+        for line in reader.lines() {
+            file_lines += 1;
+            stats.total_lines += 1;
+            let line = line?;
+            bytes_read += line.len() + 1;
+
+            if file_lines % 10_000 == 0 {
+                if let Some(size) = file_size {
+                    progress.update(bytes_read.min(size));
+                } else {
+                    progress.update(file_lines);
+                }
+            }
+
+            let Ok(entry) = serde_json::from_str::<AuditEntry>(&line) else {
+                continue;
+            };
+            stats.parsed_entries += 1;
+
+            for collector in collectors.iter_mut() {
+                collector.observe(&entry);
+            }
+        }
+
+        if let Some(size) = file_size {
+            progress.update(size);
+        }
+        progress.finish_with_message(&format!(
+            "Processed {} lines from this file",
+            format_number(file_lines)
+        ));
+
+        stats.bytes_read += bytes_read as u64;
+        stats.files_processed += 1;
+    }
+
+    let reports = collectors
+        .iter_mut()
+        .map(|c| {
+            // `observe` took `&mut`, so finalize needs ownership; collectors
+            // are consumed one at a time via a placeholder swap since the
+            // slice itself can't be drained by value.
+            let placeholder: Box<dyn Collector> = Box::new(NullCollector);
+            std::mem::replace(c, placeholder).finalize()
+        })
+        .collect();
+
+    Ok((reports, stats))
+}
+
+/// Zero-state stand-in swapped into `collectors` by [`run_collectors`] so
+/// each real collector can be moved out and finalized by value.
+struct NullCollector;
+impl Collector for NullCollector {
+    fn observe(&mut self, _entry: &AuditEntry) {}
+    fn finalize(self: Box<Self>) -> serde_json::Value {
+        serde_json::Value::Null
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    struct CountResponses(usize);
+    impl Collector for CountResponses {
+        fn observe(&mut self, entry: &AuditEntry) {
+            if entry.entry_type == "response" {
+                self.0 += 1;
+            }
+        }
+        fn finalize(self: Box<Self>) -> serde_json::Value {
+            serde_json::json!({ "responses": self.0 })
+        }
+    }
+
+    #[test]
+    fn test_run_collectors_shares_one_pass() {
+        let mut temp = tempfile::NamedTempFile::new().unwrap();
+        writeln!(temp, r#"{{"type":"request","time":"2025-01-01T00:00:00Z"}}"#).unwrap();
+        writeln!(temp, r#"{{"type":"response","time":"2025-01-01T00:00:01Z"}}"#).unwrap();
+        writeln!(temp, r#"{{"type":"response","time":"2025-01-01T00:00:02Z"}}"#).unwrap();
+        temp.flush().unwrap();
+
+        let files = vec![temp.path().to_str().unwrap().to_string()];
+        let mut collectors: Vec<Box<dyn Collector>> = vec![
+            Box::new(CountResponses(0)),
+            Box::new(CountResponses(0)),
+        ];
+
+        let (reports, stats) = run_collectors(&files, &mut collectors).unwrap();
+
+        assert_eq!(stats.total_lines, 3);
+        assert_eq!(stats.parsed_entries, 3);
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0]["responses"], 2);
+        assert_eq!(reports[1]["responses"], 2);
+    }
+}
@@ -0,0 +1,55 @@
+//! Shared `Finding` taxonomy normalizing hits from several detectors
+//! (secret-path fan-out, privileged token activity, no-entity logins) into
+//! one typed, severity-ranked alert stream - see
+//! [`crate::commands::findings`].
+//!
+//! Modeled after the "id + severity + evidence" shape common to
+//! dependency/secret/static-analysis scanners, rather than each detector
+//! reporting its own plain-text summary the way `token-analysis`,
+//! `path-hotspots`, and `entity-analysis gaps` do today.
+
+use serde::{Deserialize, Serialize};
+
+/// What a [`Finding`] represents, broad enough to bucket hits from
+/// unrelated detectors under a shared taxonomy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingCategory {
+    /// Heavy or wide-fan-out reads of `secret/data/*` / `kv/data/*`.
+    SecretAccess,
+    /// Token activity carrying a root or admin-like policy.
+    PrivilegedAuth,
+    /// Audited activity that doesn't fit Vault's normal entity model, e.g.
+    /// a login with no `entity_id` attached.
+    PolicyAnomaly,
+    /// Reserved for detectors added later that don't yet map cleanly onto
+    /// one of the categories above.
+    Unknown,
+}
+
+/// How urgently a [`Finding`] should be triaged. Ranked `Critical` (most
+/// urgent) through `Low`, the same `critical`/`high`/`medium`/`low` scale
+/// [`crate::commands::threat_scan`] already ranks indicator hits by - the
+/// derived [`Ord`] sorts a finding list most-severe-first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Critical,
+    High,
+    Medium,
+    Low,
+}
+
+/// One normalized alert, comparable and dedupable alongside findings
+/// produced by every other detector feeding [`crate::commands::findings`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Finding {
+    pub id: String,
+    pub category: FindingCategory,
+    pub severity: Severity,
+    pub resource_path: Option<String>,
+    pub entity_id: Option<String>,
+    pub display_name: Option<String>,
+    pub description: String,
+    pub evidence_count: usize,
+}
@@ -0,0 +1,364 @@
+//! Searchable inverted index over parsed [`AuditEntry`] fields.
+//!
+//! Aggregate commands re-scan every log line for each question asked of
+//! them. [`Index`] instead tokenizes a fixed set of fields once per build
+//! pass and records, per field/term, a postings list of [`Posting`]s (source
+//! file + byte offset), so an investigator can look a term up directly
+//! without re-reading the logs. The indexed fields are [`Field::Path`],
+//! [`Field::MountType`], [`Field::DisplayName`], [`Field::EntityId`],
+//! [`Field::Policies`], and [`Field::RemoteAddress`].
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use vault_audit_tools::audit::index::Index;
+//!
+//! let index = Index::build(&["audit.log".to_string()]).unwrap();
+//! index.save("audit.index.json").unwrap();
+//!
+//! let loaded = Index::load("audit.index.json").unwrap();
+//! for posting in loaded.query(&vault_audit_tools::audit::index::Query::parse("path:kubernetes").unwrap()) {
+//!     println!("{}:{}", posting.file, posting.offset);
+//! }
+//! ```
+
+use crate::audit::types::AuditEntry;
+use crate::utils::reader::open_file;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, BufReader};
+
+/// Indexed fields, named to match the `field:term` query syntax.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Field {
+    Path,
+    MountType,
+    DisplayName,
+    EntityId,
+    Policies,
+    RemoteAddress,
+}
+
+impl Field {
+    fn all() -> &'static [Field] {
+        &[
+            Field::Path,
+            Field::MountType,
+            Field::DisplayName,
+            Field::EntityId,
+            Field::Policies,
+            Field::RemoteAddress,
+        ]
+    }
+
+    fn parse(name: &str) -> Option<Field> {
+        match name {
+            "path" => Some(Field::Path),
+            "mount_type" => Some(Field::MountType),
+            "display_name" => Some(Field::DisplayName),
+            "entity_id" => Some(Field::EntityId),
+            "policies" => Some(Field::Policies),
+            "remote_address" => Some(Field::RemoteAddress),
+            _ => None,
+        }
+    }
+
+    /// Extract this field's terms from one entry. A field may contribute
+    /// zero terms (absent), one (most fields), or several (`policies`).
+    fn terms(self, entry: &AuditEntry) -> Vec<String> {
+        match self {
+            Field::Path => entry.path().map(|s| s.to_string()).into_iter().collect(),
+            Field::MountType => entry
+                .mount_type()
+                .map(|s| s.to_string())
+                .into_iter()
+                .collect(),
+            Field::DisplayName => entry
+                .display_name()
+                .map(|s| s.to_string())
+                .into_iter()
+                .collect(),
+            Field::EntityId => entry
+                .entity_id()
+                .map(|s| s.to_string())
+                .into_iter()
+                .collect(),
+            Field::Policies => entry
+                .auth
+                .as_ref()
+                .and_then(|a| a.policies.as_ref())
+                .cloned()
+                .unwrap_or_default(),
+            Field::RemoteAddress => entry
+                .request
+                .as_ref()
+                .and_then(|r| r.remote_address.clone())
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+/// One occurrence of a term: which file and which byte offset within it, so
+/// the original line can be rehydrated on demand.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Posting {
+    pub file: String,
+    pub offset: u64,
+}
+
+/// A field-scoped inverted index: `Field -> Term -> postings`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Index {
+    postings: HashMap<Field, HashMap<String, Vec<Posting>>>,
+}
+
+impl Index {
+    /// Build an index from a full pass over `log_files`.
+    pub fn build(log_files: &[String]) -> Result<Self> {
+        let mut index = Index::default();
+
+        for log_file in log_files {
+            eprintln!("Indexing: {}", log_file);
+            let file = open_file(log_file)?;
+            let mut reader = BufReader::new(file);
+            let mut offset: u64 = 0;
+
+            loop {
+                let mut line = String::new();
+                let read = reader.read_line(&mut line)?;
+                if read == 0 {
+                    break;
+                }
+                let line_offset = offset;
+                offset += read as u64;
+
+                let trimmed = line.trim_end_matches(['\n', '\r']);
+                let Ok(entry) = serde_json::from_str::<AuditEntry>(trimmed) else {
+                    continue;
+                };
+
+                for &field in Field::all() {
+                    for term in field.terms(&entry) {
+                        index
+                            .postings
+                            .entry(field)
+                            .or_default()
+                            .entry(term)
+                            .or_default()
+                            .push(Posting {
+                                file: log_file.clone(),
+                                offset: line_offset,
+                            });
+                    }
+                }
+            }
+        }
+
+        Ok(index)
+    }
+
+    /// Serialize this index to `path` as JSON.
+    pub fn save(&self, path: &str) -> Result<()> {
+        let json = serde_json::to_string(self).context("Failed to serialize index")?;
+        std::fs::write(path, json)
+            .with_context(|| format!("Failed to write index to {}", path))?;
+        Ok(())
+    }
+
+    /// Load a previously-[`save`](Index::save)d index from `path`.
+    pub fn load(path: &str) -> Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read index from {}", path))?;
+        serde_json::from_str(&json).context("Failed to parse index file")
+    }
+
+    /// Run a parsed [`Query`] against this index, returning the matching
+    /// postings with duplicates removed.
+    pub fn query(&self, query: &Query) -> Vec<Posting> {
+        let mut sets: Vec<HashSet<Posting>> = query
+            .clauses
+            .iter()
+            .map(|clause| self.postings_for_clause(clause))
+            .collect();
+
+        let Some(mut combined) = sets.pop() else {
+            return Vec::new();
+        };
+        for set in sets {
+            match query.op {
+                QueryOp::And => combined = combined.intersection(&set).cloned().collect(),
+                QueryOp::Or => combined = combined.union(&set).cloned().collect(),
+            }
+        }
+
+        let mut result: Vec<Posting> = combined.into_iter().collect();
+        result.sort_by(|a, b| a.file.cmp(&b.file).then(a.offset.cmp(&b.offset)));
+        result
+    }
+
+    fn postings_for_clause(&self, clause: &Clause) -> HashSet<Posting> {
+        let Some(terms) = self.postings.get(&clause.field) else {
+            return HashSet::new();
+        };
+
+        if let Some(prefix) = clause.term.strip_suffix('*') {
+            terms
+                .iter()
+                .filter(|(term, _)| term.starts_with(prefix))
+                .flat_map(|(_, postings)| postings.iter().cloned())
+                .collect()
+        } else {
+            terms
+                .get(&clause.term)
+                .map(|postings| postings.iter().cloned().collect())
+                .unwrap_or_default()
+        }
+    }
+}
+
+/// One `field:term` clause of a [`Query`].
+#[derive(Debug, Clone)]
+struct Clause {
+    field: Field,
+    term: String,
+}
+
+/// Whether a [`Query`]'s clauses are combined by intersecting (`AND`, the
+/// default) or unioning (`OR`) their postings lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryOp {
+    And,
+    Or,
+}
+
+/// A parsed search query: one or more `field:term` clauses combined with a
+/// single `QueryOp`. A trailing `*` on the term (e.g. `path:auth/kubernetes/*`)
+/// matches any term with that prefix; a `*` anywhere else is matched literally.
+#[derive(Debug, Clone)]
+pub struct Query {
+    clauses: Vec<Clause>,
+    op: QueryOp,
+}
+
+impl Query {
+    /// Parse a query string like `"mount_type:kubernetes entity_id:abc123"`
+    /// (space-separated clauses, `AND`ed by default) or
+    /// `"path:foo OR path:bar"` (case-sensitive `OR` keyword switches the
+    /// whole query to union semantics).
+    pub fn parse(input: &str) -> Result<Self> {
+        let op = if input.split_whitespace().any(|tok| tok == "OR") {
+            QueryOp::Or
+        } else {
+            QueryOp::And
+        };
+
+        let mut clauses = Vec::new();
+        for token in input.split_whitespace() {
+            if token == "OR" || token == "AND" {
+                continue;
+            }
+            let Some((field_name, term)) = token.split_once(':') else {
+                bail!("Invalid query clause '{}', expected field:term", token);
+            };
+            let Some(field) = Field::parse(field_name) else {
+                bail!(
+                    "Unknown field '{}'; expected one of path, mount_type, display_name, entity_id, policies, remote_address",
+                    field_name
+                );
+            };
+            clauses.push(Clause {
+                field,
+                term: term.to_string(),
+            });
+        }
+
+        if clauses.is_empty() {
+            bail!("Query must contain at least one field:term clause");
+        }
+
+        Ok(Query { clauses, op })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn sample_log() -> tempfile::NamedTempFile {
+        let mut temp = tempfile::NamedTempFile::new().unwrap();
+        writeln!(
+            temp,
+            r#"{{"type":"request","time":"2025-01-01T00:00:00Z","auth":{{"entity_id":"abc123","policies":["default"]}},"request":{{"path":"auth/kubernetes/login","mount_type":"kubernetes","remote_address":"10.0.0.1"}}}}"#
+        )
+        .unwrap();
+        writeln!(
+            temp,
+            r#"{{"type":"request","time":"2025-01-01T00:00:01Z","auth":{{"entity_id":"def456","policies":["admin"]}},"request":{{"path":"kv/data/app","mount_type":"kv","remote_address":"10.0.0.2"}}}}"#
+        )
+        .unwrap();
+        temp.flush().unwrap();
+        temp
+    }
+
+    #[test]
+    fn test_build_and_exact_query() {
+        let temp = sample_log();
+        let files = vec![temp.path().to_str().unwrap().to_string()];
+        let index = Index::build(&files).unwrap();
+
+        let query = Query::parse("entity_id:abc123").unwrap();
+        let postings = index.query(&query);
+        assert_eq!(postings.len(), 1);
+        assert_eq!(postings[0].offset, 0);
+    }
+
+    #[test]
+    fn test_and_intersection() {
+        let temp = sample_log();
+        let files = vec![temp.path().to_str().unwrap().to_string()];
+        let index = Index::build(&files).unwrap();
+
+        let query = Query::parse("path:kubernetes entity_id:def456").unwrap();
+        assert!(index.query(&query).is_empty());
+
+        let query = Query::parse("mount_type:kubernetes entity_id:abc123").unwrap();
+        assert_eq!(index.query(&query).len(), 1);
+    }
+
+    #[test]
+    fn test_or_union() {
+        let temp = sample_log();
+        let files = vec![temp.path().to_str().unwrap().to_string()];
+        let index = Index::build(&files).unwrap();
+
+        let query = Query::parse("entity_id:abc123 OR entity_id:def456").unwrap();
+        assert_eq!(index.query(&query).len(), 2);
+    }
+
+    #[test]
+    fn test_prefix_query() {
+        let temp = sample_log();
+        let files = vec![temp.path().to_str().unwrap().to_string()];
+        let index = Index::build(&files).unwrap();
+
+        let query = Query::parse("path:auth/*").unwrap();
+        assert_eq!(index.query(&query).len(), 1);
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let temp = sample_log();
+        let files = vec![temp.path().to_str().unwrap().to_string()];
+        let index = Index::build(&files).unwrap();
+
+        let out = tempfile::NamedTempFile::new().unwrap();
+        index.save(out.path().to_str().unwrap()).unwrap();
+        let loaded = Index::load(out.path().to_str().unwrap()).unwrap();
+
+        let query = Query::parse("entity_id:abc123").unwrap();
+        assert_eq!(loaded.query(&query).len(), 1);
+    }
+}
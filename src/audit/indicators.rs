@@ -0,0 +1,285 @@
+//! Configurable threat-indicator rules, shared by
+//! [`crate::commands::threat_scan`].
+//!
+//! An [`Indicator`] is the user-authored, version-controllable unit a
+//! security team ships (a path glob to watch, an entity ID to flag, a
+//! source CIDR that should never show up, a lookup-rate ceiling) -
+//! conceptually the same "declarative rule evaluated against audit data"
+//! shape as [`crate::commands::entity_churn`]'s `SignatureRule`, but scoped
+//! to individual audit entries rather than per-entity churn records, and
+//! with an explicit severity label meant for ranking a hit summary.
+//!
+//! [`load_indicators`] compiles the raw, deserialized [`Indicator`] list
+//! (parsing each glob/CIDR once) into [`CompiledIndicator`]s so matching an
+//! audit entry is cheap per line instead of re-parsing a pattern on every
+//! check.
+
+use crate::audit::types::AuditEntry;
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+use std::fs::File;
+use std::net::IpAddr;
+
+fn default_severity() -> String {
+    "medium".to_string()
+}
+
+/// One operator-defined indicator, loaded from a `--rules` CSV or JSON file.
+/// Every predicate field is optional; an indicator matches an entry when all
+/// of its present predicates match (`None` predicates are ignored).
+/// `max_lookups_per_hour` is evaluated separately, against each entity's
+/// overall read/list rate, rather than per entry - see
+/// [`CompiledIndicator::exceeds_rate`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct Indicator {
+    pub name: String,
+    #[serde(default = "default_severity")]
+    pub severity: String,
+    #[serde(default)]
+    pub path_glob: Option<String>,
+    #[serde(default)]
+    pub entity_id: Option<String>,
+    #[serde(default)]
+    pub operation: Option<String>,
+    #[serde(default)]
+    pub source_cidr: Option<String>,
+    #[serde(default)]
+    pub max_lookups_per_hour: Option<f64>,
+}
+
+/// Simple `*`-wildcard glob match (no other metacharacters), matching the
+/// level of pattern matching already used for rule predicates elsewhere in
+/// this tool (see [`crate::commands::entity_churn`]) rather than pulling in
+/// a full regex engine.
+fn glob_match(pattern: &str, value: &str) -> bool {
+    let segments: Vec<&str> = pattern.split('*').collect();
+    if segments.len() == 1 {
+        return pattern == value;
+    }
+
+    let mut rest = value;
+    for (idx, segment) in segments.iter().enumerate() {
+        if segment.is_empty() {
+            continue;
+        }
+        if idx == 0 {
+            let Some(stripped) = rest.strip_prefix(segment) else {
+                return false;
+            };
+            rest = stripped;
+        } else if idx == segments.len() - 1 {
+            return rest.ends_with(segment);
+        } else {
+            let Some(found) = rest.find(segment) else {
+                return false;
+            };
+            rest = &rest[found + segment.len()..];
+        }
+    }
+    true
+}
+
+/// Parses an `ADDRESS/PREFIX` CIDR spec into its network address and prefix
+/// length, validating the prefix against the address family.
+fn parse_cidr(spec: &str) -> Result<(IpAddr, u8)> {
+    let (addr_str, prefix_str) = spec
+        .split_once('/')
+        .ok_or_else(|| anyhow!("invalid source_cidr '{}': expected ADDRESS/PREFIX", spec))?;
+    let addr: IpAddr = addr_str
+        .parse()
+        .with_context(|| format!("invalid source_cidr '{}': bad address", spec))?;
+    let max_prefix = if addr.is_ipv4() { 32 } else { 128 };
+    let prefix: u8 = prefix_str
+        .parse()
+        .with_context(|| format!("invalid source_cidr '{}': bad prefix length", spec))?;
+    if prefix > max_prefix {
+        bail!(
+            "invalid source_cidr '{}': prefix length exceeds {}",
+            spec,
+            max_prefix
+        );
+    }
+    Ok((addr, prefix))
+}
+
+/// Whether `ip` falls within `network/prefix_len`. IPv4 and IPv6 addresses
+/// never match each other's networks.
+fn ip_in_cidr(ip: IpAddr, network: IpAddr, prefix_len: u8) -> bool {
+    match (ip, network) {
+        (IpAddr::V4(ip), IpAddr::V4(net)) => {
+            let mask = if prefix_len == 0 {
+                0
+            } else {
+                u32::MAX << (32 - prefix_len)
+            };
+            (u32::from(ip) & mask) == (u32::from(net) & mask)
+        }
+        (IpAddr::V6(ip), IpAddr::V6(net)) => {
+            let mask = if prefix_len == 0 {
+                0
+            } else {
+                u128::MAX << (128 - prefix_len)
+            };
+            (u128::from(ip) & mask) == (u128::from(net) & mask)
+        }
+        _ => false,
+    }
+}
+
+/// An [`Indicator`] with its glob/CIDR patterns pre-parsed.
+#[derive(Debug, Clone)]
+pub struct CompiledIndicator {
+    pub name: String,
+    pub severity: String,
+    path_glob: Option<String>,
+    entity_id: Option<String>,
+    operation: Option<String>,
+    cidr: Option<(IpAddr, u8)>,
+    max_lookups_per_hour: Option<f64>,
+}
+
+impl CompiledIndicator {
+    fn compile(indicator: Indicator) -> Result<Self> {
+        let cidr = indicator
+            .source_cidr
+            .as_deref()
+            .map(parse_cidr)
+            .transpose()?;
+        Ok(Self {
+            name: indicator.name,
+            severity: indicator.severity,
+            path_glob: indicator.path_glob,
+            entity_id: indicator.entity_id,
+            operation: indicator.operation,
+            cidr,
+            max_lookups_per_hour: indicator.max_lookups_per_hour,
+        })
+    }
+
+    /// Whether `entry` matches every static predicate this indicator sets
+    /// (path glob, entity ID, operation, source CIDR). Indicators with only
+    /// a `max_lookups_per_hour` predicate and no static predicates never
+    /// match here - see [`CompiledIndicator::exceeds_rate`] instead.
+    pub fn matches_entry(&self, entry: &AuditEntry) -> bool {
+        if let Some(glob) = &self.path_glob {
+            let Some(path) = entry.path() else {
+                return false;
+            };
+            if !glob_match(glob, path) {
+                return false;
+            }
+        }
+        if let Some(entity_id) = &self.entity_id {
+            if entry.entity_id() != Some(entity_id.as_str()) {
+                return false;
+            }
+        }
+        if let Some(operation) = &self.operation {
+            if entry.operation() != Some(operation.as_str()) {
+                return false;
+            }
+        }
+        if let Some((network, prefix_len)) = self.cidr {
+            let Some(remote) = entry.request.as_ref().and_then(|r| r.remote_address.as_deref())
+            else {
+                return false;
+            };
+            let Ok(remote_ip) = remote.parse::<IpAddr>() else {
+                return false;
+            };
+            if !ip_in_cidr(remote_ip, network, prefix_len) {
+                return false;
+            }
+        }
+        self.path_glob.is_some()
+            || self.entity_id.is_some()
+            || self.operation.is_some()
+            || self.cidr.is_some()
+    }
+
+    /// Whether this indicator defines a `max_lookups_per_hour` ceiling.
+    pub fn has_rate_threshold(&self) -> bool {
+        self.max_lookups_per_hour.is_some()
+    }
+
+    /// Whether `lookups_per_hour` exceeds this indicator's
+    /// `max_lookups_per_hour`, when set.
+    pub fn exceeds_rate(&self, lookups_per_hour: f64) -> bool {
+        self.max_lookups_per_hour
+            .is_some_and(|max| lookups_per_hour > max)
+    }
+}
+
+fn load_json(path: &str) -> Result<Vec<Indicator>> {
+    let file =
+        File::open(path).with_context(|| format!("Failed to open indicators file: {}", path))?;
+    serde_json::from_reader(file)
+        .with_context(|| format!("Failed to parse indicators JSON: {}", path))
+}
+
+/// Parses an indicators CSV with a header row and columns
+/// `name,severity,path_glob,entity_id,operation,source_cidr,max_lookups_per_hour`.
+/// Missing trailing columns and empty fields are treated as unset.
+fn load_csv(path: &str) -> Result<Vec<Indicator>> {
+    let file =
+        File::open(path).with_context(|| format!("Failed to open indicators file: {}", path))?;
+    let mut reader = csv::Reader::from_reader(file);
+
+    let non_empty = |s: Option<&str>| s.filter(|v| !v.is_empty()).map(str::to_string);
+
+    let mut indicators = Vec::new();
+    for result in reader.records() {
+        let record = result?;
+        let Some(name) = non_empty(record.get(0)) else {
+            continue;
+        };
+        indicators.push(Indicator {
+            name,
+            severity: non_empty(record.get(1)).unwrap_or_else(default_severity),
+            path_glob: non_empty(record.get(2)),
+            entity_id: non_empty(record.get(3)),
+            operation: non_empty(record.get(4)),
+            source_cidr: non_empty(record.get(5)),
+            max_lookups_per_hour: non_empty(record.get(6)).and_then(|s| s.parse().ok()),
+        });
+    }
+
+    Ok(indicators)
+}
+
+/// Loads and compiles an indicators file, dispatching on extension: `.json`
+/// is parsed as a JSON array of [`Indicator`], anything else as CSV.
+pub fn load_indicators(path: &str) -> Result<Vec<CompiledIndicator>> {
+    let indicators = if path.ends_with(".json") {
+        load_json(path)?
+    } else {
+        load_csv(path)?
+    };
+
+    indicators.into_iter().map(CompiledIndicator::compile).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_match_handles_prefix_suffix_and_middle_wildcards() {
+        assert!(glob_match("secret/data/*", "secret/data/myapp/config"));
+        assert!(glob_match("*/config", "secret/data/myapp/config"));
+        assert!(glob_match("secret/*/config", "secret/data/myapp/config"));
+        assert!(!glob_match("secret/data/*", "kv/data/myapp/config"));
+    }
+
+    #[test]
+    fn cidr_containment_respects_prefix_length() {
+        let (network, prefix_len) = parse_cidr("10.0.0.0/24").unwrap();
+        assert!(ip_in_cidr("10.0.0.42".parse().unwrap(), network, prefix_len));
+        assert!(!ip_in_cidr("10.0.1.1".parse().unwrap(), network, prefix_len));
+    }
+
+    #[test]
+    fn cidr_parse_rejects_out_of_range_prefix() {
+        assert!(parse_cidr("10.0.0.0/33").is_err());
+    }
+}
@@ -0,0 +1,10 @@
+//! Audit log data model and shared processing engine.
+
+pub mod collectors;
+pub mod engine;
+pub mod findings;
+pub mod index;
+pub mod indicators;
+pub mod parallel;
+pub mod pipeline;
+pub mod types;
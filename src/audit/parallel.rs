@@ -0,0 +1,157 @@
+//! Memory-mapped, newline-aligned parallel scanning of a single audit log.
+//!
+//! [`crate::utils::processor`] and [`crate::utils::parallel`] already
+//! parallelize across a *list* of files, which is where most of a run's
+//! files-at-once workload lives. Neither splits a single large file across
+//! threads, so one multi-gigabyte log still parses on one core. [`scan`]
+//! fills that gap: it `mmap`s the file once, splits it into byte ranges
+//! realigned to the next `\n` so no JSON line straddles a chunk boundary,
+//! fans the chunks out across rayon's thread pool, and reduces the
+//! per-chunk accumulators with a caller-supplied merge function - the same
+//! map/reduce shape [`crate::utils::processor::ProcessorBuilder`] uses
+//! across files, one level down.
+//!
+//! Because chunk boundaries always land on a line start, the set of lines
+//! each chunk sees - and therefore the merged result - doesn't depend on how
+//! many chunks rayon happened to use, so output is stable regardless of
+//! thread count.
+
+use crate::audit::types::AuditEntry;
+use anyhow::{Context, Result};
+use memmap2::Mmap;
+use rayon::prelude::*;
+use std::fs::File;
+
+/// Split `len` bytes into `target_chunks` roughly-even ranges, each realigned
+/// so it ends right after a `\n` (or at `len`, for the last chunk). Returns
+/// half-open `start..end` byte ranges that partition `data` with no line
+/// split across a boundary.
+fn chunk_boundaries(data: &[u8], target_chunks: usize) -> Vec<(usize, usize)> {
+    let len = data.len();
+    if len == 0 {
+        return Vec::new();
+    }
+
+    let target_chunks = target_chunks.max(1);
+    let approx_size = (len + target_chunks - 1) / target_chunks;
+
+    let mut bounds = Vec::with_capacity(target_chunks);
+    let mut start = 0;
+    while start < len {
+        let mut end = (start + approx_size).min(len);
+        if end < len {
+            match data[end..].iter().position(|&b| b == b'\n') {
+                Some(offset) => end += offset + 1,
+                None => end = len,
+            }
+        }
+        bounds.push((start, end));
+        start = end;
+    }
+
+    bounds
+}
+
+/// Memory-map `path`, fan its lines out across rayon's thread pool in
+/// newline-aligned chunks, and fold each parsed [`AuditEntry`] into a
+/// per-chunk accumulator via `map_fn`. Per-chunk accumulators are combined
+/// with `reduce_fn` into the final result. Lines that fail to parse as an
+/// `AuditEntry` are skipped, matching the serial streaming readers
+/// elsewhere in the crate.
+///
+/// `initial` seeds both the per-chunk accumulators and the reduction's
+/// identity value, so `T` should behave like a monoid under `reduce_fn`
+/// (e.g. summed counters, unioned sets) for the result to be independent of
+/// how many chunks rayon happened to use.
+pub fn scan<T, M, R>(path: &str, initial: T, map_fn: M, reduce_fn: R) -> Result<T>
+where
+    T: Clone + Send,
+    M: Fn(&AuditEntry, &mut T) + Sync,
+    R: Fn(T, T) -> T + Sync,
+{
+    let file = File::open(path).with_context(|| format!("Failed to open file: {}", path))?;
+    let mmap =
+        unsafe { Mmap::map(&file) }.with_context(|| format!("Failed to mmap file: {}", path))?;
+
+    let chunks = chunk_boundaries(&mmap, rayon::current_num_threads());
+
+    let result = chunks
+        .into_par_iter()
+        .map(|(start, end)| {
+            let mut state = initial.clone();
+            for line in mmap[start..end].split(|&b| b == b'\n') {
+                if line.is_empty() {
+                    continue;
+                }
+                if let Ok(entry) = serde_json::from_slice::<AuditEntry>(line) {
+                    map_fn(&entry, &mut state);
+                }
+            }
+            state
+        })
+        .reduce(|| initial.clone(), reduce_fn);
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn sample_line(entity_id: &str) -> String {
+        format!(
+            r#"{{"type":"response","time":"2025-10-07T10:00:00Z","auth":{{"entity_id":"{}"}}}}"#,
+            entity_id
+        )
+    }
+
+    #[test]
+    fn chunk_boundaries_never_split_a_line() {
+        let data = b"aaa\nbb\nccccc\nd\n".to_vec();
+        for target in 1..=8 {
+            let bounds = chunk_boundaries(&data, target);
+            // Every boundary (except possibly the very end) falls right after a '\n'.
+            for &(_, end) in &bounds {
+                assert!(end == data.len() || data[end - 1] == b'\n');
+            }
+            // Bounds partition the whole buffer with no gaps or overlaps.
+            assert_eq!(bounds.first().map(|b| b.0), Some(0));
+            assert_eq!(bounds.last().map(|b| b.1), Some(data.len()));
+            for pair in bounds.windows(2) {
+                assert_eq!(pair[0].1, pair[1].0);
+            }
+        }
+    }
+
+    #[test]
+    fn scan_counts_entries_regardless_of_chunk_count() {
+        let mut file = NamedTempFile::new().unwrap();
+        for i in 0..50 {
+            writeln!(file, "{}", sample_line(&format!("entity-{}", i % 5))).unwrap();
+        }
+        let path = file.path().to_str().unwrap().to_string();
+
+        let (count, distinct) = scan(
+            &path,
+            (0usize, std::collections::HashSet::<String>::new()),
+            |entry, (count, entities)| {
+                *count += 1;
+                if let Some(auth) = &entry.auth {
+                    if let Some(id) = &auth.entity_id {
+                        entities.insert(id.clone());
+                    }
+                }
+            },
+            |(count_a, mut entities_a), (count_b, entities_b)| {
+                entities_a.extend(entities_b);
+                (count_a + count_b, entities_a)
+            },
+        )
+        .unwrap();
+
+        assert_eq!(count, 50);
+        assert_eq!(distinct.len(), 5);
+    }
+}
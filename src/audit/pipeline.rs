@@ -0,0 +1,273 @@
+//! Declarative field-transform/filter pipeline applied to parsed audit
+//! records before they reach an analyzer.
+//!
+//! [`crate::commands::k8s_auth`] and similar commands each bake in their own
+//! one-off field normalization directly in their parsing loop (pulling a
+//! service account name out of an auth path, for example). A [`Pipeline`]
+//! moves that kind of dataset-specific massaging into a small,
+//! user-authored JSON file - the same "declarative rule evaluated against
+//! audit data" shape as [`crate::audit::indicators::Indicator`] - so a new
+//! quirk can be handled with a config change instead of a new `match` arm.
+//!
+//! A pipeline re-serializes the parsed [`AuditEntry`] to a JSON
+//! [`serde_json::Value`] tree, addressed by dotted field paths (e.g.
+//! `auth.display_name`), and runs each [`Processor`] over it in order:
+//!
+//! - `rename`: move a field to a new path
+//! - `drop`: remove a field
+//! - `extract`: regex-capture group 1 of an existing field into a new field
+//! - `map`: substitute a field's value via a lookup table
+//! - `filter`: drop the whole record when a field (in)equals a value
+//!
+//! [`Pipeline::apply`] returns `None` once a `filter` processor drops the
+//! record, and the caller re-deserializes the surviving [`serde_json::Value`]
+//! back into an [`AuditEntry`] before handing it to its analyzer.
+//!
+//! # Pipeline file
+//!
+//! A JSON array of processor objects, each tagged by `"op"`:
+//!
+//! ```json
+//! [
+//!   {"op": "extract", "field": "request.path", "pattern": "^auth/kubernetes/login/(?P<appcode>[^/]+)$", "into": "request.appcode"},
+//!   {"op": "map", "field": "request.appcode", "table": {"svc-a": "team-a", "svc-b": "team-b"}},
+//!   {"op": "filter", "field": "auth.entity_id", "cmp": "ne", "value": "root"}
+//! ]
+//! ```
+
+use crate::audit::types::AuditEntry;
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufRead;
+
+/// Comparison used by a `filter` processor.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum FilterCmp {
+    Eq,
+    Ne,
+}
+
+/// One pipeline step, as deserialized from the `--pipeline` JSON file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum RawProcessor {
+    Rename {
+        from: String,
+        to: String,
+    },
+    Drop {
+        field: String,
+    },
+    Extract {
+        field: String,
+        pattern: String,
+        into: String,
+    },
+    Map {
+        field: String,
+        #[serde(default)]
+        table: HashMap<String, String>,
+        #[serde(default)]
+        default: Option<String>,
+    },
+    Filter {
+        field: String,
+        cmp: FilterCmp,
+        value: String,
+    },
+}
+
+/// A [`RawProcessor`] with its pattern compiled once at load time, rather
+/// than re-parsed on every record - the same "compile once, match many"
+/// shape [`crate::audit::indicators`] uses for its own predicates.
+enum Processor {
+    Rename { from: String, to: String },
+    Drop { field: String },
+    Extract { field: String, regex: Regex, into: String },
+    Map { field: String, table: HashMap<String, String>, default: Option<String> },
+    Filter { field: String, cmp: FilterCmp, value: String },
+}
+
+impl Processor {
+    fn compile(raw: RawProcessor) -> Result<Self> {
+        Ok(match raw {
+            RawProcessor::Rename { from, to } => Self::Rename { from, to },
+            RawProcessor::Drop { field } => Self::Drop { field },
+            RawProcessor::Extract { field, pattern, into } => Self::Extract {
+                regex: Regex::new(&pattern)
+                    .with_context(|| format!("invalid extract pattern '{}'", pattern))?,
+                field,
+                into,
+            },
+            RawProcessor::Map { field, table, default } => Self::Map { field, table, default },
+            RawProcessor::Filter { field, cmp, value } => Self::Filter { field, cmp, value },
+        })
+    }
+
+    /// Applies this step to `record` in place. Returns `false` only for a
+    /// `filter` step whose predicate says the whole record should be
+    /// dropped; every other step always returns `true`, silently leaving
+    /// `record` unchanged when its target field isn't present.
+    fn apply(&self, record: &mut serde_json::Value) -> bool {
+        match self {
+            Self::Rename { from, to } => {
+                if let Some(value) = remove_path(record, from) {
+                    set_path(record, to, value);
+                }
+                true
+            }
+            Self::Drop { field } => {
+                remove_path(record, field);
+                true
+            }
+            Self::Extract { field, regex, into } => {
+                if let Some(captured) = get_path(record, field)
+                    .and_then(|v| v.as_str())
+                    .and_then(|text| regex.captures(text))
+                    .and_then(|captures| captures.get(1).or_else(|| captures.get(0)))
+                    .map(|m| m.as_str().to_string())
+                {
+                    set_path(record, into, serde_json::Value::String(captured));
+                }
+                true
+            }
+            Self::Map { field, table, default } => {
+                if let Some(current) = get_path(record, field).and_then(|v| v.as_str()) {
+                    if let Some(mapped) = table.get(current).or(default.as_ref()) {
+                        set_path(record, field, serde_json::Value::String(mapped.clone()));
+                    }
+                }
+                true
+            }
+            Self::Filter { field, cmp, value } => {
+                let matches = match get_path(record, field) {
+                    Some(serde_json::Value::String(s)) => s == value,
+                    Some(other) => other.to_string().trim_matches('"') == value,
+                    None => false,
+                };
+                // `true` keeps the record: `eq` drops it when the field
+                // equals `value`, `ne` drops it when the field differs.
+                match cmp {
+                    FilterCmp::Eq => !matches,
+                    FilterCmp::Ne => matches,
+                }
+            }
+        }
+    }
+}
+
+/// Looks up a dotted field path (e.g. `auth.entity_id`) in a JSON object tree.
+fn get_path<'a>(value: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    let mut current = value;
+    for segment in path.split('.') {
+        current = current.as_object()?.get(segment)?;
+    }
+    Some(current)
+}
+
+/// Sets a dotted field path, creating intermediate objects as needed.
+fn set_path(value: &mut serde_json::Value, path: &str, new_value: serde_json::Value) {
+    let mut segments: Vec<&str> = path.split('.').collect();
+    let Some(last) = segments.pop() else { return };
+
+    let mut current = value;
+    for segment in segments {
+        if !current.is_object() {
+            *current = serde_json::Value::Object(serde_json::Map::new());
+        }
+        current = current
+            .as_object_mut()
+            .expect("just normalized to an object")
+            .entry(segment.to_string())
+            .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+    }
+
+    if !current.is_object() {
+        *current = serde_json::Value::Object(serde_json::Map::new());
+    }
+    current
+        .as_object_mut()
+        .expect("just normalized to an object")
+        .insert(last.to_string(), new_value);
+}
+
+/// Removes and returns a dotted field path, if present.
+fn remove_path(value: &mut serde_json::Value, path: &str) -> Option<serde_json::Value> {
+    let mut segments: Vec<&str> = path.split('.').collect();
+    let last = segments.pop()?;
+    let mut current = value;
+    for segment in segments {
+        current = current.as_object_mut()?.get_mut(segment)?;
+    }
+    current.as_object_mut()?.remove(last)
+}
+
+/// An ordered list of [`Processor`]s loaded from a `--pipeline` JSON file.
+pub struct Pipeline {
+    processors: Vec<Processor>,
+}
+
+impl Pipeline {
+    /// Loads and compiles a pipeline from a JSON file of tagged processor
+    /// objects (see the module docs for the file shape).
+    pub fn load(path: &str) -> Result<Self> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open pipeline file: {}", path))?;
+        let raw: Vec<RawProcessor> =
+            serde_json::from_reader(file).context("Failed to parse pipeline JSON")?;
+        let processors = raw.into_iter().map(Processor::compile).collect::<Result<_>>()?;
+        Ok(Self { processors })
+    }
+
+    /// Runs every processor over `entry` in order, returning the
+    /// transformed record as a JSON value, or `None` once a `filter`
+    /// processor drops it. The record is re-serialized from `entry` rather
+    /// than mutated in place, so a pipeline can freely rename or drop
+    /// fields before the caller re-deserializes the result back into an
+    /// [`AuditEntry`].
+    pub fn apply(&self, entry: &AuditEntry) -> Option<serde_json::Value> {
+        let mut record = serde_json::to_value(entry).ok()?;
+        for processor in &self.processors {
+            if !processor.apply(&mut record) {
+                return None;
+            }
+        }
+        Some(record)
+    }
+}
+
+/// Prints the first `limit` parsed records from `log_files` after running
+/// them through `pipeline`, for `--pipeline-dry-run` - a record a `filter`
+/// step drops is printed as `DROPPED` rather than silently omitted, so the
+/// user can see exactly which record a rule removed.
+pub fn dry_run(pipeline: &Pipeline, log_files: &[String], limit: usize) -> Result<()> {
+    let mut shown = 0usize;
+    for file_path in log_files {
+        let file = crate::utils::reader::open_file(file_path)
+            .with_context(|| format!("Failed to open file: {}", file_path))?;
+        for line in std::io::BufReader::new(file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Ok(entry) = serde_json::from_str::<AuditEntry>(&line) else {
+                continue;
+            };
+
+            match pipeline.apply(&entry) {
+                Some(record) => println!("{}", serde_json::to_string_pretty(&record)?),
+                None => println!("DROPPED"),
+            }
+
+            shown += 1;
+            if shown >= limit {
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
@@ -72,6 +72,9 @@ pub struct RequestInfo {
     pub remote_port: Option<u16>,
     pub client_token: Option<String>,
     pub client_token_accessor: Option<String>,
+    /// Request parameters (HMAC-hashed by Vault's audit device for
+    /// sensitive values, but still useful for exact-match comparisons)
+    pub data: Option<HashMap<String, serde_json::Value>>,
 }
 
 /// Response information from the audit log.
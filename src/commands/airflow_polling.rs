@@ -26,6 +26,11 @@
 //! - Regular time intervals
 //! - Repeated access to same paths
 //!
+//! Regularity itself is detected two ways: the coefficient of variation (CV)
+//! of inter-arrival deltas (low CV = evenly spaced cron-like polling, high CV
+//! = bursty/irregular access), and binned autocorrelation, which finds the
+//! lag with the strongest repeating count pattern. See [`detect_periodicity`].
+//!
 //! # Output
 //!
 //! Displays entities with polling patterns:
@@ -42,9 +47,11 @@
 
 use crate::audit::types::AuditEntry;
 use crate::utils::progress::ProgressBar;
+use crate::utils::report::{self, OutputFormat, Report};
 use crate::utils::time::parse_timestamp;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
+use serde::Serialize;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
@@ -61,6 +68,78 @@ pub fn format_number(n: usize) -> String {
     result.chars().rev().collect()
 }
 
+/// Configuration for what counts as "excessive polling", generalizing the
+/// detector beyond a single hardcoded Airflow substring match so it can flag
+/// any secrets engine being hammered by a chatty client.
+pub struct PollingDetectorConfig {
+    /// `*`-wildcard glob patterns (case-insensitive) a path must match at
+    /// least one of to be considered. Defaults to the Airflow-era
+    /// `database/config/*` and `database/creds/*` patterns plus anything
+    /// containing "airflow".
+    pub path_patterns: Vec<String>,
+    /// Only report paths/entities with at least this many operations.
+    pub threshold: usize,
+    /// When set, drop paths whose detected polling period is slower than
+    /// this cadence (in seconds) - i.e. keep only pollers at least this
+    /// frequent.
+    pub min_interval_seconds: Option<f64>,
+}
+
+impl Default for PollingDetectorConfig {
+    fn default() -> Self {
+        Self {
+            path_patterns: vec![
+                "database/config/*".to_string(),
+                "database/creds/*".to_string(),
+                "*airflow*".to_string(),
+            ],
+            threshold: 50,
+            min_interval_seconds: None,
+        }
+    }
+}
+
+/// Simple `*`-wildcard glob match (no other metacharacters) - the same
+/// level of pattern matching `kv_mounts`'s `PathPattern::Glob` and
+/// `entity_churn`'s `SignatureRule` predicates use, rather than pulling in a
+/// full regex engine for the common case. Matching is done case-insensitively
+/// by the caller lowercasing both sides.
+fn glob_match(pattern: &str, value: &str) -> bool {
+    let segments: Vec<&str> = pattern.split('*').collect();
+    if segments.len() == 1 {
+        return pattern == value;
+    }
+
+    let mut rest = value;
+    for (idx, segment) in segments.iter().enumerate() {
+        if segment.is_empty() {
+            continue;
+        }
+        if idx == 0 {
+            let Some(stripped) = rest.strip_prefix(segment) else {
+                return false;
+            };
+            rest = stripped;
+        } else if idx == segments.len() - 1 {
+            return rest.ends_with(segment);
+        } else {
+            let Some(found) = rest.find(segment) else {
+                return false;
+            };
+            rest = &rest[found + segment.len()..];
+        }
+    }
+    true
+}
+
+/// Whether `path` matches any of `patterns` (case-insensitive).
+fn path_matches(path: &str, patterns: &[String]) -> bool {
+    let path_lower = path.to_lowercase();
+    patterns
+        .iter()
+        .any(|pattern| glob_match(&pattern.to_lowercase(), &path_lower))
+}
+
 struct PathData {
     operations: usize,
     entities: std::collections::HashSet<String>,
@@ -79,7 +158,339 @@ impl PathData {
     }
 }
 
-pub fn run(log_files: &[String], output: Option<&str>) -> Result<()> {
+/// Bucket width used to build the count series for autocorrelation.
+const PERIODICITY_BUCKET_SECS: i64 = 60;
+
+/// CV below this threshold indicates evenly-spaced, cron-like polling rather
+/// than bursty/irregular access.
+const SCHEDULED_CV_THRESHOLD: f64 = 0.25;
+
+/// Periodicity signal derived from a path's access timestamps.
+struct Periodicity {
+    /// Mean of consecutive inter-arrival deltas, in seconds.
+    mean_interval_seconds: f64,
+    /// Coefficient of variation (stddev / mean) of those deltas.
+    cv: f64,
+    /// Dominant period detected via autocorrelation of a binned count
+    /// series, in seconds. Falls back to `mean_interval_seconds` when the
+    /// time span is too short to bin meaningfully.
+    detected_period_seconds: f64,
+}
+
+/// Computes the [`Periodicity`] of a path's access timestamps: the mean and
+/// coefficient of variation of inter-arrival deltas, plus a dominant period
+/// from autocorrelation of a fixed-bin count series. A low CV (see
+/// [`SCHEDULED_CV_THRESHOLD`]) flags genuine cron-like polling; a high CV
+/// flags bursty/irregular access.
+fn detect_periodicity(timestamps: &[DateTime<Utc>]) -> Option<Periodicity> {
+    if timestamps.len() < 3 {
+        return None;
+    }
+
+    let mut sorted = timestamps.to_vec();
+    sorted.sort();
+
+    let deltas: Vec<f64> = sorted
+        .windows(2)
+        .map(|w| w[1].signed_duration_since(w[0]).num_milliseconds() as f64 / 1000.0)
+        .collect();
+
+    let mean_interval_seconds = deltas.iter().sum::<f64>() / deltas.len() as f64;
+    if mean_interval_seconds <= 0.0 {
+        return None;
+    }
+
+    let variance = deltas
+        .iter()
+        .map(|d| (d - mean_interval_seconds).powi(2))
+        .sum::<f64>()
+        / deltas.len() as f64;
+    let cv = variance.sqrt() / mean_interval_seconds;
+
+    let detected_period_seconds =
+        autocorrelation_period(&sorted).unwrap_or(mean_interval_seconds);
+
+    Some(Periodicity {
+        mean_interval_seconds,
+        cv,
+        detected_period_seconds,
+    })
+}
+
+/// Buckets `sorted_timestamps` into fixed [`PERIODICITY_BUCKET_SECS`]-second
+/// bins to build an evenly-sampled count series, then finds the lag with the
+/// strongest autocorrelation peak and reports it as the dominant period, in
+/// seconds. Returns `None` when the time span is too short to bin
+/// meaningfully or the series has no variance to correlate against.
+fn autocorrelation_period(sorted_timestamps: &[DateTime<Utc>]) -> Option<f64> {
+    let first = *sorted_timestamps.first()?;
+    let last = *sorted_timestamps.last()?;
+    let span_secs = last.signed_duration_since(first).num_seconds();
+    if span_secs < PERIODICITY_BUCKET_SECS * 4 {
+        return None;
+    }
+
+    let bucket_count = (span_secs / PERIODICITY_BUCKET_SECS) as usize + 1;
+    let mut series = vec![0u32; bucket_count];
+    for ts in sorted_timestamps {
+        let offset = ts.signed_duration_since(first).num_seconds();
+        let bucket = (offset / PERIODICITY_BUCKET_SECS) as usize;
+        if let Some(count) = series.get_mut(bucket) {
+            *count += 1;
+        }
+    }
+
+    let n = series.len();
+    let mean = series.iter().sum::<u32>() as f64 / n as f64;
+    let centered: Vec<f64> = series.iter().map(|&count| count as f64 - mean).collect();
+    let variance: f64 = centered.iter().map(|c| c * c).sum();
+    if variance <= 0.0 {
+        return None;
+    }
+
+    // Cap the search at half the series (standard autocorrelation limit) and
+    // at a day's worth of buckets, so a long multi-day file doesn't force an
+    // O(n^2) scan over every possible lag.
+    let max_lag = (n / 2).min(1440);
+    let mut best_lag = 0usize;
+    let mut best_corr = 0.0f64;
+    for lag in 1..max_lag {
+        let corr: f64 = (0..n - lag).map(|i| centered[i] * centered[i + lag]).sum();
+        if corr > best_corr {
+            best_corr = corr;
+            best_lag = lag;
+        }
+    }
+
+    if best_lag == 0 {
+        return None;
+    }
+
+    Some((best_lag as i64 * PERIODICITY_BUCKET_SECS) as f64)
+}
+
+/// One row of the "top paths by operations" section.
+#[derive(Debug, Clone, Serialize)]
+struct TopPathRow {
+    path: String,
+    operations: usize,
+    entities: usize,
+}
+
+/// One row of the "entities accessing" section.
+#[derive(Debug, Clone, Serialize)]
+struct EntityPatternRow {
+    entity_id: String,
+    operations: usize,
+    unique_paths: usize,
+}
+
+/// One row of the burst-rate/periodicity analysis - the richest per-path
+/// record, and the `ndjson` row type.
+#[derive(Debug, Clone, Serialize)]
+struct PollingPatternRow {
+    path: String,
+    operations: usize,
+    time_span_hours: f64,
+    ops_per_hour: f64,
+    avg_interval_seconds: f64,
+    cv: f64,
+    detected_period_seconds: f64,
+    /// "Scheduled" when `cv < SCHEDULED_CV_THRESHOLD`, else "Burst".
+    pattern: String,
+}
+
+/// One row of the entity-path combination breakdown.
+#[derive(Debug, Clone, Serialize)]
+struct EntityPathComboRow {
+    entity: String,
+    path: String,
+    operations: usize,
+}
+
+/// Full polling analysis: summary counts plus every section's full (i.e.
+/// not top-N-truncated) result set, so `--format json`/`ndjson` feeds
+/// downstream trend-aggregation tooling without re-parsing a table capped
+/// for terminal display.
+#[derive(Debug, Clone, Serialize)]
+struct AirflowPollingReport {
+    total_lines: usize,
+    airflow_operations: usize,
+    unique_paths: usize,
+    entities_involved: usize,
+    top_paths: Vec<TopPathRow>,
+    entity_patterns: Vec<EntityPatternRow>,
+    polling_patterns: Vec<PollingPatternRow>,
+    entity_path_combos: Vec<EntityPathComboRow>,
+}
+
+impl Report for AirflowPollingReport {
+    type Row = PollingPatternRow;
+
+    fn command_name(&self) -> &'static str {
+        "airflow-polling"
+    }
+
+    fn render_table(&self, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+        writeln!(w, "\nSummary:")?;
+        writeln!(w, "  Total lines processed: {}", format_number(self.total_lines))?;
+        writeln!(w, "  Airflow operations: {}", format_number(self.airflow_operations))?;
+        writeln!(w, "  Unique paths: {}", format_number(self.unique_paths))?;
+        writeln!(w, "  Entities involved: {}", format_number(self.entities_involved))?;
+
+        writeln!(w, "\n1. TOP AIRFLOW PATHS BY OPERATIONS")?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        writeln!(w, "{:<80} {:<12} {:<10}", "Path", "Operations", "Entities")?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        for row in self.top_paths.iter().take(30) {
+            let display_path = if row.path.len() <= 78 { row.path.as_str() } else { &row.path[..75] };
+            writeln!(
+                w,
+                "{:<80} {:<12} {:<10}",
+                display_path,
+                format_number(row.operations),
+                format_number(row.entities)
+            )?;
+        }
+
+        writeln!(w, "\n2. ENTITIES ACCESSING AIRFLOW SECRETS")?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        writeln!(w, "{:<50} {:<12} {:<15}", "Entity ID", "Operations", "Unique Paths")?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        for row in self.entity_patterns.iter().take(20) {
+            let display_entity =
+                if row.entity_id.len() <= 48 { row.entity_id.as_str() } else { &row.entity_id[..45] };
+            writeln!(
+                w,
+                "{:<50} {:<12} {:<15}",
+                display_entity,
+                format_number(row.operations),
+                format_number(row.unique_paths)
+            )?;
+        }
+
+        writeln!(w, "\n3. BURST RATE ANALYSIS (Paths with Time Data)")?;
+        writeln!(w, "   NOTE: Rates calculated over actual time span - high rates indicate bursty access")?;
+        writeln!(w, "   NOTE: Pattern is \"Scheduled\" when the coefficient of variation (CV) of inter-arrival")?;
+        writeln!(w, "         deltas is below {SCHEDULED_CV_THRESHOLD:.2} (evenly spaced); otherwise \"Burst\"")?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        writeln!(
+            w,
+            "{:<45} {:<10} {:<10} {:<12} {:<14} {:<9}",
+            "Path", "Operations", "Time Span", "Avg Interval", "Detected Period", "Pattern"
+        )?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        for row in self.polling_patterns.iter().take(25) {
+            let path_display = if row.path.len() <= 43 { &row.path } else { &row.path[..40] };
+            let time_span = format!("{:.1}h", row.time_span_hours);
+            let interval = format!("{:.1}s", row.avg_interval_seconds);
+            let detected_period = format!("{:.1}s", row.detected_period_seconds);
+            writeln!(
+                w,
+                "{:<45} {:<10} {:<10} {:<12} {:<14} {:<9}",
+                path_display,
+                format_number(row.operations),
+                time_span,
+                interval,
+                detected_period,
+                row.pattern
+            )?;
+        }
+
+        writeln!(w, "\n4. ENTITY-PATH POLLING BEHAVIOR (Top 30)")?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        writeln!(w, "{:<40} {:<45} {:<15}", "Entity", "Path", "Operations")?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        for row in self.entity_path_combos.iter().take(30) {
+            let entity_display = if row.entity.len() <= 38 { &row.entity } else { &row.entity[..35] };
+            let path_display = if row.path.len() <= 43 { &row.path } else { &row.path[..40] };
+            writeln!(
+                w,
+                "{:<40} {:<45} {:<15}",
+                entity_display,
+                path_display,
+                format_number(row.operations)
+            )?;
+        }
+
+        writeln!(w, "\n5. OPTIMIZATION RECOMMENDATIONS")?;
+        writeln!(w, "{}", "-".repeat(100))?;
+
+        let high_frequency_paths: Vec<_> =
+            self.polling_patterns.iter().filter(|p| p.ops_per_hour > 100.0).collect();
+        let total_high_freq_ops: usize = high_frequency_paths.iter().map(|p| p.operations).sum();
+
+        writeln!(w, "Total Airflow operations: {}", format_number(self.airflow_operations))?;
+        writeln!(
+            w,
+            "Paths with >100 ops/hour burst rate: {}",
+            format_number(high_frequency_paths.len())
+        )?;
+        writeln!(
+            w,
+            "Operations from high-frequency paths: {} ({:.1}%)",
+            format_number(total_high_freq_ops),
+            (total_high_freq_ops as f64 / self.airflow_operations as f64) * 100.0
+        )?;
+        writeln!(w)?;
+        writeln!(w, "Recommended Actions:")?;
+        writeln!(w)?;
+        writeln!(w, "1. IMPLEMENT AIRFLOW CONNECTION CACHING")?;
+        writeln!(w, "   - Configure Airflow to cache connection objects")?;
+        writeln!(w, "   - Expected reduction: 80-90% of reads")?;
+        writeln!(
+            w,
+            "   - Potential savings: {} operations/day",
+            format_number((self.airflow_operations as f64 * 0.85) as usize)
+        )?;
+        writeln!(w)?;
+        writeln!(w, "2. DEPLOY VAULT AGENT WITH AIRFLOW")?;
+        writeln!(w, "   - Run Vault agent as sidecar/daemon")?;
+        writeln!(w, "   - Configure template rendering for connections")?;
+        writeln!(w, "   - Expected reduction: 95% of reads")?;
+        writeln!(
+            w,
+            "   - Potential savings: {} operations/day",
+            format_number((self.airflow_operations as f64 * 0.95) as usize)
+        )?;
+        writeln!(w)?;
+        writeln!(w, "3. USE AIRFLOW SECRETS BACKEND EFFICIENTLY")?;
+        writeln!(w, "   - Review connection lookup patterns in DAGs")?;
+        writeln!(w, "   - Implement connection object reuse within tasks")?;
+        writeln!(w, "   - Cache connections at DAG level where appropriate")?;
+        writeln!(w)?;
+
+        if !self.polling_patterns.is_empty() {
+            writeln!(w, "4. PRIORITY PATHS FOR IMMEDIATE OPTIMIZATION (by burst rate):")?;
+            for (i, pattern) in self.polling_patterns.iter().take(10).enumerate() {
+                let path_name = pattern.path.split('/').next_back().unwrap_or(&pattern.path);
+                writeln!(
+                    w,
+                    "   {}. {}: {} operations ({:.0}/hour burst rate)",
+                    i + 1,
+                    path_name,
+                    format_number(pattern.operations),
+                    pattern.ops_per_hour
+                )?;
+            }
+        }
+
+        writeln!(w, "\n{}", "=".repeat(100))?;
+        Ok(())
+    }
+
+    fn rows(&self) -> &[PollingPatternRow] {
+        &self.polling_patterns
+    }
+}
+
+pub fn run(
+    log_files: &[String],
+    output: Option<&str>,
+    config: &PollingDetectorConfig,
+    format: &str,
+) -> Result<()> {
+    let format = OutputFormat::parse(format).map_err(anyhow::Error::msg)?;
     let mut airflow_operations = 0;
     let mut airflow_paths: HashMap<String, PathData> = HashMap::new();
     let mut total_lines = 0;
@@ -138,7 +549,7 @@ pub fn run(log_files: &[String], output: Option<&str>) -> Result<()> {
                 None => continue,
             };
 
-            if path.to_lowercase().contains("airflow") {
+            if path_matches(path, &config.path_patterns) {
                 airflow_operations += 1;
 
                 let entity_id = entry
@@ -182,60 +593,29 @@ pub fn run(log_files: &[String], output: Option<&str>) -> Result<()> {
         format_number(airflow_operations)
     );
 
-    println!("\nSummary:");
-    println!("  Total lines processed: {}", format_number(total_lines));
-    println!(
-        "  Airflow operations: {}",
-        format_number(airflow_operations)
-    );
-    println!("  Unique paths: {}", format_number(airflow_paths.len()));
-
     let total_entities: std::collections::HashSet<_> = airflow_paths
         .values()
         .flat_map(|data| data.entities.iter().cloned())
         .collect();
-    println!(
-        "  Entities involved: {}",
-        format_number(total_entities.len())
-    );
 
     // 1. Top Airflow paths by operations
-    println!("\n1. TOP AIRFLOW PATHS BY OPERATIONS");
-    println!("{}", "-".repeat(100));
-    println!("{:<80} {:<12} {:<10}", "Path", "Operations", "Entities");
-    println!("{}", "-".repeat(100));
-
     let mut sorted_paths: Vec<_> = airflow_paths.iter().collect();
     sorted_paths.sort_by(|a, b| b.1.operations.cmp(&a.1.operations));
-
-    for (path, data) in sorted_paths.iter().take(30) {
-        let display_path = if path.len() <= 78 {
-            path.as_str()
-        } else {
-            &path[..75]
-        };
-        println!(
-            "{:<80} {:<12} {:<10}",
-            display_path,
-            format_number(data.operations),
-            format_number(data.entities.len())
-        );
-    }
+    let top_paths: Vec<TopPathRow> = sorted_paths
+        .iter()
+        .map(|(path, data)| TopPathRow {
+            path: (*path).clone(),
+            operations: data.operations,
+            entities: data.entities.len(),
+        })
+        .collect();
 
     // 2. Entity access patterns
-    println!("\n2. ENTITIES ACCESSING AIRFLOW SECRETS");
-    println!("{}", "-".repeat(100));
-    println!(
-        "{:<50} {:<12} {:<15}",
-        "Entity ID", "Operations", "Unique Paths"
-    );
-    println!("{}", "-".repeat(100));
-
-    let mut entity_patterns: HashMap<String, (usize, std::collections::HashSet<String>)> =
+    let mut entity_patterns_map: HashMap<String, (usize, std::collections::HashSet<String>)> =
         HashMap::new();
     for (path, data) in &airflow_paths {
         for entity in &data.entities {
-            let entry = entity_patterns
+            let entry = entity_patterns_map
                 .entry(entity.clone())
                 .or_insert((0, std::collections::HashSet::new()));
             entry.0 += data.operations_by_entity.get(entity).unwrap_or(&0);
@@ -243,45 +623,25 @@ pub fn run(log_files: &[String], output: Option<&str>) -> Result<()> {
         }
     }
 
-    let mut sorted_entities: Vec<_> = entity_patterns.iter().collect();
+    let mut sorted_entities: Vec<_> = entity_patterns_map
+        .iter()
+        .filter(|(_, (ops, _))| *ops >= config.threshold)
+        .collect();
     sorted_entities.sort_by(|a, b| b.1 .0.cmp(&a.1 .0));
-
-    for (entity, (ops, paths)) in sorted_entities.iter().take(20) {
-        let display_entity = if entity.len() <= 48 {
-            entity.as_str()
-        } else {
-            &entity[..45]
-        };
-        println!(
-            "{:<50} {:<12} {:<15}",
-            display_entity,
-            format_number(*ops),
-            format_number(paths.len())
-        );
-    }
+    let entity_patterns: Vec<EntityPatternRow> = sorted_entities
+        .iter()
+        .map(|(entity, (ops, paths))| EntityPatternRow {
+            entity_id: (*entity).clone(),
+            operations: *ops,
+            unique_paths: paths.len(),
+        })
+        .collect();
 
     // 3. Polling pattern analysis with BURST RATES
-    println!("\n3. BURST RATE ANALYSIS (Paths with Time Data)");
-    println!("   NOTE: Rates calculated over actual time span - high rates indicate bursty access");
-    println!("{}", "-".repeat(100));
-    println!(
-        "{:<60} {:<12} {:<12} {:<15}",
-        "Path", "Operations", "Time Span", "Avg Interval"
-    );
-    println!("{}", "-".repeat(100));
-
-    struct PollingPattern {
-        path: String,
-        operations: usize,
-        time_span_hours: f64,
-        ops_per_hour: f64,
-        avg_interval_seconds: f64,
-    }
-
-    let mut polling_patterns = Vec::new();
+    let mut polling_patterns: Vec<PollingPatternRow> = Vec::new();
 
     for (path, data) in &airflow_paths {
-        if data.timestamps.len() < 2 {
+        if data.timestamps.len() < 2 || data.operations < config.threshold {
             continue;
         }
 
@@ -296,12 +656,27 @@ pub fn run(log_files: &[String], output: Option<&str>) -> Result<()> {
             let ops_per_hour = data.operations as f64 / time_span_hours;
             let avg_interval_seconds = time_span_seconds / data.operations as f64;
 
-            polling_patterns.push(PollingPattern {
+            let periodicity = detect_periodicity(&data.timestamps);
+            let (cv, detected_period_seconds) = periodicity
+                .map(|p| (p.cv, p.detected_period_seconds))
+                .unwrap_or((f64::NAN, avg_interval_seconds));
+            let is_scheduled = cv.is_finite() && cv < SCHEDULED_CV_THRESHOLD;
+
+            if let Some(min_interval) = config.min_interval_seconds {
+                if detected_period_seconds > min_interval {
+                    continue;
+                }
+            }
+
+            polling_patterns.push(PollingPatternRow {
                 path: path.clone(),
                 operations: data.operations,
                 time_span_hours,
                 ops_per_hour,
                 avg_interval_seconds,
+                cv,
+                detected_period_seconds,
+                pattern: if is_scheduled { "Scheduled" } else { "Burst" }.to_string(),
             });
         }
     }
@@ -309,40 +684,14 @@ pub fn run(log_files: &[String], output: Option<&str>) -> Result<()> {
     // Sort by operations per hour (highest burst rate)
     polling_patterns.sort_by(|a, b| b.ops_per_hour.partial_cmp(&a.ops_per_hour).unwrap());
 
-    for pattern in polling_patterns.iter().take(25) {
-        let path_display = if pattern.path.len() <= 58 {
-            &pattern.path
-        } else {
-            &pattern.path[..55]
-        };
-        let time_span = format!("{:.1}h", pattern.time_span_hours);
-        let interval = format!("{:.1}s", pattern.avg_interval_seconds);
-
-        println!(
-            "{:<60} {:<12} {:<12} {:<15}",
-            path_display,
-            format_number(pattern.operations),
-            time_span,
-            interval
-        );
-    }
-
     // 4. Entity-path combinations
-    println!("\n4. ENTITY-PATH POLLING BEHAVIOR (Top 30)");
-    println!("{}", "-".repeat(100));
-    println!("{:<40} {:<45} {:<15}", "Entity", "Path", "Operations");
-    println!("{}", "-".repeat(100));
-
-    struct EntityPathCombo {
-        entity: String,
-        path: String,
-        operations: usize,
-    }
-
-    let mut entity_path_combos = Vec::new();
+    let mut entity_path_combos: Vec<EntityPathComboRow> = Vec::new();
     for (path, data) in &airflow_paths {
         for (entity_id, ops) in &data.operations_by_entity {
-            entity_path_combos.push(EntityPathCombo {
+            if *ops < config.threshold {
+                continue;
+            }
+            entity_path_combos.push(EntityPathComboRow {
                 entity: entity_id.clone(),
                 path: path.clone(),
                 operations: *ops,
@@ -352,90 +701,18 @@ pub fn run(log_files: &[String], output: Option<&str>) -> Result<()> {
 
     entity_path_combos.sort_by(|a, b| b.operations.cmp(&a.operations));
 
-    for combo in entity_path_combos.iter().take(30) {
-        let entity_display = if combo.entity.len() <= 38 {
-            &combo.entity
-        } else {
-            &combo.entity[..35]
-        };
-        let path_display = if combo.path.len() <= 43 {
-            &combo.path
-        } else {
-            &combo.path[..40]
-        };
-
-        println!(
-            "{:<40} {:<45} {:<15}",
-            entity_display,
-            path_display,
-            format_number(combo.operations)
-        );
-    }
-
-    // 5. Recommendations
-    println!("\n5. OPTIMIZATION RECOMMENDATIONS");
-    println!("{}", "-".repeat(100));
-
-    let high_frequency_paths: Vec<_> = polling_patterns
-        .iter()
-        .filter(|p| p.ops_per_hour > 100.0)
-        .collect();
-    let total_high_freq_ops: usize = high_frequency_paths.iter().map(|p| p.operations).sum();
-
-    println!(
-        "Total Airflow operations: {}",
-        format_number(airflow_operations)
-    );
-    println!(
-        "Paths with >100 ops/hour burst rate: {}",
-        format_number(high_frequency_paths.len())
-    );
-    println!(
-        "Operations from high-frequency paths: {} ({:.1}%)",
-        format_number(total_high_freq_ops),
-        (total_high_freq_ops as f64 / airflow_operations as f64) * 100.0
-    );
-    println!();
-    println!("Recommended Actions:");
-    println!();
-    println!("1. IMPLEMENT AIRFLOW CONNECTION CACHING");
-    println!("   - Configure Airflow to cache connection objects");
-    println!("   - Expected reduction: 80-90% of reads");
-    println!(
-        "   - Potential savings: {} operations/day",
-        format_number((airflow_operations as f64 * 0.85) as usize)
-    );
-    println!();
-    println!("2. DEPLOY VAULT AGENT WITH AIRFLOW");
-    println!("   - Run Vault agent as sidecar/daemon");
-    println!("   - Configure template rendering for connections");
-    println!("   - Expected reduction: 95% of reads");
-    println!(
-        "   - Potential savings: {} operations/day",
-        format_number((airflow_operations as f64 * 0.95) as usize)
-    );
-    println!();
-    println!("3. USE AIRFLOW SECRETS BACKEND EFFICIENTLY");
-    println!("   - Review connection lookup patterns in DAGs");
-    println!("   - Implement connection object reuse within tasks");
-    println!("   - Cache connections at DAG level where appropriate");
-    println!();
-
-    if !polling_patterns.is_empty() {
-        println!("4. PRIORITY PATHS FOR IMMEDIATE OPTIMIZATION (by burst rate):");
-        for (i, pattern) in polling_patterns.iter().take(10).enumerate() {
-            let path_name = pattern.path.split('/').next_back().unwrap_or(&pattern.path);
-            println!(
-                "   {}. {}: {} operations ({:.0}/hour burst rate)",
-                i + 1,
-                path_name,
-                format_number(pattern.operations),
-                pattern.ops_per_hour
-            );
-        }
-    }
+    let report_data = AirflowPollingReport {
+        total_lines,
+        airflow_operations,
+        unique_paths: airflow_paths.len(),
+        entities_involved: total_entities.len(),
+        top_paths,
+        entity_patterns,
+        polling_patterns,
+        entity_path_combos,
+    };
 
-    println!("\n{}", "=".repeat(100));
+    report::emit(&report_data, format)?;
 
     if let Some(output_file) = output {
         use std::fs::File;
@@ -447,7 +724,7 @@ pub fn run(log_files: &[String], output: Option<&str>) -> Result<()> {
                 writeln!(file, "{},{},{}", entity, path, count)?;
             }
         }
-        println!("\nOutput written to: {}", output_file);
+        eprintln!("\nOutput written to: {}", output_file);
     }
 
     Ok(())
@@ -0,0 +1,241 @@
+//! Flag abnormal bursts of per-entity activity with an exponentially
+//! weighted moving average (EWMA) of each entity's request rate, instead
+//! of [`crate::commands::token_lookup_abuse`]'s fixed-count heuristic.
+//!
+//! Entries are bucketed into fixed `--bucket-seconds` windows (default
+//! 60s) by their `time` field, the same bucket-key convention
+//! [`crate::commands::path_hotspots`] uses. For each entity, windows are
+//! walked in chronological order - including zero-activity windows, so an
+//! idle gap isn't silently skipped - updating a running mean/variance:
+//!
+//! ```text
+//! z     = (x - mean) / sqrt(var + epsilon)
+//! mean' = alpha * x + (1 - alpha) * mean
+//! var'  = (1 - alpha) * (var + alpha * (x - mean)^2)
+//! ```
+//!
+//! `z` is computed against the *pre-update* mean/variance (the baseline
+//! before this window was observed), then the state updates to fold the
+//! window in. `epsilon` keeps `var` from collapsing to zero during a run
+//! of identical (including all-zero) windows, which would otherwise make
+//! the very next nonzero window score an infinite z. An entity needs
+//! `--warmup` windows of history before it's scored at all, so a handful
+//! of early requests can't be flagged against an unformed baseline.
+//!
+//! # Usage
+//!
+//! ```bash
+//! vault-audit anomaly-detect logs/*.log
+//! vault-audit anomaly-detect logs/*.log --bucket-seconds 300 --threshold 4.0
+//! ```
+
+use crate::audit::types::AuditEntry;
+use crate::utils::report::{self, OutputFormat, Report};
+use crate::utils::time::{format_timestamp, parse_timestamp};
+use anyhow::Result;
+use chrono::DateTime;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+/// Keeps `var` from collapsing to zero during a run of identical (or
+/// all-zero) windows, which would otherwise make the next differing
+/// window's z-score blow up to infinity.
+const EPSILON: f64 = 1e-6;
+
+#[derive(Default)]
+struct WindowStats {
+    count: usize,
+    paths: HashSet<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct AnomalyRow {
+    entity_id: String,
+    window_start: String,
+    observed_rate: usize,
+    expected_rate: f64,
+    z_score: f64,
+    paths: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct AnomalyReport {
+    bucket_seconds: i64,
+    alpha: f64,
+    threshold: f64,
+    rows: Vec<AnomalyRow>,
+}
+
+impl Report for AnomalyReport {
+    type Row = AnomalyRow;
+
+    fn command_name(&self) -> &'static str {
+        "anomaly-detect"
+    }
+
+    fn render_table(&self, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+        writeln!(w, "\n{}", "=".repeat(110))?;
+        writeln!(
+            w,
+            "Rate Anomalies ({}s windows, alpha={}, threshold={})",
+            self.bucket_seconds, self.alpha, self.threshold
+        )?;
+        writeln!(w, "{}", "=".repeat(110))?;
+        writeln!(
+            w,
+            "{:<36} {:<22} {:>10} {:>12} {:>8}",
+            "Entity", "Window Start", "Observed", "Expected", "Z-Score"
+        )?;
+        writeln!(w, "{}", "-".repeat(110))?;
+        for row in &self.rows {
+            writeln!(
+                w,
+                "{:<36} {:<22} {:>10} {:>12.2} {:>8.2}",
+                row.entity_id, row.window_start, row.observed_rate, row.expected_rate, row.z_score
+            )?;
+            if !row.paths.is_empty() {
+                writeln!(w, "    paths: {}", row.paths.join(", "))?;
+            }
+        }
+        writeln!(w, "{}", "=".repeat(110))?;
+        Ok(())
+    }
+
+    fn rows(&self) -> &[AnomalyRow] {
+        &self.rows
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn build(
+    log_files: &[String],
+    bucket_seconds: i64,
+    alpha: f64,
+    threshold: f64,
+    warmup: usize,
+    top: usize,
+) -> Result<AnomalyReport> {
+    let mut entity_windows: HashMap<String, BTreeMap<i64, WindowStats>> = HashMap::new();
+
+    for file_path in log_files {
+        let file = File::open(file_path)?;
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Ok(entry) = serde_json::from_str::<AuditEntry>(&line) else {
+                continue;
+            };
+            let Some(entity_id) = entry.auth.as_ref().and_then(|a| a.entity_id.clone()) else {
+                continue;
+            };
+            let Ok(time) = parse_timestamp(&entry.time) else {
+                continue;
+            };
+            let path = entry.request.as_ref().and_then(|r| r.path.clone());
+
+            let bucket = time.timestamp() / bucket_seconds;
+            let window = entity_windows.entry(entity_id).or_default().entry(bucket).or_default();
+            window.count += 1;
+            if let Some(path) = path {
+                window.paths.insert(path);
+            }
+        }
+    }
+
+    let mut rows = Vec::new();
+
+    for (entity_id, windows) in entity_windows {
+        let (Some(&min_bucket), Some(&max_bucket)) = (windows.keys().next(), windows.keys().next_back())
+        else {
+            continue;
+        };
+
+        let mut mean = 0.0_f64;
+        let mut var = 0.0_f64;
+        let mut initialized = false;
+
+        for (window_idx, bucket) in (min_bucket..=max_bucket).enumerate() {
+            let observed = windows.get(&bucket).map(|w| w.count).unwrap_or(0);
+            let x = observed as f64;
+
+            if !initialized {
+                mean = x;
+                initialized = true;
+                continue;
+            }
+
+            let z_score = (x - mean) / (var + EPSILON).sqrt();
+
+            if window_idx >= warmup && z_score.abs() >= threshold {
+                let window_start = DateTime::from_timestamp(bucket * bucket_seconds, 0)
+                    .map(|dt| format_timestamp(&dt))
+                    .unwrap_or_default();
+                let mut paths: Vec<String> = windows
+                    .get(&bucket)
+                    .map(|w| w.paths.iter().cloned().collect())
+                    .unwrap_or_default();
+                paths.sort();
+
+                rows.push(AnomalyRow {
+                    entity_id: entity_id.clone(),
+                    window_start,
+                    observed_rate: observed,
+                    expected_rate: mean,
+                    z_score,
+                    paths,
+                });
+            }
+
+            let delta = x - mean;
+            var = (1.0 - alpha) * (var + alpha * delta * delta);
+            mean = alpha * x + (1.0 - alpha) * mean;
+        }
+    }
+
+    rows.sort_by(|a, b| b.z_score.abs().total_cmp(&a.z_score.abs()));
+    rows.truncate(top);
+
+    Ok(AnomalyReport {
+        bucket_seconds,
+        alpha,
+        threshold,
+        rows,
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    log_files: &[String],
+    bucket_seconds: i64,
+    alpha: f64,
+    threshold: f64,
+    warmup: usize,
+    top: usize,
+    format: &str,
+) -> Result<()> {
+    let format = OutputFormat::parse(format).map_err(anyhow::Error::msg)?;
+    let report = build(log_files, bucket_seconds, alpha, threshold, warmup, top)?;
+    report::emit(&report, format)
+}
+
+/// Same computation as [`run`], rendered to a string instead of printed -
+/// used by the golden-fixture harness in [`crate::testing`] to compare a
+/// command's output byte-for-byte against a checked-in `expected.json`.
+#[allow(clippy::too_many_arguments)]
+pub fn run_to_string(
+    log_files: &[String],
+    bucket_seconds: i64,
+    alpha: f64,
+    threshold: f64,
+    warmup: usize,
+    top: usize,
+    format: &str,
+) -> Result<String> {
+    let format = OutputFormat::parse(format).map_err(anyhow::Error::msg)?;
+    let report = build(log_files, bucket_seconds, alpha, threshold, warmup, top)?;
+    report::render_to_string(&report, format)
+}
@@ -0,0 +1,210 @@
+//! Manage the Vault audit devices whose logs this tool analyzes
+//! (`audit-devices list/enable/disable`), wrapping Vault's `/sys/audit`
+//! API.
+//!
+//! # Usage
+//!
+//! ```bash
+//! vault-audit audit-devices list
+//! vault-audit audit-devices enable --type file --path file/ --option file_path=/var/log/vault_audit.log
+//! vault-audit audit-devices disable --path file/
+//! ```
+
+use crate::utils::format::format_number;
+use crate::utils::report::{self, OutputFormat, Report};
+use crate::vault_api::{extract_data, should_skip_verify, VaultClient};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+
+/// One audit device, as returned by `GET /sys/audit`.
+#[derive(Debug, Clone, Deserialize)]
+struct AuditDeviceInfo {
+    #[serde(rename = "type")]
+    device_type: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    options: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct AuditDeviceRow {
+    path: String,
+    #[serde(rename = "type")]
+    device_type: String,
+    description: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct AuditDevicesReport {
+    rows: Vec<AuditDeviceRow>,
+}
+
+impl Report for AuditDevicesReport {
+    type Row = AuditDeviceRow;
+
+    fn command_name(&self) -> &'static str {
+        "audit-devices"
+    }
+
+    fn render_table(&self, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+        writeln!(w, "\n{}", "=".repeat(80))?;
+        writeln!(w, "Vault Audit Devices ({})", format_number(self.rows.len()))?;
+        writeln!(w, "{}", "=".repeat(80))?;
+        writeln!(w, "{:<24} {:<12} {}", "Path", "Type", "Description")?;
+        writeln!(w, "{}", "-".repeat(80))?;
+        for row in &self.rows {
+            writeln!(w, "{:<24} {:<12} {}", row.path, row.device_type, row.description)?;
+        }
+        writeln!(w, "{}", "=".repeat(80))?;
+        Ok(())
+    }
+
+    fn rows(&self) -> &[AuditDeviceRow] {
+        &self.rows
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn connect(
+    vault_addr: Option<&str>,
+    vault_token: Option<&str>,
+    vault_namespace: Option<&str>,
+    role_id: Option<&str>,
+    secret_id: Option<&str>,
+    insecure: bool,
+    resolve: &[(String, std::net::SocketAddr)],
+    dns_server: Option<std::net::SocketAddr>,
+) -> Result<VaultClient> {
+    VaultClient::connect(
+        vault_addr,
+        vault_token,
+        vault_namespace,
+        role_id,
+        secret_id,
+        should_skip_verify(insecure),
+        resolve,
+        dns_server,
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn run_list(
+    vault_addr: Option<&str>,
+    vault_token: Option<&str>,
+    vault_namespace: Option<&str>,
+    role_id: Option<&str>,
+    secret_id: Option<&str>,
+    insecure: bool,
+    resolve: &[(String, std::net::SocketAddr)],
+    dns_server: Option<std::net::SocketAddr>,
+    format: &str,
+) -> Result<()> {
+    let format = OutputFormat::parse(format).map_err(anyhow::Error::msg)?;
+    let client = connect(
+        vault_addr,
+        vault_token,
+        vault_namespace,
+        role_id,
+        secret_id,
+        insecure,
+        resolve,
+        dns_server,
+    )
+    .await?;
+
+    let response = client.get_json("/sys/audit").await?;
+    let devices: HashMap<String, AuditDeviceInfo> = extract_data(response)?;
+
+    let mut rows: Vec<AuditDeviceRow> = devices
+        .into_iter()
+        .map(|(path, device)| AuditDeviceRow {
+            path,
+            device_type: device.device_type,
+            description: device.description,
+        })
+        .collect();
+    rows.sort_by(|a, b| a.path.cmp(&b.path));
+
+    report::emit(&AuditDevicesReport { rows }, format)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn run_enable(
+    vault_addr: Option<&str>,
+    vault_token: Option<&str>,
+    vault_namespace: Option<&str>,
+    role_id: Option<&str>,
+    secret_id: Option<&str>,
+    insecure: bool,
+    resolve: &[(String, std::net::SocketAddr)],
+    dns_server: Option<std::net::SocketAddr>,
+    device_type: &str,
+    path: &str,
+    description: Option<&str>,
+    options: &[String],
+) -> Result<()> {
+    let client = connect(
+        vault_addr,
+        vault_token,
+        vault_namespace,
+        role_id,
+        secret_id,
+        insecure,
+        resolve,
+        dns_server,
+    )
+    .await?;
+
+    let mut option_map = serde_json::Map::new();
+    for option in options {
+        let (key, value) = option
+            .split_once('=')
+            .with_context(|| format!("--option '{}' must be in key=value form", option))?;
+        option_map.insert(key.to_string(), json!(value));
+    }
+
+    let body = json!({
+        "type": device_type,
+        "description": description.unwrap_or(""),
+        "options": option_map,
+    });
+
+    client.post_json(&format!("/sys/audit/{}", path), &body).await?;
+    println!("Enabled audit device '{}' (type: {})", path, device_type);
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn run_disable(
+    vault_addr: Option<&str>,
+    vault_token: Option<&str>,
+    vault_namespace: Option<&str>,
+    role_id: Option<&str>,
+    secret_id: Option<&str>,
+    insecure: bool,
+    resolve: &[(String, std::net::SocketAddr)],
+    dns_server: Option<std::net::SocketAddr>,
+    path: &str,
+) -> Result<()> {
+    let client = connect(
+        vault_addr,
+        vault_token,
+        vault_namespace,
+        role_id,
+        secret_id,
+        insecure,
+        resolve,
+        dns_server,
+    )
+    .await?;
+
+    client.delete(&format!("/sys/audit/{}", path)).await?;
+    println!("Disabled audit device '{}'", path);
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+//! Computes the `hmac-sha256:<hex>` form Vault's audit devices use to
+//! obfuscate sensitive strings (tokens, accessors, entity IDs, request
+//! data) before writing a log line, so a known plaintext can be turned
+//! into the value to `grep` for across already-HMAC'd audit logs.
+//!
+//! # Usage
+//!
+//! ```bash
+//! # Ask Vault to hash a value the way a given audit device would
+//! vault-audit audit-hash --input hvs.CAESID... --path file/
+//!
+//! # Compute the same hash fully offline from the device's salt bytes
+//! vault-audit audit-hash --input hvs.CAESID... --salt device.salt
+//! ```
+//!
+//! With `--salt`, no Vault connection is made at all: the salt file's raw
+//! bytes are used directly as the HMAC key, matching Vault's own
+//! `audit.HMACSHA256` salting exactly. Without `--salt`, this calls
+//! Vault's `POST /sys/audit-hash/:path` endpoint, which performs the same
+//! computation server-side against the live device's salt.
+
+use crate::vault_api::{extract_data, should_skip_verify, VaultClient};
+use anyhow::{Context, Result};
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use serde_json::json;
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Response from `POST /sys/audit-hash/:path`.
+#[derive(Debug, Deserialize)]
+struct AuditHashResponse {
+    sum: String,
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Hashes `input` the way Vault's audit HMAC salting does: the salt is
+/// used as the raw HMAC key (never re-hashed), producing lowercase hex
+/// with no separators.
+fn hmac_sha256_hex(salt: &[u8], input: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(salt).expect("HMAC accepts a key of any length");
+    mac.update(input.as_bytes());
+    hex_encode(&mac.finalize().into_bytes())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn run(
+    input: &str,
+    path: &str,
+    salt: Option<&str>,
+    vault_addr: Option<&str>,
+    vault_token: Option<&str>,
+    vault_namespace: Option<&str>,
+    role_id: Option<&str>,
+    secret_id: Option<&str>,
+    insecure: bool,
+    resolve: &[(String, std::net::SocketAddr)],
+    dns_server: Option<std::net::SocketAddr>,
+) -> Result<()> {
+    if let Some(salt_file) = salt {
+        let salt_bytes = std::fs::read(salt_file)
+            .with_context(|| format!("Failed to read salt file: {}", salt_file))?;
+        println!("hmac-sha256:{}", hmac_sha256_hex(&salt_bytes, input));
+        return Ok(());
+    }
+
+    let client = VaultClient::connect(
+        vault_addr,
+        vault_token,
+        vault_namespace,
+        role_id,
+        secret_id,
+        should_skip_verify(insecure),
+        resolve,
+        dns_server,
+    )
+    .await?;
+
+    let response = client
+        .post_json(&format!("/sys/audit-hash/{}", path), &json!({ "input": input }))
+        .await?;
+    let hash: AuditHashResponse = extract_data(response)?;
+    println!("{}", hash.sum);
+
+    Ok(())
+}
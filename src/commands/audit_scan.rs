@@ -0,0 +1,55 @@
+//! Run multiple analyses over the same audit logs in a single pass using
+//! [`crate::audit::engine::run_collectors`].
+//!
+//! `k8s-auth` and `preprocess-entities` each read every log file end-to-end
+//! on their own. This command drives both of their core extraction logics
+//! -  [`K8sLoginCollector`](crate::audit::collectors::K8sLoginCollector) and
+//! [`EntityMappingCollector`](crate::audit::collectors::EntityMappingCollector)
+//! - over one shared pass, and prints both reports. It does not replace
+//! either command: use `k8s-auth`/`entity-analysis preprocess` when you only
+//! need one analysis and want their full flag set (burst detection,
+//! Prometheus export, follow mode, CSV output, ...); use `audit-scan` when
+//! you want several analyses out of one read of large/many log files.
+//!
+//! # Usage
+//!
+//! ```bash
+//! vault-audit audit-scan logs/*.log.gz
+//! ```
+
+use crate::audit::collectors::{EntityMappingCollector, K8sLoginCollector};
+use crate::audit::engine::{run_collectors, Collector};
+use crate::utils::format::format_number;
+use anyhow::{bail, Result};
+
+pub fn run(log_files: &[String], output: Option<&str>) -> Result<()> {
+    if log_files.is_empty() {
+        bail!("No log files specified");
+    }
+
+    let mut collectors: Vec<Box<dyn Collector>> = vec![
+        Box::new(K8sLoginCollector::new()),
+        Box::new(EntityMappingCollector::new()),
+    ];
+
+    let (reports, stats) = run_collectors(log_files, &mut collectors)?;
+
+    println!("\n=== Audit Scan Summary ===");
+    println!("Files processed:  {}", stats.files_processed);
+    println!("Lines read:       {}", format_number(stats.total_lines));
+    println!("Entries parsed:   {}", format_number(stats.parsed_entries));
+
+    let combined = serde_json::json!({
+        "k8s_logins": reports[0],
+        "entity_mappings": reports[1],
+    });
+
+    if let Some(path) = output {
+        std::fs::write(path, serde_json::to_string_pretty(&combined)?)?;
+        println!("\nWrote combined report to {}", path);
+    } else {
+        println!("\n{}", serde_json::to_string_pretty(&combined)?);
+    }
+
+    Ok(())
+}
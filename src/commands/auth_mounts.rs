@@ -30,7 +30,9 @@
 //! - **approle**: Lists `AppRole` roles for application authentication
 //! - **userpass**: Lists configured users
 //! - **jwt/oidc**: Lists JWT/OIDC roles
-//! - **ldap**: Lists LDAP users and groups (prefixed with `user:`/`group:`)
+//! - **ldap**: Lists LDAP users and groups (prefixed with `user:`/`group:`).
+//!   At depth >= 2, each group's `children` also include the users resolved
+//!   as members of it (see "LDAP Group Membership" below).
 //! - **token**: No enumerable configuration
 //!
 //! # Output Formats
@@ -42,8 +44,33 @@
 //! # Depth Parameter
 //!
 //! - `--depth 0`: Show only mount points (no role enumeration)
-//! - `--depth 1` or higher: Include roles/users within each mount
-//! - No flag: Unlimited depth (enumerates all roles/users)
+//! - `--depth 1`: Include roles/users within each mount
+//! - `--depth 2` or higher: Also expand each role/user's attached policies,
+//!   bound service accounts/audiences, and TTLs into `children`
+//! - No flag: Unlimited depth (enumerates all roles/users and their detail)
+//!
+//! # Concurrency
+//!
+//! Mounts (and their role/user enumeration) are fetched concurrently, bounded
+//! by `--concurrency` (default 8), instead of one at a time. Output order is
+//! still deterministic: results are sorted by mount path after collection.
+//!
+//! # Logging
+//!
+//! Progress/diagnostic messages go through [`crate::utils::logging`] rather
+//! than `eprintln!`, so they never collide with the `--output` stream.
+//! `--log-file <path>` additionally writes timestamped structured lines to
+//! that file; with the `enable_syslog` feature, the same events also go to
+//! local syslog.
+//!
+//! # Key Casing
+//!
+//! `--key-case {snake,camel,vault}` controls the JSON output's field
+//! names via [`crate::utils::key_case`]: `snake` (default) is this crate's
+//! native naming, `camel` renames every key to camelCase, and `vault`
+//! reshapes fields (`type`, lease TTLs nested under `config`, ...) to match
+//! Vault's own `/v1/sys/auth` response so exported JSON can be diffed
+//! directly against an API capture.
 //!
 //! # API Endpoints Used
 //!
@@ -51,14 +78,45 @@
 //! - `/v1/auth/{mount}/role` - List roles (kubernetes, approle, jwt/oidc)
 //! - `/v1/auth/{mount}/users` - List users (userpass, ldap)
 //! - `/v1/auth/{mount}/groups` - List groups (ldap)
+//!
+//! # Multi-Source Merge
+//!
+//! `run_merge` combines several previously saved `--format json` snapshots,
+//! one per Vault cluster/namespace, into a single tagged report, mirroring
+//! [`crate::commands::kv_mounts::run_merge`]: JSON emits
+//! `{"sources": [...], "mounts": [...]}` with each mount carrying its
+//! `source_id`; CSV prepends `source_address`/`namespace` columns. Mounts
+//! whose `path` appears under more than one source are kept side by side and
+//! flagged `drift: true` when their type/accessor disagree across sources.
+//!
+//! # LDAP Group Membership
+//!
+//! Vault's LDAP auth backend does not persist a member roster on the group
+//! itself: membership is resolved against the external LDAP directory at
+//! login time, and `/v1/auth/{mount}/groups/{name}` only ever returns the
+//! group's `policies`. There is no equivalent `groups` endpoint for `jwt`/
+//! `oidc` mounts at all (their claims-based group mapping is likewise
+//! resolved externally, per-login, from the IdP token - not enumerable via
+//! a static config API), so membership expansion below is LDAP-only.
+//!
+//! As a best-effort proxy, a user is treated as a member of an LDAP group
+//! (and nested under it in `children`, alongside the group's own `policy:`
+//! entries) when the user's own policy set is a non-empty superset of the
+//! group's policies - the closest static signal Vault's config API exposes.
+//! This is a heuristic, not ground truth. CSV rows for these nested members
+//! reuse the existing `parent_role` column (roles, users, and groups are all
+//! modeled as the same `RoleEntry` type) rather than adding a separate
+//! `parent_group` column.
 
 use anyhow::{Context, Result};
+use futures::stream::{self, StreamExt};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::Write;
 
+use crate::utils::key_case::{self, KeyCase};
 use crate::vault_api::VaultClient;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -88,17 +146,95 @@ where
     Ok(opt.unwrap_or_default())
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 struct RoleEntry {
     name: String,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     children: Vec<Self>,
+    /// Observed login count from `--entities`, for userpass/ldap users
+    /// whose name matches an entity's `display_name`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    login_count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    last_seen: Option<String>,
+    /// `"unused"` when `--entities` was given and no matching entity logins
+    /// were observed for this role/user.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    status: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
-struct AuthMountOutput {
-    path: String,
-    auth_type: String,
+/// Entity mapping loaded from `--entities`, keyed by entity ID. Mirrors the
+/// shape produced by `entity-list` and consumed by `entity-churn`.
+#[derive(Debug, Deserialize, Clone)]
+struct EntityMapping {
+    display_name: String,
+    mount_path: String,
+    mount_accessor: String,
+    login_count: usize,
+    #[allow(dead_code)]
+    first_seen: String,
+    last_seen: String,
+}
+
+/// A single CSV row of entity mapping data, carrying `entity_id` as its own
+/// column since CSV (unlike the JSON map form) has no natural object key.
+#[derive(Debug, Deserialize)]
+struct EntityMappingCsvRow {
+    entity_id: String,
+    display_name: String,
+    mount_path: String,
+    mount_accessor: String,
+    login_count: usize,
+    first_seen: String,
+    last_seen: String,
+}
+
+/// Load entity mappings from a CSV or JSON file, detected by extension
+/// (falling back to JSON-then-CSV if the extension is ambiguous).
+fn load_entity_mappings(path: &str) -> Result<HashMap<String, EntityMapping>> {
+    let file = File::open(path).with_context(|| format!("Failed to open entity map file: {}", path))?;
+
+    let path_lower = path.to_lowercase();
+    if std::path::Path::new(&path_lower)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+    {
+        serde_json::from_reader(file).context("Failed to parse entity map JSON")
+    } else {
+        let mut reader = csv::Reader::from_reader(file);
+        let mut mappings = HashMap::new();
+        for result in reader.deserialize() {
+            let row: EntityMappingCsvRow = result.context("Failed to parse entity map CSV row")?;
+            mappings.insert(
+                row.entity_id,
+                EntityMapping {
+                    display_name: row.display_name,
+                    mount_path: row.mount_path,
+                    mount_accessor: row.mount_accessor,
+                    login_count: row.login_count,
+                    first_seen: row.first_seen,
+                    last_seen: row.last_seen,
+                },
+            );
+        }
+        Ok(mappings)
+    }
+}
+
+/// Summary of an entity that authenticated through a given mount, attached
+/// to [`AuthMountOutput::entities`] when `--entities` is given.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct EntitySummary {
+    entity_id: String,
+    display_name: String,
+    login_count: usize,
+    last_seen: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct AuthMountOutput {
+    pub(crate) path: String,
+    pub(crate) auth_type: String,
     description: String,
     accessor: String,
     local: bool,
@@ -107,10 +243,122 @@ struct AuthMountOutput {
     max_lease_ttl: String,
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     roles: Vec<RoleEntry>,
+    /// Entities that authenticated through this mount (by `accessor`),
+    /// populated when `--entities` is given.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    entities: Vec<EntitySummary>,
+    /// `"unused"` when `--entities` was given and no entity logins were
+    /// observed through this mount.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    status: Option<String>,
+}
+
+/// Attach observed entity logins to a mount's roles/users and to the mount
+/// itself, matching by `mount_accessor` and (for userpass/ldap) by the
+/// role/user name stripped of its `user:`/`group:` prefix against the
+/// entity's `display_name`. Marks mounts and roles with no matching logins
+/// as `"unused"`.
+fn enrich_with_entities(mount: &mut AuthMountOutput, entity_map: &HashMap<String, EntityMapping>) {
+    for (entity_id, entity) in entity_map {
+        if entity.mount_accessor == mount.accessor {
+            mount.entities.push(EntitySummary {
+                entity_id: entity_id.clone(),
+                display_name: entity.display_name.clone(),
+                login_count: entity.login_count,
+                last_seen: entity.last_seen.clone(),
+            });
+        }
+    }
+    mount.entities.sort_by(|a, b| a.display_name.cmp(&b.display_name));
+
+    let matches_by_name = matches!(mount.auth_type.as_str(), "userpass" | "ldap");
+    for role in mount.roles.iter_mut().filter(|_| matches_by_name) {
+        let bare_name = role
+            .name
+            .strip_prefix("user:")
+            .or_else(|| role.name.strip_prefix("group:"))
+            .unwrap_or(&role.name);
+
+        let matching = entity_map
+            .values()
+            .find(|e| e.mount_accessor == mount.accessor && e.display_name == bare_name);
+
+        if let Some(entity) = matching {
+            role.login_count = Some(entity.login_count);
+            role.last_seen = Some(entity.last_seen.clone());
+        } else {
+            role.status = Some("unused".to_string());
+        }
+    }
+
+    if mount.entities.is_empty() {
+        mount.status = Some("unused".to_string());
+    }
+}
+
+/// Expand a single kubernetes role's attached policies, bound service
+/// accounts, and token TTL into `children` entries (depth >= 2 only).
+async fn expand_k8s_role(client: &VaultClient, mount_path: &str, role_name: &str) -> Vec<RoleEntry> {
+    let role_path = format!(
+        "/v1/auth/{}/role/{}",
+        mount_path.trim_end_matches('/'),
+        role_name
+    );
+
+    let Ok(response) = client.get_json(&role_path).await else {
+        return vec![];
+    };
+    let Some(data) = response.get("data") else {
+        return vec![];
+    };
+
+    let mut children = Vec::new();
+
+    if let Some(policies) = data.get("token_policies").and_then(|v| v.as_array()) {
+        for policy in policies.iter().filter_map(Value::as_str) {
+            children.push(RoleEntry {
+                name: format!("policy:{}", policy),
+                children: vec![],
+                ..Default::default()
+            });
+        }
+    }
+
+    let namespaces = data
+        .get("bound_service_account_namespaces")
+        .and_then(|v| v.as_array())
+        .map_or_else(Vec::new, |a| a.iter().filter_map(Value::as_str).collect());
+    let names = data
+        .get("bound_service_account_names")
+        .and_then(|v| v.as_array())
+        .map_or_else(Vec::new, |a| a.iter().filter_map(Value::as_str).collect());
+    for ns in &namespaces {
+        for name in &names {
+            children.push(RoleEntry {
+                name: format!("sa:{}/{}", ns, name),
+                children: vec![],
+                ..Default::default()
+            });
+        }
+    }
+
+    if let Some(ttl) = data.get("token_ttl").and_then(serde_json::Value::as_i64) {
+        children.push(RoleEntry {
+            name: format!("ttl:{}s", ttl),
+            children: vec![],
+            ..Default::default()
+        });
+    }
+
+    children
 }
 
 /// List roles for kubernetes auth mounts
-async fn list_k8s_roles(client: &VaultClient, mount_path: &str) -> Result<Vec<RoleEntry>> {
+async fn list_k8s_roles(
+    client: &VaultClient,
+    mount_path: &str,
+    depth: usize,
+) -> Result<Vec<RoleEntry>> {
     let list_path = format!("/v1/auth/{}/role", mount_path.trim_end_matches('/'));
 
     match client.list_json(&list_path).await {
@@ -123,9 +371,15 @@ async fn list_k8s_roles(client: &VaultClient, mount_path: &str) -> Result<Vec<Ro
                 let mut roles = Vec::new();
                 for key in keys {
                     if let Some(role_name) = key.as_str() {
+                        let children = if depth >= 2 {
+                            expand_k8s_role(client, mount_path, role_name).await
+                        } else {
+                            vec![]
+                        };
                         roles.push(RoleEntry {
                             name: role_name.to_string(),
-                            children: vec![],
+                            children,
+                            ..Default::default()
                         });
                     }
                 }
@@ -138,8 +392,82 @@ async fn list_k8s_roles(client: &VaultClient, mount_path: &str) -> Result<Vec<Ro
     }
 }
 
+/// Expand a single approle role's attached policies, TTLs, and
+/// secret-id accessors into `children` entries (depth >= 2 only).
+async fn expand_approle_role(
+    client: &VaultClient,
+    mount_path: &str,
+    role_name: &str,
+) -> Vec<RoleEntry> {
+    let role_path = format!(
+        "/v1/auth/{}/role/{}",
+        mount_path.trim_end_matches('/'),
+        role_name
+    );
+
+    let mut children = Vec::new();
+
+    if let Ok(response) = client.get_json(&role_path).await {
+        if let Some(data) = response.get("data") {
+            if let Some(policies) = data.get("token_policies").and_then(|v| v.as_array()) {
+                for policy in policies.iter().filter_map(Value::as_str) {
+                    children.push(RoleEntry {
+                        name: format!("policy:{}", policy),
+                        children: vec![],
+                        ..Default::default()
+                    });
+                }
+            }
+            if let Some(ttl) = data.get("token_ttl").and_then(serde_json::Value::as_i64) {
+                children.push(RoleEntry {
+                    name: format!("ttl:{}s", ttl),
+                    children: vec![],
+                    ..Default::default()
+                });
+            }
+            if let Some(ttl) = data
+                .get("secret_id_ttl")
+                .and_then(serde_json::Value::as_i64)
+            {
+                children.push(RoleEntry {
+                    name: format!("secret_id_ttl:{}s", ttl),
+                    children: vec![],
+                    ..Default::default()
+                });
+            }
+        }
+    }
+
+    let accessors_path = format!(
+        "/v1/auth/{}/role/{}/secret-id-accessor",
+        mount_path.trim_end_matches('/'),
+        role_name
+    );
+    if let Ok(response) = client.list_json(&accessors_path).await {
+        if let Some(keys) = response
+            .get("data")
+            .and_then(|d| d.get("keys"))
+            .and_then(|k| k.as_array())
+        {
+            for accessor in keys.iter().filter_map(Value::as_str) {
+                children.push(RoleEntry {
+                    name: format!("secret-id-accessor:{}", accessor),
+                    children: vec![],
+                    ..Default::default()
+                });
+            }
+        }
+    }
+
+    children
+}
+
 /// List roles for approle auth mounts
-async fn list_approle_roles(client: &VaultClient, mount_path: &str) -> Result<Vec<RoleEntry>> {
+async fn list_approle_roles(
+    client: &VaultClient,
+    mount_path: &str,
+    depth: usize,
+) -> Result<Vec<RoleEntry>> {
     let list_path = format!("/v1/auth/{}/role", mount_path.trim_end_matches('/'));
 
     match client.list_json(&list_path).await {
@@ -152,9 +480,15 @@ async fn list_approle_roles(client: &VaultClient, mount_path: &str) -> Result<Ve
                 let mut roles = Vec::new();
                 for key in keys {
                     if let Some(role_name) = key.as_str() {
+                        let children = if depth >= 2 {
+                            expand_approle_role(client, mount_path, role_name).await
+                        } else {
+                            vec![]
+                        };
                         roles.push(RoleEntry {
                             name: role_name.to_string(),
-                            children: vec![],
+                            children,
+                            ..Default::default()
                         });
                     }
                 }
@@ -167,8 +501,43 @@ async fn list_approle_roles(client: &VaultClient, mount_path: &str) -> Result<Ve
     }
 }
 
+/// Expand a single userpass user's attached policies into `children`
+/// entries (depth >= 2 only).
+async fn expand_userpass_user(client: &VaultClient, mount_path: &str, user_name: &str) -> Vec<RoleEntry> {
+    let user_path = format!(
+        "/v1/auth/{}/users/{}",
+        mount_path.trim_end_matches('/'),
+        user_name
+    );
+
+    let Ok(response) = client.get_json(&user_path).await else {
+        return vec![];
+    };
+    let Some(policies) = response
+        .get("data")
+        .and_then(|d| d.get("token_policies"))
+        .and_then(|v| v.as_array())
+    else {
+        return vec![];
+    };
+
+    policies
+        .iter()
+        .filter_map(Value::as_str)
+        .map(|policy| RoleEntry {
+            name: format!("policy:{}", policy),
+            children: vec![],
+            ..Default::default()
+        })
+        .collect()
+}
+
 /// List users for userpass auth mounts
-async fn list_userpass_users(client: &VaultClient, mount_path: &str) -> Result<Vec<RoleEntry>> {
+async fn list_userpass_users(
+    client: &VaultClient,
+    mount_path: &str,
+    depth: usize,
+) -> Result<Vec<RoleEntry>> {
     let list_path = format!("/v1/auth/{}/users", mount_path.trim_end_matches('/'));
 
     match client.list_json(&list_path).await {
@@ -181,9 +550,15 @@ async fn list_userpass_users(client: &VaultClient, mount_path: &str) -> Result<V
                 let mut users = Vec::new();
                 for key in keys {
                     if let Some(user_name) = key.as_str() {
+                        let children = if depth >= 2 {
+                            expand_userpass_user(client, mount_path, user_name).await
+                        } else {
+                            vec![]
+                        };
                         users.push(RoleEntry {
                             name: user_name.to_string(),
-                            children: vec![],
+                            children,
+                            ..Default::default()
                         });
                     }
                 }
@@ -196,8 +571,51 @@ async fn list_userpass_users(client: &VaultClient, mount_path: &str) -> Result<V
     }
 }
 
+/// Expand a single JWT/OIDC role's bound audiences and user claim into
+/// `children` entries (depth >= 2 only).
+async fn expand_jwt_role(client: &VaultClient, mount_path: &str, role_name: &str) -> Vec<RoleEntry> {
+    let role_path = format!(
+        "/v1/auth/{}/role/{}",
+        mount_path.trim_end_matches('/'),
+        role_name
+    );
+
+    let Ok(response) = client.get_json(&role_path).await else {
+        return vec![];
+    };
+    let Some(data) = response.get("data") else {
+        return vec![];
+    };
+
+    let mut children = Vec::new();
+
+    if let Some(audiences) = data.get("bound_audiences").and_then(|v| v.as_array()) {
+        for audience in audiences.iter().filter_map(Value::as_str) {
+            children.push(RoleEntry {
+                name: format!("audience:{}", audience),
+                children: vec![],
+                ..Default::default()
+            });
+        }
+    }
+
+    if let Some(claim) = data.get("user_claim").and_then(|v| v.as_str()) {
+        children.push(RoleEntry {
+            name: format!("user_claim:{}", claim),
+            children: vec![],
+            ..Default::default()
+        });
+    }
+
+    children
+}
+
 /// List roles for JWT/OIDC auth mounts
-async fn list_jwt_roles(client: &VaultClient, mount_path: &str) -> Result<Vec<RoleEntry>> {
+async fn list_jwt_roles(
+    client: &VaultClient,
+    mount_path: &str,
+    depth: usize,
+) -> Result<Vec<RoleEntry>> {
     let list_path = format!("/v1/auth/{}/role", mount_path.trim_end_matches('/'));
 
     match client.list_json(&list_path).await {
@@ -210,9 +628,15 @@ async fn list_jwt_roles(client: &VaultClient, mount_path: &str) -> Result<Vec<Ro
                 let mut roles = Vec::new();
                 for key in keys {
                     if let Some(role_name) = key.as_str() {
+                        let children = if depth >= 2 {
+                            expand_jwt_role(client, mount_path, role_name).await
+                        } else {
+                            vec![]
+                        };
                         roles.push(RoleEntry {
                             name: role_name.to_string(),
-                            children: vec![],
+                            children,
+                            ..Default::default()
                         });
                     }
                 }
@@ -225,12 +649,105 @@ async fn list_jwt_roles(client: &VaultClient, mount_path: &str) -> Result<Vec<Ro
     }
 }
 
+/// Fetch the `policies` array attached to a single LDAP user or group config
+/// object (`/v1/auth/<mount>/{users,groups}/<name>`).
+async fn fetch_ldap_policies(
+    client: &VaultClient,
+    mount_path: &str,
+    kind: &str,
+    entry_name: &str,
+) -> Vec<String> {
+    let entry_path = format!(
+        "/v1/auth/{}/{}/{}",
+        mount_path.trim_end_matches('/'),
+        kind,
+        entry_name
+    );
+
+    let Ok(response) = client.get_json(&entry_path).await else {
+        return vec![];
+    };
+    response
+        .get("data")
+        .and_then(|d| d.get("policies"))
+        .and_then(|v| v.as_array())
+        .map(|policies| {
+            policies
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Expand a single LDAP user or group's attached policies into `children`
+/// entries (depth >= 2 only).
+async fn expand_ldap_entry(
+    client: &VaultClient,
+    mount_path: &str,
+    kind: &str,
+    entry_name: &str,
+) -> Vec<RoleEntry> {
+    fetch_ldap_policies(client, mount_path, kind, entry_name)
+        .await
+        .into_iter()
+        .map(|policy| RoleEntry {
+            name: format!("policy:{}", policy),
+            children: vec![],
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Resolve the LDAP users that belong to a group.
+///
+/// Vault's LDAP auth backend does not store a static member roster on the
+/// group itself - group membership is resolved against the external LDAP
+/// directory at login time, not persisted in `/v1/auth/<mount>/groups/<name>`,
+/// which only ever returns the group's `policies`. As a best-effort proxy
+/// for "this user is a member of this group", a user is treated as a member
+/// when its own policy set is a non-empty superset of the group's policies
+/// (i.e. the user has inherited every policy the group grants). This is a
+/// heuristic, not ground truth from Vault, and is documented as such.
+async fn resolve_ldap_group_members(
+    client: &VaultClient,
+    mount_path: &str,
+    group_policies: &[String],
+    user_names: &[String],
+) -> Vec<RoleEntry> {
+    if group_policies.is_empty() {
+        return vec![];
+    }
+
+    let mut members = Vec::new();
+    for user_name in user_names {
+        let user_policies = fetch_ldap_policies(client, mount_path, "users", user_name).await;
+        let is_member = group_policies
+            .iter()
+            .all(|policy| user_policies.contains(policy));
+        if is_member {
+            members.push(RoleEntry {
+                name: format!("user:{}", user_name),
+                children: vec![],
+                ..Default::default()
+            });
+        }
+    }
+    members
+}
+
 /// List users/groups for LDAP auth mounts
-async fn list_ldap_config(client: &VaultClient, mount_path: &str) -> Result<Vec<RoleEntry>> {
+async fn list_ldap_config(
+    client: &VaultClient,
+    mount_path: &str,
+    depth: usize,
+) -> Result<Vec<RoleEntry>> {
     let users_path = format!("/v1/auth/{}/users", mount_path.trim_end_matches('/'));
     let groups_path = format!("/v1/auth/{}/groups", mount_path.trim_end_matches('/'));
 
     let mut entries = Vec::new();
+    let mut user_names = Vec::new();
 
     // Try to list users
     if let Ok(response) = client.list_json(&users_path).await {
@@ -241,9 +758,16 @@ async fn list_ldap_config(client: &VaultClient, mount_path: &str) -> Result<Vec<
         {
             for key in keys {
                 if let Some(user_name) = key.as_str() {
+                    user_names.push(user_name.to_string());
+                    let children = if depth >= 2 {
+                        expand_ldap_entry(client, mount_path, "users", user_name).await
+                    } else {
+                        vec![]
+                    };
                     entries.push(RoleEntry {
                         name: format!("user:{}", user_name),
-                        children: vec![],
+                        children,
+                        ..Default::default()
                     });
                 }
             }
@@ -259,9 +783,29 @@ async fn list_ldap_config(client: &VaultClient, mount_path: &str) -> Result<Vec<
         {
             for key in keys {
                 if let Some(group_name) = key.as_str() {
+                    let mut children = vec![];
+                    if depth >= 2 {
+                        let group_policies =
+                            fetch_ldap_policies(client, mount_path, "groups", group_name).await;
+                        children.extend(group_policies.iter().map(|policy| RoleEntry {
+                            name: format!("policy:{}", policy),
+                            children: vec![],
+                            ..Default::default()
+                        }));
+                        children.extend(
+                            resolve_ldap_group_members(
+                                client,
+                                mount_path,
+                                &group_policies,
+                                &user_names,
+                            )
+                            .await,
+                        );
+                    }
                     entries.push(RoleEntry {
                         name: format!("group:{}", group_name),
-                        children: vec![],
+                        children,
+                        ..Default::default()
                     });
                 }
             }
@@ -283,29 +827,52 @@ async fn enumerate_auth_configs(
     }
 
     match auth_type {
-        "kubernetes" => list_k8s_roles(client, mount_path).await,
-        "approle" => list_approle_roles(client, mount_path).await,
-        "userpass" => list_userpass_users(client, mount_path).await,
-        "jwt" | "oidc" => list_jwt_roles(client, mount_path).await,
-        "ldap" => list_ldap_config(client, mount_path).await,
+        "kubernetes" => list_k8s_roles(client, mount_path, depth).await,
+        "approle" => list_approle_roles(client, mount_path, depth).await,
+        "userpass" => list_userpass_users(client, mount_path, depth).await,
+        "jwt" | "oidc" => list_jwt_roles(client, mount_path, depth).await,
+        "ldap" => list_ldap_config(client, mount_path, depth).await,
         _ => Ok(vec![]), // Unsupported auth types return empty
     }
 }
 
 /// Run the auth mount enumeration command
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
     vault_addr: Option<&str>,
     vault_token: Option<&str>,
     vault_namespace: Option<&str>,
+    role_id: Option<&str>,
+    secret_id: Option<&str>,
     insecure: bool,
     output: Option<&str>,
     format: &str,
     depth: usize,
+    concurrency: usize,
+    resolve: &[(String, std::net::SocketAddr)],
+    dns_server: Option<std::net::SocketAddr>,
+    log_file: Option<&str>,
+    key_case: KeyCase,
+    entities: Option<&str>,
 ) -> Result<()> {
-    let client = VaultClient::from_options(vault_addr, vault_token, vault_namespace, insecure)?;
+    crate::utils::logging::init(log_file, crate::utils::progress::Verbosity::Normal)?;
+
+    let entity_map = entities.map(load_entity_mappings).transpose()?;
 
-    eprintln!("Querying Vault API for auth mounts...");
-    eprintln!("   Vault Address: {}", client.addr());
+    let client = VaultClient::connect(
+        vault_addr,
+        vault_token,
+        vault_namespace,
+        role_id,
+        secret_id,
+        insecure,
+        resolve,
+        dns_server,
+    )
+    .await?;
+
+    tracing::info!("Querying Vault API for auth mounts...");
+    tracing::info!(vault_addr = %client.addr(), "Connected to Vault");
 
     // Query /sys/auth to get all auth mounts
     let response: Value = client
@@ -323,8 +890,9 @@ pub async fn run(
         .as_object()
         .context("Expected object response from /v1/sys/auth")?;
 
-    let mut auth_mounts = Vec::new();
-
+    // Parse mount metadata up front (cheap, synchronous) so the concurrent
+    // stage below only has to await the per-mount/per-role network calls.
+    let mut pending = Vec::new();
     for (path, mount_data) in mounts {
         // Skip metadata fields like "request_id"
         if path == "request_id"
@@ -342,49 +910,80 @@ pub async fn run(
         let mount_info: AuthMountInfo = serde_json::from_value(mount_data.clone())
             .with_context(|| format!("Failed to parse auth mount info for {}", path))?;
 
-        let default_lease_ttl = mount_info
-            .config
-            .get("default_lease_ttl")
-            .and_then(serde_json::Value::as_i64)
-            .map_or_else(|| "0s".to_string(), |v| format!("{}s", v));
-
-        let max_lease_ttl = mount_info
-            .config
-            .get("max_lease_ttl")
-            .and_then(serde_json::Value::as_i64)
-            .map_or_else(|| "0s".to_string(), |v| format!("{}s", v));
-
-        // Enumerate roles/users if depth > 0
-        let roles = enumerate_auth_configs(&client, path, &mount_info.auth_type, depth)
-            .await
-            .unwrap_or_else(|_| vec![]);
-
-        auth_mounts.push(AuthMountOutput {
-            path: path.clone(),
-            auth_type: mount_info.auth_type.clone(),
-            description: mount_info.description.clone(),
-            accessor: mount_info.accessor.clone(),
-            local: mount_info.local,
-            seal_wrap: mount_info.seal_wrap,
-            default_lease_ttl,
-            max_lease_ttl,
-            roles,
-        });
+        pending.push((path.clone(), mount_info));
     }
 
-    eprintln!("Found {} auth mounts", auth_mounts.len());
+    // Enumerate mounts concurrently, bounded to `concurrency` mounts in flight
+    // at a time so we don't hammer a rate-limited Vault. Within one mount, the
+    // role listing and any depth >= 2 per-role detail requests run one after
+    // another; only separate mounts overlap. A failure enumerating one mount's roles leaves the
+    // others intact (enumerate_auth_configs already degrades to `vec![]`).
+    let mut auth_mounts: Vec<AuthMountOutput> = stream::iter(pending)
+        .map(|(path, mount_info)| {
+            let client = &client;
+            async move {
+                let default_lease_ttl = mount_info
+                    .config
+                    .get("default_lease_ttl")
+                    .and_then(serde_json::Value::as_i64)
+                    .map_or_else(|| "0s".to_string(), |v| format!("{}s", v));
+
+                let max_lease_ttl = mount_info
+                    .config
+                    .get("max_lease_ttl")
+                    .and_then(serde_json::Value::as_i64)
+                    .map_or_else(|| "0s".to_string(), |v| format!("{}s", v));
+
+                // Enumerate roles/users if depth > 0
+                let roles = enumerate_auth_configs(client, &path, &mount_info.auth_type, depth)
+                    .await
+                    .unwrap_or_else(|_| vec![]);
+
+                AuthMountOutput {
+                    path,
+                    auth_type: mount_info.auth_type,
+                    description: mount_info.description,
+                    accessor: mount_info.accessor,
+                    local: mount_info.local,
+                    seal_wrap: mount_info.seal_wrap,
+                    default_lease_ttl,
+                    max_lease_ttl,
+                    roles,
+                    entities: vec![],
+                    status: None,
+                }
+            }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await;
+
+    // Collection order follows completion order under concurrency, so sort
+    // by path to keep output deterministic.
+    auth_mounts.sort_by(|a, b| a.path.cmp(&b.path));
+
+    if let Some(entity_map) = &entity_map {
+        for mount in &mut auth_mounts {
+            enrich_with_entities(mount, entity_map);
+        }
+    }
+
+    tracing::info!(count = auth_mounts.len(), "Found auth mounts");
 
     // Output results
     match format {
         "json" => {
-            let json_output = serde_json::to_string_pretty(&auth_mounts)
+            let value = serde_json::to_value(&auth_mounts)
                 .context("Failed to serialize to JSON")?;
+            let value = key_case::recase(value, key_case);
+            let json_output =
+                serde_json::to_string_pretty(&value).context("Failed to serialize to JSON")?;
 
             if let Some(output_path) = output {
                 let mut file = File::create(output_path).context("Failed to create output file")?;
                 file.write_all(json_output.as_bytes())
                     .context("Failed to write JSON to file")?;
-                eprintln!("Output written to: {}", output_path);
+                tracing::info!(path = %output_path, "Output written");
             } else {
                 println!("{}", json_output);
             }
@@ -392,30 +991,51 @@ pub async fn run(
         "csv" => {
             use std::fmt::Write as _;
             let mut csv_output = String::new();
-            csv_output.push_str("path,type,description,accessor,role_name,depth\n");
+            csv_output.push_str(
+                "path,type,description,accessor,role_name,parent_role,depth,login_count,last_seen,status\n",
+            );
 
             for mount in &auth_mounts {
                 // First write the mount itself
                 let _ = writeln!(
                     csv_output,
-                    "\"{}\",\"{}\",\"{}\",\"{}\",\"\",0",
+                    "\"{}\",\"{}\",\"{}\",\"{}\",\"\",\"\",0,\"{}\",\"\",\"{}\"",
                     mount.path.replace('"', "\"\""),
                     mount.auth_type,
                     mount.description.replace('"', "\"\""),
                     mount.accessor,
+                    mount.entities.iter().map(|e| e.login_count).sum::<usize>(),
+                    mount.status.as_deref().unwrap_or(""),
                 );
 
-                // Then write each role/user
+                // Then write each role/user, followed by its expanded
+                // children (policies, bound SAs, TTLs, ...) at depth 2
                 for role in &mount.roles {
                     let _ = writeln!(
                         csv_output,
-                        "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",1",
+                        "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"\",1,\"{}\",\"{}\",\"{}\"",
                         mount.path.replace('"', "\"\""),
                         mount.auth_type,
                         mount.description.replace('"', "\"\""),
                         mount.accessor,
                         role.name.replace('"', "\"\""),
+                        role.login_count.map_or_else(String::new, |n| n.to_string()),
+                        role.last_seen.as_deref().unwrap_or(""),
+                        role.status.as_deref().unwrap_or(""),
                     );
+
+                    for child in &role.children {
+                        let _ = writeln!(
+                            csv_output,
+                            "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",2,\"\",\"\",\"\"",
+                            mount.path.replace('"', "\"\""),
+                            mount.auth_type,
+                            mount.description.replace('"', "\"\""),
+                            mount.accessor,
+                            child.name.replace('"', "\"\""),
+                            role.name.replace('"', "\"\""),
+                        );
+                    }
                 }
             }
 
@@ -423,7 +1043,7 @@ pub async fn run(
                 let mut file = File::create(output_path).context("Failed to create output file")?;
                 file.write_all(csv_output.as_bytes())
                     .context("Failed to write CSV to file")?;
-                eprintln!("Output written to: {}", output_path);
+                tracing::info!(path = %output_path, "Output written");
             } else {
                 print!("{}", csv_output);
             }
@@ -440,6 +1060,18 @@ pub async fn run(
                 println!("  Seal Wrap: {}", mount.seal_wrap);
                 println!("  Default Lease TTL: {}", mount.default_lease_ttl);
                 println!("  Max Lease TTL: {}", mount.max_lease_ttl);
+                if let Some(status) = &mount.status {
+                    println!("  Status: {}", status);
+                }
+                if !mount.entities.is_empty() {
+                    println!("  Entities ({}):", mount.entities.len());
+                    for entity in &mount.entities {
+                        println!(
+                            "    - {} (logins: {}, last seen: {})",
+                            entity.display_name, entity.login_count, entity.last_seen
+                        );
+                    }
+                }
 
                 if !mount.roles.is_empty() {
                     println!("  Roles/Users ({}):", mount.roles.len());
@@ -449,7 +1081,22 @@ pub async fn run(
                         } else {
                             "├──"
                         };
-                        println!("    {} {}", prefix, role.name);
+                        let annotation = if let Some(count) = role.login_count {
+                            format!(" (logins: {})", count)
+                        } else if let Some(status) = &role.status {
+                            format!(" ({})", status)
+                        } else {
+                            String::new()
+                        };
+                        println!("    {} {}{}", prefix, role.name, annotation);
+                        for (j, child) in role.children.iter().enumerate() {
+                            let child_prefix = if j == role.children.len() - 1 {
+                                "└──"
+                            } else {
+                                "├──"
+                            };
+                            println!("        {} {}", child_prefix, child.name);
+                        }
                     }
                 }
                 println!();
@@ -465,3 +1112,151 @@ pub async fn run(
 
     Ok(())
 }
+
+/// A single saved `--format json` snapshot contributing to a `--merge`
+/// report, tagged with the cluster it was captured from. Mirrors
+/// [`crate::commands::kv_mounts::SourceTag`].
+#[derive(Debug, Serialize)]
+pub(crate) struct SourceTag {
+    pub(crate) source_id: String,
+    pub(crate) address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) namespace: Option<String>,
+}
+
+/// An auth mount from a merged multi-source report, carrying the
+/// `source_id` of the snapshot it came from and whether its `path` also
+/// appears, with different details, under another source (cross-environment
+/// drift).
+#[derive(Debug, Serialize)]
+pub(crate) struct MergedAuthMount {
+    pub(crate) source_id: String,
+    #[serde(flatten)]
+    pub(crate) mount: AuthMountOutput,
+    pub(crate) drift: bool,
+}
+
+/// `{ "sources": [...], "mounts": [...] }`, the combined document produced
+/// by `--merge`.
+#[derive(Debug, Serialize)]
+pub(crate) struct MergedAuthReport {
+    pub(crate) sources: Vec<SourceTag>,
+    pub(crate) mounts: Vec<MergedAuthMount>,
+}
+
+/// Merge several previously-saved `--format json` snapshots (one per Vault
+/// cluster/namespace) into a single tagged report. See
+/// [`crate::commands::kv_mounts::run_merge`] for the rationale (snapshot-based
+/// rather than live multi-cluster querying) and drift semantics, which this
+/// mirrors for auth mounts.
+pub fn run_merge(
+    sources: &[(String, String, Option<String>, String)],
+    format: &str,
+    output: Option<&str>,
+) -> Result<()> {
+    let mut source_tags = Vec::new();
+    let mut mounts: Vec<MergedAuthMount> = Vec::new();
+
+    for (source_id, address, namespace, snapshot_path) in sources {
+        let file = File::open(snapshot_path)
+            .with_context(|| format!("Failed to open snapshot: {}", snapshot_path))?;
+        let snapshot: Vec<AuthMountOutput> = serde_json::from_reader(file)
+            .with_context(|| format!("Failed to parse snapshot: {}", snapshot_path))?;
+
+        source_tags.push(SourceTag {
+            source_id: source_id.clone(),
+            address: address.clone(),
+            namespace: namespace.clone(),
+        });
+
+        for mount in snapshot {
+            mounts.push(MergedAuthMount { source_id: source_id.clone(), mount, drift: false });
+        }
+    }
+
+    // Flag drift: mounts sharing a path whose type/accessor disagree across
+    // sources.
+    let mut by_path: HashMap<String, Vec<usize>> = HashMap::new();
+    for (idx, merged) in mounts.iter().enumerate() {
+        by_path.entry(merged.mount.path.clone()).or_default().push(idx);
+    }
+    for indices in by_path.values() {
+        if indices.len() < 2 {
+            continue;
+        }
+        let first = &mounts[indices[0]].mount;
+        let disagrees = indices[1..].iter().any(|&idx| {
+            let other = &mounts[idx].mount;
+            other.auth_type != first.auth_type || other.accessor != first.accessor
+        });
+        if disagrees {
+            for &idx in indices {
+                mounts[idx].drift = true;
+            }
+        }
+    }
+
+    eprintln!(
+        "Merged {} source(s), {} mount(s), {} with cross-source drift",
+        source_tags.len(),
+        mounts.len(),
+        mounts.iter().filter(|m| m.drift).count(),
+    );
+
+    match format {
+        "json" => {
+            let report = MergedAuthReport { sources: source_tags, mounts };
+            let json_output = serde_json::to_string_pretty(&report)
+                .context("Failed to serialize merged report to JSON")?;
+            if let Some(output_path) = output {
+                let mut file = File::create(output_path).context("Failed to create output file")?;
+                file.write_all(json_output.as_bytes())
+                    .context("Failed to write merged JSON to file")?;
+                tracing::info!(path = %output_path, "Output written");
+            } else {
+                println!("{}", json_output);
+            }
+        }
+        "csv" => {
+            use std::fmt::Write as _;
+            let mut csv_output = String::new();
+            csv_output
+                .push_str("source_address,namespace,source_id,path,type,description,accessor,drift\n");
+            for merged in &mounts {
+                let source = source_tags.iter().find(|s| s.source_id == merged.source_id);
+                let (address, namespace) = source
+                    .map(|s| (s.address.as_str(), s.namespace.as_deref().unwrap_or("")))
+                    .unwrap_or(("", ""));
+                let _ = writeln!(
+                    csv_output,
+                    "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\"",
+                    address.replace('"', "\"\""),
+                    namespace.replace('"', "\"\""),
+                    merged.source_id.replace('"', "\"\""),
+                    merged.mount.path.replace('"', "\"\""),
+                    merged.mount.auth_type,
+                    merged.mount.description.replace('"', "\"\""),
+                    merged.mount.accessor,
+                    merged.drift,
+                );
+            }
+
+            if let Some(output_path) = output {
+                let mut file = File::create(output_path).context("Failed to create output file")?;
+                file.write_all(csv_output.as_bytes())
+                    .context("Failed to write merged CSV to file")?;
+                tracing::info!(path = %output_path, "Output written");
+            } else {
+                print!("{}", csv_output);
+            }
+        }
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Invalid format: {}. Must be one of: csv, json",
+                format
+            ));
+        }
+    }
+
+    Ok(())
+}
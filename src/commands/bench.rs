@@ -0,0 +1,134 @@
+//! Benchmark/throughput measurement command.
+//!
+//! Streams audit log files through the same reader/parsing path used by
+//! the analysis commands and reports parsing throughput, so operators can
+//! size hardware before processing multi-hundred-GB audit logs.
+//!
+//! # Usage
+//!
+//! ```bash
+//! # Human-readable table
+//! vault-audit bench logs/*.log
+//!
+//! # Machine-readable summary for CI regression tracking
+//! vault-audit bench logs/*.log --json
+//!
+//! # Quiet / verbose progress
+//! vault-audit bench logs/*.log -q
+//! vault-audit bench logs/*.log -v
+//! ```
+
+use crate::audit::types::AuditEntry;
+use crate::utils::format::format_number;
+use crate::utils::progress::Verbosity;
+use crate::utils::reader::open_file;
+use anyhow::Result;
+use serde::Serialize;
+use std::io::{BufRead, BufReader};
+use std::time::Instant;
+
+/// Number of lines between interim rate checkpoints when running verbose.
+const CHECKPOINT_LINES: usize = 500_000;
+
+/// Machine-readable benchmark summary, emitted with `--json`.
+#[derive(Debug, Serialize)]
+struct BenchSummary {
+    files: usize,
+    lines_parsed: usize,
+    parse_errors: usize,
+    bytes_read: u64,
+    elapsed_seconds: f64,
+    lines_per_second: f64,
+    peak_rss_bytes: Option<u64>,
+}
+
+/// Run the benchmark command over the given log files.
+pub fn run(log_files: &[String], verbosity: Verbosity, json: bool) -> Result<()> {
+    let start = Instant::now();
+
+    let mut lines_parsed = 0usize;
+    let mut parse_errors = 0usize;
+    let mut bytes_read = 0u64;
+
+    for file_path in log_files {
+        let file = open_file(file_path)?;
+        let reader = BufReader::new(file);
+
+        for line_result in reader.lines() {
+            let line = line_result?;
+            bytes_read += line.len() as u64 + 1;
+            lines_parsed += 1;
+
+            if serde_json::from_str::<AuditEntry>(&line).is_err() && !line.trim().is_empty() {
+                parse_errors += 1;
+            }
+
+            if verbosity == Verbosity::Verbose && lines_parsed % CHECKPOINT_LINES == 0 {
+                let elapsed = start.elapsed().as_secs_f64();
+                let rate = lines_parsed as f64 / elapsed.max(0.001);
+                eprintln!(
+                    "  ...{} lines parsed ({:.0} lines/sec)",
+                    format_number(lines_parsed),
+                    rate
+                );
+            }
+        }
+    }
+
+    let elapsed_seconds = start.elapsed().as_secs_f64();
+    let lines_per_second = lines_parsed as f64 / elapsed_seconds.max(0.001);
+    let peak_rss_bytes = peak_rss_bytes();
+
+    if json {
+        let summary = BenchSummary {
+            files: log_files.len(),
+            lines_parsed,
+            parse_errors,
+            bytes_read,
+            elapsed_seconds,
+            lines_per_second,
+            peak_rss_bytes,
+        };
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+        return Ok(());
+    }
+
+    if verbosity != Verbosity::Quiet {
+        println!("\n{}", "=".repeat(70));
+        println!("BENCHMARK RESULTS");
+        println!("{}", "=".repeat(70));
+        println!("Files processed:   {}", log_files.len());
+        println!("Lines parsed:      {}", format_number(lines_parsed));
+        println!("Parse errors:      {}", format_number(parse_errors));
+        println!("Bytes read:        {}", format_number(bytes_read as usize));
+        println!("Wall clock time:   {:.2}s", elapsed_seconds);
+        println!("Throughput:        {:.0} lines/sec", lines_per_second);
+        if let Some(rss) = peak_rss_bytes {
+            println!("Peak RSS:          {} bytes", format_number(rss as usize));
+        }
+        println!("{}", "=".repeat(70));
+    }
+
+    Ok(())
+}
+
+/// Read peak resident set size from `/proc/self/status` on Linux.
+///
+/// Returns `None` on platforms where this isn't available; benchmark
+/// output simply omits the memory line in that case.
+#[cfg(target_os = "linux")]
+fn peak_rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    for line in status.lines() {
+        if let Some(rest) = line.strip_prefix("VmHWM:") {
+            let kb: u64 = rest.trim().trim_end_matches(" kB").trim().parse().ok()?;
+            return Some(kb * 1024);
+        }
+    }
+    None
+}
+
+#[cfg(not(target_os = "linux"))]
+fn peak_rss_bytes() -> Option<u64> {
+    None
+}
@@ -3,7 +3,17 @@
 //! Analyzes aggregated audit logs to provide insights into:
 //! - Client-to-Vault traffic patterns (top clients, request volumes)
 //! - Request distribution analysis (temporal patterns, operation types)
-//! - Client behavior clustering (automated vs interactive patterns)
+//! - Client behavior clustering: k-means over a standardized feature vector
+//!   (request volume, path diversity, error rate, operation entropy,
+//!   temporal spread), labeled by centroid profile (see [`cluster_clients`])
+//! - Per-client request-rate bursts over a `--bucket-interval` time-series,
+//!   flagged with a modified z-score (see [`detect_bursts`])
+//! - An optional `--serve <addr>` read-only JSON API over the same
+//!   aggregates, for a dashboard or script to query live (see [`serve_blocking`])
+//! - A streaming `--format ndjson` export (and `--error-details-format
+//!   ndjson --error-details-unsorted`) that writes one record per line as
+//!   it's produced, for summary/error exports too large to buffer in memory
+//!   (see [`export_ndjson`])
 //!
 //! # Usage
 //!
@@ -14,21 +24,50 @@
 //! # Export detailed metrics to CSV
 //! vault-audit client-traffic-analysis audit*.log --output traffic.csv --format csv
 //!
+//! # Export per-client/mount/error-type gauges as a Prometheus text file
+//! vault-audit client-traffic-analysis audit*.log --output traffic.prom --format prometheus
+//!
 //! # Analyze compressed logs
 //! vault-audit client-traffic-analysis logs/*.log.gz
+//!
+//! # Flag per-client request bursts over 15-minute windows and export them
+//! vault-audit client-traffic-analysis audit*.log --bucket-interval 15m --burst-output bursts.csv
+//!
+//! # Feed the same aggregates into a Prometheus textfile
+//! vault-audit client-traffic-analysis audit*.log --metrics-file traffic.prom
+//!
+//! # Cluster clients into 6 behavior groups instead of the default 4
+//! vault-audit client-traffic-analysis audit*.log --clusters 6
+//!
+//! # Rank --show-errors/--show-details output by bucketed failure ratio
+//! # instead of raw request count, surfacing low-volume clients that fail
+//! # almost every request
+//! vault-audit client-traffic-analysis audit*.log --show-errors --rank-by failure-ratio
+//!
+//! # Serve the computed stats as read-only JSON instead of printing a report
+//! vault-audit client-traffic-analysis audit*.log --serve 0.0.0.0:8089
+//!
+//! # Stream a multi-gigabyte summary export as newline-delimited JSON
+//! # instead of buffering every client in memory
+//! vault-audit client-traffic-analysis audit*.log --output traffic.ndjson --format ndjson
+//!
+//! # Stream detailed error records as unsorted NDJSON, keeping memory flat
+//! # by skipping the most-recent-first timestamp sort
+//! vault-audit client-traffic-analysis audit*.log --error-details-output errors.ndjson \
+//!   --error-details-format ndjson --error-details-unsorted
 //! ```
 
 use crate::audit::types::AuditEntry;
 use crate::utils::format::format_number;
+use crate::utils::metrics::MetricsExporter;
 use crate::utils::parallel::process_files_parallel;
-use crate::utils::progress::ProgressBar;
+use crate::utils::time::{format_timestamp, parse_duration};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Timelike, Utc};
 use serde::Serialize;
-use std::collections::HashMap;
-use std::io::{BufRead, BufReader};
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::{Arc, Mutex, OnceLock};
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
 
 /// Detailed error instance linking entity, error type, and path
 #[derive(Debug, Clone)]
@@ -67,6 +106,21 @@ struct ClientStats {
     error_instances: Vec<ErrorInstance>,
     /// Requests by hour of day (0-23)
     hourly_distribution: HashMap<u32, usize>,
+    /// Request counts keyed by fixed-width `--bucket-interval` bucket index
+    /// (`timestamp / bucket_seconds`), the same bucket-key convention
+    /// [`crate::commands::anomaly_detect`] uses - fed into [`detect_bursts`]
+    /// to flag per-client rate spikes.
+    bucket_counts: BTreeMap<i64, usize>,
+}
+
+/// A single flagged burst window, for the `print_burst_analysis` report and
+/// the `--burst-output` CSV export.
+#[derive(Debug, Clone, Serialize)]
+struct BurstWindow {
+    client_ip: String,
+    window_start: String,
+    count: usize,
+    score: f64,
 }
 
 /// Export structure for client metrics
@@ -113,11 +167,13 @@ impl ClientStats {
             error_paths: HashMap::new(),
             error_instances: Vec::new(),
             hourly_distribution: HashMap::new(),
+            bucket_counts: BTreeMap::new(),
         }
     }
 
-    /// Update stats with a new entry
-    fn update(&mut self, entry: &AuditEntry) {
+    /// Update stats with a new entry. `bucket_seconds` sizes the time-series
+    /// buckets fed into burst detection.
+    fn update(&mut self, entry: &AuditEntry, bucket_seconds: i64) {
         self.request_count += 1;
 
         // Track operation type
@@ -204,10 +260,13 @@ impl ClientStats {
             });
         }
 
-        // Track hourly distribution
+        // Track hourly distribution and the burst-detection time-series
         if let Ok(dt) = entry.time.parse::<DateTime<Utc>>() {
             let hour = dt.hour();
             *self.hourly_distribution.entry(hour).or_insert(0) += 1;
+
+            let bucket = dt.timestamp() / bucket_seconds;
+            *self.bucket_counts.entry(bucket).or_insert(0) += 1;
         }
     }
 
@@ -254,6 +313,11 @@ impl ClientStats {
             *self.hourly_distribution.entry(hour).or_insert(0) += count;
         }
 
+        // Merge burst-detection time-series buckets
+        for (bucket, count) in other.bucket_counts {
+            *self.bucket_counts.entry(bucket).or_insert(0) += count;
+        }
+
         // Update timestamps
         if self.first_seen.is_none()
             || (other.first_seen.is_some() && other.first_seen < self.first_seen)
@@ -267,18 +331,10 @@ impl ClientStats {
         }
     }
 
-    /// Classify client behavior
-    fn classify_behavior(&self) -> String {
-        let paths_per_request = self.paths.len() as f64 / self.request_count as f64;
-        if self.request_count > 1000 || paths_per_request < 0.1 {
-            "automated".to_string()
-        } else {
-            "interactive".to_string()
-        }
-    }
-
-    /// Convert to export format
-    fn to_export(&self, client_ip: String) -> ClientExport {
+    /// Convert to export format. `classification` is this client's
+    /// [`cluster_clients`] label, resolved by the caller since it depends on
+    /// every client's feature vector, not just this one.
+    fn to_export(&self, client_ip: String, classification: String) -> ClientExport {
         let error_rate = if self.request_count > 0 {
             (self.error_count as f64 / self.request_count as f64) * 100.0
         } else {
@@ -364,19 +420,11 @@ impl ClientStats {
             third_error_type_count,
             top_error_path,
             top_error_path_count,
-            classification: self.classify_behavior(),
+            classification,
         }
     }
 }
 
-/// Global progress tracking for parallel processing
-static PARALLEL_PROGRESS: OnceLock<(Arc<AtomicUsize>, Arc<Mutex<ProgressBar>>)> = OnceLock::new();
-
-/// Initialize parallel progress tracking (called by parallel processor)
-pub fn init_parallel_progress(processed: Arc<AtomicUsize>, progress: Arc<Mutex<ProgressBar>>) {
-    let _ = PARALLEL_PROGRESS.set((processed, progress));
-}
-
 /// Overall traffic statistics
 #[derive(Debug)]
 struct TrafficStats {
@@ -406,29 +454,36 @@ impl TrafficStats {
     }
 }
 
-/// Process a single file and extract client traffic stats
-fn process_file(file_path: &str) -> Result<TrafficStats> {
+/// Report progress every this many lines, batching the bytes consumed since
+/// the last report rather than calling `inc` per line.
+const PROGRESS_REPORT_LINES: usize = 1000;
+
+/// Process a single file and extract client traffic stats. `bucket_seconds`
+/// sizes the time-series buckets fed into burst detection.
+fn process_file(
+    file_path: &str,
+    progress: &dyn crate::utils::progress::Progress,
+    bucket_seconds: i64,
+) -> Result<(TrafficStats, crate::utils::parallel::FileMetrics)> {
     let file = crate::utils::reader::open_file(file_path)?;
     let reader = BufReader::new(file);
 
     let mut stats = TrafficStats::new();
-    let mut lines_processed = 0usize;
-
-    // Check if we're in parallel mode with progress tracking
-    let parallel_progress = PARALLEL_PROGRESS.get();
+    let mut file_metrics = crate::utils::parallel::FileMetrics {
+        bytes_read: std::fs::metadata(file_path).map(|m| m.len()).unwrap_or(0),
+        ..Default::default()
+    };
+    let mut bytes_since_report: usize = 0;
+    let mut lines_since_report: usize = 0;
 
     for line_result in reader.lines() {
         let line = line_result?;
-        lines_processed += 1;
-
-        // Update progress every 1000 lines to reduce contention
-        if lines_processed % 1000 == 0 {
-            if let Some((processed_lines, progress)) = parallel_progress {
-                processed_lines.fetch_add(1000, Ordering::Relaxed);
-                if let Ok(progress) = progress.lock() {
-                    progress.inc(1000);
-                }
-            }
+        bytes_since_report += line.len() + 1;
+        lines_since_report += 1;
+        if lines_since_report >= PROGRESS_REPORT_LINES {
+            progress.inc(bytes_since_report);
+            bytes_since_report = 0;
+            lines_since_report = 0;
         }
 
         // Skip empty lines
@@ -436,11 +491,17 @@ fn process_file(file_path: &str) -> Result<TrafficStats> {
             continue;
         }
 
+        file_metrics.lines_read += 1;
+
         // Parse JSON entry
         let entry: AuditEntry = match serde_json::from_str(&line) {
             Ok(entry) => entry,
-            Err(_) => continue,
+            Err(_) => {
+                file_metrics.parse_failures += 1;
+                continue;
+            }
         };
+        file_metrics.entries_parsed += 1;
 
         // Only process request entries (responses are duplicates)
         if entry.entry_type != "request" {
@@ -457,23 +518,16 @@ fn process_file(file_path: &str) -> Result<TrafficStats> {
             .clients
             .entry(client_ip.to_string())
             .or_insert_with(ClientStats::new)
-            .update(&entry);
+            .update(&entry, bucket_seconds);
 
         stats.total_requests += 1;
     }
 
-    // Update progress with any remaining lines
-    let remainder = lines_processed % 1000;
-    if remainder > 0 {
-        if let Some((processed_lines, progress)) = parallel_progress {
-            processed_lines.fetch_add(remainder, Ordering::Relaxed);
-            if let Ok(progress) = progress.lock() {
-                progress.inc(remainder as u64);
-            }
-        }
+    if bytes_since_report > 0 {
+        progress.inc(bytes_since_report);
     }
 
-    Ok(stats)
+    Ok((stats, file_metrics))
 }
 
 /// Main command function
@@ -483,12 +537,23 @@ pub fn run(
     output: Option<String>,
     format: Option<&str>,
     error_details_output: Option<String>,
+    error_details_format: &str,
+    error_details_unsorted: bool,
     top_n: usize,
     show_temporal: bool,
     min_requests: usize,
     show_operations: bool,
     show_errors: bool,
     show_details: bool,
+    num_clusters: usize,
+    rank_by: &str,
+    bucket_interval: &str,
+    burst_threshold: f64,
+    burst_output: Option<String>,
+    metrics_file: Option<&str>,
+    metrics_listen: Option<&str>,
+    metrics_top: usize,
+    serve_addr: Option<&str>,
 ) -> Result<()> {
     if log_files.len() == 1 {
         eprintln!("Analyzing client traffic patterns from 1 file...");
@@ -499,15 +564,20 @@ pub fn run(
         );
     }
 
+    let bucket_seconds = parse_duration(bucket_interval)? as i64;
+
     // Process files in parallel
-    let (combined_stats, _total_lines) =
-        process_files_parallel(log_files, process_file, |results| {
+    let (combined_stats, _total_lines, _metrics) = process_files_parallel(
+        log_files,
+        |path, progress| process_file(path, progress, bucket_seconds),
+        |results| {
             let mut combined = TrafficStats::new();
             for result in results {
                 combined.merge(result.data);
             }
             combined
-        })?;
+        },
+    )?;
 
     // Filter clients by minimum request threshold
     let filtered_stats = if min_requests > 1 {
@@ -523,36 +593,76 @@ pub fn run(
         combined_stats
     };
 
+    // Cluster clients by behavior profile once, reused by the export and the
+    // cluster-analysis report
+    let clusters = cluster_clients(&filtered_stats, num_clusters);
+
     // Export summary data if requested
     if let Some(output_file) = output {
-        export_data(&filtered_stats, &output_file, format)?;
+        export_data(&filtered_stats, &output_file, format, &clusters)?;
         eprintln!("Exported summary data to {}", output_file);
     }
 
     // Export detailed error analysis with entity information if requested
     if let Some(error_output_file) = error_details_output {
-        export_error_details(&filtered_stats, &error_output_file)?;
+        export_error_details(
+            &filtered_stats,
+            &error_output_file,
+            error_details_format,
+            error_details_unsorted,
+        )?;
         eprintln!(
             "Exported detailed error analysis (with entities) to {}",
             error_output_file
         );
     }
 
+    // Flag per-client request-rate bursts over the `--bucket-interval` time-series
+    let bursts = detect_all_bursts(&filtered_stats, bucket_seconds, burst_threshold);
+
+    if let Some(burst_output_file) = burst_output {
+        export_burst_csv(&bursts, &burst_output_file)?;
+        eprintln!(
+            "Exported {} flagged burst window(s) to {}",
+            bursts.len(),
+            burst_output_file
+        );
+    }
+
+    if metrics_file.is_some() || metrics_listen.is_some() {
+        let exporter = build_metrics_exporter(&filtered_stats, metrics_top);
+        if let Some(metrics_path) = metrics_file {
+            exporter.write_textfile(metrics_path)?;
+            eprintln!("Metrics written to: {}", metrics_path);
+        }
+        if let Some(addr) = metrics_listen {
+            exporter.serve_blocking(addr)?;
+        }
+    }
+
+    // Serve the computed stats as read-only JSON until killed, skipping the
+    // one-shot report below entirely - the same "listen mode supersedes
+    // printed output" precedent `--metrics-listen` set above
+    if let Some(addr) = serve_addr {
+        return serve_blocking(&filtered_stats, &clusters, addr);
+    }
+
     // Generate report
     print_summary(&filtered_stats);
     print_top_clients(&filtered_stats, top_n);
-    print_client_behavior_analysis(&filtered_stats);
+    print_cluster_analysis(&filtered_stats, &clusters);
+    print_burst_analysis(&bursts, bucket_seconds);
 
     if show_operations {
         print_operation_breakdown(&filtered_stats, top_n.min(10));
     }
 
     if show_errors {
-        print_error_analysis(&filtered_stats, top_n.min(10));
+        print_error_analysis(&filtered_stats, top_n.min(10), rank_by);
     }
 
     if show_details {
-        print_detailed_client_analysis(&filtered_stats, top_n.min(10));
+        print_detailed_client_analysis(&filtered_stats, &clusters, top_n.min(10), rank_by);
     }
 
     if show_temporal {
@@ -607,56 +717,488 @@ fn print_top_clients(stats: &TrafficStats, top_n: usize) {
     }
 }
 
-/// Analyze and print client behavior patterns
-fn print_client_behavior_analysis(stats: &TrafficStats) {
-    println!("\n{}", "=".repeat(100));
-    println!("Client Behavior Analysis");
-    println!("{}", "=".repeat(100));
+/// Number of standardized features [`client_feature_vector`] builds per
+/// client, and the dimensionality [`cluster_clients`] runs k-means over:
+/// request volume, unique-paths-per-request, error rate, operation-type
+/// entropy, and temporal spread.
+const CLUSTER_FEATURE_COUNT: usize = 5;
 
-    // Categorize clients
-    let mut automated_clients = Vec::new();
-    let mut interactive_clients = Vec::new();
+/// Hard cap on Lloyd's-algorithm iterations in [`lloyds_algorithm`], so a
+/// dataset that never settles still terminates.
+const KMEANS_MAX_ITERATIONS: usize = 100;
 
-    for (ip, client_stats) in &stats.clients {
-        // Heuristic: Automated clients typically have higher request volumes
-        // and access fewer unique paths per request
-        let paths_per_request = client_stats.paths.len() as f64 / client_stats.request_count as f64;
+/// Total centroid movement (sum of per-feature absolute deltas, summed
+/// across centroids) below which [`lloyds_algorithm`] considers itself
+/// converged.
+const KMEANS_CONVERGENCE_EPSILON: f64 = 1e-4;
 
-        if client_stats.request_count > 1000 || paths_per_request < 0.1 {
-            automated_clients.push((ip, client_stats));
-        } else {
-            interactive_clients.push((ip, client_stats));
+/// A client's behavior cluster, produced by [`cluster_clients`].
+#[derive(Debug, Clone)]
+struct ClusterAssignment {
+    cluster_id: usize,
+    label: String,
+}
+
+/// Shannon entropy (bits) of the distribution given by a count map's values -
+/// e.g. a client's request counts per operation type. Generalizes
+/// [`crate::commands::entity_churn::shannon_entropy`]'s per-character formula
+/// to an arbitrary count distribution.
+fn shannon_entropy_over_counts(counts: &HashMap<String, usize>) -> f64 {
+    let total: usize = counts.values().sum();
+    if total == 0 {
+        return 0.0;
+    }
+    let total = total as f64;
+    counts.values().fold(0.0, |acc, &count| {
+        if count == 0 {
+            return acc;
+        }
+        let p = count as f64 / total;
+        acc - p * p.log2()
+    })
+}
+
+/// Builds a client's raw (unstandardized) feature vector for
+/// [`cluster_clients`]: request volume, unique-paths-per-request, error
+/// rate, operation-type entropy (over [`ClientStats::operations`]), and
+/// temporal spread (fraction of the 24 hourly buckets with any traffic).
+fn client_feature_vector(stats: &ClientStats) -> [f64; CLUSTER_FEATURE_COUNT] {
+    let paths_per_request = if stats.request_count > 0 {
+        stats.paths.len() as f64 / stats.request_count as f64
+    } else {
+        0.0
+    };
+    let error_rate = if stats.request_count > 0 {
+        stats.error_count as f64 / stats.request_count as f64
+    } else {
+        0.0
+    };
+    let non_empty_hours = stats.hourly_distribution.values().filter(|&&c| c > 0).count();
+
+    [
+        stats.request_count as f64,
+        paths_per_request,
+        error_rate,
+        shannon_entropy_over_counts(&stats.operations),
+        non_empty_hours as f64 / 24.0,
+    ]
+}
+
+/// Z-score standardizes each of the [`CLUSTER_FEATURE_COUNT`] feature columns
+/// across all rows in place, so no single high-magnitude feature (e.g. raw
+/// request volume) dominates Euclidean distance in [`lloyds_algorithm`]. A
+/// column with zero variance (every client identical on that feature) is
+/// left at zero rather than dividing by zero.
+fn standardize_features(vectors: &mut [[f64; CLUSTER_FEATURE_COUNT]]) {
+    if vectors.is_empty() {
+        return;
+    }
+    let n = vectors.len() as f64;
+    for col in 0..CLUSTER_FEATURE_COUNT {
+        let mean = vectors.iter().map(|v| v[col]).sum::<f64>() / n;
+        let variance = vectors.iter().map(|v| (v[col] - mean).powi(2)).sum::<f64>() / n;
+        let stddev = variance.sqrt();
+        for v in vectors.iter_mut() {
+            v[col] = if stddev > 0.0 { (v[col] - mean) / stddev } else { 0.0 };
         }
     }
+}
 
-    println!(
-        "Automated Clients (likely services): {}",
-        automated_clients.len()
-    );
-    println!(
-        "Interactive Clients (likely users): {}",
-        interactive_clients.len()
-    );
+/// Squared Euclidean distance between two standardized feature vectors.
+fn squared_distance(
+    a: &[f64; CLUSTER_FEATURE_COUNT],
+    b: &[f64; CLUSTER_FEATURE_COUNT],
+) -> f64 {
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y).powi(2)).sum()
+}
+
+/// Seeds `k` centroids with a deterministic farthest-point heuristic: the
+/// first centroid is the first row, and each subsequent centroid is the row
+/// with the greatest squared distance to its nearest already-chosen
+/// centroid. This tree has no RNG dependency to draw the randomized
+/// k-means++ distribution from, so this stands in for it - it shares
+/// k-means++'s goal of spreading initial centroids across the data instead
+/// of picking them arbitrarily, just without the randomness.
+fn seed_centroids(
+    vectors: &[[f64; CLUSTER_FEATURE_COUNT]],
+    k: usize,
+) -> Vec<[f64; CLUSTER_FEATURE_COUNT]> {
+    let mut centroids = vec![vectors[0]];
+    while centroids.len() < k && centroids.len() < vectors.len() {
+        let next = vectors
+            .iter()
+            .max_by(|a, b| {
+                let da = centroids
+                    .iter()
+                    .map(|c| squared_distance(a, c))
+                    .fold(f64::INFINITY, f64::min);
+                let db = centroids
+                    .iter()
+                    .map(|c| squared_distance(b, c))
+                    .fold(f64::INFINITY, f64::min);
+                da.partial_cmp(&db).unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .copied()
+            .unwrap_or(vectors[0]);
+        centroids.push(next);
+    }
+    centroids
+}
+
+/// Runs Lloyd's algorithm to convergence (centroid movement below
+/// [`KMEANS_CONVERGENCE_EPSILON`]) or [`KMEANS_MAX_ITERATIONS`], whichever
+/// comes first. Returns the cluster index assigned to each input row, and
+/// the final centroids in standardized feature space. A cluster that loses
+/// all its members during an iteration keeps its previous centroid rather
+/// than collapsing to the origin.
+fn lloyds_algorithm(
+    vectors: &[[f64; CLUSTER_FEATURE_COUNT]],
+    k: usize,
+) -> (Vec<usize>, Vec<[f64; CLUSTER_FEATURE_COUNT]>) {
+    let mut centroids = seed_centroids(vectors, k);
+    let mut assignments = vec![0usize; vectors.len()];
+
+    for _ in 0..KMEANS_MAX_ITERATIONS {
+        for (i, v) in vectors.iter().enumerate() {
+            assignments[i] = centroids
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| {
+                    squared_distance(v, a)
+                        .partial_cmp(&squared_distance(v, b))
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .map_or(0, |(idx, _)| idx);
+        }
+
+        let mut sums = vec![[0.0; CLUSTER_FEATURE_COUNT]; centroids.len()];
+        let mut counts = vec![0usize; centroids.len()];
+        for (v, &cluster) in vectors.iter().zip(assignments.iter()) {
+            counts[cluster] += 1;
+            for (s, x) in sums[cluster].iter_mut().zip(v.iter()) {
+                *s += x;
+            }
+        }
+
+        let mut movement = 0.0;
+        let mut new_centroids = centroids.clone();
+        for (idx, (sum, &count)) in sums.iter().zip(counts.iter()).enumerate() {
+            if count == 0 {
+                continue;
+            }
+            let mut new_centroid = [0.0; CLUSTER_FEATURE_COUNT];
+            for (n, &s) in new_centroid.iter_mut().zip(sum.iter()) {
+                *n = s / count as f64;
+            }
+            movement += centroids[idx]
+                .iter()
+                .zip(new_centroid.iter())
+                .map(|(a, b)| (a - b).abs())
+                .sum::<f64>();
+            new_centroids[idx] = new_centroid;
+        }
+        centroids = new_centroids;
+
+        if movement < KMEANS_CONVERGENCE_EPSILON {
+            break;
+        }
+    }
+
+    (assignments, centroids)
+}
+
+/// Labels a cluster from its centroid's standardized feature profile
+/// (`[request_count, paths_per_request, error_rate, operation_entropy,
+/// temporal_spread]`): an elevated error rate takes priority ("failing"),
+/// then high volume paired with low path diversity ("automated"), then high
+/// operation entropy or temporal spread ("interactive"); anything else is
+/// "mixed".
+fn label_centroid(centroid: &[f64; CLUSTER_FEATURE_COUNT]) -> String {
+    let [request_count, paths_per_request, error_rate, operation_entropy, temporal_spread] =
+        *centroid;
+
+    if error_rate > 1.0 {
+        "failing".to_string()
+    } else if request_count > 0.5 && paths_per_request < -0.5 {
+        "automated".to_string()
+    } else if operation_entropy > 0.5 || temporal_spread > 0.5 {
+        "interactive".to_string()
+    } else {
+        "mixed".to_string()
+    }
+}
+
+/// Clusters clients by behavior profile, replacing the old fixed-threshold
+/// "automated vs interactive" heuristic: standardizes each client's
+/// [`client_feature_vector`] across all clients, runs Lloyd's k-means (`k`
+/// from `--clusters`, seeded by [`seed_centroids`]), and labels each
+/// resulting cluster from its centroid via [`label_centroid`]. `k` is capped
+/// to the number of clients present. Returns an empty map if there are no
+/// clients to cluster.
+fn cluster_clients(stats: &TrafficStats, k: usize) -> HashMap<String, ClusterAssignment> {
+    let ips: Vec<&String> = stats.clients.keys().collect();
+    if ips.is_empty() {
+        return HashMap::new();
+    }
+    let k = k.clamp(1, ips.len());
+
+    let mut vectors: Vec<[f64; CLUSTER_FEATURE_COUNT]> = ips
+        .iter()
+        .map(|ip| client_feature_vector(&stats.clients[*ip]))
+        .collect();
+    standardize_features(&mut vectors);
+
+    let (assignments, centroids) = lloyds_algorithm(&vectors, k);
+    let labels: Vec<String> = centroids.iter().map(label_centroid).collect();
+
+    ips.into_iter()
+        .zip(assignments)
+        .map(|(ip, cluster_id)| {
+            (
+                ip.clone(),
+                ClusterAssignment {
+                    cluster_id,
+                    label: labels[cluster_id].clone(),
+                },
+            )
+        })
+        .collect()
+}
+
+/// Print client behavior clusters from [`cluster_clients`], grouped by
+/// cluster (largest first) with each cluster's label and top clients by
+/// request volume.
+fn print_cluster_analysis(stats: &TrafficStats, clusters: &HashMap<String, ClusterAssignment>) {
+    println!("\n{}", "=".repeat(100));
+    println!("Client Behavior Clusters");
+    println!("{}", "=".repeat(100));
 
-    // Show top automated clients
-    if !automated_clients.is_empty() {
-        println!("\nTop Automated Clients:");
+    let mut by_cluster: HashMap<usize, Vec<&String>> = HashMap::new();
+    for (ip, assignment) in clusters {
+        by_cluster.entry(assignment.cluster_id).or_default().push(ip);
+    }
+
+    let mut cluster_ids: Vec<usize> = by_cluster.keys().copied().collect();
+    cluster_ids.sort_by_key(|id| std::cmp::Reverse(by_cluster[id].len()));
+
+    for cluster_id in cluster_ids {
+        let members = &by_cluster[&cluster_id];
+        let label = clusters
+            .values()
+            .find(|a| a.cluster_id == cluster_id)
+            .map_or("unknown", |a| a.label.as_str());
+
+        println!(
+            "\nCluster {} ({}): {} client(s)",
+            cluster_id,
+            label,
+            members.len()
+        );
         println!(
             "{:<20} {:>15} {:>15}",
             "Client IP", "Requests", "Unique Paths"
         );
         println!("{}", "-".repeat(60));
 
-        automated_clients.sort_by(|a, b| b.1.request_count.cmp(&a.1.request_count));
-        for (ip, stats) in automated_clients.iter().take(10) {
+        let mut sorted_members: Vec<_> = members
+            .iter()
+            .map(|ip| (*ip, &stats.clients[*ip]))
+            .collect();
+        sorted_members.sort_by(|a, b| b.1.request_count.cmp(&a.1.request_count));
+
+        for (ip, client_stats) in sorted_members.iter().take(10) {
             println!(
                 "{:<20} {:>15} {:>15}",
                 ip,
-                format_number(stats.request_count),
-                format_number(stats.paths.len())
+                format_number(client_stats.request_count),
+                format_number(client_stats.paths.len())
+            );
+        }
+    }
+}
+
+/// Median of a slice of values, sorting it in place. Returns 0.0 for an empty slice.
+fn median(values: &mut [f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Flag a client's anomalously busy `--bucket-interval` windows, rather than
+/// a fixed-count threshold. Builds the ordered vector of per-bucket request
+/// counts (including zero-count gaps, so a client that goes quiet then spikes
+/// isn't scored against a baseline that silently skipped the quiet windows),
+/// then computes a modified z-score `0.6745 * (x - median) / (MAD * 1.4826)`
+/// (Iglewicz & Hoaglin) for every bucket - the same formula
+/// [`crate::commands::token_analysis::display_abuse_mad`] uses for per-entity
+/// lookup rates. When at least half the buckets share the median count
+/// (`MAD == 0`, typical of a mostly-quiet client) this falls back to the mean
+/// absolute deviation; if that is also zero (every bucket identical) there is
+/// no variation to score and nothing is flagged.
+fn detect_bursts(
+    client_ip: &str,
+    bucket_counts: &BTreeMap<i64, usize>,
+    bucket_seconds: i64,
+    threshold: f64,
+) -> Vec<BurstWindow> {
+    let (Some(&min_bucket), Some(&max_bucket)) =
+        (bucket_counts.keys().next(), bucket_counts.keys().next_back())
+    else {
+        return Vec::new();
+    };
+
+    let counts: Vec<f64> = (min_bucket..=max_bucket)
+        .map(|bucket| *bucket_counts.get(&bucket).unwrap_or(&0) as f64)
+        .collect();
+
+    let mut sorted = counts.clone();
+    let center = median(&mut sorted);
+
+    let mut abs_deviations: Vec<f64> = counts.iter().map(|x| (x - center).abs()).collect();
+    let mut scale = median(&mut abs_deviations) * 1.4826;
+
+    if scale == 0.0 {
+        let mean = counts.iter().sum::<f64>() / counts.len() as f64;
+        scale = counts.iter().map(|x| (x - mean).abs()).sum::<f64>() / counts.len() as f64;
+    }
+
+    if scale == 0.0 {
+        return Vec::new();
+    }
+
+    (min_bucket..=max_bucket)
+        .zip(counts.iter())
+        .filter_map(|(bucket, &count)| {
+            let score = 0.6745 * (count - center) / scale;
+            if score.abs() < threshold {
+                return None;
+            }
+            let window_start = DateTime::from_timestamp(bucket * bucket_seconds, 0)
+                .map(|dt| format_timestamp(&dt))
+                .unwrap_or_default();
+            Some(BurstWindow {
+                client_ip: client_ip.to_string(),
+                window_start,
+                count: count as usize,
+                score,
+            })
+        })
+        .collect()
+}
+
+/// Runs [`detect_bursts`] over every client and sorts the combined flagged
+/// windows by descending score magnitude, so the most severe spikes lead the
+/// report and the CSV export.
+fn detect_all_bursts(
+    stats: &TrafficStats,
+    bucket_seconds: i64,
+    threshold: f64,
+) -> Vec<BurstWindow> {
+    let mut bursts: Vec<BurstWindow> = stats
+        .clients
+        .iter()
+        .flat_map(|(ip, client_stats)| {
+            detect_bursts(ip, &client_stats.bucket_counts, bucket_seconds, threshold)
+        })
+        .collect();
+
+    bursts.sort_by(|a, b| {
+        b.score
+            .abs()
+            .partial_cmp(&a.score.abs())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    bursts
+}
+
+/// Print flagged burst windows. Cross-reference a client's `error_instances`
+/// (via `--show-details`/`--error-details-output`) to tie a spike back to the
+/// entity or path that drove it.
+fn print_burst_analysis(bursts: &[BurstWindow], bucket_seconds: i64) {
+    println!("\n{}", "=".repeat(100));
+    println!(
+        "Request-Rate Bursts ({}s windows, modified z-score)",
+        bucket_seconds
+    );
+    println!("{}", "=".repeat(100));
+
+    if bursts.is_empty() {
+        println!("No anomalous request-rate bursts detected.");
+        return;
+    }
+
+    println!(
+        "{:<20} {:<22} {:>10} {:>10}",
+        "Client IP", "Window Start", "Count", "Score"
+    );
+    println!("{}", "-".repeat(80));
+
+    for burst in bursts {
+        println!(
+            "{:<20} {:<22} {:>10} {:>10.2}",
+            burst.client_ip, burst.window_start, burst.count, burst.score
+        );
+    }
+}
+
+/// Renders this run's per-client aggregates as Prometheus metrics: a
+/// `vault_client_requests_total` counter and `vault_client_unique_entities`/
+/// `vault_client_error_rate` gauges per client, plus a
+/// `vault_client_errors_total{client_ip,error_type}` counter per client/error
+/// pair, each sorted descending by request count and capped at `metrics_top`
+/// to bound cardinality.
+fn build_metrics_exporter(stats: &TrafficStats, metrics_top: usize) -> MetricsExporter {
+    let mut exporter = MetricsExporter::new();
+
+    let mut clients: Vec<_> = stats.clients.iter().collect();
+    clients.sort_by(|a, b| b.1.request_count.cmp(&a.1.request_count));
+
+    for (ip, client_stats) in clients.iter().take(metrics_top) {
+        exporter.counter(
+            "vault_client_requests_total",
+            "Total requests from a single client",
+            &[("client_ip", ip.as_str())],
+            client_stats.request_count as f64,
+        );
+        exporter.gauge(
+            "vault_client_unique_entities",
+            "Unique entities that have made requests from a single client",
+            &[("client_ip", ip.as_str())],
+            client_stats.entities.len() as f64,
+        );
+        let error_rate = if client_stats.request_count > 0 {
+            client_stats.error_count as f64 / client_stats.request_count as f64
+        } else {
+            0.0
+        };
+        exporter.gauge(
+            "vault_client_error_rate",
+            "Fraction of a single client's requests that errored",
+            &[("client_ip", ip.as_str())],
+            error_rate,
+        );
+
+        let mut error_types: Vec<_> = client_stats.error_types.iter().collect();
+        error_types.sort_by(|a, b| b.1.cmp(a.1));
+        for (error_type, count) in error_types {
+            exporter.counter(
+                "vault_client_errors_total",
+                "Total errors from a single client, per error type",
+                &[("client_ip", ip.as_str()), ("error_type", error_type)],
+                *count as f64,
             );
         }
     }
+
+    exporter
 }
 
 /// Print operation type breakdown for top clients
@@ -694,8 +1236,52 @@ fn print_operation_breakdown(stats: &TrafficStats, top_n: usize) {
     }
 }
 
+/// Quantizes a client's failure ratio (`error_count / request_count`) into
+/// 5%-wide buckets (`floor(ratio * 20)`, clamped to 0..=20) for
+/// `--rank-by failure-ratio` sorting. Returns `None` for a client with zero
+/// requests, since the ratio is undefined there.
+fn failure_ratio_bucket(client_stats: &ClientStats) -> Option<usize> {
+    if client_stats.request_count == 0 {
+        return None;
+    }
+    let ratio = client_stats.error_count as f64 / client_stats.request_count as f64;
+    Some((ratio * 20.0).floor().clamp(0.0, 20.0) as usize)
+}
+
+/// Human-readable label for a [`failure_ratio_bucket`] index, e.g. bucket 15
+/// (75-80% failure ratio) -> "75-80%".
+fn failure_ratio_bucket_label(bucket: usize) -> String {
+    if bucket >= 20 {
+        "100%".to_string()
+    } else {
+        format!("{}-{}%", bucket * 5, (bucket + 1) * 5)
+    }
+}
+
+/// Sorts `clients` in place per `rank_by`: `"failure-ratio"` ranks by
+/// [`failure_ratio_bucket`] descending, then `request_count` descending
+/// within a bucket, so a client failing 81% of 50k requests outranks one
+/// failing 82% of 100 requests (both in the 80-85% bucket), while one failing
+/// 100% of 3 requests outranks both; clients with zero requests are dropped since
+/// their ratio is undefined. Any other value (including the default,
+/// `"requests"`) ranks by raw `request_count` descending.
+fn sort_clients_by_rank<'a>(clients: &mut Vec<(&'a String, &'a ClientStats)>, rank_by: &str) {
+    if rank_by == "failure-ratio" {
+        clients.retain(|(_, client)| failure_ratio_bucket(client).is_some());
+        clients.sort_by(|a, b| {
+            let bucket_a = failure_ratio_bucket(a.1).unwrap_or(0);
+            let bucket_b = failure_ratio_bucket(b.1).unwrap_or(0);
+            bucket_b
+                .cmp(&bucket_a)
+                .then_with(|| b.1.request_count.cmp(&a.1.request_count))
+        });
+    } else {
+        clients.sort_by(|a, b| b.1.request_count.cmp(&a.1.request_count));
+    }
+}
+
 /// Print error analysis for clients with significant errors
-fn print_error_analysis(stats: &TrafficStats, top_n: usize) {
+fn print_error_analysis(stats: &TrafficStats, top_n: usize, rank_by: &str) {
     println!("\n{}", "=".repeat(100));
     println!("Error Analysis - Clients with Errors");
     println!("{}", "=".repeat(100));
@@ -706,29 +1292,52 @@ fn print_error_analysis(stats: &TrafficStats, top_n: usize) {
         .filter(|(_, client)| client.error_count > 0)
         .collect();
 
-    clients_with_errors.sort_by(|a, b| b.1.error_count.cmp(&a.1.error_count));
+    sort_clients_by_rank(&mut clients_with_errors, rank_by);
 
     if clients_with_errors.is_empty() {
         println!("No errors detected in the analyzed logs.");
         return;
     }
 
-    println!(
-        "{:<20} {:>15} {:>15} {:>15}",
-        "Client IP", "Total Requests", "Errors", "Error Rate"
-    );
-    println!("{}", "-".repeat(80));
+    if rank_by == "failure-ratio" {
+        println!(
+            "{:<20} {:>15} {:>15} {:>15} {:>18}",
+            "Client IP", "Total Requests", "Errors", "Error Rate", "Failure Bucket"
+        );
+        println!("{}", "-".repeat(98));
 
-    for (ip, client_stats) in clients_with_errors.iter().take(top_n) {
-        let error_rate =
-            (client_stats.error_count as f64 / client_stats.request_count as f64) * 100.0;
+        for (ip, client_stats) in clients_with_errors.iter().take(top_n) {
+            let error_rate =
+                (client_stats.error_count as f64 / client_stats.request_count as f64) * 100.0;
+            let bucket_label = failure_ratio_bucket(client_stats)
+                .map_or_else(String::new, failure_ratio_bucket_label);
+            println!(
+                "{:<20} {:>15} {:>15} {:>14.2}% {:>18}",
+                ip,
+                format_number(client_stats.request_count),
+                format_number(client_stats.error_count),
+                error_rate,
+                bucket_label
+            );
+        }
+    } else {
         println!(
-            "{:<20} {:>15} {:>15} {:>14.2}%",
-            ip,
-            format_number(client_stats.request_count),
-            format_number(client_stats.error_count),
-            error_rate
+            "{:<20} {:>15} {:>15} {:>15}",
+            "Client IP", "Total Requests", "Errors", "Error Rate"
         );
+        println!("{}", "-".repeat(80));
+
+        for (ip, client_stats) in clients_with_errors.iter().take(top_n) {
+            let error_rate =
+                (client_stats.error_count as f64 / client_stats.request_count as f64) * 100.0;
+            println!(
+                "{:<20} {:>15} {:>15} {:>14.2}%",
+                ip,
+                format_number(client_stats.request_count),
+                format_number(client_stats.error_count),
+                error_rate
+            );
+        }
     }
 
     // Print detailed error type breakdown
@@ -827,13 +1436,18 @@ fn print_error_analysis(stats: &TrafficStats, top_n: usize) {
 }
 
 /// Print detailed per-client analysis
-fn print_detailed_client_analysis(stats: &TrafficStats, top_n: usize) {
+fn print_detailed_client_analysis(
+    stats: &TrafficStats,
+    clusters: &HashMap<String, ClusterAssignment>,
+    top_n: usize,
+    rank_by: &str,
+) {
     println!("\n{}", "=".repeat(100));
     println!("Detailed Client Analysis - Top {} Clients", top_n);
     println!("{}", "=".repeat(100));
 
     let mut clients: Vec<_> = stats.clients.iter().collect();
-    clients.sort_by(|a, b| b.1.request_count.cmp(&a.1.request_count));
+    sort_clients_by_rank(&mut clients, rank_by);
 
     for (ip, client_stats) in clients.iter().take(top_n) {
         println!("\n{}", "=".repeat(100));
@@ -853,7 +1467,15 @@ fn print_detailed_client_analysis(stats: &TrafficStats, top_n: usize) {
             format_number(client_stats.mount_points.len())
         );
         println!("Error Count: {}", format_number(client_stats.error_count));
-        println!("Classification: {}", client_stats.classify_behavior());
+        if rank_by == "failure-ratio" {
+            if let Some(bucket) = failure_ratio_bucket(client_stats) {
+                println!("Failure Bucket: {}", failure_ratio_bucket_label(bucket));
+            }
+        }
+        println!(
+            "Classification: {}",
+            clusters.get(*ip).map_or("unknown", |a| a.label.as_str())
+        );
         println!(
             "First Seen: {}",
             client_stats.first_seen.as_deref().unwrap_or("unknown")
@@ -942,15 +1564,269 @@ fn print_temporal_analysis(stats: &TrafficStats, top_n: usize) {
     }
 }
 
-/// Export data to CSV or JSON
-fn export_data(stats: &TrafficStats, output_file: &str, format: Option<&str>) -> Result<()> {
+/// JSON response body for `GET /clients/{ip}` - the same breakdown
+/// [`print_detailed_client_analysis`] prints for one client, as structured
+/// data instead of a table.
+#[derive(Debug, Serialize)]
+struct ClientDetail {
+    client_ip: String,
+    classification: String,
+    total_requests: usize,
+    unique_entities: usize,
+    error_count: usize,
+    error_rate: f64,
+    first_seen: String,
+    last_seen: String,
+    paths: Vec<(String, usize)>,
+    mount_points: Vec<(String, usize)>,
+    entities: Vec<(String, String)>,
+    hourly_distribution: Vec<(u32, usize)>,
+}
+
+fn client_detail(ip: &str, client_stats: &ClientStats, classification: String) -> ClientDetail {
+    let error_rate = if client_stats.request_count > 0 {
+        (client_stats.error_count as f64 / client_stats.request_count as f64) * 100.0
+    } else {
+        0.0
+    };
+
+    let mut paths: Vec<(String, usize)> = client_stats
+        .paths
+        .iter()
+        .map(|(p, &c)| (p.clone(), c))
+        .collect();
+    paths.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let mut mount_points: Vec<(String, usize)> = client_stats
+        .mount_points
+        .iter()
+        .map(|(m, &c)| (m.clone(), c))
+        .collect();
+    mount_points.sort_by(|a, b| b.1.cmp(&a.1));
+
+    let entities: Vec<(String, String)> = client_stats
+        .entities
+        .iter()
+        .map(|(id, name)| (id.clone(), name.clone()))
+        .collect();
+
+    let mut hourly_distribution: Vec<(u32, usize)> = client_stats
+        .hourly_distribution
+        .iter()
+        .map(|(&h, &c)| (h, c))
+        .collect();
+    hourly_distribution.sort_by_key(|(hour, _)| *hour);
+
+    ClientDetail {
+        client_ip: ip.to_string(),
+        classification,
+        total_requests: client_stats.request_count,
+        unique_entities: client_stats.entities.len(),
+        error_count: client_stats.error_count,
+        error_rate,
+        first_seen: client_stats
+            .first_seen
+            .clone()
+            .unwrap_or_else(|| "unknown".to_string()),
+        last_seen: client_stats
+            .last_seen
+            .clone()
+            .unwrap_or_else(|| "unknown".to_string()),
+        paths,
+        mount_points,
+        entities,
+        hourly_distribution,
+    }
+}
+
+/// JSON response body for one row of `GET /errors` - mirrors the "Overall
+/// Error Type Distribution" table in [`print_error_analysis`].
+#[derive(Debug, Serialize)]
+struct ErrorTypeSummary {
+    error_type: String,
+    count: usize,
+    percentage: f64,
+}
+
+fn error_type_distribution(stats: &TrafficStats) -> Vec<ErrorTypeSummary> {
+    let mut overall_errors: HashMap<String, usize> = HashMap::new();
+    let mut total_errors = 0usize;
+
+    for client_stats in stats.clients.values() {
+        for (error_type, count) in &client_stats.error_types {
+            *overall_errors.entry(error_type.clone()).or_insert(0) += count;
+            total_errors += count;
+        }
+    }
+
+    let mut summary: Vec<ErrorTypeSummary> = overall_errors
+        .into_iter()
+        .map(|(error_type, count)| {
+            let percentage = if total_errors > 0 {
+                (count as f64 / total_errors as f64) * 100.0
+            } else {
+                0.0
+            };
+            ErrorTypeSummary {
+                error_type,
+                count,
+                percentage,
+            }
+        })
+        .collect();
+    summary.sort_by(|a, b| b.count.cmp(&a.count));
+    summary
+}
+
+/// Returns the value of `key` from a raw (already percent-undecoded) HTTP
+/// query string like `top_n=10&sort=requests`.
+fn query_param<'a>(query: &'a str, key: &str) -> Option<&'a str> {
+    query.split('&').find_map(|pair| {
+        let (k, v) = pair.split_once('=')?;
+        (k == key).then_some(v)
+    })
+}
+
+fn json_response(status: u16, status_text: &str, body: &impl Serialize) -> String {
+    let rendered = serde_json::to_string(body).unwrap_or_else(|_| "{}".to_string());
+    format!(
+        "HTTP/1.1 {status} {status_text}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        rendered.len(),
+        rendered,
+    )
+}
+
+/// Serves `stats`/`clusters` as read-only JSON over HTTP at `addr` until the
+/// process is killed, for `--serve`. A blocking, hand-rolled responder in
+/// the same spirit as [`crate::utils::metrics::MetricsExporter::serve_blocking`]
+/// and [`crate::commands::serve`]'s routes, just GET-only since there's no
+/// streaming ingest here:
+///
+/// - `GET /clients?top_n=N&sort=requests|failure-ratio` - paged [`ClientExport`] list
+/// - `GET /clients/{ip}` - one client's full breakdown ([`ClientDetail`])
+/// - `GET /errors` - overall error-type distribution ([`ErrorTypeSummary`])
+/// - `GET /errors/details?top_n=N` - per-instance error records ([`DetailedErrorExport`])
+fn serve_blocking(
+    stats: &TrafficStats,
+    clusters: &HashMap<String, ClusterAssignment>,
+    addr: &str,
+) -> Result<()> {
+    let listener = TcpListener::bind(addr)
+        .with_context(|| format!("Failed to bind serve listener on {addr}"))?;
+    eprintln!(
+        "Serving client traffic analysis on http://{addr} (GET /clients, /clients/{{ip}}, /errors, /errors/details)"
+    );
+
+    for stream in listener.incoming() {
+        let Ok(mut stream) = stream else { continue };
+        let mut request = [0u8; 8192];
+        let n = stream.read(&mut request).unwrap_or(0);
+        let head = String::from_utf8_lossy(&request[..n]);
+        let request_line = head.lines().next().unwrap_or_default();
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next().unwrap_or_default();
+        let target = parts.next().unwrap_or_default();
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+        let response = if method != "GET" {
+            json_response(
+                405,
+                "Method Not Allowed",
+                &serde_json::json!({ "error": "method not allowed" }),
+            )
+        } else if path == "/clients" {
+            let top_n = query_param(query, "top_n")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(usize::MAX);
+            let sort = query_param(query, "sort").unwrap_or("requests");
+
+            let mut exports: Vec<ClientExport> = stats
+                .clients
+                .iter()
+                .map(|(ip, client_stats)| {
+                    let classification = clusters
+                        .get(ip)
+                        .map_or_else(|| "unknown".to_string(), |a| a.label.clone());
+                    client_stats.to_export(ip.clone(), classification)
+                })
+                .collect();
+            match sort {
+                "failure-ratio" => exports.sort_by(|a, b| {
+                    b.error_rate
+                        .partial_cmp(&a.error_rate)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                }),
+                _ => exports.sort_by(|a, b| b.total_requests.cmp(&a.total_requests)),
+            }
+            exports.truncate(top_n);
+
+            json_response(200, "OK", &exports)
+        } else if let Some(ip) = path.strip_prefix("/clients/") {
+            match stats.clients.get(ip) {
+                Some(client_stats) => {
+                    let classification = clusters
+                        .get(ip)
+                        .map_or_else(|| "unknown".to_string(), |a| a.label.clone());
+                    json_response(200, "OK", &client_detail(ip, client_stats, classification))
+                }
+                None => json_response(
+                    404,
+                    "Not Found",
+                    &serde_json::json!({ "error": "client not found" }),
+                ),
+            }
+        } else if path == "/errors" {
+            json_response(200, "OK", &error_type_distribution(stats))
+        } else if path == "/errors/details" {
+            let top_n = query_param(query, "top_n").and_then(|v| v.parse().ok());
+            let mut details = collect_detailed_errors(stats);
+            if let Some(n) = top_n {
+                details.truncate(n);
+            }
+            json_response(200, "OK", &details)
+        } else {
+            json_response(
+                404,
+                "Not Found",
+                &serde_json::json!({ "error": "not found" }),
+            )
+        };
+
+        if let Err(e) = stream.write_all(response.as_bytes()) {
+            eprintln!("Warning: failed to write serve response: {e}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Export data to CSV, JSON, NDJSON, or Prometheus text exposition format
+fn export_data(
+    stats: &TrafficStats,
+    output_file: &str,
+    format: Option<&str>,
+    clusters: &HashMap<String, ClusterAssignment>,
+) -> Result<()> {
     let format = format.unwrap_or("csv");
 
+    // "ndjson" streams rows straight out of `stats.clients` as they're built,
+    // so it skips the collect-and-sort-by-request-count step the other
+    // formats rely on to keep memory flat on multi-gigabyte logs - rows land
+    // in hashmap iteration order rather than sorted.
+    if format == "ndjson" {
+        return export_ndjson(stats, clusters, output_file);
+    }
+
     // Convert stats to export format
     let mut exports: Vec<ClientExport> = stats
         .clients
         .iter()
-        .map(|(ip, stats)| stats.to_export(ip.clone()))
+        .map(|(ip, stats)| {
+            let classification = clusters
+                .get(ip)
+                .map_or_else(|| "unknown".to_string(), |a| a.label.clone());
+            stats.to_export(ip.clone(), classification)
+        })
         .collect();
 
     // Sort by request count descending
@@ -959,10 +1835,104 @@ fn export_data(stats: &TrafficStats, output_file: &str, format: Option<&str>) ->
     match format {
         "csv" => export_csv(&exports, output_file),
         "json" => export_json(&exports, output_file),
+        "prometheus" => export_prometheus(stats, output_file),
         _ => Err(anyhow::anyhow!("Unsupported format: {}", format)),
     }
 }
 
+/// Streams one `ClientExport` per line directly to a buffered writer as it's
+/// built from `stats.clients`, instead of buffering the whole `Vec` like
+/// [`export_json`] does - memory stays flat regardless of client count.
+fn export_ndjson(
+    stats: &TrafficStats,
+    clusters: &HashMap<String, ClusterAssignment>,
+    output_file: &str,
+) -> Result<()> {
+    let file = std::fs::File::create(output_file)
+        .context(format!("Failed to create output file: {}", output_file))?;
+    let mut writer = std::io::BufWriter::new(file);
+
+    for (ip, client_stats) in &stats.clients {
+        let classification = clusters
+            .get(ip)
+            .map_or_else(|| "unknown".to_string(), |a| a.label.clone());
+        let export = client_stats.to_export(ip.clone(), classification);
+        serde_json::to_writer(&mut writer, &export)?;
+        writer.write_all(b"\n")?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Renders `stats` as a Prometheus text-exposition-format export, for
+/// `--format prometheus`. Unlike [`export_csv`]/[`export_json`], this reads
+/// `TrafficStats` directly instead of the summarized `ClientExport` rows, so
+/// it can additionally label `vault_client_mount_requests_total` by
+/// `mount_point` and `vault_client_error_type_total` by `error_type` -
+/// detail `ClientExport` collapses to top-N summary fields. The
+/// `requests_total`/`unique_entities` families reuse [`build_metrics_exporter`]'s
+/// names (and counter-vs-gauge choices) so `--format prometheus` and
+/// `--metrics-file`/`--metrics-listen` expose the same metric for the same
+/// quantity. `vault_client_request_errors_total` is deliberately its own
+/// name rather than reusing `vault_client_errors_total`:
+/// [`build_metrics_exporter`]'s `vault_client_errors_total` is already a
+/// per-`error_type` breakdown (same shape as this function's own
+/// `vault_client_error_type_total`), while this counter is a single
+/// per-client total - scraping both under one name would mix two different
+/// cardinalities into one series. Label values are escaped by
+/// [`MetricsExporter::gauge`]/[`MetricsExporter::counter`].
+fn export_prometheus(stats: &TrafficStats, output_file: &str) -> Result<()> {
+    let mut exporter = MetricsExporter::new();
+
+    for (ip, client_stats) in &stats.clients {
+        exporter.counter(
+            "vault_client_requests_total",
+            "Total requests from a single client",
+            &[("client_ip", ip.as_str())],
+            client_stats.request_count as f64,
+        );
+        exporter.counter(
+            "vault_client_request_errors_total",
+            "Total errored requests from a single client",
+            &[("client_ip", ip.as_str())],
+            client_stats.error_count as f64,
+        );
+        exporter.gauge(
+            "vault_client_unique_entities",
+            "Unique entities that have made requests from a single client",
+            &[("client_ip", ip.as_str())],
+            client_stats.entities.len() as f64,
+        );
+
+        for (mount_point, count) in &client_stats.mount_points {
+            exporter.gauge(
+                "vault_client_mount_requests_total",
+                "Requests from a single client to a single mount point",
+                &[
+                    ("client_ip", ip.as_str()),
+                    ("mount_point", mount_point.as_str()),
+                ],
+                *count as f64,
+            );
+        }
+
+        for (error_type, count) in &client_stats.error_types {
+            exporter.gauge(
+                "vault_client_error_type_total",
+                "Errors from a single client, broken down by error type",
+                &[
+                    ("client_ip", ip.as_str()),
+                    ("error_type", error_type.as_str()),
+                ],
+                *count as f64,
+            );
+        }
+    }
+
+    exporter.write_textfile(output_file)
+}
+
 /// Export to CSV format
 fn export_csv(data: &[ClientExport], output_file: &str) -> Result<()> {
     let file = std::fs::File::create(output_file)
@@ -996,36 +1966,235 @@ struct DetailedErrorExport {
     timestamp: String,
 }
 
-/// Export detailed error analysis with entity-level granularity
-fn export_error_details(stats: &TrafficStats, output_file: &str) -> Result<()> {
+/// Flattens every client's `error_instances` into [`DetailedErrorExport`]
+/// rows, sorted by timestamp (most recent first). Shared by
+/// [`export_error_details`] (CSV) and the `GET /errors/details` route in
+/// [`serve_blocking`] (JSON).
+fn collect_detailed_errors(stats: &TrafficStats) -> Vec<DetailedErrorExport> {
+    let mut all_errors: Vec<DetailedErrorExport> = stats
+        .clients
+        .iter()
+        .flat_map(|(client_ip, client_stats)| {
+            client_stats
+                .error_instances
+                .iter()
+                .map(move |error_instance| DetailedErrorExport {
+                    client_ip: client_ip.clone(),
+                    entity_id: error_instance.entity_id.clone(),
+                    display_name: error_instance.display_name.clone(),
+                    error_type: error_instance.error_type.clone(),
+                    path: error_instance.path.clone(),
+                    timestamp: error_instance.timestamp.clone(),
+                })
+        })
+        .collect();
+
+    all_errors.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    all_errors
+}
+
+/// Export detailed error analysis with entity-level granularity.
+///
+/// `format` is `"csv"` (default) or `"ndjson"`. `unsorted` skips the
+/// most-recent-first timestamp sort [`collect_detailed_errors`] normally
+/// does - that sort requires buffering every record before the first one can
+/// be written, so `unsorted` is the only way `"ndjson"` stays flat-memory on
+/// multi-gigabyte logs; it iterates `client_stats.error_instances` directly
+/// and writes each record as it's produced.
+fn export_error_details(
+    stats: &TrafficStats,
+    output_file: &str,
+    format: &str,
+    unsorted: bool,
+) -> Result<()> {
+    if format == "ndjson" && unsorted {
+        return export_error_details_ndjson_streaming(stats, output_file);
+    }
+
+    let records: Vec<DetailedErrorExport> = collect_detailed_errors(stats);
+
+    match format {
+        "ndjson" => {
+            let file = std::fs::File::create(output_file)
+                .context(format!("Failed to create output file: {}", output_file))?;
+            let mut writer = std::io::BufWriter::new(file);
+            for record in records {
+                serde_json::to_writer(&mut writer, &record)?;
+                writer.write_all(b"\n")?;
+            }
+            writer.flush()?;
+            Ok(())
+        }
+        "csv" => {
+            let file = std::fs::File::create(output_file)
+                .context(format!("Failed to create output file: {}", output_file))?;
+            let mut writer = csv::Writer::from_writer(file);
+            for record in records {
+                writer.serialize(record)?;
+            }
+            writer.flush()?;
+            Ok(())
+        }
+        _ => Err(anyhow::anyhow!("Unsupported format: {}", format)),
+    }
+}
+
+/// Writes one `DetailedErrorExport` per line as `client_stats.error_instances`
+/// are visited, with no intermediate `Vec` and no timestamp sort - the
+/// unsorted fast path for `export_error_details`.
+fn export_error_details_ndjson_streaming(stats: &TrafficStats, output_file: &str) -> Result<()> {
     let file = std::fs::File::create(output_file)
         .context(format!("Failed to create output file: {}", output_file))?;
-    let mut writer = csv::Writer::from_writer(file);
-
-    // Collect all error instances from all clients
-    let mut all_errors = Vec::new();
+    let mut writer = std::io::BufWriter::new(file);
 
     for (client_ip, client_stats) in &stats.clients {
         for error_instance in &client_stats.error_instances {
-            all_errors.push(DetailedErrorExport {
+            let record = DetailedErrorExport {
                 client_ip: client_ip.clone(),
                 entity_id: error_instance.entity_id.clone(),
                 display_name: error_instance.display_name.clone(),
                 error_type: error_instance.error_type.clone(),
                 path: error_instance.path.clone(),
                 timestamp: error_instance.timestamp.clone(),
-            });
+            };
+            serde_json::to_writer(&mut writer, &record)?;
+            writer.write_all(b"\n")?;
         }
     }
 
-    // Sort by timestamp (most recent first)
-    all_errors.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    writer.flush()?;
+    Ok(())
+}
 
-    // Write all error records
-    for record in all_errors {
-        writer.serialize(record)?;
+/// Export flagged burst windows (client IP, window start, count, score) to CSV
+fn export_burst_csv(bursts: &[BurstWindow], output_file: &str) -> Result<()> {
+    let file = std::fs::File::create(output_file)
+        .context(format!("Failed to create output file: {}", output_file))?;
+    let mut writer = csv::Writer::from_writer(file);
+
+    for burst in bursts {
+        writer.serialize(burst)?;
     }
 
     writer.flush()?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn client_with(request_count: usize, paths: usize, errors: usize) -> ClientStats {
+        let mut stats = ClientStats::new();
+        stats.request_count = request_count;
+        for i in 0..paths {
+            stats.paths.insert(format!("kv/path{i}"), 1);
+        }
+        stats.error_count = errors;
+        stats
+    }
+
+    fn stats_with_clients(clients: Vec<(&str, ClientStats)>) -> TrafficStats {
+        let mut stats = TrafficStats::new();
+        for (ip, client) in clients {
+            stats.total_requests += client.request_count;
+            stats.clients.insert(ip.to_string(), client);
+        }
+        stats
+    }
+
+    #[test]
+    fn test_cluster_clients_empty_input() {
+        let stats = TrafficStats::new();
+        let clusters = cluster_clients(&stats, 3);
+        assert!(clusters.is_empty());
+    }
+
+    #[test]
+    fn test_cluster_clients_single_client_forms_one_cluster() {
+        let stats = stats_with_clients(vec![("1.1.1.1", client_with(100, 5, 0))]);
+        let clusters = cluster_clients(&stats, 3);
+        assert_eq!(clusters.len(), 1);
+        assert!(clusters.contains_key("1.1.1.1"));
+    }
+
+    #[test]
+    fn test_cluster_clients_all_identical_feature_vectors_one_cluster() {
+        let stats = stats_with_clients(vec![
+            ("1.1.1.1", client_with(50, 10, 1)),
+            ("2.2.2.2", client_with(50, 10, 1)),
+            ("3.3.3.3", client_with(50, 10, 1)),
+        ]);
+        let clusters = cluster_clients(&stats, 3);
+        let cluster_ids: HashSet<usize> = clusters.values().map(|c| c.cluster_id).collect();
+        // Identical clients have zero-variance features (standardized to 0),
+        // so k-means can't tell them apart - they all land in one cluster
+        // regardless of k.
+        assert_eq!(cluster_ids.len(), 1);
+    }
+
+    #[test]
+    fn test_cluster_clients_k_is_capped_to_client_count() {
+        let stats = stats_with_clients(vec![
+            ("1.1.1.1", client_with(10, 1, 0)),
+            ("2.2.2.2", client_with(2000, 1, 0)),
+        ]);
+        let clusters = cluster_clients(&stats, 10);
+        let cluster_ids: HashSet<usize> = clusters.values().map(|c| c.cluster_id).collect();
+        assert!(cluster_ids.len() <= 2);
+    }
+
+    #[test]
+    fn test_lloyds_algorithm_separates_distinct_groups() {
+        let vectors = vec![
+            [0.0, 0.0, 0.0, 0.0, 0.0],
+            [0.1, 0.0, 0.0, 0.0, 0.0],
+            [10.0, 10.0, 10.0, 10.0, 10.0],
+            [10.1, 10.0, 10.0, 10.0, 10.0],
+        ];
+        let (assignments, centroids) = lloyds_algorithm(&vectors, 2);
+        assert_eq!(assignments.len(), 4);
+        assert_eq!(centroids.len(), 2);
+        assert_eq!(assignments[0], assignments[1]);
+        assert_eq!(assignments[2], assignments[3]);
+        assert_ne!(assignments[0], assignments[2]);
+    }
+
+    #[test]
+    fn test_lloyds_algorithm_single_cluster() {
+        let vectors = vec![[1.0, 2.0, 3.0, 4.0, 5.0], [1.0, 2.0, 3.0, 4.0, 5.0]];
+        let (assignments, centroids) = lloyds_algorithm(&vectors, 1);
+        assert_eq!(assignments, vec![0, 0]);
+        assert_eq!(centroids.len(), 1);
+        assert_eq!(centroids[0], [1.0, 2.0, 3.0, 4.0, 5.0]);
+    }
+
+    #[test]
+    fn test_standardize_features_zero_variance_column_stays_zero() {
+        let mut vectors = vec![[5.0, 1.0, 0.0, 0.0, 0.0], [5.0, 2.0, 0.0, 0.0, 0.0]];
+        standardize_features(&mut vectors);
+        assert_eq!(vectors[0][0], 0.0);
+        assert_eq!(vectors[1][0], 0.0);
+    }
+
+    #[test]
+    fn test_shannon_entropy_over_counts_empty_is_zero() {
+        assert_eq!(shannon_entropy_over_counts(&HashMap::new()), 0.0);
+    }
+
+    #[test]
+    fn test_shannon_entropy_over_counts_single_category_is_zero() {
+        let mut counts = HashMap::new();
+        counts.insert("read".to_string(), 10);
+        assert_eq!(shannon_entropy_over_counts(&counts), 0.0);
+    }
+
+    #[test]
+    fn test_shannon_entropy_over_counts_even_split_is_maximal() {
+        let mut counts = HashMap::new();
+        counts.insert("read".to_string(), 5);
+        counts.insert("write".to_string(), 5);
+        assert!((shannon_entropy_over_counts(&counts) - 1.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,571 @@
+//! Declarative compliance rule engine over saved mount inventory snapshots.
+//!
+//! Evaluates user-supplied rules against previously saved
+//! [`crate::commands::kv_mounts`] / [`crate::commands::auth_mounts`]
+//! `--format json` snapshots - the same snapshot files `kv-mounts --diff`
+//! already consumes. This command never queries Vault itself; snapshot
+//! first, then evaluate, so policy checks can run offline or in CI without
+//! Vault credentials.
+//!
+//! # Rule File
+//!
+//! A rule file is JSON (there is no YAML dependency in this crate, so only
+//! JSON rule files are supported):
+//!
+//! ```json
+//! {
+//!   "rules": [
+//!     {
+//!       "name": "no-kv-v1-mounts",
+//!       "target": "kv",
+//!       "clauses": [{ "field": "version", "op": "!=", "value": "1" }]
+//!     },
+//!     {
+//!       "name": "seal-wrap-required",
+//!       "target": "auth",
+//!       "clauses": [{ "field": "seal_wrap", "op": "==", "value": true }]
+//!     },
+//!     {
+//!       "name": "approle-short-max-ttl",
+//!       "target": { "auth_type": "approle" },
+//!       "severity": "warning",
+//!       "clauses": [{ "field": "max_lease_ttl", "op": "<", "value": 86400 }]
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! `severity` (`error`, `warning`, or `note`; defaults to `error`) only
+//! affects `--format sarif` output - it has no bearing on csv/json/stdout,
+//! which report plain `PASS`/`FAIL`/`SKIP` status instead.
+//!
+//! `target` is either a mount class (`"kv"` or `"auth"`) or an object
+//! naming a specific `{"mount_type": "..."}` / `{"auth_type": "..."}`. A
+//! rule whose target doesn't match a given mount still produces a `SKIP`
+//! finding for it, so the report accounts for every (rule, mount) pair
+//! rather than silently omitting ones that don't apply.
+//!
+//! `field` is a dot-separated path into the mount's JSON representation
+//! (e.g. `"path"`, `"seal_wrap"`, `"roles.0.name"`). Lease TTL fields
+//! (`default_lease_ttl`, `max_lease_ttl`) are always stored as `"3600s"`
+//! strings; when the operator is `>` or `<` and the field value has that
+//! shape, it's parsed back into seconds before comparing, the reverse of
+//! the `format!("{}s", seconds)` done when those structs were produced.
+//!
+//! Operators: `==`, `!=`, `>`, `<`, `in` (expected is an array of allowed
+//! values), and `regex` (expected is a pattern matched against the field's
+//! string form).
+//!
+//! # Output Formats
+//!
+//! - **csv/json/stdout**: plain findings - `rule_name`, `resource_path`,
+//!   `PASS`/`FAIL`/`SKIP` status, and a human message
+//! - **sarif**: a SARIF 2.1.0 log for code-scanning dashboards. Rule
+//!   definitions (id + severity-derived `level`) are deduplicated into
+//!   `tool.driver.rules`; every finding becomes a `result` whose
+//!   `locations[0].physicalLocation.artifactLocation.uri` is the mount path
+
+use anyhow::{anyhow, Context, Result};
+use regex::Regex;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+
+use crate::commands::auth_mounts::AuthMountOutput;
+use crate::commands::kv_mounts::KvMountOutput;
+
+/// The mount class a rule's `target` can name directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum MountClass {
+    Kv,
+    Auth,
+}
+
+/// What mounts a [`Rule`] applies to: a whole class, or one specific
+/// `mount_type`/`auth_type` within it.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Target {
+    Class(MountClass),
+    MountType { mount_type: String },
+    AuthType { auth_type: String },
+}
+
+impl Target {
+    fn matches(&self, class: MountClass, type_name: &str) -> bool {
+        match self {
+            Self::Class(target_class) => *target_class == class,
+            Self::MountType { mount_type } => class == MountClass::Kv && mount_type == type_name,
+            Self::AuthType { auth_type } => class == MountClass::Auth && auth_type == type_name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+enum Operator {
+    #[serde(rename = "==")]
+    Eq,
+    #[serde(rename = "!=")]
+    Ne,
+    #[serde(rename = ">")]
+    Gt,
+    #[serde(rename = "<")]
+    Lt,
+    #[serde(rename = "in")]
+    In,
+    #[serde(rename = "regex")]
+    Regex,
+}
+
+#[derive(Debug, Deserialize)]
+struct Clause {
+    field: String,
+    op: Operator,
+    value: Value,
+}
+
+/// Severity a rule is reported at when it fails, used only for the SARIF
+/// `--format sarif` output; csv/json/stdout findings carry PASS/FAIL/SKIP
+/// status instead.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Severity {
+    #[default]
+    Error,
+    Warning,
+    Note,
+}
+
+impl Severity {
+    const fn sarif_level(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Note => "note",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Rule {
+    name: String,
+    target: Target,
+    #[serde(default)]
+    severity: Severity,
+    clauses: Vec<Clause>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuleFile {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+enum Status {
+    Pass,
+    Fail,
+    Skip,
+}
+
+impl Status {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pass => "PASS",
+            Self::Fail => "FAIL",
+            Self::Skip => "SKIP",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Finding {
+    rule_name: String,
+    resource_path: String,
+    status: Status,
+    message: String,
+}
+
+/// A SARIF 2.1.0 log: `tool.driver.rules` carries the rule metadata
+/// (deduplicated, one entry per rule) and `results` carries one entry per
+/// [`Finding`], for consumption by code-scanning dashboards.
+#[derive(Debug, Serialize)]
+struct SarifLog {
+    version: &'static str,
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifRun {
+    tool: SarifTool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifDriver {
+    name: &'static str,
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifRule {
+    id: String,
+    #[serde(rename = "defaultConfiguration")]
+    default_configuration: SarifRuleConfiguration,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifRuleConfiguration {
+    level: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifResult {
+    #[serde(rename = "ruleId")]
+    rule_id: String,
+    level: &'static str,
+    message: SarifMessage,
+    locations: Vec<SarifLocation>,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifLocation {
+    #[serde(rename = "physicalLocation")]
+    physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifPhysicalLocation {
+    #[serde(rename = "artifactLocation")]
+    artifact_location: SarifArtifactLocation,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+/// Look up a dot-separated field path (numeric segments index into arrays)
+/// within a mount's JSON representation.
+fn lookup_field<'a>(resource: &'a Value, field_path: &str) -> Option<&'a Value> {
+    field_path.split('.').try_fold(resource, |acc, segment| {
+        if let Ok(index) = segment.parse::<usize>() {
+            acc.get(index)
+        } else {
+            acc.get(segment)
+        }
+    })
+}
+
+/// Parse a `"3600s"`-style lease TTL string back into whole seconds.
+fn parse_ttl_seconds(value: &str) -> Option<f64> {
+    value.strip_suffix('s').and_then(|s| s.parse::<f64>().ok())
+}
+
+/// Coerce a field value and a rule's expected value to a comparable pair of
+/// `f64`s, normalizing `"3600s"`-style TTL strings to seconds first.
+fn numeric_values(actual: &Value, expected: &Value) -> Option<(f64, f64)> {
+    let a = actual.as_f64().or_else(|| actual.as_str().and_then(parse_ttl_seconds))?;
+    let b = expected
+        .as_f64()
+        .or_else(|| expected.as_str().and_then(parse_ttl_seconds))?;
+    Some((a, b))
+}
+
+/// Evaluate one clause against a mount's JSON representation, returning
+/// whether it passed and a human-readable description for the finding.
+fn evaluate_clause(clause: &Clause, resource: &Value) -> (bool, String) {
+    let Some(actual) = lookup_field(resource, &clause.field) else {
+        return (false, format!("field `{}` not present", clause.field));
+    };
+
+    match clause.op {
+        Operator::Eq => (*actual == clause.value, format!("{} == {}", clause.field, clause.value)),
+        Operator::Ne => (*actual != clause.value, format!("{} != {}", clause.field, clause.value)),
+        Operator::In => {
+            let Some(options) = clause.value.as_array() else {
+                return (false, format!("`in` expected an array, got {}", clause.value));
+            };
+            (options.contains(actual), format!("{} in {}", clause.field, clause.value))
+        }
+        Operator::Regex => {
+            let Some(pattern) = clause.value.as_str() else {
+                return (
+                    false,
+                    format!("`regex` expected a string pattern, got {}", clause.value),
+                );
+            };
+            let actual_str = actual.as_str().map_or_else(|| actual.to_string(), ToString::to_string);
+            match Regex::new(pattern) {
+                Ok(re) => (re.is_match(&actual_str), format!("{} =~ /{}/", clause.field, pattern)),
+                Err(e) => (false, format!("invalid regex `{}`: {}", pattern, e)),
+            }
+        }
+        Operator::Gt | Operator::Lt => match numeric_values(actual, &clause.value) {
+            Some((a, b)) => {
+                let op_str = if matches!(clause.op, Operator::Gt) { ">" } else { "<" };
+                let ok = if matches!(clause.op, Operator::Gt) { a > b } else { a < b };
+                (ok, format!("{} {} {}", clause.field, op_str, clause.value))
+            }
+            None => (
+                false,
+                format!("`{}` ({}) is not numerically comparable to {}", clause.field, actual, clause.value),
+            ),
+        },
+    }
+}
+
+/// Evaluate one rule against one mount, producing exactly one [`Finding`]:
+/// `Skip` if the rule's `target` doesn't apply to this mount, otherwise
+/// `Pass`/`Fail` depending on whether every clause holds.
+fn evaluate_rule(rule: &Rule, class: MountClass, type_name: &str, resource_path: &str, resource: &Value) -> Finding {
+    if !rule.target.matches(class, type_name) {
+        return Finding {
+            rule_name: rule.name.clone(),
+            resource_path: resource_path.to_string(),
+            status: Status::Skip,
+            message: "rule does not target this mount class/type".to_string(),
+        };
+    }
+
+    let failures: Vec<String> = rule
+        .clauses
+        .iter()
+        .filter_map(|clause| {
+            let (ok, description) = evaluate_clause(clause, resource);
+            (!ok).then_some(description)
+        })
+        .collect();
+
+    if failures.is_empty() {
+        Finding {
+            rule_name: rule.name.clone(),
+            resource_path: resource_path.to_string(),
+            status: Status::Pass,
+            message: "all clauses satisfied".to_string(),
+        }
+    } else {
+        Finding {
+            rule_name: rule.name.clone(),
+            resource_path: resource_path.to_string(),
+            status: Status::Fail,
+            message: format!("failed: {}", failures.join("; ")),
+        }
+    }
+}
+
+fn evaluate_kv_mount(rules: &[Rule], mount: &KvMountOutput) -> Result<Vec<Finding>> {
+    let resource = serde_json::to_value(mount).context("Failed to represent KV mount as JSON")?;
+    Ok(rules
+        .iter()
+        .map(|rule| evaluate_rule(rule, MountClass::Kv, &mount.mount_type, &mount.path, &resource))
+        .collect())
+}
+
+fn evaluate_auth_mount(rules: &[Rule], mount: &AuthMountOutput) -> Result<Vec<Finding>> {
+    let resource = serde_json::to_value(mount).context("Failed to represent auth mount as JSON")?;
+    Ok(rules
+        .iter()
+        .map(|rule| evaluate_rule(rule, MountClass::Auth, &mount.auth_type, &mount.path, &resource))
+        .collect())
+}
+
+fn load_rule_file(path: &str) -> Result<RuleFile> {
+    let file = File::open(path).with_context(|| format!("Failed to open rule file: {}", path))?;
+    serde_json::from_reader(file).with_context(|| format!("Failed to parse rule file: {}", path))
+}
+
+fn load_snapshot<T: serde::de::DeserializeOwned>(path: &str) -> Result<Vec<T>> {
+    let file = File::open(path).with_context(|| format!("Failed to open snapshot: {}", path))?;
+    serde_json::from_reader(file).with_context(|| format!("Failed to parse snapshot: {}", path))
+}
+
+fn write_json(findings: &[Finding], output: Option<&str>) -> Result<()> {
+    let json_output =
+        serde_json::to_string_pretty(findings).context("Failed to serialize findings to JSON")?;
+    if let Some(output_path) = output {
+        let mut file = File::create(output_path).context("Failed to create output file")?;
+        file.write_all(json_output.as_bytes())
+            .context("Failed to write JSON to file")?;
+        eprintln!("Output written to: {}", output_path);
+    } else {
+        println!("{}", json_output);
+    }
+    Ok(())
+}
+
+fn write_csv(findings: &[Finding], output: Option<&str>) -> Result<()> {
+    use std::fmt::Write as _;
+    let mut csv_output = String::new();
+    csv_output.push_str("rule_name,resource_path,status,message\n");
+    for finding in findings {
+        let _ = writeln!(
+            csv_output,
+            "\"{}\",\"{}\",\"{}\",\"{}\"",
+            finding.rule_name.replace('"', "\"\""),
+            finding.resource_path.replace('"', "\"\""),
+            finding.status.as_str(),
+            finding.message.replace('"', "\"\"")
+        );
+    }
+
+    if let Some(output_path) = output {
+        let mut file = File::create(output_path).context("Failed to create output file")?;
+        file.write_all(csv_output.as_bytes())
+            .context("Failed to write CSV to file")?;
+        eprintln!("Output written to: {}", output_path);
+    } else {
+        print!("{}", csv_output);
+    }
+    Ok(())
+}
+
+/// Build a SARIF 2.1.0 log from the findings: rule metadata is deduplicated
+/// into `tool.driver.rules` (one entry per distinct rule, `level` from its
+/// configured `severity`), and every finding - `PASS`, `FAIL`, or `SKIP` -
+/// becomes a `result`, its status folded into the message text since SARIF
+/// has no separate field for it.
+fn build_sarif(rule_file: &RuleFile, findings: &[Finding]) -> SarifLog {
+    let severity_by_rule: HashMap<&str, Severity> =
+        rule_file.rules.iter().map(|rule| (rule.name.as_str(), rule.severity)).collect();
+
+    let rules = rule_file
+        .rules
+        .iter()
+        .map(|rule| SarifRule {
+            id: rule.name.clone(),
+            default_configuration: SarifRuleConfiguration {
+                level: rule.severity.sarif_level(),
+            },
+        })
+        .collect();
+
+    let results = findings
+        .iter()
+        .map(|finding| SarifResult {
+            rule_id: finding.rule_name.clone(),
+            level: severity_by_rule
+                .get(finding.rule_name.as_str())
+                .map_or_else(|| Severity::default().sarif_level(), |severity| severity.sarif_level()),
+            message: SarifMessage {
+                text: format!("[{}] {}", finding.status.as_str(), finding.message),
+            },
+            locations: vec![SarifLocation {
+                physical_location: SarifPhysicalLocation {
+                    artifact_location: SarifArtifactLocation {
+                        uri: finding.resource_path.clone(),
+                    },
+                },
+            }],
+        })
+        .collect();
+
+    SarifLog {
+        version: "2.1.0",
+        schema: "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
+        runs: vec![SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: "vault-audit-compliance",
+                    rules,
+                },
+            },
+            results,
+        }],
+    }
+}
+
+fn write_sarif(rule_file: &RuleFile, findings: &[Finding], output: Option<&str>) -> Result<()> {
+    let sarif_output = serde_json::to_string_pretty(&build_sarif(rule_file, findings))
+        .context("Failed to serialize findings to SARIF")?;
+    if let Some(output_path) = output {
+        let mut file = File::create(output_path).context("Failed to create output file")?;
+        file.write_all(sarif_output.as_bytes())
+            .context("Failed to write SARIF to file")?;
+        eprintln!("Output written to: {}", output_path);
+    } else {
+        println!("{}", sarif_output);
+    }
+    Ok(())
+}
+
+fn print_stdout(findings: &[Finding]) {
+    println!("\nCompliance Findings:");
+    println!("{}", "=".repeat(80));
+    for finding in findings {
+        println!("[{}] {} - {}", finding.status.as_str(), finding.rule_name, finding.resource_path);
+        println!("  {}", finding.message);
+    }
+}
+
+/// Run the compliance rule engine: load a JSON rule file plus previously
+/// saved KV and/or auth mount snapshots (`kv-mounts --format json` /
+/// `auth-mounts --format json`), evaluate every rule against every
+/// enumerated mount, and emit the combined findings in the requested
+/// `--format` (csv, json, stdout, or sarif). Returns an error (after
+/// writing the report) if any rule failed, so the run can gate CI.
+pub fn run(
+    rules_path: &str,
+    kv_snapshot_path: Option<&str>,
+    auth_snapshot_path: Option<&str>,
+    format: &str,
+    output: Option<&str>,
+) -> Result<()> {
+    let rule_file = load_rule_file(rules_path)?;
+
+    let mut findings = Vec::new();
+
+    if let Some(path) = kv_snapshot_path {
+        let mounts: Vec<KvMountOutput> = load_snapshot(path)?;
+        for mount in &mounts {
+            findings.extend(evaluate_kv_mount(&rule_file.rules, mount)?);
+        }
+    }
+
+    if let Some(path) = auth_snapshot_path {
+        let mounts: Vec<AuthMountOutput> = load_snapshot(path)?;
+        for mount in &mounts {
+            findings.extend(evaluate_auth_mount(&rule_file.rules, mount)?);
+        }
+    }
+
+    let pass = findings.iter().filter(|f| matches!(f.status, Status::Pass)).count();
+    let fail = findings.iter().filter(|f| matches!(f.status, Status::Fail)).count();
+    let skip = findings.iter().filter(|f| matches!(f.status, Status::Skip)).count();
+    eprintln!("Compliance: {pass} passed, {fail} failed, {skip} skipped");
+
+    match format {
+        "json" => write_json(&findings, output)?,
+        "csv" => write_csv(&findings, output)?,
+        "stdout" => print_stdout(&findings),
+        "sarif" => write_sarif(&rule_file, &findings, output)?,
+        _ => {
+            return Err(anyhow!(
+                "Invalid format: {}. Must be one of: csv, json, stdout, sarif",
+                format
+            ));
+        }
+    }
+
+    if fail > 0 {
+        return Err(anyhow!("{fail} compliance rule(s) failed"));
+    }
+
+    Ok(())
+}
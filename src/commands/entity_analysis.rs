@@ -13,7 +13,8 @@
 //!
 //! # Creation analysis by auth path
 //! vault-audit entity-analysis creation logs/*.log
-//! vault-audit entity-analysis creation logs/*.log --export creation_data.json
+//! vault-audit entity-analysis creation logs/*.log --output creation_data.json
+//! vault-audit entity-analysis creation logs/*.log --output creation.parquet --format parquet
 //!
 //! # Extract entity mappings (preprocessing)
 //! vault-audit entity-analysis preprocess logs/*.log --output mappings.json
@@ -22,13 +23,23 @@
 //! # Detect activity gaps for entities
 //! vault-audit entity-analysis gaps logs/*.log --window-seconds 300
 //!
+//! # Export a run trace and counters for any subcommand to an OTLP collector
+//! vault-audit entity-analysis creation logs/*.log --otel-endpoint http://localhost:4317
+//! vault-audit entity-analysis preprocess logs/*.log --otel-endpoint http://localhost:4317
+//! vault-audit entity-analysis gaps logs/*.log --otel-endpoint http://localhost:4317
+//!
 //! # Individual entity timeline
 //! vault-audit entity-analysis timeline logs/*.log --entity-id abc-123
+//!
+//! # Cluster entities by overlapping KV access patterns
+//! vault-audit entity-analysis clusters logs/*.log --output clusters.csv
 //! ```
 //!
 //! **Key Improvement**: Auto-preprocessing eliminates the need for separate
 //! preprocessing steps. Entity mappings are built in-memory automatically when
-//! needed by churn or creation analysis.
+//! needed by churn or creation analysis, and cached on disk
+//! (see [`crate::utils::entity_cache`]) so repeated runs over the same logs
+//! skip the rebuild; pass `--no-cache` to force a rebuild.
 //!
 //! # Subcommands
 //!
@@ -51,11 +62,46 @@
 //! ## timeline
 //! Shows chronological activity for a specific entity ID, useful for debugging
 //! or investigating specific identity issues.
+//!
+//! ## clusters
+//! Groups entities whose KV secret access patterns overlap heavily, surfacing
+//! redundant service accounts or suspicious lookalike clients. See
+//! [`entity_clusters`](crate::commands::entity_clusters) for the algorithm.
 
 use anyhow::Result;
 use std::fs::File;
 use std::io::Write;
 
+/// Builds the entity map for auto-preprocessing, consulting the on-disk
+/// [`crate::utils::entity_cache`] first unless `use_cache` is `false` (i.e.
+/// `--no-cache` was passed). Populates the cache on a miss so the next run
+/// over the same logs can skip the rebuild.
+fn build_or_load_entity_map(
+    log_files: &[String],
+    use_cache: bool,
+) -> Result<
+    std::collections::HashMap<String, crate::commands::preprocess_entities::EntityMapping>,
+> {
+    if use_cache {
+        if let Some(map) = crate::utils::entity_cache::load(log_files) {
+            eprintln!("Auto-preprocessing: Using cached entity mappings...\n");
+            return Ok(map);
+        }
+    }
+
+    eprintln!("Auto-preprocessing: Building entity mappings in-memory...\n");
+    let map = crate::commands::preprocess_entities::build_entity_map(log_files)?;
+    eprintln!("Entity mappings ready\n");
+
+    if use_cache {
+        if let Err(err) = crate::utils::entity_cache::store(log_files, &map) {
+            eprintln!("Warning: failed to cache entity mappings: {err}");
+        }
+    }
+
+    Ok(map)
+}
+
 /// Helper to write entity map to temp JSON file for commands that expect file paths
 fn write_temp_entity_map(
     entity_map: &std::collections::HashMap<
@@ -75,6 +121,7 @@ fn write_temp_entity_map(
 }
 
 /// Run churn analysis subcommand
+#[allow(clippy::too_many_arguments)]
 pub fn run_churn(
     log_files: &[String],
     entity_map: Option<&String>,
@@ -82,13 +129,24 @@ pub fn run_churn(
     output: Option<&String>,
     format: Option<&String>,
     auto_preprocess: bool,
+    threads: Option<usize>,
+    otel_endpoint: Option<&str>,
+    cluster_eps: Option<f64>,
+    cluster_min_points: Option<usize>,
+    signature_rules: Option<&str>,
+    state_store: Option<&str>,
+    state_compact_threshold_bytes: Option<u64>,
+    use_cache: bool,
+    filter: Option<&str>,
+    bucket_secs: Option<u64>,
+    metrics_file: Option<&str>,
+    metrics_listen: Option<&str>,
+    s3_endpoint: Option<&str>,
 ) -> Result<()> {
     // Auto-preprocessing: build entity map in-memory and write to temp file
     let temp_map_file = if auto_preprocess && entity_map.is_none() {
-        eprintln!("Auto-preprocessing: Building entity mappings in-memory...\n");
-        let map = crate::commands::preprocess_entities::build_entity_map(log_files)?;
+        let map = build_or_load_entity_map(log_files, use_cache)?;
         let temp_path = write_temp_entity_map(&map)?;
-        eprintln!("Entity mappings ready\n");
         Some(temp_path)
     } else {
         None
@@ -106,6 +164,18 @@ pub fn run_churn(
         baseline.map(std::string::String::as_str),
         output.map(std::string::String::as_str),
         format.map(std::string::String::as_str),
+        threads,
+        otel_endpoint,
+        cluster_eps,
+        cluster_min_points,
+        signature_rules,
+        state_store,
+        state_compact_threshold_bytes,
+        filter,
+        bucket_secs,
+        metrics_file,
+        metrics_listen,
+        s3_endpoint,
     );
 
     // Cleanup temp file
@@ -117,18 +187,26 @@ pub fn run_churn(
 }
 
 /// Run creation analysis subcommand
+#[allow(clippy::too_many_arguments)]
 pub fn run_creation(
     log_files: &[String],
     entity_map: Option<&String>,
     output: Option<&String>,
+    format: Option<&String>,
+    since: Option<&str>,
+    until: Option<&str>,
+    threads: Option<usize>,
     auto_preprocess: bool,
+    otel_endpoint: Option<&str>,
+    use_cache: bool,
+    store_backend: crate::utils::mapping_store::StoreBackend,
+    store_path: Option<&str>,
+    s3_endpoint: Option<&str>,
 ) -> Result<()> {
     // Auto-preprocessing: build entity map in-memory and write to temp file
     let temp_map_file = if auto_preprocess && entity_map.is_none() {
-        eprintln!("Auto-preprocessing: Building entity mappings in-memory...\n");
-        let map = crate::commands::preprocess_entities::build_entity_map(log_files)?;
+        let map = build_or_load_entity_map(log_files, use_cache)?;
         let temp_path = write_temp_entity_map(&map)?;
-        eprintln!("Entity mappings ready\n");
         Some(temp_path)
     } else {
         None
@@ -144,6 +222,14 @@ pub fn run_creation(
         log_files,
         map_to_use,
         output.map(std::string::String::as_str),
+        format.map(std::string::String::as_str),
+        since,
+        until,
+        threads,
+        otel_endpoint,
+        store_backend,
+        store_path,
+        s3_endpoint,
     );
 
     // Cleanup temp file
@@ -155,23 +241,88 @@ pub fn run_creation(
 }
 
 /// Run preprocess subcommand
-pub fn run_preprocess(log_files: &[String], output: &str, format: &str) -> Result<()> {
+pub fn run_preprocess(
+    log_files: &[String],
+    output: &str,
+    format: &str,
+    otel_endpoint: Option<&str>,
+    store_backend: crate::utils::mapping_store::StoreBackend,
+    store_path: Option<&str>,
+    merge_into: Option<&str>,
+    s3_endpoint: Option<&str>,
+) -> Result<()> {
     // Delegate to existing preprocess_entities implementation
-    crate::commands::preprocess_entities::run(log_files, output, format)
+    crate::commands::preprocess_entities::run(
+        log_files,
+        output,
+        format,
+        otel_endpoint,
+        store_backend,
+        store_path,
+        merge_into,
+        s3_endpoint,
+    )
 }
 
 /// Run gaps detection subcommand
-pub fn run_gaps(log_files: &[String], window_seconds: u64) -> Result<()> {
+pub fn run_gaps(
+    log_files: &[String],
+    window_seconds: u64,
+    otel_endpoint: Option<&str>,
+    format: &str,
+    s3_endpoint: Option<&str>,
+) -> Result<()> {
     // Delegate to existing entity_gaps implementation
-    crate::commands::entity_gaps::run(log_files, window_seconds)
+    crate::commands::entity_gaps::run(log_files, window_seconds, otel_endpoint, format, s3_endpoint)
 }
 
 /// Run timeline subcommand
+#[allow(clippy::too_many_arguments)]
 pub fn run_timeline(
     log_files: &[String],
     entity_id: &str,
     display_name: Option<&String>,
-) -> Result<()> {
+    format: &str,
+    gcra_params: Option<(f64, u64, f64)>,
+    since: Option<&str>,
+    until: Option<&str>,
+    bucket_secs: u64,
+    window_secs: u64,
+    outlier_sigma: f64,
+    diurnal_concentration_threshold: f64,
+    output_dir: Option<&str>,
+    s3_endpoint: Option<&str>,
+) -> Result<Option<String>> {
     // Delegate to existing entity_timeline implementation
-    crate::commands::entity_timeline::run(log_files, entity_id, display_name)
+    crate::commands::entity_timeline::run(
+        log_files,
+        entity_id,
+        display_name,
+        format,
+        gcra_params,
+        since,
+        until,
+        bucket_secs,
+        window_secs,
+        outlier_sigma,
+        diurnal_concentration_threshold,
+        output_dir,
+        s3_endpoint,
+    )
+}
+
+/// Run entity-clusters subcommand
+pub fn run_clusters(
+    log_files: &[String],
+    output: Option<&String>,
+    similarity_threshold: f64,
+    entity_csv: Option<&str>,
+) -> Result<()> {
+    // Delegate to the entity_clusters implementation
+    crate::commands::entity_clusters::run(
+        log_files,
+        output.map(String::as_str),
+        similarity_threshold,
+        entity_csv,
+    )
 }
@@ -39,11 +39,75 @@
 //!
 //! # Export as CSV format
 //! vault-audit entity-churn *.log --output entity_churn.csv --format csv
+//!
+//! # Export as columnar Parquet/Arrow IPC for DuckDB/pandas/Spark
+//! vault-audit entity-churn *.log --output entity_churn.parquet --format parquet
+//! vault-audit entity-churn *.log --output entity_churn.arrow --format arrow
+//!
+//! # Export as an indexed binary file for O(1) per-entity lookup
+//! vault-audit entity-churn *.log --output entity_churn.bin --format bin
+//!
+//! # Cap parallel parsing to 4 worker threads (default: one per available core)
+//! vault-audit entity-churn *.log --threads 4
+//!
+//! # Export a run trace and aggregate churn metrics to an OTLP collector
+//! vault-audit entity-churn *.log --otel-endpoint http://localhost:4317
+//!
+//! # Tune the behavioral clustering pass (defaults: eps=1.5, min_points=4)
+//! vault-audit entity-churn *.log --cluster-eps 2.0 --cluster-min-points 6
+//!
+//! # Flag entities matching an operator-defined threat-intel ruleset
+//! vault-audit entity-churn *.log --signature-rules rules.json
+//!
+//! # Resume from a persistent state store, only parsing files not yet seen
+//! vault-audit entity-churn day1.log day2.log day3.log --state-store ./vault-audit.state
+//! vault-audit entity-churn day1.log day2.log day3.log day4.log --state-store ./vault-audit.state
+//!
+//! # Stream logs directly from S3 instead of downloading them first
+//! vault-audit entity-churn s3://vault-audit-archive/2025/10/*.log
 //! ```
 //!
+//! **Indexed Binary Export**: `--format bin` writes `<output>` as length-prefixed
+//! bincode records alongside a companion `<output>.idx` file mapping each
+//! `entity_id` to its byte offset, so [`read_binary_record`] can fetch a single
+//! entity without parsing the rest of the export.
+//!
+//! **OpenTelemetry Export**: With `--otel-endpoint` (and the `enable_otel` build
+//! feature), the run is emitted as a trace with one span per input file, and the
+//! daily new-vs-returning counts, total logins, `lifecycle`/`activity_pattern`
+//! bucket counts, and an `ephemeral_confidence` histogram are recorded as metrics.
+//! See [`crate::utils::otel`].
+//!
+//! **Behavioral Clustering**: Beyond the hand-coded lifecycle/ephemeral labels,
+//! entities are grouped into emergent cohorts with a DBSCAN pass over a
+//! normalized feature vector (`total_logins`, files appeared in, activity span,
+//! mean inter-appearance gap, one-hot `mount_type`). Each record gets a
+//! `cluster_id` (or `None` if it's a density outlier); `--cluster-eps` and
+//! `--cluster-min-points` tune the neighborhood radius and core-point threshold.
+//!
+//! **Signature Matching**: `--signature-rules <file>` loads a JSON list of
+//! [`SignatureRule`] predicates (glob on `display_name`/`mount_path`, exact
+//! `mount_type`/`token_type`, login-count bounds, single-day burst threshold)
+//! evaluated against every entity. This is a deterministic, version-controllable
+//! complement to the learned ephemeral heuristics — useful for encoding
+//! organization-specific indicators a statistical model wouldn't pick up.
+//!
+//! **Persistent State Store**: `--state-store <path>` turns this from a full
+//! rescan into an incremental analyzer. Accumulated entity state and the set
+//! of already-ingested file names are kept in a `<path>.snapshot` file plus a
+//! `<path>.wal` append log; each run loads both, skips any given log file it
+//! already ingested, and appends only what changed. Once the WAL exceeds
+//! `--state-compact-threshold-bytes` (default 8 MiB), it's compacted into a
+//! fresh snapshot and truncated so the store doesn't grow unboundedly.
+//!
 //! **Compressed File Support**: Automatically handles `.gz` and `.zst` files - no manual
 //! decompression required. Mix compressed and uncompressed files freely.
 //!
+//! **Parallel Parsing**: Each log file is parsed on its own worker thread into an
+//! independent partial record set, then merged deterministically (earliest
+//! `first_seen`/latest `last_seen` wins, logins sum, `files_appeared` unions and is
+//! re-sorted into day order) before the existing two-pass pattern analysis runs.
+//!
 //! # Ephemeral Pattern Detection
 //!
 //! The command uses a sophisticated two-pass analysis to detect ephemeral entities
@@ -100,15 +164,28 @@
 
 use crate::audit::types::AuditEntry;
 use crate::utils::format::format_number;
+use crate::utils::metrics::MetricsExporter;
 use crate::utils::progress::ProgressBar;
 use crate::utils::reader::open_file;
-use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
+use anyhow::{bail, Context, Result};
+use arrow::array::{
+    Array, BooleanArray, DictionaryArray, Float32Array, ListBuilder, StringArray,
+    StringBuilder, StringDictionaryBuilder, TimestampMicrosecondArray, UInt64Array,
+};
+use arrow::datatypes::{DataType, Field, Int32Type, Schema, TimeUnit};
+use arrow::ipc::writer::FileWriter as ArrowFileWriter;
+use arrow::record_batch::RecordBatch;
+use chrono::{DateTime, NaiveDate, Utc};
+use parquet::arrow::ArrowWriter as ParquetArrowWriter;
+use parquet::file::properties::WriterProperties;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 
 /// Entity mapping from baseline CSV files
 #[derive(Debug, Serialize, Deserialize)]
@@ -139,6 +216,12 @@ struct EntityChurnRecord {
     last_seen_time: DateTime<Utc>,
     files_appeared: Vec<String>,
     total_logins: usize,
+    /// Logins bucketed by calendar day (derived from each login's real
+    /// timestamp, not the file it came from). Drives [`compute_activity_time_series`]
+    /// so `activity_pattern`/ephemeral-confidence scaling reflect actual
+    /// active-day span and gaps instead of a filename-index heuristic.
+    #[serde(skip)]
+    logins_by_day: BTreeMap<NaiveDate, usize>,
     lifecycle: String, // "new_day_1", "new_day_2", "new_day_3", "pre_existing"
     activity_pattern: String, // "consistent", "sporadic", "declining", "single_burst", "unknown"
     is_ephemeral_pattern: bool,
@@ -162,6 +245,10 @@ struct EntityChurnRecord {
     historical_last_seen: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     historical_login_count: Option<usize>,
+    // Behavioral cohort from the density-based clustering pass (`cluster_entities`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cluster_id: Option<usize>,
+    is_cluster_outlier: bool,
 }
 
 /// CSV-compatible representation of entity churn record
@@ -192,6 +279,8 @@ struct EntityChurnRecordCsv {
     historical_first_seen: String,
     historical_last_seen: String,
     historical_login_count: String,
+    cluster_id: String,
+    is_cluster_outlier: bool,
 }
 
 impl From<EntityChurnRecord> for EntityChurnRecordCsv {
@@ -225,6 +314,185 @@ impl From<EntityChurnRecord> for EntityChurnRecordCsv {
                 .historical_login_count
                 .map(|n| n.to_string())
                 .unwrap_or_default(),
+            cluster_id: record
+                .cluster_id
+                .map(|n| n.to_string())
+                .unwrap_or_default(),
+            is_cluster_outlier: record.is_cluster_outlier,
+        }
+    }
+}
+
+/// Binary (bincode) representation of an entity churn record, used by the
+/// indexed `"bin"` export format. `EntityChurnRecord`'s own `Serialize` impl
+/// uses `skip_serializing_if` for JSON/CSV ergonomics, which would desync
+/// field order on a non-self-describing format like bincode, so this mirrors
+/// every field explicitly (as plain `Option<T>`s) and derives `Deserialize`
+/// so a single record round-trips without the rest of the export.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct EntityChurnRecordBin {
+    entity_id: String,
+    display_name: String,
+    mount_path: String,
+    mount_type: String,
+    token_type: String,
+    first_seen_file: String,
+    first_seen_time: DateTime<Utc>,
+    last_seen_file: String,
+    last_seen_time: DateTime<Utc>,
+    files_appeared: Vec<String>,
+    total_logins: usize,
+    lifecycle: String,
+    activity_pattern: String,
+    is_ephemeral_pattern: bool,
+    ephemeral_confidence: f32,
+    ephemeral_reasons: Vec<String>,
+    baseline_entity_name: Option<String>,
+    baseline_created: Option<String>,
+    baseline_alias_name: Option<String>,
+    baseline_mount_path: Option<String>,
+    historical_display_name: Option<String>,
+    historical_first_seen: Option<String>,
+    historical_last_seen: Option<String>,
+    historical_login_count: Option<usize>,
+    cluster_id: Option<usize>,
+    is_cluster_outlier: bool,
+}
+
+impl From<&EntityChurnRecord> for EntityChurnRecordBin {
+    fn from(record: &EntityChurnRecord) -> Self {
+        Self {
+            entity_id: record.entity_id.clone(),
+            display_name: record.display_name.clone(),
+            mount_path: record.mount_path.clone(),
+            mount_type: record.mount_type.clone(),
+            token_type: record.token_type.clone(),
+            first_seen_file: record.first_seen_file.clone(),
+            first_seen_time: record.first_seen_time,
+            last_seen_file: record.last_seen_file.clone(),
+            last_seen_time: record.last_seen_time,
+            files_appeared: record.files_appeared.clone(),
+            total_logins: record.total_logins,
+            lifecycle: record.lifecycle.clone(),
+            activity_pattern: record.activity_pattern.clone(),
+            is_ephemeral_pattern: record.is_ephemeral_pattern,
+            ephemeral_confidence: record.ephemeral_confidence,
+            ephemeral_reasons: record.ephemeral_reasons.clone(),
+            baseline_entity_name: record.baseline_entity_name.clone(),
+            baseline_created: record.baseline_created.clone(),
+            baseline_alias_name: record.baseline_alias_name.clone(),
+            baseline_mount_path: record.baseline_mount_path.clone(),
+            historical_display_name: record.historical_display_name.clone(),
+            historical_first_seen: record.historical_first_seen.clone(),
+            historical_last_seen: record.historical_last_seen.clone(),
+            historical_login_count: record.historical_login_count,
+            cluster_id: record.cluster_id,
+            is_cluster_outlier: record.is_cluster_outlier,
+        }
+    }
+}
+
+/// Full-fidelity persistence representation of [`EntityChurnRecord`], used by
+/// the `--state-store` incremental mode ([`load_state`]/[`persist_state`]).
+/// Unlike [`EntityChurnRecordBin`] (an external export format that
+/// deliberately drops internal derived state), this keeps `logins_by_day` so
+/// a resumed run's activity-pattern/gap analysis sees the same history it
+/// would have if every log file had been reprocessed from scratch.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct EntityChurnStateRecord {
+    entity_id: String,
+    display_name: String,
+    mount_path: String,
+    mount_type: String,
+    token_type: String,
+    first_seen_file: String,
+    first_seen_time: DateTime<Utc>,
+    last_seen_file: String,
+    last_seen_time: DateTime<Utc>,
+    files_appeared: Vec<String>,
+    total_logins: usize,
+    logins_by_day: BTreeMap<NaiveDate, usize>,
+    lifecycle: String,
+    activity_pattern: String,
+    is_ephemeral_pattern: bool,
+    ephemeral_confidence: f32,
+    ephemeral_reasons: Vec<String>,
+    baseline_entity_name: Option<String>,
+    baseline_created: Option<String>,
+    baseline_alias_name: Option<String>,
+    baseline_mount_path: Option<String>,
+    historical_display_name: Option<String>,
+    historical_first_seen: Option<String>,
+    historical_last_seen: Option<String>,
+    historical_login_count: Option<usize>,
+    cluster_id: Option<usize>,
+    is_cluster_outlier: bool,
+}
+
+impl From<&EntityChurnRecord> for EntityChurnStateRecord {
+    fn from(record: &EntityChurnRecord) -> Self {
+        Self {
+            entity_id: record.entity_id.clone(),
+            display_name: record.display_name.clone(),
+            mount_path: record.mount_path.clone(),
+            mount_type: record.mount_type.clone(),
+            token_type: record.token_type.clone(),
+            first_seen_file: record.first_seen_file.clone(),
+            first_seen_time: record.first_seen_time,
+            last_seen_file: record.last_seen_file.clone(),
+            last_seen_time: record.last_seen_time,
+            files_appeared: record.files_appeared.clone(),
+            total_logins: record.total_logins,
+            logins_by_day: record.logins_by_day.clone(),
+            lifecycle: record.lifecycle.clone(),
+            activity_pattern: record.activity_pattern.clone(),
+            is_ephemeral_pattern: record.is_ephemeral_pattern,
+            ephemeral_confidence: record.ephemeral_confidence,
+            ephemeral_reasons: record.ephemeral_reasons.clone(),
+            baseline_entity_name: record.baseline_entity_name.clone(),
+            baseline_created: record.baseline_created.clone(),
+            baseline_alias_name: record.baseline_alias_name.clone(),
+            baseline_mount_path: record.baseline_mount_path.clone(),
+            historical_display_name: record.historical_display_name.clone(),
+            historical_first_seen: record.historical_first_seen.clone(),
+            historical_last_seen: record.historical_last_seen.clone(),
+            historical_login_count: record.historical_login_count,
+            cluster_id: record.cluster_id,
+            is_cluster_outlier: record.is_cluster_outlier,
+        }
+    }
+}
+
+impl From<EntityChurnStateRecord> for EntityChurnRecord {
+    fn from(state: EntityChurnStateRecord) -> Self {
+        Self {
+            entity_id: state.entity_id,
+            display_name: state.display_name,
+            mount_path: state.mount_path,
+            mount_type: state.mount_type,
+            token_type: state.token_type,
+            first_seen_file: state.first_seen_file,
+            first_seen_time: state.first_seen_time,
+            last_seen_file: state.last_seen_file,
+            last_seen_time: state.last_seen_time,
+            files_appeared: state.files_appeared,
+            total_logins: state.total_logins,
+            logins_by_day: state.logins_by_day,
+            lifecycle: state.lifecycle,
+            activity_pattern: state.activity_pattern,
+            is_ephemeral_pattern: state.is_ephemeral_pattern,
+            ephemeral_confidence: state.ephemeral_confidence,
+            ephemeral_reasons: state.ephemeral_reasons,
+            baseline_entity_name: state.baseline_entity_name,
+            baseline_created: state.baseline_created,
+            baseline_alias_name: state.baseline_alias_name,
+            baseline_mount_path: state.baseline_mount_path,
+            historical_display_name: state.historical_display_name,
+            historical_first_seen: state.historical_first_seen,
+            historical_last_seen: state.historical_last_seen,
+            historical_login_count: state.historical_login_count,
+            cluster_id: state.cluster_id,
+            is_cluster_outlier: state.is_cluster_outlier,
         }
     }
 }
@@ -238,42 +506,213 @@ struct DailyStats {
     total_logins: usize,
 }
 
+/// Whether a tokenized piece of a `display_name` is a fixed label or an
+/// instance-specific identifier (build ID, hash, UUID, ...).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    Literal,
+    Variable,
+}
+
+/// Minimum Shannon entropy (bits/char) for a token to be treated as an
+/// opaque identifier rather than a human-chosen literal. Tuned for short
+/// alphanumeric build IDs / branch hashes, which run well above common
+/// English words at this length.
+const TEMPLATE_ENTROPY_THRESHOLD: f64 = 3.0;
+
+fn shannon_entropy(token: &str) -> f64 {
+    let len = token.chars().count() as f64;
+    if len == 0.0 {
+        return 0.0;
+    }
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for c in token.chars() {
+        *counts.entry(c).or_insert(0) += 1;
+    }
+    counts.values().fold(0.0, |acc, &count| {
+        let p = f64::from(u32::try_from(count).unwrap_or(u32::MAX)) / len;
+        acc - p * p.log2()
+    })
+}
+
+fn looks_like_uuid(token: &str) -> bool {
+    let groups: Vec<&str> = token.split('-').collect();
+    [8, 4, 4, 4, 12]
+        .iter()
+        .zip(groups.as_slice())
+        .all(|(&len, group)| group.len() == len && group.chars().all(|c| c.is_ascii_hexdigit()))
+        && groups.len() == 5
+}
+
+fn classify_token(token: &str) -> TokenKind {
+    if token.is_empty() {
+        return TokenKind::Literal;
+    }
+    if token.chars().all(|c| c.is_ascii_digit())
+        || (token.len() >= 8 && token.chars().all(|c| c.is_ascii_hexdigit()))
+        || looks_like_uuid(token)
+        || shannon_entropy(token) >= TEMPLATE_ENTROPY_THRESHOLD
+    {
+        TokenKind::Variable
+    } else {
+        TokenKind::Literal
+    }
+}
+
+/// Split a `display_name` on `:`, `/`, `-`, `_`, keeping the delimiters as
+/// their own tokens so the template can be rebuilt byte-for-byte.
+fn tokenize_display_name(display_name: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    for c in display_name.chars() {
+        if matches!(c, ':' | '/' | '-' | '_') {
+            tokens.push(std::mem::take(&mut current));
+            tokens.push(c.to_string());
+        } else {
+            current.push(c);
+        }
+    }
+    tokens.push(current);
+    tokens
+}
+
+/// Generalize a `display_name` into a template by replacing variable tokens
+/// (IDs, hashes, UUIDs, high-entropy strings) with `*`, e.g.
+/// `github-repo:myorg/myrepo:ref:refs/heads/main` ->
+/// `github-repo:*/*:ref:refs/heads/*`.
+fn extract_template(display_name: &str) -> String {
+    tokenize_display_name(display_name)
+        .into_iter()
+        .map(|token| {
+            if classify_token(&token) == TokenKind::Variable {
+                "*".to_string()
+            } else {
+                token
+            }
+        })
+        .collect()
+}
+
+/// Real calendar-day activity derived from `logins_by_day`, used in place of
+/// the filename-index heuristics that used to drive `activity_pattern` and
+/// ephemeral gap detection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ActivityTimeSeries {
+    /// Number of distinct calendar days with at least one login.
+    active_days: usize,
+    /// Days between the first and last active day, inclusive.
+    span_days: i64,
+    /// `span_days - active_days`: calendar days inside the span with no logins at all.
+    gap_days: i64,
+    /// Slope of a least-squares fit of logins-per-day against day offset.
+    /// Negative means activity is trailing off.
+    login_trend_slope: f64,
+}
+
+/// Bucket `entity`'s logins by calendar day and derive its active-day span,
+/// gap days, and login trend — the real-timestamp replacement for parsing a
+/// day index out of `files_appeared` filenames.
+fn compute_activity_time_series(entity: &EntityChurnRecord) -> ActivityTimeSeries {
+    let mut days: Vec<(NaiveDate, usize)> = entity
+        .logins_by_day
+        .iter()
+        .map(|(day, count)| (*day, *count))
+        .collect();
+    days.sort_by_key(|(day, _)| *day);
+
+    let Some((first_day, _)) = days.first().copied() else {
+        return ActivityTimeSeries {
+            active_days: 0,
+            span_days: 0,
+            gap_days: 0,
+            login_trend_slope: 0.0,
+        };
+    };
+    let (last_day, _) = *days.last().unwrap();
+
+    let active_days = days.len();
+    let span_days = (last_day - first_day).num_days() + 1;
+    let gap_days = span_days - active_days as i64;
+
+    let points: Vec<(f64, f64)> = days
+        .iter()
+        .map(|(day, count)| ((*day - first_day).num_days() as f64, *count as f64))
+        .collect();
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let numerator: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let denominator: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    let login_trend_slope = if denominator > 0.0 {
+        numerator / denominator
+    } else {
+        0.0
+    };
+
+    ActivityTimeSeries {
+        active_days,
+        span_days,
+        gap_days,
+        login_trend_slope,
+    }
+}
+
+/// A group of entities sharing the same (template, `mount_path`) pair.
+#[derive(Debug, Clone)]
+struct TemplateCluster {
+    size: usize,
+    /// Fraction of cluster members with `days_active <= 2`.
+    short_lived_fraction: f64,
+}
+
 /// Analyzes entity behavior patterns to detect ephemeral entities
 #[derive(Debug)]
 struct EphemeralPatternAnalyzer {
     total_files: usize,
-    short_lived_patterns: Vec<ShortLivedPattern>,
-}
-
-#[derive(Debug)]
-struct ShortLivedPattern {
-    days_active: usize,
-    display_name: String,
-    mount_path: String,
+    /// Clusters of entities whose `display_name` generalizes to the same
+    /// template on the same mount, keyed by (template, `mount_path`).
+    clusters: HashMap<(String, String), TemplateCluster>,
 }
 
 impl EphemeralPatternAnalyzer {
-    const fn new(total_files: usize) -> Self {
+    fn new(total_files: usize) -> Self {
         Self {
             total_files,
-            short_lived_patterns: Vec::new(),
+            clusters: HashMap::new(),
         }
     }
 
-    /// Learn patterns from entities that appeared 1-2 days (potential ephemeral patterns)
+    /// Group every entity by (template, `mount_path`) — a template-extraction
+    /// clustering pass inspired by log-event convergence clustering — and
+    /// record each cluster's size and short-lived fraction for use as a
+    /// data-driven ephemeral signal in [`Self::analyze_entity`].
     fn learn_from_entities(&mut self, entities: &HashMap<String, EntityChurnRecord>) {
+        let mut raw: HashMap<(String, String), (usize, usize)> = HashMap::new();
         for entity in entities.values() {
-            let days_active = entity.files_appeared.len();
-
-            // Learn from entities that appeared 1-2 days only
-            if days_active <= 2 {
-                self.short_lived_patterns.push(ShortLivedPattern {
-                    days_active,
-                    display_name: entity.display_name.clone(),
-                    mount_path: entity.mount_path.clone(),
-                });
+            let template = extract_template(&entity.display_name);
+            let counts = raw.entry((template, entity.mount_path.clone())).or_insert((0, 0));
+            counts.0 += 1;
+            if entity.files_appeared.len() <= 2 {
+                counts.1 += 1;
             }
         }
+
+        self.clusters = raw
+            .into_iter()
+            .map(|(key, (size, short_lived))| {
+                let short_lived_fraction = short_lived as f64 / size as f64;
+                (
+                    key,
+                    TemplateCluster {
+                        size,
+                        short_lived_fraction,
+                    },
+                )
+            })
+            .collect();
     }
 
     /// Analyze an entity and determine if it matches ephemeral patterns
@@ -295,41 +734,37 @@ impl EphemeralPatternAnalyzer {
             ));
         }
 
-        // Pattern matching: Check if display name follows patterns seen in other short-lived entities
-        if days_active <= 2 {
-            // Count how many other short-lived entities share similar patterns
-            let similar_count = self
-                .short_lived_patterns
-                .iter()
-                .filter(|p| {
-                    // Same mount path
-                    if p.mount_path == entity.mount_path && p.days_active <= 2 {
-                        return true;
-                    }
-                    // Similar naming pattern (e.g., github-repo:* or airflow-*)
-                    if entity.display_name.contains(':') && p.display_name.contains(':') {
-                        let entity_prefix = entity.display_name.split(':').next().unwrap_or("");
-                        let pattern_prefix = p.display_name.split(':').next().unwrap_or("");
-                        if entity_prefix == pattern_prefix && !entity_prefix.is_empty() {
-                            return true;
-                        }
-                    }
-                    false
-                })
-                .count();
-
-            if similar_count > 5 {
-                confidence += 0.2;
-                reasons.push(format!(
-                    "Matches pattern seen in {} other short-lived entities",
-                    similar_count
-                ));
-            } else if similar_count > 0 {
-                confidence += 0.1;
-                reasons.push(format!(
-                    "Similar to {} other short-lived entities",
-                    similar_count
-                ));
+        // Template clustering: generalize this entity's display_name and see how
+        // homogeneous its (template, mount_path) cluster is. A cluster that's
+        // mostly short-lived is a strong, data-driven ephemeral signal; a
+        // cluster that's mostly long-lived means the template is too generic
+        // (e.g. shared by a whole auth method) and should pull confidence down.
+        let template = extract_template(&entity.display_name);
+        if let Some(cluster) = self
+            .clusters
+            .get(&(template.clone(), entity.mount_path.clone()))
+        {
+            // Singleton clusters carry no signal either way.
+            if cluster.size > 1 {
+                let base = f32::min(0.3, 0.05 * cluster.size as f32);
+                let fraction = cluster.short_lived_fraction as f32;
+                if cluster.short_lived_fraction >= 0.5 {
+                    confidence += base * fraction;
+                    reasons.push(format!(
+                        "Matches template `{}` shared by {} entities ({:.0}% short-lived)",
+                        template,
+                        cluster.size,
+                        cluster.short_lived_fraction * 100.0
+                    ));
+                } else {
+                    confidence -= base * (1.0 - fraction);
+                    reasons.push(format!(
+                        "Shares template `{}` with {} entities, but only {:.0}% are short-lived — likely a long-lived naming scheme",
+                        template,
+                        cluster.size,
+                        cluster.short_lived_fraction * 100.0
+                    ));
+                }
             }
         }
 
@@ -342,33 +777,22 @@ impl EphemeralPatternAnalyzer {
             ));
         }
 
-        // Non-continuous appearance (sporadic pattern suggests not churned, just periodic)
-        if days_active >= 2 {
-            let first_day_idx = entity.files_appeared.first().and_then(|f| {
-                f.split('_')
-                    .next_back()
-                    .and_then(|s| s.trim_end_matches(".log").parse::<usize>().ok())
-            });
-            let last_day_idx = entity.files_appeared.last().and_then(|f| {
-                f.split('_')
-                    .next_back()
-                    .and_then(|s| s.trim_end_matches(".log").parse::<usize>().ok())
-            });
-
-            if let (Some(first), Some(last)) = (first_day_idx, last_day_idx) {
-                let span = last - first + 1;
-                if span > days_active {
-                    // Gaps in activity - reduce confidence
-                    confidence *= 0.7;
-                    reasons.push(
-                        "Has gaps in activity (possibly sporadic access, not churned)".to_string(),
-                    );
-                }
-            }
+        // Non-continuous appearance (sporadic pattern suggests not churned, just periodic).
+        // Uses the entity's real calendar-day span/gaps, not a filename-derived index,
+        // and scales the confidence reduction by how gappy the span actually is.
+        let series = compute_activity_time_series(entity);
+        if series.gap_days > 0 && series.span_days > 0 {
+            let gap_ratio = series.gap_days as f64 / series.span_days as f64;
+            confidence *= (1.0 - gap_ratio as f32 * 0.5).max(0.5);
+            reasons.push(format!(
+                "Has gaps in activity ({:.0}% of its {}-day span) — possibly sporadic access, not churned",
+                gap_ratio * 100.0,
+                series.span_days
+            ));
         }
 
         // Cap confidence and determine ephemeral status
-        confidence = f32::min(confidence, 1.0);
+        confidence = confidence.clamp(0.0, 1.0);
         let is_ephemeral = confidence >= 0.4; // Threshold for classification
 
         // Add absence indicator if not seen in recent files
@@ -382,39 +806,32 @@ impl EphemeralPatternAnalyzer {
         (is_ephemeral, confidence, reasons)
     }
 
-    /// Determine activity pattern based on appearance across files
+    /// Determine activity pattern from the entity's real calendar-day activity
+    /// (active-day span, gap days, login trend) rather than parsing a day
+    /// index out of `files_appeared` filenames.
     fn classify_activity_pattern(&self, entity: &EntityChurnRecord) -> String {
-        let days_active = entity.files_appeared.len();
+        let series = compute_activity_time_series(entity);
 
-        if days_active == 1 {
+        if series.active_days <= 1 {
             return "single_burst".to_string();
         }
 
-        if days_active == self.total_files {
+        if series.gap_days == 0 {
             return "consistent".to_string();
         }
 
-        if days_active >= (self.total_files * 2) / 3 {
-            return "consistent".to_string();
+        let gap_ratio = series.gap_days as f64 / series.span_days.max(1) as f64;
+
+        // Trending down across its active days, even if mostly gap-free.
+        if series.login_trend_slope < -0.1 {
+            return "declining".to_string();
         }
 
-        // Check if activity is declining (appeared early but stopped)
-        if let (Some(_first_file), Some(last_file)) =
-            (entity.files_appeared.first(), entity.files_appeared.last())
-        {
-            // Simple heuristic: if last seen was in first half of files, it's declining
-            let last_file_num = last_file
-                .split('_')
-                .next_back()
-                .and_then(|s| s.trim_end_matches(".log").parse::<usize>().ok())
-                .unwrap_or(self.total_files);
-
-            if last_file_num < self.total_files / 2 {
-                return "declining".to_string();
-            }
+        if gap_ratio <= 1.0 / 3.0 {
+            return "consistent".to_string();
         }
 
-        if days_active <= 2 {
+        if series.active_days <= 2 {
             return "single_burst".to_string();
         }
 
@@ -422,8 +839,238 @@ impl EphemeralPatternAnalyzer {
     }
 }
 
+/// Average gap, in days, between an entity's consecutive active calendar
+/// days. `0.0` for entities active on 0 or 1 distinct days.
+fn mean_inter_appearance_gap_days(entity: &EntityChurnRecord) -> f64 {
+    let mut days: Vec<NaiveDate> = entity.logins_by_day.keys().copied().collect();
+    days.sort_unstable();
+    if days.len() < 2 {
+        return 0.0;
+    }
+    let gaps: Vec<f64> = days
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).num_days() as f64)
+        .collect();
+    gaps.iter().sum::<f64>() / gaps.len() as f64
+}
+
+/// Build a per-entity feature vector — `total_logins`, `files_appeared.len()`,
+/// the `first_seen_time`-to-`last_seen_time` span in days,
+/// [`mean_inter_appearance_gap_days`], and a one-hot over every distinct
+/// `mount_type` seen in this run — for [`dbscan`] to cluster on.
+///
+/// Returns entity ids and their feature rows in matching order.
+fn build_feature_matrix(entities: &HashMap<String, EntityChurnRecord>) -> (Vec<String>, Vec<Vec<f64>>) {
+    let mut mount_types: Vec<String> = entities.values().map(|e| e.mount_type.clone()).collect();
+    mount_types.sort_unstable();
+    mount_types.dedup();
+
+    let mut entity_ids: Vec<String> = entities.keys().cloned().collect();
+    entity_ids.sort_unstable();
+
+    let matrix = entity_ids
+        .iter()
+        .map(|entity_id| {
+            let entity = &entities[entity_id];
+            let span_days =
+                (entity.last_seen_time - entity.first_seen_time).num_seconds() as f64 / 86_400.0;
+            let mut features = vec![
+                entity.total_logins as f64,
+                entity.files_appeared.len() as f64,
+                span_days,
+                mean_inter_appearance_gap_days(entity),
+            ];
+            features.extend(
+                mount_types
+                    .iter()
+                    .map(|mount_type| f64::from(u8::from(&entity.mount_type == mount_type))),
+            );
+            features
+        })
+        .collect();
+
+    (entity_ids, matrix)
+}
+
+/// Rescale every feature dimension in place to unit variance (zero-centered),
+/// so dimensions with naturally larger magnitudes (e.g. `total_logins`) don't
+/// dominate the Euclidean distance used by [`dbscan`]. Dimensions with zero
+/// variance (e.g. a `mount_type` column when only one mount is present) are
+/// left at zero rather than divided by zero.
+fn normalize_unit_variance(matrix: &mut [Vec<f64>]) {
+    let Some(dims) = matrix.first().map(Vec::len) else {
+        return;
+    };
+    let n = matrix.len() as f64;
+    for dim in 0..dims {
+        let mean = matrix.iter().map(|row| row[dim]).sum::<f64>() / n;
+        let variance = matrix
+            .iter()
+            .map(|row| (row[dim] - mean).powi(2))
+            .sum::<f64>()
+            / n;
+        let std_dev = variance.sqrt();
+        for row in matrix.iter_mut() {
+            row[dim] = if std_dev > f64::EPSILON {
+                (row[dim] - mean) / std_dev
+            } else {
+                0.0
+            };
+        }
+    }
+}
+
+fn euclidean_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x - y).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// Every row index (other than `point_idx` itself) within `eps` of it.
+fn region_query(matrix: &[Vec<f64>], point_idx: usize, eps: f64) -> Vec<usize> {
+    matrix
+        .iter()
+        .enumerate()
+        .filter(|(idx, row)| *idx != point_idx && euclidean_distance(&matrix[point_idx], row) <= eps)
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/// Density-based (DBSCAN) clustering over normalized feature rows: a point
+/// with at least `min_points` neighbors (itself included) within `eps` seeds
+/// a cluster, which then transitively absorbs every density-reachable point;
+/// everything else is left as noise (`None`). Returns one label per row, in
+/// the same order as `matrix`.
+fn dbscan(matrix: &[Vec<f64>], eps: f64, min_points: usize) -> Vec<Option<usize>> {
+    const UNVISITED: isize = -2;
+    const NOISE: isize = -1;
+
+    let mut labels: Vec<isize> = vec![UNVISITED; matrix.len()];
+    let mut next_cluster_id = 0usize;
+
+    for point_idx in 0..matrix.len() {
+        if labels[point_idx] != UNVISITED {
+            continue;
+        }
+
+        let neighbors = region_query(matrix, point_idx, eps);
+        if neighbors.len() + 1 < min_points {
+            labels[point_idx] = NOISE;
+            continue;
+        }
+
+        let cluster_id = next_cluster_id;
+        next_cluster_id += 1;
+        labels[point_idx] = cluster_id as isize;
+
+        let mut seeds = neighbors;
+        let mut i = 0;
+        while i < seeds.len() {
+            let neighbor_idx = seeds[i];
+            if labels[neighbor_idx] == NOISE {
+                labels[neighbor_idx] = cluster_id as isize;
+            } else if labels[neighbor_idx] == UNVISITED {
+                labels[neighbor_idx] = cluster_id as isize;
+                let neighbor_neighbors = region_query(matrix, neighbor_idx, eps);
+                if neighbor_neighbors.len() + 1 >= min_points {
+                    for nn in neighbor_neighbors {
+                        if !seeds.contains(&nn) {
+                            seeds.push(nn);
+                        }
+                    }
+                }
+            }
+            i += 1;
+        }
+    }
+
+    labels
+        .into_iter()
+        .map(|label| if label == NOISE { None } else { Some(label as usize) })
+        .collect()
+}
+
+/// Cluster every entity into emergent behavioral cohorts with DBSCAN and
+/// attach the resulting `cluster_id`/`is_cluster_outlier` to each record, as
+/// a data-driven complement to the hand-coded `lifecycle`/`ephemeral_*`
+/// labels — density outliers here often aren't caught by either.
+fn cluster_entities(entities: &mut HashMap<String, EntityChurnRecord>, eps: f64, min_points: usize) {
+    if entities.is_empty() {
+        return;
+    }
+
+    let (entity_ids, mut matrix) = build_feature_matrix(entities);
+    normalize_unit_variance(&mut matrix);
+    let labels = dbscan(&matrix, eps, min_points);
+
+    for (entity_id, label) in entity_ids.into_iter().zip(labels) {
+        if let Some(entity) = entities.get_mut(&entity_id) {
+            entity.cluster_id = label;
+            entity.is_cluster_outlier = label.is_none();
+        }
+    }
+}
+
+/// Print size, dominant `mount_path`, and median `total_logins` for each
+/// cohort `cluster_entities` found, plus the overall outlier count.
+fn print_cluster_summary(entities: &HashMap<String, EntityChurnRecord>) {
+    let mut clusters: HashMap<usize, Vec<&EntityChurnRecord>> = HashMap::new();
+    let mut outliers = 0usize;
+    for entity in entities.values() {
+        match entity.cluster_id {
+            Some(cluster_id) => clusters.entry(cluster_id).or_default().push(entity),
+            None => outliers += 1,
+        }
+    }
+
+    println!("\nBehavioral Clusters (DBSCAN):");
+    if clusters.is_empty() {
+        println!("  No cohorts found (everything is an outlier, or too few entities to cluster).");
+    }
+    let mut cluster_ids: Vec<usize> = clusters.keys().copied().collect();
+    cluster_ids.sort_unstable();
+    for cluster_id in cluster_ids {
+        let members = &clusters[&cluster_id];
+
+        let mut mount_path_counts: HashMap<&str, usize> = HashMap::new();
+        for member in members {
+            *mount_path_counts
+                .entry(member.mount_path.as_str())
+                .or_insert(0) += 1;
+        }
+        let dominant_mount_path = mount_path_counts
+            .iter()
+            .max_by_key(|(_, count)| **count)
+            .map_or("", |(path, _)| *path);
+
+        let mut logins: Vec<usize> = members.iter().map(|m| m.total_logins).collect();
+        logins.sort_unstable();
+        let median_logins = logins.get(logins.len() / 2).copied().unwrap_or(0);
+
+        println!(
+            "  Cluster {}: {} entities, dominant mount: {}, median logins: {}",
+            cluster_id,
+            format_number(members.len()),
+            dominant_mount_path,
+            format_number(median_logins)
+        );
+    }
+    println!("  Outliers (noise): {}", format_number(outliers));
+}
+
+/// Default DBSCAN neighborhood radius for [`cluster_entities`], tuned for
+/// unit-variance-normalized features.
+const DEFAULT_CLUSTER_EPS: f64 = 1.5;
+/// Default DBSCAN minimum neighborhood size (including the point itself).
+const DEFAULT_CLUSTER_MIN_POINTS: usize = 4;
+
+/// Size in bytes, used to size the shared progress bar. Remote sources
+/// (`s3://...`) have no cheap local stat, so they report `0` and the
+/// progress bar simply shows no contribution from that file's bytes.
 fn get_file_size(path: &str) -> Result<u64> {
-    Ok(std::fs::metadata(path)?.len())
+    Ok(std::fs::metadata(path).map(|m| m.len()).unwrap_or(0))
 }
 
 fn load_entity_mappings(path: &str) -> Result<HashMap<String, EntityMapping>> {
@@ -513,187 +1160,142 @@ fn load_baseline_entities(path: &str) -> Result<HashMap<String, BaselineEntity>>
     }
 }
 
-pub fn run(
-    log_files: &[String],
-    entity_map: Option<&str>,
-    baseline_entities: Option<&str>,
-    output: Option<&str>,
-    format: Option<&str>,
-) -> Result<()> {
-    println!("\n=== Multi-Day Entity Churn Analysis ===\n");
-    println!("Analyzing {} log files:", log_files.len());
-    for (i, file) in log_files.iter().enumerate() {
-        let size = get_file_size(file)?;
-        println!(
-            "  Day {}: {} ({:.2} GB)",
-            i + 1,
-            file,
-            size as f64 / 1_000_000_000.0
-        );
-    }
-    println!();
+/// Result of parsing a single log file into its own partial entity map.
+///
+/// Each worker thread owns one file end-to-end, so every field here reflects
+/// only what was observed in that one file; [`merge_file_result`] folds these
+/// partial maps into the final cross-file view.
+struct FileChunkResult {
+    file_idx: usize,
+    file_name: String,
+    logins_this_file: usize,
+    entities: HashMap<String, EntityChurnRecord>,
+}
 
-    // Load baseline entities if provided
-    let baseline = if let Some(path) = baseline_entities {
-        println!(
-            "Loading baseline entity list (Vault API metadata) from {}...",
-            path
-        );
-        let baseline_set = load_baseline_entities(path)?;
-        println!(
-            "Loaded {} pre-existing entities from Vault API baseline",
-            format_number(baseline_set.len())
-        );
-        println!();
-        Some(baseline_set)
-    } else {
-        println!("No baseline entity list provided. Cannot distinguish truly NEW entities from pre-existing.");
-        println!("   All Day 1 entities will be marked as 'pre_existing_or_new_day_1'.");
-        println!("   To get accurate results, run: ./vault-audit entity-list --output baseline_entities.json\n");
-        None
-    };
+/// Parse one log file into a partial `EntityChurnRecord` map.
+///
+/// Runs on a rayon worker thread: `lifecycle` is computed as if this file
+/// were the entity's only appearance (correct for `baseline`/`historical_*`,
+/// which don't depend on file order, but the `new_day_N` vs `pre_existing`
+/// call may need to defer to an earlier file — [`merge_file_result`] resolves
+/// that by keeping whichever copy has the earliest `first_seen_time`).
+#[allow(clippy::too_many_arguments)]
+fn process_log_file_chunk(
+    file_idx: usize,
+    log_file: &str,
+    baseline: Option<&HashMap<String, BaselineEntity>>,
+    entity_mappings: Option<&HashMap<String, EntityMapping>>,
+    total_bytes: usize,
+    bytes_processed_total: &AtomicUsize,
+    progress: &Mutex<ProgressBar>,
+) -> Result<FileChunkResult> {
+    let file_name = Path::new(log_file)
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .to_string();
+
+    let file = open_file(log_file)
+        .with_context(|| format!("Failed to open log file: {}", log_file))?;
+    let reader = BufReader::new(file);
+
+    let mut file_entities: HashMap<String, EntityChurnRecord> = HashMap::new();
+    let mut logins_this_file = 0;
+    let mut bytes_this_file = 0;
+
+    for line in reader.lines() {
+        let line = line.context("Failed to read line from log file")?;
+        bytes_this_file += line.len() + 1; // +1 for newline
+
+        // Update the shared progress bar periodically; only one thread holds
+        // the lock at a time, all others keep parsing in the meantime.
+        if bytes_this_file % 10_000 == 0 {
+            let total = bytes_processed_total.fetch_add(10_000, Ordering::Relaxed) + 10_000;
+            if let Ok(progress) = progress.lock() {
+                progress.update(total.min(total_bytes));
+            }
+        }
 
-    // Load entity mappings if provided (historical data from audit logs)
-    let entity_mappings = if let Some(path) = entity_map {
-        println!(
-            "Loading historical entity mappings (audit log enrichment) from {}...",
-            path
-        );
-        let mappings = load_entity_mappings(path)?;
-        println!(
-            "Loaded {} entity mappings with historical audit log data",
-            format_number(mappings.len())
-        );
-        println!();
-        Some(mappings)
-    } else {
-        None
-    };
-
-    // Track all entities across all files
-    // Pre-allocate for typical entity counts in enterprise environments
-    let mut entities: HashMap<String, EntityChurnRecord> = HashMap::with_capacity(5000);
-    let mut daily_stats: Vec<DailyStats> = Vec::new();
-
-    // Process each log file in order
-    for (file_idx, log_file) in log_files.iter().enumerate() {
-        let file_name = Path::new(log_file)
-            .file_name()
-            .unwrap()
-            .to_string_lossy()
-            .to_string();
-
-        println!("\nProcessing Day {} ({})...", file_idx + 1, file_name);
-
-        let file = open_file(log_file)
-            .with_context(|| format!("Failed to open log file: {}", log_file))?;
-        let file_size = get_file_size(log_file)? as usize;
-
-        let reader = BufReader::new(file);
-        let mut progress = ProgressBar::new(file_size, "Processing");
-
-        let mut new_entities_this_file = 0;
-        let mut returning_entities_this_file = HashSet::new();
-        let mut logins_this_file = 0;
-        let mut bytes_processed = 0;
-
-        for line in reader.lines() {
-            let line = line.context("Failed to read line from log file")?;
-            bytes_processed += line.len() + 1; // +1 for newline
-
-            // Update progress periodically
-            if bytes_processed % 10_000 == 0 {
-                progress.update(bytes_processed.min(file_size));
-            }
-
-            let trimmed = line.trim();
-            if trimmed.is_empty() {
-                continue;
-            }
-
-            let entry: AuditEntry = match serde_json::from_str(trimmed) {
-                Ok(e) => e,
-                Err(_) => continue,
-            };
-
-            // Only process login operations (auth paths ending in /login)
-            let Some(ref request) = entry.request else {
-                continue;
-            };
-            let Some(ref path) = request.path else {
-                continue;
-            };
-            if !path.ends_with("/login") {
-                continue;
-            }
-
-            logins_this_file += 1;
-
-            // Extract entity info
-            let Some(ref auth) = entry.auth else {
-                continue;
-            };
-            let Some(ref entity_id) = auth.entity_id else {
-                continue;
-            };
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
 
-            let display_name = auth
-                .display_name
-                .clone()
-                .unwrap_or_else(|| entity_id.clone());
-            let mount_path = request.path.clone().unwrap_or_default();
-            let mount_type = request.mount_type.clone().unwrap_or_default();
-            let token_type = auth.token_type.clone().unwrap_or_default();
-
-            // Parse timestamp
-            let first_seen_time = chrono::DateTime::parse_from_rfc3339(&entry.time)
-                .ok()
-                .map_or_else(Utc::now, |dt| dt.with_timezone(&Utc));
-
-            // Check if this entity exists from a previous file
-            if let Some(entity_record) = entities.get_mut(entity_id) {
-                // Returning entity
-                entity_record.total_logins += 1;
-                entity_record.last_seen_file.clone_from(&file_name);
-                entity_record.last_seen_time = first_seen_time;
-                if !entity_record.files_appeared.contains(&file_name) {
-                    entity_record.files_appeared.push(file_name.clone());
-                }
-                returning_entities_this_file.insert(entity_id.clone());
-            } else {
-                // New entity (first time across all files)
-                new_entities_this_file += 1;
+        let entry: AuditEntry = match serde_json::from_str(trimmed) {
+            Ok(e) => e,
+            Err(_) => continue,
+        };
+
+        // Only process login operations (auth paths ending in /login)
+        let Some(ref request) = entry.request else {
+            continue;
+        };
+        let Some(ref path) = request.path else {
+            continue;
+        };
+        if !path.ends_with("/login") {
+            continue;
+        }
 
-                // Determine lifecycle based on baseline and which file this is
-                let lifecycle = if let Some(ref baseline_set) = baseline {
-                    if baseline_set.contains_key(entity_id) {
-                        "pre_existing_baseline".to_string()
-                    } else {
-                        // Not in baseline, so truly NEW during analysis period
-                        match file_idx {
-                            0 => "new_day_1".to_string(),
-                            1 => "new_day_2".to_string(),
-                            2 => "new_day_3".to_string(),
-                            _ => format!("new_day_{}", file_idx + 1),
-                        }
-                    }
+        logins_this_file += 1;
+
+        // Extract entity info
+        let Some(ref auth) = entry.auth else {
+            continue;
+        };
+        let Some(ref entity_id) = auth.entity_id else {
+            continue;
+        };
+
+        let display_name = auth
+            .display_name
+            .clone()
+            .unwrap_or_else(|| entity_id.clone());
+        let mount_path = request.path.clone().unwrap_or_default();
+        let mount_type = request.mount_type.clone().unwrap_or_default();
+        let token_type = auth.token_type.clone().unwrap_or_default();
+
+        // Parse timestamp
+        let seen_time = chrono::DateTime::parse_from_rfc3339(&entry.time)
+            .ok()
+            .map_or_else(Utc::now, |dt| dt.with_timezone(&Utc));
+
+        if let Some(entity_record) = file_entities.get_mut(entity_id) {
+            // Seen again within this same file
+            entity_record.total_logins += 1;
+            entity_record.last_seen_file.clone_from(&file_name);
+            entity_record.last_seen_time = seen_time;
+            *entity_record
+                .logins_by_day
+                .entry(seen_time.date_naive())
+                .or_insert(0) += 1;
+        } else {
+            // First time this entity is seen in this file
+            let lifecycle = if let Some(baseline_set) = baseline {
+                if baseline_set.contains_key(entity_id) {
+                    "pre_existing_baseline".to_string()
                 } else {
-                    // No baseline provided, can't distinguish
+                    // Not in baseline, so truly NEW during analysis period
                     match file_idx {
-                        0 => "pre_existing_or_new_day_1".to_string(),
+                        0 => "new_day_1".to_string(),
                         1 => "new_day_2".to_string(),
                         2 => "new_day_3".to_string(),
                         _ => format!("new_day_{}", file_idx + 1),
                     }
-                };
+                }
+            } else {
+                // No baseline provided, can't distinguish
+                match file_idx {
+                    0 => "pre_existing_or_new_day_1".to_string(),
+                    1 => "new_day_2".to_string(),
+                    2 => "new_day_3".to_string(),
+                    _ => format!("new_day_{}", file_idx + 1),
+                }
+            };
 
-                // Get baseline metadata if entity exists in baseline
-                let (
-                    baseline_entity_name,
-                    baseline_created,
-                    baseline_alias_name,
-                    baseline_mount_path,
-                ) = if let Some(ref baseline_map) = baseline {
+            // Get baseline metadata if entity exists in baseline
+            let (baseline_entity_name, baseline_created, baseline_alias_name, baseline_mount_path) =
+                if let Some(baseline_map) = baseline {
                     if let Some(baseline_entity) = baseline_map.get(entity_id) {
                         let name = baseline_entity.get_name();
                         let created = baseline_entity.get_created();
@@ -722,104 +1324,1573 @@ pub fn run(
                     (None, None, None, None)
                 };
 
-                // Fetch historical data from entity_mappings
-                let (
+            // Fetch historical data from entity_mappings
+            let (
+                historical_display_name,
+                historical_first_seen,
+                historical_last_seen,
+                historical_login_count,
+            ) = if let Some(mappings) = entity_mappings {
+                if let Some(mapping) = mappings.get(entity_id) {
+                    (
+                        Some(mapping.display_name.clone()),
+                        Some(mapping.first_seen.clone()),
+                        Some(mapping.last_seen.clone()),
+                        Some(mapping.login_count),
+                    )
+                } else {
+                    (None, None, None, None)
+                }
+            } else {
+                (None, None, None, None)
+            };
+
+            file_entities.insert(
+                entity_id.clone(),
+                EntityChurnRecord {
+                    entity_id: entity_id.clone(),
+                    display_name,
+                    mount_path,
+                    mount_type,
+                    token_type,
+                    first_seen_file: file_name.clone(),
+                    first_seen_time: seen_time,
+                    last_seen_file: file_name.clone(),
+                    last_seen_time: seen_time,
+                    files_appeared: vec![file_name.clone()],
+                    total_logins: 1,
+                    logins_by_day: BTreeMap::from([(seen_time.date_naive(), 1)]),
+                    lifecycle,
+                    activity_pattern: "unknown".to_string(), // Will be computed in second pass
+                    is_ephemeral_pattern: false,              // Will be computed in second pass
+                    ephemeral_confidence: 0.0, // Will be computed in second pass
+                    ephemeral_reasons: Vec::new(), // Will be computed in second pass
+                    baseline_entity_name,
+                    baseline_created,
+                    baseline_alias_name,
+                    baseline_mount_path,
                     historical_display_name,
                     historical_first_seen,
                     historical_last_seen,
                     historical_login_count,
-                ) = if let Some(ref mappings) = entity_mappings {
-                    if let Some(mapping) = mappings.get(entity_id) {
-                        (
-                            Some(mapping.display_name.clone()),
-                            Some(mapping.first_seen.clone()),
-                            Some(mapping.last_seen.clone()),
-                            Some(mapping.login_count),
-                        )
-                    } else {
-                        (None, None, None, None)
+                    cluster_id: None, // Computed in the clustering pass in `run`
+                    is_cluster_outlier: false,
+                },
+            );
+        }
+    }
+
+    Ok(FileChunkResult {
+        file_idx,
+        file_name,
+        logins_this_file,
+        entities: file_entities,
+    })
+}
+
+/// Fold one file's partial record into the cross-file merged record.
+///
+/// Earliest `first_seen_time`/`first_seen_file` (and the lifecycle computed
+/// against it) wins, latest `last_seen_time`/`last_seen_file` wins,
+/// `total_logins` sums, and `files_appeared` unions. `files_appeared` is then
+/// re-sorted by day index so later pattern analysis (which still reasons
+/// about "early" vs "late" files) sees them in chronological order.
+fn merge_file_result(
+    merged: &mut HashMap<String, EntityChurnRecord>,
+    incoming: HashMap<String, EntityChurnRecord>,
+) {
+    for (entity_id, incoming_record) in incoming {
+        match merged.get_mut(&entity_id) {
+            None => {
+                merged.insert(entity_id, incoming_record);
+            }
+            Some(existing) => {
+                if incoming_record.first_seen_time < existing.first_seen_time {
+                    existing.first_seen_file = incoming_record.first_seen_file;
+                    existing.first_seen_time = incoming_record.first_seen_time;
+                    existing.lifecycle = incoming_record.lifecycle;
+                }
+                if incoming_record.last_seen_time > existing.last_seen_time {
+                    existing.last_seen_file = incoming_record.last_seen_file;
+                    existing.last_seen_time = incoming_record.last_seen_time;
+                }
+                existing.total_logins += incoming_record.total_logins;
+                for f in incoming_record.files_appeared {
+                    if !existing.files_appeared.contains(&f) {
+                        existing.files_appeared.push(f);
                     }
+                }
+                for (day, count) in incoming_record.logins_by_day {
+                    *existing.logins_by_day.entry(day).or_insert(0) += count;
+                }
+            }
+        }
+    }
+}
+
+/// Re-sort every entity's `files_appeared` by day index (the file's position
+/// in the original `log_files` argument) now that all per-file chunks have
+/// been folded in. Lifecycle classification and the ephemeral analyzer both
+/// assume chronological order, which parallel merging doesn't preserve.
+fn resort_files_appeared_by_day(
+    entities: &mut HashMap<String, EntityChurnRecord>,
+    file_day_index: &HashMap<String, usize>,
+) {
+    for record in entities.values_mut() {
+        record
+            .files_appeared
+            .sort_by_key(|f| file_day_index.get(f).copied().unwrap_or(usize::MAX));
+    }
+}
+
+/// Arrow schema shared by the Arrow IPC and Parquet exporters, one column per
+/// `EntityChurnRecord` field (kept typed rather than flattened to strings so
+/// downstream tools like DuckDB/pandas/Spark don't have to re-parse
+/// comma/semicolon-joined lists).
+fn churn_arrow_schema() -> Arc<Schema> {
+    let string_list = || DataType::List(Arc::new(Field::new("item", DataType::Utf8, false)));
+    Arc::new(Schema::new(vec![
+        Field::new("entity_id", DataType::Utf8, false),
+        Field::new("display_name", DataType::Utf8, false),
+        Field::new("mount_path", DataType::Utf8, false),
+        Field::new(
+            "mount_type",
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+            false,
+        ),
+        Field::new("token_type", DataType::Utf8, false),
+        Field::new("first_seen_file", DataType::Utf8, false),
+        Field::new(
+            "first_seen_time",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            false,
+        ),
+        Field::new("last_seen_file", DataType::Utf8, false),
+        Field::new(
+            "last_seen_time",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            false,
+        ),
+        Field::new("files_appeared", string_list(), false),
+        Field::new("total_logins", DataType::UInt64, false),
+        Field::new(
+            "lifecycle",
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+            false,
+        ),
+        Field::new("activity_pattern", DataType::Utf8, false),
+        Field::new("is_ephemeral_pattern", DataType::Boolean, false),
+        Field::new("ephemeral_confidence", DataType::Float32, false),
+        Field::new("ephemeral_reasons", string_list(), false),
+        Field::new("baseline_entity_name", DataType::Utf8, true),
+        Field::new("baseline_created", DataType::Utf8, true),
+        Field::new("baseline_alias_name", DataType::Utf8, true),
+        Field::new("baseline_mount_path", DataType::Utf8, true),
+        Field::new("historical_display_name", DataType::Utf8, true),
+        Field::new("historical_first_seen", DataType::Utf8, true),
+        Field::new("historical_last_seen", DataType::Utf8, true),
+        Field::new("historical_login_count", DataType::UInt64, true),
+        Field::new("cluster_id", DataType::UInt64, true),
+        Field::new("is_cluster_outlier", DataType::Boolean, false),
+    ]))
+}
+
+/// Build a string-list column (used for `files_appeared` / `ephemeral_reasons`).
+fn string_list_array<'a, I>(values: I) -> arrow::array::ListArray
+where
+    I: IntoIterator<Item = &'a [String]>,
+{
+    let mut builder = ListBuilder::new(StringBuilder::new());
+    for row in values {
+        for item in row {
+            builder.values().append_value(item);
+        }
+        builder.append(true);
+    }
+    builder.finish()
+}
+
+/// Build a dictionary-encoded string column (used for `mount_type` /
+/// `lifecycle`, which both have few distinct values across a large entity
+/// set).
+fn dict_string_array<'a, I>(values: I) -> DictionaryArray<Int32Type>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut builder = StringDictionaryBuilder::<Int32Type>::new();
+    for value in values {
+        builder.append_value(value);
+    }
+    builder.finish()
+}
+
+/// Assemble all `EntityChurnRecord`s into a single Arrow `RecordBatch` matching
+/// [`churn_arrow_schema`].
+fn churn_record_batch(entities: &[EntityChurnRecord]) -> Result<RecordBatch> {
+    let schema = churn_arrow_schema();
+
+    let nullable_str = |f: &dyn Fn(&EntityChurnRecord) -> Option<&str>| {
+        StringArray::from(entities.iter().map(f).collect::<Vec<_>>())
+    };
+
+    let first_seen_time = TimestampMicrosecondArray::from_iter_values(
+        entities.iter().map(|e| e.first_seen_time.timestamp_micros()),
+    )
+    .with_timezone("UTC".to_string());
+    let last_seen_time = TimestampMicrosecondArray::from_iter_values(
+        entities.iter().map(|e| e.last_seen_time.timestamp_micros()),
+    )
+    .with_timezone("UTC".to_string());
+
+    let columns: Vec<Arc<dyn Array>> = vec![
+        Arc::new(StringArray::from_iter_values(
+            entities.iter().map(|e| e.entity_id.as_str()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            entities.iter().map(|e| e.display_name.as_str()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            entities.iter().map(|e| e.mount_path.as_str()),
+        )),
+        Arc::new(dict_string_array(
+            entities.iter().map(|e| e.mount_type.as_str()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            entities.iter().map(|e| e.token_type.as_str()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            entities.iter().map(|e| e.first_seen_file.as_str()),
+        )),
+        Arc::new(first_seen_time),
+        Arc::new(StringArray::from_iter_values(
+            entities.iter().map(|e| e.last_seen_file.as_str()),
+        )),
+        Arc::new(last_seen_time),
+        Arc::new(string_list_array(
+            entities.iter().map(|e| e.files_appeared.as_slice()),
+        )),
+        Arc::new(UInt64Array::from_iter_values(
+            entities.iter().map(|e| e.total_logins as u64),
+        )),
+        Arc::new(dict_string_array(
+            entities.iter().map(|e| e.lifecycle.as_str()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            entities.iter().map(|e| e.activity_pattern.as_str()),
+        )),
+        Arc::new(BooleanArray::from_iter(
+            entities.iter().map(|e| Some(e.is_ephemeral_pattern)),
+        )),
+        Arc::new(Float32Array::from_iter_values(
+            entities.iter().map(|e| e.ephemeral_confidence),
+        )),
+        Arc::new(string_list_array(
+            entities.iter().map(|e| e.ephemeral_reasons.as_slice()),
+        )),
+        Arc::new(nullable_str(&|e| e.baseline_entity_name.as_deref())),
+        Arc::new(nullable_str(&|e| e.baseline_created.as_deref())),
+        Arc::new(nullable_str(&|e| e.baseline_alias_name.as_deref())),
+        Arc::new(nullable_str(&|e| e.baseline_mount_path.as_deref())),
+        Arc::new(nullable_str(&|e| e.historical_display_name.as_deref())),
+        Arc::new(nullable_str(&|e| e.historical_first_seen.as_deref())),
+        Arc::new(nullable_str(&|e| e.historical_last_seen.as_deref())),
+        Arc::new(UInt64Array::from(
+            entities
+                .iter()
+                .map(|e| e.historical_login_count.map(|n| n as u64))
+                .collect::<Vec<_>>(),
+        )),
+        Arc::new(UInt64Array::from(
+            entities
+                .iter()
+                .map(|e| e.cluster_id.map(|n| n as u64))
+                .collect::<Vec<_>>(),
+        )),
+        Arc::new(BooleanArray::from_iter(
+            entities.iter().map(|e| Some(e.is_cluster_outlier)),
+        )),
+    ];
+
+    RecordBatch::try_new(schema, columns).context("Failed to assemble churn Arrow RecordBatch")
+}
+
+/// Write entity churn records as Arrow IPC (`.arrow`/`.feather`).
+fn write_arrow_export(output_path: &str, entities: &[EntityChurnRecord]) -> Result<()> {
+    let schema = churn_arrow_schema();
+    let batch = churn_record_batch(entities)?;
+    let file = File::create(output_path)
+        .with_context(|| format!("Failed to create output file: {}", output_path))?;
+    let mut writer = ArrowFileWriter::try_new(file, &schema)
+        .context("Failed to create Arrow IPC writer")?;
+    writer
+        .write(&batch)
+        .context("Failed to write Arrow IPC batch")?;
+    writer.finish().context("Failed to finish Arrow IPC file")?;
+    Ok(())
+}
+
+/// Row group size for churn Parquet exports - bounds peak memory when a
+/// multi-day run produces millions of entities.
+const PARQUET_ROW_GROUP_SIZE: usize = 100_000;
+
+/// Write entity churn records as Parquet, chunked into
+/// [`PARQUET_ROW_GROUP_SIZE`]-row row groups.
+fn write_parquet_export(output_path: &str, entities: &[EntityChurnRecord]) -> Result<()> {
+    let schema = churn_arrow_schema();
+    let file = File::create(output_path)
+        .with_context(|| format!("Failed to create output file: {}", output_path))?;
+    let properties = WriterProperties::builder()
+        .set_max_row_group_size(PARQUET_ROW_GROUP_SIZE)
+        .build();
+    let mut writer = ParquetArrowWriter::try_new(file, schema, Some(properties))
+        .context("Failed to create Parquet writer")?;
+    for chunk in entities.chunks(PARQUET_ROW_GROUP_SIZE) {
+        let batch = churn_record_batch(chunk)?;
+        writer
+            .write(&batch)
+            .context("Failed to write Parquet batch")?;
+    }
+    writer.close().context("Failed to finish Parquet file")?;
+    Ok(())
+}
+
+/// Write the `"bin"` export: a data file of length-prefixed bincode-encoded
+/// [`EntityChurnRecordBin`]s, plus a companion `<output_path>.idx` file
+/// mapping each `entity_id` to its `(offset, length)` in the data file. This
+/// lets [`read_binary_record`] fetch a single entity in O(1) without
+/// deserializing the rest of the export — unlike the monolithic JSON/CSV
+/// formats, which require a full parse to find one record.
+fn write_binary_export(output_path: &str, entities: &[EntityChurnRecord]) -> Result<()> {
+    let data_file = File::create(output_path)
+        .with_context(|| format!("Failed to create binary export file: {}", output_path))?;
+    let mut writer = BufWriter::new(data_file);
+
+    let mut index: Vec<(String, u64, u64)> = Vec::with_capacity(entities.len());
+    let mut offset: u64 = 0;
+    for entity in entities {
+        let record: EntityChurnRecordBin = entity.into();
+        let bytes =
+            bincode::serialize(&record).context("Failed to encode binary entity record")?;
+        let length = bytes.len() as u64;
+        writer
+            .write_all(&length.to_le_bytes())
+            .context("Failed to write binary record length prefix")?;
+        writer
+            .write_all(&bytes)
+            .context("Failed to write binary entity record")?;
+        index.push((entity.entity_id.clone(), offset, length));
+        offset += 8 + length;
+    }
+    writer.flush().context("Failed to flush binary export file")?;
+
+    index.sort_by(|a, b| a.0.cmp(&b.0));
+    let index_path = format!("{}.idx", output_path);
+    let index_file = File::create(&index_path)
+        .with_context(|| format!("Failed to create binary index file: {}", index_path))?;
+    let mut index_writer = BufWriter::new(index_file);
+    for (entity_id, offset, length) in &index {
+        let id_bytes = entity_id.as_bytes();
+        index_writer
+            .write_all(&(id_bytes.len() as u32).to_le_bytes())
+            .context("Failed to write binary index entry")?;
+        index_writer
+            .write_all(id_bytes)
+            .context("Failed to write binary index entity id")?;
+        index_writer
+            .write_all(&offset.to_le_bytes())
+            .context("Failed to write binary index offset")?;
+        index_writer
+            .write_all(&length.to_le_bytes())
+            .context("Failed to write binary index length")?;
+    }
+    index_writer
+        .flush()
+        .context("Failed to flush binary index file")?;
+
+    Ok(())
+}
+
+/// Load a `"bin"` export's `<output_path>.idx` file into an in-memory
+/// `entity_id -> (offset, length)` map for O(1) per-id lookups against the
+/// data file.
+fn load_binary_index(output_path: &str) -> Result<HashMap<String, (u64, u64)>> {
+    let index_path = format!("{}.idx", output_path);
+    let index_bytes = std::fs::read(&index_path)
+        .with_context(|| format!("Failed to read binary index file: {}", index_path))?;
+
+    let mut index = HashMap::new();
+    let mut cursor = 0usize;
+    while cursor < index_bytes.len() {
+        let id_len =
+            u32::from_le_bytes(index_bytes[cursor..cursor + 4].try_into().unwrap()) as usize;
+        cursor += 4;
+        let entity_id = std::str::from_utf8(&index_bytes[cursor..cursor + id_len])
+            .context("Corrupt entity id in binary index")?
+            .to_string();
+        cursor += id_len;
+        let offset = u64::from_le_bytes(index_bytes[cursor..cursor + 8].try_into().unwrap());
+        cursor += 8;
+        let length = u64::from_le_bytes(index_bytes[cursor..cursor + 8].try_into().unwrap());
+        cursor += 8;
+        index.insert(entity_id, (offset, length));
+    }
+    Ok(index)
+}
+
+/// Fetch a single entity record by id from a `"bin"` export written by
+/// [`write_binary_export`], seeking directly to its offset in the data file
+/// instead of deserializing every record in the export.
+pub fn read_binary_record(
+    output_path: &str,
+    entity_id: &str,
+) -> Result<Option<EntityChurnRecordBin>> {
+    let index = load_binary_index(output_path)?;
+    let Some(&(offset, length)) = index.get(entity_id) else {
+        return Ok(None);
+    };
+
+    let mut data_file = File::open(output_path)
+        .with_context(|| format!("Failed to open binary export file: {}", output_path))?;
+    data_file
+        .seek(SeekFrom::Start(offset + 8))
+        .context("Failed to seek to entity record")?;
+    let mut buf = vec![0u8; length as usize];
+    data_file
+        .read_exact(&mut buf)
+        .context("Failed to read entity record bytes")?;
+    let record: EntityChurnRecordBin =
+        bincode::deserialize(&buf).context("Failed to decode binary entity record")?;
+    Ok(Some(record))
+}
+
+/// Default size, in bytes, a `--state-store` write-ahead log is allowed to
+/// grow to before [`persist_state`] compacts it into a fresh snapshot.
+const DEFAULT_STATE_WAL_COMPACT_THRESHOLD_BYTES: u64 = 8 * 1024 * 1024;
+
+fn state_snapshot_path(state_store: &str) -> String {
+    format!("{}.snapshot", state_store)
+}
+
+fn state_wal_path(state_store: &str) -> String {
+    format!("{}.wal", state_store)
+}
+
+/// One append-only entry in a `--state-store` write-ahead log: either an
+/// entity that changed during a run, or a log file that's now fully ingested
+/// and should be skipped on future runs.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+enum StateWalEntry {
+    UpsertEntity(EntityChurnStateRecord),
+    FileIngested(String),
+}
+
+/// Length-prefixed bincode snapshot of the full accumulated state: every
+/// entity plus the set of log file names already folded into it.
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct StateSnapshot {
+    entities: Vec<EntityChurnStateRecord>,
+    ingested_files: Vec<String>,
+}
+
+fn write_length_prefixed<T: Serialize>(writer: &mut impl Write, value: &T) -> Result<()> {
+    let bytes = bincode::serialize(value).context("Failed to encode state entry")?;
+    writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+fn read_length_prefixed_entries<T: serde::de::DeserializeOwned>(
+    reader: &mut impl BufRead,
+) -> Result<Vec<T>> {
+    let mut entries = Vec::new();
+    loop {
+        let mut len_buf = [0u8; 8];
+        match reader.read_exact(&mut len_buf) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(e).context("Failed to read state entry length"),
+        }
+        let len = u64::from_le_bytes(len_buf) as usize;
+        let mut buf = vec![0u8; len];
+        reader
+            .read_exact(&mut buf)
+            .context("Failed to read state entry bytes")?;
+        entries.push(bincode::deserialize(&buf).context("Failed to decode state entry")?);
+    }
+    Ok(entries)
+}
+
+/// Load accumulated entity state from a prior run: the last compacted
+/// snapshot (if any), with every write-ahead log entry since then replayed
+/// on top. Returns an empty map and file set if neither file exists yet
+/// (first run against this `state_store`).
+fn load_state(state_store: &str) -> Result<(HashMap<String, EntityChurnRecord>, std::collections::HashSet<String>)> {
+    let snapshot_path = state_snapshot_path(state_store);
+    let snapshot = if Path::new(&snapshot_path).exists() {
+        let file = File::open(&snapshot_path).context("Failed to open state snapshot")?;
+        let mut reader = BufReader::new(file);
+        let entries: Vec<StateSnapshot> = read_length_prefixed_entries(&mut reader)?;
+        entries.into_iter().next().unwrap_or_default()
+    } else {
+        StateSnapshot::default()
+    };
+
+    let mut entities: HashMap<String, EntityChurnRecord> = snapshot
+        .entities
+        .into_iter()
+        .map(|state| (state.entity_id.clone(), EntityChurnRecord::from(state)))
+        .collect();
+    let mut ingested_files: std::collections::HashSet<String> =
+        snapshot.ingested_files.into_iter().collect();
+
+    let wal_path = state_wal_path(state_store);
+    if Path::new(&wal_path).exists() {
+        let file = File::open(&wal_path).context("Failed to open state write-ahead log")?;
+        let mut reader = BufReader::new(file);
+        let entries: Vec<StateWalEntry> = read_length_prefixed_entries(&mut reader)?;
+        for entry in entries {
+            match entry {
+                StateWalEntry::UpsertEntity(state) => {
+                    entities.insert(state.entity_id.clone(), EntityChurnRecord::from(state));
+                }
+                StateWalEntry::FileIngested(file_name) => {
+                    ingested_files.insert(file_name);
+                }
+            }
+        }
+    }
+
+    Ok((entities, ingested_files))
+}
+
+/// Append this run's changes to the write-ahead log, then compact (rewrite
+/// the full snapshot and truncate the WAL) once the WAL exceeds
+/// `compact_threshold_bytes`, so a long-running daily deployment doesn't
+/// grow the sidecar file unboundedly.
+fn persist_state(
+    state_store: &str,
+    entities: &HashMap<String, EntityChurnRecord>,
+    ingested_files: &std::collections::HashSet<String>,
+    dirty_entity_ids: &std::collections::HashSet<String>,
+    newly_ingested_files: &[String],
+    compact_threshold_bytes: u64,
+) -> Result<()> {
+    let wal_path = state_wal_path(state_store);
+    {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&wal_path)
+            .context("Failed to open state write-ahead log for append")?;
+        let mut writer = BufWriter::new(file);
+        for entity_id in dirty_entity_ids {
+            if let Some(entity) = entities.get(entity_id) {
+                write_length_prefixed(
+                    &mut writer,
+                    &StateWalEntry::UpsertEntity(EntityChurnStateRecord::from(entity)),
+                )?;
+            }
+        }
+        for file_name in newly_ingested_files {
+            write_length_prefixed(&mut writer, &StateWalEntry::FileIngested(file_name.clone()))?;
+        }
+        writer.flush()?;
+    }
+
+    let wal_len = std::fs::metadata(&wal_path).map(|m| m.len()).unwrap_or(0);
+    if wal_len <= compact_threshold_bytes {
+        return Ok(());
+    }
+
+    // Compact: `entities` and `ingested_files` already reflect every record
+    // and file folded in so far (this run's plus everything replayed from
+    // the prior snapshot/WAL), so they're the new snapshot as-is. Write it
+    // to a temp file and rename over the old snapshot so readers never see
+    // a partially-written one, then truncate the WAL.
+    let mut all_ingested_files: Vec<String> = ingested_files.iter().cloned().collect();
+    all_ingested_files.sort_unstable();
+
+    let snapshot = StateSnapshot {
+        entities: entities.values().map(EntityChurnStateRecord::from).collect(),
+        ingested_files: all_ingested_files,
+    };
+
+    let snapshot_path = state_snapshot_path(state_store);
+    let tmp_path = format!("{}.tmp", snapshot_path);
+    {
+        let file = File::create(&tmp_path).context("Failed to create temp state snapshot")?;
+        let mut writer = BufWriter::new(file);
+        write_length_prefixed(&mut writer, &snapshot)?;
+        writer.flush()?;
+    }
+    std::fs::rename(&tmp_path, &snapshot_path).context("Failed to install compacted state snapshot")?;
+    std::fs::remove_file(&wal_path).context("Failed to truncate state write-ahead log after compaction")?;
+
+    Ok(())
+}
+
+/// One operator-defined threat-intelligence rule, loaded from an external
+/// `--signature-rules` JSON file and evaluated against every
+/// [`EntityChurnRecord`] during the report phase. Unlike the learned
+/// ephemeral heuristics, a matched rule is a deterministic, version-controllable
+/// signal (e.g. "this mount should never see this naming pattern").
+///
+/// Every predicate field is optional; a rule matches an entity when all of
+/// its present predicates match (`None` predicates are ignored).
+#[derive(Debug, Deserialize, Clone)]
+struct SignatureRule {
+    label: String,
+    #[serde(default = "default_severity")]
+    severity: String,
+    #[serde(default)]
+    display_name_glob: Option<String>,
+    #[serde(default)]
+    mount_path_glob: Option<String>,
+    #[serde(default)]
+    mount_type: Option<String>,
+    #[serde(default)]
+    token_type: Option<String>,
+    #[serde(default)]
+    min_total_logins: Option<usize>,
+    #[serde(default)]
+    max_total_logins: Option<usize>,
+    /// Matches entities active on exactly one calendar day with more than
+    /// this many logins — a single-day login burst.
+    #[serde(default)]
+    single_day_burst_over: Option<usize>,
+}
+
+fn default_severity() -> String {
+    "info".to_string()
+}
+
+fn load_signature_rules(path: &str) -> Result<Vec<SignatureRule>> {
+    let file = File::open(path).context("Failed to open signature rules file")?;
+    let rules: Vec<SignatureRule> =
+        serde_json::from_reader(file).context("Failed to parse signature rules JSON")?;
+    Ok(rules)
+}
+
+/// Simple `*`-wildcard glob match (no other metacharacters), matching the
+/// level of pattern matching already used for CLI file globs elsewhere in
+/// this tool rather than pulling in a full regex engine for rule predicates.
+fn glob_match(pattern: &str, value: &str) -> bool {
+    let segments: Vec<&str> = pattern.split('*').collect();
+    if segments.len() == 1 {
+        return pattern == value;
+    }
+
+    let mut rest = value;
+    for (idx, segment) in segments.iter().enumerate() {
+        if segment.is_empty() {
+            continue;
+        }
+        if idx == 0 {
+            let Some(stripped) = rest.strip_prefix(segment) else {
+                return false;
+            };
+            rest = stripped;
+        } else if idx == segments.len() - 1 {
+            return rest.ends_with(segment);
+        } else {
+            let Some(found) = rest.find(segment) else {
+                return false;
+            };
+            rest = &rest[found + segment.len()..];
+        }
+    }
+    true
+}
+
+fn entity_matches_rule(entity: &EntityChurnRecord, rule: &SignatureRule) -> bool {
+    if let Some(glob) = &rule.display_name_glob {
+        if !glob_match(glob, &entity.display_name) {
+            return false;
+        }
+    }
+    if let Some(glob) = &rule.mount_path_glob {
+        if !glob_match(glob, &entity.mount_path) {
+            return false;
+        }
+    }
+    if let Some(mount_type) = &rule.mount_type {
+        if &entity.mount_type != mount_type {
+            return false;
+        }
+    }
+    if let Some(token_type) = &rule.token_type {
+        if &entity.token_type != token_type {
+            return false;
+        }
+    }
+    if let Some(min) = rule.min_total_logins {
+        if entity.total_logins < min {
+            return false;
+        }
+    }
+    if let Some(max) = rule.max_total_logins {
+        if entity.total_logins > max {
+            return false;
+        }
+    }
+    if let Some(threshold) = rule.single_day_burst_over {
+        let is_single_day_burst =
+            entity.logins_by_day.len() == 1 && entity.total_logins > threshold;
+        if !is_single_day_burst {
+            return false;
+        }
+    }
+    true
+}
+
+/// Evaluate every rule against every entity and print a "Signature Matches"
+/// section grouping matched entities by rule label, alongside the count and
+/// severity. A no-op when `rules` is empty.
+fn print_signature_matches(entities: &HashMap<String, EntityChurnRecord>, rules: &[SignatureRule]) {
+    if rules.is_empty() {
+        return;
+    }
+
+    println!("\nSignature Matches:");
+    let mut any_matches = false;
+    for rule in rules {
+        let matches: Vec<&EntityChurnRecord> = entities
+            .values()
+            .filter(|entity| entity_matches_rule(entity, rule))
+            .collect();
+        if matches.is_empty() {
+            continue;
+        }
+        any_matches = true;
+        println!(
+            "  [{}] {}: {} entities",
+            rule.severity,
+            rule.label,
+            format_number(matches.len())
+        );
+        for entity in matches.iter().take(5) {
+            println!("    - {}", entity.display_name);
+        }
+        if matches.len() > 5 {
+            println!("    ... and {} more", format_number(matches.len() - 5));
+        }
+    }
+    if !any_matches {
+        println!("  No entities matched any rule.");
+    }
+}
+
+/// Renders this run's ephemeral-entity count and per-entity activity gaps as
+/// Prometheus metrics: `vault_audit_ephemeral_entities_total` (count of
+/// entities with `is_ephemeral_pattern`), plus one
+/// `vault_audit_entity_activity_gap_seconds{entity_id,display_name}` gauge
+/// per entity measuring how far behind the most recently active entity
+/// this entity's `last_seen_time` falls - a growing gap flags an entity
+/// that has gone quiet relative to its peers.
+fn build_metrics_exporter(entities: &HashMap<String, EntityChurnRecord>) -> MetricsExporter {
+    let mut exporter = MetricsExporter::new();
+
+    let ephemeral_count = entities.values().filter(|e| e.is_ephemeral_pattern).count();
+    exporter.gauge(
+        "vault_audit_ephemeral_entities_total",
+        "Entities flagged with an ephemeral activity pattern",
+        &[],
+        ephemeral_count as f64,
+    );
+
+    let Some(latest_seen) = entities.values().map(|e| e.last_seen_time).max() else {
+        return exporter;
+    };
+
+    for entity in entities.values() {
+        let gap_seconds = (latest_seen - entity.last_seen_time).num_seconds().max(0);
+        exporter.gauge(
+            "vault_audit_entity_activity_gap_seconds",
+            "Seconds between this entity's last-seen time and the most recently active entity's",
+            &[
+                ("entity_id", entity.entity_id.as_str()),
+                ("display_name", entity.display_name.as_str()),
+            ],
+            gap_seconds as f64,
+        );
+    }
+
+    exporter
+}
+
+/// A `--filter` expression field, evaluated against a finalized
+/// [`EntityChurnRecord`]. `FilesAppearedCount` reads `files_appeared.len`
+/// rather than the list itself, since the grammar only supports scalar
+/// comparisons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FilterField {
+    EntityId,
+    DisplayName,
+    MountPath,
+    MountType,
+    Lifecycle,
+    TotalLogins,
+    FilesAppearedCount,
+    FirstSeenTime,
+}
+
+impl FilterField {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "entity_id" => Some(Self::EntityId),
+            "display_name" => Some(Self::DisplayName),
+            "mount_path" => Some(Self::MountPath),
+            "mount_type" => Some(Self::MountType),
+            "lifecycle" => Some(Self::Lifecycle),
+            "total_logins" => Some(Self::TotalLogins),
+            "files_appeared.len" => Some(Self::FilesAppearedCount),
+            "first_seen_time" => Some(Self::FirstSeenTime),
+            _ => None,
+        }
+    }
+
+    fn is_numeric(self) -> bool {
+        matches!(self, Self::TotalLogins | Self::FilesAppearedCount)
+    }
+}
+
+/// Comparison operators supported by `--filter` expressions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FilterOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+    StartsWith,
+    EndsWith,
+}
+
+/// A literal value on the right-hand side of a `--filter` comparison.
+#[derive(Debug, Clone)]
+enum FilterValue {
+    Str(String),
+    Num(f64),
+}
+
+/// Parsed `--filter` AST: comparison leaves combined with `and`/`or`/`not`.
+/// See [`FilterExpr::parse`] for the grammar.
+#[derive(Debug, Clone)]
+enum FilterExpr {
+    Compare {
+        field: FilterField,
+        op: FilterOp,
+        value: FilterValue,
+    },
+    Not(Box<FilterExpr>),
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+}
+
+/// One token of a `--filter` expression.
+#[derive(Debug, Clone, PartialEq)]
+enum FilterToken {
+    Ident(String),
+    Str(String),
+    Num(f64),
+    Op(FilterOp),
+    LParen,
+    RParen,
+}
+
+fn tokenize_filter(input: &str) -> Result<Vec<FilterToken>> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = input.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '(' {
+            tokens.push(FilterToken::LParen);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(FilterToken::RParen);
+            i += 1;
+        } else if c == '"' {
+            let mut s = String::new();
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                s.push(chars[i]);
+                i += 1;
+            }
+            if i >= chars.len() {
+                bail!("Unterminated string literal in filter expression");
+            }
+            i += 1; // closing quote
+            tokens.push(FilterToken::Str(s));
+        } else if c == '=' && chars.get(i + 1) == Some(&'=') {
+            tokens.push(FilterToken::Op(FilterOp::Eq));
+            i += 2;
+        } else if c == '!' && chars.get(i + 1) == Some(&'=') {
+            tokens.push(FilterToken::Op(FilterOp::Ne));
+            i += 2;
+        } else if c == '<' && chars.get(i + 1) == Some(&'=') {
+            tokens.push(FilterToken::Op(FilterOp::Le));
+            i += 2;
+        } else if c == '>' && chars.get(i + 1) == Some(&'=') {
+            tokens.push(FilterToken::Op(FilterOp::Ge));
+            i += 2;
+        } else if c == '<' {
+            tokens.push(FilterToken::Op(FilterOp::Lt));
+            i += 1;
+        } else if c == '>' {
+            tokens.push(FilterToken::Op(FilterOp::Gt));
+            i += 1;
+        } else if c.is_ascii_digit() || (c == '-' && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit())) {
+            let start = i;
+            i += 1;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let num = text
+                .parse::<f64>()
+                .with_context(|| format!("Invalid number '{}' in filter expression", text))?;
+            tokens.push(FilterToken::Num(num));
+        } else if c.is_alphanumeric() || c == '_' || c == '.' {
+            let start = i;
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+            {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            match word.as_str() {
+                "contains" => tokens.push(FilterToken::Op(FilterOp::Contains)),
+                "startswith" => tokens.push(FilterToken::Op(FilterOp::StartsWith)),
+                "endswith" => tokens.push(FilterToken::Op(FilterOp::EndsWith)),
+                _ => tokens.push(FilterToken::Ident(word)),
+            }
+        } else {
+            bail!("Unexpected character '{}' in filter expression", c);
+        }
+    }
+    Ok(tokens)
+}
+
+/// Recursive-descent parser for `--filter` expressions: `or_expr :=
+/// and_expr ("or" and_expr)*`, `and_expr := unary ("and" unary)*`, `unary :=
+/// "not" unary | "(" or_expr ")" | comparison`, `comparison := field op
+/// value`.
+struct FilterParser {
+    tokens: Vec<FilterToken>,
+    pos: usize,
+}
+
+impl FilterParser {
+    fn peek(&self) -> Option<&FilterToken> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(FilterToken::Ident(word)) if word == keyword)
+    }
+
+    fn next(&mut self) -> Option<FilterToken> {
+        let tok = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        tok
+    }
+
+    fn parse_or(&mut self) -> Result<FilterExpr> {
+        let mut lhs = self.parse_and()?;
+        while self.peek_keyword("or") {
+            self.pos += 1;
+            let rhs = self.parse_and()?;
+            lhs = FilterExpr::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<FilterExpr> {
+        let mut lhs = self.parse_unary()?;
+        while self.peek_keyword("and") {
+            self.pos += 1;
+            let rhs = self.parse_unary()?;
+            lhs = FilterExpr::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<FilterExpr> {
+        if self.peek_keyword("not") {
+            self.pos += 1;
+            return Ok(FilterExpr::Not(Box::new(self.parse_unary()?)));
+        }
+        if matches!(self.peek(), Some(FilterToken::LParen)) {
+            self.pos += 1;
+            let inner = self.parse_or()?;
+            match self.next() {
+                Some(FilterToken::RParen) => {}
+                _ => bail!("Expected ')' in filter expression"),
+            }
+            return Ok(inner);
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<FilterExpr> {
+        let field_name = match self.next() {
+            Some(FilterToken::Ident(name)) => name,
+            other => bail!("Expected a field name in filter expression, found {:?}", other),
+        };
+        let field = FilterField::parse(&field_name).with_context(|| {
+            format!(
+                "Unknown filter field '{}'; expected one of entity_id, display_name, \
+                 mount_path, mount_type, lifecycle, total_logins, files_appeared.len, first_seen_time",
+                field_name
+            )
+        })?;
+        let op = match self.next() {
+            Some(FilterToken::Op(op)) => op,
+            other => bail!("Expected a comparison operator in filter expression, found {:?}", other),
+        };
+        let value = match self.next() {
+            Some(FilterToken::Str(s)) => FilterValue::Str(s),
+            Some(FilterToken::Num(n)) => FilterValue::Num(n),
+            other => bail!("Expected a string or number value in filter expression, found {:?}", other),
+        };
+        Ok(FilterExpr::Compare { field, op, value })
+    }
+}
+
+impl FilterExpr {
+    /// Parse a `--filter` expression like `mount_path startswith
+    /// "auth/github"`, `lifecycle == "new_day_1"`, or `total_logins >= 5 and
+    /// not files_appeared.len < 3`.
+    fn parse(input: &str) -> Result<Self> {
+        let tokens = tokenize_filter(input)?;
+        if tokens.is_empty() {
+            bail!("Filter expression must not be empty");
+        }
+        let mut parser = FilterParser { tokens, pos: 0 };
+        let expr = parser.parse_or()?;
+        if parser.pos != parser.tokens.len() {
+            bail!("Unexpected trailing tokens in filter expression");
+        }
+        Ok(expr)
+    }
+
+    /// Evaluate this expression against `record`.
+    fn eval(&self, record: &EntityChurnRecord) -> bool {
+        match self {
+            FilterExpr::Not(inner) => !inner.eval(record),
+            FilterExpr::And(lhs, rhs) => lhs.eval(record) && rhs.eval(record),
+            FilterExpr::Or(lhs, rhs) => lhs.eval(record) || rhs.eval(record),
+            FilterExpr::Compare { field, op, value } => {
+                if field.is_numeric() {
+                    let lhs = match field {
+                        FilterField::TotalLogins => record.total_logins as f64,
+                        FilterField::FilesAppearedCount => record.files_appeared.len() as f64,
+                        _ => unreachable!("non-numeric field routed through numeric comparison"),
+                    };
+                    let rhs = match value {
+                        FilterValue::Num(n) => *n,
+                        FilterValue::Str(s) => s.parse::<f64>().unwrap_or(f64::NAN),
+                    };
+                    eval_numeric(*op, lhs, rhs)
                 } else {
-                    (None, None, None, None)
-                };
+                    let lhs = match field {
+                        FilterField::EntityId => record.entity_id.as_str(),
+                        FilterField::DisplayName => record.display_name.as_str(),
+                        FilterField::MountPath => record.mount_path.as_str(),
+                        FilterField::MountType => record.mount_type.as_str(),
+                        FilterField::Lifecycle => record.lifecycle.as_str(),
+                        FilterField::FirstSeenTime => return eval_first_seen_time(*op, record, value),
+                        _ => unreachable!("numeric field routed through string comparison"),
+                    };
+                    let rhs = match value {
+                        FilterValue::Str(s) => s.as_str(),
+                        FilterValue::Num(n) => {
+                            // Bare numbers compared against string fields fall back to
+                            // formatting, so e.g. `total_logins` isn't the only numeric-looking field.
+                            return eval_str(*op, lhs, &n.to_string());
+                        }
+                    };
+                    eval_str(*op, lhs, rhs)
+                }
+            }
+        }
+    }
+}
 
-                entities.insert(
-                    entity_id.clone(),
-                    EntityChurnRecord {
-                        entity_id: entity_id.clone(),
-                        display_name: display_name.clone(),
-                        mount_path: mount_path.clone(),
-                        mount_type: mount_type.clone(),
-                        token_type: token_type.clone(),
-                        first_seen_file: file_name.clone(),
-                        first_seen_time,
-                        last_seen_file: file_name.clone(),
-                        last_seen_time: first_seen_time,
-                        files_appeared: vec![file_name.clone()],
-                        total_logins: 1,
-                        lifecycle,
-                        activity_pattern: "unknown".to_string(), // Will be computed in second pass
-                        is_ephemeral_pattern: false,             // Will be computed in second pass
-                        ephemeral_confidence: 0.0,               // Will be computed in second pass
-                        ephemeral_reasons: Vec::new(),           // Will be computed in second pass
-                        baseline_entity_name,
-                        baseline_created,
-                        baseline_alias_name,
-                        baseline_mount_path,
-                        historical_display_name,
-                        historical_first_seen,
-                        historical_last_seen,
-                        historical_login_count,
-                    },
-                );
+fn eval_numeric(op: FilterOp, lhs: f64, rhs: f64) -> bool {
+    match op {
+        FilterOp::Eq => lhs == rhs,
+        FilterOp::Ne => lhs != rhs,
+        FilterOp::Lt => lhs < rhs,
+        FilterOp::Le => lhs <= rhs,
+        FilterOp::Gt => lhs > rhs,
+        FilterOp::Ge => lhs >= rhs,
+        FilterOp::Contains | FilterOp::StartsWith | FilterOp::EndsWith => {
+            eval_str(op, &lhs.to_string(), &rhs.to_string())
+        }
+    }
+}
+
+fn eval_str(op: FilterOp, lhs: &str, rhs: &str) -> bool {
+    match op {
+        FilterOp::Eq => lhs == rhs,
+        FilterOp::Ne => lhs != rhs,
+        FilterOp::Lt => lhs < rhs,
+        FilterOp::Le => lhs <= rhs,
+        FilterOp::Gt => lhs > rhs,
+        FilterOp::Ge => lhs >= rhs,
+        FilterOp::Contains => lhs.contains(rhs),
+        FilterOp::StartsWith => lhs.starts_with(rhs),
+        FilterOp::EndsWith => lhs.ends_with(rhs),
+    }
+}
+
+/// `first_seen_time` comparisons compare RFC3339 strings directly - lexical
+/// order matches chronological order for same-format UTC timestamps, so no
+/// separate numeric path is needed.
+fn eval_first_seen_time(op: FilterOp, record: &EntityChurnRecord, value: &FilterValue) -> bool {
+    let lhs = record.first_seen_time.to_rfc3339();
+    let rhs = match value {
+        FilterValue::Str(s) => s.clone(),
+        FilterValue::Num(n) => n.to_string(),
+    };
+    eval_str(op, &lhs, &rhs)
+}
+
+/// One row of the `--bucket <duration>` time series: churn dynamics within a
+/// fixed-width time window spanning the whole concatenated input, rather
+/// than the coarse per-file "Day N" breakdown above. See
+/// [`compute_bucketed_churn_series`].
+#[derive(Debug, Clone, Serialize)]
+struct ChurnBucketRow {
+    bucket_start: String,
+    new_entities: usize,
+    returning_entities: usize,
+    churned_entities: usize,
+    total_logins: usize,
+}
+
+/// Re-reads `log_files` as one timestamp-sorted stream of login events,
+/// ignoring file boundaries, and buckets them into `bucket_secs`-wide
+/// windows. Each entity's most recent active bucket is tracked in a sliding
+/// `last_active_bucket` map so `new`/`returning` reflect whether the entity
+/// has ever been seen before (in any earlier bucket), and `churned`
+/// reflects entities active in the immediately preceding non-empty bucket
+/// that are absent from the current one.
+fn compute_bucketed_churn_series(
+    log_files: &[String],
+    bucket_secs: u64,
+) -> Result<Vec<ChurnBucketRow>> {
+    let mut events: Vec<(DateTime<Utc>, String)> = Vec::new();
+
+    for log_file in log_files {
+        let file = open_file(log_file)
+            .with_context(|| format!("Failed to open log file: {}", log_file))?;
+        let reader = BufReader::new(file);
+        for line in reader.lines() {
+            let line = line.context("Failed to read line from log file")?;
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            let Ok(entry) = serde_json::from_str::<AuditEntry>(trimmed) else {
+                continue;
+            };
+            let Some(ref request) = entry.request else {
+                continue;
+            };
+            let Some(ref path) = request.path else {
+                continue;
+            };
+            if !path.ends_with("/login") {
+                continue;
             }
+            let Some(ref auth) = entry.auth else {
+                continue;
+            };
+            let Some(ref entity_id) = auth.entity_id else {
+                continue;
+            };
+            let Ok(parsed) = chrono::DateTime::parse_from_rfc3339(&entry.time) else {
+                continue;
+            };
+            events.push((parsed.with_timezone(&Utc), entity_id.clone()));
         }
+    }
+
+    if events.is_empty() {
+        return Ok(Vec::new());
+    }
+    events.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let bucket_secs = bucket_secs.max(1) as i64;
+    let mut bucket_logins: BTreeMap<i64, HashMap<String, usize>> = BTreeMap::new();
+    for (timestamp, entity_id) in &events {
+        let bucket_idx = timestamp.timestamp().div_euclid(bucket_secs);
+        *bucket_logins
+            .entry(bucket_idx)
+            .or_default()
+            .entry(entity_id.clone())
+            .or_insert(0) += 1;
+    }
+
+    let mut rows = Vec::with_capacity(bucket_logins.len());
+    let mut last_active_bucket: HashMap<String, i64> = HashMap::new();
+    let mut prev_bucket_idx: Option<i64> = None;
+
+    for (&bucket_idx, logins) in &bucket_logins {
+        let mut new_entities = 0;
+        let mut returning_entities = 0;
+        for entity_id in logins.keys() {
+            if last_active_bucket.contains_key(entity_id) {
+                returning_entities += 1;
+            } else {
+                new_entities += 1;
+            }
+        }
+
+        let churned_entities = match prev_bucket_idx {
+            Some(prev_idx) => last_active_bucket
+                .iter()
+                .filter(|(entity_id, &last_idx)| {
+                    last_idx == prev_idx && !logins.contains_key(entity_id.as_str())
+                })
+                .count(),
+            None => 0,
+        };
+
+        for entity_id in logins.keys() {
+            last_active_bucket.insert(entity_id.clone(), bucket_idx);
+        }
+
+        let bucket_start = DateTime::from_timestamp(bucket_idx * bucket_secs, 0)
+            .unwrap_or_else(Utc::now)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+
+        rows.push(ChurnBucketRow {
+            bucket_start,
+            new_entities,
+            returning_entities,
+            churned_entities,
+            total_logins: logins.values().sum(),
+        });
 
+        prev_bucket_idx = Some(bucket_idx);
+    }
+
+    Ok(rows)
+}
+
+/// Print the `--bucket` time series as a row-per-bucket table, suitable for
+/// feeding into a plotting tool.
+fn print_bucketed_churn_series(rows: &[ChurnBucketRow]) {
+    println!("\nTime-Bucketed Churn Series:");
+    println!(
+        "  {:<22} {:<10} {:<12} {:<10} {:<10}",
+        "Bucket Start", "New", "Returning", "Churned", "Logins"
+    );
+    for row in rows {
+        println!(
+            "  {:<22} {:<10} {:<12} {:<10} {:<10}",
+            row.bucket_start,
+            format_number(row.new_entities),
+            format_number(row.returning_entities),
+            format_number(row.churned_entities),
+            format_number(row.total_logins)
+        );
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    log_files: &[String],
+    entity_map: Option<&str>,
+    baseline_entities: Option<&str>,
+    output: Option<&str>,
+    format: Option<&str>,
+    threads: Option<usize>,
+    otel_endpoint: Option<&str>,
+    cluster_eps: Option<f64>,
+    cluster_min_points: Option<usize>,
+    signature_rules: Option<&str>,
+    state_store: Option<&str>,
+    state_compact_threshold_bytes: Option<u64>,
+    filter: Option<&str>,
+    bucket_secs: Option<u64>,
+    metrics_file: Option<&str>,
+    metrics_listen: Option<&str>,
+    s3_endpoint: Option<&str>,
+) -> Result<()> {
+    let _otel_handle = crate::utils::otel::init(otel_endpoint)?;
+    let run_span = crate::utils::otel::run_span("entity_churn");
+    let run_cx = crate::utils::otel::run_context(&run_span);
+    crate::utils::s3::apply_endpoint_override(s3_endpoint);
+
+    let filter_expr = filter.map(FilterExpr::parse).transpose().context("Invalid --filter")?;
+
+    // Resolve any `s3://bucket/prefix/` or glob (`s3://bucket/.../*.log`)
+    // entries down to concrete per-object keys before the rest of this
+    // function's per-file progress/parallel-parsing logic runs.
+    let log_files = crate::utils::reader::expand_sources(log_files)?;
+    let log_files = log_files.as_slice();
+
+    let signature_rules = match signature_rules {
+        Some(path) => {
+            println!("Loading signature rules from {}...", path);
+            load_signature_rules(path)?
+        }
+        None => Vec::new(),
+    };
+
+    println!("\n=== Multi-Day Entity Churn Analysis ===\n");
+    println!("Analyzing {} log files:", log_files.len());
+    for (i, file) in log_files.iter().enumerate() {
+        let size = get_file_size(file)?;
+        println!(
+            "  Day {}: {} ({:.2} GB)",
+            i + 1,
+            file,
+            size as f64 / 1_000_000_000.0
+        );
+    }
+    println!();
+
+    // Load baseline entities if provided
+    let baseline = if let Some(path) = baseline_entities {
+        println!(
+            "Loading baseline entity list (Vault API metadata) from {}...",
+            path
+        );
+        let baseline_set = load_baseline_entities(path)?;
+        println!(
+            "Loaded {} pre-existing entities from Vault API baseline",
+            format_number(baseline_set.len())
+        );
+        println!();
+        Some(baseline_set)
+    } else {
+        println!("No baseline entity list provided. Cannot distinguish truly NEW entities from pre-existing.");
+        println!("   All Day 1 entities will be marked as 'pre_existing_or_new_day_1'.");
+        println!("   To get accurate results, run: ./vault-audit entity-list --output baseline_entities.json\n");
+        None
+    };
+
+    // Load entity mappings if provided (historical data from audit logs)
+    let entity_mappings = if let Some(path) = entity_map {
+        println!(
+            "Loading historical entity mappings (audit log enrichment) from {}...",
+            path
+        );
+        let mappings = load_entity_mappings(path)?;
+        println!(
+            "Loaded {} entity mappings with historical audit log data",
+            format_number(mappings.len())
+        );
+        println!();
+        Some(mappings)
+    } else {
+        None
+    };
+
+    // Load any state persisted from prior runs (`--state-store`) and skip
+    // re-parsing log files it already ingested, so a daily pipeline only
+    // pays for the new day's file.
+    let (mut entities, mut ingested_files): (
+        HashMap<String, EntityChurnRecord>,
+        std::collections::HashSet<String>,
+    ) = if let Some(path) = state_store {
+        println!("Loading persisted entity state from {}...", path);
+        let (entities, ingested_files) = load_state(path)?;
+        println!(
+            "Loaded {} entities and {} previously-ingested file(s) from state store\n",
+            format_number(entities.len()),
+            format_number(ingested_files.len())
+        );
+        (entities, ingested_files)
+    } else {
+        (
+            HashMap::with_capacity(5000),
+            std::collections::HashSet::new(),
+        )
+    };
+
+    let log_files: Vec<String> = if state_store.is_some() {
+        let filtered: Vec<String> = log_files
+            .iter()
+            .filter(|f| {
+                let file_name = Path::new(f).file_name().unwrap().to_string_lossy().to_string();
+                !ingested_files.contains(&file_name)
+            })
+            .cloned()
+            .collect();
+        println!(
+            "{} of {} given log files are new; skipping the rest as already ingested.\n",
+            format_number(filtered.len()),
+            format_number(log_files.len())
+        );
+        filtered
+    } else {
+        log_files.to_vec()
+    };
+
+    // Map each file to its day index up front so files_appeared can be
+    // re-sorted into chronological order after the parallel merge below.
+    let file_day_index: HashMap<String, usize> = log_files
+        .iter()
+        .enumerate()
+        .map(|(idx, log_file)| {
+            let file_name = Path::new(log_file)
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .to_string();
+            (file_name, idx)
+        })
+        .collect();
+
+    let total_bytes: usize = log_files
+        .iter()
+        .map(|f| get_file_size(f).unwrap_or(0) as usize)
+        .sum();
+    let bytes_processed_total = Arc::new(AtomicUsize::new(0));
+    let progress = Arc::new(Mutex::new(ProgressBar::new(total_bytes, "Processing")));
+
+    let worker_threads = threads.unwrap_or(0); // 0 tells rayon to pick a sensible default
+    println!(
+        "Parsing {} log files in parallel ({})...",
+        log_files.len(),
+        if worker_threads == 0 {
+            "auto thread count".to_string()
+        } else {
+            format!("{} threads", worker_threads)
+        }
+    );
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(worker_threads)
+        .build()
+        .context("Failed to build entity-churn worker thread pool")?;
+
+    let mut file_results: Vec<FileChunkResult> = pool.install(|| {
+        log_files
+            .par_iter()
+            .enumerate()
+            .map(|(file_idx, log_file)| {
+                crate::utils::otel::file_span(&run_cx, file_idx, log_file, || {
+                    process_log_file_chunk(
+                        file_idx,
+                        log_file,
+                        baseline.as_ref(),
+                        entity_mappings.as_ref(),
+                        total_bytes,
+                        &bytes_processed_total,
+                        &progress,
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>>>()
+    })?;
+
+    if let Ok(progress) = progress.lock() {
         progress.finish();
+    }
+    file_results.sort_by_key(|r| r.file_idx);
+
+    // Track all entities across all files
+    let mut daily_stats: Vec<DailyStats> = Vec::with_capacity(file_results.len());
+    let mut dirty_entity_ids: std::collections::HashSet<String> = std::collections::HashSet::new();
+    let mut newly_ingested_files: Vec<String> = Vec::with_capacity(file_results.len());
+
+    for result in file_results {
+        // An entity is "new" on this day if this file is its (merged) first
+        // appearance; everything else seen in this file is "returning".
+        let new_entities_this_file = result
+            .entities
+            .keys()
+            .filter(|id| !entities.contains_key(*id))
+            .count();
+        let returning_entities_this_file = result.entities.len() - new_entities_this_file;
+
+        dirty_entity_ids.extend(result.entities.keys().cloned());
+        newly_ingested_files.push(result.file_name.clone());
+
+        merge_file_result(&mut entities, result.entities);
 
         daily_stats.push(DailyStats {
-            file_name,
+            file_name: result.file_name.clone(),
             new_entities: new_entities_this_file,
-            returning_entities: returning_entities_this_file.len(),
-            total_logins: logins_this_file,
+            returning_entities: returning_entities_this_file,
+            total_logins: result.logins_this_file,
         });
 
         println!(
-            "Day {} Summary: {} new entities, {} returning, {} logins",
-            file_idx + 1,
+            "Day {} ({}) Summary: {} new entities, {} returning, {} logins",
+            result.file_idx + 1,
+            result.file_name,
             format_number(new_entities_this_file),
-            format_number(returning_entities_this_file.len()),
-            format_number(logins_this_file)
+            format_number(returning_entities_this_file),
+            format_number(result.logins_this_file)
         );
     }
 
+    resort_files_appeared_by_day(&mut entities, &file_day_index);
+
     // === SECOND PASS: Analyze patterns and classify entities ===
     println!("\nAnalyzing entity behavior patterns...");
 
     let mut analyzer = EphemeralPatternAnalyzer::new(log_files.len());
 
-    // Step 1: Learn patterns from short-lived entities
+    // Step 1: Cluster entities by display-name template + mount path
     analyzer.learn_from_entities(&entities);
     println!(
-        "Learned from {} short-lived entity patterns",
-        format_number(analyzer.short_lived_patterns.len())
+        "Learned {} display-name template clusters",
+        format_number(analyzer.clusters.len())
     );
 
-    // Step 2: Classify all entities using learned patterns
+    // Step 2: Classify all entities using learned patterns. `analyzer` is only
+    // read from this point on, so each worker thread can hold it by shared
+    // reference while classifying its own partition of entities in parallel;
+    // results are collected and written back once every worker has finished.
     let entity_ids: Vec<String> = entities.keys().cloned().collect();
-    for entity_id in entity_ids {
-        if let Some(entity) = entities.get_mut(&entity_id) {
-            // Classify activity pattern
-            entity.activity_pattern = analyzer.classify_activity_pattern(entity);
-
-            // Analyze for ephemeral patterns
+    let classifications: Vec<(String, String, bool, f32, Vec<String>)> = entity_ids
+        .par_iter()
+        .map(|entity_id| {
+            let entity = &entities[entity_id];
+            let activity_pattern = analyzer.classify_activity_pattern(entity);
             let (is_ephemeral, confidence, reasons) = analyzer.analyze_entity(entity);
+            (
+                entity_id.clone(),
+                activity_pattern,
+                is_ephemeral,
+                confidence,
+                reasons,
+            )
+        })
+        .collect();
+
+    for (entity_id, activity_pattern, is_ephemeral, confidence, reasons) in classifications {
+        if let Some(entity) = entities.get_mut(&entity_id) {
+            entity.activity_pattern = activity_pattern;
             entity.is_ephemeral_pattern = is_ephemeral;
             entity.ephemeral_confidence = confidence;
             entity.ephemeral_reasons = reasons;
         }
     }
 
+    // Step 3: Cluster entities into emergent behavioral cohorts, independent of
+    // the hand-coded lifecycle/ephemeral labels above.
+    println!("\nClustering entities by behavior...");
+    cluster_entities(
+        &mut entities,
+        cluster_eps.unwrap_or(DEFAULT_CLUSTER_EPS),
+        cluster_min_points.unwrap_or(DEFAULT_CLUSTER_MIN_POINTS),
+    );
+
     // Generate final report
     println!("\n=== Entity Churn Analysis ===\n");
 
@@ -834,6 +2905,27 @@ pub fn run(
         );
     }
 
+    // `--bucket` is a decoupled, finer-grained view of the same login events
+    // above: it ignores file boundaries entirely so intra-day spikes aren't
+    // hidden behind the one-file-equals-one-day assumption of the breakdown
+    // above.
+    if let Some(bucket_secs) = bucket_secs {
+        let bucket_rows = compute_bucketed_churn_series(log_files, bucket_secs)?;
+        print_bucketed_churn_series(&bucket_rows);
+    }
+
+    // Apply `--filter` before any of the summary breakdowns or export below
+    // count/see an entity, so they reflect only the filtered population.
+    if let Some(expr) = &filter_expr {
+        let before = entities.len();
+        entities.retain(|_, entity| expr.eval(entity));
+        println!(
+            "\nFilter matched {} of {} entities",
+            format_number(entities.len()),
+            format_number(before)
+        );
+    }
+
     // Lifecycle classification
     let mut lifecycle_counts: HashMap<String, usize> = HashMap::with_capacity(20); // Small set of lifecycle categories
     let mut entities_by_file_count: HashMap<usize, usize> = HashMap::with_capacity(log_files.len());
@@ -894,6 +2986,23 @@ pub fn run(
         println!("  {}: {}", pattern, format_number(*count));
     }
 
+    print_cluster_summary(&entities);
+
+    let daily_new_vs_returning: Vec<(u32, usize, usize)> = daily_stats
+        .iter()
+        .enumerate()
+        .map(|(idx, stats)| (idx as u32, stats.new_entities, stats.returning_entities))
+        .collect();
+    let total_logins: usize = daily_stats.iter().map(|stats| stats.total_logins).sum();
+    let ephemeral_confidences: Vec<f32> = entities.values().map(|e| e.ephemeral_confidence).collect();
+    crate::utils::otel::record_churn_metrics(&crate::utils::otel::ChurnRunStats {
+        daily_new_vs_returning: &daily_new_vs_returning,
+        total_logins,
+        lifecycle_counts: &lifecycle_counts,
+        activity_pattern_counts: &activity_pattern_counts,
+        ephemeral_confidences: &ephemeral_confidences,
+    });
+
     println!("\nEphemeral Entity Detection:");
     println!(
         "  Detected {} likely ephemeral entities (confidence ≥ 0.4)",
@@ -941,6 +3050,19 @@ pub fn run(
         println!("    Low (40-49%): {}", format_number(low_conf));
     }
 
+    print_signature_matches(&entities, &signature_rules);
+
+    if metrics_file.is_some() || metrics_listen.is_some() {
+        let exporter = build_metrics_exporter(&entities);
+        if let Some(metrics_path) = metrics_file {
+            exporter.write_textfile(metrics_path)?;
+            println!("Metrics written to: {}", metrics_path);
+        }
+        if let Some(addr) = metrics_listen {
+            exporter.serve_blocking(addr)?;
+        }
+    }
+
     // Mount path breakdown
     let mut mount_stats: HashMap<String, (usize, String)> = HashMap::with_capacity(100); // Typical: dozens of mount points
     for entity in entities.values() {
@@ -1010,13 +3132,17 @@ pub fn run(
 
         // Determine format from parameter or file extension
         let output_format = format.unwrap_or_else(|| {
-            if std::path::Path::new(output_path)
+            match std::path::Path::new(output_path)
                 .extension()
-                .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+                .and_then(|ext| ext.to_str())
+                .map(str::to_lowercase)
+                .as_deref()
             {
-                "csv"
-            } else {
-                "json"
+                Some("csv") => "csv",
+                Some("parquet") => "parquet",
+                Some("arrow" | "feather") => "arrow",
+                Some("bin") => "bin",
+                _ => "json",
             }
         });
 
@@ -1025,11 +3151,10 @@ pub fn run(
             output_path, output_format
         );
 
-        let output_file = File::create(output_path)
-            .with_context(|| format!("Failed to create output file: {}", output_path))?;
-
         match output_format {
             "csv" => {
+                let output_file = File::create(output_path)
+                    .with_context(|| format!("Failed to create output file: {}", output_path))?;
                 let mut writer = csv::Writer::from_writer(output_file);
                 for entity in &entities_vec {
                     let csv_record: EntityChurnRecordCsv = entity.clone().into();
@@ -1039,8 +3164,13 @@ pub fn run(
                 }
                 writer.flush().context("Failed to flush CSV writer")?;
             }
+            "parquet" => write_parquet_export(output_path, &entities_vec)?,
+            "arrow" => write_arrow_export(output_path, &entities_vec)?,
+            "bin" => write_binary_export(output_path, &entities_vec)?,
             _ => {
                 // Default to JSON
+                let output_file = File::create(output_path)
+                    .with_context(|| format!("Failed to create output file: {}", output_path))?;
                 serde_json::to_writer_pretty(output_file, &entities_vec)
                     .context("Failed to write JSON output")?;
             }
@@ -1052,6 +3182,19 @@ pub fn run(
         );
     }
 
+    if let Some(path) = state_store {
+        ingested_files.extend(newly_ingested_files.iter().cloned());
+        println!("\nPersisting entity state to {}...", path);
+        persist_state(
+            path,
+            &entities,
+            &ingested_files,
+            &dirty_entity_ids,
+            &newly_ingested_files,
+            state_compact_threshold_bytes.unwrap_or(DEFAULT_STATE_WAL_COMPACT_THRESHOLD_BYTES),
+        )?;
+    }
+
     println!("\n=== Analysis Complete ===\n");
     Ok(())
 }
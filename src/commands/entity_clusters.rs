@@ -0,0 +1,436 @@
+//! Entity access-pattern clustering.
+//!
+//! Groups entities whose KV secret access patterns overlap heavily —
+//! surfacing redundant service accounts, shared-credential usage, or a new
+//! client that looks suspiciously like an existing one.
+//!
+//! # Usage
+//!
+//! ```bash
+//! # Cluster entities by shared KV path access (default similarity: 0.8)
+//! vault-audit entity-analysis clusters logs/*.log --output clusters.csv
+//!
+//! # Looser grouping - catches partial overlap, more (and smaller) clusters
+//! vault-audit entity-analysis clusters logs/*.log --similarity-threshold 0.5
+//!
+//! # Label entity IDs with display names from a preprocess-entities export
+//! vault-audit entity-analysis clusters logs/*.log --entity-csv entities.csv
+//! ```
+//!
+//! # Algorithm
+//!
+//! Each entity's feature set is the normalized KV paths (see
+//! [`crate::commands::kv_analyzer::normalize_kv_path`]) it read or listed.
+//! Entities are connected by an edge whenever their Jaccard similarity
+//! `|A∩B| / |A∪B|` is at least `--similarity-threshold`, and each connected
+//! component of that graph is reported as one cluster.
+//!
+//! Comparing every pair of entities directly is O(n²), which doesn't scale
+//! to the tens of thousands of entities a busy Vault cluster can have. To
+//! keep it tractable, entities are first bucketed by a shared
+//! [MinHash](https://en.wikipedia.org/wiki/MinHash) signature (banded
+//! locality-sensitive hashing): only entities that land in the same band
+//! bucket at least once are ever compared against each other, so the exact
+//! Jaccard check only runs within small candidate groups rather than across
+//! the whole population.
+//!
+//! # Output
+//!
+//! CSV with one row per cluster, sorted by size descending:
+//! - `cluster_id`
+//! - `member_count`
+//! - `entity_ids` - cluster members
+//! - `display_names` - names from `--entity-csv`, when given (same order as `entity_ids`)
+//! - `shared_path_prefixes` - normalized paths every member of the cluster touched
+//! - `anomaly_flag` - `true` when the cluster has at most
+//!   [`ANOMALY_SIZE_THRESHOLD`] members, i.e. an entity (or small handful of
+//!   entities) whose access pattern didn't match any peer group - worth a
+//!   second look as a possibly misbehaving service account.
+//!
+//! Singleton entities are no longer dropped: every entity ends up in exactly
+//! one cluster, even a cluster of one, so `anomaly_flag` is the intended way
+//! to spot them rather than their simply being absent from the report.
+
+use crate::audit::types::AuditEntry;
+use crate::utils::format::format_number;
+use crate::utils::processor::{ProcessingMode, ProcessorBuilder};
+use anyhow::{Context, Result};
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+
+/// Number of independent hash functions in each entity's MinHash signature.
+const NUM_HASHES: usize = 32;
+/// Signature rows grouped per LSH band (`NUM_HASHES` must be a multiple of this).
+const BAND_ROWS: usize = 4;
+/// Clusters with at most this many members are flagged as potential
+/// anomalies - a service account behaving unlike its peers.
+const ANOMALY_SIZE_THRESHOLD: usize = 2;
+
+#[derive(Debug, Clone, Default)]
+struct ClusterState {
+    /// entity_id -> distinct normalized KV paths it touched.
+    entity_paths: HashMap<String, HashSet<String>>,
+}
+
+impl ClusterState {
+    fn merge(mut self, other: Self) -> Self {
+        for (entity_id, paths) in other.entity_paths {
+            self.entity_paths.entry(entity_id).or_default().extend(paths);
+        }
+        self
+    }
+}
+
+/// MinHash signature: for each of `NUM_HASHES` independent hash functions,
+/// the minimum hash value among the set's members. Two sets that share a
+/// high fraction of members are likely to share signature rows, so the
+/// signature itself is a compact, fixed-size proxy for Jaccard similarity.
+fn minhash_signature(paths: &HashSet<String>) -> [u64; NUM_HASHES] {
+    use std::hash::{Hash, Hasher};
+
+    let mut signature = [u64::MAX; NUM_HASHES];
+    for path in paths {
+        for (seed, slot) in signature.iter_mut().enumerate() {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            seed.hash(&mut hasher);
+            path.hash(&mut hasher);
+            let hash = hasher.finish();
+            if hash < *slot {
+                *slot = hash;
+            }
+        }
+    }
+    signature
+}
+
+/// Groups entity indices that share at least one LSH band bucket, i.e. the
+/// set of candidate pairs worth an exact Jaccard check.
+fn candidate_groups(signatures: &[[u64; NUM_HASHES]]) -> Vec<Vec<usize>> {
+    let mut buckets: HashMap<(usize, u64), Vec<usize>> = HashMap::new();
+
+    for (entity_idx, signature) in signatures.iter().enumerate() {
+        for (band_idx, rows) in signature.chunks(BAND_ROWS).enumerate() {
+            use std::hash::{Hash, Hasher};
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            rows.hash(&mut hasher);
+            let band_key = (band_idx, hasher.finish());
+            buckets.entry(band_key).or_default().push(entity_idx);
+        }
+    }
+
+    buckets.into_values().filter(|group| group.len() > 1).collect()
+}
+
+fn jaccard_similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let intersection = a.intersection(b).count();
+    if intersection == 0 {
+        return 0.0;
+    }
+    let union = a.len() + b.len() - intersection;
+    intersection as f64 / union as f64
+}
+
+/// Disjoint-set union-find over entity indices, used to turn the
+/// similarity-edge graph into connected components.
+struct UnionFind {
+    parent: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+        }
+    }
+
+    fn find(&mut self, x: usize) -> usize {
+        if self.parent[x] != x {
+            self.parent[x] = self.find(self.parent[x]);
+        }
+        self.parent[x]
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a != root_b {
+            self.parent[root_a] = root_b;
+        }
+    }
+}
+
+/// Loads an `entity_id,display_name` CSV (e.g. from
+/// [`crate::commands::preprocess_entities`]) for labeling the `entity_ids`
+/// column. Missing files are treated as "no names available" rather than an
+/// error, matching [`crate::commands::kv_analyzer`]'s entity alias loader.
+fn load_entity_display_names(entity_csv: &str) -> Result<HashMap<String, String>> {
+    let mut names = HashMap::new();
+
+    let Ok(file) = File::open(entity_csv) else {
+        eprintln!("[WARN] Entity CSV not found: {}", entity_csv);
+        return Ok(names);
+    };
+
+    let mut reader = csv::Reader::from_reader(file);
+    for result in reader.records() {
+        let record = result?;
+        if let (Some(entity_id), Some(display_name)) = (record.get(0), record.get(1)) {
+            names.insert(entity_id.to_string(), display_name.to_string());
+        }
+    }
+
+    Ok(names)
+}
+
+pub fn run(
+    log_files: &[String],
+    output: Option<&str>,
+    similarity_threshold: f64,
+    entity_csv: Option<&str>,
+) -> Result<()> {
+    let output_file = output.unwrap_or("entity_clusters.csv");
+    let display_names = entity_csv.map(load_entity_display_names).transpose()?;
+
+    let processor = ProcessorBuilder::new()
+        .mode(ProcessingMode::Auto)
+        .progress_label("Processing".to_string())
+        .build();
+
+    let (result, stats) = processor.process_files_streaming(
+        log_files,
+        |entry: &AuditEntry, state: &mut ClusterState| {
+            let Some(request) = &entry.request else {
+                return;
+            };
+            let Some(path) = request.path.as_deref() else {
+                return;
+            };
+            if !path.contains("/data/") && !path.contains("/metadata/") {
+                return;
+            }
+
+            let operation = request.operation.as_deref().unwrap_or("");
+            if operation != "read" && operation != "list" {
+                return;
+            }
+
+            let Some(entity_id) = entry.auth.as_ref().and_then(|a| a.entity_id.as_deref()) else {
+                return;
+            };
+
+            let app_path = crate::commands::kv_analyzer::normalize_kv_path(path);
+            state
+                .entity_paths
+                .entry(entity_id.to_string())
+                .or_default()
+                .insert(app_path);
+        },
+        ClusterState::merge,
+        ClusterState::default(),
+    )?;
+
+    eprintln!(
+        "\nTotal: Processed {} lines, tracked {} entities",
+        format_number(stats.total_lines),
+        format_number(result.entity_paths.len())
+    );
+
+    if result.entity_paths.is_empty() {
+        eprintln!("[ERROR] No KV operations with an entity ID found in audit logs.");
+        std::process::exit(1);
+    }
+
+    let entities: Vec<(String, HashSet<String>)> = result.entity_paths.into_iter().collect();
+    let signatures: Vec<[u64; NUM_HASHES]> = entities
+        .iter()
+        .map(|(_, paths)| minhash_signature(paths))
+        .collect();
+
+    let mut union_find = UnionFind::new(entities.len());
+    for group in candidate_groups(&signatures) {
+        for i in 0..group.len() {
+            for j in (i + 1)..group.len() {
+                let (a, b) = (group[i], group[j]);
+                if jaccard_similarity(&entities[a].1, &entities[b].1) >= similarity_threshold {
+                    union_find.union(a, b);
+                }
+            }
+        }
+    }
+
+    let mut clusters: HashMap<usize, Vec<usize>> = HashMap::new();
+    for idx in 0..entities.len() {
+        let root = union_find.find(idx);
+        clusters.entry(root).or_default().push(idx);
+    }
+
+    let mut clusters: Vec<Vec<usize>> = clusters.into_values().collect();
+    clusters.sort_by(|a, b| b.len().cmp(&a.len()));
+
+    let anomaly_count = clusters
+        .iter()
+        .filter(|members| members.len() <= ANOMALY_SIZE_THRESHOLD)
+        .count();
+
+    if let Some(parent) = std::path::Path::new(output_file).parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = std::fs::File::create(output_file).context("Failed to create output file")?;
+    let mut writer = csv::Writer::from_writer(file);
+    writer.write_record([
+        "cluster_id",
+        "member_count",
+        "entity_ids",
+        "display_names",
+        "shared_path_prefixes",
+        "anomaly_flag",
+    ])?;
+
+    for (cluster_id, members) in clusters.iter().enumerate() {
+        let mut shared_paths: Option<HashSet<String>> = None;
+        let mut entity_ids: Vec<&str> = Vec::with_capacity(members.len());
+        for &idx in members {
+            entity_ids.push(&entities[idx].0);
+            shared_paths = Some(match shared_paths.take() {
+                None => entities[idx].1.clone(),
+                Some(acc) => acc.intersection(&entities[idx].1).cloned().collect(),
+            });
+        }
+        entity_ids.sort_unstable();
+
+        let display_names_field = display_names.as_ref().map_or(String::new(), |names| {
+            entity_ids
+                .iter()
+                .map(|id| names.get(*id).map_or("", String::as_str))
+                .collect::<Vec<_>>()
+                .join(", ")
+        });
+
+        let mut shared_paths: Vec<String> = shared_paths.unwrap_or_default().into_iter().collect();
+        shared_paths.sort();
+
+        writer.write_record([
+            (cluster_id + 1).to_string(),
+            members.len().to_string(),
+            entity_ids.join(", "),
+            display_names_field,
+            shared_paths.join(", "),
+            (members.len() <= ANOMALY_SIZE_THRESHOLD).to_string(),
+        ])?;
+    }
+    writer.flush().context("Failed to flush CSV writer")?;
+
+    println!("Done. Output written to: {}", output_file);
+    println!(
+        "Summary: {} clusters found ({} flagged as potential anomalies, size <= {})",
+        clusters.len(),
+        anomaly_count,
+        ANOMALY_SIZE_THRESHOLD
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(items: &[&str]) -> HashSet<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_jaccard_similarity_identical_sets() {
+        let a = paths(&["kv/app1/secret1", "kv/app1/secret2"]);
+        let b = a.clone();
+        assert_eq!(jaccard_similarity(&a, &b), 1.0);
+    }
+
+    #[test]
+    fn test_jaccard_similarity_disjoint_sets() {
+        let a = paths(&["kv/app1/secret1"]);
+        let b = paths(&["kv/app2/secret1"]);
+        assert_eq!(jaccard_similarity(&a, &b), 0.0);
+    }
+
+    #[test]
+    fn test_jaccard_similarity_partial_overlap() {
+        let a = paths(&["kv/app1/secret1", "kv/app1/secret2"]);
+        let b = paths(&["kv/app1/secret1", "kv/app1/secret3"]);
+        // intersection = 1, union = 3
+        assert!((jaccard_similarity(&a, &b) - (1.0 / 3.0)).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_jaccard_similarity_empty_sets() {
+        let a = HashSet::new();
+        let b = HashSet::new();
+        assert_eq!(jaccard_similarity(&a, &b), 0.0);
+    }
+
+    #[test]
+    fn test_minhash_signature_identical_sets_match() {
+        let a = paths(&["kv/app1/secret1", "kv/app1/secret2", "kv/app1/secret3"]);
+        let b = a.clone();
+        assert_eq!(minhash_signature(&a), minhash_signature(&b));
+    }
+
+    #[test]
+    fn test_minhash_signature_disjoint_sets_usually_differ() {
+        let a = paths(&["kv/app1/secret1"]);
+        let b = paths(&["kv/app2/secret9"]);
+        assert_ne!(minhash_signature(&a), minhash_signature(&b));
+    }
+
+    #[test]
+    fn test_minhash_signature_empty_set_is_all_max() {
+        let empty = HashSet::new();
+        assert_eq!(minhash_signature(&empty), [u64::MAX; NUM_HASHES]);
+    }
+
+    #[test]
+    fn test_candidate_groups_empty_input() {
+        assert!(candidate_groups(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_candidate_groups_single_signature_has_no_group() {
+        let signatures = vec![minhash_signature(&paths(&["kv/app1/secret1"]))];
+        assert!(candidate_groups(&signatures).is_empty());
+    }
+
+    #[test]
+    fn test_candidate_groups_identical_signatures_grouped() {
+        let signature = minhash_signature(&paths(&["kv/app1/secret1", "kv/app1/secret2"]));
+        let signatures = vec![signature, signature, signature];
+        let groups = candidate_groups(&signatures);
+        assert!(groups.iter().any(|g| g.len() == 3));
+    }
+
+    #[test]
+    fn test_union_find_merges_connected_entities_into_one_cluster() {
+        let mut uf = UnionFind::new(4);
+        uf.union(0, 1);
+        uf.union(1, 2);
+        // 3 stays its own singleton cluster.
+        assert_eq!(uf.find(0), uf.find(2));
+        assert_ne!(uf.find(0), uf.find(3));
+    }
+
+    #[test]
+    fn test_union_find_unions_with_one_index_form_one_cluster() {
+        let mut uf = UnionFind::new(5);
+        for i in 1..5 {
+            uf.union(0, i);
+        }
+        let root = uf.find(0);
+        for i in 1..5 {
+            assert_eq!(uf.find(i), root);
+        }
+    }
+
+    #[test]
+    fn test_anomaly_size_threshold_flags_small_clusters() {
+        assert!(1 <= ANOMALY_SIZE_THRESHOLD);
+        assert!(ANOMALY_SIZE_THRESHOLD < 10);
+    }
+}
@@ -0,0 +1,703 @@
+//! Entity creation / onboarding analysis.
+//!
+//! ⚠️ **DEPRECATED**: Use `entity-analysis creation` instead.
+//!
+//! ```bash
+//! # Old (deprecated):
+//! vault-audit entity-creation logs/*.log --output creation.json
+//!
+//! # New (recommended):
+//! vault-audit entity-analysis creation logs/*.log --output creation.json
+//! ```
+//!
+//! See [`entity_analysis`](crate::commands::entity_analysis) for the unified command.
+//!
+//! ---
+//!
+//! Identifies when each entity first appears in the logs (its "creation"
+//! event) and groups entities by authentication mount path, to surface new
+//! entity onboarding patterns and per-path growth trends.
+//!
+//! # Usage
+//!
+//! ```bash
+//! vault-audit entity-analysis creation logs/*.log
+//! vault-audit entity-analysis creation logs/*.log --output creation.csv --format csv
+//! vault-audit entity-analysis creation logs/*.log --output creation.parquet --format parquet
+//! ```
+
+use crate::audit::types::AuditEntry;
+use crate::utils::format::format_number;
+use crate::utils::mapping_store::{open_store, MappingStore, StoreBackend};
+use crate::utils::progress::ProgressBar;
+use crate::utils::reader::open_file;
+use anyhow::{Context, Result};
+use arrow::array::{Array, StringArray, TimestampMicrosecondArray, UInt64Array};
+use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow::ipc::writer::FileWriter as ArrowFileWriter;
+use arrow::record_batch::RecordBatch;
+use chrono::{DateTime, Utc};
+use parquet::arrow::ArrowWriter as ParquetArrowWriter;
+use parquet::file::properties::WriterProperties;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Number of lines a `--since` seek backs off from the bisected offset, to
+/// absorb minor clock skew between nodes that could otherwise put an
+/// out-of-order early record just before it.
+const SEEK_SLACK_LINES: usize = 2000;
+
+/// One entity's first-seen ("creation") record.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EntityCreationRecord {
+    pub entity_id: String,
+    pub display_name: String,
+    pub mount_path: String,
+    pub first_seen_file: String,
+    pub first_seen_time: DateTime<Utc>,
+    pub login_count: usize,
+}
+
+/// Optional entity map entry used to enrich records with a friendlier
+/// display name, matching the shape [`crate::commands::preprocess_entities`]
+/// writes out.
+#[derive(Debug, Deserialize)]
+struct EntityMapEntry {
+    display_name: String,
+}
+
+fn load_entity_map(path: &str) -> Result<HashMap<String, EntityMapEntry>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read entity map file: {}", path))?;
+    serde_json::from_str(&contents).context("Failed to parse entity map file")
+}
+
+/// Read the first full line starting at or after byte `offset` of `path`,
+/// returning its own start offset (which may land after `offset`, if
+/// `offset` fell inside a line) and contents. Returns `None` at or past EOF.
+fn read_line_at_or_after(path: &str, offset: u64) -> Option<(u64, String)> {
+    let mut file = std::fs::File::open(path).ok()?;
+    // Seek one byte early so that an `offset` landing exactly on a line
+    // boundary doesn't get mistaken for mid-line and have that whole line
+    // discarded below.
+    let seek_offset = offset.saturating_sub(1);
+    file.seek(SeekFrom::Start(seek_offset)).ok()?;
+    let mut reader = BufReader::new(file);
+
+    let mut line_offset = seek_offset;
+    if offset > 0 {
+        let mut discard = Vec::new();
+        let discarded = reader.read_until(b'\n', &mut discard).ok()?;
+        if discarded == 0 {
+            return None;
+        }
+        line_offset += discarded as u64;
+    }
+
+    let mut line = String::new();
+    let read = reader.read_line(&mut line).ok()?;
+    if read == 0 {
+        return None;
+    }
+    Some((line_offset, line))
+}
+
+/// Parse the `time` field of one audit log line, for offset bisection only.
+fn line_time(line: &str) -> Option<DateTime<Utc>> {
+    let entry: AuditEntry = serde_json::from_str(line.trim_end()).ok()?;
+    chrono::DateTime::parse_from_rfc3339(&entry.time)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Binary-search `path` (assumed chronologically sorted by `time`, one JSON
+/// record per line) for the byte offset of the first record at or after
+/// `start`. Falls back to `0` (i.e. "scan the whole file") if the file is
+/// empty, its first record is already at or after `start`, or any probed
+/// line's `time` can't be parsed - the latter covers compressed or
+/// otherwise non-plain files, since this bisects raw byte offsets and can't
+/// see through a decompression stream.
+fn bisect_start_offset(path: &str, start: DateTime<Utc>) -> u64 {
+    let Ok(file_len) = std::fs::metadata(path).map(|m| m.len()) else {
+        return 0;
+    };
+    if file_len == 0 {
+        return 0;
+    }
+
+    let Some((_, first_line)) = read_line_at_or_after(path, 0) else {
+        return 0;
+    };
+    let Some(first_time) = line_time(&first_line) else {
+        return 0;
+    };
+    if first_time >= start {
+        return 0;
+    }
+
+    let mut lo = 0u64;
+    let mut hi = file_len;
+    // Offset of the earliest line found so far at or after `start`; `hi`
+    // only bounds where the next probe may land.
+    let mut answer = file_len;
+
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        match read_line_at_or_after(path, mid) {
+            None => hi = mid,
+            Some((line_offset, line)) => {
+                if line_offset <= lo {
+                    // `mid` is still within the line at `lo`, whose time we
+                    // already know is before `start` - skip past it directly
+                    // instead of re-probing the same line forever.
+                    lo += line.len() as u64;
+                    continue;
+                }
+                if line_offset >= answer {
+                    // No line starts in `[mid, answer)`; search below `mid`.
+                    hi = mid;
+                    continue;
+                }
+                match line_time(&line) {
+                    Some(time) if time < start => lo = line_offset,
+                    Some(_) => {
+                        answer = line_offset;
+                        hi = line_offset;
+                    }
+                    None => return 0,
+                }
+            }
+        }
+    }
+
+    answer
+}
+
+/// Back `offset` up by `slack_lines` lines, scanning backward in
+/// doubling-sized chunks. Backs all the way up to `0` if the file has fewer
+/// than `slack_lines` lines before `offset`.
+fn back_off_by_lines(path: &str, offset: u64, slack_lines: usize) -> u64 {
+    if offset == 0 || slack_lines == 0 {
+        return offset;
+    }
+
+    let mut chunk_size: u64 = 64 * 1024;
+    loop {
+        let window_start = offset.saturating_sub(chunk_size);
+        let Ok(mut file) = std::fs::File::open(path) else {
+            return offset;
+        };
+        if file.seek(SeekFrom::Start(window_start)).is_err() {
+            return offset;
+        }
+        let mut buf = vec![0u8; (offset - window_start) as usize];
+        if file.read_exact(&mut buf).is_err() {
+            return offset;
+        }
+
+        let mut lines_seen = 0usize;
+        for (i, &byte) in buf.iter().enumerate().rev() {
+            if byte == b'\n' {
+                lines_seen += 1;
+                if lines_seen > slack_lines {
+                    return window_start + i as u64 + 1;
+                }
+            }
+        }
+
+        if window_start == 0 {
+            return 0;
+        }
+        chunk_size *= 2;
+    }
+}
+
+/// One file's independent partial entity map, produced by [`process_creation_file`]
+/// on a rayon worker thread as if that file were the only one in the corpus.
+/// [`merge_creation_file`] folds these back together in file order.
+struct CreationFileResult {
+    file_idx: usize,
+    lines_this_file: usize,
+    entities: HashMap<String, EntityCreationRecord>,
+}
+
+/// Parse one log file into a partial `EntityCreationRecord` map.
+///
+/// `since` is applied by binary-searching this file (assumed plain and
+/// local) for the byte offset of the first in-range record before opening
+/// it for sequential reads; `until` just skips later entries within this
+/// file, since parallel workers can't know when a later file in the corpus
+/// would let them stop early the way the old sequential scan could.
+#[allow(clippy::too_many_arguments)]
+fn process_creation_file(
+    file_idx: usize,
+    log_file: &str,
+    enrichment: Option<&HashMap<String, EntityMapEntry>>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    total_bytes: usize,
+    bytes_processed_total: &AtomicUsize,
+    progress: &Mutex<ProgressBar>,
+) -> Result<CreationFileResult> {
+    let start_offset = since
+        .map(|since| back_off_by_lines(log_file, bisect_start_offset(log_file, since), SEEK_SLACK_LINES))
+        .unwrap_or(0);
+
+    let file: Box<dyn Read> = if start_offset > 0 {
+        let mut f = std::fs::File::open(log_file)
+            .with_context(|| format!("Failed to open audit log file: {}", log_file))?;
+        f.seek(SeekFrom::Start(start_offset))?;
+        Box::new(f)
+    } else {
+        open_file(log_file)
+            .with_context(|| format!("Failed to open audit log file: {}", log_file))?
+    };
+    let reader = BufReader::new(file);
+
+    let mut entities: HashMap<String, EntityCreationRecord> = HashMap::new();
+    let mut lines_this_file = 0;
+    let mut bytes_this_file = start_offset as usize;
+
+    for line in reader.lines() {
+        lines_this_file += 1;
+        let line = line.context("Failed to read line from log file")?;
+        bytes_this_file += line.len() + 1;
+
+        // Update the shared progress bar periodically; only one thread holds
+        // the lock at a time, all others keep parsing in the meantime.
+        if bytes_this_file % 10_000 == 0 {
+            let total = bytes_processed_total.fetch_add(10_000, Ordering::Relaxed) + 10_000;
+            if let Ok(progress) = progress.lock() {
+                progress.update(total.min(total_bytes));
+            }
+        }
+
+        let Ok(entry) = serde_json::from_str::<AuditEntry>(&line) else {
+            continue;
+        };
+
+        let Ok(parsed) = chrono::DateTime::parse_from_rfc3339(&entry.time) else {
+            continue;
+        };
+        let timestamp = parsed.with_timezone(&Utc);
+
+        if until.is_some_and(|until| timestamp > until) {
+            continue;
+        }
+        if since.is_some_and(|since| timestamp < since) {
+            continue;
+        }
+
+        let Some(path) = entry.path() else {
+            continue;
+        };
+        if !path.starts_with("auth/") || !path.contains("/login") {
+            continue;
+        }
+
+        let Some(entity_id) = entry.entity_id().filter(|id| !id.is_empty()) else {
+            continue;
+        };
+
+        let mount_path = path.trim_end_matches("/login").to_string();
+        let display_name = enrichment
+            .and_then(|map| map.get(entity_id))
+            .map(|e| e.display_name.clone())
+            .or_else(|| entry.display_name().map(str::to_string))
+            .unwrap_or_else(|| entity_id.to_string());
+
+        match entities.get_mut(entity_id) {
+            Some(record) => {
+                record.login_count += 1;
+                if timestamp < record.first_seen_time {
+                    record.first_seen_time = timestamp;
+                    record.first_seen_file = log_file.to_string();
+                    record.mount_path = mount_path;
+                    record.display_name = display_name;
+                }
+            }
+            None => {
+                entities.insert(
+                    entity_id.to_string(),
+                    EntityCreationRecord {
+                        entity_id: entity_id.to_string(),
+                        display_name,
+                        mount_path,
+                        first_seen_file: log_file.to_string(),
+                        first_seen_time: timestamp,
+                        login_count: 1,
+                    },
+                );
+            }
+        }
+    }
+
+    Ok(CreationFileResult {
+        file_idx,
+        lines_this_file,
+        entities,
+    })
+}
+
+/// Fold one file's partial record into `store`, reusing the same
+/// "freshly-defaulted record has `login_count == 0`" sentinel as the
+/// single-entry update this replaced: earliest `first_seen_time` (and the
+/// `display_name`/`mount_path` attached to it) wins, `login_count` sums.
+fn merge_creation_file(
+    store: &mut dyn MappingStore<EntityCreationRecord>,
+    incoming: HashMap<String, EntityCreationRecord>,
+) -> Result<()> {
+    for (entity_id, incoming_record) in incoming {
+        store.upsert_with(&entity_id, move |record| {
+            if record.login_count == 0 {
+                *record = incoming_record;
+            } else {
+                record.login_count += incoming_record.login_count;
+                if incoming_record.first_seen_time < record.first_seen_time {
+                    record.first_seen_time = incoming_record.first_seen_time;
+                    record.first_seen_file = incoming_record.first_seen_file;
+                    record.mount_path = incoming_record.mount_path;
+                    record.display_name = incoming_record.display_name;
+                }
+            }
+        })?;
+    }
+    Ok(())
+}
+
+/// Scan `log_files` once, recording each entity's first login and which
+/// auth mount path it first appeared under, into `store`. A multi-terabyte
+/// corpus with millions of distinct entities never needs the whole set of
+/// records resident in RAM at once when `store` is a `--store-backend sled`.
+///
+/// Files are parsed independently on a `threads`-sized rayon worker pool
+/// (map phase), then folded back together in chronological file order (the
+/// reduce phase, via [`merge_creation_file`]) so the merged result is
+/// byte-identical to the old strictly-sequential scan regardless of which
+/// worker finishes first.
+///
+/// `since`/`until` narrow the scan to entries in `[since, until]`. `since`
+/// is applied by binary-searching each plain (uncompressed, local) log file
+/// for the byte offset of the first in-range record before opening it for
+/// sequential reads, so a corpus of hundreds of millions of lines doesn't
+/// need to be parsed up to the window of interest.
+fn scan_into_store(
+    log_files: &[String],
+    entity_map: Option<&str>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    threads: Option<usize>,
+    store: &mut dyn MappingStore<EntityCreationRecord>,
+) -> Result<()> {
+    let enrichment = entity_map.map(load_entity_map).transpose()?;
+
+    // Resolve `s3://bucket/prefix/` and `s3://bucket/.../*.log` entries down
+    // to concrete per-object keys before processing.
+    let log_files = crate::utils::reader::expand_sources(log_files)?;
+
+    let total_bytes: usize = log_files
+        .iter()
+        .map(|f| std::fs::metadata(f).map(|m| m.len()).unwrap_or(0) as usize)
+        .sum();
+    let bytes_processed_total = Arc::new(AtomicUsize::new(0));
+    let progress = Arc::new(Mutex::new(ProgressBar::new(total_bytes, "Processing")));
+
+    let worker_threads = threads.unwrap_or(0); // 0 tells rayon to pick a sensible default
+    println!(
+        "Scanning {} log files in parallel ({})...",
+        log_files.len(),
+        if worker_threads == 0 {
+            "auto thread count".to_string()
+        } else {
+            format!("{} threads", worker_threads)
+        }
+    );
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(worker_threads)
+        .build()
+        .context("Failed to build entity-creation worker thread pool")?;
+
+    let mut file_results: Vec<CreationFileResult> = pool.install(|| {
+        log_files
+            .par_iter()
+            .enumerate()
+            .map(|(file_idx, log_file)| {
+                process_creation_file(
+                    file_idx,
+                    log_file,
+                    enrichment.as_ref(),
+                    since,
+                    until,
+                    total_bytes,
+                    &bytes_processed_total,
+                    &progress,
+                )
+            })
+            .collect::<Result<Vec<_>>>()
+    })?;
+
+    if let Ok(progress) = progress.lock() {
+        progress.finish();
+    }
+    file_results.sort_by_key(|r| r.file_idx);
+
+    for result in file_results {
+        eprintln!(
+            "[{}/{}] Processed {} lines from {}",
+            result.file_idx + 1,
+            log_files.len(),
+            format_number(result.lines_this_file),
+            log_files[result.file_idx]
+        );
+        merge_creation_file(store, result.entities)?;
+    }
+
+    Ok(())
+}
+
+/// Scan `log_files` and return the resulting creation records, sorted by
+/// first-seen time. Thin wrapper over [`scan_into_store`] for callers that
+/// just want the in-memory `Vec` (e.g. the default `--store-backend memory`).
+#[allow(clippy::too_many_arguments)]
+fn analyze(
+    log_files: &[String],
+    entity_map: Option<&str>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    threads: Option<usize>,
+    store_backend: StoreBackend,
+    store_path: Option<&str>,
+) -> Result<Vec<EntityCreationRecord>> {
+    let mut store = open_store::<EntityCreationRecord>(store_backend, store_path)?;
+    scan_into_store(log_files, entity_map, since, until, threads, store.as_mut())?;
+
+    let mut records: Vec<_> = store.iter()?.map(|(_, record)| record).collect();
+    records.sort_by(|a, b| a.first_seen_time.cmp(&b.first_seen_time));
+    Ok(records)
+}
+
+fn print_summary(records: &[EntityCreationRecord]) {
+    println!("\n=== Entity Creation Summary ===");
+    println!("Total entities: {}", format_number(records.len()));
+
+    let mut by_mount: HashMap<&str, usize> = HashMap::new();
+    for record in records {
+        *by_mount.entry(record.mount_path.as_str()).or_insert(0) += 1;
+    }
+
+    let mut by_mount: Vec<_> = by_mount.into_iter().collect();
+    by_mount.sort_by(|a, b| b.1.cmp(&a.1));
+
+    println!("\nNew entities by auth mount path:");
+    for (mount_path, count) in by_mount.iter().take(20) {
+        println!("  {}: {} entities", mount_path, format_number(*count));
+    }
+}
+
+fn arrow_schema() -> Arc<Schema> {
+    Arc::new(Schema::new(vec![
+        Field::new("entity_id", DataType::Utf8, false),
+        Field::new("display_name", DataType::Utf8, false),
+        Field::new("mount_path", DataType::Utf8, false),
+        Field::new("first_seen_file", DataType::Utf8, false),
+        Field::new(
+            "first_seen_time",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            false,
+        ),
+        Field::new("login_count", DataType::UInt64, false),
+    ]))
+}
+
+fn record_batch(records: &[EntityCreationRecord]) -> Result<RecordBatch> {
+    let schema = arrow_schema();
+
+    let first_seen_time = TimestampMicrosecondArray::from_iter_values(
+        records.iter().map(|r| r.first_seen_time.timestamp_micros()),
+    )
+    .with_timezone("UTC".to_string());
+
+    let columns: Vec<Arc<dyn Array>> = vec![
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|r| r.entity_id.as_str()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|r| r.display_name.as_str()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|r| r.mount_path.as_str()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|r| r.first_seen_file.as_str()),
+        )),
+        Arc::new(first_seen_time),
+        Arc::new(UInt64Array::from_iter_values(
+            records.iter().map(|r| r.login_count as u64),
+        )),
+    ];
+
+    RecordBatch::try_new(schema, columns).context("Failed to assemble creation Arrow RecordBatch")
+}
+
+fn write_arrow_export(output_path: &str, records: &[EntityCreationRecord]) -> Result<()> {
+    let schema = arrow_schema();
+    let batch = record_batch(records)?;
+    let file = File::create(output_path)
+        .with_context(|| format!("Failed to create output file: {}", output_path))?;
+    let mut writer =
+        ArrowFileWriter::try_new(file, &schema).context("Failed to create Arrow IPC writer")?;
+    writer
+        .write(&batch)
+        .context("Failed to write Arrow IPC batch")?;
+    writer.finish().context("Failed to finish Arrow IPC file")?;
+    Ok(())
+}
+
+fn write_parquet_export(output_path: &str, records: &[EntityCreationRecord]) -> Result<()> {
+    let schema = arrow_schema();
+    let batch = record_batch(records)?;
+    let file = File::create(output_path)
+        .with_context(|| format!("Failed to create output file: {}", output_path))?;
+    let mut writer = ParquetArrowWriter::try_new(file, schema, Some(WriterProperties::builder().build()))
+        .context("Failed to create Parquet writer")?;
+    writer
+        .write(&batch)
+        .context("Failed to write Parquet batch")?;
+    writer.close().context("Failed to finish Parquet file")?;
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct EntityCreationRecordCsv {
+    entity_id: String,
+    display_name: String,
+    mount_path: String,
+    first_seen_file: String,
+    first_seen_time: DateTime<Utc>,
+    login_count: usize,
+}
+
+impl From<EntityCreationRecord> for EntityCreationRecordCsv {
+    fn from(r: EntityCreationRecord) -> Self {
+        Self {
+            entity_id: r.entity_id,
+            display_name: r.display_name,
+            mount_path: r.mount_path,
+            first_seen_file: r.first_seen_file,
+            first_seen_time: r.first_seen_time,
+            login_count: r.login_count,
+        }
+    }
+}
+
+/// Run entity creation analysis, optionally exporting detailed records.
+///
+/// `format` selects the export format (`json`, `csv`, `arrow`, or
+/// `parquet`); when `None`, it's inferred from `output`'s extension,
+/// defaulting to `json`. `since`/`until` accept either an RFC3339 timestamp
+/// or a relative duration like `"7d"` (see
+/// [`crate::utils::time::resolve_time_bound`]) and narrow the scan to that
+/// window, skipping the seek fast path for any log file it can't speed up.
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    log_files: &[String],
+    entity_map: Option<&str>,
+    output: Option<&str>,
+    format: Option<&str>,
+    since: Option<&str>,
+    until: Option<&str>,
+    threads: Option<usize>,
+    otel_endpoint: Option<&str>,
+    store_backend: StoreBackend,
+    store_path: Option<&str>,
+    s3_endpoint: Option<&str>,
+) -> Result<()> {
+    let _otel_handle = crate::utils::otel::init(otel_endpoint)?;
+    let _run_span = crate::utils::otel::run_span("entity_creation");
+    crate::utils::s3::apply_endpoint_override(s3_endpoint);
+
+    let now = Utc::now();
+    let since_bound = since
+        .map(|s| crate::utils::time::resolve_time_bound(s, now))
+        .transpose()
+        .context("Invalid --since")?;
+    let until_bound = until
+        .map(|s| crate::utils::time::resolve_time_bound(s, now))
+        .transpose()
+        .context("Invalid --until")?;
+
+    let records = analyze(
+        log_files,
+        entity_map,
+        since_bound,
+        until_bound,
+        threads,
+        store_backend,
+        store_path,
+    )?;
+    print_summary(&records);
+
+    crate::utils::otel::record_run_metrics(
+        "entity_creation",
+        &[
+            ("entities.created", records.len() as u64),
+            (
+                "logins.total",
+                records.iter().map(|r| r.login_count as u64).sum(),
+            ),
+        ],
+    );
+
+    let Some(output_path) = output else {
+        return Ok(());
+    };
+
+    let output_format = format.unwrap_or_else(|| {
+        match std::path::Path::new(output_path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_lowercase)
+            .as_deref()
+        {
+            Some("csv") => "csv",
+            Some("parquet") => "parquet",
+            Some("arrow" | "feather") => "arrow",
+            _ => "json",
+        }
+    });
+
+    println!(
+        "\nExporting entity creation records to {} (format: {})...",
+        output_path, output_format
+    );
+
+    match output_format {
+        "csv" => {
+            let output_file = File::create(output_path)
+                .with_context(|| format!("Failed to create output file: {}", output_path))?;
+            let mut writer = csv::Writer::from_writer(output_file);
+            for record in &records {
+                let csv_record: EntityCreationRecordCsv = record.clone().into();
+                writer
+                    .serialize(&csv_record)
+                    .context("Failed to write CSV record")?;
+            }
+            writer.flush().context("Failed to flush CSV writer")?;
+        }
+        "parquet" => write_parquet_export(output_path, &records)?,
+        "arrow" => write_arrow_export(output_path, &records)?,
+        _ => {
+            let output_file = File::create(output_path)
+                .with_context(|| format!("Failed to create output file: {}", output_path))?;
+            serde_json::to_writer_pretty(output_file, &records)
+                .context("Failed to write JSON output")?;
+        }
+    }
+
+    Ok(())
+}
@@ -40,18 +40,35 @@
 //! - System operations
 //! - Potential authentication issues
 //! - Unauthenticated access patterns
+//!
+//! `--format table` (the default) prints the sections below. `--format json`
+//! emits the same operation-type/path breakdown as one document; `--format
+//! ndjson` streams one [`GapsCategoryRow`] per operation type and path - see
+//! [`crate::utils::report`].
 
 use crate::audit::types::AuditEntry;
 use crate::utils::format::format_number;
 use crate::utils::processor::{ProcessingMode, ProcessorBuilder};
+use crate::utils::report::{self, OutputFormat, Report};
+use crate::utils::time::parse_timestamp;
 use anyhow::Result;
+use serde::Serialize;
 use std::collections::HashMap;
 
+/// Per-window aggregate of no-entity activity, keyed by `floor(timestamp / window_seconds)`.
+#[derive(Debug, Clone, Default)]
+struct WindowBucket {
+    count: usize,
+    operations_by_type: HashMap<String, usize>,
+    paths_accessed: HashMap<String, usize>,
+}
+
 #[derive(Debug, Clone)]
 struct GapsState {
     operations_by_type: HashMap<String, usize>,
     paths_accessed: HashMap<String, usize>,
     no_entity_operations: usize,
+    windows: HashMap<i64, WindowBucket>,
 }
 
 impl GapsState {
@@ -60,6 +77,7 @@ impl GapsState {
             operations_by_type: HashMap::new(),
             paths_accessed: HashMap::new(),
             no_entity_operations: 0,
+            windows: HashMap::new(),
         }
     }
 
@@ -77,11 +95,222 @@ impl GapsState {
         // Merge counters
         self.no_entity_operations += other.no_entity_operations;
 
+        // Merge windows
+        for (window, bucket) in other.windows {
+            let entry = self.windows.entry(window).or_default();
+            entry.count += bucket.count;
+            for (op, count) in bucket.operations_by_type {
+                *entry.operations_by_type.entry(op).or_insert(0) += count;
+            }
+            for (path, count) in bucket.paths_accessed {
+                *entry.paths_accessed.entry(path).or_insert(0) += count;
+            }
+        }
+
         self
     }
 }
 
-pub fn run(log_files: &[String], _window_seconds: u64) -> Result<()> {
+/// A burst window whose count exceeded the mean-plus-3-sigma threshold taken over all windows.
+struct BurstWindow {
+    window_start: i64,
+    count: usize,
+    top_operation: Option<String>,
+    top_path: Option<String>,
+}
+
+/// Flag windows whose count exceeds `mean + 3*stddev` over all windows.
+fn detect_bursts(windows: &HashMap<i64, WindowBucket>, window_seconds: u64) -> Vec<BurstWindow> {
+    if windows.is_empty() {
+        return Vec::new();
+    }
+
+    let counts: Vec<f64> = windows.values().map(|b| b.count as f64).collect();
+    let mean = counts.iter().sum::<f64>() / counts.len() as f64;
+    let variance = counts.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / counts.len() as f64;
+    let stddev = variance.sqrt();
+    let threshold = mean + 3.0 * stddev;
+
+    let mut bursts: Vec<BurstWindow> = windows
+        .iter()
+        .filter(|(_, bucket)| bucket.count as f64 > threshold)
+        .map(|(window, bucket)| {
+            let top_operation = bucket
+                .operations_by_type
+                .iter()
+                .max_by_key(|(_, count)| **count)
+                .map(|(op, _)| op.clone());
+            let top_path = bucket
+                .paths_accessed
+                .iter()
+                .max_by_key(|(_, count)| **count)
+                .map(|(path, _)| path.clone());
+
+            BurstWindow {
+                window_start: window * window_seconds as i64,
+                count: bucket.count,
+                top_operation,
+                top_path,
+            }
+        })
+        .collect();
+
+    bursts.sort_by(|a, b| b.count.cmp(&a.count));
+    bursts
+}
+
+/// One operation-type or path row in `--format json`/`ndjson`, flattened so
+/// both dimensions share a single record shape.
+#[derive(Debug, Clone, Serialize)]
+struct GapsCategoryRow {
+    category: &'static str,
+    name: String,
+    count: usize,
+    percentage: f64,
+}
+
+/// One flagged burst window, reported alongside `categories` in `--format json`.
+#[derive(Debug, Clone, Serialize)]
+struct BurstRow {
+    window_start_epoch: i64,
+    count: usize,
+    top_operation: Option<String>,
+    top_path: Option<String>,
+}
+
+/// Full no-entity-operations result: summary counts plus the operation-type
+/// and path breakdowns every output format is built from.
+#[derive(Debug, Clone, Serialize)]
+struct GapsReport {
+    total_lines: usize,
+    no_entity_operations: usize,
+    percentage_no_entity: f64,
+    categories: Vec<GapsCategoryRow>,
+    bursts: Vec<BurstRow>,
+    window_seconds: u64,
+}
+
+impl Report for GapsReport {
+    type Row = GapsCategoryRow;
+
+    fn command_name(&self) -> &'static str {
+        "entity-analysis-gaps"
+    }
+
+    fn render_table(&self, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+        if self.no_entity_operations == 0 {
+            writeln!(w, "\nNo operations without entity ID found!")?;
+            return Ok(());
+        }
+
+        writeln!(w, "\n{}", "=".repeat(100))?;
+        writeln!(w, "NO-ENTITY OPERATIONS ANALYSIS")?;
+        writeln!(w, "{}", "=".repeat(100))?;
+
+        writeln!(w, "\n1. SUMMARY")?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        writeln!(
+            w,
+            "Total no-entity operations: {}",
+            format_number(self.no_entity_operations)
+        )?;
+        writeln!(
+            w,
+            "Percentage of all operations: {:.2}%",
+            self.percentage_no_entity
+        )?;
+
+        writeln!(w, "\n2. OPERATION TYPE DISTRIBUTION")?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        writeln!(w, "{:<30} {:<15} {:<15}", "Operation", "Count", "Percentage")?;
+        writeln!(w, "{}", "-".repeat(100))?;
+
+        for row in self.categories.iter().filter(|r| r.category == "operation").take(20) {
+            writeln!(
+                w,
+                "{:<30} {:<15} {:<15.2}%",
+                row.name,
+                format_number(row.count),
+                row.percentage
+            )?;
+        }
+
+        writeln!(w, "\n3. TOP 30 PATHS ACCESSED")?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        writeln!(w, "{:<70} {:>15} {:>15}", "Path", "Count", "% of No-Entity")?;
+        writeln!(w, "{}", "-".repeat(100))?;
+
+        for row in self.categories.iter().filter(|r| r.category == "path").take(30) {
+            let display_path = if row.name.len() > 68 {
+                format!("{}...", &row.name[..65])
+            } else {
+                row.name.clone()
+            };
+            writeln!(
+                w,
+                "{:<70} {:>15} {:>14.2}%",
+                display_path,
+                format_number(row.count),
+                row.percentage
+            )?;
+        }
+
+        if self.window_seconds > 0 {
+            writeln!(
+                w,
+                "\n4. BURST WINDOWS ({}s buckets, count > mean + 3\u{b7}stddev)",
+                self.window_seconds
+            )?;
+            writeln!(w, "{}", "-".repeat(100))?;
+
+            if self.bursts.is_empty() {
+                writeln!(w, "No burst windows detected.")?;
+            } else {
+                writeln!(
+                    w,
+                    "{:<22} {:>10}  {:<30} {:<30}",
+                    "Window Start (UTC)", "Count", "Top Operation", "Top Path"
+                )?;
+                writeln!(w, "{}", "-".repeat(100))?;
+
+                for burst in self.bursts.iter().take(20) {
+                    let start = chrono::DateTime::from_timestamp(burst.window_start_epoch, 0)
+                        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+                        .unwrap_or_else(|| burst.window_start_epoch.to_string());
+
+                    writeln!(
+                        w,
+                        "{:<22} {:>10}  {:<30} {:<30}",
+                        start,
+                        format_number(burst.count),
+                        burst.top_operation.as_deref().unwrap_or("-"),
+                        burst.top_path.as_deref().unwrap_or("-"),
+                    )?;
+                }
+            }
+        }
+
+        writeln!(w, "\n{}", "=".repeat(100))?;
+        Ok(())
+    }
+
+    fn rows(&self) -> &[GapsCategoryRow] {
+        &self.categories
+    }
+}
+
+pub fn run(
+    log_files: &[String],
+    window_seconds: u64,
+    otel_endpoint: Option<&str>,
+    format: &str,
+    s3_endpoint: Option<&str>,
+) -> Result<()> {
+    let format = OutputFormat::parse(format).map_err(anyhow::Error::msg)?;
+    let _otel_handle = crate::utils::otel::init(otel_endpoint)?;
+    let _run_span = crate::utils::otel::run_span("entity_gaps");
+    crate::utils::s3::apply_endpoint_override(s3_endpoint);
+
     let processor = ProcessorBuilder::new()
         .mode(ProcessingMode::Auto)
         .progress_label("Processing".to_string())
@@ -105,6 +334,21 @@ pub fn run(log_files: &[String], _window_seconds: u64) -> Result<()> {
             if let Some(path) = entry.path() {
                 *state.paths_accessed.entry(path.to_string()).or_insert(0) += 1;
             }
+
+            // Bucket into a fixed time window for burst detection
+            if window_seconds > 0 {
+                if let Ok(ts) = parse_timestamp(&entry.time) {
+                    let window = ts.timestamp() / window_seconds as i64;
+                    let bucket = state.windows.entry(window).or_default();
+                    bucket.count += 1;
+                    if let Some(op) = entry.operation() {
+                        *bucket.operations_by_type.entry(op.to_string()).or_insert(0) += 1;
+                    }
+                    if let Some(path) = entry.path() {
+                        *bucket.paths_accessed.entry(path.to_string()).or_insert(0) += 1;
+                    }
+                }
+            }
         },
         GapsState::merge,
         GapsState::new(),
@@ -114,6 +358,21 @@ pub fn run(log_files: &[String], _window_seconds: u64) -> Result<()> {
     let no_entity_operations = result.no_entity_operations;
     let operations_by_type = result.operations_by_type;
     let paths_accessed = result.paths_accessed;
+    let windows = result.windows;
+
+    let bursts_detected = if window_seconds > 0 {
+        detect_bursts(&windows, window_seconds).len() as u64
+    } else {
+        0
+    };
+    crate::utils::otel::record_run_metrics(
+        "entity_gaps",
+        &[
+            ("lines.total", total_lines as u64),
+            ("operations.no_entity", no_entity_operations as u64),
+            ("bursts.detected", bursts_detected),
+        ],
+    );
 
     eprintln!("\nTotal: Processed {} lines", format_number(total_lines));
     eprintln!(
@@ -121,68 +380,52 @@ pub fn run(log_files: &[String], _window_seconds: u64) -> Result<()> {
         format_number(no_entity_operations)
     );
 
-    if no_entity_operations == 0 {
-        println!("\nNo operations without entity ID found!");
-        return Ok(());
-    }
-
-    println!("\n{}", "=".repeat(100));
-    println!("NO-ENTITY OPERATIONS ANALYSIS");
-    println!("{}", "=".repeat(100));
-
-    println!("\n1. SUMMARY");
-    println!("{}", "-".repeat(100));
-    println!(
-        "Total no-entity operations: {}",
-        format_number(no_entity_operations)
-    );
-    println!(
-        "Percentage of all operations: {:.2}%",
-        (no_entity_operations as f64 / total_lines as f64) * 100.0
-    );
-
-    println!("\n2. OPERATION TYPE DISTRIBUTION");
-    println!("{}", "-".repeat(100));
-    println!("{:<30} {:<15} {:<15}", "Operation", "Count", "Percentage");
-    println!("{}", "-".repeat(100));
+    let mut categories: Vec<GapsCategoryRow> = Vec::new();
 
     let mut sorted_ops: Vec<_> = operations_by_type.iter().collect();
     sorted_ops.sort_by(|a, b| b.1.cmp(a.1));
-
-    for (op, count) in sorted_ops.iter().take(20) {
-        let percentage = (**count as f64 / no_entity_operations as f64) * 100.0;
-        println!(
-            "{:<30} {:<15} {:<15.2}%",
-            op,
-            format_number(**count),
-            percentage
-        );
+    for (op, count) in &sorted_ops {
+        categories.push(GapsCategoryRow {
+            category: "operation",
+            name: (*op).clone(),
+            count: **count,
+            percentage: (**count as f64 / no_entity_operations.max(1) as f64) * 100.0,
+        });
     }
 
-    println!("\n3. TOP 30 PATHS ACCESSED");
-    println!("{}", "-".repeat(100));
-    println!("{:<70} {:>15} {:>15}", "Path", "Count", "% of No-Entity");
-    println!("{}", "-".repeat(100));
-
     let mut sorted_paths: Vec<_> = paths_accessed.iter().collect();
     sorted_paths.sort_by(|a, b| b.1.cmp(a.1));
-
-    for (path, count) in sorted_paths.iter().take(30) {
-        let percentage = (**count as f64 / no_entity_operations as f64) * 100.0;
-        let display_path = if path.len() > 68 {
-            format!("{}...", &path[..65])
-        } else {
-            (*path).to_string()
-        };
-        println!(
-            "{:<70} {:>15} {:>14.2}%",
-            display_path,
-            format_number(**count),
-            percentage
-        );
+    for (path, count) in &sorted_paths {
+        categories.push(GapsCategoryRow {
+            category: "path",
+            name: (*path).clone(),
+            count: **count,
+            percentage: (**count as f64 / no_entity_operations.max(1) as f64) * 100.0,
+        });
     }
 
-    println!("\n{}", "=".repeat(100));
+    let bursts: Vec<BurstRow> = if window_seconds > 0 {
+        detect_bursts(&windows, window_seconds)
+            .into_iter()
+            .map(|burst| BurstRow {
+                window_start_epoch: burst.window_start,
+                count: burst.count,
+                top_operation: burst.top_operation,
+                top_path: burst.top_path,
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let report = GapsReport {
+        total_lines,
+        no_entity_operations,
+        percentage_no_entity: (no_entity_operations as f64 / total_lines.max(1) as f64) * 100.0,
+        categories,
+        bursts,
+        window_seconds,
+    };
 
-    Ok(())
+    report::emit(&report, format)
 }
@@ -35,9 +35,11 @@
 use crate::utils::format::format_number;
 use crate::vault_api::{extract_data, should_skip_verify, VaultClient};
 use anyhow::{Context, Result};
+use futures::stream::{self, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::File;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// Authentication mount configuration
 #[derive(Debug, Deserialize)]
@@ -91,17 +93,38 @@ struct EntityOutput {
     alias_metadata: String,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
     vault_addr: Option<&str>,
     vault_token: Option<&str>,
     vault_namespace: Option<&str>,
+    role_id: Option<&str>,
+    secret_id: Option<&str>,
     insecure: bool,
     output: Option<&str>,
     format: &str,
     filter_mount: Option<&str>,
+    resolve: &[(String, std::net::SocketAddr)],
+    dns_server: Option<std::net::SocketAddr>,
+    concurrency: usize,
+    sandbox: bool,
 ) -> Result<()> {
+    if sandbox {
+        crate::utils::sandbox::harden(output.map(std::path::Path::new))?;
+    }
+
     let skip_verify = should_skip_verify(insecure);
-    let client = VaultClient::from_options(vault_addr, vault_token, vault_namespace, skip_verify)?;
+    let client = VaultClient::connect(
+        vault_addr,
+        vault_token,
+        vault_namespace,
+        role_id,
+        secret_id,
+        skip_verify,
+        resolve,
+        dns_server,
+    )
+    .await?;
 
     eprintln!("=== Vault Entity Analysis ===");
     eprintln!("Vault Address: {}", client.addr());
@@ -127,24 +150,32 @@ pub async fn run(
     eprintln!("Found {} entities", format_number(entity_count));
     eprintln!();
 
-    // Fetch each entity's details
+    // Fetch each entity's details, bounded to `concurrency` in-flight requests
+    // at a time so we don't hammer the cluster on large installs. A failed
+    // fetch for one entity is skipped rather than aborting the whole run.
     eprintln!("Fetching entity details...");
-    let mut entities_data = Vec::new();
-    let mut processed = 0;
-
-    for entity_id in &entity_list.keys {
-        processed += 1;
-        if processed % 100 == 0 || processed == entity_count {
-            eprint!("\rProcessing entity {}/{}...", processed, entity_count);
-        }
+    let processed = AtomicUsize::new(0);
+
+    let entities_data: Vec<EntityData> = stream::iter(&entity_list.keys)
+        .map(|entity_id| {
+            let client = &client;
+            let processed = &processed;
+            async move {
+                let entity_path = format!("/v1/identity/entity/id/{}", entity_id);
+                let result = client.get_json(&entity_path).await.ok();
+
+                let done = processed.fetch_add(1, Ordering::Relaxed) + 1;
+                if done % 100 == 0 || done == entity_count {
+                    eprint!("\rProcessing entity {}/{}...", done, entity_count);
+                }
 
-        let entity_path = format!("/v1/identity/entity/id/{}", entity_id);
-        if let Ok(entity_json) = client.get_json(&entity_path).await {
-            if let Ok(entity) = extract_data::<EntityData>(entity_json) {
-                entities_data.push(entity);
+                result.and_then(|entity_json| extract_data::<EntityData>(entity_json).ok())
             }
-        }
-    }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .filter_map(|entity| async move { entity })
+        .collect()
+        .await;
     eprintln!("\n");
 
     // Convert to output format
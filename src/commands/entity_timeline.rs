@@ -41,30 +41,520 @@
 //! - Time-based patterns (hourly distribution)
 //! - Mount point usage
 //! - First and last seen timestamps
+//!
+//! # Machine-readable output
+//!
+//! `--format text` (the default) prints the sections above. `--format json`
+//! serializes a [`TimelineReport`] with the same summary statistics,
+//! operation/path breakdowns, hourly buckets, and behavioral findings.
+//! `--format ndjson` instead streams one JSON object per timeline
+//! [`Operation`], for piping into line-oriented consumers - see
+//! [`crate::utils::report`].
+//!
+//! `--since`/`--until` (RFC3339 or a relative duration like `"7d"`, via
+//! [`crate::utils::time::resolve_time_bound`]) skip entries outside the
+//! window during the read loop; `--bucket` sizes the activity-pattern
+//! buckets (default `1h`) and `--window` sizes the peak-activity windows
+//! (default `5m`), both parsed by [`crate::utils::time::parse_duration`]
+//! instead of being fixed at hourly/5-minute.
+//!
+//! `--outlier-sigma` (default `3.0`) flags hour-of-day buckets that exceed
+//! `mean + sigma*stddev` across the entity's own 24-hour profile, and
+//! `--diurnal-concentration-threshold` (default `0.5`) flags a single
+//! dominant hour as an unusually narrow diurnal profile - see
+//! [`detect_diurnal_outliers`].
+//!
+//! `--output-dir` writes the report to a rotation-style file instead of
+//! stdout - `entity-<id>-<run time>.txt`/`.json`/`.ndjson` (see
+//! [`output_file_name`]) - so scheduled runs against rolling logs never
+//! clobber a prior output. stdout stays silent in this mode (progress still
+//! goes to stderr) and [`run`] returns the written path.
 
 use crate::audit::types::AuditEntry;
 use crate::utils::format::format_number;
 use crate::utils::progress::ProgressBar;
 use crate::utils::reader::open_file;
-use anyhow::Result;
+use crate::utils::report::{self, OutputFormat, Report};
+use anyhow::{Context, Result};
 use chrono::{DateTime, Timelike, Utc};
+use serde::Serialize;
 use std::collections::HashMap;
 use std::io::{BufRead, BufReader};
 
-#[derive(Clone)]
-#[allow(dead_code)]
+#[derive(Clone, Serialize)]
 struct Operation {
     timestamp: DateTime<Utc>,
     path: String,
     op: String,
 }
 
-pub fn run(log_files: &[String], entity_id: &str, display_name: Option<&String>) -> Result<()> {
-    println!("Analyzing timeline for entity: {}", entity_id);
+/// Floor `timestamp` to the start of its `bucket_secs`-wide, UTC-epoch
+/// aligned bucket (the same floor-to-window approach `k8s_auth` uses for
+/// its spike-detection buckets).
+fn bucket_start(timestamp: DateTime<Utc>, bucket_secs: u64) -> DateTime<Utc> {
+    if bucket_secs == 0 {
+        return timestamp;
+    }
+    let epoch = timestamp.timestamp();
+    let floored = epoch - epoch.rem_euclid(bucket_secs as i64);
+    DateTime::from_timestamp(floored, 0).unwrap_or(timestamp)
+}
+
+/// One entry in `operations_by_type`/top-paths, flattened for JSON.
+#[derive(Debug, Clone, Serialize)]
+struct PathCount {
+    path: String,
+    count: usize,
+    percentage: f64,
+}
+
+/// One row of the "HOURLY ACTIVITY PATTERN" table.
+#[derive(Debug, Clone, Serialize)]
+struct HourBucket {
+    hour: String,
+    total: usize,
+    read: usize,
+    update: usize,
+    list: usize,
+    other: usize,
+}
+
+/// One row of the "PEAK ACTIVITY WINDOWS" table.
+#[derive(Debug, Clone, Serialize)]
+struct PeakWindow {
+    window_start: String,
+    operations: usize,
+    ops_per_sec: f64,
+}
+
+/// One contiguous run of GCRA rate-violating operations.
+#[derive(Debug, Clone, Serialize)]
+struct GcraViolationSpan {
+    start: String,
+    end: String,
+    op_count: usize,
+    peak_ops_per_sec: f64,
+    top_path: String,
+    top_operation: String,
+}
+
+/// Sustained-rate burst detection, modeled on the Generic Cell Rate
+/// Algorithm: the entity is allowed `rate` ops per `period_secs`, with
+/// `burst_tolerance` emission-intervals of slack before a violation fires.
+#[derive(Debug, Clone, Serialize)]
+struct GcraReport {
+    sustained_rate: f64,
+    rate_period_secs: u64,
+    burst_tolerance: f64,
+    violations: Vec<GcraViolationSpan>,
+}
+
+/// Walks `ops` (must be sorted by timestamp) tracking a Theoretical Arrival
+/// Time (TAT) per the Generic Cell Rate Algorithm, flags every operation
+/// that arrives before `TAT - burst_tolerance * emission_interval`, and
+/// groups contiguous flagged operations into violation spans.
+fn detect_gcra_violations(
+    ops: &[Operation],
+    rate: f64,
+    period_secs: u64,
+    burst_tolerance: f64,
+) -> Vec<GcraViolationSpan> {
+    if ops.is_empty() || rate <= 0.0 {
+        return Vec::new();
+    }
+
+    let emission_interval = chrono::Duration::milliseconds(
+        (period_secs as f64 / rate * 1000.0).round() as i64,
+    );
+    let tolerance = chrono::Duration::milliseconds(
+        (burst_tolerance * period_secs as f64 / rate * 1000.0).round() as i64,
+    );
+
+    let mut tat = ops[0].timestamp;
+    let violations: Vec<bool> = ops
+        .iter()
+        .map(|op| {
+            let is_violation = op.timestamp < tat - tolerance;
+            tat = std::cmp::max(tat, op.timestamp) + emission_interval;
+            is_violation
+        })
+        .collect();
+
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while i < ops.len() {
+        if !violations[i] {
+            i += 1;
+            continue;
+        }
+        let start_idx = i;
+        let mut end_idx = i;
+        while end_idx + 1 < ops.len() && violations[end_idx + 1] {
+            end_idx += 1;
+        }
+        let span_ops = &ops[start_idx..=end_idx];
+        let start = span_ops.first().unwrap().timestamp;
+        let end = span_ops.last().unwrap().timestamp;
+        let duration_secs = (end - start).num_milliseconds() as f64 / 1000.0;
+        let peak_ops_per_sec = if duration_secs > 0.0 {
+            span_ops.len() as f64 / duration_secs
+        } else {
+            span_ops.len() as f64
+        };
+
+        let mut path_counts: HashMap<&str, usize> = HashMap::new();
+        let mut op_counts: HashMap<&str, usize> = HashMap::new();
+        for op in span_ops {
+            *path_counts.entry(op.path.as_str()).or_insert(0) += 1;
+            *op_counts.entry(op.op.as_str()).or_insert(0) += 1;
+        }
+        let top_path = path_counts
+            .iter()
+            .max_by_key(|(_, count)| **count)
+            .map(|(path, _)| (*path).to_string())
+            .unwrap_or_default();
+        let top_operation = op_counts
+            .iter()
+            .max_by_key(|(_, count)| **count)
+            .map(|(op, _)| (*op).to_string())
+            .unwrap_or_default();
+
+        spans.push(GcraViolationSpan {
+            start: start.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            end: end.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+            op_count: span_ops.len(),
+            peak_ops_per_sec,
+            top_path,
+            top_operation,
+        });
+
+        i = end_idx + 1;
+    }
+
+    spans
+}
+
+/// Computes the mean and population standard deviation of the 24
+/// hour-of-day buckets (treating missing hours as zero), flags hours
+/// exceeding `mean + outlier_sigma * stddev` as activity spikes, and flags
+/// a single dominant hour holding more than `concentration_threshold` of
+/// all activity as an unusually concentrated diurnal profile.
+fn detect_diurnal_outliers(
+    hour_of_day_stats: &HashMap<u32, usize>,
+    total_ops: usize,
+    outlier_sigma: f64,
+    concentration_threshold: f64,
+) -> Vec<PatternFinding> {
+    let counts: Vec<f64> = (0..24).map(|h| *hour_of_day_stats.get(&h).unwrap_or(&0) as f64).collect();
+    let mean = counts.iter().sum::<f64>() / 24.0;
+    let variance = counts.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / 24.0;
+    let stddev = variance.sqrt();
+
+    let mut findings = Vec::new();
+
+    if stddev > 0.0 {
+        let threshold = mean + outlier_sigma * stddev;
+        let outlier_hours: Vec<String> = (0..24)
+            .filter(|h| counts[*h as usize] > threshold)
+            .map(|h| format!("{:02}:00 ({} ops)", h, counts[h as usize] as usize))
+            .collect();
+        if !outlier_hours.is_empty() {
+            findings.push(PatternFinding {
+                kind: "HOUR-OF-DAY OUTLIER".to_string(),
+                severity: "medium".to_string(),
+                message: format!(
+                    "{} hour-of-day bucket(s) exceed {:.1} + {}*{:.1} ops",
+                    outlier_hours.len(),
+                    mean,
+                    outlier_sigma,
+                    stddev
+                ),
+                recommended_action: "Review activity in the flagged hours against this entity's normal rhythm".to_string(),
+                evidence: outlier_hours.join(", "),
+            });
+        }
+    }
+
+    if total_ops > 0 {
+        if let Some((peak_hour, peak_count)) = counts
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(std::cmp::Ordering::Equal))
+        {
+            let fraction = peak_count / total_ops as f64;
+            if fraction > concentration_threshold {
+                findings.push(PatternFinding {
+                    kind: "DIURNAL CONCENTRATION".to_string(),
+                    severity: "low".to_string(),
+                    message: format!(
+                        "{:.1}% of all activity falls in a single hour of day ({:02}:00)",
+                        fraction * 100.0,
+                        peak_hour
+                    ),
+                    recommended_action: "Unusually narrow diurnal profile - confirm this matches the entity's expected schedule".to_string(),
+                    evidence: format!("{:02}:00 = {} of {} total ops", peak_hour, *peak_count as usize, total_ops),
+                });
+            }
+        }
+    }
+
+    findings
+}
+
+/// Builds the rotation-style output filename for `--output-dir`: a
+/// `entity-<id>-<run time>.<ext>` name where `<run time>` is `now` formatted
+/// as `YYYY-MM-DD-HH:MM:SS` so repeated runs against rolling logs never
+/// clobber a prior file. `entity_id` is sanitized to `_` for characters that
+/// don't belong in a filename (vault entity IDs are UUIDs, but display names
+/// aren't always passed through here, so this stays defensive).
+fn output_file_name(entity_id: &str, format: OutputFormat, now: DateTime<Utc>) -> String {
+    let safe_entity_id: String = entity_id
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    let ext = match format {
+        OutputFormat::Table => "txt",
+        OutputFormat::Json => "json",
+        OutputFormat::Ndjson => "ndjson",
+    };
+    format!(
+        "entity-{}-{}.{}",
+        safe_entity_id,
+        now.format("%Y-%m-%d-%H:%M:%S"),
+        ext
+    )
+}
+
+/// One triggered entry from the "BEHAVIORAL PATTERNS" section.
+#[derive(Debug, Clone, Serialize)]
+struct PatternFinding {
+    kind: String,
+    severity: String,
+    message: String,
+    recommended_action: String,
+    evidence: String,
+}
+
+/// Full structured timeline result for `--format json`; `--format ndjson`
+/// streams [`Operation`] rows instead (see [`Report::rows`]).
+#[derive(Debug, Clone, Serialize)]
+struct TimelineReport {
+    entity_id: String,
+    display_name: Option<String>,
+    total_operations: usize,
+    time_span_hours: f64,
+    time_span_days: f64,
+    avg_ops_per_hour: f64,
+    avg_ops_per_minute: f64,
+    first_seen: String,
+    last_seen: String,
+    operations_by_type: HashMap<String, usize>,
+    top_paths: Vec<PathCount>,
+    hourly_activity: Vec<HourBucket>,
+    bucket_secs: u64,
+    hour_of_day_histogram: HashMap<u32, usize>,
+    window_secs: u64,
+    peak_windows: Vec<PeakWindow>,
+    gcra: Option<GcraReport>,
+    findings: Vec<PatternFinding>,
+    #[serde(skip)]
+    operations: Vec<Operation>,
+}
+
+impl Report for TimelineReport {
+    type Row = Operation;
+
+    fn command_name(&self) -> &'static str {
+        "entity-analysis-timeline"
+    }
+
+    fn render_table(&self, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+        writeln!(w, "\n{}", "=".repeat(100))?;
+        writeln!(w, "TIMELINE ANALYSIS FOR: {}", self.entity_id)?;
+        writeln!(w, "{}", "=".repeat(100))?;
+
+        writeln!(w, "\n1. SUMMARY STATISTICS")?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        writeln!(w, "Total operations: {}", format_number(self.total_operations))?;
+        writeln!(
+            w,
+            "Time span: {:.2} hours ({:.2} days)",
+            self.time_span_hours, self.time_span_days
+        )?;
+        writeln!(
+            w,
+            "Average rate: {:.1} operations/hour ({:.2}/minute)",
+            self.avg_ops_per_hour, self.avg_ops_per_minute
+        )?;
+        writeln!(w, "First operation: {}", self.first_seen)?;
+        writeln!(w, "Last operation: {}", self.last_seen)?;
+
+        writeln!(w, "\n2. OPERATION TYPE DISTRIBUTION")?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        writeln!(w, "{:<30} {:<15} {:<15}", "Operation", "Count", "Percentage")?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        let mut sorted_ops: Vec<_> = self.operations_by_type.iter().collect();
+        sorted_ops.sort_by(|a, b| b.1.cmp(a.1));
+        for (op, count) in sorted_ops {
+            let percentage = (*count as f64 / self.total_operations as f64) * 100.0;
+            writeln!(w, "{:<30} {:<15} {:<15.2}%", op, format_number(*count), percentage)?;
+        }
+
+        writeln!(w, "\n3. TOP 30 PATHS ACCESSED")?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        writeln!(w, "{:<70} {:<15} {:<15}", "Path", "Count", "Percentage")?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        for path_count in &self.top_paths {
+            let display_path = if path_count.path.len() > 68 {
+                format!("{}...", &path_count.path[..65])
+            } else {
+                path_count.path.clone()
+            };
+            writeln!(
+                w,
+                "{:<70} {:<15} {:<15.2}%",
+                display_path,
+                format_number(path_count.count),
+                path_count.percentage
+            )?;
+        }
+
+        writeln!(
+            w,
+            "\n4. ACTIVITY PATTERN BY {}s BUCKET (Top 30)",
+            self.bucket_secs
+        )?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        writeln!(
+            w,
+            "{:<20} {:<12} {:<10} {:<10} {:<10} {:<10}",
+            "Bucket", "Total Ops", "read", "update", "list", "Other"
+        )?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        for bucket in &self.hourly_activity {
+            writeln!(
+                w,
+                "{:<20} {:<12} {:<10} {:<10} {:<10} {:<10}",
+                bucket.hour,
+                format_number(bucket.total),
+                format_number(bucket.read),
+                format_number(bucket.update),
+                format_number(bucket.list),
+                format_number(bucket.other)
+            )?;
+        }
+
+        writeln!(w, "\n5. ACTIVITY DISTRIBUTION BY HOUR OF DAY")?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        writeln!(w, "{:<10} {:<15} {:<50}", "Hour", "Operations", "Bar Chart")?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        let max_ops_in_hour = self.hour_of_day_histogram.values().max().copied().unwrap_or(1);
+        for hour in 0..24 {
+            let ops = *self.hour_of_day_histogram.get(&hour).unwrap_or(&0);
+            let bar_length = if max_ops_in_hour > 0 { (ops * 50) / max_ops_in_hour } else { 0 };
+            let bar = "█".repeat(bar_length);
+            writeln!(w, "{:02}:00     {:<15} {}", hour, format_number(ops), bar)?;
+        }
+
+        writeln!(w, "\n6. PEAK ACTIVITY WINDOWS ({}s window)", self.window_secs)?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        writeln!(
+            w,
+            "{:<25} {:<15} {:<20}",
+            "Window", "Operations", "Rate (ops/sec)"
+        )?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        for window in &self.peak_windows {
+            writeln!(
+                w,
+                "{:<25} {:<15} {:<20.3}",
+                window.window_start, format_number(window.operations), window.ops_per_sec
+            )?;
+        }
+
+        if let Some(gcra) = &self.gcra {
+            writeln!(
+                w,
+                "\n6b. SUSTAINED-RATE VIOLATIONS (GCRA, {} ops / {}s, burst tolerance {})",
+                gcra.sustained_rate, gcra.rate_period_secs, gcra.burst_tolerance
+            )?;
+            writeln!(w, "{}", "-".repeat(100))?;
+            if gcra.violations.is_empty() {
+                writeln!(w, "No sustained-rate violations detected.")?;
+            } else {
+                writeln!(
+                    w,
+                    "{:<22} {:<22} {:<10} {:<15} {:<30}",
+                    "Start", "End", "Ops", "Peak ops/sec", "Dominant path/op"
+                )?;
+                writeln!(w, "{}", "-".repeat(100))?;
+                for span in &gcra.violations {
+                    writeln!(
+                        w,
+                        "{:<22} {:<22} {:<10} {:<15.3} {} ({})",
+                        span.start,
+                        span.end,
+                        format_number(span.op_count),
+                        span.peak_ops_per_sec,
+                        span.top_path,
+                        span.top_operation
+                    )?;
+                }
+            }
+        }
+
+        writeln!(w, "\n7. BEHAVIORAL PATTERNS")?;
+        writeln!(w, "{}", "-".repeat(100))?;
+        for finding in &self.findings {
+            writeln!(w, "⚠️  {}: {}", finding.kind, finding.message)?;
+            writeln!(w, "   Recommended action: {}", finding.recommended_action)?;
+        }
+
+        writeln!(w, "\n{}", "=".repeat(100))?;
+        Ok(())
+    }
+
+    fn rows(&self) -> &[Operation] {
+        &self.operations
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    log_files: &[String],
+    entity_id: &str,
+    display_name: Option<&String>,
+    format: &str,
+    gcra_params: Option<(f64, u64, f64)>,
+    since: Option<&str>,
+    until: Option<&str>,
+    bucket_secs: u64,
+    window_secs: u64,
+    outlier_sigma: f64,
+    diurnal_concentration_threshold: f64,
+    output_dir: Option<&str>,
+    s3_endpoint: Option<&str>,
+) -> Result<Option<String>> {
+    let format = OutputFormat::parse(format).map_err(anyhow::Error::msg)?;
+    crate::utils::s3::apply_endpoint_override(s3_endpoint);
+    let now = Utc::now();
+    let since_bound = since
+        .map(|s| crate::utils::time::resolve_time_bound(s, now))
+        .transpose()
+        .context("Invalid --since")?;
+    let until_bound = until
+        .map(|s| crate::utils::time::resolve_time_bound(s, now))
+        .transpose()
+        .context("Invalid --until")?;
+    eprintln!("Analyzing timeline for entity: {}", entity_id);
     if let Some(name) = display_name {
-        println!("Display name: {}", name);
+        eprintln!("Display name: {}", name);
     }
-    println!();
+    eprintln!();
+
+    // Resolve `s3://bucket/prefix/` and `s3://bucket/.../*.log` entries down
+    // to concrete per-object keys before processing.
+    let log_files = crate::utils::reader::expand_sources(log_files)?;
 
     let mut operations_by_hour: HashMap<String, HashMap<String, usize>> = HashMap::new();
     let mut operations_by_type: HashMap<String, usize> = HashMap::new();
@@ -120,6 +610,18 @@ pub fn run(log_files: &[String], entity_id: &str, display_name: Option<&String>)
                 continue;
             }
 
+            if since_bound.is_some() || until_bound.is_some() {
+                if let Ok(entry_time) = chrono::DateTime::parse_from_rfc3339(&entry.time) {
+                    let entry_time = entry_time.with_timezone(&Utc);
+                    if since_bound.is_some_and(|since| entry_time < since) {
+                        continue;
+                    }
+                    if until_bound.is_some_and(|until| entry_time > until) {
+                        continue;
+                    }
+                }
+            }
+
             entity_operations += 1;
 
             let path = entry
@@ -138,9 +640,11 @@ pub fn run(log_files: &[String], entity_id: &str, display_name: Option<&String>)
             if let Ok(ts) = chrono::DateTime::parse_from_rfc3339(&entry.time) {
                 let ts_utc = ts.with_timezone(&Utc);
 
-                // Track by hour
-                let hour_key = ts_utc.format("%Y-%m-%d %H:00").to_string();
-                let hour_ops = operations_by_hour.entry(hour_key).or_default();
+                // Track by configurable bucket (defaults to hourly)
+                let bucket_key = bucket_start(ts_utc, bucket_secs)
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string();
+                let hour_ops = operations_by_hour.entry(bucket_key).or_default();
                 *hour_ops.entry("total".to_string()).or_insert(0) += 1;
                 *hour_ops.entry(operation.clone()).or_insert(0) += 1;
 
@@ -176,96 +680,35 @@ pub fn run(log_files: &[String], entity_id: &str, display_name: Option<&String>)
     );
 
     if entity_operations == 0 {
-        println!("\nNo operations found for this entity!");
-        return Ok(());
+        eprintln!("\nNo operations found for this entity!");
+        return Ok(None);
     }
 
     // Sort timeline
     operations_timeline.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
 
     // Calculate time span
-    let (first_op, last_op, time_span_hours) = if operations_timeline.is_empty() {
-        return Ok(());
-    } else {
+    let (first_op, last_op, time_span_hours) = {
         let first = operations_timeline.first().unwrap().timestamp;
         let last = operations_timeline.last().unwrap().timestamp;
         let span = (last - first).num_seconds() as f64 / 3600.0;
         (first, last, span)
     };
 
-    // Analysis and reporting
-    println!("\n{}", "=".repeat(100));
-    println!("TIMELINE ANALYSIS FOR: {}", entity_id);
-    println!("{}", "=".repeat(100));
-
-    // 1. Summary statistics
-    println!("\n1. SUMMARY STATISTICS");
-    println!("{}", "-".repeat(100));
-    println!("Total operations: {}", format_number(entity_operations));
-    println!(
-        "Time span: {:.2} hours ({:.2} days)",
-        time_span_hours,
-        time_span_hours / 24.0
-    );
-    println!(
-        "Average rate: {:.1} operations/hour ({:.2}/minute)",
-        entity_operations as f64 / time_span_hours,
-        entity_operations as f64 / time_span_hours / 60.0
-    );
-    println!("First operation: {}", first_op.format("%Y-%m-%d %H:%M:%S"));
-    println!("Last operation: {}", last_op.format("%Y-%m-%d %H:%M:%S"));
-
-    // 2. Operation type distribution
-    println!("\n2. OPERATION TYPE DISTRIBUTION");
-    println!("{}", "-".repeat(100));
-    println!("{:<30} {:<15} {:<15}", "Operation", "Count", "Percentage");
-    println!("{}", "-".repeat(100));
-
     let mut sorted_ops: Vec<_> = operations_by_type.iter().collect();
     sorted_ops.sort_by(|a, b| b.1.cmp(a.1));
 
-    for (op, count) in sorted_ops {
-        let percentage = (*count as f64 / entity_operations as f64) * 100.0;
-        println!(
-            "{:<30} {:<15} {:<15.2}%",
-            op,
-            format_number(*count),
-            percentage
-        );
-    }
-
-    // 3. Top paths accessed
-    println!("\n3. TOP 30 PATHS ACCESSED");
-    println!("{}", "-".repeat(100));
-    println!("{:<70} {:<15} {:<15}", "Path", "Count", "Percentage");
-    println!("{}", "-".repeat(100));
-
     let mut sorted_paths: Vec<_> = paths_accessed.iter().collect();
     sorted_paths.sort_by(|a, b| b.1.cmp(a.1));
-
-    for (path, count) in sorted_paths.iter().take(30) {
-        let percentage = (**count as f64 / entity_operations as f64) * 100.0;
-        let display_path = if path.len() > 68 {
-            format!("{}...", &path[..65])
-        } else {
-            (*path).clone()
-        };
-        println!(
-            "{:<70} {:<15} {:<15.2}%",
-            display_path,
-            format_number(**count),
-            percentage
-        );
-    }
-
-    // 4. Hourly activity pattern
-    println!("\n4. HOURLY ACTIVITY PATTERN (Top 30 Hours)");
-    println!("{}", "-".repeat(100));
-    println!(
-        "{:<20} {:<12} {:<10} {:<10} {:<10} {:<10}",
-        "Hour", "Total Ops", "read", "update", "list", "Other"
-    );
-    println!("{}", "-".repeat(100));
+    let top_paths: Vec<PathCount> = sorted_paths
+        .iter()
+        .take(30)
+        .map(|(path, count)| PathCount {
+            path: (*path).clone(),
+            count: **count,
+            percentage: (**count as f64 / entity_operations as f64) * 100.0,
+        })
+        .collect();
 
     let mut sorted_hours: Vec<_> = operations_by_hour.iter().collect();
     sorted_hours.sort_by(|a, b| {
@@ -273,28 +716,24 @@ pub fn run(log_files: &[String], entity_id: &str, display_name: Option<&String>)
         let b_total = b.1.get("total").unwrap_or(&0);
         b_total.cmp(a_total)
     });
-
-    for (hour, ops) in sorted_hours.iter().take(30) {
-        let total = *ops.get("total").unwrap_or(&0);
-        let read = *ops.get("read").unwrap_or(&0);
-        let update = *ops.get("update").unwrap_or(&0);
-        let list_operations = *ops.get("list").unwrap_or(&0);
-        let other = total - read - update - list_operations;
-
-        println!(
-            "{:<20} {:<12} {:<10} {:<10} {:<10} {:<10}",
-            hour,
-            format_number(total),
-            format_number(read),
-            format_number(update),
-            format_number(list_operations),
-            format_number(other)
-        );
-    }
-
-    // 5. Activity distribution by hour of day
-    println!("\n5. ACTIVITY DISTRIBUTION BY HOUR OF DAY");
-    println!("{}", "-".repeat(100));
+    let hourly_activity: Vec<HourBucket> = sorted_hours
+        .iter()
+        .take(30)
+        .map(|(hour, ops)| {
+            let total = *ops.get("total").unwrap_or(&0);
+            let read = *ops.get("read").unwrap_or(&0);
+            let update = *ops.get("update").unwrap_or(&0);
+            let list = *ops.get("list").unwrap_or(&0);
+            HourBucket {
+                hour: (*hour).clone(),
+                total,
+                read,
+                update,
+                list,
+                other: total - read - update - list,
+            }
+        })
+        .collect();
 
     let mut hour_of_day_stats: HashMap<u32, usize> = HashMap::new();
     for op in &operations_timeline {
@@ -302,128 +741,156 @@ pub fn run(log_files: &[String], entity_id: &str, display_name: Option<&String>)
         *hour_of_day_stats.entry(hour).or_insert(0) += 1;
     }
 
-    println!("{:<10} {:<15} {:<50}", "Hour", "Operations", "Bar Chart");
-    println!("{}", "-".repeat(100));
-
-    let max_ops_in_hour = hour_of_day_stats.values().max().copied().unwrap_or(1);
-
-    for hour in 0..24 {
-        let ops = *hour_of_day_stats.get(&hour).unwrap_or(&0);
-        let bar_length = if max_ops_in_hour > 0 {
-            (ops * 50) / max_ops_in_hour
-        } else {
-            0
-        };
-        let bar = "█".repeat(bar_length);
-        println!("{:02}:00     {:<15} {}", hour, format_number(ops), bar);
-    }
-
-    // 6. Peak activity analysis
-    println!("\n6. PEAK ACTIVITY WINDOWS");
-    println!("{}", "-".repeat(100));
-
     let mut window_counts: HashMap<DateTime<Utc>, usize> = HashMap::new();
-
     for op in &operations_timeline {
-        // Round to 5-minute window
-        let minute = (op.timestamp.minute() / 5) * 5;
-        let window_start = op
-            .timestamp
-            .with_minute(minute)
-            .unwrap()
-            .with_second(0)
-            .unwrap()
-            .with_nanosecond(0)
-            .unwrap();
+        let window_start = bucket_start(op.timestamp, window_secs);
         *window_counts.entry(window_start).or_insert(0) += 1;
     }
-
     let mut sorted_windows: Vec<_> = window_counts.iter().collect();
     sorted_windows.sort_by(|a, b| b.1.cmp(a.1));
+    let peak_windows: Vec<PeakWindow> = sorted_windows
+        .iter()
+        .take(20)
+        .map(|(window, count)| PeakWindow {
+            window_start: window.format("%Y-%m-%d %H:%M:%S").to_string(),
+            operations: **count,
+            ops_per_sec: **count as f64 / window_secs.max(1) as f64,
+        })
+        .collect();
+
+    let gcra = gcra_params.map(|(rate, period_secs, burst_tolerance)| GcraReport {
+        sustained_rate: rate,
+        rate_period_secs: period_secs,
+        burst_tolerance,
+        violations: detect_gcra_violations(&operations_timeline, rate, period_secs, burst_tolerance),
+    });
 
-    println!(
-        "{:<25} {:<15} {:<20}",
-        "5-Minute Window", "Operations", "Rate (ops/sec)"
-    );
-    println!("{}", "-".repeat(100));
-
-    for (window, count) in sorted_windows.iter().take(20) {
-        let rate = **count as f64 / 300.0;
-        println!(
-            "{:<25} {:<15} {:<20.3}",
-            window.format("%Y-%m-%d %H:%M"),
-            format_number(**count),
-            rate
-        );
+    let mut findings: Vec<PatternFinding> = Vec::new();
+    if let Some(gcra_report) = &gcra {
+        if let Some(worst) = gcra_report
+            .violations
+            .iter()
+            .max_by(|a, b| a.peak_ops_per_sec.partial_cmp(&b.peak_ops_per_sec).unwrap_or(std::cmp::Ordering::Equal))
+        {
+            findings.push(PatternFinding {
+                kind: "SUSTAINED BURST (GCRA)".to_string(),
+                severity: "high".to_string(),
+                message: format!(
+                    "{} sustained-rate violation span(s) exceeding {} ops/{}s",
+                    gcra_report.violations.len(),
+                    gcra_report.sustained_rate,
+                    gcra_report.rate_period_secs
+                ),
+                recommended_action: "Review the dominant path/operation in each span for automated or abusive access".to_string(),
+                evidence: format!(
+                    "worst span: {} ops, {:.3} ops/sec, {} ({})",
+                    worst.op_count, worst.peak_ops_per_sec, worst.top_path, worst.top_operation
+                ),
+            });
+        }
     }
-
-    // 7. Behavioral patterns
-    println!("\n7. BEHAVIORAL PATTERNS");
-    println!("{}", "-".repeat(100));
-
     if time_span_hours > 1.0 {
         let ops_per_hour = entity_operations as f64 / time_span_hours;
         if ops_per_hour > 100.0 {
-            println!(
-                "⚠️  HIGH FREQUENCY: {:.0} operations/hour suggests automated polling",
-                ops_per_hour
-            );
-            println!("   Recommended action: Implement caching or increase polling interval");
+            findings.push(PatternFinding {
+                kind: "HIGH FREQUENCY".to_string(),
+                severity: "medium".to_string(),
+                message: format!(
+                    "{:.0} operations/hour suggests automated polling",
+                    ops_per_hour
+                ),
+                recommended_action: "Implement caching or increase polling interval".to_string(),
+                evidence: format!("{:.1} ops/hour", ops_per_hour),
+            });
         }
 
-        // Check for token lookup abuse
-        let token_lookup_paths: Vec<_> = paths_accessed
-            .keys()
-            .filter(|p| p.contains("token/lookup"))
-            .collect();
-        let total_token_lookups: usize = token_lookup_paths
+        let total_token_lookups: usize = paths_accessed
             .iter()
-            .map(|p| paths_accessed.get(*p).unwrap_or(&0))
+            .filter(|(p, _)| p.contains("token/lookup"))
+            .map(|(_, count)| *count)
             .sum();
-
         if total_token_lookups > 1000 {
-            println!(
-                "⚠️  TOKEN LOOKUP ABUSE: {} token lookups detected",
-                format_number(total_token_lookups)
-            );
-            println!(
-                "   Rate: {:.1} lookups/hour = {:.2} lookups/second",
-                total_token_lookups as f64 / time_span_hours,
-                total_token_lookups as f64 / time_span_hours / 3600.0
-            );
-            println!("   Recommended action: Implement client-side token TTL tracking");
+            findings.push(PatternFinding {
+                kind: "TOKEN LOOKUP ABUSE".to_string(),
+                severity: "high".to_string(),
+                message: format!("{} token lookups detected", format_number(total_token_lookups)),
+                recommended_action: "Implement client-side token TTL tracking".to_string(),
+                evidence: format!(
+                    "{:.1} lookups/hour = {:.2} lookups/second",
+                    total_token_lookups as f64 / time_span_hours,
+                    total_token_lookups as f64 / time_span_hours / 3600.0
+                ),
+            });
         }
 
-        // Check for path concentration
         if let Some((top_path, top_count)) = sorted_paths.first() {
             let top_path_pct = (**top_count as f64 / entity_operations as f64) * 100.0;
             if top_path_pct > 30.0 {
-                println!(
-                    "⚠️  PATH CONCENTRATION: {:.1}% of operations on single path",
-                    top_path_pct
-                );
-                println!("   Path: {}", top_path);
-                println!(
-                    "   Recommended action: Review why this path is accessed {} times",
-                    format_number(**top_count)
-                );
+                findings.push(PatternFinding {
+                    kind: "PATH CONCENTRATION".to_string(),
+                    severity: "low".to_string(),
+                    message: format!("{:.1}% of operations on single path", top_path_pct),
+                    recommended_action: format!(
+                        "Review why {} is accessed {} times",
+                        top_path,
+                        format_number(**top_count)
+                    ),
+                    evidence: (*top_path).clone(),
+                });
             }
         }
 
-        // Check for 24/7 activity
-        let hours_with_activity = (0..24)
-            .filter(|h| hour_of_day_stats.contains_key(h))
-            .count();
+        let hours_with_activity = (0..24).filter(|h| hour_of_day_stats.contains_key(h)).count();
         if hours_with_activity >= 20 {
-            println!(
-                "⚠️  24/7 ACTIVITY: Active in {}/24 hours",
-                hours_with_activity
-            );
-            println!("   Suggests automated system or background process");
+            findings.push(PatternFinding {
+                kind: "24/7 ACTIVITY".to_string(),
+                severity: "low".to_string(),
+                message: format!("Active in {}/24 hours", hours_with_activity),
+                recommended_action: "Suggests automated system or background process".to_string(),
+                evidence: format!("{}/24 hours with activity", hours_with_activity),
+            });
         }
+
+        findings.extend(detect_diurnal_outliers(
+            &hour_of_day_stats,
+            entity_operations,
+            outlier_sigma,
+            diurnal_concentration_threshold,
+        ));
     }
 
-    println!("\n{}", "=".repeat(100));
+    let report_data = TimelineReport {
+        entity_id: entity_id.to_string(),
+        display_name: display_name.cloned(),
+        total_operations: entity_operations,
+        time_span_hours,
+        time_span_days: time_span_hours / 24.0,
+        avg_ops_per_hour: entity_operations as f64 / time_span_hours,
+        avg_ops_per_minute: entity_operations as f64 / time_span_hours / 60.0,
+        first_seen: first_op.format("%Y-%m-%d %H:%M:%S").to_string(),
+        last_seen: last_op.format("%Y-%m-%d %H:%M:%S").to_string(),
+        operations_by_type,
+        top_paths,
+        hourly_activity,
+        bucket_secs,
+        hour_of_day_histogram: hour_of_day_stats,
+        window_secs,
+        peak_windows,
+        gcra,
+        findings,
+        operations: operations_timeline,
+    };
 
-    Ok(())
+    if let Some(output_dir) = output_dir {
+        std::fs::create_dir_all(output_dir)?;
+        let file_name = output_file_name(entity_id, format, now);
+        let path = std::path::Path::new(output_dir).join(&file_name);
+        std::fs::write(&path, report::render_to_string(&report_data, format)?)?;
+        let path = path.to_string_lossy().to_string();
+        eprintln!("Timeline report written to: {}", path);
+        Ok(Some(path))
+    } else {
+        report::emit(&report_data, format)?;
+        Ok(None)
+    }
 }
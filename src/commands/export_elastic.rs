@@ -0,0 +1,63 @@
+//! Ship a previously exported JSON report to Elasticsearch (`export-elastic`).
+//!
+//! Reads a `--format json` export from another command in this tool
+//! (`entity-list`, `token-analysis --export`, `kv-analysis`, ...) - an
+//! array of row objects - and re-normalizes each row into an
+//! [ECS](https://www.elastic.co/guide/en/ecs/current/index.html) document
+//! via [`crate::utils::elastic::row_to_ecs_doc`], batching them to the
+//! target index's `_bulk` endpoint. This never queries Vault itself;
+//! export first with the source command's own `--format json`, then ship
+//! that file here, so existing Beats/Elastic dashboards can be fed from
+//! this tool's enriched output instead of raw audit lines.
+//!
+//! # Usage
+//!
+//! ```bash
+//! vault-audit token-analysis --abuse-threshold 50 --format json --output abuse.json
+//! vault-audit export-elastic --input abuse.json --elastic-url http://localhost:9200 --elastic-index vault-audit
+//! ```
+
+use crate::utils::elastic::{row_to_ecs_doc, BulkSender};
+use anyhow::{bail, Context, Result};
+use std::fs;
+
+pub async fn run(
+    input: &str,
+    elastic_url: &str,
+    elastic_index: &str,
+    batch_size: usize,
+) -> Result<()> {
+    let raw = fs::read_to_string(input)
+        .with_context(|| format!("Failed to read input file: {}", input))?;
+    let rows: Vec<serde_json::Value> = serde_json::from_str(&raw)
+        .with_context(|| format!("Failed to parse {} as a JSON array of rows", input))?;
+
+    let docs: Vec<serde_json::Value> = rows
+        .iter()
+        .map(|row| match row.as_object() {
+            Some(obj) => Ok(row_to_ecs_doc(obj)),
+            None => bail!("Expected {} to contain an array of JSON objects", input),
+        })
+        .collect::<Result<_>>()?;
+
+    eprintln!(
+        "Shipping {} documents to {}/_bulk (index: {})...",
+        docs.len(),
+        elastic_url,
+        elastic_index
+    );
+
+    let sender = BulkSender::new(elastic_url, elastic_index, batch_size);
+    let failed = sender.send_all(&docs).await?;
+
+    if failed > 0 {
+        eprintln!(
+            "Done with {} document(s) rejected by Elasticsearch after retries",
+            failed
+        );
+    } else {
+        eprintln!("Done, all documents indexed successfully");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,124 @@
+//! Run the token-abuse, secret-access, and no-entity-login detectors over
+//! one shared pass of the logs and normalize their hits into a single
+//! typed, severity-ranked [`Finding`](crate::audit::findings::Finding)
+//! stream, instead of the plain-text tables `token-analysis`,
+//! `path-hotspots`, and `entity-analysis gaps` each print on their own.
+//!
+//! Detection lives in
+//! [`FindingsCollector`](crate::audit::collectors::FindingsCollector),
+//! driven through [`crate::audit::engine::run_collectors`] the same way
+//! `audit-scan` drives its own pair of collectors - see that command's
+//! module doc for when to reach for a shared-pass command instead of the
+//! single-purpose ones.
+//!
+//! # Usage
+//!
+//! ```bash
+//! vault-audit findings logs/*.log
+//! vault-audit findings logs/*.log --secret-fanout-threshold 5 --privileged-ops-threshold 10
+//! ```
+
+use crate::audit::collectors::FindingsCollector;
+use crate::audit::engine::{run_collectors, Collector};
+use crate::audit::findings::{Finding, FindingCategory, Severity};
+use crate::utils::report::{self, OutputFormat, Report};
+use anyhow::Result;
+use serde::Serialize;
+
+fn category_label(category: FindingCategory) -> &'static str {
+    match category {
+        FindingCategory::SecretAccess => "SECRET_ACCESS",
+        FindingCategory::PrivilegedAuth => "PRIVILEGED_AUTH",
+        FindingCategory::PolicyAnomaly => "POLICY_ANOMALY",
+        FindingCategory::Unknown => "UNKNOWN",
+    }
+}
+
+fn severity_label(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "CRITICAL",
+        Severity::High => "HIGH",
+        Severity::Medium => "MEDIUM",
+        Severity::Low => "LOW",
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct FindingsReport {
+    findings: Vec<Finding>,
+}
+
+impl Report for FindingsReport {
+    type Row = Finding;
+
+    fn command_name(&self) -> &'static str {
+        "findings"
+    }
+
+    fn render_table(&self, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+        writeln!(w, "\n{}", "=".repeat(100))?;
+        writeln!(w, "Findings ({})", self.findings.len())?;
+        writeln!(w, "{}", "=".repeat(100))?;
+        for finding in &self.findings {
+            writeln!(
+                w,
+                "[{}] {} - {} (evidence: {})",
+                severity_label(finding.severity),
+                category_label(finding.category),
+                finding.description,
+                finding.evidence_count
+            )?;
+        }
+        writeln!(w, "{}", "=".repeat(100))?;
+        Ok(())
+    }
+
+    fn rows(&self) -> &[Finding] {
+        &self.findings
+    }
+}
+
+fn build(
+    log_files: &[String],
+    secret_fanout_threshold: usize,
+    secret_ops_threshold: usize,
+    privileged_ops_threshold: usize,
+) -> Result<FindingsReport> {
+    let mut collectors: Vec<Box<dyn Collector>> = vec![Box::new(FindingsCollector::new(
+        secret_fanout_threshold,
+        secret_ops_threshold,
+        privileged_ops_threshold,
+    ))];
+
+    let (reports, _stats) = run_collectors(log_files, &mut collectors)?;
+    let findings: Vec<Finding> = serde_json::from_value(reports[0].clone())?;
+
+    Ok(FindingsReport { findings })
+}
+
+pub fn run(
+    log_files: &[String],
+    secret_fanout_threshold: usize,
+    secret_ops_threshold: usize,
+    privileged_ops_threshold: usize,
+    format: &str,
+) -> Result<()> {
+    let format = OutputFormat::parse(format).map_err(anyhow::Error::msg)?;
+    let report = build(log_files, secret_fanout_threshold, secret_ops_threshold, privileged_ops_threshold)?;
+    report::emit(&report, format)
+}
+
+/// Same computation as [`run`], rendered to a string instead of printed -
+/// used by the golden-fixture harness in [`crate::testing`] to compare a
+/// command's output byte-for-byte against a checked-in `expected.json`.
+pub fn run_to_string(
+    log_files: &[String],
+    secret_fanout_threshold: usize,
+    secret_ops_threshold: usize,
+    privileged_ops_threshold: usize,
+    format: &str,
+) -> Result<String> {
+    let format = OutputFormat::parse(format).map_err(anyhow::Error::msg)?;
+    let report = build(log_files, secret_fanout_threshold, secret_ops_threshold, privileged_ops_threshold)?;
+    report::render_to_string(&report, format)
+}
@@ -12,6 +12,21 @@
 //!
 //! # Multi-day analysis with CSV export
 //! vault-audit k8s-auth logs/*.log --output k8s-usage.csv
+//!
+//! # Flag hourly login bursts more than 3 standard deviations above trend
+//! vault-audit k8s-auth logs/*.log --window 1h --spike-threshold 3.0
+//!
+//! # Export totals and per-entity counts as a Prometheus textfile
+//! vault-audit k8s-auth logs/*.log --metrics-file k8s_auth.prom
+//!
+//! # Continuously monitor a live Vault socket audit device
+//! vault-audit k8s-auth --follow unix:///var/run/vault-audit.sock --window 1h
+//!
+//! # Only analyze the last 24 hours of a multi-day log set
+//! vault-audit k8s-auth logs/*.log --since 24h
+//!
+//! # Chart hourly login volume as a CSV time series
+//! vault-audit k8s-auth logs/*.log --window 1h --window-output k8s-trend.csv
 //! ```
 //!
 //! # Output
@@ -28,17 +43,106 @@
 //! - Service accounts with excessive auth requests
 //! - K8s authentication patterns by namespace
 //! - Pods making frequent Vault requests
+//!
+//! # Burst Detection
+//!
+//! Passing `--window <duration>` additionally floors each
+//! login's `entry.time` to a fixed window via [`parse_timestamp`] and
+//! accumulates per-window totals and per-entity counts in a
+//! `BTreeMap<DateTime<Utc>, WindowCounts>`, so windows are walked in
+//! chronological order regardless of file/parallel processing order. An
+//! exponentially weighted moving average and variance of the window total
+//! (`alpha` = 0.3, see [`ewma_update`]) are tracked across that walk; once
+//! [`SPIKE_WARMUP_WINDOWS`] windows have primed the average, any window
+//! whose count exceeds `mean + spike_threshold * sqrt(variance)` is
+//! reported as a spike, along with the entities that drove it - see
+//! [`detect_spikes`]. Only windows that saw at least one login are walked:
+//! a gap with no logins has no entry in the map and is not fed to the EWMA
+//! as a zero-count window.
+//!
+//! The same windows are rendered as a compact Unicode sparkline in the
+//! console report (see [`render_sparkline`]), and `--window-output <path>`
+//! additionally writes them as a long-format CSV (`bucket_start,
+//! login_count,unique_entities`, one row per window in chronological
+//! order) for charting trend over a multi-day run.
+//!
+//! # Time-Window Filtering
+//!
+//! `--since`/`--until` accept either an RFC3339 timestamp or a relative
+//! duration like `"7d"`/`"24h"` (resolved via
+//! [`crate::utils::time::resolve_time_bound`]) and drop any login outside
+//! that window before it's counted or bucketed. Entries with an unparseable
+//! timestamp are kept rather than silently dropped.
+//!
+//! # Prometheus Metrics
+//!
+//! `--metrics-file <path>` writes a node_exporter-style textfile
+//! (`vault_k8s_logins_total`, `vault_k8s_unique_entities`, and one
+//! `vault_k8s_entity_login_count{entity_id="..."}` gauge per entity, capped
+//! at `--metrics-top` entities to bound cardinality - see
+//! [`build_metrics_exporter`]); `--metrics-listen <addr>` serves the same
+//! text at `/metrics` instead. Both reuse [`crate::utils::metrics`].
+//!
+//! # Follow Mode
+//!
+//! `--follow <addr>` (`unix://path` or `tcp://host:port`) connects to a
+//! live Vault `socket` audit device via [`crate::utils::reader::open_follow`]
+//! instead of reading static `log_files`, applying the same login-matching
+//! and (optional) `--window` burst logic to each entry as it arrives - see
+//! [`observe_entry`]/[`run_follow`]. Since a live stream never reaches EOF,
+//! there's no final report: an incremental summary (and any newly detected
+//! burst) prints every 30 seconds instead.
 
 use crate::audit::types::AuditEntry;
 use crate::utils::format::format_number;
+use crate::utils::metrics::MetricsExporter;
 use crate::utils::processor::{ProcessingMode, ProcessorBuilder};
-use anyhow::Result;
-use std::collections::HashMap;
+use crate::utils::time::parse_timestamp;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// EWMA smoothing factor for window-total spike detection.
+const SPIKE_EWMA_ALPHA: f64 = 0.3;
+/// Number of windows that must be observed before spike detection starts
+/// flagging, so the EWMA/variance have primed past their initial zero state.
+const SPIKE_WARMUP_WINDOWS: usize = 3;
+/// Number of top entities reported alongside each detected spike.
+const SPIKE_TOP_ENTITIES: usize = 5;
+/// Number of representative entity IDs kept per service account - a service
+/// account usually maps to one entity, but Vault allows re-provisioning, so a
+/// handful of samples is enough to notice that without holding every entity.
+const SERVICE_ACCOUNT_SAMPLE_ENTITIES: usize = 5;
+
+#[derive(Debug, Clone, Default)]
+struct WindowCounts {
+    total: usize,
+    entities: HashMap<String, usize>,
+}
+
+/// Login activity for one Kubernetes service account, keyed by
+/// `(namespace, service_account)` in [`K8sAuthState::service_accounts`].
+#[derive(Debug, Clone, Default)]
+struct ServiceAccountCounts {
+    logins: usize,
+    /// Up to [`SERVICE_ACCOUNT_SAMPLE_ENTITIES`] distinct entity IDs this
+    /// service account authenticated as.
+    sample_entities: Vec<String>,
+    /// Pod names seen in the login metadata, when the Kubernetes auth mount
+    /// is configured to include one (not all deployments set it).
+    pods: HashSet<String>,
+    /// Distinct `service_account_uid` values seen for this name/namespace -
+    /// more than one means the service account was deleted and recreated
+    /// (or the name is shared across workloads) during the log window.
+    uids: HashSet<String>,
+}
 
 #[derive(Debug, Clone)]
 struct K8sAuthState {
     k8s_logins: usize,
     entities_seen: HashMap<String, usize>,
+    service_accounts: HashMap<(String, String), ServiceAccountCounts>,
+    windows: BTreeMap<DateTime<Utc>, WindowCounts>,
 }
 
 impl K8sAuthState {
@@ -46,6 +150,8 @@ impl K8sAuthState {
         Self {
             k8s_logins: 0,
             entities_seen: HashMap::with_capacity(1000),
+            service_accounts: HashMap::new(),
+            windows: BTreeMap::new(),
         }
     }
 
@@ -54,54 +160,409 @@ impl K8sAuthState {
         for (entity, count) in other.entities_seen {
             *self.entities_seen.entry(entity).or_insert(0) += count;
         }
+        for (key, counts) in other.service_accounts {
+            let existing = self.service_accounts.entry(key).or_default();
+            existing.logins += counts.logins;
+            for entity in counts.sample_entities {
+                if existing.sample_entities.len() >= SERVICE_ACCOUNT_SAMPLE_ENTITIES {
+                    break;
+                }
+                if !existing.sample_entities.contains(&entity) {
+                    existing.sample_entities.push(entity);
+                }
+            }
+            existing.pods.extend(counts.pods);
+            existing.uids.extend(counts.uids);
+        }
+        for (bucket, counts) in other.windows {
+            let existing = self.windows.entry(bucket).or_default();
+            existing.total += counts.total;
+            for (entity, count) in counts.entities {
+                *existing.entities.entry(entity).or_insert(0) += count;
+            }
+        }
         self
     }
 }
 
-pub fn run(log_files: &[String], output: Option<&str>) -> Result<()> {
-    let processor = ProcessorBuilder::new()
-        .mode(ProcessingMode::Auto)
-        .progress_label("Processing".to_string())
-        .build();
+/// Floor `timestamp` to the start of its `window_secs`-wide, UTC-epoch
+/// aligned bucket. Returns `None` for unparseable timestamps, which the
+/// caller skips rather than aborting the whole run.
+fn bucket_start(timestamp: &str, window_secs: i64) -> Option<DateTime<Utc>> {
+    let epoch = parse_timestamp(timestamp).ok()?.timestamp();
+    let floored = epoch - epoch.rem_euclid(window_secs);
+    DateTime::from_timestamp(floored, 0)
+}
 
-    let (result, stats) = processor.process_files_streaming(
-        log_files,
-        |entry: &AuditEntry, state: &mut K8sAuthState| {
-            // Filter for successful Kubernetes auth operations (response type, no error)
-            if entry.entry_type != "response" || entry.error.is_some() {
+/// One window flagged as an abnormal burst of K8s logins.
+#[derive(Debug, Clone)]
+pub struct SpikeWindow {
+    pub window_start: DateTime<Utc>,
+    pub count: usize,
+    pub mean: f64,
+    pub stddev: f64,
+    pub top_entities: Vec<(String, usize)>,
+}
+
+/// `(mean, variance)` update for one new observation `x`, per the standard
+/// EWMA recurrence: `mean' = alpha*x + (1-alpha)*mean`,
+/// `var' = (1-alpha)*(var + alpha*(x-mean)^2)`.
+fn ewma_update(mean: f64, variance: f64, x: f64, alpha: f64) -> (f64, f64) {
+    let new_variance = (1.0 - alpha) * (variance + alpha * (x - mean).powi(2));
+    let new_mean = alpha * x + (1.0 - alpha) * mean;
+    (new_mean, new_variance)
+}
+
+/// Walk `windows` in chronological order, maintaining an EWMA mean/variance
+/// of the total login count and flagging any window (after
+/// [`SPIKE_WARMUP_WINDOWS`] primes the average) whose count exceeds
+/// `mean + spike_threshold * sqrt(variance)`.
+fn detect_spikes(
+    windows: &BTreeMap<DateTime<Utc>, WindowCounts>,
+    spike_threshold: f64,
+) -> Vec<SpikeWindow> {
+    let mut mean = 0.0_f64;
+    let mut variance = 0.0_f64;
+    let mut spikes = Vec::new();
+
+    for (i, (window_start, counts)) in windows.iter().enumerate() {
+        let x = counts.total as f64;
+        let stddev = variance.sqrt();
+
+        if i >= SPIKE_WARMUP_WINDOWS && x > mean + spike_threshold * stddev {
+            let mut top_entities: Vec<_> = counts
+                .entities
+                .iter()
+                .map(|(entity, count)| (entity.clone(), *count))
+                .collect();
+            top_entities.sort_by(|a, b| b.1.cmp(&a.1));
+            top_entities.truncate(SPIKE_TOP_ENTITIES);
+
+            spikes.push(SpikeWindow {
+                window_start: *window_start,
+                count: counts.total,
+                mean,
+                stddev,
+                top_entities,
+            });
+        }
+
+        (mean, variance) = ewma_update(mean, variance, x, SPIKE_EWMA_ALPHA);
+    }
+
+    spikes
+}
+
+/// Flags service accounts whose total login count is an outlier relative to
+/// the rest of the run, reusing the same `mean + spike_threshold * stddev`
+/// rule as [`detect_spikes`] but as a single pass over final totals rather
+/// than a walk over a time series.
+fn excessive_auth_offenders(
+    service_accounts: &HashMap<(String, String), ServiceAccountCounts>,
+    spike_threshold: f64,
+) -> Vec<(&(String, String), &ServiceAccountCounts)> {
+    if service_accounts.len() < 2 {
+        return Vec::new();
+    }
+
+    let counts: Vec<f64> = service_accounts
+        .values()
+        .map(|c| c.logins as f64)
+        .collect();
+    let mean = counts.iter().sum::<f64>() / counts.len() as f64;
+    let variance = counts.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / counts.len() as f64;
+    let stddev = variance.sqrt();
+
+    let mut offenders: Vec<_> = service_accounts
+        .iter()
+        .filter(|(_, counts)| counts.logins as f64 > mean + spike_threshold * stddev)
+        .collect();
+    offenders.sort_by(|a, b| b.1.logins.cmp(&a.1.logins));
+    offenders
+}
+
+/// Unicode block characters used to render [`render_sparkline`], from
+/// emptiest to fullest.
+const SPARKLINE_BLOCKS: [char; 8] = ['\u{2581}', '\u{2582}', '\u{2583}', '\u{2584}', '\u{2585}', '\u{2586}', '\u{2587}', '\u{2588}'];
+
+/// Renders `windows` (already in chronological order, as [`BTreeMap`]
+/// guarantees) as a single-line sparkline, one block character per window
+/// scaled relative to the busiest window, so a long run's login trend fits
+/// in one glance rather than a full table.
+fn render_sparkline(windows: &BTreeMap<DateTime<Utc>, WindowCounts>) -> String {
+    let max = windows.values().map(|w| w.total).max().unwrap_or(0);
+    if max == 0 {
+        return String::new();
+    }
+    windows
+        .values()
+        .map(|w| {
+            let level = (w.total * (SPARKLINE_BLOCKS.len() - 1)) / max;
+            SPARKLINE_BLOCKS[level]
+        })
+        .collect()
+}
+
+/// Renders this run's aggregate results as Prometheus metrics: top-level
+/// `vault_k8s_logins_total`/`vault_k8s_unique_entities` gauges, one
+/// `vault_k8s_entity_login_count` gauge per entity, and one
+/// `vault_k8s_service_account_logins_total{namespace,service_account}` gauge per service
+/// account, each sorted descending and capped at `metrics_top` to bound
+/// cardinality.
+fn build_metrics_exporter(
+    k8s_logins: usize,
+    entities_seen: &HashMap<String, usize>,
+    service_accounts: &HashMap<(String, String), ServiceAccountCounts>,
+    metrics_top: usize,
+) -> MetricsExporter {
+    let mut exporter = MetricsExporter::new();
+    exporter.gauge(
+        "vault_k8s_logins_total",
+        "Total Kubernetes/OpenShift auth logins",
+        &[],
+        k8s_logins as f64,
+    );
+    exporter.gauge(
+        "vault_k8s_unique_entities",
+        "Unique entities that performed a Kubernetes/OpenShift login",
+        &[],
+        entities_seen.len() as f64,
+    );
+
+    let mut sorted: Vec<_> = entities_seen.iter().collect();
+    sorted.sort_by(|a, b| b.1.cmp(a.1));
+    for (entity, count) in sorted.into_iter().take(metrics_top) {
+        exporter.gauge(
+            "vault_k8s_entity_login_count",
+            "Login count for a single entity, per entity",
+            &[("entity_id", entity)],
+            *count as f64,
+        );
+    }
+
+    let mut sorted_sas: Vec<_> = service_accounts.iter().collect();
+    sorted_sas.sort_by(|a, b| b.1.logins.cmp(&a.1.logins));
+    for ((namespace, service_account), counts) in sorted_sas.into_iter().take(metrics_top) {
+        exporter.gauge(
+            "vault_k8s_service_account_logins_total",
+            "Login count for a single service account, per namespace/service_account",
+            &[("namespace", namespace), ("service_account", service_account)],
+            counts.logins as f64,
+        );
+    }
+
+    exporter
+}
+
+/// Shared per-entry logic for both the batch (`process_files_streaming`)
+/// and `--follow` (live socket) ingestion paths: filters for successful
+/// Kubernetes/OpenShift login responses and folds one into `state`.
+///
+/// `since`/`until` narrow the run to a time window; entries with an
+/// unparseable timestamp are kept rather than silently dropped, matching
+/// [`crate::commands::kv_analyzer::run`]'s `--since`/`--until` behavior.
+fn observe_entry(
+    entry: &AuditEntry,
+    state: &mut K8sAuthState,
+    window_secs: Option<u64>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+) {
+    // Filter for successful Kubernetes auth operations (response type, no error)
+    if entry.entry_type != "response" || entry.error.is_some() {
+        return;
+    }
+
+    let Some(request) = &entry.request else {
+        return;
+    };
+
+    let path = match &request.path {
+        Some(p) => p.as_str(),
+        None => return,
+    };
+
+    if !path.ends_with("/login") {
+        return;
+    }
+
+    // Check if it's a K8s/OpenShift login by path OR mount_type
+    let is_k8s_by_path = path.contains("kubernetes") || path.contains("openshift");
+    let is_k8s_by_mount = request
+        .mount_type
+        .as_deref()
+        .is_some_and(|mt| mt == "kubernetes" || mt == "openshift");
+
+    if !(is_k8s_by_path || is_k8s_by_mount) {
+        return;
+    }
+
+    if since.is_some() || until.is_some() {
+        if let Ok(entry_time) = parse_timestamp(&entry.time) {
+            if since.is_some_and(|since| entry_time < since) {
                 return;
             }
-
-            let Some(request) = &entry.request else {
+            if until.is_some_and(|until| entry_time > until) {
                 return;
-            };
+            }
+        }
+    }
 
-            let path = match &request.path {
-                Some(p) => p.as_str(),
-                None => return,
-            };
+    state.k8s_logins += 1;
 
-            if !path.ends_with("/login") {
-                return;
+    let entity_id = entry.auth.as_ref().and_then(|a| a.entity_id.as_deref());
+
+    if let Some(entity_id) = entity_id {
+        *state
+            .entities_seen
+            .entry(entity_id.to_string())
+            .or_insert(0) += 1;
+    }
+
+    let metadata = entry.auth.as_ref().and_then(|a| a.metadata.as_ref());
+    let metadata_str = |key: &str| {
+        metadata
+            .and_then(|m| m.get(key))
+            .and_then(|v| v.as_str())
+    };
+    let service_account = metadata_str("service_account_name")
+        .unwrap_or("unknown")
+        .to_string();
+    let namespace = metadata_str("service_account_namespace")
+        .unwrap_or("unknown")
+        .to_string();
+
+    let sa_counts = state
+        .service_accounts
+        .entry((namespace, service_account))
+        .or_default();
+    sa_counts.logins += 1;
+    if let Some(entity_id) = entity_id {
+        if sa_counts.sample_entities.len() < SERVICE_ACCOUNT_SAMPLE_ENTITIES
+            && !sa_counts.sample_entities.iter().any(|e| e == entity_id)
+        {
+            sa_counts.sample_entities.push(entity_id.to_string());
+        }
+    }
+    if let Some(pod) = metadata_str("pod_name") {
+        sa_counts.pods.insert(pod.to_string());
+    }
+    if let Some(uid) = metadata_str("service_account_uid") {
+        sa_counts.uids.insert(uid.to_string());
+    }
+
+    if let Some(window_secs) = window_secs {
+        if let Some(bucket) = bucket_start(&entry.time, window_secs as i64) {
+            let window = state.windows.entry(bucket).or_default();
+            window.total += 1;
+            if let Some(entity_id) = entity_id {
+                *window.entities.entry(entity_id.to_string()).or_insert(0) += 1;
             }
+        }
+    }
+}
+
+/// How often a `--follow` run prints an incremental summary, since there's
+/// no EOF/file-size to report completion against.
+const FOLLOW_SUMMARY_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Stream from a live Vault `socket` audit device (`unix://...`/`tcp://...`)
+/// instead of static files, applying [`observe_entry`] to each
+/// newline-delimited JSON entry as it arrives and printing an incremental
+/// summary every [`FOLLOW_SUMMARY_INTERVAL`] rather than only at EOF, since
+/// a live stream has no natural end.
+fn run_follow(
+    addr: &str,
+    window_secs: Option<u64>,
+    spike_threshold: f64,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+) -> Result<()> {
+    use crate::utils::reader::open_follow;
+    use std::io::BufRead as _;
+
+    eprintln!("Following live audit stream at {}...", addr);
+    let reader = open_follow(addr)?;
+
+    let mut state = K8sAuthState::new();
+    let mut total_lines = 0usize;
+    let mut last_summary = std::time::Instant::now();
+
+    for line in reader.lines() {
+        let line = line.context("Failed to read from audit stream")?;
+        total_lines += 1;
+
+        if let Ok(entry) = serde_json::from_str::<AuditEntry>(&line) {
+            observe_entry(&entry, &mut state, window_secs, since, until);
+        }
 
-            // Check if it's a K8s/OpenShift login by path OR mount_type
-            let is_k8s_by_path = path.contains("kubernetes") || path.contains("openshift");
-            let is_k8s_by_mount = request
-                .mount_type
-                .as_deref()
-                .is_some_and(|mt| mt == "kubernetes" || mt == "openshift");
-
-            if is_k8s_by_path || is_k8s_by_mount {
-                state.k8s_logins += 1;
-
-                if let Some(entity_id) = entry.auth.as_ref().and_then(|a| a.entity_id.as_deref()) {
-                    *state
-                        .entities_seen
-                        .entry(entity_id.to_string())
-                        .or_insert(0) += 1;
+        if last_summary.elapsed() >= FOLLOW_SUMMARY_INTERVAL {
+            eprintln!(
+                "[follow] {} lines, {} K8s logins, {} unique entities",
+                format_number(total_lines),
+                format_number(state.k8s_logins),
+                format_number(state.entities_seen.len())
+            );
+            if let Some(latest_bucket) = state.windows.keys().next_back().copied() {
+                let spikes = detect_spikes(&state.windows, spike_threshold);
+                for spike in spikes.iter().filter(|s| s.window_start == latest_bucket) {
+                    eprintln!(
+                        "[follow] BURST at {}: {} logins (baseline {:.1} +/- {:.1})",
+                        spike.window_start.to_rfc3339(),
+                        spike.count,
+                        spike.mean,
+                        spike.stddev
+                    );
                 }
             }
+            last_summary = std::time::Instant::now();
+        }
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    log_files: &[String],
+    output: Option<&str>,
+    window_secs: Option<u64>,
+    window_output: Option<&str>,
+    spike_threshold: f64,
+    metrics_file: Option<&str>,
+    metrics_listen: Option<&str>,
+    metrics_top: usize,
+    follow: Option<&str>,
+    since: Option<&str>,
+    until: Option<&str>,
+) -> Result<()> {
+    let now = Utc::now();
+    let since_bound = since
+        .map(|s| crate::utils::time::resolve_time_bound(s, now))
+        .transpose()
+        .context("Invalid --since")?;
+    let until_bound = until
+        .map(|s| crate::utils::time::resolve_time_bound(s, now))
+        .transpose()
+        .context("Invalid --until")?;
+
+    if let Some(addr) = follow {
+        return run_follow(addr, window_secs, spike_threshold, since_bound, until_bound);
+    }
+
+    if log_files.is_empty() {
+        anyhow::bail!("at least one log file is required unless --follow is given");
+    }
+
+    let processor = ProcessorBuilder::new()
+        .mode(ProcessingMode::Auto)
+        .progress_label("Processing".to_string())
+        .build();
+
+    let (result, stats) = processor.process_files_streaming(
+        log_files,
+        |entry: &AuditEntry, state: &mut K8sAuthState| {
+            observe_entry(entry, state, window_secs, since_bound, until_bound);
         },
         K8sAuthState::merge,
         K8sAuthState::new(),
@@ -144,17 +605,143 @@ pub fn run(log_files: &[String], output: Option<&str>) -> Result<()> {
         }
     }
 
+    if !result.service_accounts.is_empty() {
+        println!("\nTop 20 Service Accounts by Login Count:");
+        println!("{}", "-".repeat(80));
+
+        let mut sorted_sas: Vec<_> = result.service_accounts.iter().collect();
+        sorted_sas.sort_by(|a, b| b.1.logins.cmp(&a.1.logins));
+
+        for (i, ((namespace, service_account), counts)) in sorted_sas.iter().take(20).enumerate() {
+            println!(
+                "{}. {}/{} - {} logins ({} entities, {} pods{})",
+                i + 1,
+                namespace,
+                service_account,
+                format_number(counts.logins),
+                counts.sample_entities.len(),
+                counts.pods.len(),
+                if counts.uids.len() > 1 {
+                    format!(", {} distinct UIDs", counts.uids.len())
+                } else {
+                    String::new()
+                }
+            );
+        }
+
+        let offenders = excessive_auth_offenders(&result.service_accounts, spike_threshold);
+        if !offenders.is_empty() {
+            println!(
+                "\nExcessive-Auth Offenders ({:.1}+ stddev above mean):",
+                spike_threshold
+            );
+            println!("{}", "-".repeat(80));
+            for ((namespace, service_account), counts) in &offenders {
+                println!(
+                    "  {}/{} - {} logins",
+                    namespace,
+                    service_account,
+                    format_number(counts.logins)
+                );
+            }
+        }
+    }
+
+    if window_secs.is_some() {
+        let spikes = detect_spikes(&result.windows, spike_threshold);
+
+        println!("\nLogin Rate Windows: {}", format_number(result.windows.len()));
+        let sparkline = render_sparkline(&result.windows);
+        if !sparkline.is_empty() {
+            println!(
+                "  {} ({} .. {})",
+                sparkline,
+                result.windows.keys().next().unwrap().to_rfc3339(),
+                result.windows.keys().next_back().unwrap().to_rfc3339()
+            );
+        }
+        if spikes.is_empty() {
+            println!("  No abnormal bursts detected.");
+        } else {
+            println!(
+                "\nDetected {} Burst{}:",
+                spikes.len(),
+                if spikes.len() == 1 { "" } else { "s" }
+            );
+            println!("{}", "-".repeat(80));
+            for spike in &spikes {
+                println!(
+                    "  {} - {} logins (baseline {:.1} +/- {:.1})",
+                    spike.window_start.to_rfc3339(),
+                    format_number(spike.count),
+                    spike.mean,
+                    spike.stddev
+                );
+                for (entity, count) in &spike.top_entities {
+                    println!("      {} - {} logins", entity, format_number(*count));
+                }
+            }
+        }
+
+        if let Some(window_output_file) = window_output {
+            use std::fs::File;
+            use std::io::Write;
+            let mut file = File::create(window_output_file)?;
+            writeln!(file, "bucket_start,login_count,unique_entities")?;
+            for (bucket_start, counts) in &result.windows {
+                writeln!(
+                    file,
+                    "{},{},{}",
+                    bucket_start.to_rfc3339(),
+                    counts.total,
+                    counts.entities.len()
+                )?;
+            }
+            println!("\nWindow time-series written to: {}", window_output_file);
+        }
+    }
+
     if let Some(output_file) = output {
         use std::fs::File;
         use std::io::Write;
         let mut file = File::create(output_file)?;
-        writeln!(file, "entity_id,login_count")?;
-        for (entity, count) in &entities_seen {
-            writeln!(file, "{},{}", entity, count)?;
+        writeln!(
+            file,
+            "namespace,service_account,login_count,unique_entities,unique_pods,unique_uids"
+        )?;
+        let mut sorted_sas: Vec<_> = result.service_accounts.iter().collect();
+        sorted_sas.sort_by(|a, b| b.1.logins.cmp(&a.1.logins));
+        for ((namespace, service_account), counts) in &sorted_sas {
+            writeln!(
+                file,
+                "{},{},{},{},{},{}",
+                namespace,
+                service_account,
+                counts.logins,
+                counts.sample_entities.len(),
+                counts.pods.len(),
+                counts.uids.len()
+            )?;
         }
         println!("\nOutput written to: {}", output_file);
     }
 
+    if metrics_file.is_some() || metrics_listen.is_some() {
+        let exporter = build_metrics_exporter(
+            k8s_logins,
+            &entities_seen,
+            &result.service_accounts,
+            metrics_top,
+        );
+        if let Some(metrics_path) = metrics_file {
+            exporter.write_textfile(metrics_path)?;
+            println!("\nMetrics written to: {}", metrics_path);
+        }
+        if let Some(addr) = metrics_listen {
+            exporter.serve_blocking(addr)?;
+        }
+    }
+
     println!("\n{}", "=".repeat(80));
 
     Ok(())
@@ -35,11 +35,22 @@
 use anyhow::Result;
 
 /// Run analyze subcommand
+#[allow(clippy::too_many_arguments)]
 pub fn run_analyze(
     log_files: &[String],
     kv_prefix: &str,
     output: Option<&String>,
     entity_csv: Option<&String>,
+    format: Option<&String>,
+    approx_clients: bool,
+    max_memory_entries: Option<usize>,
+    temp_dir: Option<&String>,
+    anomaly_report: Option<&String>,
+    anomaly_top_n: usize,
+    metrics_file: Option<&String>,
+    metrics_listen: Option<&String>,
+    since: Option<&String>,
+    until: Option<&String>,
 ) -> Result<()> {
     // Delegate to existing kv_analyzer implementation
     crate::commands::kv_analyzer::run(
@@ -47,6 +58,16 @@ pub fn run_analyze(
         kv_prefix,
         output.map(std::string::String::as_str),
         entity_csv.map(std::string::String::as_str),
+        format.map(std::string::String::as_str),
+        approx_clients,
+        max_memory_entries,
+        temp_dir.map(std::string::String::as_str),
+        anomaly_report.map(std::string::String::as_str),
+        anomaly_top_n,
+        metrics_file.map(std::string::String::as_str),
+        metrics_listen.map(std::string::String::as_str),
+        since.map(std::string::String::as_str),
+        until.map(std::string::String::as_str),
     )
 }
 
@@ -30,16 +30,53 @@
 //!
 //! # Filter specific KV mount
 //! vault-audit kv-analyzer *.log --kv-prefix "appcodes/" --output appcodes.csv
+//!
+//! # Stream to NDJSON instead of CSV (one JSON object per KV path)
+//! vault-audit kv-analyzer *.log --output kv_usage.ndjson --format ndjson
+//!
+//! # Or a single pretty-printed JSON document
+//! vault-audit kv-analyzer *.log --output kv_usage.json --format json
+//!
+//! # Approximate unique-client counts to bound memory on huge reports
+//! vault-audit kv-analyzer *.log --output kv_usage.csv --approx-clients
+//!
+//! # Flag suspicious access patterns (runaway clients, over-broad entities,
+//! # paths whose unique-client count is trending up) alongside the report
+//! vault-audit kv-analyzer *.log --output kv_usage.csv --anomaly-report anomalies.csv
+//!
+//! # Also write a node_exporter textfile with per-path operation/client counts
+//! vault-audit kv-analyzer *.log --output kv_usage.csv --metrics-file kv_usage.prom
+//!
+//! # Or serve the same metrics over HTTP for Prometheus to scrape
+//! vault-audit kv-analyzer *.log --output kv_usage.csv --metrics-listen 0.0.0.0:9102
+//!
+//! # Restrict analysis to the last 7 days (accepts RFC3339 or a duration)
+//! vault-audit kv-analyzer *.log --output kv_usage.csv --since 7d
+//! vault-audit kv-analyzer *.log --output kv_usage.csv --since 2025-10-01T00:00:00Z --until 2025-10-08T00:00:00Z
 //! ```
 //!
 //! **Compressed File Support**: Processes `.gz` and `.zst` files with no manual decompression.
 //!
+//! **Ingestion**: Already shares [`crate::audit::types::AuditEntry`] and
+//! [`crate::utils::processor::ProcessorBuilder`] with every other analysis
+//! command rather than a bespoke parser - `process_files_streaming` already
+//! processes `log_files` in parallel per-file (via rayon) with a commutative
+//! `KvAnalyzerState::merge` reduce and a single shared [`crate::utils::progress::ProgressBar`]
+//! total, so there's no separate single-threaded ingestion path left to
+//! unify here.
+//!
+//! **Metrics**: `--metrics-file`/`--metrics-listen` export this run's results
+//! as Prometheus metrics via [`crate::utils::metrics`] — see that module for
+//! the exposition-format details. Entity churn, k8s-auth, token analysis
+//! and client traffic analysis export their metrics through the same module.
+//!
 //! # Output
 //!
-//! Generates a CSV report with:
+//! Generates a report (CSV by default; see [`OutputSink`] for `ndjson`/`json`) with:
 //! - Mount point
 //! - Normalized secret path (without /data/ or /metadata/)
-//! - Number of unique entities accessing the secret
+//! - Number of unique entities accessing the secret (estimated, dropping the
+//!   `entity_ids` column/field, when `--approx-clients` is set — see [`ClientTracker`])
 //! - Total operations count
 //! - List of unique paths accessed
 //!
@@ -53,47 +90,302 @@ use crate::audit::types::AuditEntry;
 use crate::utils::format::format_number;
 use crate::utils::processor::{ProcessingMode, ProcessorBuilder};
 use anyhow::{Context, Result};
-use std::collections::{HashMap, HashSet};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Number of registers bits (`p`); `m = 2^p` registers of one byte each.
+const HLL_PRECISION: u32 = 14;
+/// Number of registers (`m`), one byte each — a fixed 16 KiB per sketch
+/// regardless of how many distinct entity IDs are actually inserted.
+const HLL_NUM_REGISTERS: usize = 1 << HLL_PRECISION;
+
+/// HyperLogLog cardinality sketch used by [`ClientTracker::Approx`] to bound
+/// the memory a `kv-analysis` report spends on unique-client tracking.
+///
+/// Each inserted entity ID is hashed to 64 bits; the top [`HLL_PRECISION`]
+/// bits select a register, and the register stores the longest run of
+/// leading zeros (+1) seen among the remaining bits, capped by keeping only
+/// the max per register. [`HyperLogLog::merge`] is an element-wise max over
+/// the two register arrays, so it composes cleanly with the processor's
+/// parallel `merge` fold.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    fn new() -> Self {
+        Self {
+            registers: vec![0u8; HLL_NUM_REGISTERS],
+        }
+    }
+
+    fn insert(&mut self, value: &str) {
+        use std::hash::{Hash, Hasher};
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        value.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let index = (hash >> (64 - HLL_PRECISION)) as usize;
+
+        // Leading-zero run (+1) among the (64 - p) bits that weren't used as
+        // the register index. `rest` masks those bits off, so its top `p`
+        // bits are forced to zero and must be subtracted back out.
+        let window_mask = (1u64 << (64 - HLL_PRECISION)) - 1;
+        let rest = hash & window_mask;
+        let rank = (rest.leading_zeros() - HLL_PRECISION + 1) as u8;
+
+        if rank > self.registers[index] {
+            self.registers[index] = rank;
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        for (reg, other_reg) in self.registers.iter_mut().zip(other.registers.iter()) {
+            if *other_reg > *reg {
+                *reg = *other_reg;
+            }
+        }
+    }
+
+    /// Estimated cardinality, per the standard HyperLogLog estimator with
+    /// the small-range linear-counting correction.
+    fn estimate(&self) -> usize {
+        let m = HLL_NUM_REGISTERS as f64;
+        let alpha_m = 0.7213 / (1.0 + 1.079 / m);
+
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|&r| 2f64.powi(-(r as i32)))
+            .sum();
+        let raw_estimate = alpha_m * m * m / sum;
+
+        let estimate = if raw_estimate <= 2.5 * m {
+            let zero_registers = self.registers.iter().filter(|&&r| r == 0).count();
+            if zero_registers > 0 {
+                m * (m / zero_registers as f64).ln()
+            } else {
+                raw_estimate
+            }
+        } else {
+            raw_estimate
+        };
+
+        estimate.round().max(0.0) as usize
+    }
+}
+
+/// How a [`KvUsageData`] tracks the distinct clients hitting a path: either
+/// an exact `HashSet` of entity IDs, or a [`HyperLogLog`] sketch when
+/// `--approx-clients` trades exact `entity_ids` for bounded memory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum ClientTracker {
+    Exact(HashSet<String>),
+    Approx(HyperLogLog),
+}
+
+impl ClientTracker {
+    fn new(approx: bool) -> Self {
+        if approx {
+            Self::Approx(HyperLogLog::new())
+        } else {
+            Self::Exact(HashSet::new())
+        }
+    }
+
+    fn insert(&mut self, entity_id: &str) {
+        match self {
+            Self::Exact(set) => {
+                set.insert(entity_id.to_string());
+            }
+            Self::Approx(hll) => hll.insert(entity_id),
+        }
+    }
+
+    fn merge(&mut self, other: Self) {
+        match (self, other) {
+            (Self::Exact(set), Self::Exact(other_set)) => set.extend(other_set),
+            (Self::Approx(hll), Self::Approx(other_hll)) => hll.merge(&other_hll),
+            _ => unreachable!("ClientTracker variants must agree within a single run"),
+        }
+    }
+
+    /// Unique client count: exact length, or the HyperLogLog estimate.
+    fn unique_count(&self) -> usize {
+        match self {
+            Self::Exact(set) => set.len(),
+            Self::Approx(hll) => hll.estimate(),
+        }
+    }
+
+    /// Sorted entity IDs, or `None` in approximate mode where individual IDs
+    /// were never retained.
+    fn exact_ids(&self) -> Option<Vec<String>> {
+        match self {
+            Self::Exact(set) => {
+                let mut ids: Vec<_> = set.iter().cloned().collect();
+                ids.sort();
+                Some(ids)
+            }
+            Self::Approx(_) => None,
+        }
+    }
+}
 
 /// Tracks KV usage statistics for a specific path
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct KvUsageData {
-    entity_ids: HashSet<String>,
+    clients: ClientTracker,
     operations_count: usize,
     paths_accessed: HashSet<String>,
 }
 
 impl KvUsageData {
-    fn new() -> Self {
+    fn new(approx_clients: bool) -> Self {
         Self {
-            entity_ids: HashSet::new(),
+            clients: ClientTracker::new(approx_clients),
             operations_count: 0,
             paths_accessed: HashSet::new(),
         }
     }
 
     fn merge(&mut self, other: Self) {
-        self.entity_ids.extend(other.entity_ids);
+        self.clients.merge(other.clients);
         self.operations_count += other.operations_count;
         self.paths_accessed.extend(other.paths_accessed);
     }
 }
 
+/// Optional per-entity / per-path bookkeeping accumulated only when
+/// `--anomaly-report` is requested, kept separate from [`KvUsageData`] so
+/// the default `kv-analysis analyze` run (and its `--approx-clients` /
+/// `--max-memory-entries` memory-bounding modes) pays nothing for it.
+/// Unlike `kv_usage`, this is never spilled to disk: it's sized by distinct
+/// entities and the paths/days each one touched, which is typically far
+/// smaller than the full path-level aggregation.
+#[derive(Debug, Clone, Default)]
+struct AnomalyAccumulator {
+    /// entity_id -> distinct normalized KV paths it touched, used to flag
+    /// entities with unusually broad access compared to the population.
+    entity_paths: HashMap<String, HashSet<String>>,
+    /// normalized KV path -> day -> distinct entity IDs seen that day, used
+    /// to compare unique-client counts between the earlier and later halves
+    /// of the days a path was accessed on.
+    path_day_clients: HashMap<String, HashMap<NaiveDate, HashSet<String>>>,
+}
+
+impl AnomalyAccumulator {
+    fn record(&mut self, app_path: &str, entity_id: &str, day: NaiveDate) {
+        self.entity_paths
+            .entry(entity_id.to_string())
+            .or_default()
+            .insert(app_path.to_string());
+
+        self.path_day_clients
+            .entry(app_path.to_string())
+            .or_default()
+            .entry(day)
+            .or_default()
+            .insert(entity_id.to_string());
+    }
+
+    fn merge(&mut self, other: Self) {
+        for (entity_id, paths) in other.entity_paths {
+            self.entity_paths.entry(entity_id).or_default().extend(paths);
+        }
+        for (path, other_days) in other.path_day_clients {
+            let days = self.path_day_clients.entry(path).or_default();
+            for (day, clients) in other_days {
+                days.entry(day).or_default().extend(clients);
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 struct KvAnalyzerState {
     kv_usage: HashMap<String, KvUsageData>,
     kv_prefix: String,
+    approx_clients: bool,
+    /// Skip entries outside this time window. `None` in either bound means
+    /// unbounded on that side.
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
     parsed_lines: usize,
+    /// When set, `kv_usage` is spilled to a sorted run file (see
+    /// [`spill_run_file`]) once it holds more than this many paths, bounding
+    /// per-worker memory on datasets larger than RAM.
+    max_memory_entries: Option<usize>,
+    temp_dir: PathBuf,
+    /// Paths of sorted run files spilled so far; folded back together by a
+    /// k-way merge in [`run`] once processing completes.
+    run_files: Vec<PathBuf>,
+    /// First error encountered while spilling, if any. The processing
+    /// closure can't return `Result`, so it's surfaced here instead.
+    spill_error: Option<String>,
+    /// Populated only when `--anomaly-report` is requested.
+    anomaly: Option<AnomalyAccumulator>,
 }
 
 impl KvAnalyzerState {
-    fn new(kv_prefix: String) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        kv_prefix: String,
+        approx_clients: bool,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
+        max_memory_entries: Option<usize>,
+        temp_dir: PathBuf,
+        anomaly_report: bool,
+    ) -> Self {
         Self {
             kv_usage: HashMap::with_capacity(10000),
             kv_prefix,
+            approx_clients,
+            since,
+            until,
             parsed_lines: 0,
+            max_memory_entries,
+            temp_dir,
+            run_files: Vec::new(),
+            spill_error: None,
+            anomaly: anomaly_report.then(AnomalyAccumulator::default),
+        }
+    }
+
+    /// Spills all current entries, sorted by path, into a new run file, then
+    /// clears the in-memory map. Called once `kv_usage` crosses
+    /// `max_memory_entries`, and once more at the end of processing to flush
+    /// whatever's left so the final k-way merge only ever reads from disk.
+    fn spill_run_file(&mut self) -> Result<()> {
+        if self.kv_usage.is_empty() {
+            return Ok(());
         }
+
+        let mut entries: Vec<(String, KvUsageData)> =
+            std::mem::take(&mut self.kv_usage).into_iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let run_path = self.temp_dir.join(format!(
+            "vault-audit-kv-analysis-run-{}-{}.bin",
+            std::process::id(),
+            self.run_files.len()
+        ));
+        let file = File::create(&run_path).context("Failed to create spill run file")?;
+        let mut writer = BufWriter::new(file);
+        for entry in &entries {
+            write_length_prefixed(&mut writer, entry)?;
+        }
+        writer.flush().context("Failed to flush spill run file")?;
+
+        self.run_files.push(run_path);
+        Ok(())
     }
 
     fn merge(mut self, other: Self) -> Self {
@@ -104,12 +396,25 @@ impl KvAnalyzerState {
                 .and_modify(|data| data.merge(other_data.clone()))
                 .or_insert(other_data);
         }
+        self.run_files.extend(other.run_files);
+        if self.spill_error.is_none() {
+            self.spill_error = other.spill_error;
+        }
+        match (&mut self.anomaly, other.anomaly) {
+            (Some(acc), Some(other_acc)) => acc.merge(other_acc),
+            (anomaly @ None, Some(other_acc)) => *anomaly = Some(other_acc),
+            _ => {}
+        }
         self
     }
 }
 
-/// Normalizes KV paths by removing KV v2 /data/ and /metadata/ components
-fn normalize_kv_path(path: &str) -> String {
+/// Normalizes KV paths by removing KV v2 /data/ and /metadata/ components.
+///
+/// `pub(crate)` so [`crate::commands::entity_clusters`] can group entities by
+/// the same normalized path identity this module reports usage against,
+/// rather than re-deriving KV v2 path collapsing independently.
+pub(crate) fn normalize_kv_path(path: &str) -> String {
     let parts: Vec<&str> = path.trim_matches('/').split('/').collect();
 
     // Handle KV v2 paths (kv/data/... or kv/metadata/...)
@@ -170,21 +475,619 @@ fn load_entity_alias_mapping(alias_export_csv: &str) -> Result<HashMap<String, V
     Ok(entity_aliases)
 }
 
+/// Appends a bincode-encoded, length-prefixed `value` to `writer`, the same
+/// on-disk framing used by a spilled run file: an 8-byte little-endian
+/// length followed by that many bytes of bincode.
+fn write_length_prefixed<T: Serialize>(writer: &mut impl Write, value: &T) -> Result<()> {
+    let bytes = bincode::serialize(value).context("Failed to encode run file entry")?;
+    writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+/// Reads a single length-prefixed bincode entry from a run file, or `None`
+/// once the reader is exhausted.
+fn read_one_length_prefixed<T: serde::de::DeserializeOwned>(
+    reader: &mut impl BufRead,
+) -> Result<Option<T>> {
+    let mut len_buf = [0u8; 8];
+    match reader.read_exact(&mut len_buf) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e).context("Failed to read run file entry length"),
+    }
+    let len = u64::from_le_bytes(len_buf) as usize;
+    let mut buf = vec![0u8; len];
+    reader
+        .read_exact(&mut buf)
+        .context("Failed to read run file entry bytes")?;
+    Ok(Some(
+        bincode::deserialize(&buf).context("Failed to decode run file entry")?,
+    ))
+}
+
+/// One run file's read cursor during the final k-way merge: the current
+/// decoded `(path, data)` record (if any remain), lazily advanced so no run
+/// is ever fully loaded into memory.
+struct RunCursor {
+    reader: BufReader<File>,
+    current: Option<(String, KvUsageData)>,
+}
+
+impl RunCursor {
+    fn open(path: &Path) -> Result<Self> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open spill run file: {}", path.display()))?;
+        let mut reader = BufReader::new(file);
+        let current = read_one_length_prefixed(&mut reader)?;
+        Ok(Self { reader, current })
+    }
+
+    fn advance(&mut self) -> Result<()> {
+        self.current = read_one_length_prefixed(&mut self.reader)?;
+        Ok(())
+    }
+}
+
+/// Folds every spilled run file back together into one path-sorted stream,
+/// writing each merged `KvUsageData` straight to `sink` as soon as its path
+/// is fully resolved, so the merge itself never holds more than one record
+/// per run in memory. Duplicate paths across runs (the same path spilled
+/// more than once) are folded with [`KvUsageData::merge`] before emitting.
+fn merge_run_files(
+    run_files: &[PathBuf],
+    sink: &mut dyn OutputSink,
+    entity_aliases: &HashMap<String, Vec<String>>,
+    mut path_metrics: Option<&mut Vec<(String, usize, usize)>>,
+) -> Result<usize> {
+    let mut cursors: Vec<RunCursor> = run_files
+        .iter()
+        .map(|path| RunCursor::open(path))
+        .collect::<Result<_>>()?;
+
+    let mut heap: BinaryHeap<Reverse<(String, usize)>> = BinaryHeap::new();
+    for (index, cursor) in cursors.iter().enumerate() {
+        if let Some((path, _)) = &cursor.current {
+            heap.push(Reverse((path.clone(), index)));
+        }
+    }
+
+    let mut paths_written = 0usize;
+    while let Some(Reverse((min_path, _))) = heap.peek().cloned() {
+        let mut merged: Option<KvUsageData> = None;
+
+        while let Some(Reverse((path, index))) = heap.peek().cloned() {
+            if path != min_path {
+                break;
+            }
+            heap.pop();
+
+            let cursor = &mut cursors[index];
+            let (_, data) = cursor
+                .current
+                .take()
+                .expect("heap entry must have a current record");
+            merged = Some(match merged {
+                Some(mut acc) => {
+                    acc.merge(data);
+                    acc
+                }
+                None => data,
+            });
+
+            cursor.advance()?;
+            if let Some((next_path, _)) = &cursor.current {
+                heap.push(Reverse((next_path.clone(), index)));
+            }
+        }
+
+        let data = merged.expect("at least one record was folded for this path");
+        write_kv_path_record(
+            sink,
+            &min_path,
+            &data,
+            entity_aliases,
+            path_metrics.as_mut().map(|v| &mut **v),
+        )?;
+        paths_written += 1;
+    }
+
+    Ok(paths_written)
+}
+
+/// Builds the `unique_clients` / `alias_names` / sample-paths fields for one
+/// KV path and writes them to `sink`. Shared by the direct in-memory pass
+/// and the external-merge pass so both stay byte-for-byte consistent. When
+/// `path_metrics` is given (`--anomaly-report` or a metrics output was
+/// requested), also records `(kv_path, unique_clients, operations_count)`
+/// for the anomaly and metrics passes that run after every path has been
+/// written.
+fn write_kv_path_record(
+    sink: &mut dyn OutputSink,
+    kv_path: &str,
+    data: &KvUsageData,
+    entity_aliases: &HashMap<String, Vec<String>>,
+    path_metrics: Option<&mut Vec<(String, usize, usize)>>,
+) -> Result<()> {
+    let entity_ids = data.clients.exact_ids();
+    let unique_clients = data.clients.unique_count();
+
+    if let Some(metrics) = path_metrics {
+        metrics.push((kv_path.to_string(), unique_clients, data.operations_count));
+    }
+
+    // Collect alias names (only possible when exact entity IDs were retained)
+    let mut alias_names = Vec::new();
+    if let Some(ids) = &entity_ids {
+        for eid in ids {
+            if let Some(aliases) = entity_aliases.get(eid) {
+                alias_names.extend(aliases.iter().cloned());
+            }
+        }
+    }
+
+    // Sample paths (limit to 5)
+    let mut sample_paths: Vec<_> = data.paths_accessed.iter().cloned().collect();
+    sample_paths.sort();
+    sample_paths.truncate(5);
+
+    sink.write_path_record(
+        kv_path,
+        unique_clients,
+        data.operations_count,
+        entity_ids.as_deref(),
+        &alias_names,
+        &sample_paths,
+    )
+}
+
+/// A pluggable destination for the per-KV-path rows of a `kv-analysis
+/// analyze` report. `--format` selects the implementation: [`CsvSink`]
+/// (default, backward-compatible with the original comma-joined columns),
+/// [`NdjsonSink`] (one JSON object per path, written and flushed as each
+/// record is emitted so memory stays flat for reports with tens of
+/// thousands of paths), or [`JsonSink`] (a single pretty-printed document).
+trait OutputSink {
+    fn start_report(&mut self) -> Result<()>;
+
+    #[allow(clippy::too_many_arguments)]
+    fn write_path_record(
+        &mut self,
+        kv_path: &str,
+        unique_clients: usize,
+        operations_count: usize,
+        entity_ids: Option<&[String]>,
+        alias_names: &[String],
+        sample_paths_accessed: &[String],
+    ) -> Result<()>;
+
+    fn finish(&mut self) -> Result<()>;
+}
+
+/// JSON representation of a single KV path row, shared by [`NdjsonSink`] and
+/// [`JsonSink`] so downstream tooling gets real arrays for `entity_ids` /
+/// `alias_names` / `sample_paths_accessed` instead of comma-joined strings.
+/// `entity_ids` is omitted entirely (rather than emitted as `null`) when
+/// `--approx-clients` means individual IDs were never retained.
+#[derive(Debug, Serialize)]
+struct KvPathRecord<'a> {
+    kv_path: &'a str,
+    unique_clients: usize,
+    operations_count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entity_ids: Option<&'a [String]>,
+    alias_names: &'a [String],
+    sample_paths_accessed: &'a [String],
+}
+
+struct CsvSink {
+    writer: csv::Writer<File>,
+    include_entity_ids: bool,
+}
+
+impl CsvSink {
+    fn new(output_file: &str, include_entity_ids: bool) -> Result<Self> {
+        let file = File::create(output_file).context("Failed to create output file")?;
+        Ok(Self {
+            writer: csv::Writer::from_writer(file),
+            include_entity_ids,
+        })
+    }
+}
+
+impl OutputSink for CsvSink {
+    fn start_report(&mut self) -> Result<()> {
+        let mut header = vec!["kv_path", "unique_clients", "operations_count"];
+        if self.include_entity_ids {
+            header.push("entity_ids");
+        }
+        header.push("alias_names");
+        header.push("sample_paths_accessed");
+        self.writer.write_record(header)?;
+        Ok(())
+    }
+
+    fn write_path_record(
+        &mut self,
+        kv_path: &str,
+        unique_clients: usize,
+        operations_count: usize,
+        entity_ids: Option<&[String]>,
+        alias_names: &[String],
+        sample_paths_accessed: &[String],
+    ) -> Result<()> {
+        let mut row = vec![
+            kv_path.to_string(),
+            unique_clients.to_string(),
+            operations_count.to_string(),
+        ];
+        if self.include_entity_ids {
+            row.push(entity_ids.unwrap_or(&[]).join(", "));
+        }
+        row.push(alias_names.join(", "));
+        row.push(sample_paths_accessed.join(", "));
+        self.writer.write_record(&row)?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.writer.flush().context("Failed to flush CSV writer")
+    }
+}
+
+struct NdjsonSink {
+    writer: BufWriter<File>,
+}
+
+impl NdjsonSink {
+    fn new(output_file: &str) -> Result<Self> {
+        let file = File::create(output_file).context("Failed to create output file")?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+        })
+    }
+}
+
+impl OutputSink for NdjsonSink {
+    fn start_report(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn write_path_record(
+        &mut self,
+        kv_path: &str,
+        unique_clients: usize,
+        operations_count: usize,
+        entity_ids: Option<&[String]>,
+        alias_names: &[String],
+        sample_paths_accessed: &[String],
+    ) -> Result<()> {
+        let record = KvPathRecord {
+            kv_path,
+            unique_clients,
+            operations_count,
+            entity_ids,
+            alias_names,
+            sample_paths_accessed,
+        };
+        serde_json::to_writer(&mut self.writer, &record)
+            .context("Failed to write NDJSON record")?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        self.writer.flush().context("Failed to flush NDJSON writer")
+    }
+}
+
+/// Buffers records in memory (unlike [`NdjsonSink`]) so the whole report can
+/// be written as a single pretty-printed JSON array.
+struct JsonSink {
+    output_file: String,
+    records: Vec<KvPathRecordOwned>,
+}
+
+#[derive(Debug, Serialize)]
+struct KvPathRecordOwned {
+    kv_path: String,
+    unique_clients: usize,
+    operations_count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    entity_ids: Option<Vec<String>>,
+    alias_names: Vec<String>,
+    sample_paths_accessed: Vec<String>,
+}
+
+impl JsonSink {
+    fn new(output_file: &str) -> Self {
+        Self {
+            output_file: output_file.to_string(),
+            records: Vec::new(),
+        }
+    }
+}
+
+impl OutputSink for JsonSink {
+    fn start_report(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn write_path_record(
+        &mut self,
+        kv_path: &str,
+        unique_clients: usize,
+        operations_count: usize,
+        entity_ids: Option<&[String]>,
+        alias_names: &[String],
+        sample_paths_accessed: &[String],
+    ) -> Result<()> {
+        self.records.push(KvPathRecordOwned {
+            kv_path: kv_path.to_string(),
+            unique_clients,
+            operations_count,
+            entity_ids: entity_ids.map(<[String]>::to_vec),
+            alias_names: alias_names.to_vec(),
+            sample_paths_accessed: sample_paths_accessed.to_vec(),
+        });
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        let file = File::create(&self.output_file).context("Failed to create output file")?;
+        serde_json::to_writer_pretty(file, &self.records)
+            .context("Failed to write JSON output")?;
+        Ok(())
+    }
+}
+
+fn build_output_sink(
+    output_file: &str,
+    format: &str,
+    include_entity_ids: bool,
+) -> Result<Box<dyn OutputSink>> {
+    match format {
+        "ndjson" => Ok(Box::new(NdjsonSink::new(output_file)?)),
+        "json" => Ok(Box::new(JsonSink::new(output_file))),
+        _ => Ok(Box::new(CsvSink::new(output_file, include_entity_ids)?)),
+    }
+}
+
+/// Population mean and standard deviation of `values` — "population" because
+/// the whole set is exactly what each value is being compared against, not a
+/// sample drawn from some larger population.
+fn population_mean_stddev(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    if n == 0.0 {
+        return (0.0, 0.0);
+    }
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    (mean, variance.sqrt())
+}
+
+/// One row of the `--anomaly-report` CSV: a single outlier metric scored
+/// against the population mean/stddev it was drawn from.
+struct AnomalyRecord {
+    category: &'static str,
+    subject: String,
+    metric_value: f64,
+    population_mean: f64,
+    population_stddev: f64,
+    z_score: f64,
+}
+
+/// Scores every `(subject, value)` pair against the population mean/stddev
+/// of `values`, sorts by descending z-score, and keeps the top `top_n`. A
+/// zero-stddev population (every value identical) scores everything `0.0`
+/// rather than dividing by zero.
+fn rank_by_zscore(
+    category: &'static str,
+    items: Vec<(String, f64)>,
+    top_n: usize,
+) -> Vec<AnomalyRecord> {
+    let values: Vec<f64> = items.iter().map(|(_, v)| *v).collect();
+    let (mean, stddev) = population_mean_stddev(&values);
+
+    let mut records: Vec<AnomalyRecord> = items
+        .into_iter()
+        .map(|(subject, value)| {
+            let z_score = if stddev > 0.0 {
+                (value - mean) / stddev
+            } else {
+                0.0
+            };
+            AnomalyRecord {
+                category,
+                subject,
+                metric_value: value,
+                population_mean: mean,
+                population_stddev: stddev,
+                z_score,
+            }
+        })
+        .collect();
+
+    records.sort_by(|a, b| {
+        b.z_score
+            .partial_cmp(&a.z_score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    records.truncate(top_n);
+    records
+}
+
+/// Splits a path's days of activity into earlier/later halves (by distinct
+/// calendar day, not operation count) and returns how much the distinct-
+/// client count grew from the earlier half to the later half. `None` if the
+/// path was only ever seen on a single day, since there's no "earlier" to
+/// compare against.
+fn path_client_spike(days: &HashMap<NaiveDate, HashSet<String>>) -> Option<f64> {
+    if days.len() < 2 {
+        return None;
+    }
+
+    let mut sorted_days: Vec<&NaiveDate> = days.keys().collect();
+    sorted_days.sort();
+    let midpoint = sorted_days.len() / 2;
+
+    let mut earlier: HashSet<&String> = HashSet::new();
+    for day in &sorted_days[..midpoint] {
+        earlier.extend(&days[*day]);
+    }
+    let mut later: HashSet<&String> = HashSet::new();
+    for day in &sorted_days[midpoint..] {
+        later.extend(&days[*day]);
+    }
+
+    Some(later.len() as f64 - earlier.len() as f64)
+}
+
+/// Writes the `--anomaly-report` CSV: the top `top_n` outliers (by z-score)
+/// in each of three categories —
+/// - `single_client_high_volume`: paths read by exactly one entity, ranked
+///   by operation count (a possible runaway client)
+/// - `entity_broad_access`: entities ranked by how many distinct KV paths
+///   they've touched (a possible over-broad policy or credential compromise)
+/// - `path_client_spike`: paths ranked by how much their distinct-client
+///   count grew between the earlier and later days they were accessed on
+fn write_anomaly_report(
+    output_file: &str,
+    path_metrics: &[(String, usize, usize)],
+    anomaly: &AnomalyAccumulator,
+    top_n: usize,
+) -> Result<()> {
+    let single_client_volume: Vec<(String, f64)> = path_metrics
+        .iter()
+        .filter(|(_, unique_clients, _)| *unique_clients == 1)
+        .map(|(path, _, operations_count)| (path.clone(), *operations_count as f64))
+        .collect();
+
+    let entity_breadth: Vec<(String, f64)> = anomaly
+        .entity_paths
+        .iter()
+        .map(|(entity_id, paths)| (entity_id.clone(), paths.len() as f64))
+        .collect();
+
+    let path_spikes: Vec<(String, f64)> = anomaly
+        .path_day_clients
+        .iter()
+        .filter_map(|(path, days)| path_client_spike(days).map(|delta| (path.clone(), delta)))
+        .collect();
+
+    let mut records = rank_by_zscore("single_client_high_volume", single_client_volume, top_n);
+    records.extend(rank_by_zscore("entity_broad_access", entity_breadth, top_n));
+    records.extend(rank_by_zscore("path_client_spike", path_spikes, top_n));
+
+    let file = File::create(output_file).context("Failed to create anomaly report file")?;
+    let mut writer = csv::Writer::from_writer(file);
+    writer.write_record([
+        "category",
+        "subject",
+        "metric_value",
+        "population_mean",
+        "population_stddev",
+        "z_score",
+    ])?;
+    for record in &records {
+        writer.write_record(&[
+            record.category.to_string(),
+            record.subject.clone(),
+            record.metric_value.to_string(),
+            format!("{:.4}", record.population_mean),
+            format!("{:.4}", record.population_stddev),
+            format!("{:.4}", record.z_score),
+        ])?;
+    }
+    writer
+        .flush()
+        .context("Failed to flush anomaly report writer")
+}
+
+/// Renders this run's aggregate results as Prometheus metrics: top-level
+/// gauges for the line counts and path total, plus one `operations_total`
+/// counter and one `unique_clients` gauge per KV path. `path_metrics` is the
+/// same `(kv_path, unique_clients, operations_count)` tuple list collected
+/// for `--anomaly-report`, reused here rather than walking the report again.
+fn build_metrics_exporter(
+    total_lines: usize,
+    parsed_lines: usize,
+    paths_total: usize,
+    path_metrics: &[(String, usize, usize)],
+) -> crate::utils::metrics::MetricsExporter {
+    let mut exporter = crate::utils::metrics::MetricsExporter::new();
+    exporter.gauge(
+        "vault_kv_analysis_lines_total",
+        "Total audit log lines processed",
+        &[],
+        total_lines as f64,
+    );
+    exporter.gauge(
+        "vault_kv_analysis_lines_parsed_total",
+        "Audit log lines parsed as KV read/list operations",
+        &[],
+        parsed_lines as f64,
+    );
+    exporter.gauge(
+        "vault_kv_analysis_paths_total",
+        "Distinct KV paths found in the report",
+        &[],
+        paths_total as f64,
+    );
+    for (kv_path, unique_clients, operations_count) in path_metrics {
+        exporter.counter(
+            "vault_kv_operations_total",
+            "Total KV read/list operations, per normalized path",
+            &[("kv_path", kv_path)],
+            *operations_count as f64,
+        );
+        exporter.gauge(
+            "vault_kv_unique_clients",
+            "Unique entities that accessed a KV path",
+            &[("kv_path", kv_path)],
+            *unique_clients as f64,
+        );
+    }
+    exporter
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     log_files: &[String],
     kv_prefix: &str,
     output: Option<&str>,
     entity_csv: Option<&str>,
+    format: Option<&str>,
+    approx_clients: bool,
+    max_memory_entries: Option<usize>,
+    temp_dir: Option<&str>,
+    anomaly_report: Option<&str>,
+    anomaly_top_n: usize,
+    metrics_file: Option<&str>,
+    metrics_listen: Option<&str>,
+    since: Option<&str>,
+    until: Option<&str>,
 ) -> Result<()> {
     let output_file = output.unwrap_or("kv_usage_by_client.csv");
     let kv_prefix_owned = kv_prefix.to_string();
+    let temp_dir_path = temp_dir.map(PathBuf::from).unwrap_or_else(std::env::temp_dir);
+
+    let now = Utc::now();
+    let since_bound = since
+        .map(|s| crate::utils::time::resolve_time_bound(s, now))
+        .transpose()
+        .context("Invalid --since")?;
+    let until_bound = until
+        .map(|s| crate::utils::time::resolve_time_bound(s, now))
+        .transpose()
+        .context("Invalid --until")?;
 
     let processor = ProcessorBuilder::new()
         .mode(ProcessingMode::Auto)
         .progress_label("Processing".to_string())
         .build();
 
-    let (result, stats) = processor.process_files_streaming(
+    let (mut result, stats) = processor.process_files_streaming(
         log_files,
         |entry: &AuditEntry, state: &mut KvAnalyzerState| {
             // Filter for KV operations
@@ -197,6 +1100,19 @@ pub fn run(
                 None => return,
             };
 
+            // Time-window filter (--since/--until); entries with an
+            // unparseable timestamp are kept rather than silently dropped.
+            if state.since.is_some() || state.until.is_some() {
+                if let Ok(entry_time) = crate::utils::time::parse_timestamp(&entry.time) {
+                    if state.since.is_some_and(|since| entry_time < since) {
+                        return;
+                    }
+                    if state.until.is_some_and(|until| entry_time > until) {
+                        return;
+                    }
+                }
+            }
+
             // Check prefix
             if !state.kv_prefix.is_empty() && !path.starts_with(&state.kv_prefix) {
                 return;
@@ -223,22 +1139,52 @@ pub fn run(
             // Normalize path
             let app_path = normalize_kv_path(path);
 
+            if let Some(acc) = state.anomaly.as_mut() {
+                if let Ok(ts) = crate::utils::time::parse_timestamp(&entry.time) {
+                    acc.record(&app_path, entity_id, ts.date_naive());
+                }
+            }
+
+            let approx_clients = state.approx_clients;
+
             let usage = state
                 .kv_usage
                 .entry(app_path)
-                .or_insert_with(KvUsageData::new);
+                .or_insert_with(|| KvUsageData::new(approx_clients));
 
-            usage.entity_ids.insert(entity_id.to_string());
+            usage.clients.insert(entity_id);
             usage.operations_count += 1;
             usage.paths_accessed.insert(path.to_string());
+
+            // Spill to disk once the in-memory map grows past the configured
+            // threshold, so a single worker's accumulated paths never grow
+            // unbounded across a large batch of `*.log.gz` files.
+            if let Some(threshold) = state.max_memory_entries {
+                if state.kv_usage.len() > threshold {
+                    if let Err(e) = state.spill_run_file() {
+                        state.spill_error.get_or_insert_with(|| e.to_string());
+                    }
+                }
+            }
         },
         KvAnalyzerState::merge,
-        KvAnalyzerState::new(kv_prefix_owned),
+        KvAnalyzerState::new(
+            kv_prefix_owned,
+            approx_clients,
+            since_bound,
+            until_bound,
+            max_memory_entries,
+            temp_dir_path,
+            anomaly_report.is_some(),
+        ),
     )?;
 
+    if let Some(err) = result.spill_error.take() {
+        anyhow::bail!("Failed to spill kv_usage to a run file: {}", err);
+    }
+
     let total_lines = stats.total_lines;
     let parsed_lines = result.parsed_lines;
-    let kv_usage = result.kv_usage;
 
     eprintln!(
         "\nTotal: Processed {} lines, parsed {} KV operations",
@@ -246,7 +1192,22 @@ pub fn run(
         format_number(parsed_lines)
     );
 
-    if kv_usage.is_empty() {
+    // External-merge mode: flush whatever's still in memory as one last run
+    // file, so every record (spilled or not) is folded together by the same
+    // path-sorted k-way merge below.
+    let using_external_merge = max_memory_entries.is_some();
+    if using_external_merge {
+        result
+            .spill_run_file()
+            .context("Failed to spill final kv_usage batch to a run file")?;
+    }
+
+    let no_data = if using_external_merge {
+        result.run_files.is_empty()
+    } else {
+        result.kv_usage.is_empty()
+    };
+    if no_data {
         eprintln!("[ERROR] No KV operations found in audit logs.");
         std::process::exit(1);
     }
@@ -259,62 +1220,74 @@ pub fn run(
     };
 
     // Ensure output directory exists
-    if let Some(parent) = std::path::Path::new(output_file).parent() {
+    if let Some(parent) = Path::new(output_file).parent() {
         std::fs::create_dir_all(parent)?;
     }
 
-    // Write CSV
-    let file = File::create(output_file).context("Failed to create output file")?;
-    let mut writer = csv::Writer::from_writer(file);
-
-    writer.write_record([
-        "kv_path",
-        "unique_clients",
-        "operations_count",
-        "entity_ids",
-        "alias_names",
-        "sample_paths_accessed",
-    ])?;
+    // Write the report via the selected output sink
+    let mut sink = build_output_sink(output_file, format.unwrap_or("csv"), !approx_clients)?;
+    sink.start_report()?;
 
-    let mut paths: Vec<_> = kv_usage.keys().collect();
-    paths.sort();
+    let wants_path_metrics =
+        anomaly_report.is_some() || metrics_file.is_some() || metrics_listen.is_some();
+    let mut path_metrics: Option<Vec<(String, usize, usize)>> = wants_path_metrics.then(Vec::new);
 
-    for kv_path in paths {
-        let data = &kv_usage[kv_path];
+    let paths_written = if using_external_merge {
+        let written = merge_run_files(
+            &result.run_files,
+            &mut *sink,
+            &entity_aliases,
+            path_metrics.as_mut(),
+        )?;
+        for run_file in &result.run_files {
+            let _ = std::fs::remove_file(run_file);
+        }
+        written
+    } else {
+        let mut paths: Vec<_> = result.kv_usage.keys().collect();
+        paths.sort();
+        for kv_path in &paths {
+            write_kv_path_record(
+                &mut *sink,
+                kv_path,
+                &result.kv_usage[*kv_path],
+                &entity_aliases,
+                path_metrics.as_mut(),
+            )?;
+        }
+        paths.len()
+    };
 
-        let mut entity_ids: Vec<_> = data.entity_ids.iter().cloned().collect();
-        entity_ids.sort();
+    sink.finish()?;
 
-        let unique_clients = entity_ids.len();
-        let operations = data.operations_count;
+    println!("Done. Output written to: {}", output_file);
+    println!("Summary: {} KV paths analyzed", paths_written);
 
-        // Collect alias names
-        let mut alias_names = Vec::new();
-        for eid in &entity_ids {
-            if let Some(aliases) = entity_aliases.get(eid) {
-                alias_names.extend(aliases.iter().cloned());
-            }
+    if metrics_file.is_some() || metrics_listen.is_some() {
+        let exporter = build_metrics_exporter(
+            total_lines,
+            parsed_lines,
+            paths_written,
+            path_metrics.as_deref().unwrap_or_default(),
+        );
+        if let Some(metrics_path) = metrics_file {
+            exporter.write_textfile(metrics_path)?;
+            println!("Metrics written to: {}", metrics_path);
+        }
+        if let Some(addr) = metrics_listen {
+            exporter.serve_blocking(addr)?;
         }
-
-        // Sample paths (limit to 5)
-        let mut sample_paths: Vec<_> = data.paths_accessed.iter().cloned().collect();
-        sample_paths.sort();
-        sample_paths.truncate(5);
-
-        writer.write_record([
-            kv_path,
-            &unique_clients.to_string(),
-            &operations.to_string(),
-            &entity_ids.join(", "),
-            &alias_names.join(", "),
-            &sample_paths.join(", "),
-        ])?;
     }
 
-    writer.flush()?;
-
-    println!("Done. Output written to: {}", output_file);
-    println!("Summary: {} KV paths analyzed", kv_usage.len());
+    if let Some(anomaly_path) = anomaly_report {
+        write_anomaly_report(
+            anomaly_path,
+            &path_metrics.unwrap_or_default(),
+            &result.anomaly.unwrap_or_default(),
+            anomaly_top_n,
+        )?;
+        println!("Anomaly report written to: {}", anomaly_path);
+    }
 
     Ok(())
 }
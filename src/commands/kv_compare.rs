@@ -14,8 +14,9 @@
 //!
 //! ---
 //!
-//! Compares KV secrets engine usage between two CSV exports to identify
-//! changes in access patterns over time.
+//! Compares KV secrets engine usage between two CSV exports (from
+//! [`kv_analyzer`](crate::commands::kv_analyzer)) to identify changes in
+//! access patterns over time.
 //!
 //! # Usage
 //!
@@ -30,20 +31,20 @@
 //!
 //! # Output
 //!
-//! Displays comparison metrics by mount point:
-//! - Change in total operations
-//! - Change in unique secrets accessed
-//! - Change in entity count
-//! - Percentage changes
-//!
-//! Helps identify:
-//! - Growing or shrinking KV usage
-//! - New secrets being accessed
-//! - Secrets no longer used
-//! - Changes in access patterns
+//! Both CSVs are keyed by `kv_path` and diffed directly (not just
+//! summarized side by side):
+//! - **Per-mount summary**: added/removed/changed path counts and net
+//!   operations delta for each mount
+//! - **New secrets being accessed**: paths present only in the new period
+//! - **Secrets no longer used**: paths present only in the old period
+//! - **Changed paths**: shared paths whose operation count moved, sorted by
+//!   magnitude of the delta
+//! - **Entity churn**: entities seen in the new period but not the old, and
+//!   vice versa - the most actionable signal for onboarded/decommissioned
+//!   clients
 
 use anyhow::{Context, Result};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 
 fn format_number(n: usize) -> String {
@@ -58,129 +59,230 @@ fn format_number(n: usize) -> String {
     result.chars().rev().collect()
 }
 
-/// Mount point usage statistics
-struct MountData {
+/// Per-`kv_path` usage pulled from one `kv-analyzer` CSV export.
+struct PathStats {
     operations: usize,
-    paths: usize,
     entities: HashSet<String>,
 }
 
-fn analyze_mount(csvfile: &str) -> Result<Option<MountData>> {
-    let file = match File::open(csvfile) {
-        Ok(f) => f,
-        Err(_) => return Ok(None),
-    };
+/// Net change for a single mount across the two periods.
+#[derive(Default)]
+struct MountDelta {
+    added_paths: usize,
+    removed_paths: usize,
+    changed_paths: usize,
+    old_operations: usize,
+    new_operations: usize,
+}
+
+/// First path segment of a `kv_path`, used to group paths by mount.
+fn mount_of(kv_path: &str) -> &str {
+    kv_path.split('/').next().unwrap_or(kv_path)
+}
 
+fn load_paths(csvfile: &str) -> Result<HashMap<String, PathStats>> {
+    let file = File::open(csvfile).with_context(|| format!("Failed to open {}", csvfile))?;
     let mut reader = csv::Reader::from_reader(file);
-    let mut operations = 0;
-    let mut paths = 0;
-    let mut entities: HashSet<String> = HashSet::new();
 
+    let mut paths = HashMap::new();
     for result in reader.records() {
         let record = result?;
 
-        // Get operations_count (column 2)
-        if let Some(ops_str) = record.get(2) {
-            if let Ok(ops) = ops_str.parse::<usize>() {
-                operations += ops;
-            }
-        }
+        let Some(kv_path) = record.get(0) else {
+            continue;
+        };
 
-        paths += 1;
+        let operations = record
+            .get(2)
+            .and_then(|s| s.parse::<usize>().ok())
+            .unwrap_or(0);
 
-        // Get entity_ids (column 3)
-        if let Some(entity_ids_str) = record.get(3) {
-            for eid in entity_ids_str.split(',') {
-                let trimmed = eid.trim();
-                if !trimmed.is_empty() {
-                    entities.insert(trimmed.to_string());
-                }
-            }
-        }
-    }
+        let entities: HashSet<String> = record
+            .get(3)
+            .map(|s| {
+                s.split(',')
+                    .map(str::trim)
+                    .filter(|e| !e.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
 
-    if paths == 0 {
-        return Ok(None);
+        let normalized_path = kv_path.trim_end_matches('/').to_string();
+        paths.insert(
+            normalized_path,
+            PathStats {
+                operations,
+                entities,
+            },
+        );
     }
 
-    Ok(Some(MountData {
-        operations,
-        paths,
-        entities,
-    }))
+    Ok(paths)
 }
 
 pub fn run(csv1: &str, csv2: &str) -> Result<()> {
-    let csv_files = vec![csv1.to_string(), csv2.to_string()];
+    let old = load_paths(csv1).with_context(|| format!("Failed to analyze {}", csv1))?;
+    let new = load_paths(csv2).with_context(|| format!("Failed to analyze {}", csv2))?;
 
-    println!("{}", "=".repeat(95));
-    println!(
-        "{:<20} {:<18} {:<18} {:<20}",
-        "KV Mount", "Operations", "Unique Paths", "Unique Entities"
-    );
-    println!("{}", "=".repeat(95));
-
-    let mut results = Vec::new();
-    let mut total_ops = 0;
-    let mut total_paths = 0;
-    let mut all_entities: HashSet<String> = HashSet::new();
-
-    for csv_file in &csv_files {
-        // Extract mount name from filename
-        let mount_name = std::path::Path::new(csv_file)
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or(csv_file);
-
-        match analyze_mount(csv_file).context(format!("Failed to analyze {}", csv_file))? {
-            Some(data) => {
-                println!(
-                    "{:<20} {:<18} {:<18} {:<20}",
-                    mount_name,
-                    format_number(data.operations),
-                    format_number(data.paths),
-                    format_number(data.entities.len())
-                );
-
-                total_ops += data.operations;
-                total_paths += data.paths;
-                all_entities.extend(data.entities.iter().cloned());
-
-                results.push((mount_name.to_string(), data));
-            }
-            None => {
-                println!("{:<20} {:<18}", mount_name, "(file not found)");
+    let old_keys: HashSet<&String> = old.keys().collect();
+    let new_keys: HashSet<&String> = new.keys().collect();
+
+    let mut added: Vec<&String> = new_keys.difference(&old_keys).copied().collect();
+    let mut removed: Vec<&String> = old_keys.difference(&new_keys).copied().collect();
+    let mut changed: Vec<(&String, i64)> = old_keys
+        .intersection(&new_keys)
+        .filter_map(|&path| {
+            let delta = new[path].operations as i64 - old[path].operations as i64;
+            if delta == 0 {
+                None
+            } else {
+                Some((path, delta))
             }
+        })
+        .collect();
+
+    added.sort_by_key(|path| std::cmp::Reverse(new[*path].operations));
+    removed.sort_by_key(|path| std::cmp::Reverse(old[*path].operations));
+    changed.sort_by_key(|(_, delta)| std::cmp::Reverse(delta.abs()));
+
+    // Per-mount summary over the union of both periods' paths.
+    let mut mounts: HashMap<&str, MountDelta> = HashMap::new();
+    for path in old_keys.union(&new_keys) {
+        let mount = mounts.entry(mount_of(path.as_str())).or_default();
+        let old_ops = old.get(*path).map_or(0, |p| p.operations);
+        let new_ops = new.get(*path).map_or(0, |p| p.operations);
+        mount.old_operations += old_ops;
+        mount.new_operations += new_ops;
+        match (old.contains_key(*path), new.contains_key(*path)) {
+            (false, true) => mount.added_paths += 1,
+            (true, false) => mount.removed_paths += 1,
+            (true, true) if old_ops != new_ops => mount.changed_paths += 1,
+            _ => {}
         }
     }
 
-    println!("{}", "=".repeat(95));
+    println!("{}", "=".repeat(100));
+    println!("KV Usage Comparison: {} -> {}", csv1, csv2);
+    println!("{}", "=".repeat(100));
+
     println!(
-        "{:<20} {:<18} {:<18} {:<20}",
-        "TOTAL",
-        format_number(total_ops),
-        format_number(total_paths),
-        format_number(all_entities.len())
+        "\n{:<25} {:>10} {:>10} {:>10} {:>14} {:>10}",
+        "Mount", "Added", "Removed", "Changed", "Op Delta", "Op Delta %"
     );
-    println!("{}", "=".repeat(95));
+    println!("{}", "-".repeat(85));
 
-    // Show percentage breakdown
-    if !results.is_empty() {
-        println!("\nPercentage Breakdown by Operations:");
-        println!("{}", "-".repeat(50));
+    let mut mount_names: Vec<&&str> = mounts.keys().collect();
+    mount_names.sort();
+    for mount in mount_names {
+        let delta = &mounts[mount];
+        let op_delta = delta.new_operations as i64 - delta.old_operations as i64;
+        let pct = if delta.old_operations > 0 {
+            (op_delta as f64 / delta.old_operations as f64) * 100.0
+        } else {
+            0.0
+        };
+        println!(
+            "{:<25} {:>10} {:>10} {:>10} {:>+14} {:>9.1}%",
+            mount, delta.added_paths, delta.removed_paths, delta.changed_paths, op_delta, pct
+        );
+    }
 
-        // Sort by operations descending
-        results.sort_by(|a, b| b.1.operations.cmp(&a.1.operations));
+    println!(
+        "\nNew secrets being accessed ({} path(s)):",
+        format_number(added.len())
+    );
+    if added.is_empty() {
+        println!("  (none)");
+    } else {
+        for path in added.iter().take(20) {
+            println!("  + {} ({} ops)", path, format_number(new[*path].operations));
+        }
+        if added.len() > 20 {
+            println!("  ... and {} more", format_number(added.len() - 20));
+        }
+    }
 
-        for (mount, data) in results {
-            let pct = if total_ops > 0 {
-                (data.operations as f64 / total_ops as f64) * 100.0
+    println!(
+        "\nSecrets no longer used ({} path(s)):",
+        format_number(removed.len())
+    );
+    if removed.is_empty() {
+        println!("  (none)");
+    } else {
+        for path in removed.iter().take(20) {
+            println!("  - {} ({} ops)", path, format_number(old[*path].operations));
+        }
+        if removed.len() > 20 {
+            println!("  ... and {} more", format_number(removed.len() - 20));
+        }
+    }
+
+    println!(
+        "\nChanged paths, sorted by magnitude of operation delta ({} path(s)):",
+        format_number(changed.len())
+    );
+    if changed.is_empty() {
+        println!("  (none)");
+    } else {
+        for (path, delta) in changed.iter().take(20) {
+            let old_ops = old[*path].operations;
+            let new_ops = new[*path].operations;
+            let pct = if old_ops > 0 {
+                (*delta as f64 / old_ops as f64) * 100.0
             } else {
-                0.0
+                f64::INFINITY
             };
-            println!("{:<20} {:>6.2}%", mount, pct);
+            println!(
+                "  {} {} -> {} ({:>+} ops, {:>+.1}%)",
+                path, old_ops, new_ops, delta, pct
+            );
+        }
+        if changed.len() > 20 {
+            println!("  ... and {} more", format_number(changed.len() - 20));
         }
     }
 
+    // Entity churn: who showed up or disappeared between the two periods.
+    let old_entities: HashSet<&String> = old.values().flat_map(|p| p.entities.iter()).collect();
+    let new_entities: HashSet<&String> = new.values().flat_map(|p| p.entities.iter()).collect();
+
+    let mut new_only: Vec<&&String> = new_entities.difference(&old_entities).collect();
+    let mut departed: Vec<&&String> = old_entities.difference(&new_entities).collect();
+    new_only.sort();
+    departed.sort();
+
+    println!(
+        "\nNew entities (active in new period, not in old) ({}):",
+        format_number(new_only.len())
+    );
+    if new_only.is_empty() {
+        println!("  (none)");
+    } else {
+        for entity in new_only.iter().take(20) {
+            println!("  + {}", entity);
+        }
+        if new_only.len() > 20 {
+            println!("  ... and {} more", format_number(new_only.len() - 20));
+        }
+    }
+
+    println!(
+        "\nDeparted entities (active in old period, not in new) ({}):",
+        format_number(departed.len())
+    );
+    if departed.is_empty() {
+        println!("  (none)");
+    } else {
+        for entity in departed.iter().take(20) {
+            println!("  - {}", entity);
+        }
+        if departed.len() > 20 {
+            println!("  ... and {} more", format_number(departed.len() - 20));
+        }
+    }
+
+    println!("\n{}", "=".repeat(100));
+
     Ok(())
 }
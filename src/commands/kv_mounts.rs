@@ -8,7 +8,8 @@
 //! - **Automatic Discovery**: Discovers all KV mounts without needing to know mount names
 //! - **Version Detection**: Automatically detects and handles both KV v1 and KV v2 mounts
 //! - **Depth Control**: Optional depth parameter to control traversal (unlimited by default)
-//! - **Multiple Output Formats**: CSV (flattened with depth), JSON (nested tree), or stdout (visual tree)
+//! - **Multiple Output Formats**: CSV (flattened with depth), JSON (nested tree), stdout (visual tree),
+//!   or NDJSON (one line per entry, streamed as the traversal discovers it)
 //!
 //! # Usage Examples
 //!
@@ -24,13 +25,69 @@
 //!
 //! # Save full tree to CSV file
 //! vault-audit kv-mounts --format csv --output kv-inventory.csv
+//!
+//! # Stream a multi-million-secret mount to a file one line at a time
+//! vault-audit kv-mounts --format ndjson --output kv-inventory.ndjson
+//!
+//! # Also emit a node_exporter textfile-collector-compatible summary
+//! vault-audit kv-mounts --format json --metrics-file /var/lib/node_exporter/textfile/vault_kv.prom
+//!
+//! # Audit only one team's secrets without walking the whole mount
+//! vault-audit kv-mounts --format json --include "secret/appcodes/payments/*" --exclude "*/archived/*"
+//!
+//! # Compare two saved snapshots for secret drift, without re-querying Vault
+//! vault-audit kv-mounts --diff old.json new.json --format stdout
 //! ```
 //!
+//! # Snapshot Diffing
+//!
+//! `--diff <old.json> <new.json>` skips Vault entirely and instead loads two
+//! previously saved `--format json` trees, indexes each by full path, and
+//! reports the set difference plus a timestamp comparison on the
+//! intersection: secrets only in `new` are `added`, secrets only in `old`
+//! are `removed`, and secrets whose `updated_time` changed are `modified`.
+//! Available in all three formats — an annotated (`+`/`-`/`~`) stdout list,
+//! a flat CSV with a `change` column, or JSON.
+//!
+//! # Multi-Source Merge
+//!
+//! `run_merge` combines several previously saved `--format json` snapshots,
+//! one per Vault cluster/namespace, into a single tagged report (JSON:
+//! `{"sources": [...], "mounts": [...]}` with each mount carrying its
+//! `source_id`; CSV: `source_address`/`namespace` columns prepended). Like
+//! `--diff`, this loads snapshots already captured by separate invocations
+//! rather than querying multiple clusters live in one process. Mounts whose
+//! `path` appears under more than one source are kept side by side and
+//! flagged `drift: true` when their type/version/accessor disagree, so
+//! cross-environment differences stay visible instead of being collapsed.
+//!
+//! # Path Scoping
+//!
+//! Repeatable `--include`/`--exclude` patterns (glob by default, `--regex`
+//! to match against full regular expressions instead) are evaluated against
+//! each entry's accumulated `full_path` *during* traversal, not after: a
+//! folder is only descended into if an include pattern could still match
+//! something beneath it, and a secret is only kept (and its metadata only
+//! fetched) once it matches an include pattern and no exclude pattern. This
+//! prunes LIST/metadata calls at the source on large deployments instead of
+//! paying for the whole walk and filtering the result.
+//!
 //! # Output Formats
 //!
 //! - **CSV**: Flattened paths with depth column, one row per path/secret
 //! - **JSON**: Nested tree structure with parent-child relationships
 //! - **stdout**: Visual tree with Unicode box-drawing characters (├──, └──, │)
+//! - **NDJSON**: One `{"mount":...,"full_path":...,"type":...,"depth":...,"created_time":...,"updated_time":...}`
+//!   object per line, written the instant each entry is resolved rather than
+//!   after the whole tree is collected — lets `jq` or a log pipeline consume
+//!   a large mount incrementally instead of waiting for the full traversal
+//!
+//! `--metrics-file PATH` is independent of `--format`: after enumeration it
+//! additionally writes `vault_kv_mounts_total`, `vault_kv_secrets_total`,
+//! `vault_kv_folders_total`, a cumulative `vault_kv_secret_age_seconds`
+//! histogram, and `vault_kv_secrets_never_updated_total` for a node_exporter
+//! textfile collector to scrape, turning the one-shot audit into continuous
+//! drift monitoring.
 //!
 //! # Depth Parameter
 //!
@@ -39,6 +96,15 @@
 //! - `--depth 2`: Show mounts + two levels of traversal
 //! - No flag: Unlimited depth (discovers entire tree structure)
 //!
+//! # Concurrency
+//!
+//! Within each mount, folder descents and secret-metadata fetches are issued
+//! concurrently rather than one at a time, bounded by `--concurrency`
+//! (default 8) Vault requests in flight at once across the *whole*
+//! traversal (every depth shares one semaphore). Output order is still
+//! deterministic: each folder's `children` are sorted by `path` once all of
+//! that folder's concurrent work completes.
+//!
 //! # API Endpoints Used
 //!
 //! - `/v1/sys/mounts` - Discover all secret mounts
@@ -46,14 +112,31 @@
 //! - `/v1/{mount}/{path}` - List KV v1 paths (using LIST method)
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
 use std::fs::File;
 use std::io::Write;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Semaphore;
 
+use crate::utils::time::parse_timestamp;
 use crate::vault_api::VaultClient;
 
+/// Cumulative (Prometheus histogram `le`) age-bucket thresholds, in seconds,
+/// for `vault_kv_secret_age_seconds_bucket`.
+const SECRET_AGE_BUCKETS_SECONDS: [(&str, i64); 5] = [
+    ("3600", 3_600),
+    ("86400", 86_400),
+    ("604800", 604_800),
+    ("2592000", 2_592_000),
+    ("+Inf", i64::MAX),
+];
+
 #[derive(Debug, Serialize, Deserialize)]
 struct MountInfo {
     #[serde(rename = "type")]
@@ -77,212 +160,495 @@ where
     Ok(opt.unwrap_or_default())
 }
 
-/// Recursively list paths within a KV v2 mount up to a specified depth
+/// Receives one resolved `PathEntry` at the instant a traversal discovers it,
+/// rather than waiting for the whole tree to be collected. `--format ndjson`
+/// is the only caller today: it writes each entry out as a line immediately,
+/// letting a multi-million-secret enumeration be piped into `jq` or a log
+/// pipeline incrementally. Every other `--format` passes `None`.
+trait EntrySink {
+    #[allow(clippy::too_many_arguments)]
+    fn emit(
+        &self,
+        mount: &str,
+        full_path: &str,
+        entry_type: &str,
+        depth: usize,
+        created_time: Option<&str>,
+        updated_time: Option<&str>,
+    ) -> Result<()>;
+}
+
+#[derive(Debug, Serialize)]
+struct NdjsonRecord<'a> {
+    mount: &'a str,
+    full_path: &'a str,
+    #[serde(rename = "type")]
+    entry_type: &'a str,
+    depth: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created_time: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    updated_time: Option<&'a str>,
+}
+
+/// Writes one JSON object per line as entries are discovered. Shared across
+/// concurrently-resolved siblings via a lock held only for the duration of a
+/// single line write.
+struct NdjsonWriter {
+    writer: Mutex<std::io::BufWriter<File>>,
+}
+
+impl NdjsonWriter {
+    fn create(output_path: &str) -> Result<Self> {
+        let file = File::create(output_path)
+            .with_context(|| format!("Failed to create NDJSON output file: {}", output_path))?;
+        Ok(Self {
+            writer: Mutex::new(std::io::BufWriter::new(file)),
+        })
+    }
+
+    fn finish(&self) -> Result<()> {
+        self.writer
+            .lock()
+            .expect("ndjson writer mutex poisoned")
+            .flush()
+            .context("Failed to flush NDJSON output file")
+    }
+}
+
+impl EntrySink for NdjsonWriter {
+    fn emit(
+        &self,
+        mount: &str,
+        full_path: &str,
+        entry_type: &str,
+        depth: usize,
+        created_time: Option<&str>,
+        updated_time: Option<&str>,
+    ) -> Result<()> {
+        let record = NdjsonRecord {
+            mount,
+            full_path,
+            entry_type,
+            depth,
+            created_time,
+            updated_time,
+        };
+        let mut writer = self.writer.lock().expect("ndjson writer mutex poisoned");
+        serde_json::to_writer(&mut *writer, &record).context("Failed to write NDJSON record")?;
+        writer
+            .write_all(b"\n")
+            .context("Failed to write NDJSON record")
+    }
+}
+
+/// Simple `*`-wildcard glob match (no other metacharacters) — the same
+/// level of pattern matching `entity_churn`'s `SignatureRule` predicates use,
+/// rather than pulling in a full regex engine for the common case.
+fn glob_match(pattern: &str, value: &str) -> bool {
+    let segments: Vec<&str> = pattern.split('*').collect();
+    if segments.len() == 1 {
+        return pattern == value;
+    }
+
+    let mut rest = value;
+    for (idx, segment) in segments.iter().enumerate() {
+        if segment.is_empty() {
+            continue;
+        }
+        if idx == 0 {
+            let Some(stripped) = rest.strip_prefix(segment) else {
+                return false;
+            };
+            rest = stripped;
+        } else if idx == segments.len() - 1 {
+            return rest.ends_with(segment);
+        } else {
+            let Some(found) = rest.find(segment) else {
+                return false;
+            };
+            rest = &rest[found + segment.len()..];
+        }
+    }
+    true
+}
+
+/// One compiled `--include`/`--exclude` pattern.
+enum PathPattern {
+    Glob(String),
+    Regex(Regex),
+}
+
+impl PathPattern {
+    fn compile(pattern: &str, use_regex: bool) -> Result<Self> {
+        if use_regex {
+            Regex::new(pattern)
+                .map(Self::Regex)
+                .with_context(|| format!("Invalid --regex pattern: {}", pattern))
+        } else {
+            Ok(Self::Glob(pattern.to_string()))
+        }
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        match self {
+            Self::Glob(pattern) => glob_match(pattern, path),
+            Self::Regex(re) => re.is_match(path),
+        }
+    }
+
+    /// Whether a folder at `prefix` could still have a descendant matching
+    /// this pattern. A glob is pruned once `prefix` diverges from the
+    /// pattern's literal text before its first `*` — in either direction,
+    /// since the shorter of the two is the one being compared as a prefix.
+    /// A regex's match set can't be soundly prefix-tested in general, so
+    /// descent is always permitted for regex patterns; exclusion/inclusion
+    /// is still re-checked at every leaf.
+    fn could_match_descendant(&self, prefix: &str) -> bool {
+        match self {
+            Self::Glob(pattern) => {
+                let literal_prefix = pattern.split('*').next().unwrap_or("");
+                if literal_prefix.len() <= prefix.len() {
+                    prefix.starts_with(literal_prefix)
+                } else {
+                    literal_prefix.starts_with(prefix)
+                }
+            }
+            Self::Regex(_) => true,
+        }
+    }
+}
+
+/// `--include`/`--exclude` path scoping, pruning the traversal at the source
+/// instead of walking the whole mount and filtering afterward.
+struct PathFilter {
+    includes: Vec<PathPattern>,
+    excludes: Vec<PathPattern>,
+}
+
+impl PathFilter {
+    fn new(includes: &[String], excludes: &[String], use_regex: bool) -> Result<Self> {
+        Ok(Self {
+            includes: includes
+                .iter()
+                .map(|p| PathPattern::compile(p, use_regex))
+                .collect::<Result<Vec<_>>>()?,
+            excludes: excludes
+                .iter()
+                .map(|p| PathPattern::compile(p, use_regex))
+                .collect::<Result<Vec<_>>>()?,
+        })
+    }
+
+    /// A secret is kept if it matches at least one include pattern (or none
+    /// were given) and no exclude pattern.
+    fn allows_leaf(&self, path: &str) -> bool {
+        let included = self.includes.is_empty() || self.includes.iter().any(|p| p.matches(path));
+        included && !self.excludes.iter().any(|p| p.matches(path))
+    }
+
+    /// A folder is only descended into if some include pattern could still
+    /// match a descendant (or none were given), and the folder itself isn't
+    /// an exact exclude match.
+    fn allows_descent(&self, folder_path: &str) -> bool {
+        let could_include = self.includes.is_empty()
+            || self
+                .includes
+                .iter()
+                .any(|p| p.could_match_descendant(folder_path));
+        let excluded = self.excludes.iter().any(|p| p.matches(folder_path));
+        could_include && !excluded
+    }
+}
+
+/// Recursively list paths within a KV v2 mount up to a specified depth.
+/// Creates the `visited` set and the `--concurrency`-sized semaphore shared
+/// by every recursive call made during this mount's traversal.
 #[allow(clippy::future_not_send)]
+#[allow(clippy::too_many_arguments)]
 async fn list_kv_v2_paths(
     client: &VaultClient,
     mount_path: &str,
     current_depth: usize,
     max_depth: usize,
+    concurrency: usize,
+    sink: Option<&dyn EntrySink>,
+    filter: Option<&PathFilter>,
 ) -> Result<Vec<PathEntry>> {
     list_kv_v2_paths_with_visited(
         client,
         mount_path,
         current_depth,
         max_depth,
-        &mut std::collections::HashSet::new(),
+        Arc::new(Mutex::new(HashSet::new())),
+        Arc::new(Semaphore::new(concurrency.max(1))),
+        concurrency,
+        sink,
+        filter,
     )
     .await
 }
 
 /// Internal function with cycle detection
 #[allow(clippy::future_not_send)]
+#[allow(clippy::too_many_arguments)]
 async fn list_kv_v2_paths_with_visited(
     client: &VaultClient,
     mount_path: &str,
     current_depth: usize,
     max_depth: usize,
-    visited: &mut std::collections::HashSet<String>,
+    visited: Arc<Mutex<HashSet<String>>>,
+    semaphore: Arc<Semaphore>,
+    concurrency: usize,
+    sink: Option<&dyn EntrySink>,
+    filter: Option<&PathFilter>,
 ) -> Result<Vec<PathEntry>> {
     if current_depth > max_depth {
         return Ok(Vec::new());
     }
 
-    let mut entries = Vec::new();
     let mount_trimmed = mount_path.trim_end_matches('/');
 
     // List the root of the mount using LIST method on metadata endpoint
     let list_path = format!("/v1/{}/metadata", mount_trimmed);
 
-    let response: Result<Value> = client.list_json(&list_path).await;
-
-    if let Ok(resp) = response {
-        // Extract keys from the data.keys field
-        if let Some(data) = resp.get("data") {
-            if let Some(keys) = data.get("keys") {
-                if let Some(keys_array) = keys.as_array() {
-                    for key in keys_array {
-                        if let Some(key_str) = key.as_str() {
-                            let is_folder = key_str.ends_with('/');
-                            let entry_type = if is_folder { "folder" } else { "secret" };
-
-                            // For secrets (not folders), fetch metadata to get timestamps
-                            let (created_time, updated_time) = if is_folder {
-                                (None, None)
-                            } else {
-                                let metadata_path =
-                                    format!("{}/metadata/{}", mount_trimmed, key_str);
-                                fetch_secret_metadata(client, &metadata_path).await
-                            };
-
-                            let children = if is_folder && current_depth < max_depth {
-                                // Pass just the relative path, not the full mount path
-                                let rel_path = key_str.trim_end_matches('/');
-                                let full_path = format!("{}/{}", mount_trimmed, rel_path);
-
-                                // Check for cycles
-                                if visited.contains(&full_path) {
-                                    eprintln!(
-                                        "Warning: Detected circular reference at path: {}",
-                                        full_path
-                                    );
-                                    None
-                                } else {
-                                    visited.insert(full_path.clone());
-                                    Some(
-                                        list_kv_v2_subpath_with_visited(
-                                            client,
-                                            mount_trimmed,
-                                            rel_path,
-                                            current_depth + 1,
-                                            max_depth,
-                                            visited,
-                                        )
-                                        .await?,
-                                    )
-                                }
-                            } else {
-                                None
-                            };
-
-                            entries.push(PathEntry {
-                                path: key_str.to_string(),
-                                entry_type: entry_type.to_string(),
-                                children,
-                                created_time,
-                                updated_time,
-                            });
-                        }
-                    }
-                }
-            }
-        }
-    }
+    let response: Result<Value> = {
+        let _permit = semaphore.acquire().await.expect("semaphore closed");
+        client.list_json(&list_path).await
+    };
+
     // If we can't list the root, that's okay - mount might be empty or no permissions
+    let Ok(resp) = response else {
+        return Ok(Vec::new());
+    };
+    let Some(keys_array) = resp
+        .get("data")
+        .and_then(|d| d.get("keys"))
+        .and_then(Value::as_array)
+    else {
+        return Ok(Vec::new());
+    };
 
-    Ok(entries)
+    resolve_kv_v2_entries(
+        client,
+        mount_trimmed,
+        "",
+        keys_array,
+        current_depth,
+        max_depth,
+        &visited,
+        &semaphore,
+        concurrency,
+        sink,
+        filter,
+    )
+    .await
 }
 
 /// List paths within a KV v2 subpath (folder) with cycle detection
 #[allow(clippy::future_not_send)]
+#[allow(clippy::too_many_arguments)]
 fn list_kv_v2_subpath_with_visited<'a>(
     client: &'a VaultClient,
     mount_path: &'a str,
     rel_path: &'a str,
     current_depth: usize,
     max_depth: usize,
-    visited: &'a mut std::collections::HashSet<String>,
+    visited: Arc<Mutex<HashSet<String>>>,
+    semaphore: Arc<Semaphore>,
+    concurrency: usize,
+    sink: Option<&'a dyn EntrySink>,
+    filter: Option<&'a PathFilter>,
 ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Vec<PathEntry>>> + 'a>> {
     Box::pin(async move {
         if current_depth > max_depth {
             return Ok(Vec::new());
         }
 
-        let mut entries = Vec::new();
         let mount_trimmed = mount_path.trim_end_matches('/');
 
         // For KV v2, the metadata endpoint is /v1/{mount}/metadata/{path}
         let list_path = format!("/v1/{}/metadata/{}", mount_trimmed, rel_path);
 
-        let response: Result<Value> = client.list_json(&list_path).await;
-
-        if let Ok(resp) = response {
-            if let Some(data) = resp.get("data") {
-                if let Some(keys) = data.get("keys") {
-                    if let Some(keys_array) = keys.as_array() {
-                        for key in keys_array {
-                            if let Some(key_str) = key.as_str() {
-                                let is_folder = key_str.ends_with('/');
-                                let entry_type = if is_folder { "folder" } else { "secret" };
-
-                                // For secrets (not folders), fetch metadata to get timestamps
-                                let (created_time, updated_time) = if is_folder {
-                                    (None, None)
-                                } else {
-                                    let metadata_path = format!(
-                                        "{}/metadata/{}/{}",
-                                        mount_trimmed, rel_path, key_str
-                                    );
-                                    fetch_secret_metadata(client, &metadata_path).await
-                                };
-
-                                let children = if is_folder && current_depth < max_depth {
-                                    let new_rel_path =
-                                        format!("{}/{}", rel_path, key_str.trim_end_matches('/'));
-                                    let full_path = format!("{}/{}", mount_trimmed, new_rel_path);
-
-                                    // Check for cycles
-                                    if visited.contains(&full_path) {
-                                        eprintln!(
-                                            "Warning: Detected circular reference at path: {}",
-                                            full_path
-                                        );
-                                        None
-                                    } else {
-                                        visited.insert(full_path.clone());
-                                        Some(
-                                            list_kv_v2_subpath_with_visited(
-                                                client,
-                                                mount_path,
-                                                &new_rel_path,
-                                                current_depth + 1,
-                                                max_depth,
-                                                visited,
-                                            )
-                                            .await?,
-                                        )
-                                    }
-                                } else {
-                                    None
-                                };
-
-                                entries.push(PathEntry {
-                                    path: key_str.to_string(),
-                                    entry_type: entry_type.to_string(),
-                                    children,
-                                    created_time,
-                                    updated_time,
-                                });
-                            }
-                        }
-                    }
-                }
-            }
-        }
+        let response: Result<Value> = {
+            let _permit = semaphore.acquire().await.expect("semaphore closed");
+            client.list_json(&list_path).await
+        };
+
         // Silently ignore list errors for subpaths
+        let Ok(resp) = response else {
+            return Ok(Vec::new());
+        };
+        let Some(keys_array) = resp
+            .get("data")
+            .and_then(|d| d.get("keys"))
+            .and_then(Value::as_array)
+        else {
+            return Ok(Vec::new());
+        };
 
-        Ok(entries)
+        resolve_kv_v2_entries(
+            client,
+            mount_trimmed,
+            rel_path,
+            keys_array,
+            current_depth,
+            max_depth,
+            &visited,
+            &semaphore,
+            concurrency,
+            sink,
+            filter,
+        )
+        .await
     })
 }
 
-/// Recursively list paths within a KV v1 mount up to a specified depth
+/// Turns one LIST response's `keys` into resolved `PathEntry` values,
+/// concurrently: a metadata fetch for each secret and a recursive descent
+/// for each folder are scheduled onto the same `buffer_unordered(concurrency)`
+/// stream, with the actual in-flight request cap enforced by `semaphore`
+/// (shared across every depth of the traversal, not just this level) rather
+/// than by the stream's buffer width alone. A folder's `full_path` is
+/// inserted into `visited` before it's descended into, so two concurrently
+/// resolved siblings can't both schedule the same cyclic folder. Each entry
+/// is handed to `sink` (if any) the moment it resolves, before the final sort
+/// — `sink` therefore sees entries in completion order, not path order.
+/// Results are sorted by `path` afterward so the returned tree stays
+/// deterministic regardless of completion order. `filter` (if any) drops
+/// excluded secrets before their metadata is ever fetched and prunes descent
+/// into folders no include pattern could still match beneath.
+#[allow(clippy::too_many_arguments)]
+async fn resolve_kv_v2_entries(
+    client: &VaultClient,
+    mount_trimmed: &str,
+    rel_path: &str,
+    keys_array: &[Value],
+    current_depth: usize,
+    max_depth: usize,
+    visited: &Arc<Mutex<HashSet<String>>>,
+    semaphore: &Arc<Semaphore>,
+    concurrency: usize,
+    sink: Option<&dyn EntrySink>,
+    filter: Option<&PathFilter>,
+) -> Result<Vec<PathEntry>> {
+    let mut entries: Vec<PathEntry> = stream::iter(keys_array.iter().filter_map(Value::as_str))
+        .map(|key_str| {
+            let visited = Arc::clone(visited);
+            let semaphore = Arc::clone(semaphore);
+            async move {
+                let is_folder = key_str.ends_with('/');
+                let entry_type = if is_folder { "folder" } else { "secret" };
+
+                let entry_rel_path = if rel_path.is_empty() {
+                    key_str.trim_end_matches('/').to_string()
+                } else {
+                    format!("{}/{}", rel_path, key_str.trim_end_matches('/'))
+                };
+                let entry_full_path = format!("{}/{}", mount_trimmed, entry_rel_path);
+
+                // A secret excluded by the path filter costs nothing further:
+                // skip its metadata fetch entirely rather than fetching then
+                // discarding.
+                if !is_folder && !filter.map_or(true, |f| f.allows_leaf(&entry_full_path)) {
+                    return Ok(None);
+                }
+
+                // For secrets (not folders), fetch metadata to get timestamps
+                let (created_time, updated_time) = if is_folder {
+                    (None, None)
+                } else {
+                    let metadata_path = format!("{}/metadata/{}", mount_trimmed, entry_rel_path);
+                    let _permit = semaphore.acquire().await.expect("semaphore closed");
+                    fetch_secret_metadata(client, &metadata_path).await
+                };
+
+                if let Some(sink) = sink {
+                    sink.emit(
+                        mount_trimmed,
+                        &entry_full_path,
+                        entry_type,
+                        current_depth,
+                        created_time.as_deref(),
+                        updated_time.as_deref(),
+                    )?;
+                }
+
+                let can_descend = is_folder
+                    && current_depth < max_depth
+                    && filter.map_or(true, |f| f.allows_descent(&entry_full_path));
+
+                let children = if can_descend {
+                    // Check for cycles: insert before descending so a sibling
+                    // scheduled concurrently sees this folder as claimed.
+                    let already_visited = {
+                        let mut visited = visited.lock().expect("visited mutex poisoned");
+                        !visited.insert(entry_full_path.clone())
+                    };
+
+                    if already_visited {
+                        eprintln!(
+                            "Warning: Detected circular reference at path: {}",
+                            entry_full_path
+                        );
+                        None
+                    } else {
+                        Some(
+                            list_kv_v2_subpath_with_visited(
+                                client,
+                                mount_trimmed,
+                                &entry_rel_path,
+                                current_depth + 1,
+                                max_depth,
+                                Arc::clone(&visited),
+                                Arc::clone(&semaphore),
+                                concurrency,
+                                sink,
+                                filter,
+                            )
+                            .await?,
+                        )
+                    }
+                } else {
+                    None
+                };
+
+                Ok(Some(PathEntry {
+                    path: key_str.to_string(),
+                    entry_type: entry_type.to_string(),
+                    children,
+                    created_time,
+                    updated_time,
+                }))
+            }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .collect::<Vec<Result<Option<PathEntry>>>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<Option<PathEntry>>>>()?
+        .into_iter()
+        .flatten()
+        .collect();
+
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(entries)
+}
+
+/// Recursively list paths within a KV v1 mount up to a specified depth.
+/// Creates the `visited` set and the `--concurrency`-sized semaphore shared
+/// by every recursive call made during this mount's traversal.
 #[allow(clippy::future_not_send)]
+#[allow(clippy::too_many_arguments)]
 fn list_kv_v1_paths<'a>(
     client: &'a VaultClient,
     mount_path: &'a str,
     subpath: &'a str,
     current_depth: usize,
     max_depth: usize,
+    concurrency: usize,
+    sink: Option<&'a dyn EntrySink>,
+    filter: Option<&'a PathFilter>,
 ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Vec<PathEntry>>> + 'a>> {
     Box::pin(async move {
         list_kv_v1_paths_with_visited(
@@ -291,7 +657,11 @@ fn list_kv_v1_paths<'a>(
             subpath,
             current_depth,
             max_depth,
-            &mut std::collections::HashSet::new(),
+            Arc::new(Mutex::new(HashSet::new())),
+            Arc::new(Semaphore::new(concurrency.max(1))),
+            concurrency,
+            sink,
+            filter,
         )
         .await
     })
@@ -299,20 +669,24 @@ fn list_kv_v1_paths<'a>(
 
 /// Internal KV v1 function with cycle detection
 #[allow(clippy::future_not_send)]
+#[allow(clippy::too_many_arguments)]
 fn list_kv_v1_paths_with_visited<'a>(
     client: &'a VaultClient,
     mount_path: &'a str,
     subpath: &'a str,
     current_depth: usize,
     max_depth: usize,
-    visited: &'a mut std::collections::HashSet<String>,
+    visited: Arc<Mutex<HashSet<String>>>,
+    semaphore: Arc<Semaphore>,
+    concurrency: usize,
+    sink: Option<&'a dyn EntrySink>,
+    filter: Option<&'a PathFilter>,
 ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Vec<PathEntry>>> + 'a>> {
     Box::pin(async move {
         if current_depth > max_depth {
             return Ok(Vec::new());
         }
 
-        let mut entries = Vec::new();
         let mount_trimmed = mount_path.trim_end_matches('/');
 
         // For KV v1, use LIST on the mount path directly
@@ -322,75 +696,149 @@ fn list_kv_v1_paths_with_visited<'a>(
             format!("/v1/{}/{}", mount_trimmed, subpath.trim_end_matches('/'))
         };
 
-        let response: Result<Value> = client.list_json(&list_path).await;
-
-        if let Ok(resp) = response {
-            if let Some(data) = resp.get("data") {
-                if let Some(keys) = data.get("keys") {
-                    if let Some(keys_array) = keys.as_array() {
-                        for key in keys_array {
-                            if let Some(key_str) = key.as_str() {
-                                let is_folder = key_str.ends_with('/');
-                                let entry_type = if is_folder { "folder" } else { "secret" };
-
-                                let children = if is_folder && current_depth < max_depth {
-                                    let new_subpath = if subpath.is_empty() {
-                                        key_str.trim_end_matches('/').to_string()
-                                    } else {
-                                        format!(
-                                            "{}/{}",
-                                            subpath.trim_end_matches('/'),
-                                            key_str.trim_end_matches('/')
-                                        )
-                                    };
-
-                                    let full_path = format!("{}/{}", mount_trimmed, new_subpath);
-
-                                    // Check for cycles
-                                    if visited.contains(&full_path) {
-                                        eprintln!(
-                                            "Warning: Detected circular reference at path: {}",
-                                            full_path
-                                        );
-                                        None
-                                    } else {
-                                        visited.insert(full_path.clone());
-                                        Some(
-                                            list_kv_v1_paths_with_visited(
-                                                client,
-                                                mount_path,
-                                                &new_subpath,
-                                                current_depth + 1,
-                                                max_depth,
-                                                visited,
-                                            )
-                                            .await?,
-                                        )
-                                    }
-                                } else {
-                                    None
-                                };
-
-                                // KV v1 doesn't support metadata endpoint, so timestamps are None
-                                entries.push(PathEntry {
-                                    path: key_str.to_string(),
-                                    entry_type: entry_type.to_string(),
-                                    children,
-                                    created_time: None,
-                                    updated_time: None,
-                                });
-                            }
-                        }
-                    }
-                }
-            }
-        }
+        let response: Result<Value> = {
+            let _permit = semaphore.acquire().await.expect("semaphore closed");
+            client.list_json(&list_path).await
+        };
+
         // If we can't list, that's okay - might be empty or no permissions
+        let Ok(resp) = response else {
+            return Ok(Vec::new());
+        };
+        let Some(keys_array) = resp
+            .get("data")
+            .and_then(|d| d.get("keys"))
+            .and_then(Value::as_array)
+        else {
+            return Ok(Vec::new());
+        };
 
-        Ok(entries)
+        resolve_kv_v1_entries(
+            client,
+            mount_path,
+            mount_trimmed,
+            subpath,
+            keys_array,
+            current_depth,
+            max_depth,
+            &visited,
+            &semaphore,
+            concurrency,
+            sink,
+            filter,
+        )
+        .await
     })
 }
 
+/// KV v1 analog of [`resolve_kv_v2_entries`]: no metadata fetch (KV v1 has
+/// no metadata endpoint), but the same concurrent-descent-with-shared-
+/// semaphore, `sink`-emits-on-resolve, and deterministic-sort-after-collect
+/// behavior.
+#[allow(clippy::too_many_arguments)]
+async fn resolve_kv_v1_entries(
+    client: &VaultClient,
+    mount_path: &str,
+    mount_trimmed: &str,
+    subpath: &str,
+    keys_array: &[Value],
+    current_depth: usize,
+    max_depth: usize,
+    visited: &Arc<Mutex<HashSet<String>>>,
+    semaphore: &Arc<Semaphore>,
+    concurrency: usize,
+    sink: Option<&dyn EntrySink>,
+    filter: Option<&PathFilter>,
+) -> Result<Vec<PathEntry>> {
+    let mut entries: Vec<PathEntry> = stream::iter(keys_array.iter().filter_map(Value::as_str))
+        .map(|key_str| {
+            let visited = Arc::clone(visited);
+            let semaphore = Arc::clone(semaphore);
+            async move {
+                let is_folder = key_str.ends_with('/');
+                let entry_type = if is_folder { "folder" } else { "secret" };
+
+                let new_subpath = if subpath.is_empty() {
+                    key_str.trim_end_matches('/').to_string()
+                } else {
+                    format!(
+                        "{}/{}",
+                        subpath.trim_end_matches('/'),
+                        key_str.trim_end_matches('/')
+                    )
+                };
+                let full_path = format!("{}/{}", mount_trimmed, new_subpath);
+
+                // A secret excluded by the path filter is dropped outright;
+                // KV v1 has no metadata fetch to skip, but filtering still
+                // keeps it out of the final tree and off the sink.
+                if !is_folder && !filter.map_or(true, |f| f.allows_leaf(&full_path)) {
+                    return Ok(None);
+                }
+
+                if let Some(sink) = sink {
+                    sink.emit(mount_trimmed, &full_path, entry_type, current_depth, None, None)?;
+                }
+
+                let can_descend = is_folder
+                    && current_depth < max_depth
+                    && filter.map_or(true, |f| f.allows_descent(&full_path));
+
+                let children = if can_descend {
+                    // Check for cycles: insert before descending so a sibling
+                    // scheduled concurrently sees this folder as claimed.
+                    let already_visited = {
+                        let mut visited = visited.lock().expect("visited mutex poisoned");
+                        !visited.insert(full_path.clone())
+                    };
+
+                    if already_visited {
+                        eprintln!("Warning: Detected circular reference at path: {}", full_path);
+                        None
+                    } else {
+                        Some(
+                            list_kv_v1_paths_with_visited(
+                                client,
+                                mount_path,
+                                &new_subpath,
+                                current_depth + 1,
+                                max_depth,
+                                Arc::clone(&visited),
+                                Arc::clone(&semaphore),
+                                concurrency,
+                                sink,
+                                filter,
+                            )
+                            .await?,
+                        )
+                    }
+                } else {
+                    None
+                };
+
+                // KV v1 doesn't support metadata endpoint, so timestamps are None
+                Ok(Some(PathEntry {
+                    path: key_str.to_string(),
+                    entry_type: entry_type.to_string(),
+                    children,
+                    created_time: None,
+                    updated_time: None,
+                }))
+            }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .collect::<Vec<Result<Option<PathEntry>>>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<Option<PathEntry>>>>()?
+        .into_iter()
+        .flatten()
+        .collect();
+
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(entries)
+}
+
 /// Fetch metadata for a KV v2 secret to get `created_time` and `updated_time`
 #[allow(clippy::future_not_send)]
 async fn fetch_secret_metadata(
@@ -422,6 +870,129 @@ async fn fetch_secret_metadata(
     }
 }
 
+/// Per-mount tallies for `--metrics-file`: secret/folder counts, a
+/// cumulative age histogram bucketed by `SECRET_AGE_BUCKETS_SECONDS`, and a
+/// count of secrets whose `updated_time` still equals their `created_time`.
+#[derive(Debug, Default)]
+struct MountTreeStats {
+    secrets_total: u64,
+    folders_total: u64,
+    never_updated_total: u64,
+    age_bucket_counts: [u64; SECRET_AGE_BUCKETS_SECONDS.len()],
+}
+
+/// Recursively fold a traversed tree into `stats`. Secrets with no metadata
+/// (no permission, fetch failure) are still counted toward `secrets_total`
+/// but don't contribute to the age histogram or never-updated counter.
+fn accumulate_mount_stats(entries: &[PathEntry], stats: &mut MountTreeStats, now: DateTime<Utc>) {
+    for entry in entries {
+        match entry.entry_type.as_str() {
+            "folder" => stats.folders_total += 1,
+            "secret" => {
+                stats.secrets_total += 1;
+
+                if let (Some(created), Some(updated)) = (&entry.created_time, &entry.updated_time)
+                {
+                    if created == updated {
+                        stats.never_updated_total += 1;
+                    }
+                    if let Ok(created_at) = parse_timestamp(created) {
+                        let age_seconds = now.signed_duration_since(created_at).num_seconds().max(0);
+                        for (i, (_, threshold)) in SECRET_AGE_BUCKETS_SECONDS.iter().enumerate() {
+                            if age_seconds <= *threshold {
+                                stats.age_bucket_counts[i] += 1;
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(children) = &entry.children {
+            accumulate_mount_stats(children, stats, now);
+        }
+    }
+}
+
+/// Write a node_exporter textfile-collector-compatible summary of the
+/// discovered tree to `metrics_path`, in addition to whatever `--format` was
+/// requested. Lets the one-shot enumeration feed a continuous
+/// drift-monitoring scrape, mirroring how a storage service exposes
+/// per-bucket object counts as metrics.
+fn write_prometheus_metrics(metrics_path: &str, kv_mounts: &[KvMountOutput]) -> Result<()> {
+    let now = Utc::now();
+    let mut output = String::new();
+
+    let mut mounts_by_version: HashMap<&str, u64> = HashMap::new();
+    for mount in kv_mounts {
+        *mounts_by_version.entry(mount.version.as_str()).or_insert(0) += 1;
+    }
+
+    let _ = writeln!(output, "# HELP vault_kv_mounts_total Number of KV mounts discovered, by KV version.");
+    let _ = writeln!(output, "# TYPE vault_kv_mounts_total gauge");
+    let mut versions: Vec<&&str> = mounts_by_version.keys().collect();
+    versions.sort_unstable();
+    for version in versions {
+        let _ = writeln!(
+            output,
+            "vault_kv_mounts_total{{version=\"{}\"}} {}",
+            version, mounts_by_version[version]
+        );
+    }
+
+    let _ = writeln!(output, "# HELP vault_kv_secrets_total Number of secrets discovered under a mount.");
+    let _ = writeln!(output, "# TYPE vault_kv_secrets_total gauge");
+    let _ = writeln!(output, "# HELP vault_kv_folders_total Number of folders discovered under a mount.");
+    let _ = writeln!(output, "# TYPE vault_kv_folders_total gauge");
+    let _ = writeln!(
+        output,
+        "# HELP vault_kv_secret_age_seconds Age of each secret's creation time, bucketed cumulatively (Prometheus histogram)."
+    );
+    let _ = writeln!(output, "# TYPE vault_kv_secret_age_seconds histogram");
+    let _ = writeln!(
+        output,
+        "# HELP vault_kv_secrets_never_updated_total Number of secrets whose updated_time still equals their created_time."
+    );
+    let _ = writeln!(output, "# TYPE vault_kv_secrets_never_updated_total gauge");
+
+    for mount in kv_mounts {
+        let mut stats = MountTreeStats::default();
+        if let Some(children) = &mount.children {
+            accumulate_mount_stats(children, &mut stats, now);
+        }
+
+        let _ = writeln!(
+            output,
+            "vault_kv_secrets_total{{mount=\"{}\"}} {}",
+            mount.path, stats.secrets_total
+        );
+        let _ = writeln!(
+            output,
+            "vault_kv_folders_total{{mount=\"{}\"}} {}",
+            mount.path, stats.folders_total
+        );
+        for (i, (le, _)) in SECRET_AGE_BUCKETS_SECONDS.iter().enumerate() {
+            let _ = writeln!(
+                output,
+                "vault_kv_secret_age_seconds_bucket{{mount=\"{}\",le=\"{}\"}} {}",
+                mount.path, le, stats.age_bucket_counts[i]
+            );
+        }
+        let _ = writeln!(
+            output,
+            "vault_kv_secrets_never_updated_total{{mount=\"{}\"}} {}",
+            mount.path, stats.never_updated_total
+        );
+    }
+
+    let mut file = File::create(metrics_path)
+        .with_context(|| format!("Failed to create metrics file: {}", metrics_path))?;
+    file.write_all(output.as_bytes())
+        .context("Failed to write Prometheus metrics")?;
+    Ok(())
+}
+
 /// Helper function to flatten nested path entries to CSV format
 fn flatten_paths_to_csv(output: &mut String, base_path: &str, entries: &[PathEntry], depth: usize) {
     use std::fmt::Write as _;
@@ -480,42 +1051,69 @@ fn print_tree(base_path: &str, entries: &[PathEntry], prefix: &str, is_last_at_l
     }
 }
 
-#[derive(Debug, Serialize)]
-struct KvMountOutput {
-    path: String,
-    mount_type: String,
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct KvMountOutput {
+    pub(crate) path: String,
+    pub(crate) mount_type: String,
     description: String,
     version: String,
     accessor: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
     children: Option<Vec<PathEntry>>,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 struct PathEntry {
     path: String,
     #[serde(rename = "type")]
     entry_type: String, // "folder" or "secret"
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
     children: Option<Vec<PathEntry>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
     created_time: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
     updated_time: Option<String>,
 }
 
 /// Run the KV mount enumeration command
 #[allow(clippy::future_not_send)]
+#[allow(clippy::too_many_arguments)]
 pub async fn run(
     vault_addr: Option<&str>,
     vault_token: Option<&str>,
     vault_namespace: Option<&str>,
+    role_id: Option<&str>,
+    secret_id: Option<&str>,
     insecure: bool,
     output: Option<&str>,
     format: &str,
     depth: usize,
+    resolve: &[(String, std::net::SocketAddr)],
+    dns_server: Option<std::net::SocketAddr>,
+    concurrency: usize,
+    metrics_file: Option<&str>,
+    include_patterns: &[String],
+    exclude_patterns: &[String],
+    use_regex: bool,
 ) -> Result<()> {
-    let client = VaultClient::from_options(vault_addr, vault_token, vault_namespace, insecure)?;
+    let filter = if include_patterns.is_empty() && exclude_patterns.is_empty() {
+        None
+    } else {
+        Some(PathFilter::new(include_patterns, exclude_patterns, use_regex)?)
+    };
+    let filter = filter.as_ref();
+
+    let client = VaultClient::connect(
+        vault_addr,
+        vault_token,
+        vault_namespace,
+        role_id,
+        secret_id,
+        insecure,
+        resolve,
+        dns_server,
+    )
+    .await?;
 
     eprintln!("Querying Vault API for KV mounts...");
     eprintln!("   Vault Address: {}", client.addr());
@@ -536,6 +1134,13 @@ pub async fn run(
         .as_object()
         .context("Expected object response from /v1/sys/mounts")?;
 
+    let ndjson_writer = if format == "ndjson" {
+        Some(NdjsonWriter::create(output.unwrap_or("kv_inventory.ndjson"))?)
+    } else {
+        None
+    };
+    let sink: Option<&dyn EntrySink> = ndjson_writer.as_ref().map(|w| w as &dyn EntrySink);
+
     let mut kv_mounts = Vec::new();
 
     for (path, mount_data) in mounts {
@@ -570,12 +1175,19 @@ pub async fn run(
                 })
                 .unwrap_or("1");
 
+            if let Some(sink) = sink {
+                sink.emit(path, path, "mount", 0, None, None)?;
+            }
+
             // Traverse paths if depth > 0
             let children = if depth > 0 {
                 if version == "2" {
-                    Some(list_kv_v2_paths(&client, path, 1, depth).await?)
+                    Some(list_kv_v2_paths(&client, path, 1, depth, concurrency, sink, filter).await?)
                 } else {
-                    Some(list_kv_v1_paths(&client, path, "", 1, depth).await?)
+                    Some(
+                        list_kv_v1_paths(&client, path, "", 1, depth, concurrency, sink, filter)
+                            .await?,
+                    )
                 }
             } else {
                 None
@@ -671,6 +1283,249 @@ pub async fn run(
                 println!();
             }
         }
+        "ndjson" => {
+            // Entries were already written line-by-line as the traversal
+            // discovered them; nothing left to do but flush.
+            let writer = ndjson_writer
+                .as_ref()
+                .expect("ndjson_writer is Some whenever format == \"ndjson\"");
+            writer.finish()?;
+            eprintln!(
+                "Output written to: {}",
+                output.unwrap_or("kv_inventory.ndjson")
+            );
+        }
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Invalid format: {}. Must be one of: csv, json, stdout, ndjson",
+                format
+            ));
+        }
+    }
+
+    if let Some(metrics_path) = metrics_file {
+        write_prometheus_metrics(metrics_path, &kv_mounts)?;
+        eprintln!("Prometheus metrics written to: {}", metrics_path);
+    }
+
+    Ok(())
+}
+
+/// A secret/folder's identity and timestamps as captured in one
+/// `--format json` snapshot, indexed by full path for `--diff` comparison.
+#[derive(Debug, Clone)]
+struct SnapshotEntry {
+    entry_type: String,
+    created_time: Option<String>,
+    updated_time: Option<String>,
+}
+
+/// Recursively index a loaded snapshot's mounts (and their `children` trees)
+/// into a flat `full_path -> SnapshotEntry` map.
+fn index_snapshot(mounts: &[KvMountOutput]) -> HashMap<String, SnapshotEntry> {
+    fn index_entries(base_path: &str, entries: &[PathEntry], index: &mut HashMap<String, SnapshotEntry>) {
+        for entry in entries {
+            let full_path = format!("{}/{}", base_path, entry.path);
+            index.insert(
+                full_path.clone(),
+                SnapshotEntry {
+                    entry_type: entry.entry_type.clone(),
+                    created_time: entry.created_time.clone(),
+                    updated_time: entry.updated_time.clone(),
+                },
+            );
+            if let Some(children) = &entry.children {
+                index_entries(&full_path, children, index);
+            }
+        }
+    }
+
+    let mut index = HashMap::new();
+    for mount in mounts {
+        index.insert(
+            mount.path.clone(),
+            SnapshotEntry {
+                entry_type: "mount".to_string(),
+                created_time: None,
+                updated_time: None,
+            },
+        );
+        if let Some(children) = &mount.children {
+            index_entries(&mount.path, children, &mut index);
+        }
+    }
+    index
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl ChangeKind {
+    fn symbol(self) -> &'static str {
+        match self {
+            Self::Added => "+",
+            Self::Removed => "-",
+            Self::Modified => "~",
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Removed => "removed",
+            Self::Modified => "modified",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct DiffRecord {
+    full_path: String,
+    #[serde(rename = "type")]
+    entry_type: String,
+    change: ChangeKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    old_updated_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new_updated_time: Option<String>,
+}
+
+/// Diffs two previously saved `--format json` snapshots path-by-path: a
+/// secret present only in `new` is `Added`, present only in `old` is
+/// `Removed`, and present in both with a changed `updated_time` is
+/// `Modified`. Lets operators get a periodic "what changed in our secret
+/// store" audit without re-querying Vault.
+fn diff_snapshots(old: &[KvMountOutput], new: &[KvMountOutput]) -> Vec<DiffRecord> {
+    let old_index = index_snapshot(old);
+    let new_index = index_snapshot(new);
+
+    let mut records = Vec::new();
+
+    for (full_path, new_entry) in &new_index {
+        match old_index.get(full_path) {
+            None => records.push(DiffRecord {
+                full_path: full_path.clone(),
+                entry_type: new_entry.entry_type.clone(),
+                change: ChangeKind::Added,
+                old_updated_time: None,
+                new_updated_time: new_entry.updated_time.clone(),
+            }),
+            Some(old_entry) => {
+                if old_entry.updated_time != new_entry.updated_time {
+                    records.push(DiffRecord {
+                        full_path: full_path.clone(),
+                        entry_type: new_entry.entry_type.clone(),
+                        change: ChangeKind::Modified,
+                        old_updated_time: old_entry.updated_time.clone(),
+                        new_updated_time: new_entry.updated_time.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    for (full_path, old_entry) in &old_index {
+        if !new_index.contains_key(full_path) {
+            records.push(DiffRecord {
+                full_path: full_path.clone(),
+                entry_type: old_entry.entry_type.clone(),
+                change: ChangeKind::Removed,
+                old_updated_time: old_entry.updated_time.clone(),
+                new_updated_time: None,
+            });
+        }
+    }
+
+    records.sort_by(|a, b| a.full_path.cmp(&b.full_path));
+    records
+}
+
+/// Run `kv-mounts --diff old.json new.json`: load two saved snapshots and
+/// report structural and temporal drift between them in the requested
+/// `--format` (csv, json, or stdout).
+pub fn run_diff(old_path: &str, new_path: &str, format: &str, output: Option<&str>) -> Result<()> {
+    let old_file =
+        File::open(old_path).with_context(|| format!("Failed to open old snapshot: {}", old_path))?;
+    let old: Vec<KvMountOutput> = serde_json::from_reader(old_file)
+        .with_context(|| format!("Failed to parse old snapshot: {}", old_path))?;
+
+    let new_file =
+        File::open(new_path).with_context(|| format!("Failed to open new snapshot: {}", new_path))?;
+    let new: Vec<KvMountOutput> = serde_json::from_reader(new_file)
+        .with_context(|| format!("Failed to parse new snapshot: {}", new_path))?;
+
+    let records = diff_snapshots(&old, &new);
+
+    eprintln!(
+        "Diff: {} added, {} removed, {} modified",
+        records.iter().filter(|r| matches!(r.change, ChangeKind::Added)).count(),
+        records.iter().filter(|r| matches!(r.change, ChangeKind::Removed)).count(),
+        records.iter().filter(|r| matches!(r.change, ChangeKind::Modified)).count(),
+    );
+
+    match format {
+        "json" => {
+            let json_output =
+                serde_json::to_string_pretty(&records).context("Failed to serialize diff to JSON")?;
+            if let Some(output_path) = output {
+                let mut file = File::create(output_path).context("Failed to create output file")?;
+                file.write_all(json_output.as_bytes())
+                    .context("Failed to write diff JSON to file")?;
+                eprintln!("Output written to: {}", output_path);
+            } else {
+                println!("{}", json_output);
+            }
+        }
+        "csv" => {
+            use std::fmt::Write as _;
+            let mut csv_output = String::new();
+            csv_output.push_str("full_path,type,change,old_updated_time,new_updated_time\n");
+            for record in &records {
+                let _ = writeln!(
+                    csv_output,
+                    "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\"",
+                    record.full_path.replace('"', "\"\""),
+                    record.entry_type,
+                    record.change.as_str(),
+                    record.old_updated_time.as_deref().unwrap_or("").replace('"', "\"\""),
+                    record.new_updated_time.as_deref().unwrap_or("").replace('"', "\"\"")
+                );
+            }
+
+            if let Some(output_path) = output {
+                let mut file = File::create(output_path).context("Failed to create output file")?;
+                file.write_all(csv_output.as_bytes())
+                    .context("Failed to write diff CSV to file")?;
+                eprintln!("Output written to: {}", output_path);
+            } else {
+                print!("{}", csv_output);
+            }
+        }
+        "stdout" => {
+            for record in &records {
+                let changed_at = match record.change {
+                    ChangeKind::Added => record.new_updated_time.as_deref().unwrap_or(""),
+                    ChangeKind::Removed => record.old_updated_time.as_deref().unwrap_or(""),
+                    ChangeKind::Modified => record.new_updated_time.as_deref().unwrap_or(""),
+                };
+                println!(
+                    "{} {} ({}){}",
+                    record.change.symbol(),
+                    record.full_path,
+                    record.entry_type,
+                    if changed_at.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" [updated: {}]", changed_at)
+                    }
+                );
+            }
+        }
         _ => {
             return Err(anyhow::anyhow!(
                 "Invalid format: {}. Must be one of: csv, json, stdout",
@@ -681,3 +1536,165 @@ pub async fn run(
 
     Ok(())
 }
+
+/// A single saved `--format json` snapshot contributing to a `--merge` report,
+/// tagged with the cluster it was captured from.
+#[derive(Debug, Serialize)]
+pub(crate) struct SourceTag {
+    pub(crate) source_id: String,
+    pub(crate) address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) namespace: Option<String>,
+}
+
+/// A mount from a merged multi-source report, carrying the `source_id` of
+/// the snapshot it came from and whether its `path` also appears under
+/// another source with a different type, version or accessor
+/// (cross-environment drift).
+#[derive(Debug, Serialize)]
+pub(crate) struct MergedKvMount {
+    pub(crate) source_id: String,
+    #[serde(flatten)]
+    pub(crate) mount: KvMountOutput,
+    pub(crate) drift: bool,
+}
+
+/// `{ "sources": [...], "mounts": [...] }`, the combined document produced by
+/// `--merge`.
+#[derive(Debug, Serialize)]
+pub(crate) struct MergedKvReport {
+    pub(crate) sources: Vec<SourceTag>,
+    pub(crate) mounts: Vec<MergedKvMount>,
+}
+
+/// Merge several previously-saved `--format json` snapshots (one per Vault
+/// cluster/namespace) into a single tagged report.
+///
+/// This operates on snapshots already captured by separate `kv-mounts`
+/// invocations against each target, the same way `--diff` compares two saved
+/// snapshots rather than querying two clusters live in one process - each
+/// `(source_id, address, namespace, snapshot_path)` tuple in `sources`
+/// identifies one prior capture. Mounts whose `path` appears under more than
+/// one source are kept (not deduplicated) and flagged `drift: true` when
+/// their `mount_type`/`version`/`accessor` disagree across sources, so
+/// cross-environment differences stay visible instead of being silently
+/// collapsed.
+pub fn run_merge(
+    sources: &[(String, String, Option<String>, String)],
+    format: &str,
+    output: Option<&str>,
+) -> Result<()> {
+    let mut source_tags = Vec::new();
+    let mut mounts: Vec<MergedKvMount> = Vec::new();
+
+    for (source_id, address, namespace, snapshot_path) in sources {
+        let file = File::open(snapshot_path)
+            .with_context(|| format!("Failed to open snapshot: {}", snapshot_path))?;
+        let snapshot: Vec<KvMountOutput> = serde_json::from_reader(file)
+            .with_context(|| format!("Failed to parse snapshot: {}", snapshot_path))?;
+
+        source_tags.push(SourceTag {
+            source_id: source_id.clone(),
+            address: address.clone(),
+            namespace: namespace.clone(),
+        });
+
+        for mount in snapshot {
+            mounts.push(MergedKvMount {
+                source_id: source_id.clone(),
+                mount,
+                drift: false,
+            });
+        }
+    }
+
+    // Flag drift: mounts sharing a path whose type/version/accessor disagree
+    // across sources.
+    let mut by_path: HashMap<String, Vec<usize>> = HashMap::new();
+    for (idx, merged) in mounts.iter().enumerate() {
+        by_path.entry(merged.mount.path.clone()).or_default().push(idx);
+    }
+    for indices in by_path.values() {
+        if indices.len() < 2 {
+            continue;
+        }
+        let first = &mounts[indices[0]].mount;
+        let disagrees = indices[1..].iter().any(|&idx| {
+            let other = &mounts[idx].mount;
+            other.mount_type != first.mount_type
+                || other.version != first.version
+                || other.accessor != first.accessor
+        });
+        if disagrees {
+            for &idx in indices {
+                mounts[idx].drift = true;
+            }
+        }
+    }
+
+    eprintln!(
+        "Merged {} source(s), {} mount(s), {} with cross-source drift",
+        source_tags.len(),
+        mounts.len(),
+        mounts.iter().filter(|m| m.drift).count(),
+    );
+
+    match format {
+        "json" => {
+            let report = MergedKvReport { sources: source_tags, mounts };
+            let json_output =
+                serde_json::to_string_pretty(&report).context("Failed to serialize merged report to JSON")?;
+            if let Some(output_path) = output {
+                let mut file = File::create(output_path).context("Failed to create output file")?;
+                file.write_all(json_output.as_bytes())
+                    .context("Failed to write merged JSON to file")?;
+                eprintln!("Output written to: {}", output_path);
+            } else {
+                println!("{}", json_output);
+            }
+        }
+        "csv" => {
+            use std::fmt::Write as _;
+            let mut csv_output = String::new();
+            csv_output.push_str(
+                "source_address,namespace,source_id,path,type,description,version,accessor,drift\n",
+            );
+            for merged in &mounts {
+                let source = source_tags.iter().find(|s| s.source_id == merged.source_id);
+                let (address, namespace) = source
+                    .map(|s| (s.address.as_str(), s.namespace.as_deref().unwrap_or("")))
+                    .unwrap_or(("", ""));
+                let _ = writeln!(
+                    csv_output,
+                    "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\"",
+                    address.replace('"', "\"\""),
+                    namespace.replace('"', "\"\""),
+                    merged.source_id.replace('"', "\"\""),
+                    merged.mount.path.replace('"', "\"\""),
+                    merged.mount.mount_type,
+                    merged.mount.description.replace('"', "\"\""),
+                    merged.mount.version,
+                    merged.mount.accessor,
+                    merged.drift,
+                );
+            }
+
+            if let Some(output_path) = output {
+                let mut file = File::create(output_path).context("Failed to create output file")?;
+                file.write_all(csv_output.as_bytes())
+                    .context("Failed to write merged CSV to file")?;
+                eprintln!("Output written to: {}", output_path);
+            } else {
+                print!("{}", csv_output);
+            }
+        }
+        _ => {
+            return Err(anyhow::anyhow!(
+                "Invalid format: {}. Must be one of: csv, json",
+                format
+            ));
+        }
+    }
+
+    Ok(())
+}
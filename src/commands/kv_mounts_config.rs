@@ -0,0 +1,231 @@
+//! Composable config file for multi-target `kv-mounts` audits.
+//!
+//! Organizations running many Vault clusters/namespaces end up repeating
+//! `--vault-addr`/`--vault-token`/`--vault-namespace`/`--depth`/`--format`
+//! on every invocation. This module parses a small INI-style config file of
+//! named `[target.NAME]` stanzas, plus a `%include <path>` directive that
+//! splices in another config file, so shared defaults can live in one file
+//! and per-environment overrides in another.
+//!
+//! # File Format
+//!
+//! ```ini
+//! %include "shared-defaults.conf"
+//!
+//! [target.prod-us]
+//! vault_addr = https://vault-us.internal:8200
+//! vault_namespace = prod
+//! depth = 3
+//! format = json
+//! output = prod-us.json
+//!
+//! [target.prod-eu]
+//! vault_addr = https://vault-eu.internal:8200
+//! vault_namespace = prod
+//! ```
+//!
+//! Blank lines and lines starting with `#` or `;` are ignored.
+//!
+//! # Resolution Order
+//!
+//! For a given target, lowest to highest priority:
+//!
+//! 1. Values from `%include`d files (earlier includes lose to later ones)
+//! 2. Values set directly in the including file, for the same target
+//! 3. Explicit CLI flags, applied on top via [`apply_cli_overrides`]
+//!
+//! `%include` paths are resolved relative to the including file's own
+//! directory. Circular includes are rejected.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// The subset of `kv-mounts` CLI flags useful to template per Vault
+/// cluster/namespace. Every field is optional so a target can leave a value
+/// unset here and inherit it from an `%include`d file, or from the CLI.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditTarget {
+    pub vault_addr: Option<String>,
+    pub vault_token: Option<String>,
+    pub vault_namespace: Option<String>,
+    pub depth: Option<usize>,
+    pub format: Option<String>,
+    pub output: Option<String>,
+}
+
+impl AuditTarget {
+    /// Overlay `other`'s set fields onto `self` ("last writer wins").
+    fn merge_over(&mut self, other: &Self) {
+        if other.vault_addr.is_some() {
+            self.vault_addr = other.vault_addr.clone();
+        }
+        if other.vault_token.is_some() {
+            self.vault_token = other.vault_token.clone();
+        }
+        if other.vault_namespace.is_some() {
+            self.vault_namespace = other.vault_namespace.clone();
+        }
+        if other.depth.is_some() {
+            self.depth = other.depth;
+        }
+        if other.format.is_some() {
+            self.format = other.format.clone();
+        }
+        if other.output.is_some() {
+            self.output = other.output.clone();
+        }
+    }
+}
+
+/// A fully resolved config file: every `[target.NAME]` stanza discovered
+/// across the file and everything it transitively `%include`s.
+#[derive(Debug, Clone, Default)]
+pub struct AuditConfig {
+    pub targets: HashMap<String, AuditTarget>,
+}
+
+impl AuditConfig {
+    fn merge_over(&mut self, other: &Self) {
+        for (name, target) in &other.targets {
+            self.targets.entry(name.clone()).or_default().merge_over(target);
+        }
+    }
+
+    /// Look up one target by name, for `--target <name>`.
+    pub fn target(&self, name: &str) -> Result<&AuditTarget> {
+        self.targets
+            .get(name)
+            .with_context(|| format!("No [target.{}] stanza found in config", name))
+    }
+
+    /// All target names, for `--all-targets`. Sorted so iteration order is
+    /// deterministic across runs.
+    pub fn target_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.targets.keys().map(std::string::String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+/// Load a config file, recursively resolving every `%include <path>`
+/// directive before this file's own stanzas are applied on top.
+pub fn load_config(path: &str) -> Result<AuditConfig> {
+    load_config_inner(Path::new(path), &mut Vec::new())
+}
+
+fn load_config_inner(path: &Path, ancestors: &mut Vec<PathBuf>) -> Result<AuditConfig> {
+    let canonical = path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve config file: {}", path.display()))?;
+    if ancestors.contains(&canonical) {
+        return Err(anyhow::anyhow!(
+            "Circular %include detected at: {}",
+            path.display()
+        ));
+    }
+    ancestors.push(canonical);
+
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+
+    let mut included = AuditConfig::default();
+    let mut own = AuditConfig::default();
+    let mut current_target: Option<String> = None;
+
+    for raw_line in contents.lines() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if let Some(include_arg) = line.strip_prefix("%include") {
+            let include_path = include_arg.trim().trim_matches('"');
+            let resolved = base_dir.join(include_path);
+            let nested = load_config_inner(&resolved, ancestors)?;
+            included.merge_over(&nested);
+            continue;
+        }
+
+        if let Some(section) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            let Some(name) = section.strip_prefix("target.") else {
+                return Err(anyhow::anyhow!(
+                    "Unknown config section: [{}] (expected [target.NAME])",
+                    section
+                ));
+            };
+            own.targets.entry(name.to_string()).or_default();
+            current_target = Some(name.to_string());
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(anyhow::anyhow!(
+                "Malformed config line (expected key = value): {}",
+                line
+            ));
+        };
+        let key = key.trim();
+        let value = value.trim().trim_matches('"');
+
+        let Some(target_name) = &current_target else {
+            return Err(anyhow::anyhow!(
+                "Config key '{}' set outside of any [target.NAME] section",
+                key
+            ));
+        };
+        let target = own.targets.entry(target_name.clone()).or_default();
+        match key {
+            "vault_addr" => target.vault_addr = Some(value.to_string()),
+            "vault_token" => target.vault_token = Some(value.to_string()),
+            "vault_namespace" => target.vault_namespace = Some(value.to_string()),
+            "depth" => {
+                target.depth =
+                    Some(value.parse().with_context(|| format!("Invalid depth value: {}", value))?);
+            }
+            "format" => target.format = Some(value.to_string()),
+            "output" => target.output = Some(value.to_string()),
+            other => return Err(anyhow::anyhow!("Unknown config key: {}", other)),
+        }
+    }
+
+    included.merge_over(&own);
+    ancestors.pop();
+    Ok(included)
+}
+
+/// Layer explicit CLI flags (highest priority) over a resolved
+/// [`AuditTarget`]. Each `Some` argument overrides the target's config
+/// value; `None` leaves whatever the config file set (if anything) in
+/// place.
+#[allow(clippy::too_many_arguments)]
+pub fn apply_cli_overrides(
+    mut target: AuditTarget,
+    vault_addr: Option<&str>,
+    vault_token: Option<&str>,
+    vault_namespace: Option<&str>,
+    depth: Option<usize>,
+    format: Option<&str>,
+    output: Option<&str>,
+) -> AuditTarget {
+    if let Some(v) = vault_addr {
+        target.vault_addr = Some(v.to_string());
+    }
+    if let Some(v) = vault_token {
+        target.vault_token = Some(v.to_string());
+    }
+    if let Some(v) = vault_namespace {
+        target.vault_namespace = Some(v.to_string());
+    }
+    if let Some(v) = depth {
+        target.depth = Some(v);
+    }
+    if let Some(v) = format {
+        target.format = Some(v.to_string());
+    }
+    if let Some(v) = output {
+        target.output = Some(v.to_string());
+    }
+    target
+}
@@ -15,6 +15,7 @@
 //!   - `entity-analysis gaps` - Find gaps in entity activity patterns
 //!   - `entity-analysis timeline` - Visualize entity activity over time
 //!   - `entity-analysis preprocess` - Extract entity data for external processing
+//! - [`entity_clusters`] - Cluster entities by overlapping KV access patterns
 //! - [`entity_list`] - List all entities found in audit logs
 //! - [`entity_creation`] - ⚠️ DEPRECATED: Use `entity-analysis creation` instead
 //! - [`entity_churn`] - ⚠️ DEPRECATED: Use `entity-analysis churn` instead
@@ -56,25 +57,88 @@
 //! - [`system_overview`] - Generate high-level statistics about audit logs
 //! - [`path_hotspots`] - Identify most frequently accessed paths
 //! - [`client_activity`] - Analyze client access patterns
+//! - [`client_traffic_analysis`] - Analyze client traffic patterns and behavior from audit logs
 //! - [`airflow_polling`] - Detect Airflow polling behavior patterns
+//! - [`bench`] - Measure parsing/analysis throughput
+//! - [`findings`] - Normalize several detectors into one typed, severity-ranked alert stream
+//! - [`anomaly_detect`] - Flag per-entity rate anomalies with an EWMA mean/variance baseline
+//!
+//! ### Mount Inventory Commands
+//!
+//! Enumerate the mount trees that the policy commands below evaluate:
+//!
+//! - [`kv_mounts`] - Discover and tree-list KV v1/v2 mounts, with snapshot diffing
+//! - [`auth_mounts`] - Discover auth mounts and expand their roles/users
+//! - [`pki_mounts`] - Discover PKI mounts and their issuers, roles, and issued certificates
+//!
+//! ### Policy Commands
+//!
+//! - [`compliance`] - Evaluate declarative rules against saved KV/auth mount snapshots
+//! - [`threat_scan`] - Match audit entries against a configurable set of threat indicators
+//!
+//! ### Multi-Analysis Commands
+//!
+//! - [`audit_scan`] - Run several analyses (K8s login counting, entity mapping) over one shared pass of the logs
+//! - [`search`] - Build and query a field-scoped inverted index over audit logs
+//!
+//! ### Export Commands
+//!
+//! - [`export_elastic`] - Ship a command's `--format json` export to Elasticsearch as ECS documents
+//!
+//! ### Streaming Commands
+//!
+//! - [`serve`] - Accept a live audit stream over HTTP and serve rolling in-memory findings
+//!
+//! ### Telemetry Reconciliation Commands
+//!
+//! - [`vault_metrics`] - Cross-reference Vault's `/sys/metrics` telemetry against audit-log-derived counts
+//!
+//! ### Obfuscation Commands
+//!
+//! - [`audit_hash`] - Compute the `hmac-sha256:<hex>` form an audit device would give a plaintext, for grepping obfuscated logs
+//! - [`unhash`] - Bulk-rewrite `hmac-sha256:...` fields back to plaintext using an entity map and device salt
+//! - [`audit_devices`] - List, enable, and disable Vault audit devices
+//!
+//! ### Introspection Commands
+//!
+//! - [`schema`] - Print (or validate against) the `--format json` output envelope's JSON Schema
 
 pub mod airflow_polling;
+pub mod anomaly_detect;
+pub mod audit_devices;
+pub mod audit_hash;
+pub mod audit_scan;
+pub mod auth_mounts;
+pub mod bench;
 pub mod client_activity;
+pub mod client_traffic_analysis;
+pub mod compliance;
 pub mod entity_analysis;
 pub mod entity_churn;
+pub mod entity_clusters;
 pub mod entity_creation;
 pub mod entity_gaps;
 pub mod entity_list;
 pub mod entity_timeline;
+pub mod export_elastic;
+pub mod findings;
 pub mod k8s_auth;
 pub mod kv_analysis;
 pub mod kv_analyzer;
 pub mod kv_compare;
+pub mod kv_mounts;
 pub mod kv_summary;
 pub mod path_hotspots;
+pub mod pki_mounts;
 pub mod preprocess_entities;
+pub mod schema;
+pub mod search;
+pub mod serve;
 pub mod system_overview;
+pub mod threat_scan;
 pub mod token_analysis;
 pub mod token_export;
 pub mod token_lookup_abuse;
 pub mod token_operations;
+pub mod unhash;
+pub mod vault_metrics;
@@ -0,0 +1,584 @@
+//! PKI secret-engine enumeration: issuers, roles, and issued certificates.
+//!
+//! Queries `/v1/sys/mounts` for `pki`-type mounts and, for each, lists its
+//! issuers (`/v1/<mount>/issuers`), roles (`/v1/<mount>/roles`), and issued
+//! certificate serials (`/v1/<mount>/certs`). Each certificate's PEM is then
+//! fetched individually (`/v1/<mount>/cert/<serial>`) and parsed - with a
+//! small hand-rolled DER/ASN.1 reader, since this crate has no X.509
+//! dependency - to pull out the subject CN, validity window, and key usage
+//! flags.
+//!
+//! # Scope Note
+//!
+//! Vault's PKI API doesn't record which role (if any) issued a given
+//! certificate - `/v1/<mount>/certs` returns bare serials with no role
+//! attribution. The CSV's `role` column is therefore only populated on role
+//! rows and left blank on certificate rows, rather than fabricating a
+//! cert-to-role link the API doesn't provide.
+//!
+//! # Output Formats
+//!
+//! - **stdout**: a tree per mount, with "Issuers", "Roles", and
+//!   "Certificates" sections
+//! - **csv**: `mount_path,issuer,role,serial,cn,not_after,depth,expiring_soon`
+//!   - one row per issuer, one row per role, one row per certificate
+//! - **json**: the full [`PkiMountOutput`] tree
+//!
+//! `--expiring-within <seconds>` (default 30 days) flags certificates whose
+//! `not_after` falls within that window from now as `expiring_soon`, so
+//! operators can audit cert sprawl without eyeballing raw expiry dates.
+//!
+//! # Concurrency
+//!
+//! Certificate PEM fetches within a mount are issued concurrently, bounded
+//! by `--concurrency` in-flight requests at once, same as `kv-mounts`.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::File;
+use std::io::Write;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+use crate::vault_api::VaultClient;
+
+// ---- Minimal base64 + DER/ASN.1 reader, just enough to pull CN / validity /
+// keyUsage out of an X.509 certificate. No base64 or X.509 crate exists
+// anywhere else in this codebase, so both are hand-rolled here rather than
+// adding a dependency for three fields.
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64_decode(input: &str) -> Option<Vec<u8>> {
+    let mut reverse = [255u8; 256];
+    for (i, &b) in BASE64_ALPHABET.iter().enumerate() {
+        reverse[b as usize] = i as u8;
+    }
+
+    let clean: Vec<u8> = input.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    if clean.is_empty() || clean.len() % 4 != 0 {
+        return None;
+    }
+
+    let mut out = Vec::with_capacity(clean.len() / 4 * 3);
+    for chunk in clean.chunks_exact(4) {
+        let mut vals = [0u8; 4];
+        let mut pad = 0usize;
+        for (i, &b) in chunk.iter().enumerate() {
+            if b == b'=' {
+                pad += 1;
+            } else {
+                let v = reverse[b as usize];
+                if v == 255 {
+                    return None;
+                }
+                vals[i] = v;
+            }
+        }
+        let n = (u32::from(vals[0]) << 18)
+            | (u32::from(vals[1]) << 12)
+            | (u32::from(vals[2]) << 6)
+            | u32::from(vals[3]);
+        out.push((n >> 16) as u8);
+        if pad < 2 {
+            out.push((n >> 8) as u8);
+        }
+        if pad < 1 {
+            out.push(n as u8);
+        }
+    }
+    Some(out)
+}
+
+/// Strip the `-----BEGIN .../-----END ...-----` armor and base64-decode the
+/// body into raw DER bytes.
+fn pem_to_der(pem: &str) -> Option<Vec<u8>> {
+    let body: String = pem.lines().filter(|line| !line.starts_with("-----")).collect();
+    base64_decode(&body)
+}
+
+/// One decoded DER tag-length-value, definite-length encoding only (the only
+/// encoding X.509 certificates use).
+#[derive(Debug, Clone, Copy)]
+struct DerTlv<'a> {
+    tag: u8,
+    content: &'a [u8],
+}
+
+fn parse_der_tlv(bytes: &[u8]) -> Option<(DerTlv<'_>, &[u8])> {
+    let (&tag, rest) = bytes.split_first()?;
+    let (&first_len, rest) = rest.split_first()?;
+
+    let (length, rest) = if first_len & 0x80 == 0 {
+        (usize::from(first_len), rest)
+    } else {
+        let num_bytes = usize::from(first_len & 0x7f);
+        if num_bytes == 0 || num_bytes > std::mem::size_of::<usize>() || rest.len() < num_bytes {
+            return None;
+        }
+        let (len_bytes, rest) = rest.split_at(num_bytes);
+        let len = len_bytes.iter().fold(0usize, |acc, &b| (acc << 8) | usize::from(b));
+        (len, rest)
+    };
+
+    if rest.len() < length {
+        return None;
+    }
+    let (content, rest) = rest.split_at(length);
+    Some((DerTlv { tag, content }, rest))
+}
+
+/// Parse a DER `SEQUENCE`/`SET`'s content into its immediate child TLVs.
+fn parse_der_children(content: &[u8]) -> Vec<DerTlv<'_>> {
+    let mut items = Vec::new();
+    let mut rest = content;
+    while let Some((tlv, remainder)) = parse_der_tlv(rest) {
+        items.push(tlv);
+        rest = remainder;
+    }
+    items
+}
+
+const OID_COMMON_NAME: [u8; 3] = [0x55, 0x04, 0x03];
+const OID_KEY_USAGE: [u8; 3] = [0x55, 0x1D, 0x0F];
+const KEY_USAGE_BITS: [&str; 8] = [
+    "digitalSignature",
+    "nonRepudiation",
+    "keyEncipherment",
+    "dataEncipherment",
+    "keyAgreement",
+    "keyCertSign",
+    "cRLSign",
+    "encipherOnly",
+];
+
+/// Find the `commonName` attribute value in an X.509 `Name` (RDNSequence).
+fn extract_common_name(name: &DerTlv) -> Option<String> {
+    for rdn in parse_der_children(name.content) {
+        for attribute in parse_der_children(rdn.content) {
+            let fields = parse_der_children(attribute.content);
+            let (Some(oid), Some(value)) = (fields.first(), fields.get(1)) else {
+                continue;
+            };
+            if oid.content == OID_COMMON_NAME {
+                return std::str::from_utf8(value.content).ok().map(str::to_string);
+            }
+        }
+    }
+    None
+}
+
+/// Decode a DER `UTCTime` (`YYMMDDHHMMSSZ`) or `GeneralizedTime`
+/// (`YYYYMMDDHHMMSSZ`) value into an RFC 3339 string.
+fn parse_der_time(tlv: &DerTlv) -> Option<String> {
+    let raw = std::str::from_utf8(tlv.content).ok()?;
+    let naive = match tlv.tag {
+        0x17 => {
+            let two_digit_year: i32 = raw.get(0..2)?.parse().ok()?;
+            let year = if two_digit_year >= 50 { 1900 + two_digit_year } else { 2000 + two_digit_year };
+            let full = format!("{year}{}", &raw[2..]);
+            chrono::NaiveDateTime::parse_from_str(&full, "%Y%m%d%H%M%SZ").ok()
+        }
+        0x18 => chrono::NaiveDateTime::parse_from_str(raw, "%Y%m%d%H%M%SZ").ok(),
+        _ => None,
+    }?;
+    Some(naive.and_utc().to_rfc3339())
+}
+
+/// Decode a `KeyUsage` `BIT STRING`'s content (`[unused_bits, data...]`)
+/// into the names of its set bits. Only the first data byte is decoded
+/// (`encipherOnly`/`decipherOnly` share it save for the last bit, which this
+/// skips) - plenty for the audit-facing flags this command surfaces.
+fn decode_key_usage(bitstring_content: &[u8]) -> Vec<String> {
+    let Some((_unused_bits, data)) = bitstring_content.split_first() else {
+        return Vec::new();
+    };
+    let Some(&byte) = data.first() else {
+        return Vec::new();
+    };
+    KEY_USAGE_BITS
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| byte & (0x80 >> i) != 0)
+        .map(|(_, name)| (*name).to_string())
+        .collect()
+}
+
+fn find_key_usage_extension(extensions: &[DerTlv]) -> Option<Vec<String>> {
+    for extension in extensions {
+        let fields = parse_der_children(extension.content);
+        let Some(extn_id) = fields.first() else { continue };
+        if extn_id.content != OID_KEY_USAGE {
+            continue;
+        }
+        // `extnValue` is always the last field (the optional `critical`
+        // BOOLEAN, if present, sits between `extnID` and it).
+        let Some(extn_value) = fields.last() else { continue };
+        let Some((bitstring, _)) = parse_der_tlv(extn_value.content) else { continue };
+        return Some(decode_key_usage(bitstring.content));
+    }
+    None
+}
+
+#[derive(Debug, Clone, Default)]
+struct ParsedCertificate {
+    cn: Option<String>,
+    not_before: Option<String>,
+    not_after: Option<String>,
+    key_usage: Vec<String>,
+}
+
+/// Parse a PEM-encoded X.509 certificate's `TBSCertificate` far enough to
+/// extract the subject CN, validity window, and key usage flags.
+fn parse_certificate_pem(pem: &str) -> Option<ParsedCertificate> {
+    let der = pem_to_der(pem)?;
+    let (certificate, _) = parse_der_tlv(&der)?;
+    let certificate_fields = parse_der_children(certificate.content);
+    let tbs = certificate_fields.first()?;
+    let tbs_fields = parse_der_children(tbs.content);
+
+    let mut idx = 0;
+    // version [0] EXPLICIT, optional
+    if tbs_fields.get(idx).is_some_and(|t| t.tag == 0xA0) {
+        idx += 1;
+    }
+    idx += 1; // serialNumber
+    idx += 1; // signature AlgorithmIdentifier
+    idx += 1; // issuer Name
+
+    let validity = tbs_fields.get(idx)?;
+    idx += 1;
+    let validity_fields = parse_der_children(validity.content);
+    let not_before = validity_fields.first().and_then(parse_der_time);
+    let not_after = validity_fields.get(1).and_then(parse_der_time);
+
+    let subject = tbs_fields.get(idx)?;
+    idx += 1;
+    let cn = extract_common_name(subject);
+
+    idx += 1; // subjectPublicKeyInfo
+
+    // issuerUniqueID [1] / subjectUniqueID [2], both optional
+    while tbs_fields.get(idx).is_some_and(|t| t.tag == 0x81 || t.tag == 0x82) {
+        idx += 1;
+    }
+
+    // extensions [3] EXPLICIT, optional
+    let key_usage = tbs_fields
+        .get(idx)
+        .filter(|t| t.tag == 0xA3)
+        .and_then(|wrapper| parse_der_tlv(wrapper.content))
+        .map(|(extensions_seq, _)| parse_der_children(extensions_seq.content))
+        .and_then(|extensions| find_key_usage_extension(&extensions))
+        .unwrap_or_default();
+
+    Some(ParsedCertificate { cn, not_before, not_after, key_usage })
+}
+
+// ---- Output types ----
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct PkiIssuerOutput {
+    issuer_id: String,
+    issuer_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct PkiRoleOutput {
+    name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct PkiCertOutput {
+    serial: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    cn: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    not_before: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    not_after: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    key_usage: Vec<String>,
+    expiring_soon: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PkiMountOutput {
+    path: String,
+    mount_type: String,
+    description: String,
+    accessor: String,
+    issuers: Vec<PkiIssuerOutput>,
+    roles: Vec<PkiRoleOutput>,
+    certs: Vec<PkiCertOutput>,
+}
+
+// ---- Enumeration ----
+
+async fn list_keys(client: &VaultClient, path: &str) -> Vec<String> {
+    let Ok(response) = client.list_json(path).await else {
+        return Vec::new();
+    };
+    response
+        .get("data")
+        .and_then(|d| d.get("keys"))
+        .and_then(Value::as_array)
+        .map(|keys| keys.iter().filter_map(Value::as_str).map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+async fn list_issuers(client: &VaultClient, mount_trimmed: &str) -> Vec<PkiIssuerOutput> {
+    let path = format!("{}/issuers", mount_trimmed);
+    let Ok(response) = client.list_json(&path).await else {
+        return Vec::new();
+    };
+    let data = response.get("data");
+    let key_info = data.and_then(|d| d.get("key_info"));
+
+    data.and_then(|d| d.get("keys"))
+        .and_then(Value::as_array)
+        .map(|keys| {
+            keys.iter()
+                .filter_map(Value::as_str)
+                .map(|issuer_id| {
+                    let issuer_name = key_info
+                        .and_then(|info| info.get(issuer_id))
+                        .and_then(|info| info.get("issuer_name"))
+                        .and_then(Value::as_str)
+                        .filter(|name| !name.is_empty())
+                        .unwrap_or("default")
+                        .to_string();
+                    PkiIssuerOutput { issuer_id: issuer_id.to_string(), issuer_name }
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+async fn fetch_cert(
+    client: &VaultClient,
+    mount_trimmed: &str,
+    serial: &str,
+    expiring_within: chrono::Duration,
+    now: DateTime<Utc>,
+) -> PkiCertOutput {
+    let path = format!("{}/cert/{}", mount_trimmed, serial);
+    let pem = client
+        .get_json(&path)
+        .await
+        .ok()
+        .and_then(|v| v.get("data").and_then(|d| d.get("certificate")).and_then(Value::as_str).map(str::to_string));
+
+    let parsed = pem.as_deref().and_then(parse_certificate_pem).unwrap_or_default();
+
+    let expiring_soon = parsed
+        .not_after
+        .as_deref()
+        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+        .is_some_and(|not_after| not_after.with_timezone(&Utc) <= now + expiring_within);
+
+    PkiCertOutput {
+        serial: serial.to_string(),
+        cn: parsed.cn,
+        not_before: parsed.not_before,
+        not_after: parsed.not_after,
+        key_usage: parsed.key_usage,
+        expiring_soon,
+    }
+}
+
+fn print_tree(mount: &PkiMountOutput) {
+    println!("Path: {}", mount.path);
+    println!("  Accessor: {}", mount.accessor);
+    println!("  Description: {}", mount.description);
+
+    if !mount.issuers.is_empty() {
+        println!("  Issuers:");
+        for issuer in &mount.issuers {
+            println!("    - {} ({})", issuer.issuer_name, issuer.issuer_id);
+        }
+    }
+
+    if !mount.roles.is_empty() {
+        println!("  Roles:");
+        for role in &mount.roles {
+            println!("    - {}", role.name);
+        }
+    }
+
+    if !mount.certs.is_empty() {
+        println!("  Certificates:");
+        for cert in &mount.certs {
+            let flag = if cert.expiring_soon { " [EXPIRING SOON]" } else { "" };
+            println!(
+                "    - {} cn={} not_after={}{}",
+                cert.serial,
+                cert.cn.as_deref().unwrap_or("-"),
+                cert.not_after.as_deref().unwrap_or("-"),
+                flag
+            );
+        }
+    }
+    println!();
+}
+
+fn write_csv(mounts: &[PkiMountOutput], output: Option<&str>) -> Result<()> {
+    use std::fmt::Write as _;
+    let mut csv_output = String::new();
+    csv_output.push_str("mount_path,issuer,role,serial,cn,not_after,depth,expiring_soon\n");
+
+    for mount in mounts {
+        for issuer in &mount.issuers {
+            let _ = writeln!(csv_output, "\"{}\",\"{}\",\"\",\"\",\"\",\"\",1,\"\"", mount.path, issuer.issuer_name);
+        }
+        for role in &mount.roles {
+            let _ = writeln!(csv_output, "\"{}\",\"\",\"{}\",\"\",\"\",\"\",1,\"\"", mount.path, role.name);
+        }
+        for cert in &mount.certs {
+            let _ = writeln!(
+                csv_output,
+                "\"{}\",\"\",\"\",\"{}\",\"{}\",\"{}\",1,\"{}\"",
+                mount.path,
+                cert.serial,
+                cert.cn.as_deref().unwrap_or(""),
+                cert.not_after.as_deref().unwrap_or(""),
+                cert.expiring_soon
+            );
+        }
+    }
+
+    if let Some(output_path) = output {
+        let mut file = File::create(output_path).context("Failed to create output file")?;
+        file.write_all(csv_output.as_bytes()).context("Failed to write CSV to file")?;
+        eprintln!("Output written to: {}", output_path);
+    } else {
+        print!("{}", csv_output);
+    }
+    Ok(())
+}
+
+fn write_json(mounts: &[PkiMountOutput], output: Option<&str>) -> Result<()> {
+    let json_output = serde_json::to_string_pretty(mounts).context("Failed to serialize to JSON")?;
+    if let Some(output_path) = output {
+        let mut file = File::create(output_path).context("Failed to create output file")?;
+        file.write_all(json_output.as_bytes()).context("Failed to write JSON to file")?;
+        eprintln!("Output written to: {}", output_path);
+    } else {
+        println!("{}", json_output);
+    }
+    Ok(())
+}
+
+/// Run the PKI mount enumeration command: discover every `pki`-type mount,
+/// list its issuers/roles/certificates, parse each certificate's PEM, flag
+/// ones expiring within `expiring_within_secs`, and emit the result in the
+/// requested `--format` (csv, json, or stdout).
+#[allow(clippy::future_not_send)]
+#[allow(clippy::too_many_arguments)]
+pub async fn run(
+    vault_addr: Option<&str>,
+    vault_token: Option<&str>,
+    vault_namespace: Option<&str>,
+    role_id: Option<&str>,
+    secret_id: Option<&str>,
+    insecure: bool,
+    output: Option<&str>,
+    format: &str,
+    concurrency: usize,
+    expiring_within_secs: i64,
+    resolve: &[(String, std::net::SocketAddr)],
+    dns_server: Option<std::net::SocketAddr>,
+) -> Result<()> {
+    let client = VaultClient::connect(
+        vault_addr,
+        vault_token,
+        vault_namespace,
+        role_id,
+        secret_id,
+        insecure,
+        resolve,
+        dns_server,
+    )
+    .await?;
+
+    eprintln!("Querying Vault API for PKI mounts...");
+    eprintln!("   Vault Address: {}", client.addr());
+
+    let response: Value = client.get("/v1/sys/mounts").await.context("Failed to query /v1/sys/mounts")?;
+    let mounts_data = response.get("data").or(Some(&response)).context("Failed to get mounts data")?;
+    let mounts = mounts_data.as_object().context("Expected object response from /v1/sys/mounts")?;
+
+    let pki_mount_paths: Vec<String> = mounts
+        .iter()
+        .filter(|(_, info)| info.get("type").and_then(Value::as_str) == Some("pki"))
+        .map(|(path, _)| path.clone())
+        .collect();
+
+    let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+    let expiring_within = chrono::Duration::seconds(expiring_within_secs);
+    let now = Utc::now();
+
+    let mut pki_mounts = Vec::new();
+    for path in pki_mount_paths {
+        let mount_info = &mounts[&path];
+        let description = mount_info.get("description").and_then(Value::as_str).unwrap_or_default().to_string();
+        let accessor = mount_info.get("accessor").and_then(Value::as_str).unwrap_or_default().to_string();
+        let mount_trimmed = format!("/v1/{}", path.trim_end_matches('/'));
+
+        let issuers = list_issuers(&client, &mount_trimmed).await;
+        let roles = list_keys(&client, &format!("{}/roles", mount_trimmed))
+            .await
+            .into_iter()
+            .map(|name| PkiRoleOutput { name })
+            .collect();
+        let serials = list_keys(&client, &format!("{}/certs", mount_trimmed)).await;
+
+        let certs: Vec<PkiCertOutput> = stream::iter(serials)
+            .map(|serial| {
+                let client = &client;
+                let mount_trimmed = &mount_trimmed;
+                let semaphore = Arc::clone(&semaphore);
+                async move {
+                    let _permit = semaphore.acquire().await.expect("semaphore closed");
+                    fetch_cert(client, mount_trimmed, &serial, expiring_within, now).await
+                }
+            })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await;
+
+        pki_mounts.push(PkiMountOutput {
+            path,
+            mount_type: "pki".to_string(),
+            description,
+            accessor,
+            issuers,
+            roles,
+            certs,
+        });
+    }
+
+    pki_mounts.sort_by(|a, b| a.path.cmp(&b.path));
+
+    eprintln!("Found {} PKI mounts", pki_mounts.len());
+
+    match format {
+        "json" => write_json(&pki_mounts, output)?,
+        "csv" => write_csv(&pki_mounts, output)?,
+        "stdout" => {
+            println!("\nPKI Mounts:");
+            println!("{}", "=".repeat(80));
+            for mount in &pki_mounts {
+                print_tree(mount);
+            }
+        }
+        _ => {
+            return Err(anyhow!("Invalid format: {}. Must be one of: csv, json, stdout", format));
+        }
+    }
+
+    Ok(())
+}
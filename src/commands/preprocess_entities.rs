@@ -50,6 +50,7 @@
 //! - Historical trending
 
 use crate::audit::types::AuditEntry;
+use crate::utils::mapping_store::{open_store, InMemoryStore, MappingStore, StoreBackend};
 use crate::utils::progress::ProgressBar;
 use crate::utils::reader::open_file;
 use anyhow::{Context, Result};
@@ -59,25 +60,192 @@ use std::fs::File;
 use std::io::{BufRead, BufReader, Write};
 
 /// Entity mapping with login statistics
-#[derive(Debug, Serialize, Deserialize)]
-struct EntityMapping {
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EntityMapping {
+    pub display_name: String,
+    pub mount_path: String,
+    pub mount_accessor: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    pub login_count: usize,
+    pub first_seen: String,
+    pub last_seen: String,
+}
+
+/// A single CSV row of entity mapping data, carrying `entity_id` as its own
+/// column since CSV (unlike the JSON map form) has no natural object key.
+#[derive(Debug, Deserialize)]
+struct EntityMappingCsvRow {
+    entity_id: String,
     display_name: String,
     mount_path: String,
     mount_accessor: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
     username: Option<String>,
     login_count: usize,
     first_seen: String,
     last_seen: String,
 }
 
-pub fn run(log_files: &[String], output: &str, format: &str) -> Result<()> {
-    eprintln!("Preprocessing audit logs...");
-    eprintln!("Extracting entity → display_name mappings from login events...\n");
+/// Load a previously-written entity mapping file, auto-detecting JSON vs.
+/// CSV by extension. Used by [`run`]'s `--merge-into` and by the standalone
+/// `entity-map merge` subcommand ([`run_merge`]) to fold prior runs'
+/// mappings together without re-scanning raw audit logs.
+pub fn load_entity_mappings(path: &str) -> Result<HashMap<String, EntityMapping>> {
+    let file =
+        File::open(path).with_context(|| format!("Failed to open entity map file: {}", path))?;
+
+    if std::path::Path::new(path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+    {
+        let mut reader = csv::Reader::from_reader(file);
+        let mut mappings = HashMap::new();
+        for result in reader.deserialize() {
+            let row: EntityMappingCsvRow = result.context("Failed to parse entity map CSV row")?;
+            mappings.insert(
+                row.entity_id,
+                EntityMapping {
+                    display_name: row.display_name,
+                    mount_path: row.mount_path,
+                    mount_accessor: row.mount_accessor,
+                    username: row.username,
+                    login_count: row.login_count,
+                    first_seen: row.first_seen,
+                    last_seen: row.last_seen,
+                },
+            );
+        }
+        Ok(mappings)
+    } else {
+        serde_json::from_reader(file).context("Failed to parse entity map JSON")
+    }
+}
+
+/// Fold `incoming` (a freshly-scanned or freshly-loaded map) into `existing`,
+/// accumulating `login_count`, keeping the earliest `first_seen` and latest
+/// `last_seen`, and preserving `username`/`mount_accessor`/`display_name`
+/// when `incoming`'s record lacks them.
+fn merge_entity_maps(
+    existing: HashMap<String, EntityMapping>,
+    incoming: HashMap<String, EntityMapping>,
+) -> HashMap<String, EntityMapping> {
+    let mut merged = existing;
+    for (entity_id, new_mapping) in incoming {
+        merged
+            .entry(entity_id)
+            .and_modify(|current| {
+                current.login_count += new_mapping.login_count;
+                if new_mapping.first_seen < current.first_seen {
+                    current.first_seen.clone_from(&new_mapping.first_seen);
+                }
+                if new_mapping.last_seen > current.last_seen {
+                    current.last_seen.clone_from(&new_mapping.last_seen);
+                }
+                if !new_mapping.display_name.is_empty() {
+                    current.display_name.clone_from(&new_mapping.display_name);
+                }
+                if !new_mapping.mount_accessor.is_empty() {
+                    current
+                        .mount_accessor
+                        .clone_from(&new_mapping.mount_accessor);
+                }
+                if new_mapping.username.is_some() {
+                    current.username.clone_from(&new_mapping.username);
+                }
+            })
+            .or_insert(new_mapping);
+    }
+    merged
+}
+
+/// Fold any number of previously-written JSON/CSV entity maps into one,
+/// combining counts via [`merge_entity_maps`] - the standalone counterpart
+/// to [`run`]'s `--merge-into`, for combining per-day preprocessed maps
+/// without re-scanning raw logs.
+pub fn run_merge(inputs: &[String], output: &str) -> Result<()> {
+    anyhow::ensure!(
+        !inputs.is_empty(),
+        "entity-map merge requires at least one input file"
+    );
+
+    let mut merged: HashMap<String, EntityMapping> = HashMap::new();
+    for input in inputs {
+        eprintln!("Loading entity map: {}", input);
+        let mappings = load_entity_mappings(input)?;
+        merged = merge_entity_maps(merged, mappings);
+    }
+
+    eprintln!(
+        "\nMerged {} entity maps into {} entities",
+        inputs.len(),
+        merged.len()
+    );
+
+    let is_csv = std::path::Path::new(output)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+
+    if is_csv {
+        let output_file = File::create(output)
+            .with_context(|| format!("Failed to create output file: {}", output))?;
+        let mut csv_writer = csv::Writer::from_writer(output_file);
+        csv_writer.write_record([
+            "entity_id",
+            "display_name",
+            "mount_path",
+            "mount_accessor",
+            "username",
+            "login_count",
+            "first_seen",
+            "last_seen",
+        ])?;
+        for (entity_id, mapping) in &merged {
+            csv_writer.write_record([
+                entity_id,
+                &mapping.display_name,
+                &mapping.mount_path,
+                &mapping.mount_accessor,
+                mapping.username.as_deref().unwrap_or(""),
+                &mapping.login_count.to_string(),
+                &mapping.first_seen,
+                &mapping.last_seen,
+            ])?;
+        }
+        csv_writer.flush()?;
+    } else {
+        let output_file = File::create(output)
+            .with_context(|| format!("Failed to create output file: {}", output))?;
+        serde_json::to_writer_pretty(output_file, &merged)
+            .context("Failed to write merged entity map JSON")?;
+    }
 
-    let mut entity_map: HashMap<String, EntityMapping> = HashMap::new();
-    let mut login_events = 0;
-    let mut lines_processed = 0;
+    eprintln!("✓ Wrote merged entity map to {}\n", output);
+
+    Ok(())
+}
+
+/// Scan `log_files` once and build the entity-to-mapping table, without
+/// writing it anywhere. Used by other commands'
+/// auto-preprocessing (e.g. [`crate::commands::entity_analysis::run_churn`]),
+/// which only need the in-memory map.
+pub fn build_entity_map(log_files: &[String]) -> Result<HashMap<String, EntityMapping>> {
+    let mut store: InMemoryStore<EntityMapping> = InMemoryStore::new();
+    scan_into_store(log_files, &mut store)?;
+    Ok(store.iter()?.collect())
+}
+
+/// Scan `log_files` once, upserting each entity's mapping into `store` as
+/// it goes. Backs both [`build_entity_map`]'s in-memory default and [`run`]'s
+/// `--store-backend sled` path, so a multi-terabyte corpus with millions of
+/// distinct entities never needs the whole map resident in RAM at once.
+fn scan_into_store(
+    log_files: &[String],
+    store: &mut dyn MappingStore<EntityMapping>,
+) -> Result<()> {
+    // Resolve `s3://bucket/prefix/` and `s3://bucket/.../*.log` entries down
+    // to concrete per-object keys before processing.
+    let log_files = crate::utils::reader::expand_sources(log_files)?;
 
     // Process each log file sequentially
     for (file_idx, log_file) in log_files.iter().enumerate() {
@@ -105,7 +273,6 @@ pub fn run(log_files: &[String], output: &str, format: &str) -> Result<()> {
 
         for line in reader.lines() {
             file_lines += 1;
-            lines_processed += 1;
             let line = line?;
             bytes_read += line.len() + 1; // +1 for newline
 
@@ -158,8 +325,6 @@ pub fn run(log_files: &[String], output: &str, format: &str) -> Result<()> {
                 _ => continue,
             };
 
-            login_events += 1;
-
             // Extract mount path from the auth path (e.g., "auth/github/login" -> "auth/github")
             let mount_path = path
                 .trim_end_matches("/login")
@@ -174,26 +339,28 @@ pub fn run(log_files: &[String], output: &str, format: &str) -> Result<()> {
                 .and_then(|v| v.as_str())
                 .map(|s| s.to_string());
 
-            // Update or insert entity mapping
-            entity_map
-                .entry(entity_id)
-                .and_modify(|mapping| {
+            // Update or insert entity mapping (a freshly-defaulted record has
+            // `login_count == 0`, which marks "not yet seen")
+            store.upsert_with(&entity_id, |mapping| {
+                if mapping.login_count == 0 {
+                    *mapping = EntityMapping {
+                        display_name,
+                        mount_path,
+                        mount_accessor,
+                        username,
+                        login_count: 1,
+                        first_seen: entry.time.clone(),
+                        last_seen: entry.time.clone(),
+                    };
+                } else {
                     mapping.login_count += 1;
                     mapping.last_seen = entry.time.clone();
                     // Update display_name if it's newer (handle case variations)
                     if entry.time > mapping.last_seen {
-                        mapping.display_name = display_name.clone();
+                        mapping.display_name = display_name;
                     }
-                })
-                .or_insert_with(|| EntityMapping {
-                    display_name,
-                    mount_path,
-                    mount_accessor,
-                    username,
-                    login_count: 1,
-                    first_seen: entry.time.clone(),
-                    last_seen: entry.time.clone(),
-                });
+                }
+            })?;
         }
 
         // Ensure we show 100% complete for this file
@@ -206,11 +373,52 @@ pub fn run(log_files: &[String], output: &str, format: &str) -> Result<()> {
         progress.finish_with_message(&format!("Processed {} lines from this file", file_lines));
     }
 
+    Ok(())
+}
+
+pub fn run(
+    log_files: &[String],
+    output: &str,
+    format: &str,
+    otel_endpoint: Option<&str>,
+    store_backend: StoreBackend,
+    store_path: Option<&str>,
+    merge_into: Option<&str>,
+    s3_endpoint: Option<&str>,
+) -> Result<()> {
+    let _otel_handle = crate::utils::otel::init(otel_endpoint)?;
+    let _run_span = crate::utils::otel::run_span("preprocess_entities");
+    crate::utils::s3::apply_endpoint_override(s3_endpoint);
+
+    eprintln!("Preprocessing audit logs...");
+    eprintln!("Extracting entity → display_name mappings from login events...\n");
+
+    let mut store = open_store::<EntityMapping>(store_backend, store_path)?;
+    scan_into_store(log_files, store.as_mut())?;
+
+    let mut entity_map: HashMap<String, EntityMapping> = store.iter()?.collect();
+
+    if let Some(prior_path) = merge_into {
+        eprintln!("\nMerging into existing entity map: {}", prior_path);
+        let prior = load_entity_mappings(prior_path)?;
+        entity_map = merge_entity_maps(prior, entity_map);
+    }
+
+    let login_events: usize = entity_map.values().map(|m| m.login_count).sum();
+    let entity_count = entity_map.len();
+
     eprintln!(
-        "\nTotal: Processed {} lines, found {} login events, tracked {} entities",
-        lines_processed,
+        "\nTotal: found {} login events, tracked {} entities",
         login_events,
-        entity_map.len()
+        entity_count
+    );
+
+    crate::utils::otel::record_run_metrics(
+        "preprocess_entities",
+        &[
+            ("login_events.total", login_events as u64),
+            ("entities.tracked", entity_count as u64),
+        ],
     );
 
     // Write output based on format
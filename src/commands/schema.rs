@@ -0,0 +1,41 @@
+//! Prints the JSON Schema for the `--format json` output envelope (see
+//! [`crate::utils::report`]), so downstream tooling can validate a
+//! command's JSON output without guessing the envelope shape by hand.
+//!
+//! # Usage
+//!
+//! ```bash
+//! vault-audit schema
+//! vault-audit path-hotspots logs/*.log --format json | vault-audit schema --check
+//! ```
+
+use anyhow::{bail, Result};
+use std::io::Read;
+
+/// Prints [`crate::utils::report::schema_json`]. With `check`, instead
+/// reads a JSON document from stdin and validates it against that schema,
+/// exiting non-zero (via an `Err`) on the first non-conforming output.
+pub fn run(check: bool) -> Result<()> {
+    if !check {
+        println!("{}", crate::utils::report::schema_json());
+        return Ok(());
+    }
+
+    let mut input = String::new();
+    std::io::stdin().read_to_string(&mut input)?;
+    let value: serde_json::Value = serde_json::from_str(&input)?;
+    let schema: serde_json::Value = serde_json::from_str(crate::utils::report::schema_json())?;
+
+    match crate::utils::report::validate_output(&value, &schema) {
+        Ok(()) => {
+            println!("valid");
+            Ok(())
+        }
+        Err(errors) => {
+            for error in &errors {
+                eprintln!("{}", error);
+            }
+            bail!("{} schema violation(s)", errors.len());
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! Build and query a searchable inverted index over audit logs.
+//!
+//! Wraps [`crate::audit::index::Index`] with two subcommands: `build`
+//! persists an index to disk from a pass over the logs, and `query` loads
+//! it back and runs a `field:term` search, rehydrating and printing the
+//! matching lines on demand.
+//!
+//! # Usage
+//!
+//! ```bash
+//! vault-audit search build logs/*.log --output audit.index.json
+//! vault-audit search query audit.index.json "mount_type:kubernetes entity_id:abc123"
+//! vault-audit search query audit.index.json "path:auth/kubernetes/*"
+//! vault-audit search query audit.index.json "entity_id:abc123 OR entity_id:def456"
+//! ```
+
+use crate::audit::index::{Index, Query};
+use crate::utils::reader::open_file;
+use anyhow::{Context, Result};
+use std::io::{BufRead, Read};
+
+pub fn run_build(log_files: &[String], output: &str) -> Result<()> {
+    let index = Index::build(log_files)?;
+    index.save(output)?;
+    println!("Index written to {}", output);
+    Ok(())
+}
+
+pub fn run_query(index_path: &str, query: &str, limit: usize) -> Result<()> {
+    let index = Index::load(index_path)?;
+    let parsed = Query::parse(query)?;
+    let postings = index.query(&parsed);
+
+    println!("{} match(es)", postings.len());
+    for posting in postings.iter().take(limit) {
+        match rehydrate(&posting.file, posting.offset) {
+            Ok(line) => println!("{}:{}: {}", posting.file, posting.offset, line),
+            Err(err) => eprintln!(
+                "{}:{}: <failed to rehydrate: {}>",
+                posting.file, posting.offset, err
+            ),
+        }
+    }
+
+    if postings.len() > limit {
+        println!("... {} more match(es) not shown", postings.len() - limit);
+    }
+
+    Ok(())
+}
+
+/// Re-open `file` and read back the one line starting at `offset`.
+///
+/// `open_file`'s `Box<dyn Read + Send>` return type may wrap a decompressor,
+/// which can't seek, so this skip-reads to `offset` instead of seeking -
+/// fine for on-demand single-line lookups even on large compressed sources.
+fn rehydrate(file: &str, offset: u64) -> Result<String> {
+    let reader = open_file(file).with_context(|| format!("Failed to open {}", file))?;
+    let mut reader = std::io::BufReader::new(reader);
+
+    let mut skip_buf = vec![0u8; offset as usize];
+    reader.read_exact(&mut skip_buf)?;
+
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    Ok(line.trim_end_matches(['\n', '\r']).to_string())
+}
@@ -0,0 +1,386 @@
+//! Real-time audit ingest server (`serve`).
+//!
+//! Every other command in this tool is batch-oriented: point it at rotated
+//! `log_files` on disk and it reads them to completion. `serve` instead
+//! starts an HTTP listener that accepts a live Vault audit stream - the
+//! same newline-delimited JSON [`AuditEntry`] records a socket/file audit
+//! device emits - and keeps incrementally-updating in-memory aggregates
+//! for a selected subset of analyzers, so an operator can point `vault
+//! audit enable socket ...` (or a forwarder tailing the audit log) directly
+//! at this tool instead of post-processing rotated files later.
+//!
+//! Like [`crate::utils::metrics::MetricsExporter::serve_blocking`], this is
+//! a small hand-rolled HTTP responder rather than a pulled-in framework -
+//! there are exactly two routes, and the bodies involved are either a
+//! streamed NDJSON batch or a small JSON summary.
+//!
+//! # Routes
+//!
+//! - `POST /ingest`: body is newline-delimited [`AuditEntry`] JSON. Each
+//!   line that fails to parse is counted and skipped, mirroring the
+//!   tolerant-skip behavior every batch command already uses.
+//! - `GET /findings`: returns the current state of every enabled
+//!   analyzer's threshold breaches, as JSON.
+//!
+//! # Analyzers (`--analyzers`)
+//!
+//! A comma-separated subset of:
+//! - `token-lookup-abuse` - per-entity/token-accessor lookup counts past `--threshold`
+//! - `entity-churn` - first/last-seen per entity, flagging ones with no
+//!   activity for an hour (relative to the newest timestamp ingested) as
+//!   ephemeral (a streaming approximation of
+//!   [`crate::commands::entity_churn`]'s fuller multi-day classification)
+//! - `path-hotspots` - per-path access tallies
+//!
+//! # Checkpointing
+//!
+//! Every `--checkpoint-interval` (default 60s), the current aggregate
+//! state is written to `--checkpoint-file` (if set) via a
+//! temp-file-then-rename, the same pattern
+//! [`crate::utils::metrics::MetricsExporter::write_textfile`] uses. The file
+//! holds the same JSON `GET /findings` returns and is not read back on
+//! startup: a restarted server begins from empty state.
+
+use crate::audit::types::AuditEntry;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+const DEFAULT_LOOKUP_THRESHOLD: u64 = 100;
+/// An entity with no activity for this long (relative to the newest
+/// timestamp seen) is classified "ephemeral" rather than "active".
+const EPHEMERAL_IDLE_SECS: i64 = 3600;
+
+#[derive(Default)]
+struct TokenLookupState {
+    /// Lookups per (entity_id, token_accessor).
+    counts: HashMap<(String, String), u64>,
+}
+
+#[derive(Default)]
+struct EntityChurnState {
+    first_seen: HashMap<String, i64>,
+    last_seen: HashMap<String, i64>,
+    request_count: HashMap<String, u64>,
+}
+
+#[derive(Default)]
+struct PathHotspotState {
+    counts: HashMap<String, u64>,
+}
+
+#[derive(Default)]
+struct ServeState {
+    token_lookup_abuse: Option<TokenLookupState>,
+    entity_churn: Option<EntityChurnState>,
+    path_hotspots: Option<PathHotspotState>,
+    newest_timestamp: Option<i64>,
+}
+
+#[derive(Serialize)]
+struct LookupBreach {
+    entity_id: String,
+    token_accessor: String,
+    lookups: u64,
+}
+
+#[derive(Serialize)]
+struct EphemeralEntity {
+    entity_id: String,
+    request_count: u64,
+    seconds_since_last_seen: i64,
+}
+
+#[derive(Serialize)]
+struct PathHotspot {
+    path: String,
+    count: u64,
+}
+
+#[derive(Serialize, Default)]
+struct Findings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token_lookup_abuse: Option<Vec<LookupBreach>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ephemeral_entities: Option<Vec<EphemeralEntity>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path_hotspots: Option<Vec<PathHotspot>>,
+    entries_ingested: u64,
+    entries_failed_to_parse: u64,
+}
+
+struct Server {
+    state: RwLock<ServeState>,
+    threshold: u64,
+    entries_ingested: AtomicU64,
+    entries_failed_to_parse: AtomicU64,
+}
+
+impl Server {
+    fn new(analyzers: &[String], threshold: u64) -> Self {
+        let mut state = ServeState::default();
+        for analyzer in analyzers {
+            match analyzer.as_str() {
+                "token-lookup-abuse" => state.token_lookup_abuse = Some(TokenLookupState::default()),
+                "entity-churn" => state.entity_churn = Some(EntityChurnState::default()),
+                "path-hotspots" => state.path_hotspots = Some(PathHotspotState::default()),
+                other => eprintln!("Warning: unknown analyzer '{other}', ignoring"),
+            }
+        }
+        Self {
+            state: RwLock::new(state),
+            threshold,
+            entries_ingested: AtomicU64::new(0),
+            entries_failed_to_parse: AtomicU64::new(0),
+        }
+    }
+
+    /// Parses and folds one NDJSON batch into the rolling aggregates.
+    /// Unparseable lines are counted and skipped rather than aborting the
+    /// batch, matching the rest of this tool's tolerant handling of
+    /// malformed audit lines.
+    fn ingest_batch(&self, body: &str) {
+        let mut state = self.state.write().expect("serve state lock poisoned");
+        for line in body.lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let Ok(entry) = serde_json::from_str::<AuditEntry>(line) else {
+                self.entries_failed_to_parse.fetch_add(1, Ordering::Relaxed);
+                continue;
+            };
+            self.entries_ingested.fetch_add(1, Ordering::Relaxed);
+
+            let timestamp = crate::utils::time::parse_timestamp(&entry.time)
+                .map(|dt| dt.timestamp())
+                .ok();
+            if let Some(ts) = timestamp {
+                state.newest_timestamp = Some(state.newest_timestamp.map_or(ts, |cur| cur.max(ts)));
+            }
+
+            let entity_id = entry.auth.as_ref().and_then(|a| a.entity_id.clone());
+            let accessor = entry.auth.as_ref().and_then(|a| a.accessor.clone());
+            let path = entry.request.as_ref().and_then(|r| r.path.clone());
+            let operation = entry.request.as_ref().and_then(|r| r.operation.clone());
+
+            if let Some(lookup) = &mut state.token_lookup_abuse {
+                if operation.as_deref() == Some("read") && path.as_deref().is_some_and(|p| p.contains("/token/lookup")) {
+                    if let (Some(entity_id), Some(accessor)) = (&entity_id, &accessor) {
+                        *lookup
+                            .counts
+                            .entry((entity_id.clone(), accessor.clone()))
+                            .or_insert(0) += 1;
+                    }
+                }
+            }
+
+            if let Some(churn) = &mut state.entity_churn {
+                if let (Some(entity_id), Some(ts)) = (&entity_id, timestamp) {
+                    churn.first_seen.entry(entity_id.clone()).or_insert(ts);
+                    churn
+                        .last_seen
+                        .entry(entity_id.clone())
+                        .and_modify(|seen| *seen = (*seen).max(ts))
+                        .or_insert(ts);
+                    *churn.request_count.entry(entity_id.clone()).or_insert(0) += 1;
+                }
+            }
+
+            if let Some(hotspots) = &mut state.path_hotspots {
+                if let Some(path) = path {
+                    *hotspots.counts.entry(path).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+
+    fn findings(&self) -> Findings {
+        let state = self.state.read().expect("serve state lock poisoned");
+
+        let token_lookup_abuse = state.token_lookup_abuse.as_ref().map(|lookup| {
+            let mut breaches: Vec<LookupBreach> = lookup
+                .counts
+                .iter()
+                .filter(|(_, &count)| count >= self.threshold)
+                .map(|((entity_id, token_accessor), &lookups)| LookupBreach {
+                    entity_id: entity_id.clone(),
+                    token_accessor: token_accessor.clone(),
+                    lookups,
+                })
+                .collect();
+            breaches.sort_by(|a, b| b.lookups.cmp(&a.lookups));
+            breaches
+        });
+
+        let ephemeral_entities = state.entity_churn.as_ref().map(|churn| {
+            let newest = state.newest_timestamp.unwrap_or(0);
+            let mut ephemeral: Vec<EphemeralEntity> = churn
+                .last_seen
+                .iter()
+                .filter(|(_, &last)| newest - last >= EPHEMERAL_IDLE_SECS)
+                .map(|(entity_id, &last)| EphemeralEntity {
+                    entity_id: entity_id.clone(),
+                    request_count: churn.request_count.get(entity_id).copied().unwrap_or(0),
+                    seconds_since_last_seen: newest - last,
+                })
+                .collect();
+            ephemeral.sort_by(|a, b| b.seconds_since_last_seen.cmp(&a.seconds_since_last_seen));
+            ephemeral
+        });
+
+        let path_hotspots = state.path_hotspots.as_ref().map(|hotspots| {
+            let mut hot: Vec<PathHotspot> = hotspots
+                .counts
+                .iter()
+                .map(|(path, &count)| PathHotspot { path: path.clone(), count })
+                .collect();
+            hot.sort_by(|a, b| b.count.cmp(&a.count));
+            hot
+        });
+
+        Findings {
+            token_lookup_abuse,
+            ephemeral_entities,
+            path_hotspots,
+            entries_ingested: self.entries_ingested.load(Ordering::Relaxed),
+            entries_failed_to_parse: self.entries_failed_to_parse.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Serializes the current findings (the document `GET /findings`
+    /// returns) to JSON for `--checkpoint-file`.
+    fn checkpoint_snapshot(&self) -> serde_json::Value {
+        serde_json::to_value(self.findings()).unwrap_or(serde_json::Value::Null)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn run(
+    listen_addr: &str,
+    analyzers: &[String],
+    threshold: u64,
+    checkpoint_interval_secs: u64,
+    checkpoint_file: Option<&str>,
+) -> Result<()> {
+    let server = Arc::new(Server::new(analyzers, threshold));
+
+    if let Some(path) = checkpoint_file {
+        let server = Arc::clone(&server);
+        let path = path.to_string();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(checkpoint_interval_secs.max(1)));
+            loop {
+                interval.tick().await;
+                if let Err(e) = write_checkpoint(&server, &path) {
+                    eprintln!("Warning: failed to write checkpoint: {e}");
+                }
+            }
+        });
+    }
+
+    let listener = TcpListener::bind(listen_addr)
+        .await
+        .with_context(|| format!("Failed to bind serve listener on {listen_addr}"))?;
+    eprintln!("Serving audit ingest on http://{listen_addr} (POST /ingest, GET /findings)");
+
+    loop {
+        let (stream, _) = listener.accept().await.context("Failed to accept connection")?;
+        let server = Arc::clone(&server);
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, &server).await {
+                eprintln!("Warning: connection handling failed: {e}");
+            }
+        });
+    }
+}
+
+fn write_checkpoint(server: &Server, path: &str) -> Result<()> {
+    let snapshot = server.checkpoint_snapshot();
+    let rendered = serde_json::to_string_pretty(&snapshot)?;
+    let tmp_path = format!("{path}.tmp");
+    std::fs::write(&tmp_path, &rendered)
+        .with_context(|| format!("Failed to write checkpoint file: {tmp_path}"))?;
+    std::fs::rename(&tmp_path, path)
+        .with_context(|| format!("Failed to finalize checkpoint file: {path}"))?;
+    Ok(())
+}
+
+async fn handle_connection(mut stream: tokio::net::TcpStream, server: &Server) -> Result<()> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 8192];
+
+    // Read until the end of the headers, then the body (if Content-Length
+    // is present), the same minimal parsing `MetricsExporter::serve_blocking`
+    // does for its one GET route - just extended to cover a request body.
+    let headers_end = loop {
+        let n = stream.read(&mut chunk).await.context("Failed to read request")?;
+        if n == 0 {
+            break None;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = find_subslice(&buf, b"\r\n\r\n") {
+            break Some(pos + 4);
+        }
+        if buf.len() > 1024 * 1024 {
+            anyhow::bail!("Request headers too large");
+        }
+    };
+
+    let Some(headers_end) = headers_end else {
+        return Ok(());
+    };
+
+    let head = String::from_utf8_lossy(&buf[..headers_end]).to_string();
+    let mut lines = head.lines();
+    let request_line = lines.next().unwrap_or_default();
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+
+    let content_length: usize = lines
+        .find_map(|line| line.strip_prefix("Content-Length:").or_else(|| line.strip_prefix("content-length:")))
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(0);
+
+    while buf.len() < headers_end + content_length {
+        let n = stream.read(&mut chunk).await.context("Failed to read request body")?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+
+    let response = match (method, path) {
+        ("POST", "/ingest") => {
+            let body = String::from_utf8_lossy(&buf[headers_end..headers_end + content_length.min(buf.len() - headers_end)]).to_string();
+            server.ingest_batch(&body);
+            json_response(200, "OK", &serde_json::json!({ "status": "ok" }))
+        }
+        ("GET", "/findings") => {
+            let findings = server.findings();
+            json_response(200, "OK", &findings)
+        }
+        _ => json_response(404, "Not Found", &serde_json::json!({ "error": "not found" })),
+    };
+
+    stream.write_all(response.as_bytes()).await.context("Failed to write response")?;
+    Ok(())
+}
+
+fn json_response(status: u16, status_text: &str, body: &impl Serialize) -> String {
+    let rendered = serde_json::to_string(body).unwrap_or_else(|_| "{}".to_string());
+    format!(
+        "HTTP/1.1 {status} {status_text}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        rendered.len(),
+        rendered,
+    )
+}
+
+fn find_subslice(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
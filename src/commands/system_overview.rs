@@ -16,6 +16,27 @@
 //!
 //! # Using shell globbing with compressed files
 //! vault-audit system-overview logs/vault_audit.2025-10-*.log.gz
+//!
+//! # Structured output for dashboards/CI regression checks
+//! vault-audit system-overview audit.log --format json
+//! vault-audit system-overview audit.log --format csv
+//!
+//! # Bucket activity into hourly windows to spot load spikes
+//! vault-audit system-overview day1.log day2.log --interval 1h
+//!
+//! # Render the breakdown/prefix tables as terminal bar charts, and the
+//! # hourly trend as a standalone SVG line chart
+//! vault-audit system-overview audit.log --interval 1h --plot --plot-svg trend.svg
+//!
+//! # Flag request signatures repeated more than 10 times as likely replays
+//! vault-audit system-overview audit.log --dup-threshold 10
+//!
+//! # Bound per-path memory with an approximate unique-entity count instead
+//! # of an exact HashSet, for runs over huge multi-file datasets
+//! vault-audit system-overview logs/*.log.gz --approx
+//!
+//! # Print per-stage wall-clock/CPU timing and parse success/failure counts
+//! vault-audit system-overview logs/*.log --stats
 //! ```
 //!
 //! **Compressed File Support**: Automatically detects and decompresses `.gz` (gzip)
@@ -39,34 +60,329 @@
 //! - Capacity planning
 //! - Identifying hotspots
 //! - Security audits
+//!
+//! # Output Formats
+//!
+//! `--format text` (the default) prints the five fixed-width ASCII tables
+//! above. `--format json` serializes the same aggregated data as a single
+//! [`OverviewReport`] document to stdout instead, with `PathData` flattened
+//! into `{path, count, entities, operations}` rows - suitable for piping
+//! into dashboards or diffing across days. `--format csv` writes the same
+//! five tables as separate CSV sections to stdout, each preceded by a
+//! `# <section>` comment line - see [`build_report`].
+//!
+//! # Time-Series Trend
+//!
+//! Passing `--interval <duration>` adds a sixth section: entries are
+//! bucketed by their `time` field into fixed-width, UTC-epoch-aligned
+//! windows (e.g. every `1h`), and each bucket's operation count, unique
+//! entity count, error count, and ops/sec are reported alongside the
+//! busiest (peak) and quietest (trough) interval - see [`BucketStats`] and
+//! [`bucket_start`]. Without `--interval` no bucketing is done and this
+//! section is omitted.
+//!
+//! # Charts
+//!
+//! `--plot` renders the operation-type breakdown and top path prefixes as
+//! horizontal Unicode bar-histograms in the terminal alongside (not instead
+//! of) the usual tables. `--plot-svg <file>` writes a standalone SVG line
+//! chart of ops/interval from the `--interval` trend section; it's a no-op
+//! without `--interval`. Both reuse [`crate::utils::chart`].
+//!
+//! # Duplicate / Replay Detection
+//!
+//! Every entry is fingerprinted twice, cheapest-first: a 64-bit "partial"
+//! hash over `(entity_id, path, operation)` is always computed and counted
+//! in a `HashMap<u64, u32>` ([`DuplicateTracker::partial_counts`]) - one
+//! small integer per distinct signature, regardless of log size. Only once
+//! a partial bucket's count exceeds `--dup-threshold` (default 5) is a
+//! stronger 128-bit "full" hash computed, additionally folding in the
+//! remote address and canonicalized request data, and a
+//! [`DuplicateGroup`] retained and counted for it - see [`full_fingerprint`].
+//! This means a full-hash group's `count` reflects occurrences observed
+//! from the promotion point onward, not the whole history, which is the
+//! accepted tradeoff for not re-scanning already-processed lines. The top
+//! groups by count are reported as likely replays/retry storms. Because
+//! promotion happens per file, a signature that's only hot when summed
+//! across files (but never crosses the threshold within any single file)
+//! is not reported - see [`combine_results`].
+//!
+//! # Approximate Entity Counts
+//!
+//! `--approx` swaps the exact per-path entity `HashSet` for a
+//! [`HyperLogLog`] sketch (2^14 registers, ~16 KiB per path) via
+//! [`EntityTracker::Approx`], bounding per-path memory to a fixed size no
+//! matter how many millions of distinct entities touch a hot path, at a
+//! typical ~1% error on the "Entities" column of the top-paths table.
+//! Sketches merge by element-wise register max, so parallel file
+//! processing still combines correctly. The tradeoff: stress-point
+//! detection needs individual entity IDs, which approx mode never
+//! retains, so paths tracked with a sketch are skipped for that section
+//! - see [`EntityTracker::exact_ids`].
 
 use crate::audit::types::AuditEntry;
+use crate::utils::chart::{bar_chart, write_svg_line_chart};
 use crate::utils::format::format_number;
 use crate::utils::parallel::process_files_parallel;
 use crate::utils::progress::ProgressBar;
 use crate::utils::reader::open_file;
+use crate::utils::time::parse_timestamp;
 use anyhow::Result;
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 use std::io::{BufRead, BufReader};
 
+/// Number of register bits (`p`); `m = 2^p` registers of one byte each.
+const HLL_PRECISION: u32 = 14;
+/// Number of registers (`m`), one byte each - a fixed 16 KiB per sketch
+/// regardless of how many distinct entity IDs actually touch the path.
+const HLL_NUM_REGISTERS: usize = 1 << HLL_PRECISION;
+
+/// HyperLogLog cardinality sketch used by [`EntityTracker::Approx`] to bound
+/// the memory `--approx` mode spends tracking unique entities per path.
+///
+/// Each inserted entity ID is hashed to 64 bits; the top [`HLL_PRECISION`]
+/// bits select a register, and the register stores the longest run of
+/// leading zeros (+1) seen among the remaining bits, capped by keeping only
+/// the max per register. [`HyperLogLog::merge`] is an element-wise max over
+/// the two register arrays, so it composes cleanly with the parallel
+/// `combine_results` fold.
+#[derive(Debug, Clone)]
+struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    fn new() -> Self {
+        Self {
+            registers: vec![0u8; HLL_NUM_REGISTERS],
+        }
+    }
+
+    fn insert(&mut self, value: &str) {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let index = (hash >> (64 - HLL_PRECISION)) as usize;
+
+        // Leading-zero run (+1) among the (64 - p) bits that weren't used as
+        // the register index. `rest` masks those bits off, so its top `p`
+        // bits are forced to zero and must be subtracted back out.
+        let window_mask = (1u64 << (64 - HLL_PRECISION)) - 1;
+        let rest = hash & window_mask;
+        let rank = (rest.leading_zeros() - HLL_PRECISION + 1) as u8;
+
+        if rank > self.registers[index] {
+            self.registers[index] = rank;
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        for (reg, other_reg) in self.registers.iter_mut().zip(other.registers.iter()) {
+            if *other_reg > *reg {
+                *reg = *other_reg;
+            }
+        }
+    }
+
+    /// Estimated cardinality, per the standard HyperLogLog estimator with
+    /// the small-range linear-counting correction.
+    fn estimate(&self) -> usize {
+        let m = HLL_NUM_REGISTERS as f64;
+        let alpha_m = 0.7213 / (1.0 + 1.079 / m);
+
+        let sum: f64 = self
+            .registers
+            .iter()
+            .map(|&r| 2f64.powi(-(r as i32)))
+            .sum();
+        let raw_estimate = alpha_m * m * m / sum;
+
+        let estimate = if raw_estimate <= 2.5 * m {
+            let zero_registers = self.registers.iter().filter(|&&r| r == 0).count();
+            if zero_registers > 0 {
+                m * (m / zero_registers as f64).ln()
+            } else {
+                raw_estimate
+            }
+        } else {
+            raw_estimate
+        };
+
+        estimate.round().max(0.0) as usize
+    }
+}
+
+/// How a [`PathData`] tracks the distinct entities that hit it: either an
+/// exact `HashSet` of entity IDs, or a [`HyperLogLog`] sketch when `--approx`
+/// trades exact IDs for bounded per-path memory.
+#[derive(Debug, Clone)]
+enum EntityTracker {
+    Exact(HashSet<String>),
+    Approx(HyperLogLog),
+}
+
+impl EntityTracker {
+    fn new(approx: bool) -> Self {
+        if approx {
+            Self::Approx(HyperLogLog::new())
+        } else {
+            Self::Exact(HashSet::with_capacity(50)) // Typical: dozens of entities per popular path
+        }
+    }
+
+    fn insert(&mut self, entity_id: &str) {
+        match self {
+            Self::Exact(set) => {
+                set.insert(entity_id.to_string());
+            }
+            Self::Approx(hll) => hll.insert(entity_id),
+        }
+    }
+
+    fn merge(&mut self, other: Self) {
+        match (self, other) {
+            (Self::Exact(set), Self::Exact(other_set)) => set.extend(other_set),
+            (Self::Approx(hll), Self::Approx(other_hll)) => hll.merge(&other_hll),
+            _ => unreachable!("EntityTracker variants must agree within a single run"),
+        }
+    }
+
+    /// Unique entity count: exact length, or the HyperLogLog estimate.
+    fn unique_count(&self) -> usize {
+        match self {
+            Self::Exact(set) => set.len(),
+            Self::Approx(hll) => hll.estimate(),
+        }
+    }
+
+    /// Entity IDs, or `None` in approximate mode where individual IDs were
+    /// never retained (stress-point detection is skipped for such paths).
+    fn exact_ids(&self) -> Option<&HashSet<String>> {
+        match self {
+            Self::Exact(set) => Some(set),
+            Self::Approx(_) => None,
+        }
+    }
+}
+
 /// Path access statistics
 #[derive(Debug)]
 struct PathData {
     count: usize,
     operations: HashMap<String, usize>,
-    entities: HashSet<String>,
+    entities: EntityTracker,
 }
 
 impl PathData {
-    fn new() -> Self {
+    fn new(approx: bool) -> Self {
         Self {
             count: 0,
             operations: HashMap::with_capacity(10), // Typical: few operation types per path
-            entities: HashSet::with_capacity(50),   // Typical: dozens of entities per popular path
+            entities: EntityTracker::new(approx),
         }
     }
 }
 
+/// Per-bucket activity for the `--interval` trend section: keyed by
+/// UTC-epoch bucket start so buckets from different files line up exactly
+/// when merged in [`combine_results`].
+#[derive(Debug, Default)]
+struct BucketStats {
+    operations: usize,
+    unique_entities: HashSet<String>,
+    errors: usize,
+}
+
+/// Floor `timestamp` (RFC3339) to the start of its `interval_secs`-wide,
+/// UTC-epoch-aligned bucket. Returns `None` if `timestamp` doesn't parse,
+/// in which case the caller simply skips bucketing that entry.
+fn bucket_start(timestamp: &str, interval_secs: u64) -> Option<i64> {
+    let epoch = parse_timestamp(timestamp).ok()?.timestamp();
+    let interval_secs = interval_secs as i64;
+    Some(epoch - epoch.rem_euclid(interval_secs))
+}
+
+/// A retained full-hash duplicate/replay group: the request signature that
+/// was hot enough to promote past `--dup-threshold`, plus how many times
+/// it was seen from the promotion point onward.
+#[derive(Debug, Clone)]
+struct DuplicateGroup {
+    entity_id: String,
+    path: String,
+    operation: String,
+    remote_address: String,
+    count: u32,
+}
+
+/// Two-tier duplicate/replay fingerprint tracking - see the module-level
+/// "Duplicate / Replay Detection" docs for the promotion rule.
+#[derive(Debug, Default)]
+struct DuplicateTracker {
+    partial_counts: HashMap<u64, u32>,
+    full_groups: HashMap<u128, DuplicateGroup>,
+}
+
+/// Cheap 64-bit fingerprint over the fields that identify a request's
+/// "shape" - computed for every entry.
+fn partial_fingerprint(entity_id: &str, path: &str, operation: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    entity_id.hash(&mut hasher);
+    path.hash(&mut hasher);
+    operation.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Stronger 128-bit fingerprint, additionally folding in the remote
+/// address and canonicalized request data - only computed once a partial
+/// bucket is hot enough to be worth confirming as a true duplicate.
+/// Combines two independently-seeded 64-bit hashes rather than pulling in
+/// a dedicated 128-bit hashing crate for one call site.
+fn full_fingerprint(
+    entity_id: &str,
+    path: &str,
+    operation: &str,
+    remote_address: &str,
+    request_data: &str,
+) -> u128 {
+    let mut low_hasher = DefaultHasher::new();
+    entity_id.hash(&mut low_hasher);
+    path.hash(&mut low_hasher);
+    operation.hash(&mut low_hasher);
+    remote_address.hash(&mut low_hasher);
+    request_data.hash(&mut low_hasher);
+    let low = low_hasher.finish();
+
+    let mut high_hasher = DefaultHasher::new();
+    0xA5A5_A5A5_A5A5_A5A5u64.hash(&mut high_hasher); // decorrelate from `low`
+    entity_id.hash(&mut high_hasher);
+    path.hash(&mut high_hasher);
+    operation.hash(&mut high_hasher);
+    remote_address.hash(&mut high_hasher);
+    request_data.hash(&mut high_hasher);
+    let high = high_hasher.finish();
+
+    ((high as u128) << 64) | low as u128
+}
+
+/// Render a request's `data` map as a deterministic string (sorted keys) so
+/// it can be folded into [`full_fingerprint`] regardless of `HashMap`
+/// iteration order.
+fn canonical_request_data(data: &Option<HashMap<String, serde_json::Value>>) -> String {
+    let Some(map) = data else {
+        return String::new();
+    };
+    let mut keys: Vec<&String> = map.keys().collect();
+    keys.sort();
+    keys.iter()
+        .map(|k| format!("{}={}", k, map[*k]))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
 /// Results from processing a single file
 #[derive(Debug)]
 struct FileAnalysisResult {
@@ -75,10 +391,22 @@ struct FileAnalysisResult {
     path_prefixes: HashMap<String, usize>,
     entity_paths: HashMap<String, HashMap<String, usize>>,
     entity_names: HashMap<String, String>,
+    bucket_stats: HashMap<i64, BucketStats>,
+    dup_tracker: DuplicateTracker,
 }
 
+/// Report progress to `progress` every this many lines, batching the bytes
+/// consumed since the last report rather than calling `inc` per line.
+const PROGRESS_REPORT_LINES: usize = 1000;
+
 /// Process entries from a single file using streaming to reduce memory usage
-fn process_file_entries_streaming(file_path: &str) -> Result<FileAnalysisResult> {
+fn process_file_entries_streaming(
+    file_path: &str,
+    progress: &dyn crate::utils::progress::Progress,
+    interval_secs: Option<u64>,
+    dup_threshold: u32,
+    approx: bool,
+) -> Result<(FileAnalysisResult, crate::utils::parallel::FileMetrics)> {
     use crate::utils::reader::open_file;
     use std::io::{BufRead, BufReader};
 
@@ -87,23 +415,44 @@ fn process_file_entries_streaming(file_path: &str) -> Result<FileAnalysisResult>
     let mut path_prefixes: HashMap<String, usize> = HashMap::with_capacity(100);
     let mut entity_paths: HashMap<String, HashMap<String, usize>> = HashMap::with_capacity(2000);
     let mut entity_names: HashMap<String, String> = HashMap::with_capacity(2000);
+    let mut bucket_stats: HashMap<i64, BucketStats> = HashMap::new();
+    let mut dup_tracker = DuplicateTracker::default();
+    let mut file_metrics = crate::utils::parallel::FileMetrics {
+        bytes_read: std::fs::metadata(file_path).map(|m| m.len()).unwrap_or(0),
+        ..Default::default()
+    };
+    let mut bytes_since_report: usize = 0;
+    let mut lines_since_report: usize = 0;
 
     let file = open_file(file_path)?;
     let reader = BufReader::new(file);
 
     for line_result in reader.lines() {
         let line = line_result?;
+        bytes_since_report += line.len() + 1;
+        lines_since_report += 1;
+        if lines_since_report >= PROGRESS_REPORT_LINES {
+            progress.inc(bytes_since_report);
+            bytes_since_report = 0;
+            lines_since_report = 0;
+        }
 
         // Skip empty lines
         if line.trim().is_empty() {
             continue;
         }
 
+        file_metrics.lines_read += 1;
+
         // Parse JSON entry
         let entry: AuditEntry = match serde_json::from_str(&line) {
             Ok(entry) => entry,
-            Err(_) => continue, // Skip invalid JSON lines
+            Err(_) => {
+                file_metrics.parse_failures += 1;
+                continue; // Skip invalid JSON lines
+            }
         };
+        file_metrics.entries_parsed += 1;
 
         let Some(request) = &entry.request else {
             continue;
@@ -138,13 +487,13 @@ fn process_file_entries_streaming(file_path: &str) -> Result<FileAnalysisResult>
         // Track by full path
         let path_data = path_operations
             .entry(path.to_string())
-            .or_insert_with(PathData::new);
+            .or_insert_with(|| PathData::new(approx));
         path_data.count += 1;
         *path_data
             .operations
             .entry(operation.to_string())
             .or_insert(0) += 1;
-        path_data.entities.insert(entity_id.to_string());
+        path_data.entities.insert(entity_id);
 
         // Track by operation type
         *operation_types.entry(operation.to_string()).or_insert(0) += 1;
@@ -166,24 +515,74 @@ fn process_file_entries_streaming(file_path: &str) -> Result<FileAnalysisResult>
         entity_names
             .entry(entity_id.to_string())
             .or_insert_with(|| display_name.to_string());
+
+        if let Some(interval_secs) = interval_secs {
+            if let Some(bucket) = bucket_start(&entry.time, interval_secs) {
+                let stats = bucket_stats.entry(bucket).or_default();
+                stats.operations += 1;
+                stats.unique_entities.insert(entity_id.to_string());
+                if entry.error.is_some() {
+                    stats.errors += 1;
+                }
+            }
+        }
+
+        // Two-tier duplicate/replay fingerprinting: always bump the cheap
+        // partial counter, only promote to a full hash once it's hot.
+        let partial = partial_fingerprint(entity_id, path, operation);
+        let partial_count = dup_tracker.partial_counts.entry(partial).or_insert(0);
+        *partial_count += 1;
+        if *partial_count > dup_threshold {
+            let remote_address = request.remote_address.as_deref().unwrap_or("");
+            let request_data = canonical_request_data(&request.data);
+            let full = full_fingerprint(entity_id, path, operation, remote_address, &request_data);
+            let group = dup_tracker
+                .full_groups
+                .entry(full)
+                .or_insert_with(|| DuplicateGroup {
+                    entity_id: entity_id.to_string(),
+                    path: path.to_string(),
+                    operation: operation.to_string(),
+                    remote_address: remote_address.to_string(),
+                    count: 0,
+                });
+            group.count += 1;
+        }
     }
 
-    Ok(FileAnalysisResult {
-        path_operations,
-        operation_types,
-        path_prefixes,
-        entity_paths,
-        entity_names,
-    })
+    if bytes_since_report > 0 {
+        progress.inc(bytes_since_report);
+    }
+
+    Ok((
+        FileAnalysisResult {
+            path_operations,
+            operation_types,
+            path_prefixes,
+            entity_paths,
+            entity_names,
+            bucket_stats,
+            dup_tracker,
+        },
+        file_metrics,
+    ))
 }
 
 /// Process entries from a single file (original non-streaming version)
-fn process_file_entries(_file_path: &str, entries: Vec<AuditEntry>) -> FileAnalysisResult {
+fn process_file_entries(
+    _file_path: &str,
+    entries: Vec<AuditEntry>,
+    interval_secs: Option<u64>,
+    dup_threshold: u32,
+    approx: bool,
+) -> FileAnalysisResult {
     let mut path_operations: HashMap<String, PathData> = HashMap::with_capacity(5000);
     let mut operation_types: HashMap<String, usize> = HashMap::with_capacity(20);
     let mut path_prefixes: HashMap<String, usize> = HashMap::with_capacity(100);
     let mut entity_paths: HashMap<String, HashMap<String, usize>> = HashMap::with_capacity(2000);
     let mut entity_names: HashMap<String, String> = HashMap::with_capacity(2000);
+    let mut bucket_stats: HashMap<i64, BucketStats> = HashMap::new();
+    let mut dup_tracker = DuplicateTracker::default();
 
     for entry in entries {
         let Some(request) = &entry.request else {
@@ -219,13 +618,13 @@ fn process_file_entries(_file_path: &str, entries: Vec<AuditEntry>) -> FileAnaly
         // Track by full path
         let path_data = path_operations
             .entry(path.to_string())
-            .or_insert_with(PathData::new);
+            .or_insert_with(|| PathData::new(approx));
         path_data.count += 1;
         *path_data
             .operations
             .entry(operation.to_string())
             .or_insert(0) += 1;
-        path_data.entities.insert(entity_id.to_string());
+        path_data.entities.insert(entity_id);
 
         // Track by operation type
         *operation_types.entry(operation.to_string()).or_insert(0) += 1;
@@ -247,6 +646,37 @@ fn process_file_entries(_file_path: &str, entries: Vec<AuditEntry>) -> FileAnaly
         entity_names
             .entry(entity_id.to_string())
             .or_insert_with(|| display_name.to_string());
+
+        if let Some(interval_secs) = interval_secs {
+            if let Some(bucket) = bucket_start(&entry.time, interval_secs) {
+                let stats = bucket_stats.entry(bucket).or_default();
+                stats.operations += 1;
+                stats.unique_entities.insert(entity_id.to_string());
+                if entry.error.is_some() {
+                    stats.errors += 1;
+                }
+            }
+        }
+
+        let partial = partial_fingerprint(entity_id, path, operation);
+        let partial_count = dup_tracker.partial_counts.entry(partial).or_insert(0);
+        *partial_count += 1;
+        if *partial_count > dup_threshold {
+            let remote_address = request.remote_address.as_deref().unwrap_or("");
+            let request_data = canonical_request_data(&request.data);
+            let full = full_fingerprint(entity_id, path, operation, remote_address, &request_data);
+            let group = dup_tracker
+                .full_groups
+                .entry(full)
+                .or_insert_with(|| DuplicateGroup {
+                    entity_id: entity_id.to_string(),
+                    path: path.to_string(),
+                    operation: operation.to_string(),
+                    remote_address: remote_address.to_string(),
+                    count: 0,
+                });
+            group.count += 1;
+        }
     }
 
     FileAnalysisResult {
@@ -255,6 +685,8 @@ fn process_file_entries(_file_path: &str, entries: Vec<AuditEntry>) -> FileAnaly
         path_prefixes,
         entity_paths,
         entity_names,
+        bucket_stats,
+        dup_tracker,
     }
 }
 
@@ -268,6 +700,8 @@ fn combine_results(
         path_prefixes: HashMap::with_capacity(100),
         entity_paths: HashMap::with_capacity(2000),
         entity_names: HashMap::with_capacity(2000),
+        bucket_stats: HashMap::new(),
+        dup_tracker: DuplicateTracker::default(),
     };
 
     for file_result in results {
@@ -280,7 +714,7 @@ fn combine_results(
                 for (op, count) in path_data.operations {
                     *existing.operations.entry(op).or_insert(0) += count;
                 }
-                existing.entities.extend(path_data.entities);
+                existing.entities.merge(path_data.entities);
             } else {
                 combined.path_operations.insert(path, path_data);
             }
@@ -308,19 +742,57 @@ fn combine_results(
         for (entity_id, name) in result.entity_names {
             combined.entity_names.entry(entity_id).or_insert(name);
         }
+
+        // Merge bucket stats
+        for (bucket, stats) in result.bucket_stats {
+            let existing = combined.bucket_stats.entry(bucket).or_default();
+            existing.operations += stats.operations;
+            existing.unique_entities.extend(stats.unique_entities);
+            existing.errors += stats.errors;
+        }
+
+        // Merge duplicate-fingerprint tracking
+        for (hash, count) in result.dup_tracker.partial_counts {
+            *combined
+                .dup_tracker
+                .partial_counts
+                .entry(hash)
+                .or_insert(0) += count;
+        }
+        for (hash, group) in result.dup_tracker.full_groups {
+            let existing = combined
+                .dup_tracker
+                .full_groups
+                .entry(hash)
+                .or_insert_with(|| DuplicateGroup {
+                    entity_id: group.entity_id.clone(),
+                    path: group.path.clone(),
+                    operation: group.operation.clone(),
+                    remote_address: group.remote_address.clone(),
+                    count: 0,
+                });
+            existing.count += group.count;
+        }
     }
 
     combined
 }
 
 /// Sequential processing fallback for compatibility and single files
-fn run_sequential(log_files: &[String]) -> Result<(FileAnalysisResult, usize)> {
+fn run_sequential(
+    log_files: &[String],
+    interval_secs: Option<u64>,
+    dup_threshold: u32,
+    approx: bool,
+) -> Result<(FileAnalysisResult, usize)> {
     let mut combined_result = FileAnalysisResult {
         path_operations: HashMap::with_capacity(5000),
         operation_types: HashMap::with_capacity(20),
         path_prefixes: HashMap::with_capacity(100),
         entity_paths: HashMap::with_capacity(2000),
         entity_names: HashMap::with_capacity(2000),
+        bucket_stats: HashMap::new(),
+        dup_tracker: DuplicateTracker::default(),
     };
     let mut total_lines = 0;
 
@@ -376,7 +848,8 @@ fn run_sequential(log_files: &[String]) -> Result<(FileAnalysisResult, usize)> {
         ));
 
         // Process the entries from this file and merge directly
-        let file_result = process_file_entries(log_file, entries);
+        let file_result =
+            process_file_entries(log_file, entries, interval_secs, dup_threshold, approx);
 
         // Merge path operations
         for (path, path_data) in file_result.path_operations {
@@ -385,7 +858,7 @@ fn run_sequential(log_files: &[String]) -> Result<(FileAnalysisResult, usize)> {
                 for (op, count) in path_data.operations {
                     *existing.operations.entry(op).or_insert(0) += count;
                 }
-                existing.entities.extend(path_data.entities);
+                existing.entities.merge(path_data.entities);
             } else {
                 combined_result.path_operations.insert(path, path_data);
             }
@@ -416,68 +889,349 @@ fn run_sequential(log_files: &[String]) -> Result<(FileAnalysisResult, usize)> {
                 .entry(entity_id)
                 .or_insert(name);
         }
+
+        // Merge bucket stats
+        for (bucket, stats) in file_result.bucket_stats {
+            let existing = combined_result.bucket_stats.entry(bucket).or_default();
+            existing.operations += stats.operations;
+            existing.unique_entities.extend(stats.unique_entities);
+            existing.errors += stats.errors;
+        }
+
+        // Merge duplicate-fingerprint tracking
+        for (hash, count) in file_result.dup_tracker.partial_counts {
+            *combined_result
+                .dup_tracker
+                .partial_counts
+                .entry(hash)
+                .or_insert(0) += count;
+        }
+        for (hash, group) in file_result.dup_tracker.full_groups {
+            let existing = combined_result
+                .dup_tracker
+                .full_groups
+                .entry(hash)
+                .or_insert_with(|| DuplicateGroup {
+                    entity_id: group.entity_id.clone(),
+                    path: group.path.clone(),
+                    operation: group.operation.clone(),
+                    remote_address: group.remote_address.clone(),
+                    count: 0,
+                });
+            existing.count += group.count;
+        }
     }
 
     Ok((combined_result, total_lines))
 }
 
-pub fn run(
-    log_files: &[String],
+/// One row of table 1 (`operation_types`): an operation type's share of all
+/// operations observed.
+#[derive(Debug, Serialize)]
+struct OperationTypeSummary {
+    operation: String,
+    count: usize,
+    percentage: f64,
+}
+
+/// One row of table 2 (`top_path_prefixes`): a path's first two components
+/// and its share of all operations observed.
+#[derive(Debug, Serialize)]
+struct PathPrefixSummary {
+    prefix: String,
+    count: usize,
+    percentage: f64,
+}
+
+/// One row of table 3 (`top_paths`): [`PathData`] flattened into a
+/// serializable shape, with `operations` narrowed to the single dominant
+/// operation type (the fixed-width table only ever showed one "Top Op"
+/// column).
+#[derive(Debug, Serialize)]
+struct PathSummary {
+    path: String,
+    count: usize,
+    entities: usize,
+    operations: String,
+}
+
+/// One row of table 4 (`top_entities`).
+#[derive(Debug, Serialize)]
+struct EntitySummary {
+    display_name: String,
+    entity_id: String,
+    total_operations: usize,
+}
+
+/// One row of table 5 (`stress_points`): an entity/path pair both over
+/// `min_operations`.
+#[derive(Debug, Serialize)]
+struct StressPointSummary {
+    entity_name: String,
+    path: String,
+    operations: usize,
+}
+
+/// One row of the optional 6th section: a single `--interval`-wide bucket.
+#[derive(Debug, Clone, Serialize)]
+struct BucketSummary {
+    bucket_start: String,
+    operations: usize,
+    unique_entities: usize,
+    errors: usize,
+    ops_per_sec: f64,
+}
+
+/// The optional time-series trend section, present only when `--interval`
+/// was passed. Buckets are sorted chronologically; `peak`/`trough` are the
+/// same rows picked out by operation count so capacity-planning users don't
+/// have to scan the whole list.
+#[derive(Debug, Serialize)]
+struct TrendSummary {
+    interval_secs: u64,
+    buckets: Vec<BucketSummary>,
+    peak: Option<BucketSummary>,
+    trough: Option<BucketSummary>,
+}
+
+/// One row of the duplicate/replay section: a confirmed full-hash group.
+#[derive(Debug, Serialize)]
+struct DuplicateGroupSummary {
+    entity_id: String,
+    path: String,
+    operation: String,
+    remote_address: String,
+    count: u32,
+}
+
+/// The full structured document emitted by `--format json`/`--format csv`,
+/// and the data the `--format text` tables are rendered from - built once so
+/// every format sees exactly the same aggregates.
+#[derive(Debug, Serialize)]
+struct OverviewReport {
+    total_lines: usize,
+    total_operations: usize,
+    operation_types: Vec<OperationTypeSummary>,
+    top_path_prefixes: Vec<PathPrefixSummary>,
+    top_paths: Vec<PathSummary>,
+    top_entities: Vec<EntitySummary>,
+    stress_points: Vec<StressPointSummary>,
+    trend: Option<TrendSummary>,
+    duplicates: Vec<DuplicateGroupSummary>,
+}
+
+/// Aggregate the raw per-file maps into the top-`top` rows of every table,
+/// applying `min_operations` the same way the original text-only report did:
+/// table 3 stops once sorted (descending) counts drop below the threshold,
+/// and table 5 only considers entity/path pairs that both clear it.
+#[allow(clippy::too_many_arguments)]
+fn build_report(
+    path_operations: &HashMap<String, PathData>,
+    operation_types: &HashMap<String, usize>,
+    path_prefixes: &HashMap<String, usize>,
+    entity_paths: &HashMap<String, HashMap<String, usize>>,
+    entity_names: &HashMap<String, String>,
+    bucket_stats: &HashMap<i64, BucketStats>,
+    interval_secs: Option<u64>,
+    dup_tracker: &DuplicateTracker,
     top: usize,
     min_operations: usize,
-    sequential: bool,
-) -> Result<()> {
-    let (combined_result, total_lines) = if sequential || log_files.len() == 1 {
-        // Use sequential processing for single files or when explicitly requested
-        eprintln!("Processing {} files sequentially...", log_files.len());
-        run_sequential(log_files)?
-    } else {
-        // Use parallel processing for multiple files with streaming
-        process_files_parallel(log_files, process_file_entries_streaming, combine_results)?
+    total_lines: usize,
+) -> OverviewReport {
+    let total_operations: usize = operation_types.values().sum();
+    let pct_of_total = |count: usize| {
+        if total_operations > 0 {
+            count as f64 / total_operations as f64 * 100.0
+        } else {
+            0.0
+        }
     };
 
-    eprintln!("\nTotal: Processed {} lines", format_number(total_lines));
+    let mut sorted_ops: Vec<_> = operation_types.iter().collect();
+    sorted_ops.sort_by(|a, b| b.1.cmp(a.1));
+    let operation_type_rows = sorted_ops
+        .into_iter()
+        .map(|(op, count)| OperationTypeSummary {
+            operation: op.clone(),
+            count: *count,
+            percentage: pct_of_total(*count),
+        })
+        .collect();
+
+    let mut sorted_prefixes: Vec<_> = path_prefixes.iter().collect();
+    sorted_prefixes.sort_by(|a, b| b.1.cmp(a.1));
+    let top_path_prefixes = sorted_prefixes
+        .into_iter()
+        .take(top)
+        .map(|(prefix, count)| PathPrefixSummary {
+            prefix: prefix.clone(),
+            count: *count,
+            percentage: pct_of_total(*count),
+        })
+        .collect();
 
-    let path_operations = combined_result.path_operations;
-    let operation_types = combined_result.operation_types;
-    let path_prefixes = combined_result.path_prefixes;
-    let entity_paths = combined_result.entity_paths;
-    let entity_names = combined_result.entity_names;
+    let mut sorted_paths: Vec<_> = path_operations.iter().collect();
+    sorted_paths.sort_by(|a, b| b.1.count.cmp(&a.1.count));
+    let top_paths = sorted_paths
+        .into_iter()
+        .take(top)
+        .take_while(|(_, data)| data.count >= min_operations)
+        .map(|(path, data)| {
+            let dominant_operation = data
+                .operations
+                .iter()
+                .max_by_key(|x| x.1)
+                .map_or("N/A", |x| x.0.as_str())
+                .to_string();
+            PathSummary {
+                path: path.clone(),
+                count: data.count,
+                entities: data.entities.unique_count(),
+                operations: dominant_operation,
+            }
+        })
+        .collect();
 
-    let total_operations: usize = operation_types.values().sum();
+    let mut entity_totals: HashMap<String, usize> = HashMap::with_capacity(entity_paths.len());
+    for (entity_id, paths) in entity_paths {
+        entity_totals.insert(entity_id.clone(), paths.values().sum());
+    }
+    let mut sorted_entities: Vec<_> = entity_totals.iter().collect();
+    sorted_entities.sort_by(|a, b| b.1.cmp(a.1));
+    let top_entities = sorted_entities
+        .into_iter()
+        .take(top)
+        .map(|(entity_id, total)| EntitySummary {
+            display_name: entity_names
+                .get(entity_id)
+                .cloned()
+                .unwrap_or_else(|| "N/A".to_string()),
+            entity_id: entity_id.clone(),
+            total_operations: *total,
+        })
+        .collect();
+
+    let mut stress_points = Vec::new();
+    for (path, data) in path_operations {
+        if data.count < min_operations {
+            continue;
+        }
+        // Approx mode never retains individual entity IDs, so stress-point
+        // detection (which needs per-entity per-path counts) is skipped for
+        // paths tracked with a sketch.
+        let Some(entity_ids) = data.entities.exact_ids() else {
+            continue;
+        };
+        for entity_id in entity_ids {
+            let Some(entity_ops_map) = entity_paths.get(entity_id) else {
+                continue;
+            };
+            let Some(&entity_ops) = entity_ops_map.get(path) else {
+                continue;
+            };
+            if entity_ops >= min_operations {
+                stress_points.push(StressPointSummary {
+                    entity_name: entity_names
+                        .get(entity_id)
+                        .cloned()
+                        .unwrap_or_else(|| "N/A".to_string()),
+                    path: path.clone(),
+                    operations: entity_ops,
+                });
+            }
+        }
+    }
+    stress_points.sort_by(|a, b| b.operations.cmp(&a.operations));
+    stress_points.truncate(top);
+
+    let trend = interval_secs.map(|interval_secs| {
+        let mut sorted_buckets: Vec<_> = bucket_stats.iter().collect();
+        sorted_buckets.sort_by_key(|(bucket, _)| **bucket);
+        let buckets: Vec<BucketSummary> = sorted_buckets
+            .into_iter()
+            .map(|(bucket, stats)| BucketSummary {
+                bucket_start: chrono::DateTime::from_timestamp(*bucket, 0)
+                    .map(|dt| dt.to_rfc3339())
+                    .unwrap_or_else(|| bucket.to_string()),
+                operations: stats.operations,
+                unique_entities: stats.unique_entities.len(),
+                errors: stats.errors,
+                ops_per_sec: stats.operations as f64 / interval_secs as f64,
+            })
+            .collect();
+
+        let peak = buckets
+            .iter()
+            .max_by_key(|b| b.operations)
+            .cloned();
+        let trough = buckets
+            .iter()
+            .min_by_key(|b| b.operations)
+            .cloned();
+
+        TrendSummary {
+            interval_secs,
+            buckets,
+            peak,
+            trough,
+        }
+    });
+
+    let mut sorted_groups: Vec<_> = dup_tracker.full_groups.values().collect();
+    sorted_groups.sort_by(|a, b| b.count.cmp(&a.count));
+    let duplicates = sorted_groups
+        .into_iter()
+        .take(top)
+        .map(|group| DuplicateGroupSummary {
+            entity_id: group.entity_id.clone(),
+            path: group.path.clone(),
+            operation: group.operation.clone(),
+            remote_address: group.remote_address.clone(),
+            count: group.count,
+        })
+        .collect();
+
+    OverviewReport {
+        total_lines,
+        total_operations,
+        operation_types: operation_type_rows,
+        top_path_prefixes,
+        top_paths,
+        top_entities,
+        stress_points,
+        trend,
+        duplicates,
+    }
+}
 
-    // Print results
+/// Render `report` as the five fixed-width ASCII tables this command has
+/// always printed, with `top` only used in section headings (the rows
+/// themselves were already trimmed to `top` by [`build_report`]).
+fn print_text_report(report: &OverviewReport, top: usize) {
     println!("\n{}", "=".repeat(100));
     println!("High-Volume Vault Operations Analysis");
     println!("{}", "=".repeat(100));
 
-    // 1. Operation Types Summary
     println!("\n1. Operation Types (Overall)");
     println!("{}", "-".repeat(100));
     println!("{:<20} {:>15} {:>12}", "Operation", "Count", "Percentage");
     println!("{}", "-".repeat(100));
-
-    let mut sorted_ops: Vec<_> = operation_types.iter().collect();
-    sorted_ops.sort_by(|a, b| b.1.cmp(a.1));
-
-    for (op, count) in sorted_ops {
-        let pct = if total_operations > 0 {
-            (*count as f64 / total_operations as f64) * 100.0
-        } else {
-            0.0
-        };
-        println!("{:<20} {:>15} {:>11.2}%", op, format_number(*count), pct);
+    for op in &report.operation_types {
+        println!(
+            "{:<20} {:>15} {:>11.2}%",
+            op.operation,
+            format_number(op.count),
+            op.percentage
+        );
     }
-
     println!("{}", "-".repeat(100));
     println!(
         "{:<20} {:>15} {:>11.2}%",
         "TOTAL",
-        format_number(total_operations),
+        format_number(report.total_operations),
         100.0
     );
 
-    // 2. Top Path Prefixes
     println!("\n2. Top Path Prefixes (First 2 components)");
     println!("{}", "-".repeat(100));
     println!(
@@ -485,25 +1239,15 @@ pub fn run(
         "Path Prefix", "Operations", "Percentage"
     );
     println!("{}", "-".repeat(100));
-
-    let mut sorted_prefixes: Vec<_> = path_prefixes.iter().collect();
-    sorted_prefixes.sort_by(|a, b| b.1.cmp(a.1));
-
-    for (prefix, count) in sorted_prefixes.iter().take(top) {
-        let pct = if total_operations > 0 {
-            (**count as f64 / total_operations as f64) * 100.0
-        } else {
-            0.0
-        };
+    for prefix in &report.top_path_prefixes {
         println!(
             "{:<40} {:>15} {:>11.2}%",
-            prefix,
-            format_number(**count),
-            pct
+            prefix.prefix,
+            format_number(prefix.count),
+            prefix.percentage
         );
     }
 
-    // 3. Top Individual Paths
     println!("\n3. Top {} Individual Paths (Highest Volume)", top);
     println!("{}", "-".repeat(100));
     println!(
@@ -511,34 +1255,21 @@ pub fn run(
         "Path", "Ops", "Entities", "Top Op"
     );
     println!("{}", "-".repeat(100));
-
-    let mut sorted_paths: Vec<_> = path_operations.iter().collect();
-    sorted_paths.sort_by(|a, b| b.1.count.cmp(&a.1.count));
-
-    for (path, data) in sorted_paths.iter().take(top) {
-        if data.count < min_operations {
-            break;
-        }
-        let top_op = data
-            .operations
-            .iter()
-            .max_by_key(|x| x.1)
-            .map_or("N/A", |x| x.0.as_str());
-        let path_display = if path.len() > 60 {
-            format!("{}...", &path[..58])
+    for path in &report.top_paths {
+        let path_display = if path.path.len() > 60 {
+            format!("{}...", &path.path[..58])
         } else {
-            (*path).to_string()
+            path.path.clone()
         };
         println!(
             "{:<60} {:>10} {:>10} {:>15}",
             path_display,
-            format_number(data.count),
-            format_number(data.entities.len()),
-            top_op
+            format_number(path.count),
+            format_number(path.entities),
+            path.operations
         );
     }
 
-    // 4. Top Entities by Total Operations
     println!("\n4. Top {} Entities by Total Operations", top);
     println!("{}", "-".repeat(100));
     println!(
@@ -546,74 +1277,30 @@ pub fn run(
         "Display Name", "Entity ID", "Total Ops"
     );
     println!("{}", "-".repeat(100));
-
-    let mut entity_totals: HashMap<String, usize> = HashMap::with_capacity(entity_paths.len());
-    for (entity_id, paths) in &entity_paths {
-        let total: usize = paths.values().sum();
-        entity_totals.insert(entity_id.clone(), total);
-    }
-
-    let mut sorted_entities: Vec<_> = entity_totals.iter().collect();
-    sorted_entities.sort_by(|a, b| b.1.cmp(a.1));
-
-    for (entity_id, total) in sorted_entities.iter().take(top) {
-        let name = entity_names
-            .get(*entity_id)
-            .map_or("N/A", std::string::String::as_str);
-        let name_display = if name.len() > 48 { &name[..48] } else { name };
-        let entity_short = if entity_id.len() > 36 {
-            &entity_id[..36]
+    for entity in &report.top_entities {
+        let name_display = if entity.display_name.len() > 48 {
+            &entity.display_name[..48]
         } else {
-            entity_id
+            &entity.display_name
+        };
+        let entity_short = if entity.entity_id.len() > 36 {
+            &entity.entity_id[..36]
+        } else {
+            &entity.entity_id
         };
         println!(
             "{:<50} {:<38} {:>10}",
             name_display,
             entity_short,
-            format_number(**total)
+            format_number(entity.total_operations)
         );
     }
 
-    // 5. Potential Stress Points
     println!("\n5. Potential System Stress Points");
     println!("{}", "-".repeat(100));
-
-    #[derive(Debug)]
-    struct StressPoint {
-        path: String,
-        entity_name: String,
-        operations: usize,
-    }
-
-    let mut stress_points = Vec::new();
-
-    for (path, data) in &path_operations {
-        if data.count >= min_operations {
-            for entity_id in &data.entities {
-                if let Some(entity_ops_map) = entity_paths.get(entity_id) {
-                    if let Some(&entity_ops) = entity_ops_map.get(path) {
-                        if entity_ops >= min_operations {
-                            stress_points.push(StressPoint {
-                                path: path.clone(),
-                                entity_name: entity_names
-                                    .get(entity_id)
-                                    .cloned()
-                                    .unwrap_or_else(|| "N/A".to_string()),
-                                operations: entity_ops,
-                            });
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    stress_points.sort_by(|a, b| b.operations.cmp(&a.operations));
-
     println!("{:<40} {:<40} {:>10}", "Entity", "Path", "Ops");
     println!("{}", "-".repeat(100));
-
-    for sp in stress_points.iter().take(top) {
+    for sp in &report.stress_points {
         let entity_display = if sp.entity_name.len() > 38 {
             &sp.entity_name[..38]
         } else {
@@ -632,10 +1319,279 @@ pub fn run(
         );
     }
 
+    if let Some(trend) = &report.trend {
+        println!(
+            "\n6. Time-Series Trend ({}s buckets)",
+            trend.interval_secs
+        );
+        println!("{}", "-".repeat(100));
+        println!(
+            "{:<25} {:>12} {:>10} {:>10} {:>12}",
+            "Bucket Start", "Ops", "Entities", "Errors", "Ops/sec"
+        );
+        println!("{}", "-".repeat(100));
+        for bucket in &trend.buckets {
+            println!(
+                "{:<25} {:>12} {:>10} {:>10} {:>12.2}",
+                bucket.bucket_start,
+                format_number(bucket.operations),
+                format_number(bucket.unique_entities),
+                format_number(bucket.errors),
+                bucket.ops_per_sec
+            );
+        }
+        println!("{}", "-".repeat(100));
+        if let Some(peak) = &trend.peak {
+            println!(
+                "Peak interval:   {} ({} ops)",
+                peak.bucket_start,
+                format_number(peak.operations)
+            );
+        }
+        if let Some(trough) = &trend.trough {
+            println!(
+                "Trough interval: {} ({} ops)",
+                trough.bucket_start,
+                format_number(trough.operations)
+            );
+        }
+    }
+
+    if !report.duplicates.is_empty() {
+        println!("\n7. Potential Duplicate/Replayed Requests");
+        println!("{}", "-".repeat(100));
+        println!(
+            "{:<40} {:<40} {:<10} {:>10}",
+            "Entity", "Path", "Operation", "Count"
+        );
+        println!("{}", "-".repeat(100));
+        for dup in &report.duplicates {
+            let entity_display = if dup.entity_id.len() > 38 {
+                &dup.entity_id[..38]
+            } else {
+                &dup.entity_id
+            };
+            let path_display = if dup.path.len() > 38 {
+                &dup.path[..38]
+            } else {
+                &dup.path
+            };
+            println!(
+                "{:<40} {:<40} {:<10} {:>10}",
+                entity_display,
+                path_display,
+                dup.operation,
+                format_number(dup.count as usize)
+            );
+        }
+    }
+
     println!("{}", "=".repeat(100));
-    println!("\nTotal Lines Processed: {}", format_number(total_lines));
-    println!("Total Operations: {}", format_number(total_operations));
+    println!("\nTotal Lines Processed: {}", format_number(report.total_lines));
+    println!("Total Operations: {}", format_number(report.total_operations));
     println!("{}", "=".repeat(100));
+}
+
+/// Serialize `report` as pretty-printed JSON to stdout.
+fn print_json_report(report: &OverviewReport) -> Result<()> {
+    let json = serde_json::to_string_pretty(report)?;
+    println!("{}", json);
+    Ok(())
+}
+
+/// Write `report` to stdout as one CSV section per table, each preceded by
+/// a `# <section>` comment line so a human can still tell the tables apart
+/// while a downstream parser can split on the `#` lines.
+fn print_csv_report(report: &OverviewReport) -> Result<()> {
+    println!("# operation_types");
+    let mut writer = csv::Writer::from_writer(std::io::stdout());
+    writer.write_record(["operation", "count", "percentage"])?;
+    for op in &report.operation_types {
+        writer.write_record([op.operation.as_str(), &op.count.to_string(), &format!("{:.2}", op.percentage)])?;
+    }
+    writer.flush()?;
+
+    println!("\n# top_path_prefixes");
+    let mut writer = csv::Writer::from_writer(std::io::stdout());
+    writer.write_record(["prefix", "count", "percentage"])?;
+    for prefix in &report.top_path_prefixes {
+        writer.write_record([prefix.prefix.as_str(), &prefix.count.to_string(), &format!("{:.2}", prefix.percentage)])?;
+    }
+    writer.flush()?;
+
+    println!("\n# top_paths");
+    let mut writer = csv::Writer::from_writer(std::io::stdout());
+    writer.write_record(["path", "count", "entities", "dominant_operation"])?;
+    for path in &report.top_paths {
+        writer.write_record([
+            path.path.as_str(),
+            &path.count.to_string(),
+            &path.entities.to_string(),
+            path.operations.as_str(),
+        ])?;
+    }
+    writer.flush()?;
+
+    println!("\n# top_entities");
+    let mut writer = csv::Writer::from_writer(std::io::stdout());
+    writer.write_record(["display_name", "entity_id", "total_operations"])?;
+    for entity in &report.top_entities {
+        writer.write_record([
+            entity.display_name.as_str(),
+            entity.entity_id.as_str(),
+            &entity.total_operations.to_string(),
+        ])?;
+    }
+    writer.flush()?;
+
+    println!("\n# stress_points");
+    let mut writer = csv::Writer::from_writer(std::io::stdout());
+    writer.write_record(["entity_name", "path", "operations"])?;
+    for sp in &report.stress_points {
+        writer.write_record([sp.entity_name.as_str(), sp.path.as_str(), &sp.operations.to_string()])?;
+    }
+    writer.flush()?;
+
+    if let Some(trend) = &report.trend {
+        println!("\n# trend");
+        let mut writer = csv::Writer::from_writer(std::io::stdout());
+        writer.write_record(["bucket_start", "operations", "unique_entities", "errors", "ops_per_sec"])?;
+        for bucket in &trend.buckets {
+            writer.write_record([
+                bucket.bucket_start.as_str(),
+                &bucket.operations.to_string(),
+                &bucket.unique_entities.to_string(),
+                &bucket.errors.to_string(),
+                &format!("{:.2}", bucket.ops_per_sec),
+            ])?;
+        }
+        writer.flush()?;
+    }
+
+    if !report.duplicates.is_empty() {
+        println!("\n# duplicates");
+        let mut writer = csv::Writer::from_writer(std::io::stdout());
+        writer.write_record(["entity_id", "path", "operation", "remote_address", "count"])?;
+        for dup in &report.duplicates {
+            writer.write_record([
+                dup.entity_id.as_str(),
+                dup.path.as_str(),
+                dup.operation.as_str(),
+                dup.remote_address.as_str(),
+                &dup.count.to_string(),
+            ])?;
+        }
+        writer.flush()?;
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    log_files: &[String],
+    top: usize,
+    min_operations: usize,
+    sequential: bool,
+    format: &str,
+    interval_secs: Option<u64>,
+    plot: bool,
+    plot_svg: Option<&str>,
+    dup_threshold: u32,
+    approx: bool,
+    stats: bool,
+) -> Result<()> {
+    let (combined_result, total_lines) = if sequential || log_files.len() == 1 {
+        // Use sequential processing for single files or when explicitly requested
+        eprintln!("Processing {} files sequentially...", log_files.len());
+        if stats {
+            eprintln!("(--stats is only collected for parallel processing; skipping)");
+        }
+        run_sequential(log_files, interval_secs, dup_threshold, approx)?
+    } else {
+        // Use parallel processing for multiple files with streaming
+        let (combined_result, total_lines, metrics) = process_files_parallel(
+            log_files,
+            |path, progress| {
+                process_file_entries_streaming(path, progress, interval_secs, dup_threshold, approx)
+            },
+            combine_results,
+        )?;
+        if stats {
+            metrics.report();
+        }
+        (combined_result, total_lines)
+    };
+
+    eprintln!("\nTotal: Processed {} lines", format_number(total_lines));
+
+    let report = build_report(
+        &combined_result.path_operations,
+        &combined_result.operation_types,
+        &combined_result.path_prefixes,
+        &combined_result.entity_paths,
+        &combined_result.entity_names,
+        &combined_result.bucket_stats,
+        interval_secs,
+        &combined_result.dup_tracker,
+        top,
+        min_operations,
+        total_lines,
+    );
+
+    match format {
+        "json" => print_json_report(&report)?,
+        "csv" => print_csv_report(&report)?,
+        _ => print_text_report(&report, top),
+    }
+
+    if plot {
+        print_plots(&report);
+    }
+
+    if let Some(svg_path) = plot_svg {
+        match &report.trend {
+            Some(trend) => {
+                let points: Vec<(String, f64)> = trend
+                    .buckets
+                    .iter()
+                    .map(|b| (b.bucket_start.clone(), b.operations as f64))
+                    .collect();
+                write_svg_line_chart(svg_path, "Operations per Interval", &points)?;
+                eprintln!("\nWrote SVG trend chart to {}", svg_path);
+            }
+            None => {
+                eprintln!("\n--plot-svg requires --interval; skipping SVG chart");
+            }
+        }
+    }
 
     Ok(())
 }
+
+/// Render the operation-type breakdown and top path prefixes as terminal
+/// bar-histograms, in addition to whatever `--format` already printed.
+fn print_plots(report: &OverviewReport) {
+    println!("\n{}", "=".repeat(100));
+    println!("Operation Types (bar chart)");
+    println!("{}", "-".repeat(100));
+    let op_items: Vec<(String, usize)> = report
+        .operation_types
+        .iter()
+        .map(|op| (op.operation.clone(), op.count))
+        .collect();
+    for line in bar_chart(&op_items, 40) {
+        println!("{}", line);
+    }
+
+    println!("\nTop Path Prefixes (bar chart)");
+    println!("{}", "-".repeat(100));
+    let prefix_items: Vec<(String, usize)> = report
+        .top_path_prefixes
+        .iter()
+        .map(|p| (p.prefix.clone(), p.count))
+        .collect();
+    for line in bar_chart(&prefix_items, 40) {
+        println!("{}", line);
+    }
+}
@@ -0,0 +1,379 @@
+//! Indicator-based threat matching across audit logs.
+//!
+//! Where [`crate::commands::token_lookup_abuse`] and
+//! [`crate::commands::entity_gaps`] bake in a single fixed heuristic each,
+//! `threat-scan` is a configurable detection engine: a security team ships
+//! their own indicator set (path globs, entity IDs, operation types, source
+//! IP CIDRs, or lookup-rate ceilings - see
+//! [`crate::audit::indicators::Indicator`]) and every audit entry is checked
+//! against it in one streaming pass, the same way a threat-intelligence feed
+//! is checked against network telemetry.
+//!
+//! # Usage
+//!
+//! ```bash
+//! # Scan logs against a JSON or CSV indicator set
+//! vault-audit threat-scan logs/*.log --rules indicators.json
+//!
+//! # Also write the sample matches kept per rule to a CSV
+//! vault-audit threat-scan logs/*.log --rules indicators.csv --output hits.csv
+//! ```
+//!
+//! # Indicators file
+//!
+//! A `.json` file is a JSON array of indicator objects; anything else is
+//! read as CSV with columns
+//! `name,severity,path_glob,entity_id,operation,source_cidr,max_lookups_per_hour`.
+//! Every predicate on an indicator is optional, and all predicates an
+//! indicator sets must match for it to hit (see
+//! [`crate::audit::indicators::Indicator`] for the exact semantics).
+//!
+//! # Output
+//!
+//! `--format table` (the default) prints a ranked summary of hit counts by
+//! rule (highest severity first, then by count), with up to
+//! `DEFAULT_SAMPLES_PER_RULE` matching entries shown per rule. `--format
+//! json` emits the same data as one document; `--format ndjson` streams one
+//! matched sample per line - see [`crate::utils::report`]. With `--output`,
+//! the kept samples - up to `DEFAULT_SAMPLES_PER_RULE` per rule, one row
+//! each, with the matched rule, severity, and entry details - are also
+//! written to CSV regardless of `--format`.
+
+use crate::audit::indicators::{load_indicators, CompiledIndicator};
+use crate::audit::types::AuditEntry;
+use crate::utils::format::format_number;
+use crate::utils::processor::{ProcessingMode, ProcessorBuilder};
+use crate::utils::report::{self, OutputFormat, Report};
+use crate::utils::time::parse_timestamp;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Number of sample matches kept per rule for the printed summary and CSV.
+const DEFAULT_SAMPLES_PER_RULE: usize = 5;
+
+/// One audit entry that matched an indicator, kept for display/export.
+#[derive(Debug, Clone, Serialize)]
+struct SampleHit {
+    time: String,
+    entity_id: String,
+    path: String,
+    operation: String,
+}
+
+#[derive(Debug, Clone, Default)]
+struct RuleHits {
+    count: usize,
+    samples: Vec<SampleHit>,
+}
+
+/// Tracks an entity's overall read/list rate, for indicators whose only
+/// predicate is `max_lookups_per_hour`.
+#[derive(Debug, Clone, Default)]
+struct EntityLookupStats {
+    count: usize,
+    first_seen: String,
+    last_seen: String,
+}
+
+impl EntityLookupStats {
+    fn record(&mut self, time: &str) {
+        self.count += 1;
+        if self.first_seen.is_empty() || time < self.first_seen.as_str() {
+            self.first_seen = time.to_string();
+        }
+        if time > self.last_seen.as_str() {
+            self.last_seen = time.to_string();
+        }
+    }
+
+    fn merge(mut self, other: Self) -> Self {
+        if other.first_seen.is_empty() {
+            return self;
+        }
+        if self.first_seen.is_empty() || other.first_seen < self.first_seen {
+            self.first_seen = other.first_seen;
+        }
+        if other.last_seen > self.last_seen {
+            self.last_seen = other.last_seen;
+        }
+        self.count += other.count;
+        self
+    }
+
+    fn lookups_per_hour(&self) -> f64 {
+        let span_hours = match (parse_timestamp(&self.first_seen), parse_timestamp(&self.last_seen))
+        {
+            (Ok(first), Ok(last)) => last.signed_duration_since(first).num_seconds() as f64 / 3600.0,
+            _ => 0.0,
+        };
+        if span_hours > 0.0 {
+            self.count as f64 / span_hours
+        } else {
+            self.count as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct ScanState {
+    /// indicator name -> hits against it
+    hits: HashMap<String, RuleHits>,
+    /// entity_id -> read/list rate stats, for rate-threshold indicators
+    entity_lookups: HashMap<String, EntityLookupStats>,
+}
+
+impl ScanState {
+    fn merge(mut self, other: Self) -> Self {
+        for (name, other_hits) in other.hits {
+            let hits = self.hits.entry(name).or_default();
+            hits.count += other_hits.count;
+            hits.samples.extend(other_hits.samples);
+            hits.samples.truncate(DEFAULT_SAMPLES_PER_RULE);
+        }
+        for (entity_id, other_stats) in other.entity_lookups {
+            let stats = self.entity_lookups.entry(entity_id).or_default();
+            *stats = std::mem::take(stats).merge(other_stats);
+        }
+        self
+    }
+}
+
+/// One matched indicator and its hit count, ranked by severity then count.
+#[derive(Debug, Clone, Serialize)]
+struct RuleSummary {
+    name: String,
+    severity: String,
+    count: usize,
+}
+
+/// One sample match, flattened for `--format json`/`ndjson` and the
+/// `--output` CSV - the same shape either way.
+#[derive(Debug, Clone, Serialize)]
+struct ThreatHitRow {
+    rule_name: String,
+    severity: String,
+    time: String,
+    entity_id: String,
+    path: String,
+    operation: String,
+}
+
+/// Full scan result: rule-level summary plus the sample rows every output
+/// format is built from.
+#[derive(Debug, Clone, Serialize)]
+struct ThreatScanReport {
+    indicator_count: usize,
+    total_lines: usize,
+    rules: Vec<RuleSummary>,
+    samples: Vec<ThreatHitRow>,
+}
+
+impl Report for ThreatScanReport {
+    type Row = ThreatHitRow;
+
+    fn command_name(&self) -> &'static str {
+        "threat-scan"
+    }
+
+    fn render_table(&self, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+        writeln!(w, "\n{}", "=".repeat(100))?;
+        writeln!(w, "Threat Scan Summary ({} indicator(s))", self.indicator_count)?;
+        writeln!(w, "{}", "=".repeat(100))?;
+
+        if self.rules.iter().all(|r| r.count == 0) {
+            writeln!(w, "\nNo indicator matches found.")?;
+        } else {
+            for rule in &self.rules {
+                if rule.count == 0 {
+                    continue;
+                }
+                writeln!(
+                    w,
+                    "\n[{}] {} - {} hit(s)",
+                    rule.severity.to_uppercase(),
+                    rule.name,
+                    format_number(rule.count)
+                )?;
+                let rule_samples: Vec<&ThreatHitRow> =
+                    self.samples.iter().filter(|row| row.rule_name == rule.name).collect();
+                for sample in &rule_samples {
+                    writeln!(
+                        w,
+                        "    {} entity={} path={} op={}",
+                        sample.time, sample.entity_id, sample.path, sample.operation
+                    )?;
+                }
+                if rule.count > rule_samples.len() {
+                    writeln!(w, "    ... and {} more", format_number(rule.count - rule_samples.len()))?;
+                }
+            }
+        }
+        writeln!(w, "\n{}", "=".repeat(100))?;
+        Ok(())
+    }
+
+    fn rows(&self) -> &[ThreatHitRow] {
+        &self.samples
+    }
+}
+
+pub fn run(log_files: &[String], rules: &str, output: Option<&str>, format: &str) -> Result<()> {
+    let format = OutputFormat::parse(format).map_err(anyhow::Error::msg)?;
+    let indicators = load_indicators(rules).with_context(|| format!("Failed to load indicators from {}", rules))?;
+    if indicators.is_empty() {
+        eprintln!("[WARN] No indicators loaded from {} - nothing to scan for.", rules);
+    }
+
+    let processor = ProcessorBuilder::new()
+        .mode(ProcessingMode::Auto)
+        .progress_label("Scanning".to_string())
+        .build();
+
+    let (result, stats) = processor.process_files_streaming(
+        log_files,
+        |entry: &AuditEntry, state: &mut ScanState| {
+            for indicator in &indicators {
+                if indicator.has_rate_threshold() {
+                    continue;
+                }
+                if indicator.matches_entry(entry) {
+                    let hits = state.hits.entry(indicator.name.clone()).or_default();
+                    hits.count += 1;
+                    if hits.samples.len() < DEFAULT_SAMPLES_PER_RULE {
+                        hits.samples.push(SampleHit {
+                            time: entry.time.clone(),
+                            entity_id: entry.entity_id().unwrap_or("").to_string(),
+                            path: entry.path().unwrap_or("").to_string(),
+                            operation: entry.operation().unwrap_or("").to_string(),
+                        });
+                    }
+                }
+            }
+
+            if indicators.iter().any(CompiledIndicator::has_rate_threshold) {
+                if let Some(op) = entry.operation() {
+                    if op == "read" || op == "list" {
+                        if let Some(entity_id) = entry.entity_id() {
+                            state
+                                .entity_lookups
+                                .entry(entity_id.to_string())
+                                .or_default()
+                                .record(&entry.time);
+                        }
+                    }
+                }
+            }
+        },
+        ScanState::merge,
+        ScanState::default(),
+    )?;
+
+    eprintln!(
+        "\nTotal: Processed {} lines across {} file(s)",
+        format_number(stats.total_lines),
+        log_files.len()
+    );
+
+    // Rate-threshold indicators are evaluated once, against the final
+    // per-entity lookup rate, rather than per streamed entry.
+    let mut hits = result.hits;
+    for indicator in indicators.iter().filter(|i| i.has_rate_threshold()) {
+        let rule_hits = hits.entry(indicator.name.clone()).or_default();
+        let mut offenders: Vec<(&String, f64)> = result
+            .entity_lookups
+            .iter()
+            .map(|(entity_id, stats)| (entity_id, stats.lookups_per_hour()))
+            .filter(|(_, rate)| indicator.exceeds_rate(*rate))
+            .collect();
+        offenders.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        rule_hits.count += offenders.len();
+        for (entity_id, rate) in offenders.into_iter().take(DEFAULT_SAMPLES_PER_RULE) {
+            if rule_hits.samples.len() >= DEFAULT_SAMPLES_PER_RULE {
+                break;
+            }
+            rule_hits.samples.push(SampleHit {
+                time: String::new(),
+                entity_id: entity_id.clone(),
+                path: String::new(),
+                operation: format!("{:.1} lookups/hr", rate),
+            });
+        }
+    }
+
+    let severity_rank = |severity: &str| match severity {
+        "critical" => 0,
+        "high" => 1,
+        "medium" => 2,
+        "low" => 3,
+        _ => 4,
+    };
+    let severity_of: HashMap<&str, &str> = indicators
+        .iter()
+        .map(|i| (i.name.as_str(), i.severity.as_str()))
+        .collect();
+
+    let mut ranked: Vec<(&String, &RuleHits)> = hits.iter().collect();
+    ranked.sort_by(|(name_a, hits_a), (name_b, hits_b)| {
+        let severity_a = severity_of.get(name_a.as_str()).copied().unwrap_or("");
+        let severity_b = severity_of.get(name_b.as_str()).copied().unwrap_or("");
+        severity_rank(severity_a)
+            .cmp(&severity_rank(severity_b))
+            .then_with(|| hits_b.count.cmp(&hits_a.count))
+    });
+
+    let rules: Vec<RuleSummary> = ranked
+        .iter()
+        .map(|(name, rule_hits)| RuleSummary {
+            name: (*name).clone(),
+            severity: severity_of.get(name.as_str()).copied().unwrap_or("unknown").to_string(),
+            count: rule_hits.count,
+        })
+        .collect();
+    let samples: Vec<ThreatHitRow> = ranked
+        .iter()
+        .flat_map(|(name, rule_hits)| {
+            let severity = severity_of.get(name.as_str()).copied().unwrap_or("unknown").to_string();
+            rule_hits.samples.iter().map(move |sample| ThreatHitRow {
+                rule_name: (*name).clone(),
+                severity: severity.clone(),
+                time: sample.time.clone(),
+                entity_id: sample.entity_id.clone(),
+                path: sample.path.clone(),
+                operation: sample.operation.clone(),
+            })
+        })
+        .collect();
+
+    let report_data = ThreatScanReport {
+        indicator_count: indicators.len(),
+        total_lines: stats.total_lines,
+        rules,
+        samples,
+    };
+    report::emit(&report_data, format)?;
+
+    if let Some(output_file) = output {
+        if let Some(parent) = std::path::Path::new(output_file).parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = std::fs::File::create(output_file).context("Failed to create output file")?;
+        let mut writer = csv::Writer::from_writer(file);
+        writer.write_record(["rule_name", "severity", "time", "entity_id", "path", "operation"])?;
+        for row in &report_data.samples {
+            writer.write_record([
+                row.rule_name.as_str(),
+                row.severity.as_str(),
+                row.time.as_str(),
+                row.entity_id.as_str(),
+                row.path.as_str(),
+                row.operation.as_str(),
+            ])?;
+        }
+        writer.flush().context("Failed to flush CSV writer")?;
+        eprintln!("Samples written to: {}", output_file);
+    }
+
+    Ok(())
+}
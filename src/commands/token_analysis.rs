@@ -21,11 +21,20 @@
 //! # Export to CSV for further analysis
 //! vault-audit token-analysis logs/*.log --export token_data.csv
 //!
+//! # Export as NDJSON for direct SIEM/log-aggregation ingestion
+//! vault-audit token-analysis logs/*.log --export token_data.ndjson --export-format ndjson
+//!
 //! # Export only high-volume token accessors (individual tokens)
 //! vault-audit token-analysis logs/*.log --min-operations 1000 --export high_volume_tokens.csv
 //!
 //! # Combine abuse detection with export
 //! vault-audit token-analysis logs/*.log --abuse-threshold 500 --export abuse_patterns.csv
+//!
+//! # Surface short bursts an hourly average would smooth away
+//! vault-audit token-analysis logs/*.log --bucket 1m
+//!
+//! # Distinguish long-lived service tokens from re-auth-per-request churn
+//! vault-audit token-analysis logs/*.log --lifecycle
 //! ```
 //!
 //! **Compressed File Support**: Automatically handles `.gz` and `.zst` files.
@@ -50,25 +59,61 @@
 //! - One row per entity (combines all tokens for that entity)
 //!
 //! ## Abuse Detection Mode (--abuse-threshold)
-//! Identifies entities exceeding lookup threshold:
+//! Identifies entities whose busiest `--burst-window` (default 60s) span of
+//! lookups meets or exceeds the threshold, rather than their total lookup
+//! count - a token firing 10,000 lookups in one minute and then going quiet
+//! for a day would otherwise hide behind a low whole-run average. Reports:
 //! - Entity details and lookup count
-//! - Time range and rate (lookups/hour)
+//! - Time range, whole-run rate (lookups/hour), and peak rate (lookups in
+//!   the busiest `--burst-window`)
 //! - Helps find misconfigured apps or compromised credentials
 //!
+//! ### Relative Outliers (--abuse-mode mad)
+//! A single hard-coded `--abuse-threshold` misses an entity doing 10x more
+//! lookups than its peers while still under the threshold, and over-flags in
+//! high-traffic environments where every entity's rate is naturally high.
+//! Passing `--abuse-mode mad` instead scores every entity's `lookups/hour`
+//! rate against the population via a robust modified z-score - see
+//! [`display_abuse_mad`].
+//!
+//! ## Burst Detection (--bucket)
+//! An entity's `lookups/hour` rate is an average over its whole first/last-seen
+//! span, so a 50,000-lookup burst compressed into two minutes looks identical
+//! to the same total spread over a week. `--bucket <duration>` (e.g. `1m`,
+//! `1h`) additionally tracks per-entity operation counts in fixed,
+//! epoch-aligned time windows and reports each entity's single busiest
+//! bucket plus its start timestamp - see [`display_bursts`].
+//!
+//! ## Lifecycle Mode (--lifecycle)
+//! The default summary collapses every accessor (token) an entity has ever
+//! held into one row, so a long-lived service token and an app that mints a
+//! fresh token on every request look the same. `--lifecycle` instead reports,
+//! per entity: distinct accessor count, the min/median/max accessor duration
+//! (`first_seen` to `last_seen`), and an accessor-churn rate (new accessors
+//! per hour across the entity's observed time span) - see
+//! [`display_lifecycle`].
+//!
 //! ## Export Mode (--export) - Per-Accessor Detail
-//! Generates CSV with per-token accessor granularity:
+//! Generates a report with per-token accessor granularity:
 //! - `entity_id`, `display_name`, accessor (token identifier)
 //! - operations, `first_seen`, `last_seen`, `duration_hours`
 //! - Shows individual token lifecycle and usage patterns
 //! - Use --min-operations to filter low-activity tokens
 //! - First/last seen timestamps
 //! - Duration
+//! - `--export-format {csv,json,ndjson}` (default: `csv`) selects the output
+//!   layout; `json`/`ndjson` go through `serde_json` so a `display_name`
+//!   containing a comma can't corrupt the record - see [`export_data`]
 
+use crate::audit::pipeline::Pipeline;
 use crate::audit::types::AuditEntry;
 use crate::utils::format::format_number;
+use crate::utils::metrics::MetricsExporter;
 use crate::utils::processor::{ProcessingMode, ProcessorBuilder};
 use crate::utils::time::parse_timestamp;
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::Write;
@@ -80,6 +125,138 @@ type ProcessLogsResult = (
     usize,
 );
 
+/// Per-entity cap on raw `lookup-self` timestamps kept for the
+/// `--burst-window` peak-rate scan before falling back to the coarser
+/// [`LookupTimestamps::Bucketed`] representation - bounds memory for an
+/// entity that racks up millions of lookups over a long run.
+const MAX_RAW_LOOKUP_TIMESTAMPS: usize = 100_000;
+
+/// Bucket width, in seconds, used by the [`LookupTimestamps::Bucketed`]
+/// fallback. Coarser than any realistic `--burst-window`, so the peak-rate
+/// scan stays a reasonable approximation once an entity tips into it.
+const LOOKUP_TIMESTAMP_BUCKET_SECS: i64 = 5;
+
+/// Per-entity `lookup-self` timestamps backing the `--burst-window`
+/// peak-rate scan in [`display_abuse`]. Starts as a plain sorted-on-demand
+/// `Vec<i64>` of epoch seconds; once an entity crosses
+/// [`MAX_RAW_LOOKUP_TIMESTAMPS`] it is rewritten, once, into a
+/// `HashMap<i64, u32>` of `LOOKUP_TIMESTAMP_BUCKET_SECS`-wide buckets so
+/// memory stays bounded for runaway automation loops.
+#[derive(Debug, Clone)]
+enum LookupTimestamps {
+    Raw(Vec<i64>),
+    Bucketed(HashMap<i64, u32>),
+}
+
+impl Default for LookupTimestamps {
+    fn default() -> Self {
+        Self::Raw(Vec::new())
+    }
+}
+
+impl LookupTimestamps {
+    fn record(&mut self, epoch: i64) {
+        match self {
+            Self::Raw(timestamps) => {
+                timestamps.push(epoch);
+                if timestamps.len() > MAX_RAW_LOOKUP_TIMESTAMPS {
+                    let mut buckets = HashMap::new();
+                    for ts in timestamps.iter() {
+                        *buckets
+                            .entry(ts - ts.rem_euclid(LOOKUP_TIMESTAMP_BUCKET_SECS))
+                            .or_insert(0) += 1;
+                    }
+                    *self = Self::Bucketed(buckets);
+                }
+            }
+            Self::Bucketed(buckets) => {
+                *buckets
+                    .entry(epoch - epoch.rem_euclid(LOOKUP_TIMESTAMP_BUCKET_SECS))
+                    .or_insert(0) += 1;
+            }
+        }
+    }
+
+    /// Absorb `other`'s timestamps into `self`, converting to
+    /// [`Self::Bucketed`] if the combined raw count would exceed
+    /// [`MAX_RAW_LOOKUP_TIMESTAMPS`].
+    fn merge_from(&mut self, other: Self) {
+        match (&mut *self, other) {
+            (Self::Raw(timestamps), Self::Raw(other_timestamps))
+                if timestamps.len() + other_timestamps.len() <= MAX_RAW_LOOKUP_TIMESTAMPS =>
+            {
+                timestamps.extend(other_timestamps);
+            }
+            (_, Self::Raw(other_timestamps)) => {
+                for ts in other_timestamps {
+                    self.record(ts);
+                }
+            }
+            (_, Self::Bucketed(other_buckets)) => {
+                if let Self::Raw(timestamps) = self {
+                    let mut buckets = HashMap::new();
+                    for ts in timestamps.iter() {
+                        *buckets
+                            .entry(ts - ts.rem_euclid(LOOKUP_TIMESTAMP_BUCKET_SECS))
+                            .or_insert(0) += 1;
+                    }
+                    *self = Self::Bucketed(buckets);
+                }
+                if let Self::Bucketed(buckets) = self {
+                    for (bucket, count) in other_buckets {
+                        *buckets.entry(bucket).or_insert(0) += count;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Maximum number of lookups observed in any `window_secs`-wide span, via
+    /// a two-pointer sliding-window scan over timestamps sorted ascending. A
+    /// single timestamp (or a single occupied bucket) yields a peak of 1 (or
+    /// that bucket's count).
+    fn peak_rate(&self, window_secs: i64) -> usize {
+        match self {
+            Self::Raw(timestamps) => {
+                if timestamps.is_empty() {
+                    return 0;
+                }
+                let mut sorted = timestamps.clone();
+                sorted.sort_unstable();
+                let mut left = 0;
+                let mut peak = 0;
+                for right in 0..sorted.len() {
+                    while sorted[right] - sorted[left] > window_secs {
+                        left += 1;
+                    }
+                    peak = peak.max(right - left + 1);
+                }
+                peak
+            }
+            Self::Bucketed(buckets) => {
+                if buckets.is_empty() {
+                    return 0;
+                }
+                let mut sorted: Vec<(i64, u32)> =
+                    buckets.iter().map(|(bucket, count)| (*bucket, *count)).collect();
+                sorted.sort_unstable_by_key(|(bucket, _)| *bucket);
+                let mut left = 0;
+                let mut window_sum: u64 = 0;
+                let mut peak: u64 = 0;
+                for right in 0..sorted.len() {
+                    window_sum += u64::from(sorted[right].1);
+                    while sorted[right].0 - sorted[left].0 > window_secs {
+                        window_sum -= u64::from(sorted[left].1);
+                        left += 1;
+                    }
+                    peak = peak.max(window_sum);
+                }
+                peak as usize
+            }
+        }
+    }
+}
+
 /// Token operation statistics for a single entity
 #[derive(Debug, Default, Clone)]
 struct TokenOps {
@@ -93,6 +270,13 @@ struct TokenOps {
     username: Option<String>,
     first_seen: Option<String>,
     last_seen: Option<String>,
+    /// Operation counts keyed by bucket-start epoch second, populated only
+    /// when `--bucket` is set. Empty otherwise.
+    buckets: HashMap<i64, usize>,
+    /// Epoch-second timestamp of every `lookup-self` call, used by
+    /// [`display_abuse`]'s `--burst-window` peak-rate scan. See
+    /// [`LookupTimestamps`].
+    lookup_times: LookupTimestamps,
 }
 
 impl TokenOps {
@@ -173,6 +357,12 @@ impl TokenAnalysisState {
             {
                 ops.last_seen = other_ops.last_seen;
             }
+
+            for (bucket, count) in other_ops.buckets {
+                *ops.buckets.entry(bucket).or_insert(0) += count;
+            }
+
+            ops.lookup_times.merge_from(other_ops.lookup_times);
         }
 
         // Merge accessor_data
@@ -213,10 +403,20 @@ fn calculate_time_span_hours(first_seen: &str, last_seen: &str) -> Result<f64> {
     Ok(duration.num_seconds() as f64 / 3600.0)
 }
 
+/// Floor `timestamp` to the start of its `bucket_secs`-wide, UTC-epoch
+/// aligned bucket. Returns `None` for unparseable timestamps, which the
+/// caller skips rather than aborting the whole run.
+fn bucket_start(timestamp: &str, bucket_secs: i64) -> Option<i64> {
+    let epoch = parse_timestamp(timestamp).ok()?.timestamp();
+    Some(epoch - epoch.rem_euclid(bucket_secs))
+}
+
 /// Process audit logs and collect token operation data
 fn process_logs(
     log_files: &[String],
     operation_filter: Option<&[String]>,
+    bucket_secs: Option<u64>,
+    pipeline: Option<&Pipeline>,
 ) -> Result<ProcessLogsResult> {
     let processor = ProcessorBuilder::new()
         .progress_label("Analyzing tokens")
@@ -228,6 +428,20 @@ fn process_logs(
     let (result, stats) = processor.process_files_streaming(
         log_files,
         move |entry: &AuditEntry, state: &mut TokenAnalysisState| {
+            // Run the record through the transform/filter pipeline, if one
+            // was given, before it reaches any of the analysis below.
+            let transformed;
+            let entry = match pipeline {
+                Some(pipeline) => match pipeline.apply(entry).and_then(|v| serde_json::from_value(v).ok()) {
+                    Some(e) => {
+                        transformed = e;
+                        &transformed
+                    }
+                    None => return,
+                },
+                None => entry,
+            };
+
             // Skip if no request or auth info
             let Some(request) = &entry.request else {
                 return;
@@ -270,7 +484,12 @@ fn process_logs(
             // Update token operations summary
             let ops = state.token_ops.entry(entity_id.clone()).or_default();
             match op_type {
-                "lookup" => ops.lookup_self += 1,
+                "lookup" => {
+                    ops.lookup_self += 1;
+                    if let Ok(parsed) = parse_timestamp(&entry.time) {
+                        ops.lookup_times.record(parsed.timestamp());
+                    }
+                }
                 "renew" => ops.renew_self += 1,
                 "revoke" => ops.revoke_self += 1,
                 "create" => ops.create += 1,
@@ -290,6 +509,12 @@ fn process_logs(
             }
             ops.update_timestamps(&entry.time);
 
+            if let Some(bucket_secs) = bucket_secs {
+                if let Some(bucket) = bucket_start(&entry.time, bucket_secs as i64) {
+                    *ops.buckets.entry(bucket).or_insert(0) += 1;
+                }
+            }
+
             // Track accessor-level data for detailed analysis
             if let Some(accessor) = &auth.accessor {
                 let entity_acc = state.accessor_data.entry(entity_id).or_default();
@@ -421,36 +646,46 @@ fn display_summary(token_ops: &HashMap<String, TokenOps>, total_lines: usize) {
     println!("TOTAL:              {:>16}", format_number(total_ops));
 }
 
-/// Display abuse detection results
-fn display_abuse(token_ops: &HashMap<String, TokenOps>, threshold: usize) {
+/// Display abuse detection results.
+///
+/// Triggers on `peak_rate` - the busiest `burst_window_secs`-wide span of
+/// lookups, from a two-pointer sliding-window scan over every `lookup-self`
+/// timestamp ([`LookupTimestamps::peak_rate`]) - rather than the total
+/// lookup count, so an entity firing 10,000 lookups in one minute and then
+/// going quiet for a day is still flagged even though its whole-span
+/// average rate looks unremarkable.
+fn display_abuse(token_ops: &HashMap<String, TokenOps>, threshold: usize, burst_window_secs: i64) {
     let mut abusers: Vec<_> = token_ops
         .iter()
-        .filter(|(_, ops)| ops.lookup_self >= threshold)
+        .map(|(entity_id, ops)| (entity_id, ops, ops.lookup_times.peak_rate(burst_window_secs)))
+        .filter(|(_, _, peak)| *peak >= threshold)
         .collect();
 
-    abusers.sort_by(|a, b| b.1.lookup_self.cmp(&a.1.lookup_self));
+    abusers.sort_by(|a, b| b.2.cmp(&a.2));
 
     if abusers.is_empty() {
         println!(
-            "\n No entities found exceeding threshold of {} lookup operations",
-            format_number(threshold)
+            "\n No entities found with a peak of {} or more lookups within any {}s window",
+            format_number(threshold),
+            burst_window_secs
         );
         return;
     }
 
     println!(
-        "\n Found {} entities exceeding {} lookup operations:\n",
+        "\n Found {} entities with a peak of {} or more lookups within a {}s window:\n",
         abusers.len(),
-        format_number(threshold)
+        format_number(threshold),
+        burst_window_secs
     );
 
     println!(
-        "{:<50} {:>12} {:>20} {:>12}",
-        "Entity", "Lookups", "Time Span", "Rate/Hour"
+        "{:<50} {:>12} {:>20} {:>12} {:>14}",
+        "Entity", "Lookups", "Time Span", "Rate/Hour", "Peak/Window"
     );
-    println!("{}", "=".repeat(106));
+    println!("{}", "=".repeat(121));
 
-    for (entity_id, ops) in abusers {
+    for (entity_id, ops, peak) in abusers {
         let display = ops
             .display_name
             .as_deref()
@@ -476,7 +711,7 @@ fn display_abuse(token_ops: &HashMap<String, TokenOps>, threshold: usize) {
         };
 
         println!(
-            "{:<50} {:>12} {:>17.1}h {:>12.1}",
+            "{:<50} {:>12} {:>17.1}h {:>12.1} {:>14}",
             if display.len() > 50 {
                 format!("{}...", &display[..47])
             } else {
@@ -484,25 +719,382 @@ fn display_abuse(token_ops: &HashMap<String, TokenOps>, threshold: usize) {
             },
             format_number(ops.lookup_self),
             time_span,
-            rate
+            rate,
+            format_number(peak)
+        );
+    }
+}
+
+/// Median of a slice of values, sorting it in place. Returns 0.0 for an empty slice.
+fn median(values: &mut [f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Flag entities whose `lookups/hour` rate is a relative outlier, rather than
+/// one that merely crosses a fixed `--abuse-threshold`. Computes a modified
+/// z-score `0.6745 * (rate - median) / MAD` (Iglewicz & Hoaglin) for every
+/// entity's rate and flags those exceeding `cutoff`. When every rate is
+/// identical (`MAD == 0`) this falls back to the mean absolute deviation;
+/// if that is also zero, there is no variation to score and nothing is
+/// flagged.
+fn display_abuse_mad(token_ops: &HashMap<String, TokenOps>, cutoff: f64) {
+    let entities: Vec<(&String, &TokenOps, f64, f64)> = token_ops
+        .iter()
+        .map(|(entity_id, ops)| {
+            let time_span = if let (Some(first), Some(last)) = (&ops.first_seen, &ops.last_seen) {
+                calculate_time_span_hours(first, last).unwrap_or_else(|err| {
+                    eprintln!(
+                        "Warning: Failed to calculate time span for entity {}: {}",
+                        entity_id, err
+                    );
+                    0.0
+                })
+            } else {
+                0.0
+            };
+
+            let rate = if time_span > 0.0 {
+                ops.lookup_self as f64 / time_span
+            } else {
+                0.0
+            };
+
+            (entity_id, ops, time_span, rate)
+        })
+        .collect();
+
+    if entities.is_empty() {
+        println!("\n No entities found with token lookup activity");
+        return;
+    }
+
+    let mut rates: Vec<f64> = entities.iter().map(|(_, _, _, rate)| *rate).collect();
+    let rate_median = median(&mut rates);
+
+    let mut abs_deviations: Vec<f64> = entities
+        .iter()
+        .map(|(_, _, _, rate)| (rate - rate_median).abs())
+        .collect();
+    let mut scale = median(&mut abs_deviations) * 1.4826;
+
+    if scale == 0.0 {
+        // All rates are identical (or nearly so) - a zero MAD would make
+        // every z-score infinite. Fall back to the mean absolute deviation.
+        let mean = rates.iter().sum::<f64>() / rates.len() as f64;
+        scale = entities
+            .iter()
+            .map(|(_, _, _, rate)| (rate - mean).abs())
+            .sum::<f64>()
+            / entities.len() as f64;
+    }
+
+    if scale == 0.0 {
+        println!(
+            "\n All {} entities have an identical lookup rate ({:.1}/hour) - no outliers to report",
+            entities.len(),
+            rate_median
+        );
+        return;
+    }
+
+    let mut scored: Vec<_> = entities
+        .into_iter()
+        .map(|(entity_id, ops, time_span, rate)| {
+            let z_score = 0.6745 * (rate - rate_median) / scale;
+            (entity_id, ops, time_span, rate, z_score)
+        })
+        .filter(|(_, _, _, _, z_score)| z_score.abs() > cutoff)
+        .collect();
+
+    if scored.is_empty() {
+        println!(
+            "\n No entities found with modified z-score exceeding {:.1}",
+            cutoff
+        );
+        return;
+    }
+
+    scored.sort_by(|a, b| {
+        b.4.abs()
+            .partial_cmp(&a.4.abs())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    println!(
+        "\n Found {} entities with anomalous lookup rates (modified z-score > {:.1}):\n",
+        scored.len(),
+        cutoff
+    );
+
+    println!(
+        "{:<50} {:>12} {:>20} {:>12} {:>10}",
+        "Entity", "Lookups", "Time Span", "Rate/Hour", "Z-Score"
+    );
+    println!("{}", "=".repeat(118));
+
+    for (entity_id, ops, time_span, rate, z_score) in scored {
+        let display = ops
+            .display_name
+            .as_deref()
+            .or(ops.username.as_deref())
+            .unwrap_or(entity_id);
+
+        println!(
+            "{:<50} {:>12} {:>17.1}h {:>12.1} {:>10.2}",
+            if display.len() > 50 {
+                format!("{}...", &display[..47])
+            } else {
+                display.to_string()
+            },
+            format_number(ops.lookup_self),
+            time_span,
+            rate,
+            z_score
+        );
+    }
+}
+
+/// Report each entity's busiest fixed-size time bucket: the count and start
+/// timestamp of its highest-activity window. Surfaces short, intense bursts
+/// (e.g. credential stuffing) that a whole-run lookups/hour average smooths
+/// away. Populated only when `--bucket` was passed to [`process_logs`].
+fn display_bursts(token_ops: &HashMap<String, TokenOps>) {
+    let mut peaks: Vec<(&String, &TokenOps, i64, usize)> = token_ops
+        .iter()
+        .filter_map(|(entity_id, ops)| {
+            ops.buckets
+                .iter()
+                .max_by_key(|(_, count)| **count)
+                .map(|(bucket, count)| (entity_id, ops, *bucket, *count))
+        })
+        .collect();
+
+    if peaks.is_empty() {
+        println!("\n No bucketed activity recorded");
+        return;
+    }
+
+    peaks.sort_by(|a, b| b.3.cmp(&a.3));
+
+    println!("\n Peak activity bucket per entity (top 50):\n");
+    println!(
+        "{:<50} {:>12} {:>26}",
+        "Entity", "Peak Count", "Peak Bucket Start"
+    );
+    println!("{}", "=".repeat(90));
+
+    for (entity_id, ops, bucket, count) in peaks.into_iter().take(50) {
+        let display = ops
+            .display_name
+            .as_deref()
+            .or(ops.username.as_deref())
+            .unwrap_or(entity_id);
+
+        let bucket_ts = DateTime::from_timestamp(bucket, 0)
+            .map(|dt| dt.to_rfc3339())
+            .unwrap_or_else(|| bucket.to_string());
+
+        println!(
+            "{:<50} {:>12} {:>26}",
+            if display.len() > 50 {
+                format!("{}...", &display[..47])
+            } else {
+                display.to_string()
+            },
+            format_number(count),
+            bucket_ts
+        );
+    }
+}
+
+/// Per-entity token lifetime summary reported by `--lifecycle`.
+struct LifecycleStats {
+    accessor_count: usize,
+    min_duration_hours: f64,
+    median_duration_hours: f64,
+    max_duration_hours: f64,
+    churn_per_hour: f64,
+}
+
+/// Summarize one entity's accessor durations and churn rate from its
+/// [`EntityAccessors`]. Returns `None` if the entity has no accessors with a
+/// parseable time span.
+fn lifecycle_stats(entity: &EntityAccessors) -> Option<LifecycleStats> {
+    if entity.accessors.is_empty() {
+        return None;
+    }
+
+    let mut durations: Vec<f64> = Vec::with_capacity(entity.accessors.len());
+    let mut earliest_first: Option<&str> = None;
+    let mut latest_last: Option<&str> = None;
+
+    for data in entity.accessors.values() {
+        durations.push(
+            calculate_time_span_hours(&data.first_seen, &data.last_seen).unwrap_or(0.0),
         );
+
+        if earliest_first.is_none() || Some(data.first_seen.as_str()) < earliest_first {
+            earliest_first = Some(&data.first_seen);
+        }
+        if latest_last.is_none() || Some(data.last_seen.as_str()) > latest_last {
+            latest_last = Some(&data.last_seen);
+        }
     }
+
+    let span_hours = match (earliest_first, latest_last) {
+        (Some(first), Some(last)) => calculate_time_span_hours(first, last).unwrap_or(0.0),
+        _ => 0.0,
+    };
+
+    let min_duration_hours = durations.iter().copied().fold(f64::INFINITY, f64::min);
+    let max_duration_hours = durations.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let median_duration_hours = median(&mut durations);
+
+    let churn_per_hour = if span_hours > 0.0 {
+        entity.accessors.len() as f64 / span_hours
+    } else {
+        0.0
+    };
+
+    Some(LifecycleStats {
+        accessor_count: entity.accessors.len(),
+        min_duration_hours,
+        median_duration_hours,
+        max_duration_hours,
+        churn_per_hour,
+    })
 }
 
-/// Export data to CSV
-fn export_csv(
+/// Report per-entity accessor-duration distribution and churn rate.
+///
+/// A healthy long-lived service token shows one accessor spanning most of
+/// the entity's observed time span; an app that re-authenticates on every
+/// request instead shows a high accessor count and churn rate with
+/// near-zero per-accessor duration - a misconfiguration that silently
+/// bloats Vault's token store but collapses into a single unremarkable row
+/// in the default per-entity operations summary.
+fn display_lifecycle(accessor_data: &HashMap<String, EntityAccessors>) {
+    let mut rows: Vec<(&String, &EntityAccessors, LifecycleStats)> = accessor_data
+        .iter()
+        .filter_map(|(entity_id, entity)| {
+            lifecycle_stats(entity).map(|stats| (entity_id, entity, stats))
+        })
+        .collect();
+
+    if rows.is_empty() {
+        println!("\n No accessor data available for lifecycle analysis");
+        return;
+    }
+
+    rows.sort_by(|a, b| {
+        b.2.churn_per_hour
+            .partial_cmp(&a.2.churn_per_hour)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    println!("\n Token lifecycle by entity (top 50, sorted by churn rate):\n");
+    println!(
+        "{:<50} {:>10} {:>10} {:>10} {:>10} {:>12}",
+        "Entity", "Accessors", "Min(h)", "Median(h)", "Max(h)", "Churn/Hour"
+    );
+    println!("{}", "=".repeat(106));
+
+    for (entity_id, entity, stats) in rows.into_iter().take(50) {
+        let display = entity.display_name.as_deref().unwrap_or(entity_id);
+
+        println!(
+            "{:<50} {:>10} {:>10.1} {:>10.1} {:>10.1} {:>12.2}",
+            if display.len() > 50 {
+                format!("{}...", &display[..47])
+            } else {
+                display.to_string()
+            },
+            format_number(stats.accessor_count),
+            stats.min_duration_hours,
+            stats.median_duration_hours,
+            stats.max_duration_hours,
+            stats.churn_per_hour
+        );
+    }
+}
+
+/// Renders this run's aggregate totals and peak lookup rates as Prometheus
+/// metrics: top-level `vault_audit_lines_total`/`vault_audit_token_entities_total`
+/// gauges, plus one `vault_audit_token_lookup_abuse{entity_id,display_name}`
+/// gauge per entity, sorted descending by peak rate and capped at
+/// `metrics_top` to bound cardinality on a large run.
+fn build_metrics_exporter(
+    token_ops: &HashMap<String, TokenOps>,
+    total_lines: usize,
+    burst_window_secs: i64,
+    metrics_top: usize,
+) -> MetricsExporter {
+    let mut exporter = MetricsExporter::new();
+    exporter.gauge(
+        "vault_audit_lines_total",
+        "Total audit log lines processed",
+        &[],
+        total_lines as f64,
+    );
+    exporter.gauge(
+        "vault_audit_token_entities_total",
+        "Distinct entities with token operations",
+        &[],
+        token_ops.len() as f64,
+    );
+
+    let mut peaks: Vec<(&String, &TokenOps, usize)> = token_ops
+        .iter()
+        .map(|(entity_id, ops)| (entity_id, ops, ops.lookup_times.peak_rate(burst_window_secs)))
+        .collect();
+    peaks.sort_by(|a, b| b.2.cmp(&a.2));
+
+    for (entity_id, ops, peak) in peaks.into_iter().take(metrics_top) {
+        let display = ops.display_name.as_deref().unwrap_or(entity_id);
+        exporter.gauge(
+            "vault_audit_token_lookup_abuse",
+            "Peak token lookup-self rate within --burst-window, per entity",
+            &[("entity_id", entity_id), ("display_name", display)],
+            peak as f64,
+        );
+    }
+
+    exporter
+}
+
+/// One per-accessor row of the `--export` output.
+#[derive(Debug, Serialize)]
+struct AccessorExportRecord<'a> {
+    entity_id: &'a str,
+    display_name: &'a str,
+    accessor: &'a str,
+    operations: usize,
+    first_seen: &'a str,
+    last_seen: &'a str,
+    duration_hours: f64,
+}
+
+/// Export per-accessor data as `format` (`"csv"`, `"json"`, or `"ndjson"`).
+///
+/// JSON and NDJSON go through [`serde_json`] rather than the raw
+/// comma-joined CSV writer, so a `display_name` containing a comma or quote
+/// can't corrupt the output - NDJSON in particular streams straight into
+/// log-aggregation/SIEM ingestion without a CSV-to-JSON conversion step.
+fn export_data(
     accessor_data: &HashMap<String, EntityAccessors>,
     output: &str,
+    format: &str,
     min_operations: usize,
 ) -> Result<()> {
-    let mut file = File::create(output)
-        .with_context(|| format!("Failed to create output file: {}", output))?;
-
-    writeln!(
-        file,
-        "entity_id,display_name,accessor,operations,first_seen,last_seen,duration_hours"
-    )?;
-
     let mut rows: Vec<_> = accessor_data
         .iter()
         .flat_map(|(entity_id, entity_data)| {
@@ -516,55 +1108,142 @@ fn export_csv(
 
     rows.sort_by(|a, b| b.3.operations.cmp(&a.3.operations));
 
-    for (entity_id, display_name, accessor, data) in rows {
-        let duration =
-            calculate_time_span_hours(&data.first_seen, &data.last_seen).unwrap_or_else(|err| {
-                eprintln!(
-                    "Warning: Failed to calculate duration for accessor {}: {}",
-                    accessor, err
-                );
-                0.0
-            });
-        let display = display_name.as_deref().unwrap_or(entity_id);
-
-        writeln!(
-            file,
-            "{},{},{},{},{},{},{:.2}",
-            entity_id,
-            display,
-            accessor,
-            data.operations,
-            data.first_seen,
-            data.last_seen,
-            duration
-        )?;
+    let records: Vec<AccessorExportRecord> = rows
+        .into_iter()
+        .map(|(entity_id, display_name, accessor, data)| {
+            let duration = calculate_time_span_hours(&data.first_seen, &data.last_seen)
+                .unwrap_or_else(|err| {
+                    eprintln!(
+                        "Warning: Failed to calculate duration for accessor {}: {}",
+                        accessor, err
+                    );
+                    0.0
+                });
+
+            AccessorExportRecord {
+                entity_id,
+                display_name: display_name.as_deref().unwrap_or(entity_id),
+                accessor,
+                operations: data.operations,
+                first_seen: &data.first_seen,
+                last_seen: &data.last_seen,
+                duration_hours: duration,
+            }
+        })
+        .collect();
+
+    let mut file = File::create(output)
+        .with_context(|| format!("Failed to create output file: {}", output))?;
+
+    match format {
+        "json" => {
+            serde_json::to_writer_pretty(&file, &records)
+                .context("Failed to write JSON output")?;
+        }
+        "ndjson" => {
+            for record in &records {
+                serde_json::to_writer(&file, record).context("Failed to write NDJSON record")?;
+                writeln!(file)?;
+            }
+        }
+        "csv" => {
+            writeln!(
+                file,
+                "entity_id,display_name,accessor,operations,first_seen,last_seen,duration_hours"
+            )?;
+            for record in &records {
+                writeln!(
+                    file,
+                    "{},{},{},{},{},{},{:.2}",
+                    record.entity_id,
+                    record.display_name,
+                    record.accessor,
+                    record.operations,
+                    record.first_seen,
+                    record.last_seen,
+                    record.duration_hours
+                )?;
+            }
+        }
+        other => bail!(
+            "Invalid --export-format '{}': expected 'csv', 'json', or 'ndjson'",
+            other
+        ),
     }
 
     Ok(())
 }
 
 /// Main entry point for token analysis command
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     log_files: &[String],
     abuse_threshold: Option<usize>,
+    abuse_mode: &str,
+    abuse_mad_cutoff: f64,
+    burst_window_secs: u64,
+    bucket_secs: Option<u64>,
+    lifecycle: bool,
     operation_filter: Option<&[String]>,
     export_path: Option<&str>,
+    export_format: &str,
     min_operations: usize,
+    metrics_file: Option<&str>,
+    metrics_listen: Option<&str>,
+    metrics_top: usize,
+    pipeline: Option<&str>,
+    pipeline_dry_run: Option<usize>,
 ) -> Result<()> {
+    let pipeline = pipeline.map(Pipeline::load).transpose()?;
+
+    if let Some(limit) = pipeline_dry_run {
+        let pipeline = pipeline
+            .as_ref()
+            .context("--pipeline-dry-run requires --pipeline")?;
+        return crate::audit::pipeline::dry_run(pipeline, log_files, limit);
+    }
+
+    let use_mad = match abuse_mode {
+        "threshold" => false,
+        "mad" => true,
+        other => {
+            bail!(
+                "Invalid --abuse-mode '{}': expected 'threshold' or 'mad'",
+                other
+            );
+        }
+    };
+
     eprintln!("Token Analysis");
     eprintln!("   Files: {}", log_files.len());
     if let Some(filters) = operation_filter {
         eprintln!("   Filter: {}", filters.join(", "));
     }
-    if let Some(threshold) = abuse_threshold {
-        eprintln!("   Abuse threshold: {}", format_number(threshold));
+    if use_mad {
+        eprintln!(
+            "   Abuse mode: mad (z-score cutoff {:.1})",
+            abuse_mad_cutoff
+        );
+    } else if let Some(threshold) = abuse_threshold {
+        eprintln!(
+            "   Abuse threshold: {} peak lookups per {}s window",
+            format_number(threshold),
+            burst_window_secs
+        );
+    }
+    if let Some(bucket_secs) = bucket_secs {
+        eprintln!("   Bucket: {}s", bucket_secs);
+    }
+    if lifecycle {
+        eprintln!("   Lifecycle: enabled");
     }
     if let Some(output) = export_path {
         eprintln!("   Export: {}", output);
     }
     eprintln!();
 
-    let (token_ops, accessor_data, total_lines) = process_logs(log_files, operation_filter)?;
+    let (token_ops, accessor_data, total_lines) =
+        process_logs(log_files, operation_filter, bucket_secs, pipeline.as_ref())?;
 
     eprintln!("\n Processed {} total lines", format_number(total_lines));
     eprintln!(
@@ -573,16 +1252,42 @@ pub fn run(
     );
 
     // Display based on mode
-    if let Some(threshold) = abuse_threshold {
-        display_abuse(&token_ops, threshold);
+    if use_mad {
+        display_abuse_mad(&token_ops, abuse_mad_cutoff);
+    } else if let Some(threshold) = abuse_threshold {
+        display_abuse(&token_ops, threshold, burst_window_secs as i64);
     } else {
         display_summary(&token_ops, total_lines);
     }
 
+    if bucket_secs.is_some() {
+        display_bursts(&token_ops);
+    }
+
+    if lifecycle {
+        display_lifecycle(&accessor_data);
+    }
+
     // Export if requested
     if let Some(output) = export_path {
-        export_csv(&accessor_data, output, min_operations)?;
-        eprintln!("\n Exported data to: {}", output);
+        export_data(&accessor_data, output, export_format, min_operations)?;
+        eprintln!("\n Exported data to: {} ({})", output, export_format);
+    }
+
+    if metrics_file.is_some() || metrics_listen.is_some() {
+        let exporter = build_metrics_exporter(
+            &token_ops,
+            total_lines,
+            burst_window_secs as i64,
+            metrics_top,
+        );
+        if let Some(metrics_path) = metrics_file {
+            exporter.write_textfile(metrics_path)?;
+            eprintln!("Metrics written to: {}", metrics_path);
+        }
+        if let Some(addr) = metrics_listen {
+            exporter.serve_blocking(addr)?;
+        }
     }
 
     Ok(())
@@ -41,156 +41,716 @@
 //! - First seen timestamp
 //! - Last seen timestamp
 //! - Duration (time between first and last seen)
+//! - Max lookups observed in any `--burst-window`-wide window (0 if unset)
+//! - Timestamp the busiest window started at, if any
+//! - Whether that max exceeded `--burst-threshold`
 //!
 //! Useful for:
 //! - Token usage trending
 //! - Token lifetime analysis
 //! - Identifying long-lived vs short-lived tokens
+//!
+//! # Burst Detection
+//!
+//! Passing `--burst-window <duration>` (e.g. "60s", "5m") makes the ingester
+//! additionally record
+//! every lookup timestamp per accessor (see [`ingest_entry`]'s
+//! `collect_timestamps` flag) and runs a sliding window over them
+//! ([`max_lookups_in_window`]) to find the densest cluster of lookups. Rows
+//! whose max exceeds `--burst-threshold` (default 100) are flagged
+//! `burst_flag = true`, surfacing short storms of lookups that a flat
+//! `lookups_per_hour` average would smooth over. Omitting `--burst-window`
+//! skips timestamp collection entirely to avoid the memory overhead on runs
+//! that don't need it.
+//!
+//! # Time-Series Export
+//!
+//! Passing `--time-series hourly` or `--time-series daily` replaces the
+//! per-token summary with a calendar-bucketed one: each lookup's timestamp
+//! is floored to the bucket boundary ([`TimeSeriesBucket::floor`]) and
+//! counted per `(bucket, entity_id)` pair, so the output CSV has columns
+//! `bucket_start,entity_id,display_name,lookups` instead. Useful for
+//! charting lookup volume over time to spot diurnal patterns or spikes that
+//! a single first/last-seen row would collapse.
+//!
+//! # Parallel Ingestion
+//!
+//! Files are parsed through the shared [`ProcessorBuilder`] streaming
+//! pipeline (the same one [`crate::commands::k8s_auth`] uses),
+//! folding each entry into a [`TokenExportState`] and merging per-file states
+//! additively (see [`TokenExportState::merge`]) rather than maintaining a
+//! bespoke byte-range-chunking reader. `--threads` pins the rayon worker
+//! count the processor runs on (default: rayon's automatic choice); omit it
+//! to let rayon size the pool to the available cores.
+//!
+//! # Rotated Log Archives
+//!
+//! `log_files` entries may be a plain file, a directory (every log-like file
+//! directly inside it is taken, sorted by name), or a `*`-wildcard glob - see
+//! [`expand_log_files`]. This lets one invocation cover a whole rotated-log
+//! retention window instead of requiring the caller to list every file.
+//!
+//! # Run-History Baseline
+//!
+//! Passing `--history <path>` turns a one-shot report into a regression
+//! tracker. Each run's summary (timestamp, total lookups, unique entities,
+//! unique accessors, per-entity totals) is appended to `path` as one JSON
+//! line ([`HistoryRun`]), and only the most recent `--history-keep` runs
+//! (default 20) are retained - see [`append_history_run`]. Before writing
+//! that new record, the prior runs are loaded ([`load_history`]) and each
+//! entity's current lookup total is compared against the mean/stddev of its
+//! own totals across those past runs; an entity whose current total is more
+//! than `--history-deviation` standard deviations (default 3.0) from its own
+//! baseline is flagged in the printed summary - see
+//! [`flag_deviating_entities`]. Entities with fewer than two past runs have
+//! no baseline yet and are never flagged.
+//!
+//! # Prometheus Metrics
+//!
+//! `--metrics-file <path>` writes a node_exporter-style textfile
+//! (`vault_token_lookups_total`/`vault_token_lookups_per_hour`, labeled by
+//! `entity_id`/`accessor`, capped at `--metrics-top` accessors to bound
+//! cardinality - see [`build_metrics_exporter`]); `--metrics-listen addr`
+//! serves the same text at `/metrics` instead. Both reuse
+//! [`crate::utils::metrics`].
+//!
+//! # Time-Window Filtering
+//!
+//! `--since`/`--until` accept either an RFC3339 timestamp or a relative
+//! duration like `"7d"`/`"24h"` (resolved via
+//! [`crate::utils::time::resolve_time_bound`]) and drop any lookup outside
+//! that window before it's counted. Entries with an unparseable timestamp
+//! are kept rather than silently dropped.
 
 use crate::audit::types::AuditEntry;
 use crate::utils::format::format_number;
-use crate::utils::progress::ProgressBar;
-use crate::utils::reader::open_file;
+use crate::utils::metrics::MetricsExporter;
+use crate::utils::processor::{ProcessingMode, ProcessorBuilder};
+use crate::utils::reader::expand_path;
 use crate::utils::time::parse_timestamp;
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
 
 /// Token activity statistics
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 struct TokenData {
     lookups: usize,
     first_seen: String,
     last_seen: String,
+    /// Every lookup timestamp for this accessor, only populated when
+    /// `--burst-window` is given (see [`ingest_entry`]) since most runs have
+    /// no use for per-lookup detail and it isn't worth the memory otherwise.
+    #[serde(default)]
+    timestamps: Vec<String>,
 }
 
 /// Entity with associated token data
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct EntityData {
     display_name: String,
     tokens: HashMap<String, TokenData>,
+    /// Lookup counts keyed by calendar bucket start (RFC 3339), only
+    /// populated when `--time-series` is given (see [`ingest_entry`]).
+    #[serde(default)]
+    buckets: HashMap<String, usize>,
 }
 
-fn calculate_time_span_hours(first: &str, last: &str) -> Result<f64> {
-    let first_dt = parse_timestamp(first)
-        .with_context(|| format!("Failed to parse first timestamp: {}", first))?;
-    let last_dt = parse_timestamp(last)
-        .with_context(|| format!("Failed to parse last timestamp: {}", last))?;
+/// Calendar bucket width for `--time-series` aggregation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TimeSeriesBucket {
+    Hourly,
+    Daily,
+}
 
-    let duration = last_dt.signed_duration_since(first_dt);
-    Ok(duration.num_seconds() as f64 / 3600.0)
+impl TimeSeriesBucket {
+    /// Floor `dt` to this bucket's boundary and render it as RFC 3339.
+    fn floor(self, dt: chrono::DateTime<chrono::Utc>) -> String {
+        use chrono::Timelike;
+        let floored = match self {
+            TimeSeriesBucket::Hourly => dt
+                .with_minute(0)
+                .and_then(|d| d.with_second(0))
+                .and_then(|d| d.with_nanosecond(0)),
+            TimeSeriesBucket::Daily => dt
+                .with_hour(0)
+                .and_then(|d| d.with_minute(0))
+                .and_then(|d| d.with_second(0))
+                .and_then(|d| d.with_nanosecond(0)),
+        };
+        floored.unwrap_or(dt).to_rfc3339()
+    }
 }
 
-pub fn run(log_files: &[String], output: &str, min_lookups: usize) -> Result<()> {
-    let mut entities: HashMap<String, EntityData> = HashMap::new();
-    let mut total_lines = 0;
-    let mut lookup_count = 0;
+/// Parse the `--time-series` flag value ("hourly" or "daily").
+fn parse_time_series_bucket(s: &str) -> Result<TimeSeriesBucket> {
+    match s.to_lowercase().as_str() {
+        "hourly" => Ok(TimeSeriesBucket::Hourly),
+        "daily" => Ok(TimeSeriesBucket::Daily),
+        other => Err(anyhow::anyhow!(
+            "invalid --time-series bucket '{}' (expected 'hourly' or 'daily')",
+            other
+        )),
+    }
+}
 
-    // Process each log file sequentially
-    for (file_idx, log_file) in log_files.iter().enumerate() {
-        eprintln!(
-            "[{}/{}] Processing: {}",
-            file_idx + 1,
-            log_files.len(),
-            log_file
-        );
+/// Simple `*`-wildcard glob match (no other metacharacters), matching the
+/// level of pattern matching already used for CLI file globs elsewhere in
+/// this tool rather than pulling in a full regex engine.
+fn glob_match(pattern: &str, value: &str) -> bool {
+    let segments: Vec<&str> = pattern.split('*').collect();
+    if segments.len() == 1 {
+        return pattern == value;
+    }
 
-        // Get file size for progress tracking
-        let file_size = std::fs::metadata(log_file).ok().map(|m| m.len() as usize);
-        let mut progress = if let Some(size) = file_size {
-            ProgressBar::new(size, "Processing")
+    let mut rest = value;
+    for (idx, segment) in segments.iter().enumerate() {
+        if segment.is_empty() {
+            continue;
+        }
+        if idx == 0 {
+            let Some(stripped) = rest.strip_prefix(segment) else {
+                return false;
+            };
+            rest = stripped;
+        } else if idx == segments.len() - 1 {
+            return rest.ends_with(segment);
         } else {
-            ProgressBar::new_spinner("Processing")
-        };
-
-        let file = open_file(log_file)?;
-        let reader = BufReader::new(file);
-
-        let mut file_lines = 0;
-        let mut bytes_read = 0;
+            let Some(found) = rest.find(segment) else {
+                return false;
+            };
+            rest = &rest[found + segment.len()..];
+        }
+    }
+    true
+}
 
-        for line in reader.lines() {
-            file_lines += 1;
-            total_lines += 1;
-            let line = line?;
-            bytes_read += line.len() + 1; // +1 for newline
+/// Expand every entry of `log_files` into the sorted, flattened list of
+/// plain files to actually process:
+///
+/// - An entry containing `*` is matched against the files directly inside
+///   its parent directory (non-recursive), sorted by name.
+/// - Anything else goes through [`expand_path`], which already handles a
+///   plain file (returned as-is) or a directory (every log-like file
+///   directly inside it, sorted by name).
+fn expand_log_files(log_files: &[String]) -> Result<Vec<String>> {
+    let mut expanded = Vec::new();
+    for entry in log_files {
+        if entry.contains('*') {
+            let path = Path::new(entry);
+            let dir = path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+            let file_pattern = path.file_name().and_then(|n| n.to_str()).unwrap_or(entry.as_str());
+
+            let mut matches: Vec<String> = std::fs::read_dir(dir)
+                .with_context(|| format!("Failed to read directory for glob: {}", entry))?
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().is_file())
+                .filter_map(|e| e.file_name().into_string().ok())
+                .filter(|name| glob_match(file_pattern, name))
+                .map(|name| dir.join(name).to_string_lossy().into_owned())
+                .collect();
+            matches.sort();
+            expanded.extend(matches);
+        } else {
+            let paths = expand_path(Path::new(entry)).with_context(|| format!("Failed to expand: {}", entry))?;
+            expanded.extend(paths.into_iter().map(|p| p.to_string_lossy().into_owned()));
+        }
+    }
+    Ok(expanded)
+}
 
-            // Update progress every 10k lines for smooth animation
-            if file_lines % 10_000 == 0 {
-                if let Some(size) = file_size {
-                    progress.update(bytes_read.min(size)); // Cap at file size
-                } else {
-                    progress.update(file_lines);
-                }
+/// Fold a single audit entry's token-lookup activity into `entities`, the
+/// shared parsing logic for both the chunked and single-threaded code paths.
+///
+/// `collect_timestamps` records every lookup's timestamp onto
+/// `TokenData::timestamps` for later sliding-window burst analysis
+/// (`--burst-window`); left `false` otherwise so a plain export run doesn't
+/// pay for per-lookup detail it won't use.
+fn ingest_entry(
+    entry: &AuditEntry,
+    entities: &mut HashMap<String, EntityData>,
+    collect_timestamps: bool,
+    time_series: Option<TimeSeriesBucket>,
+    since: Option<chrono::DateTime<chrono::Utc>>,
+    until: Option<chrono::DateTime<chrono::Utc>>,
+) {
+    let Some(request) = &entry.request else {
+        return;
+    };
+    let Some(path) = request.path.as_deref() else {
+        return;
+    };
+    if !path.starts_with("auth/token/lookup") {
+        return;
+    }
+    let Some(entity_id) = entry.auth.as_ref().and_then(|a| a.entity_id.as_deref()) else {
+        return;
+    };
+
+    // Time-window filter (--since/--until); entries with an unparseable
+    // timestamp are kept rather than silently dropped.
+    if since.is_some() || until.is_some() {
+        if let Ok(entry_time) = parse_timestamp(&entry.time) {
+            if since.is_some_and(|since| entry_time < since) {
+                return;
             }
+            if until.is_some_and(|until| entry_time > until) {
+                return;
+            }
+        }
+    }
 
-            let entry: AuditEntry = match serde_json::from_str(&line) {
-                Ok(e) => e,
-                Err(_) => continue,
-            };
+    let display_name = entry
+        .auth
+        .as_ref()
+        .and_then(|a| a.display_name.as_deref())
+        .unwrap_or("N/A");
+
+    let entity_data = entities
+        .entry(entity_id.to_string())
+        .or_insert_with(|| EntityData {
+            display_name: display_name.to_string(),
+            tokens: HashMap::new(),
+            buckets: HashMap::new(),
+        });
+
+    let accessor = entry
+        .auth
+        .as_ref()
+        .and_then(|a| a.accessor.as_deref())
+        .unwrap_or("unknown")
+        .to_string();
+    let timestamp = entry.time.clone();
+
+    let token_data = entity_data.tokens.entry(accessor).or_default();
+    token_data.lookups += 1;
+    if token_data.first_seen.is_empty() {
+        token_data.first_seen.clone_from(&timestamp);
+    }
+    if collect_timestamps {
+        token_data.timestamps.push(timestamp.clone());
+    }
+    token_data.last_seen = timestamp.clone();
 
-            // Filter for token lookup operations
-            let Some(request) = &entry.request else {
-                continue;
-            };
+    if let Some(bucket) = time_series {
+        if let Ok(dt) = parse_timestamp(&timestamp) {
+            let bucket_start = bucket.floor(dt);
+            *entity_data.buckets.entry(bucket_start).or_insert(0) += 1;
+        }
+    }
+}
 
-            let path = match &request.path {
-                Some(p) => p.as_str(),
-                None => continue,
-            };
+/// Merge per-worker/per-file entity maps, summing `TokenData.lookups` per
+/// accessor and taking the min `first_seen` / max `last_seen` across maps.
+fn merge_entities(maps: Vec<HashMap<String, EntityData>>) -> HashMap<String, EntityData> {
+    let mut merged: HashMap<String, EntityData> = HashMap::new();
+    for map in maps {
+        for (entity_id, data) in map {
+            let entry = merged.entry(entity_id).or_insert_with(|| EntityData {
+                display_name: data.display_name.clone(),
+                tokens: HashMap::new(),
+                buckets: HashMap::new(),
+            });
+            if entry.display_name == "N/A" && data.display_name != "N/A" {
+                entry.display_name = data.display_name;
+            }
 
-            if !path.starts_with("auth/token/lookup") {
-                continue;
+            for (accessor, token_data) in data.tokens {
+                let acc_entry = entry.tokens.entry(accessor).or_default();
+                acc_entry.lookups += token_data.lookups;
+                if acc_entry.first_seen.is_empty()
+                    || (!token_data.first_seen.is_empty() && token_data.first_seen < acc_entry.first_seen)
+                {
+                    acc_entry.first_seen = token_data.first_seen;
+                }
+                if token_data.last_seen > acc_entry.last_seen {
+                    acc_entry.last_seen = token_data.last_seen;
+                }
+                acc_entry.timestamps.extend(token_data.timestamps);
             }
 
-            let Some(entity_id) = entry.auth.as_ref().and_then(|a| a.entity_id.as_deref()) else {
-                continue;
-            };
+            for (bucket_start, count) in data.buckets {
+                *entry.buckets.entry(bucket_start).or_insert(0) += count;
+            }
+        }
+    }
+    merged
+}
 
-            lookup_count += 1;
+/// One run's summary as persisted to the `--history` file, one JSON record
+/// per line, oldest first. `entity_totals` is keyed by `entity_id` so a
+/// later run can look up each entity's own history of past totals.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct HistoryRun {
+    timestamp: String,
+    total_lookups: usize,
+    unique_entities: usize,
+    unique_accessors: usize,
+    entity_totals: HashMap<String, usize>,
+}
 
-            let display_name = entry
-                .auth
-                .as_ref()
-                .and_then(|a| a.display_name.as_deref())
-                .unwrap_or("N/A");
+/// Load every run recorded in `path`, oldest first. Returns an empty vec if
+/// the file doesn't exist yet (the first run against this history file).
+fn load_history(path: &str) -> Result<Vec<HistoryRun>> {
+    let Ok(file) = File::open(path) else {
+        return Ok(Vec::new());
+    };
+    let reader = BufReader::new(file);
+    let mut runs = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+        runs.push(
+            serde_json::from_str(&line)
+                .with_context(|| format!("Failed to parse history record from {}", path))?,
+        );
+    }
+    Ok(runs)
+}
 
-            let entity_data = entities
-                .entry(entity_id.to_string())
-                .or_insert_with(|| EntityData {
-                    display_name: display_name.to_string(),
-                    tokens: HashMap::new(),
-                });
+/// Append `run` to `runs` and rewrite `path` keeping only the most recent
+/// `keep` runs (oldest dropped first), so a long-running daily job doesn't
+/// grow the history file unboundedly. Writes to a temp file and renames over
+/// the original so readers never see a partially-written file.
+fn append_history_run(path: &str, mut runs: Vec<HistoryRun>, run: HistoryRun, keep: usize) -> Result<()> {
+    runs.push(run);
+    if runs.len() > keep {
+        let drop = runs.len() - keep;
+        runs.drain(0..drop);
+    }
 
-            let accessor = entry
-                .auth
-                .as_ref()
-                .and_then(|a| a.accessor.as_deref())
-                .unwrap_or("unknown")
-                .to_string();
+    let tmp_path = format!("{}.tmp", path);
+    {
+        let file = File::create(&tmp_path).context("Failed to create temp history file")?;
+        let mut writer = BufWriter::new(file);
+        for run in &runs {
+            serde_json::to_writer(&mut writer, run)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+    }
+    std::fs::rename(&tmp_path, path).context("Failed to install updated history file")?;
+    Ok(())
+}
 
-            let timestamp = entry.time.clone();
+/// Population mean and standard deviation of `values` — "population"
+/// because the whole set is exactly what each value is being compared
+/// against, not a sample drawn from some larger population.
+fn population_mean_stddev(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    if n == 0.0 {
+        return (0.0, 0.0);
+    }
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    (mean, variance.sqrt())
+}
 
-            let token_data = entity_data.tokens.entry(accessor).or_default();
-            token_data.lookups += 1;
+/// One entity whose current lookup total deviates from its own historical
+/// baseline by more than `--history-deviation` standard deviations.
+struct HistoryDeviation {
+    entity_id: String,
+    display_name: String,
+    current_lookups: usize,
+    baseline_mean: f64,
+    baseline_stddev: f64,
+    deviation: f64,
+}
 
-            if token_data.first_seen.is_empty() {
-                token_data.first_seen.clone_from(&timestamp);
+/// Compare this run's per-entity lookup totals (`current`) against each
+/// entity's own history of past runs, flagging any whose current total is
+/// more than `deviation_threshold` standard deviations from its own
+/// mean/stddev baseline. Entities with fewer than two past runs have no
+/// baseline to compare against and are skipped entirely rather than
+/// flagged; a zero-stddev baseline (every past run identical) is likewise
+/// skipped rather than flagging any deviation from it as infinite. Sorted by
+/// descending absolute deviation.
+fn flag_deviating_entities(
+    current: &HashMap<String, (String, usize)>,
+    past_runs: &[HistoryRun],
+    deviation_threshold: f64,
+) -> Vec<HistoryDeviation> {
+    let mut flagged: Vec<HistoryDeviation> = current
+        .iter()
+        .filter_map(|(entity_id, (display_name, lookups))| {
+            let history: Vec<f64> = past_runs
+                .iter()
+                .filter_map(|run| run.entity_totals.get(entity_id).map(|v| *v as f64))
+                .collect();
+            if history.len() < 2 {
+                return None;
+            }
+            let (mean, stddev) = population_mean_stddev(&history);
+            if stddev <= 0.0 {
+                return None;
             }
-            token_data.last_seen = timestamp;
+            let deviation = (*lookups as f64 - mean) / stddev;
+            if deviation.abs() <= deviation_threshold {
+                return None;
+            }
+            Some(HistoryDeviation {
+                entity_id: entity_id.clone(),
+                display_name: display_name.clone(),
+                current_lookups: *lookups,
+                baseline_mean: mean,
+                baseline_stddev: stddev,
+                deviation,
+            })
+        })
+        .collect();
+
+    flagged.sort_by(|a, b| {
+        b.deviation
+            .abs()
+            .partial_cmp(&a.deviation.abs())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    flagged
+}
+
+/// Per-file/per-thread accumulator threaded through
+/// [`ProcessorBuilder::process_files_streaming`]: every matching entry folds
+/// into `entities` via [`ingest_entry`], and [`Self::merge`] unions two
+/// states the same way [`merge_entities`] already unions chunk/flush maps.
+#[derive(Debug, Clone, Default)]
+struct TokenExportState {
+    entities: HashMap<String, EntityData>,
+}
+
+impl TokenExportState {
+    /// Merge two states produced by independent files/threads, summing
+    /// lookups and taking the min `first_seen` / max `last_seen` per
+    /// accessor - see [`merge_entities`].
+    fn merge(self, other: Self) -> Self {
+        Self {
+            entities: merge_entities(vec![self.entities, other.entities]),
         }
+    }
+}
 
-        // Ensure 100% progress for this file
-        if let Some(size) = file_size {
-            progress.update(size);
+fn calculate_time_span_hours(first: &str, last: &str) -> Result<f64> {
+    let first_dt = parse_timestamp(first)
+        .with_context(|| format!("Failed to parse first timestamp: {}", first))?;
+    let last_dt = parse_timestamp(last)
+        .with_context(|| format!("Failed to parse last timestamp: {}", last))?;
+
+    let duration = last_dt.signed_duration_since(first_dt);
+    Ok(duration.num_seconds() as f64 / 3600.0)
+}
+
+/// Slide a `window_secs`-wide window across `timestamps` and return the
+/// largest number of lookups that ever fall inside one window, along with
+/// that window's start time - a short burst (e.g. a script hammering
+/// `auth/token/lookup-self` for 30s then idling for a day) that a flat
+/// average over the whole time span would otherwise hide.
+///
+/// Unparseable timestamps are skipped. Returns `(0, None)` if fewer than two
+/// timestamps parse.
+fn max_lookups_in_window(timestamps: &[String], window_secs: i64) -> (usize, Option<String>) {
+    let mut parsed: Vec<(chrono::DateTime<chrono::Utc>, &str)> = timestamps
+        .iter()
+        .filter_map(|ts| parse_timestamp(ts).ok().map(|dt| (dt, ts.as_str())))
+        .collect();
+    if parsed.len() < 2 {
+        return (0, None);
+    }
+    parsed.sort_by_key(|(dt, _)| *dt);
+
+    let mut left = 0;
+    let mut max_count = 0;
+    let mut max_start: Option<String> = None;
+    for right in 0..parsed.len() {
+        while parsed[right].0.signed_duration_since(parsed[left].0).num_seconds() > window_secs {
+            left += 1;
         }
+        let count = right - left + 1;
+        if count > max_count {
+            max_count = count;
+            max_start = Some(parsed[left].1.to_string());
+        }
+    }
+    (max_count, max_start)
+}
+
+/// A single CSV row: one token accessor's lookup summary, plus its sliding-
+/// window burst stats when `--burst-window` is given.
+struct ExportRow {
+    entity_id: String,
+    display_name: String,
+    accessor: String,
+    lookups: usize,
+    time_span_hours: f64,
+    lookups_per_hour: f64,
+    first_seen: String,
+    last_seen: String,
+    max_lookups_in_window: usize,
+    burst_start: Option<String>,
+    burst_flag: bool,
+}
+
+/// Write the `--time-series` CSV: one row per (bucket, entity) pair with
+/// columns `bucket_start,entity_id,display_name,lookups`, sorted by bucket
+/// then entity so output is stable across runs. Replaces the per-token
+/// summary entirely rather than running alongside it.
+fn write_time_series(
+    entities: &HashMap<String, EntityData>,
+    output: &str,
+    bucket: TimeSeriesBucket,
+) -> Result<()> {
+    let bucket_label = match bucket {
+        TimeSeriesBucket::Hourly => "hourly",
+        TimeSeriesBucket::Daily => "daily",
+    };
+
+    let mut rows: Vec<(&str, &str, &str, usize)> = entities
+        .iter()
+        .flat_map(|(entity_id, entity_data)| {
+            entity_data
+                .buckets
+                .iter()
+                .map(move |(bucket_start, count)| {
+                    (bucket_start.as_str(), entity_id.as_str(), entity_data.display_name.as_str(), *count)
+                })
+        })
+        .collect();
+    rows.sort_by(|a, b| a.0.cmp(b.0).then_with(|| a.1.cmp(b.1)));
+
+    if let Some(parent) = std::path::Path::new(output).parent() {
+        std::fs::create_dir_all(parent).context("Failed to create output directory")?;
+    }
 
-        progress.finish_with_message(&format!(
-            "Processed {} lines from this file",
-            format_number(file_lines)
-        ));
+    let file = File::create(output).context("Failed to create output file")?;
+    let mut writer = csv::Writer::from_writer(file);
+    writer.write_record(["bucket_start", "entity_id", "display_name", "lookups"])?;
+    for (bucket_start, entity_id, display_name, count) in &rows {
+        writer.write_record([*bucket_start, *entity_id, *display_name, &count.to_string()])?;
     }
+    writer.flush()?;
+
+    eprintln!(
+        "\n[SUCCESS] Exported {} {} time-series bucket(s) to: {}",
+        format_number(rows.len()),
+        bucket_label,
+        output
+    );
+
+    Ok(())
+}
+
+/// Renders the exported rows as Prometheus metrics: one
+/// `vault_token_lookups_total{entity_id,accessor}` and
+/// `vault_token_lookups_per_hour{entity_id,accessor}` gauge pair per
+/// accessor, sorted descending by lookup count and capped at `metrics_top`
+/// to bound cardinality on a large export.
+fn build_metrics_exporter(rows: &[ExportRow], metrics_top: usize) -> MetricsExporter {
+    let mut exporter = MetricsExporter::new();
+
+    let mut sorted: Vec<&ExportRow> = rows.iter().collect();
+    sorted.sort_by(|a, b| b.lookups.cmp(&a.lookups));
+
+    for row in sorted.into_iter().take(metrics_top) {
+        let labels = [
+            ("entity_id", row.entity_id.as_str()),
+            ("accessor", row.accessor.as_str()),
+        ];
+        exporter.gauge(
+            "vault_token_lookups_total",
+            "Total token lookup-self operations, per entity/accessor",
+            &labels,
+            row.lookups as f64,
+        );
+        exporter.gauge(
+            "vault_token_lookups_per_hour",
+            "Token lookup-self rate over the accessor's observed time span, per entity/accessor",
+            &labels,
+            row.lookups_per_hour,
+        );
+    }
+
+    exporter
+}
+
+/// Export token lookup patterns from `log_files` to `output`.
+///
+/// Files are parsed through the shared [`ProcessorBuilder`] streaming
+/// pipeline, which picks [`ProcessingMode::Auto`] (parallel for 2+ files,
+/// sequential for 1) and merges each file's [`TokenExportState`] via
+/// [`TokenExportState::merge`]. `threads`, when given, pins the rayon worker
+/// pool the processor runs on instead of rayon's default.
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    log_files: &[String],
+    output: &str,
+    min_lookups: usize,
+    threads: Option<usize>,
+    burst_window_secs: Option<i64>,
+    burst_threshold: usize,
+    time_series: Option<&str>,
+    history: Option<&str>,
+    history_keep: usize,
+    history_deviation: f64,
+    metrics_file: Option<&str>,
+    metrics_listen: Option<&str>,
+    metrics_top: usize,
+    since: Option<&str>,
+    until: Option<&str>,
+) -> Result<()> {
+    let time_series_bucket = time_series.map(parse_time_series_bucket).transpose()?;
+    let log_files = expand_log_files(log_files)?;
+
+    let now = chrono::Utc::now();
+    let since_bound = since
+        .map(|s| crate::utils::time::resolve_time_bound(s, now))
+        .transpose()
+        .context("Invalid --since")?;
+    let until_bound = until
+        .map(|s| crate::utils::time::resolve_time_bound(s, now))
+        .transpose()
+        .context("Invalid --until")?;
+
+    let collect_timestamps = burst_window_secs.is_some();
+
+    let processor = ProcessorBuilder::new()
+        .mode(ProcessingMode::Auto)
+        .progress_label("Processing".to_string())
+        .build();
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads.unwrap_or(0)) // 0 tells rayon to pick a sensible default
+        .build()
+        .context("Failed to build token-export worker thread pool")?;
+
+    let (result, stats) = pool.install(|| {
+        processor.process_files_streaming(
+            &log_files,
+            |entry: &AuditEntry, state: &mut TokenExportState| {
+                ingest_entry(
+                    entry,
+                    &mut state.entities,
+                    collect_timestamps,
+                    time_series_bucket,
+                    since_bound,
+                    until_bound,
+                );
+            },
+            TokenExportState::merge,
+            TokenExportState::default(),
+        )
+    })?;
+
+    let entities = result.entities;
+    let total_lines = stats.total_lines;
+
+    let lookup_count: usize = entities
+        .values()
+        .flat_map(|e| e.tokens.values())
+        .map(|t| t.lookups)
+        .sum();
 
     eprintln!(
         "\nTotal: Processed {} lines, found {} token lookups from {} entities",
@@ -199,48 +759,97 @@ pub fn run(log_files: &[String], output: &str, min_lookups: usize) -> Result<()>
         format_number(entities.len())
     );
 
+    if let Some(history_path) = history {
+        let unique_accessors: usize = entities.values().map(|e| e.tokens.len()).sum();
+        let entity_totals: HashMap<String, (String, usize)> = entities
+            .iter()
+            .map(|(entity_id, data)| {
+                let total: usize = data.tokens.values().map(|t| t.lookups).sum();
+                (entity_id.clone(), (data.display_name.clone(), total))
+            })
+            .collect();
+
+        let past_runs = load_history(history_path)?;
+        let flagged = flag_deviating_entities(&entity_totals, &past_runs, history_deviation);
+        if !flagged.is_empty() {
+            eprintln!(
+                "\nHistorical Deviation Flags (beyond {:.1}σ of each entity's baseline):",
+                history_deviation
+            );
+            eprintln!("{}", "-".repeat(80));
+            for flag in &flagged {
+                eprintln!(
+                    "{} ({}): {} lookups vs baseline {:.1} ± {:.1} ({:+.2}σ)",
+                    flag.display_name,
+                    flag.entity_id,
+                    flag.current_lookups,
+                    flag.baseline_mean,
+                    flag.baseline_stddev,
+                    flag.deviation
+                );
+            }
+        }
+
+        let run = HistoryRun {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            total_lookups: lookup_count,
+            unique_entities: entities.len(),
+            unique_accessors,
+            entity_totals: entity_totals.into_iter().map(|(id, (_, total))| (id, total)).collect(),
+        };
+        append_history_run(history_path, past_runs, run, history_keep)?;
+    }
+
+    if let Some(bucket) = time_series_bucket {
+        return write_time_series(&entities, output, bucket);
+    }
+
     // Prepare CSV rows
-    let mut rows: Vec<_> = entities
+    let mut rows: Vec<ExportRow> = entities
         .iter()
         .flat_map(|(entity_id, entity_data)| {
-            entity_data
-                .tokens
-                .iter()
-                .map(move |(accessor, token_data)| {
-                    let time_span =
-                        calculate_time_span_hours(&token_data.first_seen, &token_data.last_seen)
-                            .unwrap_or_else(|err| {
-                                eprintln!(
-                                    "Warning: Failed to calculate time span for accessor {}: {}",
-                                    accessor, err
-                                );
-                                0.0
-                            });
-                    let lookups_per_hour = if time_span > 0.0 {
-                        token_data.lookups as f64 / time_span
-                    } else {
+            entity_data.tokens.iter().map(move |(accessor, token_data)| {
+                let time_span = calculate_time_span_hours(&token_data.first_seen, &token_data.last_seen)
+                    .unwrap_or_else(|err| {
+                        eprintln!(
+                            "Warning: Failed to calculate time span for accessor {}: {}",
+                            accessor, err
+                        );
                         0.0
-                    };
-
-                    (
-                        entity_id.clone(),
-                        entity_data.display_name.clone(),
-                        accessor.clone(),
-                        token_data.lookups,
-                        time_span,
-                        lookups_per_hour,
-                        token_data.first_seen.clone(),
-                        token_data.last_seen.clone(),
-                    )
-                })
+                    });
+                let lookups_per_hour = if time_span > 0.0 {
+                    token_data.lookups as f64 / time_span
+                } else {
+                    0.0
+                };
+
+                let (max_in_window, burst_start) = match burst_window_secs {
+                    Some(window_secs) => max_lookups_in_window(&token_data.timestamps, window_secs),
+                    None => (0, None),
+                };
+
+                ExportRow {
+                    entity_id: entity_id.clone(),
+                    display_name: entity_data.display_name.clone(),
+                    accessor: accessor.clone(),
+                    lookups: token_data.lookups,
+                    time_span_hours: time_span,
+                    lookups_per_hour,
+                    first_seen: token_data.first_seen.clone(),
+                    last_seen: token_data.last_seen.clone(),
+                    max_lookups_in_window: max_in_window,
+                    burst_start,
+                    burst_flag: burst_window_secs.is_some() && max_in_window > burst_threshold,
+                }
+            })
         })
         .collect();
 
     // Sort by total lookups descending
-    rows.sort_by(|a, b| b.3.cmp(&a.3));
+    rows.sort_by(|a, b| b.lookups.cmp(&a.lookups));
 
     // Filter by minimum lookups
-    rows.retain(|row| row.3 >= min_lookups);
+    rows.retain(|row| row.lookups >= min_lookups);
 
     // Create output directory if needed
     if let Some(parent) = std::path::Path::new(output).parent() {
@@ -260,18 +869,24 @@ pub fn run(log_files: &[String], output: &str, min_lookups: usize) -> Result<()>
         "lookups_per_hour",
         "first_seen",
         "last_seen",
+        "max_lookups_in_window",
+        "burst_start",
+        "burst_flag",
     ])?;
 
-    for (entity_id, display_name, accessor, lookups, time_span, rate, first, last) in &rows {
+    for row in &rows {
         writer.write_record([
-            entity_id,
-            display_name,
-            accessor,
-            &lookups.to_string(),
-            &format!("{:.2}", time_span),
-            &format!("{:.2}", rate),
-            first,
-            last,
+            row.entity_id.as_str(),
+            row.display_name.as_str(),
+            row.accessor.as_str(),
+            &row.lookups.to_string(),
+            &format!("{:.2}", row.time_span_hours),
+            &format!("{:.2}", row.lookups_per_hour),
+            row.first_seen.as_str(),
+            row.last_seen.as_str(),
+            &row.max_lookups_in_window.to_string(),
+            row.burst_start.as_deref().unwrap_or(""),
+            &row.burst_flag.to_string(),
         ])?;
     }
 
@@ -283,8 +898,17 @@ pub fn run(log_files: &[String], output: &str, min_lookups: usize) -> Result<()>
         output
     );
 
+    if burst_window_secs.is_some() {
+        let burst_count = rows.iter().filter(|r| r.burst_flag).count();
+        eprintln!(
+            "Burst detection: {} accessor(s) exceeded {} lookups within the sliding window",
+            format_number(burst_count),
+            format_number(burst_threshold)
+        );
+    }
+
     // Print summary
-    let total_lookups: usize = rows.iter().map(|r| r.3).sum();
+    let total_lookups: usize = rows.iter().map(|r| r.lookups).sum();
     let unique_entities = entities.len();
     let unique_tokens = rows.len();
 
@@ -305,9 +929,9 @@ pub fn run(log_files: &[String], output: &str, min_lookups: usize) -> Result<()>
     // Top 5 entities by lookup count
     let mut entity_totals: HashMap<String, usize> = HashMap::new();
     let mut entity_names: HashMap<String, String> = HashMap::new();
-    for (entity_id, display_name, _, lookups, _, _, _, _) in &rows {
-        *entity_totals.entry(entity_id.clone()).or_insert(0) += lookups;
-        entity_names.insert(entity_id.clone(), display_name.clone());
+    for row in &rows {
+        *entity_totals.entry(row.entity_id.clone()).or_insert(0) += row.lookups;
+        entity_names.insert(row.entity_id.clone(), row.display_name.clone());
     }
 
     let mut top_entities: Vec<_> = entity_totals.into_iter().collect();
@@ -329,5 +953,16 @@ pub fn run(log_files: &[String], output: &str, min_lookups: usize) -> Result<()>
     eprintln!("{}", "=".repeat(80));
     eprintln!("\n✓ Token lookup data exported to: {}", output);
 
+    if metrics_file.is_some() || metrics_listen.is_some() {
+        let exporter = build_metrics_exporter(&rows, metrics_top);
+        if let Some(metrics_path) = metrics_file {
+            exporter.write_textfile(metrics_path)?;
+            println!("\nMetrics written to: {}", metrics_path);
+        }
+        if let Some(addr) = metrics_listen {
+            exporter.serve_blocking(addr)?;
+        }
+    }
+
     Ok(())
 }
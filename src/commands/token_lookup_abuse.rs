@@ -2,7 +2,9 @@
 //!
 //! Identifies entities performing excessive token lookup operations,
 //! which can indicate misconfigured applications or potential security issues.
-//! Supports multi-file analysis for pattern detection over time.
+//! Processes multiple files in parallel via [`ProcessorBuilder`](crate::utils::processor::ProcessorBuilder),
+//! so burst/peak patterns can be detected across rotated log files rather
+//! than siloed per file.
 //!
 //! # Usage
 //!
@@ -26,30 +28,137 @@
 //! - Applications polling tokens too frequently
 //! - Misconfigured token renewal logic
 //! - Potential reconnaissance activity
+//!
+//! # Cross-Entity Sharing Detection (--detect-sharing)
+//!
+//! The per-entity volume analysis above can't see a token accessor shared
+//! across entities, since it's keyed by entity first. `--detect-sharing`
+//! inverts the same `patterns` map into `accessor -> entities`, flags any
+//! accessor seen under two or more distinct entity IDs, and reports it with
+//! the entities involved, its total lookups, and the earliest/latest time it
+//! crossed an entity boundary - a signal for a leaked or shared-credential
+//! token rather than excessive polling. See [`display_sharing`].
+//!
+//! `--format table` (the default) prints the ranked summary below.
+//! `--format json` emits the same rows as one document; `--format ndjson`
+//! streams one [`LookupRow`] per excessive-lookup entity/accessor pair with
+//! stable field names (`entity_id`, `accessor`, `lookups`,
+//! `time_span_hours`, `rate_per_hour`, `first_seen`, `last_seen`) for
+//! downstream ingestion - see [`crate::utils::report`]. Only applies to the
+//! default volume analysis; `--detect-sharing` always prints its table.
+//!
+//! # Burst Detection (--window / --rate)
+//!
+//! The default mode flags a pair by its *lifetime* `lookups` count, so a
+//! brief burst buried in an otherwise quiet week is invisible while a slow
+//! steady client can trip the alarm. Passing `--window <duration>` (e.g.
+//! "60s", "5m") switches to sliding-window mode: every lookup timestamp per
+//! `(entity_id, accessor)` pair is kept, and a two-pointer sweep
+//! ([`max_window_density`]) finds the densest `--window`-wide span. A pair
+//! is flagged when that max density meets `--rate` (default 10), and the
+//! row reports the matching window's start/end instead of the lifetime
+//! `first_seen`/`last_seen`. This catches reconnaissance-style bursts that
+//! the cumulative `--threshold` mode misses.
 
 use crate::audit::types::AuditEntry;
-use crate::utils::progress::ProgressBar;
-use crate::utils::reader::open_file;
-use crate::utils::time::parse_timestamp;
+use crate::utils::format::format_number;
+use crate::utils::processor::{ProcessingMode, ProcessorBuilder};
+use crate::utils::report::{self, OutputFormat, Report};
+use crate::utils::time::{format_timestamp, parse_timestamp};
 use anyhow::Result;
-use std::collections::HashMap;
-use std::io::{BufRead, BufReader};
+use chrono::DateTime;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 
-/// Tracks token lookup statistics for an entity
-#[derive(Debug)]
+/// Tracks token lookup statistics for an entity/accessor pair
+#[derive(Debug, Clone)]
 struct TokenData {
     lookups: usize,
     first_seen: String,
     last_seen: String,
+    /// Every lookup's epoch-seconds timestamp, populated only when
+    /// `--window` burst detection is active (empty otherwise, to avoid the
+    /// memory overhead on runs that don't need it). Unsorted until
+    /// [`max_window_density`] sorts a clone at use time.
+    timestamps: Vec<i64>,
 }
 
 impl TokenData {
-    fn new(timestamp: String) -> Self {
+    fn new(timestamp: String, collect_timestamps: bool) -> Self {
+        let timestamps = if collect_timestamps {
+            parse_timestamp(&timestamp)
+                .map(|t| vec![t.timestamp()])
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
         Self {
             lookups: 1,
             first_seen: timestamp.clone(),
             last_seen: timestamp,
+            timestamps,
+        }
+    }
+
+    /// Absorb `other`'s counts into `self`, summing `lookups` and keeping the
+    /// earliest `first_seen`/latest `last_seen` across files.
+    fn merge_from(&mut self, other: Self) {
+        self.lookups += other.lookups;
+        if other.first_seen < self.first_seen {
+            self.first_seen = other.first_seen;
         }
+        if other.last_seen > self.last_seen {
+            self.last_seen = other.last_seen;
+        }
+        self.timestamps.extend(other.timestamps);
+    }
+}
+
+/// Two-pointer sweep over `timestamps` (sorted ascending internally) finding
+/// the densest `window_seconds`-wide span: advances `right`, and advances
+/// `left` whenever `ts[right] - ts[left] > window_seconds`, tracking the
+/// maximum `right - left + 1` seen. Returns the max count and that window's
+/// start/end epoch-seconds timestamps, or `None` if `timestamps` is empty.
+fn max_window_density(timestamps: &[i64], window_seconds: i64) -> Option<(usize, i64, i64)> {
+    let mut sorted = timestamps.to_vec();
+    sorted.sort_unstable();
+
+    let mut left = 0;
+    let mut best = (0usize, *sorted.first()?, *sorted.first()?);
+
+    for right in 0..sorted.len() {
+        while sorted[right] - sorted[left] > window_seconds {
+            left += 1;
+        }
+        let count = right - left + 1;
+        if count > best.0 {
+            best = (count, sorted[left], sorted[right]);
+        }
+    }
+
+    Some(best)
+}
+
+/// entity_id -> accessor -> TokenData, folded across all files.
+#[derive(Debug, Clone, Default)]
+struct TokenLookupState {
+    patterns: HashMap<String, HashMap<String, TokenData>>,
+    lookup_lines: usize,
+}
+
+impl TokenLookupState {
+    fn merge(mut self, other: Self) -> Self {
+        for (entity_id, other_tokens) in other.patterns {
+            let entity_map = self.patterns.entry(entity_id).or_default();
+            for (accessor, other_data) in other_tokens {
+                entity_map
+                    .entry(accessor)
+                    .and_modify(|data| data.merge_from(other_data.clone()))
+                    .or_insert(other_data);
+            }
+        }
+        self.lookup_lines += other.lookup_lines;
+        self
     }
 }
 
@@ -63,229 +172,441 @@ fn calculate_time_span_hours(first_seen: &str, last_seen: &str) -> f64 {
     }
 }
 
-fn format_number(n: usize) -> String {
-    let s = n.to_string();
-    let mut result = String::new();
-    for (i, c) in s.chars().rev().enumerate() {
-        if i > 0 && i % 3 == 0 {
-            result.push(',');
+/// One token accessor seen under more than one entity ID.
+struct SharedAccessor {
+    accessor: String,
+    entities: Vec<String>,
+    total_lookups: usize,
+    first_seen: String,
+    last_seen: String,
+}
+
+/// Invert `patterns` (`entity_id -> accessor -> TokenData`) into
+/// `accessor -> entity_id`s and report every accessor held by 2+ distinct
+/// entities, ranked by entity count then total lookups.
+fn display_sharing(patterns: &HashMap<String, HashMap<String, TokenData>>) {
+    let mut by_accessor: HashMap<&str, HashSet<&str>> = HashMap::new();
+
+    for (entity_id, tokens) in patterns {
+        for accessor in tokens.keys() {
+            by_accessor
+                .entry(accessor.as_str())
+                .or_default()
+                .insert(entity_id.as_str());
         }
-        result.push(c);
     }
-    result.chars().rev().collect()
-}
 
-pub fn run(log_files: &[String], threshold: usize) -> Result<()> {
-    // entity_id -> accessor -> TokenData
-    let mut patterns: HashMap<String, HashMap<String, TokenData>> = HashMap::new();
-    let mut total_lines = 0;
-    let mut lookup_lines = 0;
-
-    // Process each log file sequentially
-    for (file_idx, log_file) in log_files.iter().enumerate() {
-        eprintln!(
-            "[{}/{}] Processing: {}",
-            file_idx + 1,
-            log_files.len(),
-            log_file
-        );
+    let mut shared: Vec<SharedAccessor> = by_accessor
+        .into_iter()
+        .filter(|(_, entities)| entities.len() >= 2)
+        .map(|(accessor, entities)| {
+            let mut entities: Vec<String> = entities.into_iter().map(String::from).collect();
+            entities.sort();
+
+            let mut total_lookups = 0;
+            let mut first_seen: Option<&str> = None;
+            let mut last_seen: Option<&str> = None;
+
+            for entity_id in &entities {
+                let data = &patterns[entity_id][accessor];
+                total_lookups += data.lookups;
+                if first_seen.is_none() || Some(data.first_seen.as_str()) < first_seen {
+                    first_seen = Some(&data.first_seen);
+                }
+                if last_seen.is_none() || Some(data.last_seen.as_str()) > last_seen {
+                    last_seen = Some(&data.last_seen);
+                }
+            }
+
+            SharedAccessor {
+                accessor: accessor.to_string(),
+                entities,
+                total_lookups,
+                first_seen: first_seen.unwrap_or_default().to_string(),
+                last_seen: last_seen.unwrap_or_default().to_string(),
+            }
+        })
+        .collect();
+
+    shared.sort_by(|a, b| {
+        b.entities
+            .len()
+            .cmp(&a.entities.len())
+            .then_with(|| b.total_lookups.cmp(&a.total_lookups))
+    });
+
+    println!("\n{}", "=".repeat(120));
+    println!("Cross-Entity Token Sharing Analysis");
+    println!("{}", "=".repeat(120));
 
-        // Get file size for progress tracking
-        let file_size = std::fs::metadata(log_file).ok().map(|m| m.len() as usize);
-        let mut progress = if let Some(size) = file_size {
-            ProgressBar::new(size, "Processing")
+    if shared.is_empty() {
+        println!("\nNo token accessors found shared across multiple entities.");
+        println!("{}", "=".repeat(120));
+        return;
+    }
+
+    println!(
+        "\nFound {} accessor(s) shared across multiple entities:\n",
+        format_number(shared.len())
+    );
+
+    for accessor in &shared {
+        let accessor_display = if accessor.accessor.len() > 23 {
+            format!("{}...", &accessor.accessor[..20])
         } else {
-            ProgressBar::new_spinner("Processing")
+            accessor.accessor.clone()
         };
 
-        let file = open_file(log_file)?;
-        let reader = BufReader::new(file);
+        println!("Accessor: {}", accessor_display);
+        println!(
+            "  Entities ({}): {}",
+            accessor.entities.len(),
+            accessor.entities.join(", ")
+        );
+        println!("  Total Lookups: {}", format_number(accessor.total_lookups));
+        println!(
+            "  Crossed Entity Boundary: {} -> {}",
+            accessor.first_seen, accessor.last_seen
+        );
+        println!();
+    }
+
+    println!("{}", "=".repeat(120));
+}
+
+/// One entity/accessor pair meeting `--threshold` (or, in `--window` burst
+/// mode, `--rate`), with stable field names for `--format json`/`ndjson`.
+#[derive(Debug, Clone, Serialize)]
+struct LookupRow {
+    entity_id: String,
+    accessor: String,
+    lookups: usize,
+    time_span_hours: f64,
+    rate_per_hour: f64,
+    first_seen: String,
+    last_seen: String,
+    /// Densest `--window`-wide lookup count; only set in burst mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    burst_count: Option<usize>,
+    /// Start of the densest `--window`-wide span; only set in burst mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    burst_window_start: Option<String>,
+    /// End of the densest `--window`-wide span; only set in burst mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    burst_window_end: Option<String>,
+}
+
+/// Full excessive-lookup result: entity count plus the ranked rows every
+/// output format is built from.
+#[derive(Debug, Clone, Serialize)]
+struct LookupReport {
+    threshold: usize,
+    total_entities: usize,
+    /// Set when `--window`/`--rate` burst mode produced these rows, so
+    /// `render_table` can label and size columns accordingly.
+    burst_window_seconds: Option<u64>,
+    rows: Vec<LookupRow>,
+}
+
+impl Report for LookupReport {
+    type Row = LookupRow;
+
+    fn command_name(&self) -> &'static str {
+        "token-lookup-abuse"
+    }
 
-        let mut file_lines = 0;
-        let mut bytes_read = 0;
+    fn render_table(&self, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+        writeln!(w, "\n{}", "=".repeat(120))?;
+        writeln!(w, "Token Lookup Pattern Analysis")?;
+        writeln!(w, "{}", "=".repeat(120))?;
+        writeln!(w, "\nTotal Entities: {}", format_number(self.total_entities))?;
+        if let Some(window_secs) = self.burst_window_seconds {
+            writeln!(
+                w,
+                "Entity/accessor pairs with \u{2265}{} lookups in any {}s window: {}",
+                self.threshold,
+                window_secs,
+                format_number(self.rows.len())
+            )?;
+        } else {
+            writeln!(
+                w,
+                "Entities with \u{2265}{} lookups on same token: {}",
+                self.threshold,
+                format_number(self.rows.len())
+            )?;
+        }
 
-        for line in reader.lines() {
-            file_lines += 1;
-            total_lines += 1;
-            let line = line?;
-            bytes_read += line.len() + 1; // +1 for newline
+        if self.rows.is_empty() {
+            writeln!(w, "{}", "=".repeat(120))?;
+            return Ok(());
+        }
 
-            // Update progress every 10k lines for smooth animation
-            if file_lines % 10_000 == 0 {
-                if let Some(size) = file_size {
-                    progress.update(bytes_read.min(size)); // Cap at file size
+        let top = 20;
+        if let Some(window_secs) = self.burst_window_seconds {
+            writeln!(
+                w,
+                "\nTop {} Entities by Burst Density (densest {}s window):",
+                top, window_secs
+            )?;
+            writeln!(w, "{}", "-".repeat(120))?;
+            writeln!(
+                w,
+                "{:<40} {:<25} {:>10} {:>12} {:<20} {:<20}",
+                "Entity ID", "Token Accessor", "Lookups", "Burst Count", "Window Start", "Window End"
+            )?;
+            writeln!(w, "{}", "-".repeat(120))?;
+
+            for row in self.rows.iter().take(top) {
+                let accessor_display = if row.accessor.len() > 23 {
+                    format!("{}...", &row.accessor[..20])
                 } else {
-                    progress.update(file_lines);
-                }
+                    row.accessor.clone()
+                };
+                writeln!(
+                    w,
+                    "{:<40} {:<25} {:>10} {:>12} {:<20} {:<20}",
+                    row.entity_id,
+                    accessor_display,
+                    format_number(row.lookups),
+                    row.burst_count.unwrap_or(0),
+                    row.burst_window_start.as_deref().unwrap_or(""),
+                    row.burst_window_end.as_deref().unwrap_or("")
+                )?;
+            }
+        } else {
+            writeln!(w, "\nTop {} Entities with Excessive Token Lookups:", top)?;
+            writeln!(w, "{}", "-".repeat(120))?;
+            writeln!(
+                w,
+                "{:<40} {:<25} {:>10} {:>12} {:>15}",
+                "Entity ID", "Token Accessor", "Lookups", "Time Span", "Rate"
+            )?;
+            writeln!(
+                w,
+                "{:<40} {:<25} {:>10} {:>12} {:>15}",
+                "", "", "", "(hours)", "(lookups/hr)"
+            )?;
+            writeln!(w, "{}", "-".repeat(120))?;
+
+            for row in self.rows.iter().take(top) {
+                let accessor_display = if row.accessor.len() > 23 {
+                    format!("{}...", &row.accessor[..20])
+                } else {
+                    row.accessor.clone()
+                };
+                writeln!(
+                    w,
+                    "{:<40} {:<25} {:>10} {:>12.1} {:>15.1}",
+                    row.entity_id,
+                    accessor_display,
+                    format_number(row.lookups),
+                    row.time_span_hours,
+                    row.rate_per_hour
+                )?;
             }
+        }
 
-            let entry: AuditEntry = match serde_json::from_str(&line) {
-                Ok(e) => e,
-                Err(_) => continue,
-            };
+        let total_excessive_lookups: usize = self.rows.iter().map(|r| r.lookups).sum();
+        let avg_lookups = total_excessive_lookups as f64 / self.rows.len() as f64;
 
-            // Filter for token lookup-self operations
-            let request = match &entry.request {
-                Some(r) => r,
-                None => continue,
+        writeln!(w, "\n{}", "-".repeat(120))?;
+        writeln!(
+            w,
+            "Total Excessive Lookups: {}",
+            format_number(total_excessive_lookups)
+        )?;
+        writeln!(w, "Average Lookups per Entity: {:.1}", avg_lookups)?;
+
+        if self.burst_window_seconds.is_some() {
+            let max_burst = self.rows[0].burst_count.unwrap_or(0);
+            writeln!(
+                w,
+                "Maximum Burst Count (single window): {}",
+                format_number(max_burst)
+            )?;
+        } else {
+            let max_lookups = self.rows[0].lookups;
+            writeln!(
+                w,
+                "Maximum Lookups (single token): {}",
+                format_number(max_lookups)
+            )?;
+
+            let mut by_rate = self.rows.clone();
+            by_rate.sort_by(|a, b| {
+                b.rate_per_hour
+                    .partial_cmp(&a.rate_per_hour)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+
+            if by_rate[0].rate_per_hour > 0.0 {
+                writeln!(w, "\nHighest Rate: {:.1} lookups/hour", by_rate[0].rate_per_hour)?;
+                writeln!(w, "  Entity: {}", by_rate[0].entity_id)?;
+                writeln!(w, "  Lookups: {}", format_number(by_rate[0].lookups))?;
+            }
+        }
+
+        writeln!(w, "{}", "=".repeat(120))?;
+        Ok(())
+    }
+
+    fn rows(&self) -> &[LookupRow] {
+        &self.rows
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    log_files: &[String],
+    threshold: usize,
+    detect_sharing: bool,
+    format: &str,
+    window: Option<u64>,
+    rate: usize,
+) -> Result<()> {
+    let format = OutputFormat::parse(format).map_err(anyhow::Error::msg)?;
+    let collect_timestamps = window.is_some();
+    let processor = ProcessorBuilder::new()
+        .mode(ProcessingMode::Auto)
+        .progress_label("Processing".to_string())
+        .build();
+
+    let (result, stats) = processor.process_files_streaming(
+        log_files,
+        |entry: &AuditEntry, state: &mut TokenLookupState| {
+            let Some(request) = &entry.request else {
+                return;
             };
 
-            let path = match &request.path {
-                Some(p) => p.as_str(),
-                None => continue,
+            let Some(path) = request.path.as_deref() else {
+                return;
             };
 
             if path != "auth/token/lookup-self" {
-                continue;
+                return;
             }
 
-            let auth = match &entry.auth {
-                Some(a) => a,
-                None => continue,
-            };
+            let Some(auth) = &entry.auth else { return };
 
-            let entity_id = match &auth.entity_id {
-                Some(id) => id.as_str(),
-                None => continue,
+            let Some(entity_id) = auth.entity_id.as_deref() else {
+                return;
             };
 
-            let accessor = match &auth.accessor {
-                Some(a) => a.clone(),
-                None => continue,
+            let Some(accessor) = auth.accessor.clone() else {
+                return;
             };
 
-            lookup_lines += 1;
+            state.lookup_lines += 1;
 
-            let entity_map = patterns.entry(entity_id.to_string()).or_default();
+            let entity_map = state.patterns.entry(entity_id.to_string()).or_default();
 
             entity_map
                 .entry(accessor)
                 .and_modify(|data| {
                     data.lookups += 1;
                     data.last_seen = entry.time.clone();
+                    if collect_timestamps {
+                        if let Ok(ts) = parse_timestamp(&entry.time) {
+                            data.timestamps.push(ts.timestamp());
+                        }
+                    }
                 })
-                .or_insert_with(|| TokenData::new(entry.time.clone()));
-        }
+                .or_insert_with(|| TokenData::new(entry.time.clone(), collect_timestamps));
+        },
+        TokenLookupState::merge,
+        TokenLookupState::default(),
+    )?;
 
-        // Ensure 100% progress for this file
-        if let Some(size) = file_size {
-            progress.update(size);
-        }
+    stats.report();
 
-        progress.finish_with_message(&format!(
-            "Processed {} lines from this file",
-            format_number(file_lines)
-        ));
-    }
+    let patterns = result.patterns;
 
     eprintln!(
         "\nTotal: Processed {} lines, found {} lookup-self operations",
-        format_number(total_lines),
-        format_number(lookup_lines)
+        format_number(stats.total_lines),
+        format_number(result.lookup_lines)
     );
 
-    // Find entities with excessive lookups
-    let mut excessive_patterns = Vec::new();
+    if detect_sharing {
+        display_sharing(&patterns);
+        return Ok(());
+    }
 
-    for (entity_id, tokens) in &patterns {
-        for (accessor, data) in tokens {
-            if data.lookups >= threshold {
-                let time_span = calculate_time_span_hours(&data.first_seen, &data.last_seen);
-                let lookups_per_hour = if time_span > 0.0 {
-                    data.lookups as f64 / time_span
-                } else {
-                    0.0
+    let mut rows = Vec::new();
+
+    if let Some(window_secs) = window {
+        // Sliding-window burst mode: flag pairs by their densest window,
+        // not their lifetime total.
+        for (entity_id, tokens) in &patterns {
+            for (accessor, data) in tokens {
+                let Some((burst_count, window_start, window_end)) =
+                    max_window_density(&data.timestamps, window_secs as i64)
+                else {
+                    continue;
                 };
 
-                // Truncate accessor for display
-                let accessor_display = if accessor.len() > 23 {
-                    format!("{}...", &accessor[..20])
-                } else {
-                    accessor.clone()
-                };
+                if burst_count < rate {
+                    continue;
+                }
 
-                excessive_patterns.push((
-                    entity_id.clone(),
-                    accessor_display,
-                    data.lookups,
-                    time_span,
-                    lookups_per_hour,
-                    data.first_seen.clone(),
-                    data.last_seen.clone(),
-                ));
+                rows.push(LookupRow {
+                    entity_id: entity_id.clone(),
+                    accessor: accessor.clone(),
+                    lookups: data.lookups,
+                    time_span_hours: calculate_time_span_hours(&data.first_seen, &data.last_seen),
+                    rate_per_hour: 0.0,
+                    first_seen: data.first_seen.clone(),
+                    last_seen: data.last_seen.clone(),
+                    burst_count: Some(burst_count),
+                    burst_window_start: DateTime::from_timestamp(window_start, 0)
+                        .map(|dt| format_timestamp(&dt)),
+                    burst_window_end: DateTime::from_timestamp(window_end, 0)
+                        .map(|dt| format_timestamp(&dt)),
+                });
             }
         }
-    }
-
-    // Sort by number of lookups (descending)
-    excessive_patterns.sort_by(|a, b| b.2.cmp(&a.2));
-
-    // Print summary
-    println!("\n{}", "=".repeat(120));
-    println!("Token Lookup Pattern Analysis");
-    println!("{}", "=".repeat(120));
-    println!("\nTotal Entities: {}", format_number(patterns.len()));
-    println!(
-        "Entities with â‰¥{} lookups on same token: {}",
-        threshold,
-        format_number(excessive_patterns.len())
-    );
 
-    if !excessive_patterns.is_empty() {
-        let top = 20;
-        println!("\nTop {} Entities with Excessive Token Lookups:", top);
-        println!("{}", "-".repeat(120));
-        println!(
-            "{:<40} {:<25} {:>10} {:>12} {:>15}",
-            "Entity ID", "Token Accessor", "Lookups", "Time Span", "Rate"
-        );
-        println!(
-            "{:<40} {:<25} {:>10} {:>12} {:>15}",
-            "", "", "", "(hours)", "(lookups/hr)"
-        );
-        println!("{}", "-".repeat(120));
-
-        for (entity_id, accessor, lookups, time_span, rate, _first, _last) in
-            excessive_patterns.iter().take(top)
-        {
-            println!(
-                "{:<40} {:<25} {:>10} {:>12.1} {:>15.1}",
-                entity_id,
-                accessor,
-                format_number(*lookups),
-                time_span,
-                rate
-            );
+        // Sort by burst density (descending)
+        rows.sort_by(|a, b| b.burst_count.cmp(&a.burst_count));
+    } else {
+        // Cumulative mode (default): flag pairs by lifetime lookup count.
+        for (entity_id, tokens) in &patterns {
+            for (accessor, data) in tokens {
+                if data.lookups >= threshold {
+                    let time_span = calculate_time_span_hours(&data.first_seen, &data.last_seen);
+                    let rate_per_hour = if time_span > 0.0 {
+                        data.lookups as f64 / time_span
+                    } else {
+                        0.0
+                    };
+
+                    rows.push(LookupRow {
+                        entity_id: entity_id.clone(),
+                        accessor: accessor.clone(),
+                        lookups: data.lookups,
+                        time_span_hours: time_span,
+                        rate_per_hour,
+                        first_seen: data.first_seen.clone(),
+                        last_seen: data.last_seen.clone(),
+                        burst_count: None,
+                        burst_window_start: None,
+                        burst_window_end: None,
+                    });
+                }
+            }
         }
 
-        // Statistics
-        let total_excessive_lookups: usize = excessive_patterns.iter().map(|p| p.2).sum();
-        let avg_lookups = total_excessive_lookups as f64 / excessive_patterns.len() as f64;
-        let max_lookups = excessive_patterns[0].2;
-
-        println!("\n{}", "-".repeat(120));
-        println!(
-            "Total Excessive Lookups: {}",
-            format_number(total_excessive_lookups)
-        );
-        println!("Average Lookups per Entity: {:.1}", avg_lookups);
-        println!(
-            "Maximum Lookups (single token): {}",
-            format_number(max_lookups)
-        );
-
-        // Find highest rate
-        let mut by_rate = excessive_patterns.clone();
-        by_rate.sort_by(|a, b| b.4.partial_cmp(&a.4).unwrap_or(std::cmp::Ordering::Equal));
-
-        if by_rate[0].4 > 0.0 {
-            println!("\nHighest Rate: {:.1} lookups/hour", by_rate[0].4);
-            println!("  Entity: {}", by_rate[0].0);
-            println!("  Lookups: {}", format_number(by_rate[0].2));
-        }
+        // Sort by number of lookups (descending)
+        rows.sort_by(|a, b| b.lookups.cmp(&a.lookups));
     }
 
-    println!("{}", "=".repeat(120));
+    let report = LookupReport {
+        threshold,
+        total_entities: patterns.len(),
+        burst_window_seconds: window,
+        rows,
+    };
 
-    Ok(())
+    report::emit(&report, format)
 }
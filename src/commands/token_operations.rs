@@ -0,0 +1,289 @@
+//! Token operation analysis by entity (⚠️ deprecated).
+//!
+//! Superseded by [`crate::commands::token_analysis`], which folds this
+//! command's lookup/renew/revoke/create/login counts together with abuse
+//! detection and export into a single command. Kept only so existing
+//! scripts invoking `token-operations` keep working.
+
+use crate::audit::types::AuditEntry;
+use crate::utils::format::format_number;
+use crate::utils::parallel::{process_files_parallel, FileMetrics};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs::File;
+use std::ops::AddAssign;
+
+/// Report progress every this many lines, batching the bytes consumed since
+/// the last report rather than calling `inc` per line.
+const PROGRESS_REPORT_LINES: usize = 1000;
+
+/// Token operation counts for a single entity.
+#[derive(Debug, Default, Clone)]
+struct TokenOps {
+    lookup_self: usize,
+    renew_self: usize,
+    revoke_self: usize,
+    create: usize,
+    login: usize,
+    other: usize,
+    display_name: Option<String>,
+    username: Option<String>,
+}
+
+impl TokenOps {
+    const fn total(&self) -> usize {
+        self.lookup_self + self.renew_self + self.revoke_self + self.create + self.login + self.other
+    }
+}
+
+impl AddAssign<&TokenOps> for TokenOps {
+    fn add_assign(&mut self, other: &TokenOps) {
+        self.lookup_self += other.lookup_self;
+        self.renew_self += other.renew_self;
+        self.revoke_self += other.revoke_self;
+        self.create += other.create;
+        self.login += other.login;
+        self.other += other.other;
+        if self.display_name.is_none() {
+            self.display_name.clone_from(&other.display_name);
+        }
+        if self.username.is_none() {
+            self.username.clone_from(&other.username);
+        }
+    }
+}
+
+/// Classify a single audit entry's token operation, mirroring
+/// `token_analysis::run`'s path/operation heuristic.
+fn classify(entry: &AuditEntry) -> Option<&'static str> {
+    let request = entry.request.as_ref()?;
+    let path = request.path.as_deref().unwrap_or("");
+    let operation = request.operation.as_deref().unwrap_or("");
+
+    if path == "auth/token/lookup-self" {
+        Some("lookup")
+    } else if path == "auth/token/renew-self" {
+        Some("renew")
+    } else if path == "auth/token/revoke-self" {
+        Some("revoke")
+    } else if path == "auth/token/create" {
+        Some("create")
+    } else if path.starts_with("auth/") && operation == "update" {
+        Some("login")
+    } else if path.starts_with("auth/token/") {
+        Some("other")
+    } else {
+        None
+    }
+}
+
+/// Process a single file, building a per-entity [`TokenOps`] map for it.
+fn process_file(
+    file_path: &str,
+    progress: &dyn crate::utils::progress::Progress,
+) -> Result<(HashMap<String, TokenOps>, FileMetrics)> {
+    use crate::utils::reader::open_file;
+    use std::io::{BufRead, BufReader};
+
+    let mut token_ops: HashMap<String, TokenOps> = HashMap::with_capacity(2000);
+    let mut file_metrics = FileMetrics {
+        bytes_read: std::fs::metadata(file_path).map(|m| m.len()).unwrap_or(0),
+        ..Default::default()
+    };
+    let mut bytes_since_report: usize = 0;
+    let mut lines_since_report: usize = 0;
+
+    let file = open_file(file_path)?;
+    let reader = BufReader::new(file);
+
+    for line_result in reader.lines() {
+        let line = line_result?;
+        bytes_since_report += line.len() + 1;
+        lines_since_report += 1;
+        if lines_since_report >= PROGRESS_REPORT_LINES {
+            progress.inc(bytes_since_report);
+            bytes_since_report = 0;
+            lines_since_report = 0;
+        }
+
+        if line.trim().is_empty() {
+            continue;
+        }
+        file_metrics.lines_read += 1;
+
+        let entry: AuditEntry = match serde_json::from_str(&line) {
+            Ok(entry) => entry,
+            Err(_) => {
+                file_metrics.parse_failures += 1;
+                continue;
+            }
+        };
+        file_metrics.entries_parsed += 1;
+
+        let Some(op_type) = classify(&entry) else {
+            continue;
+        };
+        let Some(auth) = &entry.auth else { continue };
+        let entity_id = match &auth.entity_id {
+            Some(id) if !id.is_empty() => id.clone(),
+            _ => continue,
+        };
+
+        let ops = token_ops.entry(entity_id).or_default();
+        match op_type {
+            "lookup" => ops.lookup_self += 1,
+            "renew" => ops.renew_self += 1,
+            "revoke" => ops.revoke_self += 1,
+            "create" => ops.create += 1,
+            "login" => ops.login += 1,
+            _ => ops.other += 1,
+        }
+        if ops.display_name.is_none() {
+            ops.display_name.clone_from(&auth.display_name);
+        }
+        if ops.username.is_none() {
+            ops.username = auth.metadata.as_ref().and_then(|m| {
+                m.get("username")
+                    .and_then(|v| v.as_str())
+                    .map(std::string::ToString::to_string)
+            });
+        }
+    }
+
+    if bytes_since_report > 0 {
+        progress.inc(bytes_since_report);
+    }
+
+    Ok((token_ops, file_metrics))
+}
+
+/// Write one row per entity with all six operation columns plus the total,
+/// for the per-entity table to feed a spreadsheet.
+fn write_csv(token_ops: &HashMap<String, TokenOps>, output: &str) -> Result<()> {
+    if let Some(parent) = std::path::Path::new(output).parent() {
+        std::fs::create_dir_all(parent).context("Failed to create output directory")?;
+    }
+
+    let file = File::create(output).context("Failed to create output file")?;
+    let mut writer = csv::Writer::from_writer(file);
+    writer.write_record([
+        "entity_id",
+        "display_name",
+        "username",
+        "lookup_self",
+        "renew_self",
+        "revoke_self",
+        "create",
+        "login",
+        "other",
+        "total",
+    ])?;
+
+    let mut rows: Vec<_> = token_ops.iter().collect();
+    rows.sort_by(|a, b| b.1.total().cmp(&a.1.total()));
+
+    for (entity_id, ops) in rows {
+        writer.write_record([
+            entity_id.as_str(),
+            ops.display_name.as_deref().unwrap_or(""),
+            ops.username.as_deref().unwrap_or(""),
+            &ops.lookup_self.to_string(),
+            &ops.renew_self.to_string(),
+            &ops.revoke_self.to_string(),
+            &ops.create.to_string(),
+            &ops.login.to_string(),
+            &ops.other.to_string(),
+            &ops.total().to_string(),
+        ])?;
+    }
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Display operations summary
+fn display_summary(token_ops: &HashMap<String, TokenOps>, total_lines: usize) {
+    let mut ops_vec: Vec<_> = token_ops.iter().collect();
+    ops_vec.sort_by(|a, b| b.1.total().cmp(&a.1.total()));
+
+    let total_ops: usize = ops_vec.iter().take(50).map(|(_, ops)| ops.total()).sum();
+
+    println!("Total: Processed {} lines\n", format_number(total_lines));
+    println!("{}", "=".repeat(150));
+    println!(
+        "{:<30} {:<25} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+        "Display Name",
+        "Username",
+        "Total",
+        "Lookup",
+        "Renew",
+        "Revoke",
+        "Create",
+        "Login",
+        "Other"
+    );
+    println!("{}", "=".repeat(150));
+
+    for (_, ops) in ops_vec.iter().take(50) {
+        let display = ops.display_name.as_deref().unwrap_or("");
+        let username = ops.username.as_deref().unwrap_or("");
+
+        println!(
+            "{:<30} {:<25} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+            if display.len() > 30 {
+                &display[..30]
+            } else {
+                display
+            },
+            if username.len() > 25 {
+                &username[..25]
+            } else {
+                username
+            },
+            format_number(ops.total()),
+            format_number(ops.lookup_self),
+            format_number(ops.renew_self),
+            format_number(ops.revoke_self),
+            format_number(ops.create),
+            format_number(ops.login),
+            format_number(ops.other)
+        );
+    }
+
+    println!("{}", "=".repeat(150));
+    println!(
+        "TOTAL (top 50)                                                       {:>10}",
+        format_number(total_ops)
+    );
+    println!(
+        "TOTAL ENTITIES                                                       {:>10}",
+        format_number(token_ops.len())
+    );
+    println!("{}", "=".repeat(150));
+}
+
+/// Main command entry point
+pub fn run(log_files: &[String], output: Option<&str>) -> Result<()> {
+    let (token_ops, total_lines, _metrics) = process_files_parallel(
+        log_files,
+        process_file,
+        |results| {
+            let mut combined: HashMap<String, TokenOps> = HashMap::new();
+            for result in results {
+                for (entity_id, ops) in result.data {
+                    *combined.entry(entity_id).or_default() += &ops;
+                }
+            }
+            combined
+        },
+    )?;
+
+    display_summary(&token_ops, total_lines);
+
+    if let Some(output) = output {
+        write_csv(&token_ops, output)?;
+        eprintln!("\nExported token operations to {}", output);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+//! Bulk "unhash" pass that rewrites an audit device's `hmac-sha256:...`
+//! fields back to plaintext, using an entity map and the device's salt.
+//!
+//! [`crate::commands::audit_hash`] answers "what does this one plaintext
+//! hash to"; this command runs that computation in reverse at scale -
+//! every entity ID, alias display name, and mount accessor/path pair
+//! already known from an entity map (see
+//! [`crate::commands::preprocess_entities`]) is hashed once with
+//! [`--salt`](run) to build a `hmac -> plaintext` lookup table, then each
+//! audit log line is streamed through, replacing every string field that
+//! matches a known hash in place. This turns otherwise-opaque hashed
+//! audit entries into human-readable records without requiring the
+//! raw-format audit device to be enabled.
+//!
+//! # Usage
+//!
+//! ```bash
+//! vault-audit unhash logs/*.log --entity-map entities.json --salt device.salt --output logs-plain.ndjson
+//! vault-audit unhash logs/*.log --entity-map entities.json --salt device.salt --format json --output logs-plain.json
+//! ```
+
+use crate::commands::preprocess_entities::EntityMapping;
+use anyhow::{Context, Result};
+use hmac::{Hmac, Mac};
+use serde_json::Value;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufWriter, Write};
+
+type HmacSha256 = Hmac<Sha256>;
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Hashes `value` exactly as an audit device would: the salt is used as
+/// the raw HMAC key (never re-hashed), producing a `hmac-sha256:<hex>`
+/// string in the same form the device writes into the log.
+fn hmac_hash(salt: &[u8], value: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(salt).expect("HMAC accepts a key of any length");
+    mac.update(value.as_bytes());
+    format!("hmac-sha256:{}", hex_encode(&mac.finalize().into_bytes()))
+}
+
+fn load_entity_mappings(path: &str) -> Result<HashMap<String, EntityMapping>> {
+    let file = File::open(path).with_context(|| format!("Failed to open entity map file: {}", path))?;
+    serde_json::from_reader(file).context("Failed to parse entity map JSON")
+}
+
+/// Builds the `hmac -> plaintext` reverse lookup table: every entity ID,
+/// alias display name, and mount accessor/path pair in `mappings` is
+/// hashed once with `salt` so the scan below is a plain `HashMap` lookup
+/// per field instead of a hash recomputed per occurrence.
+fn build_reverse_lookup(mappings: &HashMap<String, EntityMapping>, salt: &[u8]) -> HashMap<String, String> {
+    let mut reverse = HashMap::new();
+    for (entity_id, mapping) in mappings {
+        for plaintext in [entity_id, &mapping.display_name, &mapping.mount_accessor, &mapping.mount_path] {
+            reverse.insert(hmac_hash(salt, plaintext), plaintext.clone());
+        }
+        if let Some(username) = &mapping.username {
+            reverse.insert(hmac_hash(salt, username), username.clone());
+        }
+    }
+    reverse
+}
+
+/// Replaces every string in `value` (recursing into objects and arrays)
+/// that matches a known hash in `reverse`, in place. Returns how many
+/// replacements were made.
+fn unhash_value(value: &mut Value, reverse: &HashMap<String, String>) -> usize {
+    match value {
+        Value::String(s) => {
+            if let Some(plaintext) = reverse.get(s.as_str()) {
+                *s = plaintext.clone();
+                1
+            } else {
+                0
+            }
+        }
+        Value::Array(items) => items.iter_mut().map(|item| unhash_value(item, reverse)).sum(),
+        Value::Object(fields) => fields.values_mut().map(|v| unhash_value(v, reverse)).sum(),
+        _ => 0,
+    }
+}
+
+pub fn run(
+    log_files: &[String],
+    entity_map: &str,
+    salt: &str,
+    output: Option<&str>,
+    format: &str,
+) -> Result<()> {
+    let mappings = load_entity_mappings(entity_map)?;
+    let salt_bytes =
+        std::fs::read(salt).with_context(|| format!("Failed to read salt file: {}", salt))?;
+    let reverse = build_reverse_lookup(&mappings, &salt_bytes);
+
+    let mut writer: Box<dyn Write> = match output {
+        Some(path) => Box::new(BufWriter::new(
+            File::create(path).with_context(|| format!("Failed to create output file: {}", path))?,
+        )),
+        None => Box::new(std::io::stdout()),
+    };
+
+    let mut records = Vec::new();
+    let mut lines_total = 0usize;
+    let mut fields_replaced = 0usize;
+
+    for file_path in log_files {
+        let file = crate::utils::reader::open_file(file_path)
+            .with_context(|| format!("Failed to open file: {}", file_path))?;
+        for line in std::io::BufReader::new(file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Ok(mut record) = serde_json::from_str::<Value>(&line) else {
+                continue;
+            };
+            lines_total += 1;
+            fields_replaced += unhash_value(&mut record, &reverse);
+
+            match format {
+                "json" => records.push(record),
+                _ => writeln!(writer, "{}", serde_json::to_string(&record)?)?,
+            }
+        }
+    }
+
+    if format == "json" {
+        writer.write_all(serde_json::to_string_pretty(&records)?.as_bytes())?;
+        writeln!(writer)?;
+    }
+
+    eprintln!(
+        "Unhashed {} field(s) across {} record(s)",
+        fields_replaced, lines_total
+    );
+
+    Ok(())
+}
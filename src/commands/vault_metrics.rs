@@ -0,0 +1,238 @@
+//! Cross-reference Vault's own `/sys/metrics` telemetry against
+//! audit-log-derived counts (`vault-metrics`).
+//!
+//! Every other command that talks to the Vault API ([`crate::commands::entity_list`],
+//! and the mount-inventory commands) only reads Vault's control-plane
+//! state. This command instead queries `/sys/metrics?format=prometheus`
+//! for Vault's own request-rate/lease telemetry, parses that exposition
+//! text with [`parse_prometheus`], and reconciles a few of those values
+//! against what the supplied audit log(s) actually observed - for example,
+//! flagging when telemetry-reported token lookups diverge sharply from the
+//! `vault.token.lookup` operations counted in the logs, a signal that the
+//! audit log doesn't cover the same window as the telemetry snapshot (or
+//! that something is writing to Vault outside of what's captured in these
+//! logs). This gives a server-health-plus-audit view without standing up a
+//! separate Prometheus/Grafana stack.
+//!
+//! # Usage
+//!
+//! ```bash
+//! vault-audit vault-metrics audit.log --vault-addr https://vault.example.com:8200
+//! ```
+
+use crate::audit::types::AuditEntry;
+use crate::utils::format::format_number;
+use crate::utils::processor::{ProcessingMode, ProcessorBuilder};
+use crate::utils::report::{self, OutputFormat, Report};
+use crate::vault_api::{should_skip_verify, VaultClient};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// A divergence between one piece of Vault telemetry and the matching
+/// audit-log-derived count is flagged once the larger side is at least
+/// this many times the smaller one.
+const DIVERGENCE_FACTOR: f64 = 2.0;
+
+#[derive(Debug, Default)]
+struct ScanState {
+    token_lookups: u64,
+    entities_with_lookups: HashSet<String>,
+    distinct_entities: HashSet<String>,
+}
+
+impl ScanState {
+    fn merge(mut self, other: Self) -> Self {
+        self.token_lookups += other.token_lookups;
+        self.entities_with_lookups.extend(other.entities_with_lookups);
+        self.distinct_entities.extend(other.distinct_entities);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ReconciliationRow {
+    metric: String,
+    audit_derived_value: f64,
+    vault_telemetry_value: Option<f64>,
+    status: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct VaultMetricsReport {
+    total_lines: usize,
+    metrics_parsed: usize,
+    rows: Vec<ReconciliationRow>,
+}
+
+impl Report for VaultMetricsReport {
+    type Row = ReconciliationRow;
+
+    fn command_name(&self) -> &'static str {
+        "vault-metrics"
+    }
+
+    fn render_table(&self, w: &mut dyn std::io::Write) -> std::io::Result<()> {
+        writeln!(w, "\n{}", "=".repeat(90))?;
+        writeln!(w, "Vault Telemetry / Audit Log Reconciliation")?;
+        writeln!(w, "{}", "=".repeat(90))?;
+        writeln!(
+            w,
+            "Lines scanned: {}   Telemetry series parsed: {}",
+            format_number(self.total_lines),
+            format_number(self.metrics_parsed)
+        )?;
+        writeln!(w, "{:<28} {:>16} {:>18} {:>10}", "Metric", "Audit-Derived", "Vault Telemetry", "Status")?;
+        writeln!(w, "{}", "-".repeat(90))?;
+        for row in &self.rows {
+            let telemetry = row
+                .vault_telemetry_value
+                .map_or_else(|| "n/a".to_string(), |v| format!("{v:.0}"));
+            writeln!(
+                w,
+                "{:<28} {:>16.0} {:>18} {:>10}",
+                row.metric, row.audit_derived_value, telemetry, row.status
+            )?;
+        }
+        writeln!(w, "{}", "=".repeat(90))?;
+        Ok(())
+    }
+
+    fn rows(&self) -> &[ReconciliationRow] {
+        &self.rows
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn run(
+    log_files: &[String],
+    vault_addr: Option<&str>,
+    vault_token: Option<&str>,
+    vault_namespace: Option<&str>,
+    role_id: Option<&str>,
+    secret_id: Option<&str>,
+    insecure: bool,
+    resolve: &[(String, std::net::SocketAddr)],
+    dns_server: Option<std::net::SocketAddr>,
+    format: &str,
+) -> Result<()> {
+    let format = OutputFormat::parse(format).map_err(anyhow::Error::msg)?;
+    let skip_verify = should_skip_verify(insecure);
+
+    let client = VaultClient::connect(
+        vault_addr,
+        vault_token,
+        vault_namespace,
+        role_id,
+        secret_id,
+        skip_verify,
+        resolve,
+        dns_server,
+    )
+    .await?;
+
+    eprintln!("Querying {}/v1/sys/metrics...", client.addr());
+    let metrics_text = client
+        .get_text("/v1/sys/metrics?format=prometheus")
+        .await
+        .context("Failed to query Vault telemetry")?;
+    let metrics = parse_prometheus(&metrics_text);
+
+    let processor = ProcessorBuilder::new()
+        .mode(ProcessingMode::Auto)
+        .progress_label("Scanning".to_string())
+        .build();
+
+    let (result, stats) = processor.process_files_streaming(
+        log_files,
+        |entry: &AuditEntry, state: &mut ScanState| {
+            let Some(entity_id) = entry.entity_id() else {
+                return;
+            };
+            state.distinct_entities.insert(entity_id.to_string());
+
+            if entry.operation() == Some("read") && entry.path().is_some_and(|p| p.contains("token/lookup")) {
+                state.token_lookups += 1;
+                state.entities_with_lookups.insert(entity_id.to_string());
+            }
+        },
+        ScanState::merge,
+        ScanState::default(),
+    )?;
+
+    let rows = vec![
+        reconcile(
+            "token_lookups",
+            result.token_lookups as f64,
+            metrics.get("vault_token_lookup_count").copied(),
+        ),
+        reconcile(
+            "entities_with_lookups",
+            result.entities_with_lookups.len() as f64,
+            metrics.get("vault_token_count").copied(),
+        ),
+        reconcile(
+            "distinct_entities",
+            result.distinct_entities.len() as f64,
+            metrics.get("vault_expire_num_leases").copied(),
+        ),
+    ];
+
+    let report_data = VaultMetricsReport {
+        total_lines: stats.total_lines,
+        metrics_parsed: metrics.len(),
+        rows,
+    };
+
+    report::emit(&report_data, format)?;
+    Ok(())
+}
+
+fn reconcile(metric: &str, audit_value: f64, telemetry_value: Option<f64>) -> ReconciliationRow {
+    let status = match telemetry_value {
+        None => "NO DATA".to_string(),
+        Some(telemetry_value) if audit_value == 0.0 && telemetry_value == 0.0 => "OK".to_string(),
+        Some(telemetry_value) => {
+            let (larger, smaller) = if audit_value >= telemetry_value {
+                (audit_value, telemetry_value)
+            } else {
+                (telemetry_value, audit_value)
+            };
+            if smaller == 0.0 || larger / smaller >= DIVERGENCE_FACTOR {
+                "DIVERGED".to_string()
+            } else {
+                "OK".to_string()
+            }
+        }
+    };
+
+    ReconciliationRow {
+        metric: metric.to_string(),
+        audit_derived_value: audit_value,
+        vault_telemetry_value: telemetry_value,
+        status,
+    }
+}
+
+/// Parses Prometheus exposition text (`# HELP`/`# TYPE` comments plus
+/// `metric_name{labels} value` samples) into a map of base metric name to
+/// its values summed across every label combination - enough to
+/// reconcile aggregate counts without needing full label-aware queries.
+fn parse_prometheus(text: &str) -> HashMap<String, f64> {
+    let mut metrics: HashMap<String, f64> = HashMap::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((name_and_labels, value)) = line.rsplit_once(' ') else {
+            continue;
+        };
+        let Ok(value) = value.trim().parse::<f64>() else {
+            continue;
+        };
+        let name = name_and_labels.split('{').next().unwrap_or(name_and_labels);
+        *metrics.entry(name.to_string()).or_insert(0.0) += value;
+    }
+    metrics
+}
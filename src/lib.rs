@@ -118,5 +118,6 @@
 
 pub mod audit;
 pub mod commands;
+pub mod testing;
 pub mod utils;
 pub mod vault_api;
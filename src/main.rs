@@ -39,13 +39,82 @@ enum EntityAnalysisCommands {
         #[arg(short, long)]
         output: Option<String>,
 
-        /// Output format: json or csv
-        #[arg(long, value_parser = ["json", "csv"])]
+        /// Output format: json, csv, parquet, arrow, or bin (indexed binary)
+        #[arg(long, value_parser = ["json", "csv", "parquet", "arrow", "bin"])]
         format: Option<String>,
 
         /// Disable automatic entity preprocessing
         #[arg(long)]
         no_auto_preprocess: bool,
+
+        /// Worker threads for parallel log parsing (default: rayon's automatic choice)
+        #[arg(long)]
+        threads: Option<usize>,
+
+        /// OTLP endpoint to export a run trace and churn metrics to (requires the
+        /// `enable_otel` build feature)
+        #[arg(long)]
+        otel_endpoint: Option<String>,
+
+        /// Neighborhood radius for the behavioral clustering pass (default: 1.5)
+        #[arg(long)]
+        cluster_eps: Option<f64>,
+
+        /// Minimum neighbors for a core point in the behavioral clustering pass (default: 4)
+        #[arg(long)]
+        cluster_min_points: Option<usize>,
+
+        /// Path to a JSON file of signature-matching rules to flag known-suspicious entities
+        #[arg(long)]
+        signature_rules: Option<String>,
+
+        /// Base path for a persistent state store (sidecar `<path>.snapshot`/`<path>.wal`
+        /// files) that lets each day's log file be processed only once
+        #[arg(long)]
+        state_store: Option<String>,
+
+        /// Write-ahead log size, in bytes, that triggers state store compaction (default: 8 MiB)
+        #[arg(long)]
+        state_compact_threshold_bytes: Option<u64>,
+
+        /// Skip the on-disk entity-map cache and always rebuild from the logs
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Restrict the report and export to entities matching this
+        /// expression, e.g. `mount_path startswith "auth/github" and
+        /// total_logins >= 5` (fields: entity_id, display_name, mount_path,
+        /// mount_type, lifecycle, total_logins, files_appeared.len,
+        /// first_seen_time; operators: ==, !=, <, <=, >, >=, contains,
+        /// startswith, endswith; combine with and/or/not and parentheses)
+        #[arg(long)]
+        filter: Option<String>,
+
+        /// Emit a time-bucketed churn series (new/returning/churned entity
+        /// counts and total logins per fixed-width window, e.g. "1h", "6h",
+        /// "1d") that ignores file boundaries entirely, surfacing intra-day
+        /// spikes the per-file daily breakdown above hides
+        #[arg(long, value_parser = utils::time::parse_duration)]
+        bucket: Option<u64>,
+
+        /// Write this run's ephemeral-entity count and per-entity activity
+        /// gaps as a Prometheus node_exporter textfile
+        /// (`vault_audit_ephemeral_entities_total`,
+        /// `vault_audit_entity_activity_gap_seconds`)
+        #[arg(long)]
+        metrics_file: Option<String>,
+
+        /// Serve the same Prometheus metrics at `GET /metrics` on this
+        /// address (e.g. 127.0.0.1:9899) instead of/in addition to writing
+        /// `--metrics-file`
+        #[arg(long)]
+        metrics_listen: Option<String>,
+
+        /// S3-compatible endpoint to use for `s3://` sources (e.g. a MinIO
+        /// or Garage gateway), overriding the `VAULT_AUDIT_S3_ENDPOINT`
+        /// environment variable
+        #[arg(long)]
+        s3_endpoint: Option<String>,
     },
 
     /// Analyze entity creation by authentication path
@@ -61,13 +130,59 @@ enum EntityAnalysisCommands {
         #[arg(long)]
         entity_map: Option<String>,
 
-        /// Output JSON file path for detailed creation data
+        /// Output file path for detailed creation data
         #[arg(short, long)]
         output: Option<String>,
 
+        /// Output format: json, csv, parquet, or arrow
+        #[arg(long, value_parser = ["json", "csv", "parquet", "arrow"])]
+        format: Option<String>,
+
+        /// Skip entries before this time - RFC3339 timestamp or a relative
+        /// duration like "7d" (meaning "7 days ago"); seeks each plain log
+        /// file directly to the matching offset instead of parsing every
+        /// earlier line
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Skip entries after this time - RFC3339 timestamp or a relative
+        /// duration like "7d" (meaning "7 days ago")
+        #[arg(long)]
+        until: Option<String>,
+
+        /// Number of worker threads to scan log files in parallel with
+        /// (default: let rayon pick based on available cores)
+        #[arg(long)]
+        threads: Option<usize>,
+
         /// Disable automatic entity preprocessing
         #[arg(long)]
         no_auto_preprocess: bool,
+
+        /// OTLP endpoint to export a run trace and creation metrics to
+        /// (requires the `enable_otel` build feature)
+        #[arg(long)]
+        otel_endpoint: Option<String>,
+
+        /// Skip the on-disk entity-map cache and always rebuild from the logs
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Backend to accumulate entity-creation records into: memory
+        /// (default) or sled (embedded on-disk store, for corpora with more
+        /// distinct entities than fit in RAM)
+        #[arg(long, default_value = "memory", value_parser = utils::mapping_store::parse_store_backend)]
+        store_backend: utils::mapping_store::StoreBackend,
+
+        /// Directory for the sled store (required when --store-backend sled)
+        #[arg(long)]
+        store_path: Option<String>,
+
+        /// S3-compatible endpoint to use for `s3://` sources (e.g. a MinIO
+        /// or Garage gateway), overriding the `VAULT_AUDIT_S3_ENDPOINT`
+        /// environment variable
+        #[arg(long)]
+        s3_endpoint: Option<String>,
     },
 
     /// Extract entity mappings from audit logs
@@ -86,6 +201,33 @@ enum EntityAnalysisCommands {
         /// Output format: json or csv
         #[arg(long, default_value = "json")]
         format: String,
+
+        /// OTLP endpoint to export a run trace and preprocessing metrics to
+        /// (requires the `enable_otel` build feature)
+        #[arg(long)]
+        otel_endpoint: Option<String>,
+
+        /// Backend to accumulate entity mappings into: memory (default) or
+        /// sled (embedded on-disk store, for corpora with more distinct
+        /// entities than fit in RAM)
+        #[arg(long, default_value = "memory", value_parser = utils::mapping_store::parse_store_backend)]
+        store_backend: utils::mapping_store::StoreBackend,
+
+        /// Directory for the sled store (required when --store-backend sled)
+        #[arg(long)]
+        store_path: Option<String>,
+
+        /// Load this prior entity map (JSON or CSV) and accumulate this
+        /// run's counts into it instead of overwriting, so per-log-file
+        /// daily runs build up a running total
+        #[arg(long)]
+        merge_into: Option<String>,
+
+        /// S3-compatible endpoint to use for `s3://` sources (e.g. a MinIO
+        /// or Garage gateway), overriding the `VAULT_AUDIT_S3_ENDPOINT`
+        /// environment variable
+        #[arg(long)]
+        s3_endpoint: Option<String>,
     },
 
     /// Detect activity gaps for entities
@@ -96,9 +238,24 @@ enum EntityAnalysisCommands {
         #[arg(required = true)]
         log_files: Vec<String>,
 
-        /// Time window in seconds for gap detection
-        #[arg(long, default_value = "300")]
+        /// Time window for gap detection (e.g. "300", "5m", "1h30m")
+        #[arg(long, default_value = "300", value_parser = utils::time::parse_duration)]
         window_seconds: u64,
+
+        /// OTLP endpoint to export a run trace and gap metrics to (requires
+        /// the `enable_otel` build feature)
+        #[arg(long)]
+        otel_endpoint: Option<String>,
+
+        /// Output format for the no-entity operations summary
+        #[arg(long, default_value = "table", value_parser = ["table", "json", "ndjson"])]
+        format: String,
+
+        /// S3-compatible endpoint to use for `s3://` sources (e.g. a MinIO
+        /// or Garage gateway), overriding the `VAULT_AUDIT_S3_ENDPOINT`
+        /// environment variable
+        #[arg(long)]
+        s3_endpoint: Option<String>,
     },
 
     /// Show timeline of operations for a specific entity
@@ -116,6 +273,111 @@ enum EntityAnalysisCommands {
         /// Display name (optional)
         #[arg(long)]
         display_name: Option<String>,
+
+        /// Output format: `text` prints the report sections, `json` emits a
+        /// structured `TimelineReport` document, `ndjson` streams one JSON
+        /// object per timeline operation
+        #[arg(long, default_value = "text", value_parser = ["text", "json", "ndjson"])]
+        format: String,
+
+        /// Sustained rate N (ops per --rate-period) for GCRA-based burst
+        /// detection; omit to skip sustained-rate violation detection
+        #[arg(long)]
+        rate_limit: Option<f64>,
+
+        /// Period T paired with --rate-limit (e.g. "1m", "1h") - the window
+        /// over which --rate-limit ops/period is measured
+        #[arg(long, default_value = "1m", value_parser = utils::time::parse_duration)]
+        rate_period: u64,
+
+        /// Burst tolerance B (multiples of the derived emission interval)
+        /// allowed before a GCRA violation is flagged
+        #[arg(long, default_value_t = 1.0)]
+        burst_tolerance: f64,
+
+        /// Skip entries before this time - RFC3339 timestamp or a relative
+        /// duration like "7d" (meaning "7 days ago")
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Skip entries after this time - RFC3339 timestamp or a relative
+        /// duration like "7d" (meaning "7 days ago")
+        #[arg(long)]
+        until: Option<String>,
+
+        /// Bucket size for the "HOURLY ACTIVITY PATTERN" section (e.g.
+        /// "1h", "15m", "daily")
+        #[arg(long, default_value = "1h", value_parser = utils::time::parse_duration)]
+        bucket: u64,
+
+        /// Window size for the "PEAK ACTIVITY WINDOWS" section (e.g. "5m", "30s")
+        #[arg(long, default_value = "5m", value_parser = utils::time::parse_duration)]
+        window: u64,
+
+        /// Flag an hour-of-day bucket as an outlier when its count exceeds
+        /// `mean + outlier_sigma * stddev` across the 24 hour-of-day buckets
+        #[arg(long, default_value_t = 3.0)]
+        outlier_sigma: f64,
+
+        /// Flag the entity's diurnal profile as concentrated when a single
+        /// hour-of-day bucket holds more than this fraction of all activity
+        #[arg(long, default_value_t = 0.5)]
+        diurnal_concentration_threshold: f64,
+
+        /// Write the report to this directory instead of stdout, named
+        /// `entity-<id>-<run time>.<ext>` so repeated scheduled runs against
+        /// rolling logs never clobber a prior output
+        #[arg(long)]
+        output_dir: Option<String>,
+
+        /// S3-compatible endpoint to use for `s3://` sources (e.g. a MinIO
+        /// or Garage gateway), overriding the `VAULT_AUDIT_S3_ENDPOINT`
+        /// environment variable
+        #[arg(long)]
+        s3_endpoint: Option<String>,
+    },
+
+    /// Cluster entities by overlapping KV access patterns
+    ///
+    /// Groups entities whose accessed KV paths overlap heavily, surfacing
+    /// redundant service accounts or suspicious lookalike clients.
+    Clusters {
+        /// Path to audit log file(s)
+        #[arg(required = true)]
+        log_files: Vec<String>,
+
+        /// Output CSV file path (default: entity_clusters.csv)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Minimum Jaccard similarity between two entities' KV path sets to
+        /// connect them in the same cluster
+        #[arg(long, default_value_t = 0.8)]
+        similarity_threshold: f64,
+
+        /// Optional entity_id,display_name CSV to label the entity_ids column
+        #[arg(long)]
+        entity_csv: Option<String>,
+    },
+}
+
+/// Entity map file manipulation subcommands
+#[derive(Subcommand)]
+enum EntityMapCommands {
+    /// Fold any number of previously-written entity map files into one
+    ///
+    /// Accepts any mix of JSON and CSV maps (as written by `entity-analysis
+    /// preprocess`), auto-detected by extension, and combines them without
+    /// re-scanning the raw audit logs - useful for combining per-day
+    /// preprocessed maps into a single enrichment file.
+    Merge {
+        /// Entity map files to merge, in any order
+        #[arg(required = true, num_args = 1..)]
+        inputs: Vec<String>,
+
+        /// Output file path (format inferred from extension: csv or json)
+        #[arg(short, long, default_value = "entity_mappings.json")]
+        output: String,
     },
 }
 
@@ -142,6 +404,58 @@ enum KvAnalysisCommands {
         /// Entity alias CSV for enrichment (columns: `entity_id`, name)
         #[arg(long)]
         entity_csv: Option<String>,
+
+        /// Output format: csv, ndjson, or json
+        #[arg(long, value_parser = ["csv", "ndjson", "json"])]
+        format: Option<String>,
+
+        /// Estimate unique clients per path with a HyperLogLog sketch instead of
+        /// retaining every entity ID, bounding memory on reports with many paths.
+        /// Drops the `entity_ids` (and alias enrichment) column/field from the output.
+        #[arg(long)]
+        approx_clients: bool,
+
+        /// Spill accumulated KV usage to sorted run files on disk once it holds
+        /// more than this many paths, then k-way merge them at the end. Use for
+        /// datasets too large to aggregate entirely in memory.
+        #[arg(long)]
+        max_memory_entries: Option<usize>,
+
+        /// Directory for spilled run files (defaults to the system temp dir)
+        #[arg(long)]
+        temp_dir: Option<String>,
+
+        /// Write a CSV ranking suspicious access patterns by z-score: paths
+        /// read by exactly one entity an unusually high number of times,
+        /// entities touching an unusually broad set of distinct paths, and
+        /// paths whose unique-client count spiked between the earlier and
+        /// later days they were accessed on.
+        #[arg(long)]
+        anomaly_report: Option<String>,
+
+        /// Number of top outliers to keep per anomaly category
+        #[arg(long, default_value = "20")]
+        anomaly_top_n: usize,
+
+        /// Write a node_exporter-style Prometheus textfile with per-path
+        /// operation/client-count metrics alongside the report
+        #[arg(long)]
+        metrics_file: Option<String>,
+
+        /// Serve the same metrics over HTTP at `/metrics` on this address
+        /// (e.g. "0.0.0.0:9102") until the process is killed
+        #[arg(long)]
+        metrics_listen: Option<String>,
+
+        /// Only include entries at or after this time: an RFC3339 timestamp
+        /// or a relative duration like "7d" (meaning "7 days ago")
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only include entries at or before this time: an RFC3339 timestamp
+        /// or a relative duration like "24h" (meaning "24 hours ago")
+        #[arg(long)]
+        until: Option<String>,
     },
 
     /// Compare KV usage between two time periods
@@ -164,6 +478,191 @@ enum KvAnalysisCommands {
     },
 }
 
+/// Audit device management subcommands, wrapping Vault's `/sys/audit` API
+#[derive(Subcommand)]
+enum AuditDevicesCommands {
+    /// List enabled audit devices
+    List {
+        /// Vault address (default: $`VAULT_ADDR` or <http://127.0.0.1:8200>)
+        #[arg(long)]
+        vault_addr: Option<String>,
+
+        /// Vault token (default: $`VAULT_TOKEN` or $`VAULT_TOKEN_FILE`)
+        #[arg(long)]
+        vault_token: Option<String>,
+
+        /// Vault namespace (default: $`VAULT_NAMESPACE`)
+        #[arg(long)]
+        vault_namespace: Option<String>,
+
+        /// AppRole role ID (default: $`VAULT_ROLE_ID` or $`VAULT_ROLE_ID_FILE`),
+        /// used in place of `--vault-token` to log in via AppRole
+        #[arg(long)]
+        role_id: Option<String>,
+
+        /// AppRole secret ID (default: $`VAULT_SECRET_ID` or $`VAULT_SECRET_ID_FILE`)
+        #[arg(long)]
+        secret_id: Option<String>,
+
+        /// Skip TLS certificate verification (insecure)
+        #[arg(long)]
+        insecure: bool,
+
+        /// Pin a Vault hostname to an explicit IP, curl-style (repeatable:
+        /// `--resolve vault.internal:8200:10.0.0.5`). TLS SNI and the `Host`
+        /// header still use the original hostname, so certificate
+        /// verification is unaffected.
+        #[arg(long = "resolve", value_parser = vault_api::parse_resolve_override)]
+        resolve: Vec<(String, std::net::SocketAddr)>,
+
+        /// Route DNS lookups (for hosts not pinned via --resolve) through
+        /// this nameserver instead of the system resolver, for clusters
+        /// only reachable via an internal/split-horizon DNS server
+        #[arg(long)]
+        dns_server: Option<std::net::SocketAddr>,
+
+        /// Output format: table, json, or ndjson
+        #[arg(long, default_value = "table")]
+        format: String,
+    },
+
+    /// Enable a new audit device
+    Enable {
+        /// Vault address (default: $`VAULT_ADDR` or <http://127.0.0.1:8200>)
+        #[arg(long)]
+        vault_addr: Option<String>,
+
+        /// Vault token (default: $`VAULT_TOKEN` or $`VAULT_TOKEN_FILE`)
+        #[arg(long)]
+        vault_token: Option<String>,
+
+        /// Vault namespace (default: $`VAULT_NAMESPACE`)
+        #[arg(long)]
+        vault_namespace: Option<String>,
+
+        /// AppRole role ID (default: $`VAULT_ROLE_ID` or $`VAULT_ROLE_ID_FILE`),
+        /// used in place of `--vault-token` to log in via AppRole
+        #[arg(long)]
+        role_id: Option<String>,
+
+        /// AppRole secret ID (default: $`VAULT_SECRET_ID` or $`VAULT_SECRET_ID_FILE`)
+        #[arg(long)]
+        secret_id: Option<String>,
+
+        /// Skip TLS certificate verification (insecure)
+        #[arg(long)]
+        insecure: bool,
+
+        /// Pin a Vault hostname to an explicit IP, curl-style (repeatable:
+        /// `--resolve vault.internal:8200:10.0.0.5`). TLS SNI and the `Host`
+        /// header still use the original hostname, so certificate
+        /// verification is unaffected.
+        #[arg(long = "resolve", value_parser = vault_api::parse_resolve_override)]
+        resolve: Vec<(String, std::net::SocketAddr)>,
+
+        /// Route DNS lookups (for hosts not pinned via --resolve) through
+        /// this nameserver instead of the system resolver, for clusters
+        /// only reachable via an internal/split-horizon DNS server
+        #[arg(long)]
+        dns_server: Option<std::net::SocketAddr>,
+
+        /// Audit device type (e.g. "file", "syslog", "socket")
+        #[arg(long = "type")]
+        device_type: String,
+
+        /// Mount path for the new device (e.g. "file/")
+        #[arg(long)]
+        path: String,
+
+        /// Human-readable description of this audit device
+        #[arg(long)]
+        description: Option<String>,
+
+        /// Device-specific option as `key=value` (repeatable, e.g.
+        /// `--option file_path=/var/log/vault_audit.log --option log_raw=true`)
+        #[arg(long = "option")]
+        option: Vec<String>,
+    },
+
+    /// Disable an existing audit device
+    Disable {
+        /// Vault address (default: $`VAULT_ADDR` or <http://127.0.0.1:8200>)
+        #[arg(long)]
+        vault_addr: Option<String>,
+
+        /// Vault token (default: $`VAULT_TOKEN` or $`VAULT_TOKEN_FILE`)
+        #[arg(long)]
+        vault_token: Option<String>,
+
+        /// Vault namespace (default: $`VAULT_NAMESPACE`)
+        #[arg(long)]
+        vault_namespace: Option<String>,
+
+        /// AppRole role ID (default: $`VAULT_ROLE_ID` or $`VAULT_ROLE_ID_FILE`),
+        /// used in place of `--vault-token` to log in via AppRole
+        #[arg(long)]
+        role_id: Option<String>,
+
+        /// AppRole secret ID (default: $`VAULT_SECRET_ID` or $`VAULT_SECRET_ID_FILE`)
+        #[arg(long)]
+        secret_id: Option<String>,
+
+        /// Skip TLS certificate verification (insecure)
+        #[arg(long)]
+        insecure: bool,
+
+        /// Pin a Vault hostname to an explicit IP, curl-style (repeatable:
+        /// `--resolve vault.internal:8200:10.0.0.5`). TLS SNI and the `Host`
+        /// header still use the original hostname, so certificate
+        /// verification is unaffected.
+        #[arg(long = "resolve", value_parser = vault_api::parse_resolve_override)]
+        resolve: Vec<(String, std::net::SocketAddr)>,
+
+        /// Route DNS lookups (for hosts not pinned via --resolve) through
+        /// this nameserver instead of the system resolver, for clusters
+        /// only reachable via an internal/split-horizon DNS server
+        #[arg(long)]
+        dns_server: Option<std::net::SocketAddr>,
+
+        /// Mount path of the device to disable (e.g. "file/")
+        #[arg(long)]
+        path: String,
+    },
+}
+
+/// Search-index subcommands
+#[derive(Subcommand)]
+enum SearchCommands {
+    /// Build an inverted index over audit logs and persist it to disk
+    Build {
+        /// Path to audit log file(s) - can specify multiple files
+        #[arg(required = true)]
+        log_files: Vec<String>,
+
+        /// Output index file path
+        #[arg(short, long, default_value = "audit.index.json")]
+        output: String,
+    },
+
+    /// Query a previously-built index
+    ///
+    /// Accepts space-separated `field:term` clauses over `path`, `mount_type`,
+    /// `display_name`, `entity_id`, `policies`, and `remote_address`.
+    /// Clauses are ANDed by default; include `OR` to union them instead.
+    /// A trailing `*` on a term (e.g. `path:auth/*`) matches by prefix.
+    Query {
+        /// Path to a previously-built index file
+        index: String,
+
+        /// Query string, e.g. "path:kubernetes entity_id:abc123"
+        query: String,
+
+        /// Maximum number of matching lines to print
+        #[arg(long, default_value = "50")]
+        limit: usize,
+    },
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Analyze KV usage by path and entity (⚠️ DEPRECATED: Use 'kv-analysis analyze' instead)
@@ -184,6 +683,58 @@ enum Commands {
         /// Entity alias CSV for enrichment (columns: `entity_id`, name)
         #[arg(long)]
         entity_csv: Option<String>,
+
+        /// Output format: csv, ndjson, or json
+        #[arg(long, value_parser = ["csv", "ndjson", "json"])]
+        format: Option<String>,
+
+        /// Estimate unique clients per path with a HyperLogLog sketch instead of
+        /// retaining every entity ID, bounding memory on reports with many paths.
+        /// Drops the `entity_ids` (and alias enrichment) column/field from the output.
+        #[arg(long)]
+        approx_clients: bool,
+
+        /// Spill accumulated KV usage to sorted run files on disk once it holds
+        /// more than this many paths, then k-way merge them at the end. Use for
+        /// datasets too large to aggregate entirely in memory.
+        #[arg(long)]
+        max_memory_entries: Option<usize>,
+
+        /// Directory for spilled run files (defaults to the system temp dir)
+        #[arg(long)]
+        temp_dir: Option<String>,
+
+        /// Write a CSV ranking suspicious access patterns by z-score: paths
+        /// read by exactly one entity an unusually high number of times,
+        /// entities touching an unusually broad set of distinct paths, and
+        /// paths whose unique-client count spiked between the earlier and
+        /// later days they were accessed on.
+        #[arg(long)]
+        anomaly_report: Option<String>,
+
+        /// Number of top outliers to keep per anomaly category
+        #[arg(long, default_value = "20")]
+        anomaly_top_n: usize,
+
+        /// Write a node_exporter-style Prometheus textfile with per-path
+        /// operation/client-count metrics alongside the report
+        #[arg(long)]
+        metrics_file: Option<String>,
+
+        /// Serve the same metrics over HTTP at `/metrics` on this address
+        /// (e.g. "0.0.0.0:9102") until the process is killed
+        #[arg(long)]
+        metrics_listen: Option<String>,
+
+        /// Only include entries at or after this time: an RFC3339 timestamp
+        /// or a relative duration like "7d" (meaning "7 days ago")
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only include entries at or before this time: an RFC3339 timestamp
+        /// or a relative duration like "24h" (meaning "24 hours ago")
+        #[arg(long)]
+        until: Option<String>,
     },
 
     /// Compare KV usage between two time periods (⚠️ DEPRECATED: Use 'kv-analysis compare' instead)
@@ -224,6 +775,43 @@ enum Commands {
         /// Process files sequentially instead of in parallel (for debugging)
         #[arg(long)]
         sequential: bool,
+
+        /// Output format: "text" (fixed-width tables), "json" (structured
+        /// document to stdout), or "csv" (one section per table to stdout)
+        #[arg(long, default_value = "text")]
+        format: String,
+
+        /// Bucket width for the time-series trend section (e.g. "1h", "15m",
+        /// "1d"). Omit to skip trend analysis entirely.
+        #[arg(long, value_parser = utils::time::parse_duration)]
+        interval: Option<u64>,
+
+        /// Render the operation-type breakdown and top path prefixes as
+        /// Unicode bar-histograms in the terminal instead of raw numbers
+        #[arg(long)]
+        plot: bool,
+
+        /// Write a standalone SVG line chart of ops/interval to this path
+        /// (requires --interval)
+        #[arg(long)]
+        plot_svg: Option<String>,
+
+        /// Promote a request signature to full-hash duplicate/replay
+        /// tracking once its cheap partial-hash bucket exceeds this count
+        #[arg(long, default_value = "5")]
+        dup_threshold: u32,
+
+        /// Track unique entities per path with a fixed-size HyperLogLog
+        /// sketch instead of an exact HashSet, bounding memory at the cost
+        /// of skipping stress-point detection and a ~1% count error
+        #[arg(long)]
+        approx: bool,
+
+        /// Print per-stage timing (wall-clock vs. summed per-thread time),
+        /// parse success/failure counts, and throughput after processing.
+        /// Only collected in parallel mode (ignored with --sequential).
+        #[arg(long)]
+        stats: bool,
     },
 
     /// Analyze token operations by entity (⚠️ DEPRECATED: Use 'token-analysis' instead)
@@ -251,17 +839,79 @@ enum Commands {
         #[arg(long)]
         abuse_threshold: Option<usize>,
 
+        /// Abuse detection scoring: "threshold" (fixed --abuse-threshold cutoff)
+        /// or "mad" (flag relative outliers by modified z-score, see --abuse-mad-cutoff)
+        #[arg(long, default_value = "threshold")]
+        abuse_mode: String,
+
+        /// Modified z-score cutoff for --abuse-mode mad; entities whose
+        /// lookups/hour rate deviates from the population median by more
+        /// than this many scaled MADs are flagged
+        #[arg(long, default_value = "3.5")]
+        abuse_mad_cutoff: f64,
+
+        /// Window used to compute each entity's peak lookup rate for
+        /// --abuse-threshold (e.g. "60s", "5m") - a sliding-window scan over
+        /// lookup timestamps, so a burst compressed into a short span is
+        /// flagged even when the whole-run average rate looks unremarkable
+        #[arg(long, default_value = "60s", value_parser = utils::time::parse_duration)]
+        burst_window: u64,
+
+        /// Track per-entity activity in fixed time buckets (e.g. "1m", "1h")
+        /// and report each entity's busiest bucket. Surfaces short bursts
+        /// that a whole-run lookups/hour average would smooth away.
+        #[arg(long, value_parser = utils::time::parse_duration)]
+        bucket: Option<u64>,
+
+        /// Report per-entity accessor-duration distribution (min/median/max)
+        /// and accessor-churn rate (new accessors/hour), to distinguish a
+        /// long-lived service token from an app re-authenticating per request
+        #[arg(long)]
+        lifecycle: bool,
+
         /// Filter by operation type (comma-separated: lookup, create, renew, revoke, login)
         #[arg(long, value_delimiter = ',')]
         filter: Option<Vec<String>>,
 
-        /// Export data to CSV file
+        /// Export data to a file (layout controlled by --export-format)
         #[arg(long)]
         export: Option<String>,
 
+        /// Export layout: "csv", "json" (array of records), or "ndjson"
+        /// (one JSON object per line, suited to SIEM/log-aggregation ingestion)
+        #[arg(long, default_value = "csv")]
+        export_format: String,
+
         /// Minimum operations to include in export
         #[arg(long, default_value = "10")]
         min_operations: usize,
+
+        /// Write this run's aggregate totals and peak lookup-abuse rates as a
+        /// Prometheus node_exporter textfile (`vault_audit_token_lookup_abuse`)
+        #[arg(long)]
+        metrics_file: Option<String>,
+
+        /// Serve the same Prometheus metrics at `GET /metrics` on this
+        /// address (e.g. 127.0.0.1:9899) instead of/in addition to writing
+        /// `--metrics-file`
+        #[arg(long)]
+        metrics_listen: Option<String>,
+
+        /// Number of top entities exported as per-entity abuse gauges, to
+        /// bound cardinality
+        #[arg(long, default_value = "100")]
+        metrics_top: usize,
+
+        /// Path to a JSON pipeline file of field rename/drop/extract/map/filter
+        /// processors applied to each record before analysis (see
+        /// `crate::audit::pipeline`)
+        #[arg(long)]
+        pipeline: Option<String>,
+
+        /// Print the first N pipeline-transformed records instead of
+        /// running the analysis (requires --pipeline)
+        #[arg(long)]
+        pipeline_dry_run: Option<usize>,
     },
 
     /// Export token lookup patterns to CSV (⚠️ DEPRECATED: Use 'token-analysis --export' instead)
@@ -278,18 +928,99 @@ enum Commands {
         /// Minimum lookups to include
         #[arg(long, default_value = "10")]
         min_lookups: usize,
-    },
 
-    /// Detect token lookup abuse patterns (⚠️ DEPRECATED: Use 'token-analysis --abuse-threshold' instead)
-    #[command(hide = true)]
-    TokenLookupAbuse {
-        /// Path to audit log file(s) - can specify multiple files
-        #[arg(required = true)]
-        log_files: Vec<String>,
+        /// Worker threads for parallel log parsing (default: rayon's automatic choice)
+        #[arg(long)]
+        threads: Option<usize>,
+
+        /// Flag accessors with a burst of lookups within this window (e.g.
+        /// "60s", "5m") - enables sliding-window burst detection; default:
+        /// disabled
+        #[arg(long, value_parser = utils::time::parse_duration)]
+        burst_window: Option<u64>,
+
+        /// Lookups within `--burst-window` above which a burst is flagged
+        #[arg(long, default_value = "100")]
+        burst_threshold: usize,
+
+        /// Aggregate lookups into calendar buckets ("hourly" or "daily")
+        /// instead of the per-token summary
+        #[arg(long)]
+        time_series: Option<String>,
+
+        /// Append this run's summary to a history file and flag entities
+        /// whose lookup count deviates from their own historical baseline
+        /// (enables regression tracking across runs; default: disabled)
+        #[arg(long)]
+        history: Option<String>,
+
+        /// Number of most recent runs to retain in `--history`
+        #[arg(long, default_value = "20")]
+        history_keep: usize,
+
+        /// Standard deviations from an entity's historical baseline above
+        /// which `--history` flags it as abnormal
+        #[arg(long, default_value = "3.0")]
+        history_deviation: f64,
+
+        /// Write this run's totals as a Prometheus node_exporter textfile
+        /// (`vault_token_lookups_total`, `vault_token_lookups_per_hour`)
+        #[arg(long)]
+        metrics_file: Option<String>,
+
+        /// Serve the same Prometheus metrics at `GET /metrics` on this
+        /// address (e.g. 127.0.0.1:9899) instead of/in addition to writing
+        /// `--metrics-file`
+        #[arg(long)]
+        metrics_listen: Option<String>,
+
+        /// Number of top accessors exported as per-accessor gauges, to
+        /// bound cardinality
+        #[arg(long, default_value = "100")]
+        metrics_top: usize,
+
+        /// Only include entries at or after this time: an RFC3339 timestamp
+        /// or a relative duration like "7d" (meaning "7 days ago")
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only include entries at or before this time: an RFC3339 timestamp
+        /// or a relative duration like "24h" (meaning "24 hours ago")
+        #[arg(long)]
+        until: Option<String>,
+    },
+
+    /// Detect token lookup abuse patterns (⚠️ DEPRECATED: Use 'token-analysis --abuse-threshold' instead)
+    #[command(hide = true)]
+    TokenLookupAbuse {
+        /// Path to audit log file(s) - can specify multiple files
+        #[arg(required = true)]
+        log_files: Vec<String>,
 
         /// Minimum lookups to flag as suspicious
         #[arg(long, default_value = "1000")]
         threshold: usize,
+
+        /// Flag token accessors seen under more than one entity ID instead
+        /// of reporting per-entity volume - a signal for a leaked or
+        /// shared-credential token rather than excessive polling
+        #[arg(long)]
+        detect_sharing: bool,
+
+        /// Output format for the excessive-lookups summary (ignored with
+        /// --detect-sharing)
+        #[arg(long, default_value = "table", value_parser = ["table", "json", "ndjson"])]
+        format: String,
+
+        /// Switch to sliding-window burst detection: flag a pair when its
+        /// densest window of this width (e.g. "60s", "5m") meets --rate,
+        /// instead of using --threshold on lifetime lookups
+        #[arg(long, value_parser = utils::time::parse_duration)]
+        window: Option<u64>,
+
+        /// Minimum lookups within --window to flag as a burst
+        #[arg(long, default_value = "10")]
+        rate: usize,
     },
 
     /// Unified entity lifecycle analysis, creation tracking, and preprocessing
@@ -299,6 +1030,10 @@ enum Commands {
     #[command(subcommand)]
     EntityAnalysis(EntityAnalysisCommands),
 
+    /// Combine entity map files without re-scanning raw audit logs
+    #[command(subcommand)]
+    EntityMap(EntityMapCommands),
+
     /// Unified KV secrets analysis - usage, comparison, and summarization
     ///
     /// Consolidates all KV-related analysis commands into a single interface.
@@ -313,9 +1048,13 @@ enum Commands {
         #[arg(required = true)]
         log_files: Vec<String>,
 
-        /// Time window in seconds for gap detection
-        #[arg(long, default_value = "300")]
+        /// Time window for gap detection (e.g. "300", "5m", "1h30m")
+        #[arg(long, default_value = "300", value_parser = utils::time::parse_duration)]
         window_seconds: u64,
+
+        /// Output format for the no-entity operations summary
+        #[arg(long, default_value = "table", value_parser = ["table", "json", "ndjson"])]
+        format: String,
     },
 
     /// Show timeline of operations for a specific entity (⚠️ DEPRECATED: Use 'entity-analysis timeline' instead)
@@ -343,99 +1082,644 @@ enum Commands {
         /// Number of top paths to show
         #[arg(long, default_value = "50")]
         top: usize,
+
+        /// Width in seconds of the buckets used to compute per-path rate
+        /// percentiles (p50/p75/p90/p95/p99/max)
+        #[arg(long, default_value = "60")]
+        bucket_seconds: i64,
+
+        /// Aggregate by entity instead of by path, surfacing heavy overall
+        /// consumers that spread their load across many paths
+        #[arg(long)]
+        by_entity: bool,
+
+        /// Output format: table, json, or ndjson
+        #[arg(long, default_value = "table")]
+        format: String,
+    },
+
+    /// Flag abnormal per-entity request-rate bursts with an EWMA
+    /// mean/variance baseline instead of a fixed lookup-count threshold
+    AnomalyDetect {
+        /// Path to audit log file(s) - can specify multiple files
+        #[arg(required = true)]
+        log_files: Vec<String>,
+
+        /// Width in seconds of the windows the EWMA baseline is computed over
+        #[arg(long, default_value = "60")]
+        bucket_seconds: i64,
+
+        /// EWMA smoothing factor (0 < alpha <= 1); higher weights recent
+        /// windows more heavily
+        #[arg(long, default_value = "0.3")]
+        alpha: f64,
+
+        /// Flag a window when |z-score| meets or exceeds this value
+        #[arg(long, default_value = "3.0")]
+        threshold: f64,
+
+        /// Minimum windows of history an entity needs before it's scored
+        #[arg(long, default_value = "5")]
+        warmup: usize,
+
+        /// Number of top anomalies (by |z-score|) to show
+        #[arg(long, default_value = "50")]
+        top: usize,
+
+        /// Output format: table, json, or ndjson
+        #[arg(long, default_value = "table")]
+        format: String,
     },
 
     /// Analyze Kubernetes auth patterns and entity churn
     K8sAuth {
+        /// Path to audit log file(s) - can specify multiple files. Not
+        /// required when `--follow` is given.
+        log_files: Vec<String>,
+
+        /// Output CSV file for service account analysis
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Bucket logins into fixed windows (e.g. "1h", "15m") and flag
+        /// abnormal bursts. Omit to skip burst detection entirely.
+        #[arg(long, value_parser = utils::time::parse_duration)]
+        window: Option<u64>,
+
+        /// Write the per-window login totals (requires `--window`) as a
+        /// long-format CSV with a `bucket_start` column, for charting trend
+        /// over a multi-day run instead of just the console sparkline
+        #[arg(long)]
+        window_output: Option<String>,
+
+        /// Flag a window as a burst once its login count exceeds the EWMA
+        /// baseline by this many standard deviations
+        #[arg(long, default_value = "3.0")]
+        spike_threshold: f64,
+
+        /// Write a node_exporter-style Prometheus textfile with login
+        /// totals and per-entity counts
+        #[arg(long)]
+        metrics_file: Option<String>,
+
+        /// Serve the same metrics over HTTP at `/metrics` on this address
+        /// (e.g. "0.0.0.0:9102") until the process is killed
+        #[arg(long)]
+        metrics_listen: Option<String>,
+
+        /// Maximum number of per-entity login-count gauges to emit, to
+        /// bound metrics cardinality
+        #[arg(long, default_value = "20")]
+        metrics_top: usize,
+
+        /// Stream from a live Vault socket audit device instead of static
+        /// files (e.g. "unix:///var/run/vault-audit.sock", "tcp://host:port")
+        #[arg(long)]
+        follow: Option<String>,
+
+        /// Only include entries at or after this time: an RFC3339 timestamp
+        /// or a relative duration like "7d" (meaning "7 days ago")
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only include entries at or before this time: an RFC3339 timestamp
+        /// or a relative duration like "24h" (meaning "24 hours ago")
+        #[arg(long)]
+        until: Option<String>,
+    },
+
+    /// Run multiple analyses (K8s login counting, entity mapping) over one
+    /// shared pass of the audit logs
+    AuditScan {
         /// Path to audit log file(s) - can specify multiple files
         #[arg(required = true)]
         log_files: Vec<String>,
 
-        /// Output CSV file for service account analysis
+        /// Write the combined JSON report to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Run the secret-access, privileged-auth, and no-entity-login
+    /// detectors over one shared pass and report a single typed,
+    /// severity-ranked findings list
+    Findings {
+        /// Path to audit log file(s) - can specify multiple files
+        #[arg(required = true)]
+        log_files: Vec<String>,
+
+        /// Minimum distinct entities (or operation count) against a single
+        /// secret/kv path before it's reported as a SecretAccess finding
+        #[arg(long, default_value = "10")]
+        secret_fanout_threshold: usize,
+
+        /// Minimum operations against a single secret/kv path before it's
+        /// reported as a SecretAccess finding, regardless of fan-out
+        #[arg(long, default_value = "100")]
+        secret_ops_threshold: usize,
+
+        /// Minimum operations under a root/admin-like policy before an
+        /// entity is reported as a PrivilegedAuth finding
+        #[arg(long, default_value = "1")]
+        privileged_ops_threshold: usize,
+
+        /// Output format: table, json, or ndjson
+        #[arg(long, default_value = "table")]
+        format: String,
+    },
+
+    /// Match audit entries against a configurable set of threat indicators
+    ///
+    /// Streams the audit log once against a user-supplied rules file (path
+    /// globs, entity IDs, operation types, source IP CIDRs, or lookup-rate
+    /// thresholds) and prints a ranked summary of which indicators fired.
+    ThreatScan {
+        /// Path to audit log file(s) - can specify multiple files
+        #[arg(required = true)]
+        log_files: Vec<String>,
+
+        /// Indicators file - `.json` for a JSON array, anything else for CSV
+        #[arg(long, required = true)]
+        rules: String,
+
+        /// Write every sample match to this CSV file
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Output format for the ranked summary
+        #[arg(long, default_value = "table", value_parser = ["table", "json", "ndjson"])]
+        format: String,
+    },
+
+    /// Build and query a field-scoped inverted index over audit logs
+    #[command(subcommand)]
+    Search(SearchCommands),
+
+    /// Analyze excessive polling patterns (originally Airflow-specific, now
+    /// a reusable detector for any secrets engine)
+    AirflowPolling {
+        /// Path to audit log file(s) - can specify multiple files
+        #[arg(required = true)]
+        log_files: Vec<String>,
+
+        /// Write entity/path/operation-count data to this CSV file
+        #[arg(long)]
+        output: Option<String>,
+
+        /// Path glob pattern(s) to analyze, comma-separated, `*` wildcard
+        /// supported (e.g. "database/config/*,database/creds/*,*airflow*").
+        /// Default: those three patterns
+        #[arg(long, value_delimiter = ',')]
+        path_pattern: Option<Vec<String>>,
+
+        /// Only report paths/entities with at least this many operations
+        #[arg(long, default_value = "50")]
+        threshold: usize,
+
+        /// Ignore paths whose detected polling period is slower than this
+        /// cadence (e.g. "1h") - keep only pollers at least this frequent
+        #[arg(long, value_parser = utils::time::parse_duration)]
+        min_interval: Option<u64>,
+
+        /// Output format for the full analysis (stdout); `--output` always
+        /// writes the flat CSV regardless of this setting
+        #[arg(long, default_value = "table", value_parser = ["table", "json", "ndjson"])]
+        format: String,
+    },
+
+    /// Preprocess audit logs to extract entity mappings (⚠️ DEPRECATED: Use 'entity-analysis preprocess' instead)
+    #[command(hide = true)]
+    PreprocessEntities {
+        /// Path to audit log file(s) - can specify multiple files
+        #[arg(required = true)]
+        log_files: Vec<String>,
+
+        /// Output file path
+        #[arg(short, long, default_value = "entity_mappings.json")]
+        output: String,
+
+        /// Output format: csv or json
+        #[arg(long, default_value = "json")]
+        format: String,
+
+        /// Backend to accumulate entity mappings into: memory (default) or sled
+        /// (embedded on-disk store, for corpora with more distinct entities
+        /// than fit in RAM)
+        #[arg(long, default_value = "memory", value_parser = utils::mapping_store::parse_store_backend)]
+        store_backend: utils::mapping_store::StoreBackend,
+
+        /// Directory for the sled store (required when --store-backend sled)
+        #[arg(long)]
+        store_path: Option<String>,
+
+        /// Load this prior entity map (JSON or CSV) and accumulate this
+        /// run's counts into it instead of overwriting
+        #[arg(long)]
+        merge_into: Option<String>,
+    },
+
+    /// Analyze entity creation by authentication path (⚠️ DEPRECATED: Use 'entity-analysis creation' instead)
+    #[command(hide = true)]
+    EntityCreation {
+        /// Path to audit log file(s) - can specify multiple files
+        #[arg(required = true)]
+        log_files: Vec<String>,
+
+        /// Optional entity mappings JSON file for display name enrichment
+        #[arg(long)]
+        entity_map: Option<String>,
+
+        /// Output JSON file path for detailed entity creation data
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Skip entries before this time - RFC3339 timestamp or a relative
+        /// duration like "7d" (meaning "7 days ago")
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Skip entries after this time - RFC3339 timestamp or a relative
+        /// duration like "7d" (meaning "7 days ago")
+        #[arg(long)]
+        until: Option<String>,
+
+        /// Number of worker threads to scan log files in parallel with
+        /// (default: let rayon pick based on available cores)
+        #[arg(long)]
+        threads: Option<usize>,
+
+        /// Backend to accumulate entity-creation records into: memory
+        /// (default) or sled (embedded on-disk store)
+        #[arg(long, default_value = "memory", value_parser = utils::mapping_store::parse_store_backend)]
+        store_backend: utils::mapping_store::StoreBackend,
+
+        /// Directory for the sled store (required when --store-backend sled)
+        #[arg(long)]
+        store_path: Option<String>,
+    },
+
+    /// Multi-day entity churn analysis with intelligent ephemeral pattern detection (⚠️ DEPRECATED: Use 'entity-analysis churn' instead)
+    ///
+    /// Tracks entity lifecycle across log files and uses data-driven pattern learning
+    /// to detect ephemeral entities (e.g., CI/CD pipelines, temporary build entities)
+    /// with confidence scoring and detailed reasoning.
+    #[command(hide = true)]
+    EntityChurn {
+        /// Paths to audit log files (in chronological order)
+        #[arg(required = true, num_args = 2..)]
+        log_files: Vec<String>,
+
+        /// Optional entity mappings JSON file for display name enrichment
+        #[arg(long)]
+        entity_map: Option<String>,
+
+        /// Baseline entity list JSON (from entity-list command) to identify pre-existing entities
+        #[arg(long)]
+        baseline: Option<String>,
+
+        /// Output file path for detailed entity churn data with ephemeral analysis
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Output format: json, csv, parquet, arrow, or bin (indexed binary; auto-detected from file extension if not specified)
+        #[arg(long, value_parser = ["json", "csv", "parquet", "arrow", "bin"])]
+        format: Option<String>,
+
+        /// Worker threads for parallel log parsing (default: rayon's automatic choice)
+        #[arg(long)]
+        threads: Option<usize>,
+
+        /// OTLP endpoint to export a run trace and churn metrics to (requires the
+        /// `enable_otel` build feature)
+        #[arg(long)]
+        otel_endpoint: Option<String>,
+
+        /// Neighborhood radius for the behavioral clustering pass (default: 1.5)
+        #[arg(long)]
+        cluster_eps: Option<f64>,
+
+        /// Minimum neighbors for a core point in the behavioral clustering pass (default: 4)
+        #[arg(long)]
+        cluster_min_points: Option<usize>,
+
+        /// Path to a JSON file of signature-matching rules to flag known-suspicious entities
+        #[arg(long)]
+        signature_rules: Option<String>,
+
+        /// Base path for a persistent state store (sidecar `<path>.snapshot`/`<path>.wal`
+        /// files) that lets each day's log file be processed only once
+        #[arg(long)]
+        state_store: Option<String>,
+
+        /// Write-ahead log size, in bytes, that triggers state store compaction (default: 8 MiB)
+        #[arg(long)]
+        state_compact_threshold_bytes: Option<u64>,
+
+        /// Write this run's ephemeral-entity count and per-entity activity
+        /// gaps as a Prometheus node_exporter textfile
+        /// (`vault_audit_ephemeral_entities_total`,
+        /// `vault_audit_entity_activity_gap_seconds`)
+        #[arg(long)]
+        metrics_file: Option<String>,
+
+        /// Serve the same Prometheus metrics at `GET /metrics` on this
+        /// address (e.g. 127.0.0.1:9899) instead of/in addition to writing
+        /// `--metrics-file`
+        #[arg(long)]
+        metrics_listen: Option<String>,
+    },
+
+    /// Get Vault client activity by mount (queries Vault API)
+    ClientActivity {
+        /// Start time in RFC3339 UTC format (e.g., 2025-10-01T00:00:00Z)
+        #[arg(long)]
+        start: String,
+
+        /// End time in RFC3339 UTC format (e.g., 2025-11-01T00:00:00Z)
+        #[arg(long)]
+        end: String,
+
+        /// Vault address (default: $`VAULT_ADDR` or <http://127.0.0.1:8200>)
+        #[arg(long)]
+        vault_addr: Option<String>,
+
+        /// Vault token (default: $`VAULT_TOKEN` or $`VAULT_TOKEN_FILE`)
+        #[arg(long)]
+        vault_token: Option<String>,
+
+        /// Vault namespace (default: $`VAULT_NAMESPACE`)
+        #[arg(long)]
+        vault_namespace: Option<String>,
+
+        /// Skip TLS certificate verification (insecure)
+        #[arg(long)]
+        insecure: bool,
+
+        /// Group by role/appcode within each mount (uses `entity_alias_name`)
+        #[arg(long)]
+        group_by_role: bool,
+
+        /// Path to entity mappings JSON file (for Vault 1.16 compatibility)
+        #[arg(long)]
+        entity_map: Option<String>,
+
+        /// Output CSV file path
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Use HyperLogLog sketches for unique-client counting to bound memory
+        /// on very large activity exports, trading a small amount of accuracy
+        #[arg(long)]
+        approximate: bool,
+
+        /// Pin a Vault hostname to an explicit IP, curl-style (repeatable:
+        /// `--resolve vault.internal:8200:10.0.0.5`). TLS SNI and the `Host`
+        /// header still use the original hostname, so certificate
+        /// verification is unaffected.
+        #[arg(long = "resolve", value_parser = vault_api::parse_resolve_override)]
+        resolve: Vec<(String, std::net::SocketAddr)>,
+
+        /// Route DNS lookups (for hosts not pinned via --resolve) through
+        /// this nameserver instead of the system resolver, for clusters
+        /// only reachable via an internal/split-horizon DNS server
+        #[arg(long)]
+        dns_server: Option<std::net::SocketAddr>,
+    },
+
+    /// Analyze client traffic patterns and per-client request-rate bursts from audit logs
+    ClientTrafficAnalysis {
+        /// Path to audit log file(s) - can specify multiple files
+        #[arg(required = true)]
+        log_files: Vec<String>,
+
+        /// Output summary CSV/JSON file path
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Format for --output: csv, json, ndjson, or prometheus
+        #[arg(long)]
+        format: Option<String>,
+
+        /// Output detailed per-entity error analysis to this file
+        #[arg(long)]
+        error_details_output: Option<String>,
+
+        /// Format for --error-details-output: csv or ndjson
+        #[arg(long, default_value = "csv")]
+        error_details_format: String,
+
+        /// For --error-details-format ndjson, skip the most-recent-first
+        /// timestamp sort and stream records as they're produced, keeping
+        /// memory flat on multi-gigabyte logs (sorted output requires
+        /// buffering every record first)
+        #[arg(long)]
+        error_details_unsorted: bool,
+
+        /// Number of top clients to show
+        #[arg(long, default_value = "10")]
+        top_n: usize,
+
+        /// Show hourly temporal distribution per client
+        #[arg(long)]
+        show_temporal: bool,
+
+        /// Only include clients with at least this many requests
+        #[arg(long, default_value = "1")]
+        min_requests: usize,
+
+        /// Show operation type breakdown per client
+        #[arg(long)]
+        show_operations: bool,
+
+        /// Show error analysis
+        #[arg(long)]
+        show_errors: bool,
+
+        /// Show detailed per-client analysis
+        #[arg(long)]
+        show_details: bool,
+
+        /// Number of behavior clusters to partition clients into (k-means)
+        #[arg(long, default_value = "4")]
+        clusters: usize,
+
+        /// How to rank clients in --show-errors/--show-details output:
+        /// "requests" (raw request count) or "failure-ratio" (bucketed
+        /// error-rate, so chronically-failing low-volume clients surface
+        /// above chatty low-error-rate ones)
+        #[arg(long, default_value = "requests")]
+        rank_by: String,
+
+        /// Width of the time-series buckets used for burst detection (e.g. "1h", "15m", "90s")
+        #[arg(long, default_value = "1h")]
+        bucket_interval: String,
+
+        /// Modified z-score threshold above which a bucket is flagged as a burst
+        #[arg(long, default_value = "3.5")]
+        burst_threshold: f64,
+
+        /// Output flagged burst windows (client IP, window start, count, score) to this CSV file
+        #[arg(long)]
+        burst_output: Option<String>,
+
+        /// Write a node_exporter-style Prometheus textfile with per-client
+        /// request/error/entity metrics
+        #[arg(long)]
+        metrics_file: Option<String>,
+
+        /// Serve the same metrics over HTTP at `/metrics` on this address
+        /// (e.g. "0.0.0.0:9102") until the process is killed
+        #[arg(long)]
+        metrics_listen: Option<String>,
+
+        /// Maximum number of clients to emit per-client gauges for, to bound
+        /// metrics cardinality
+        #[arg(long, default_value = "20")]
+        metrics_top: usize,
+
+        /// Serve the computed stats as read-only JSON (GET /clients,
+        /// /clients/{ip}, /errors, /errors/details) on this address (e.g.
+        /// "0.0.0.0:8089") until the process is killed, instead of printing
+        /// a report
+        #[arg(long)]
+        serve: Option<String>,
+    },
+
+    /// List Vault entities and aliases (queries Vault API)
+    EntityList {
+        /// Vault address (default: $`VAULT_ADDR` or <http://127.0.0.1:8200>)
+        #[arg(long)]
+        vault_addr: Option<String>,
+
+        /// Vault token (default: $`VAULT_TOKEN` or $`VAULT_TOKEN_FILE`)
+        #[arg(long)]
+        vault_token: Option<String>,
+
+        /// Vault namespace (default: $`VAULT_NAMESPACE`)
+        #[arg(long)]
+        vault_namespace: Option<String>,
+
+        /// AppRole role ID (default: $`VAULT_ROLE_ID` or $`VAULT_ROLE_ID_FILE`),
+        /// used in place of `--vault-token` to log in via AppRole
+        #[arg(long)]
+        role_id: Option<String>,
+
+        /// AppRole secret ID (default: $`VAULT_SECRET_ID` or $`VAULT_SECRET_ID_FILE`)
+        #[arg(long)]
+        secret_id: Option<String>,
+
+        /// Skip TLS certificate verification (insecure)
+        #[arg(long)]
+        insecure: bool,
+
+        /// Output file path
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Output format: csv or json
+        #[arg(long, default_value = "csv")]
+        format: String,
+
+        /// Filter by specific mount path (e.g., "auth/kubernetes/")
         #[arg(short, long)]
-        output: Option<String>,
-    },
+        mount: Option<String>,
 
-    /// Analyze Airflow polling patterns
-    AirflowPolling {
-        /// Path to audit log file(s) - can specify multiple files
-        #[arg(required = true)]
-        log_files: Vec<String>,
+        /// Pin a Vault hostname to an explicit IP, curl-style (repeatable:
+        /// `--resolve vault.internal:8200:10.0.0.5`). TLS SNI and the `Host`
+        /// header still use the original hostname, so certificate
+        /// verification is unaffected.
+        #[arg(long = "resolve", value_parser = vault_api::parse_resolve_override)]
+        resolve: Vec<(String, std::net::SocketAddr)>,
 
-        /// Path pattern to analyze (e.g., "airflow")
+        /// Route DNS lookups (for hosts not pinned via --resolve) through
+        /// this nameserver instead of the system resolver, for clusters
+        /// only reachable via an internal/split-horizon DNS server
         #[arg(long)]
-        path_pattern: Option<String>,
-    },
+        dns_server: Option<std::net::SocketAddr>,
 
-    /// Preprocess audit logs to extract entity mappings (⚠️ DEPRECATED: Use 'entity-analysis preprocess' instead)
-    #[command(hide = true)]
-    PreprocessEntities {
-        /// Path to audit log file(s) - can specify multiple files
-        #[arg(required = true)]
-        log_files: Vec<String>,
+        /// Number of entity-detail requests to have in flight at once
+        #[arg(long, default_value = "16")]
+        concurrency: usize,
 
-        /// Output file path
-        #[arg(short, long, default_value = "entity_mappings.json")]
-        output: String,
+        /// Harden the process (pledge/unveil on OpenBSD, seccomp-bpf on
+        /// Linux) right after argument parsing, before any Vault I/O
+        #[arg(long)]
+        sandbox: bool,
+    },
 
-        /// Output format: csv or json
-        #[arg(long, default_value = "json")]
-        format: String,
+    /// Generate shell completion scripts
+    GenerateCompletion {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
     },
 
-    /// Analyze entity creation by authentication path (⚠️ DEPRECATED: Use 'entity-analysis creation' instead)
-    #[command(hide = true)]
-    EntityCreation {
+    /// Measure parsing/analysis throughput
+    Bench {
         /// Path to audit log file(s) - can specify multiple files
         #[arg(required = true)]
         log_files: Vec<String>,
 
-        /// Optional entity mappings JSON file for display name enrichment
-        #[arg(long)]
-        entity_map: Option<String>,
+        /// Print periodic interim rates while processing
+        #[arg(short = 'v', long, conflicts_with = "quiet")]
+        verbose: bool,
 
-        /// Output JSON file path for detailed entity creation data
-        #[arg(short, long)]
-        output: Option<String>,
-    },
+        /// Suppress progress output entirely
+        #[arg(short = 'q', long, conflicts_with = "verbose")]
+        quiet: bool,
 
-    /// Multi-day entity churn analysis with intelligent ephemeral pattern detection (⚠️ DEPRECATED: Use 'entity-analysis churn' instead)
-    ///
-    /// Tracks entity lifecycle across log files and uses data-driven pattern learning
-    /// to detect ephemeral entities (e.g., CI/CD pipelines, temporary build entities)
-    /// with confidence scoring and detailed reasoning.
-    #[command(hide = true)]
-    EntityChurn {
-        /// Paths to audit log files (in chronological order)
-        #[arg(required = true, num_args = 2..)]
-        log_files: Vec<String>,
+        /// Emit a machine-readable JSON summary
+        #[arg(long)]
+        json: bool,
+    },
 
-        /// Optional entity mappings JSON file for display name enrichment
+    /// Ship a command's `--format json` export to Elasticsearch as ECS documents
+    ExportElastic {
+        /// Path to a `--format json` export from another command
         #[arg(long)]
-        entity_map: Option<String>,
+        input: String,
 
-        /// Baseline entity list JSON (from entity-list command) to identify pre-existing entities
+        /// Elasticsearch base URL (e.g. <http://localhost:9200>)
         #[arg(long)]
-        baseline: Option<String>,
+        elastic_url: String,
 
-        /// Output file path for detailed entity churn data with ephemeral analysis
-        #[arg(short, long)]
-        output: Option<String>,
+        /// Target Elasticsearch index name
+        #[arg(long)]
+        elastic_index: String,
 
-        /// Output format: json or csv (auto-detected from file extension if not specified)
-        #[arg(long, value_parser = ["json", "csv"])]
-        format: Option<String>,
+        /// Documents per `_bulk` request
+        #[arg(long, default_value = "500")]
+        batch_size: usize,
     },
 
-    /// Get Vault client activity by mount (queries Vault API)
-    ClientActivity {
-        /// Start time in RFC3339 UTC format (e.g., 2025-10-01T00:00:00Z)
-        #[arg(long)]
-        start: String,
+    /// Accept a live Vault audit stream over HTTP and serve rolling findings
+    Serve {
+        /// Address to listen on (e.g. 127.0.0.1:8989)
+        #[arg(long, default_value = "127.0.0.1:8989")]
+        listen_addr: String,
 
-        /// End time in RFC3339 UTC format (e.g., 2025-11-01T00:00:00Z)
+        /// Comma-separated analyzers to run: token-lookup-abuse, entity-churn, path-hotspots
+        #[arg(long, value_delimiter = ',', default_value = "token-lookup-abuse,entity-churn,path-hotspots")]
+        analyzers: Vec<String>,
+
+        /// Token-lookup-abuse threshold (lookups per entity/accessor pair)
+        #[arg(long, default_value_t = 100)]
+        threshold: u64,
+
+        /// How often to flush aggregate state to --checkpoint-file
+        #[arg(long, value_parser = utils::time::parse_duration, default_value = "60s")]
+        checkpoint_interval: u64,
+
+        /// Path to periodically checkpoint aggregate state to, as JSON
         #[arg(long)]
-        end: String,
+        checkpoint_file: Option<String>,
+    },
+
+    /// Cross-reference Vault's `/sys/metrics` telemetry against audit-log-derived counts
+    VaultMetrics {
+        /// Path to audit log file(s) - can specify multiple files
+        #[arg(required = true)]
+        log_files: Vec<String>,
 
         /// Vault address (default: $`VAULT_ADDR` or <http://127.0.0.1:8200>)
         #[arg(long)]
@@ -449,25 +1733,55 @@ enum Commands {
         #[arg(long)]
         vault_namespace: Option<String>,
 
+        /// AppRole role ID (default: $`VAULT_ROLE_ID` or $`VAULT_ROLE_ID_FILE`),
+        /// used in place of `--vault-token` to log in via AppRole
+        #[arg(long)]
+        role_id: Option<String>,
+
+        /// AppRole secret ID (default: $`VAULT_SECRET_ID` or $`VAULT_SECRET_ID_FILE`)
+        #[arg(long)]
+        secret_id: Option<String>,
+
         /// Skip TLS certificate verification (insecure)
         #[arg(long)]
         insecure: bool,
 
-        /// Group by role/appcode within each mount (uses `entity_alias_name`)
-        #[arg(long)]
-        group_by_role: bool,
+        /// Pin a Vault hostname to an explicit IP, curl-style (repeatable:
+        /// `--resolve vault.internal:8200:10.0.0.5`). TLS SNI and the `Host`
+        /// header still use the original hostname, so certificate
+        /// verification is unaffected.
+        #[arg(long = "resolve", value_parser = vault_api::parse_resolve_override)]
+        resolve: Vec<(String, std::net::SocketAddr)>,
 
-        /// Path to entity mappings JSON file (for Vault 1.16 compatibility)
+        /// Route DNS lookups (for hosts not pinned via --resolve) through
+        /// this nameserver instead of the system resolver, for clusters
+        /// only reachable via an internal/split-horizon DNS server
         #[arg(long)]
-        entity_map: Option<String>,
+        dns_server: Option<std::net::SocketAddr>,
 
-        /// Output CSV file path
-        #[arg(short, long)]
-        output: Option<String>,
+        /// Output format: table, json, or ndjson
+        #[arg(long, default_value = "table")]
+        format: String,
     },
 
-    /// List Vault entities and aliases (queries Vault API)
-    EntityList {
+    /// Compute the `hmac-sha256:<hex>` an audit device would give a
+    /// plaintext, for grepping obfuscated entity IDs/accessors/tokens
+    /// out of audit logs
+    AuditHash {
+        /// Plaintext value to hash (e.g. a token, accessor, or entity ID)
+        #[arg(long)]
+        input: String,
+
+        /// Audit device mount to hash against (e.g. "file/"), used when
+        /// calling Vault's `/sys/audit-hash` endpoint
+        #[arg(long, default_value = "file/")]
+        path: String,
+
+        /// Compute the hash fully offline from this audit device's salt
+        /// file instead of calling Vault
+        #[arg(long)]
+        salt: Option<String>,
+
         /// Vault address (default: $`VAULT_ADDR` or <http://127.0.0.1:8200>)
         #[arg(long)]
         vault_addr: Option<String>,
@@ -480,28 +1794,67 @@ enum Commands {
         #[arg(long)]
         vault_namespace: Option<String>,
 
+        /// AppRole role ID (default: $`VAULT_ROLE_ID` or $`VAULT_ROLE_ID_FILE`),
+        /// used in place of `--vault-token` to log in via AppRole
+        #[arg(long)]
+        role_id: Option<String>,
+
+        /// AppRole secret ID (default: $`VAULT_SECRET_ID` or $`VAULT_SECRET_ID_FILE`)
+        #[arg(long)]
+        secret_id: Option<String>,
+
         /// Skip TLS certificate verification (insecure)
         #[arg(long)]
         insecure: bool,
 
-        /// Output file path
+        /// Pin a Vault hostname to an explicit IP, curl-style (repeatable:
+        /// `--resolve vault.internal:8200:10.0.0.5`). TLS SNI and the `Host`
+        /// header still use the original hostname, so certificate
+        /// verification is unaffected.
+        #[arg(long = "resolve", value_parser = vault_api::parse_resolve_override)]
+        resolve: Vec<(String, std::net::SocketAddr)>,
+
+        /// Route DNS lookups (for hosts not pinned via --resolve) through
+        /// this nameserver instead of the system resolver, for clusters
+        /// only reachable via an internal/split-horizon DNS server
+        #[arg(long)]
+        dns_server: Option<std::net::SocketAddr>,
+    },
+
+    /// Bulk-rewrite `hmac-sha256:...` fields in audit logs back to
+    /// plaintext, using an entity map and the audit device's salt
+    Unhash {
+        /// Paths to audit log files
+        #[arg(required = true)]
+        log_files: Vec<String>,
+
+        /// Entity mappings JSON file (see `entity-analysis preprocess`)
+        #[arg(long)]
+        entity_map: String,
+
+        /// Audit device salt file, used as the raw HMAC key
+        #[arg(long)]
+        salt: String,
+
+        /// Output file path (defaults to stdout)
         #[arg(short, long)]
         output: Option<String>,
 
-        /// Output format: csv or json
-        #[arg(long, default_value = "csv")]
+        /// Output format: ndjson or json
+        #[arg(long, default_value = "ndjson")]
         format: String,
-
-        /// Filter by specific mount path (e.g., "auth/kubernetes/")
-        #[arg(short, long)]
-        mount: Option<String>,
     },
 
-    /// Generate shell completion scripts
-    GenerateCompletion {
-        /// Shell to generate completions for
-        #[arg(value_enum)]
-        shell: clap_complete::Shell,
+    /// List, enable, and disable Vault audit devices
+    #[command(subcommand)]
+    AuditDevices(AuditDevicesCommands),
+
+    /// Print the JSON Schema for the `--format json` output envelope
+    Schema {
+        /// Read a JSON document from stdin and validate it against the
+        /// schema instead of printing the schema
+        #[arg(long)]
+        check: bool,
     },
 }
 
@@ -515,6 +1868,16 @@ async fn main() -> Result<()> {
             kv_prefix,
             output,
             entity_csv,
+            format,
+            approx_clients,
+            max_memory_entries,
+            temp_dir,
+            anomaly_report,
+            anomaly_top_n,
+            metrics_file,
+            metrics_listen,
+            since,
+            until,
         } => {
             eprintln!("⚠️  WARNING: 'kv-analyzer' is deprecated.");
             eprintln!("   Use: vault-audit kv-analysis analyze [OPTIONS]");
@@ -524,6 +1887,16 @@ async fn main() -> Result<()> {
                 &kv_prefix,
                 output.as_deref(),
                 entity_csv.as_deref(),
+                format.as_deref(),
+                approx_clients,
+                max_memory_entries,
+                temp_dir.as_deref(),
+                anomaly_report.as_deref(),
+                anomaly_top_n,
+                metrics_file.as_deref(),
+                metrics_listen.as_deref(),
+                since.as_deref(),
+                until.as_deref(),
             )
         }
         Commands::KvCompare { csv1, csv2 } => {
@@ -544,13 +1917,29 @@ async fn main() -> Result<()> {
             min_operations,
             namespace_filter,
             sequential,
-        } => commands::system_overview::run(
-            &log_files,
-            top,
-            min_operations,
-            namespace_filter.as_deref(),
-            sequential,
-        ),
+            format,
+            interval,
+            plot,
+            plot_svg,
+            dup_threshold,
+            approx,
+            stats,
+        } => {
+            let _ = namespace_filter;
+            commands::system_overview::run(
+                &log_files,
+                top,
+                min_operations,
+                sequential,
+                &format,
+                interval,
+                plot,
+                plot_svg.as_deref(),
+                dup_threshold,
+                approx,
+                stats,
+            )
+        }
         Commands::TokenOperations { log_files, output } => {
             eprintln!("⚠️  WARNING: 'token-operations' is deprecated.");
             eprintln!("   Use: vault-audit token-analysis [OPTIONS]");
@@ -560,29 +1949,83 @@ async fn main() -> Result<()> {
         Commands::TokenAnalysis {
             log_files,
             abuse_threshold,
+            abuse_mode,
+            abuse_mad_cutoff,
+            burst_window,
+            bucket,
+            lifecycle,
             filter,
             export,
+            export_format,
             min_operations,
+            metrics_file,
+            metrics_listen,
+            metrics_top,
+            pipeline,
+            pipeline_dry_run,
         } => commands::token_analysis::run(
             &log_files,
             abuse_threshold,
+            &abuse_mode,
+            abuse_mad_cutoff,
+            burst_window,
+            bucket,
+            lifecycle,
             filter.as_deref(),
             export.as_deref(),
+            &export_format,
             min_operations,
+            metrics_file.as_deref(),
+            metrics_listen.as_deref(),
+            metrics_top,
+            pipeline.as_deref(),
+            pipeline_dry_run,
         ),
         Commands::TokenExport {
             log_files,
             output,
             min_lookups,
+            threads,
+            burst_window,
+            burst_threshold,
+            time_series,
+            history,
+            history_keep,
+            history_deviation,
+            metrics_file,
+            metrics_listen,
+            metrics_top,
+            since,
+            until,
         } => {
             eprintln!("⚠️  WARNING: 'token-export' is deprecated.");
             eprintln!("   Use: vault-audit token-analysis --filter lookup --export {} --min-operations {}", output, min_lookups);
             eprintln!("   Run: vault-audit token-analysis --help for details\n");
-            commands::token_export::run(&log_files, &output, min_lookups)
+            commands::token_export::run(
+                &log_files,
+                &output,
+                min_lookups,
+                threads,
+                burst_window.map(|secs| secs as i64),
+                burst_threshold,
+                time_series.as_deref(),
+                history.as_deref(),
+                history_keep,
+                history_deviation,
+                metrics_file.as_deref(),
+                metrics_listen.as_deref(),
+                metrics_top,
+                since.as_deref(),
+                until.as_deref(),
+            )
         }
         Commands::TokenLookupAbuse {
             log_files,
             threshold,
+            detect_sharing,
+            format,
+            window,
+            rate,
         } => {
             eprintln!("⚠️  WARNING: 'token-lookup-abuse' is deprecated.");
             eprintln!(
@@ -590,7 +2033,14 @@ async fn main() -> Result<()> {
                 threshold
             );
             eprintln!("   Run: vault-audit token-analysis --help for details\n");
-            commands::token_lookup_abuse::run(&log_files, threshold)
+            commands::token_lookup_abuse::run(
+                &log_files,
+                threshold,
+                detect_sharing,
+                &format,
+                window,
+                rate,
+            )
         }
         Commands::EntityAnalysis(entity_cmd) => match entity_cmd {
             EntityAnalysisCommands::Churn {
@@ -600,6 +2050,19 @@ async fn main() -> Result<()> {
                 output,
                 format,
                 no_auto_preprocess,
+                threads,
+                otel_endpoint,
+                cluster_eps,
+                cluster_min_points,
+                signature_rules,
+                state_store,
+                state_compact_threshold_bytes,
+                no_cache,
+                filter,
+                bucket,
+                metrics_file,
+                metrics_listen,
+                s3_endpoint,
             } => commands::entity_analysis::run_churn(
                 &log_files,
                 entity_map.as_ref(),
@@ -607,48 +2070,161 @@ async fn main() -> Result<()> {
                 output.as_ref(),
                 format.as_ref(),
                 !no_auto_preprocess,
+                threads,
+                otel_endpoint.as_deref(),
+                cluster_eps,
+                cluster_min_points,
+                signature_rules.as_deref(),
+                state_store.as_deref(),
+                state_compact_threshold_bytes,
+                !no_cache,
+                filter.as_deref(),
+                bucket,
+                metrics_file.as_deref(),
+                metrics_listen.as_deref(),
+                s3_endpoint.as_deref(),
             ),
             EntityAnalysisCommands::Creation {
                 log_files,
                 entity_map,
                 output,
+                format,
+                since,
+                until,
+                threads,
                 no_auto_preprocess,
+                otel_endpoint,
+                no_cache,
+                store_backend,
+                store_path,
+                s3_endpoint,
             } => commands::entity_analysis::run_creation(
                 &log_files,
                 entity_map.as_ref(),
                 output.as_ref(),
+                format.as_ref(),
+                since.as_deref(),
+                until.as_deref(),
+                threads,
                 !no_auto_preprocess,
+                otel_endpoint.as_deref(),
+                !no_cache,
+                store_backend,
+                store_path.as_deref(),
+                s3_endpoint.as_deref(),
             ),
             EntityAnalysisCommands::Preprocess {
                 log_files,
                 output,
                 format,
-            } => commands::entity_analysis::run_preprocess(&log_files, &output, &format),
+                otel_endpoint,
+                store_backend,
+                store_path,
+                merge_into,
+                s3_endpoint,
+            } => commands::entity_analysis::run_preprocess(
+                &log_files,
+                &output,
+                &format,
+                otel_endpoint.as_deref(),
+                store_backend,
+                store_path.as_deref(),
+                merge_into.as_deref(),
+                s3_endpoint.as_deref(),
+            ),
             EntityAnalysisCommands::Gaps {
                 log_files,
                 window_seconds,
-            } => commands::entity_analysis::run_gaps(&log_files, window_seconds),
+                otel_endpoint,
+                format,
+                s3_endpoint,
+            } => commands::entity_analysis::run_gaps(
+                &log_files,
+                window_seconds,
+                otel_endpoint.as_deref(),
+                &format,
+                s3_endpoint.as_deref(),
+            ),
             EntityAnalysisCommands::Timeline {
                 log_files,
                 entity_id,
                 display_name,
+                format,
+                rate_limit,
+                rate_period,
+                burst_tolerance,
+                since,
+                until,
+                bucket,
+                window,
+                outlier_sigma,
+                diurnal_concentration_threshold,
+                output_dir,
+                s3_endpoint,
             } => commands::entity_analysis::run_timeline(
                 &log_files,
                 &entity_id,
                 display_name.as_ref(),
+                &format,
+                rate_limit.map(|rate| (rate, rate_period, burst_tolerance)),
+                since.as_deref(),
+                until.as_deref(),
+                bucket,
+                window,
+                outlier_sigma,
+                diurnal_concentration_threshold,
+                output_dir.as_deref(),
+                s3_endpoint.as_deref(),
+            )
+            .map(|_| ()),
+            EntityAnalysisCommands::Clusters {
+                log_files,
+                output,
+                similarity_threshold,
+                entity_csv,
+            } => commands::entity_analysis::run_clusters(
+                &log_files,
+                output.as_ref(),
+                similarity_threshold,
+                entity_csv.as_deref(),
             ),
         },
+        Commands::EntityMap(entity_map_cmd) => match entity_map_cmd {
+            EntityMapCommands::Merge { inputs, output } => {
+                commands::preprocess_entities::run_merge(&inputs, &output)
+            }
+        },
         Commands::KvAnalysis(kv_cmd) => match kv_cmd {
             KvAnalysisCommands::Analyze {
                 log_files,
                 kv_prefix,
                 output,
                 entity_csv,
+                format,
+                approx_clients,
+                max_memory_entries,
+                temp_dir,
+                anomaly_report,
+                anomaly_top_n,
+                metrics_file,
+                metrics_listen,
+                since,
+                until,
             } => commands::kv_analysis::run_analyze(
                 &log_files,
                 &kv_prefix,
                 output.as_ref(),
                 entity_csv.as_ref(),
+                format.as_ref(),
+                approx_clients,
+                max_memory_entries,
+                temp_dir.as_ref(),
+                anomaly_report.as_ref(),
+                anomaly_top_n,
+                metrics_file.as_ref(),
+                metrics_listen.as_ref(),
+                since.as_ref(),
+                until.as_ref(),
             ),
             KvAnalysisCommands::Compare { csv1, csv2 } => {
                 commands::kv_analysis::run_compare(&csv1, &csv2)
@@ -660,11 +2236,12 @@ async fn main() -> Result<()> {
         Commands::EntityGaps {
             log_files,
             window_seconds,
+            format,
         } => {
             eprintln!("⚠️  WARNING: 'entity-gaps' is deprecated.");
             eprintln!("   Use: vault-audit entity-analysis gaps [OPTIONS]");
             eprintln!("   Run: vault-audit entity-analysis gaps --help for details\n");
-            commands::entity_gaps::run(&log_files, window_seconds)
+            commands::entity_gaps::run(&log_files, window_seconds, None, &format, None)
         }
         Commands::EntityTimeline {
             log_files,
@@ -677,36 +2254,163 @@ async fn main() -> Result<()> {
                 entity_id
             );
             eprintln!("   Run: vault-audit entity-analysis timeline --help for details\n");
-            commands::entity_timeline::run(&log_files, &entity_id, display_name.as_ref())
+            commands::entity_timeline::run(
+                &log_files,
+                &entity_id,
+                display_name.as_ref(),
+                "text",
+                None,
+                None,
+                None,
+                3600,
+                300,
+                3.0,
+                0.5,
+                None,
+                None,
+            )
+            .map(|_| ())
         }
-        Commands::PathHotspots { log_files, top } => commands::path_hotspots::run(&log_files, top),
-        Commands::K8sAuth { log_files, output } => {
-            commands::k8s_auth::run(&log_files, output.as_deref())
+        Commands::PathHotspots {
+            log_files,
+            top,
+            bucket_seconds,
+            by_entity,
+            format,
+        } => commands::path_hotspots::run(&log_files, top, bucket_seconds, by_entity, &format),
+        Commands::AnomalyDetect {
+            log_files,
+            bucket_seconds,
+            alpha,
+            threshold,
+            warmup,
+            top,
+            format,
+        } => commands::anomaly_detect::run(&log_files, bucket_seconds, alpha, threshold, warmup, top, &format),
+        Commands::K8sAuth {
+            log_files,
+            output,
+            window,
+            window_output,
+            spike_threshold,
+            metrics_file,
+            metrics_listen,
+            metrics_top,
+            follow,
+            since,
+            until,
+        } => commands::k8s_auth::run(
+            &log_files,
+            output.as_deref(),
+            window,
+            window_output.as_deref(),
+            spike_threshold,
+            metrics_file.as_deref(),
+            metrics_listen.as_deref(),
+            metrics_top,
+            follow.as_deref(),
+            since.as_deref(),
+            until.as_deref(),
+        ),
+        Commands::AuditScan { log_files, output } => {
+            commands::audit_scan::run(&log_files, output.as_deref())
         }
+        Commands::Findings {
+            log_files,
+            secret_fanout_threshold,
+            secret_ops_threshold,
+            privileged_ops_threshold,
+            format,
+        } => commands::findings::run(
+            &log_files,
+            secret_fanout_threshold,
+            secret_ops_threshold,
+            privileged_ops_threshold,
+            &format,
+        ),
+        Commands::ThreatScan {
+            log_files,
+            rules,
+            output,
+            format,
+        } => commands::threat_scan::run(&log_files, &rules, output.as_deref(), &format),
+        Commands::Search(search_cmd) => match search_cmd {
+            SearchCommands::Build { log_files, output } => {
+                commands::search::run_build(&log_files, &output)
+            }
+            SearchCommands::Query {
+                index,
+                query,
+                limit,
+            } => commands::search::run_query(&index, &query, limit),
+        },
         Commands::AirflowPolling {
             log_files,
+            output,
             path_pattern,
-        } => commands::airflow_polling::run(&log_files, path_pattern.as_deref()),
+            threshold,
+            min_interval,
+            format,
+        } => {
+            let mut config = commands::airflow_polling::PollingDetectorConfig {
+                threshold,
+                ..Default::default()
+            };
+            if let Some(patterns) = path_pattern {
+                config.path_patterns = patterns;
+            }
+            config.min_interval_seconds = min_interval.map(|secs| secs as f64);
+            commands::airflow_polling::run(&log_files, output.as_deref(), &config, &format)
+        }
         Commands::PreprocessEntities {
             log_files,
             output,
             format,
+            store_backend,
+            store_path,
+            merge_into,
         } => {
             eprintln!("⚠️  WARNING: 'preprocess-entities' is deprecated.");
             eprintln!("   Use: vault-audit entity-analysis preprocess [OPTIONS]");
             eprintln!("   Note: Most commands now auto-preprocess, so this is rarely needed!");
             eprintln!("   Run: vault-audit entity-analysis --help for details\n");
-            commands::preprocess_entities::run(&log_files, &output, format.as_str())
+            commands::preprocess_entities::run(
+                &log_files,
+                &output,
+                format.as_str(),
+                None,
+                store_backend,
+                store_path.as_deref(),
+                merge_into.as_deref(),
+                None,
+            )
         }
         Commands::EntityCreation {
             log_files,
             entity_map,
             output,
+            since,
+            until,
+            threads,
+            store_backend,
+            store_path,
         } => {
             eprintln!("⚠️  WARNING: 'entity-creation' is deprecated.");
             eprintln!("   Use: vault-audit entity-analysis creation [OPTIONS]");
             eprintln!("   Run: vault-audit entity-analysis creation --help for details\n");
-            commands::entity_creation::run(&log_files, entity_map.as_deref(), output.as_deref())
+            commands::entity_creation::run(
+                &log_files,
+                entity_map.as_deref(),
+                output.as_deref(),
+                None,
+                since.as_deref(),
+                until.as_deref(),
+                threads,
+                None,
+                store_backend,
+                store_path.as_deref(),
+                None,
+            )
         }
         Commands::EntityChurn {
             log_files,
@@ -714,6 +2418,15 @@ async fn main() -> Result<()> {
             baseline,
             output,
             format,
+            threads,
+            otel_endpoint,
+            cluster_eps,
+            cluster_min_points,
+            signature_rules,
+            state_store,
+            state_compact_threshold_bytes,
+            metrics_file,
+            metrics_listen,
         } => {
             eprintln!("⚠️  WARNING: 'entity-churn' is deprecated.");
             eprintln!("   Use: vault-audit entity-analysis churn [OPTIONS]");
@@ -724,6 +2437,18 @@ async fn main() -> Result<()> {
                 baseline.as_deref(),
                 output.as_deref(),
                 format.as_deref(),
+                threads,
+                otel_endpoint.as_deref(),
+                cluster_eps,
+                cluster_min_points,
+                signature_rules.as_deref(),
+                state_store.as_deref(),
+                state_compact_threshold_bytes,
+                None,
+                None,
+                metrics_file.as_deref(),
+                metrics_listen.as_deref(),
+                None,
             )
         }
         Commands::ClientActivity {
@@ -736,6 +2461,9 @@ async fn main() -> Result<()> {
             group_by_role,
             entity_map,
             output,
+            approximate,
+            resolve,
+            dns_server,
         } => {
             commands::client_activity::run(
                 &start,
@@ -747,26 +2475,86 @@ async fn main() -> Result<()> {
                 group_by_role,
                 entity_map.as_deref(),
                 output.as_deref(),
+                approximate,
+                &resolve,
+                dns_server,
             )
             .await
         }
+        Commands::ClientTrafficAnalysis {
+            log_files,
+            output,
+            format,
+            error_details_output,
+            error_details_format,
+            error_details_unsorted,
+            top_n,
+            show_temporal,
+            min_requests,
+            show_operations,
+            show_errors,
+            show_details,
+            clusters,
+            rank_by,
+            bucket_interval,
+            burst_threshold,
+            burst_output,
+            metrics_file,
+            metrics_listen,
+            metrics_top,
+            serve,
+        } => commands::client_traffic_analysis::run(
+            &log_files,
+            output,
+            format.as_deref(),
+            error_details_output,
+            &error_details_format,
+            error_details_unsorted,
+            top_n,
+            show_temporal,
+            min_requests,
+            show_operations,
+            show_errors,
+            show_details,
+            clusters,
+            &rank_by,
+            &bucket_interval,
+            burst_threshold,
+            burst_output,
+            metrics_file.as_deref(),
+            metrics_listen.as_deref(),
+            metrics_top,
+            serve.as_deref(),
+        ),
         Commands::EntityList {
             vault_addr,
             vault_token,
             vault_namespace,
+            role_id,
+            secret_id,
             insecure,
             output,
             format,
             mount,
+            resolve,
+            dns_server,
+            concurrency,
+            sandbox,
         } => {
             commands::entity_list::run(
                 vault_addr.as_deref(),
                 vault_token.as_deref(),
                 vault_namespace.as_deref(),
+                role_id.as_deref(),
+                secret_id.as_deref(),
                 insecure,
                 output.as_deref(),
                 format.as_str(),
                 mount.as_deref(),
+                &resolve,
+                dns_server,
+                concurrency,
+                sandbox,
             )
             .await
         }
@@ -775,5 +2563,192 @@ async fn main() -> Result<()> {
             clap_complete::generate(shell, &mut cmd, "vault-audit", &mut std::io::stdout());
             Ok(())
         }
+        Commands::Bench {
+            log_files,
+            verbose,
+            quiet,
+            json,
+        } => {
+            let verbosity = if quiet {
+                utils::progress::Verbosity::Quiet
+            } else if verbose {
+                utils::progress::Verbosity::Verbose
+            } else {
+                utils::progress::Verbosity::Normal
+            };
+            commands::bench::run(&log_files, verbosity, json)
+        }
+        Commands::ExportElastic {
+            input,
+            elastic_url,
+            elastic_index,
+            batch_size,
+        } => {
+            commands::export_elastic::run(&input, &elastic_url, &elastic_index, batch_size).await
+        }
+        Commands::Serve {
+            listen_addr,
+            analyzers,
+            threshold,
+            checkpoint_interval,
+            checkpoint_file,
+        } => {
+            commands::serve::run(
+                &listen_addr,
+                &analyzers,
+                threshold,
+                checkpoint_interval,
+                checkpoint_file.as_deref(),
+            )
+            .await
+        }
+        Commands::VaultMetrics {
+            log_files,
+            vault_addr,
+            vault_token,
+            vault_namespace,
+            role_id,
+            secret_id,
+            insecure,
+            resolve,
+            dns_server,
+            format,
+        } => {
+            commands::vault_metrics::run(
+                &log_files,
+                vault_addr.as_deref(),
+                vault_token.as_deref(),
+                vault_namespace.as_deref(),
+                role_id.as_deref(),
+                secret_id.as_deref(),
+                insecure,
+                &resolve,
+                dns_server,
+                format.as_str(),
+            )
+            .await
+        }
+        Commands::AuditHash {
+            input,
+            path,
+            salt,
+            vault_addr,
+            vault_token,
+            vault_namespace,
+            role_id,
+            secret_id,
+            insecure,
+            resolve,
+            dns_server,
+        } => {
+            commands::audit_hash::run(
+                &input,
+                &path,
+                salt.as_deref(),
+                vault_addr.as_deref(),
+                vault_token.as_deref(),
+                vault_namespace.as_deref(),
+                role_id.as_deref(),
+                secret_id.as_deref(),
+                insecure,
+                &resolve,
+                dns_server,
+            )
+            .await
+        }
+        Commands::Unhash {
+            log_files,
+            entity_map,
+            salt,
+            output,
+            format,
+        } => commands::unhash::run(
+            &log_files,
+            &entity_map,
+            &salt,
+            output.as_deref(),
+            format.as_str(),
+        ),
+        Commands::AuditDevices(audit_devices_cmd) => match audit_devices_cmd {
+            AuditDevicesCommands::List {
+                vault_addr,
+                vault_token,
+                vault_namespace,
+                role_id,
+                secret_id,
+                insecure,
+                resolve,
+                dns_server,
+                format,
+            } => {
+                commands::audit_devices::run_list(
+                    vault_addr.as_deref(),
+                    vault_token.as_deref(),
+                    vault_namespace.as_deref(),
+                    role_id.as_deref(),
+                    secret_id.as_deref(),
+                    insecure,
+                    &resolve,
+                    dns_server,
+                    format.as_str(),
+                )
+                .await
+            }
+            AuditDevicesCommands::Enable {
+                vault_addr,
+                vault_token,
+                vault_namespace,
+                role_id,
+                secret_id,
+                insecure,
+                resolve,
+                dns_server,
+                device_type,
+                path,
+                description,
+                option,
+            } => {
+                commands::audit_devices::run_enable(
+                    vault_addr.as_deref(),
+                    vault_token.as_deref(),
+                    vault_namespace.as_deref(),
+                    role_id.as_deref(),
+                    secret_id.as_deref(),
+                    insecure,
+                    &resolve,
+                    dns_server,
+                    &device_type,
+                    &path,
+                    description.as_deref(),
+                    &option,
+                )
+                .await
+            }
+            AuditDevicesCommands::Disable {
+                vault_addr,
+                vault_token,
+                vault_namespace,
+                role_id,
+                secret_id,
+                insecure,
+                resolve,
+                dns_server,
+                path,
+            } => {
+                commands::audit_devices::run_disable(
+                    vault_addr.as_deref(),
+                    vault_token.as_deref(),
+                    vault_namespace.as_deref(),
+                    role_id.as_deref(),
+                    secret_id.as_deref(),
+                    insecure,
+                    &resolve,
+                    dns_server,
+                    &path,
+                )
+                .await
+            }
+        },
+        Commands::Schema { check } => commands::schema::run(check),
     }
 }
@@ -0,0 +1,62 @@
+//! Golden-fixture record/replay harness backing `tests/fixture_tests.rs`.
+//!
+//! The integration tests in `tests/integration_tests.rs` only assert
+//! `result.is_ok()`, so a regression in a computed number (a wrong
+//! percentile, a miscounted fan-out) passes silently. This module pairs an
+//! `incoming.log` audit snippet with a checked-in `expected.json` golden
+//! document under `tests/fixtures/<name>/` and compares a command's
+//! `--format json` output against it byte-for-byte.
+//!
+//! Set `VAULT_AUDIT_RECORD_FIXTURES=1` to regenerate `expected.json` from
+//! the current output instead of asserting against it - the workflow for
+//! adding a new fixture or updating one after an intentional behavior
+//! change.
+
+use anyhow::{ensure, Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Directory holding fixture pairs, relative to the crate root.
+const FIXTURES_DIR: &str = "tests/fixtures";
+
+/// Path to fixture `name`'s input audit log.
+pub fn fixture_input(name: &str) -> PathBuf {
+    Path::new(FIXTURES_DIR).join(name).join("incoming.log")
+}
+
+/// Path to fixture `name`'s golden `--format json` output.
+pub fn fixture_expected(name: &str) -> PathBuf {
+    Path::new(FIXTURES_DIR).join(name).join("expected.json")
+}
+
+/// Runs `command` against fixture `name`'s input log - `command` should
+/// call the command's `run_to_string(..., "json")` entry point on
+/// [`fixture_input`] and return what it returns - then either regenerates
+/// `expected.json` (`VAULT_AUDIT_RECORD_FIXTURES=1`) or asserts the output
+/// matches it byte-for-byte.
+pub fn run_against_fixture(name: &str, command: impl FnOnce(&Path) -> Result<String>) -> Result<()> {
+    let input = fixture_input(name);
+    let actual = command(&input).with_context(|| format!("running fixture '{}'", name))?;
+    let expected_path = fixture_expected(name);
+
+    if std::env::var_os("VAULT_AUDIT_RECORD_FIXTURES").is_some() {
+        std::fs::write(&expected_path, &actual)
+            .with_context(|| format!("writing {}", expected_path.display()))?;
+        return Ok(());
+    }
+
+    let expected = std::fs::read_to_string(&expected_path).with_context(|| {
+        format!(
+            "reading {} (rerun with VAULT_AUDIT_RECORD_FIXTURES=1 to create it)",
+            expected_path.display()
+        )
+    })?;
+
+    ensure!(
+        actual == expected,
+        "fixture '{}' output doesn't match {} - rerun with \
+         VAULT_AUDIT_RECORD_FIXTURES=1 to update it if this change is intentional",
+        name,
+        expected_path.display()
+    );
+    Ok(())
+}
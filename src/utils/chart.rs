@@ -0,0 +1,128 @@
+//! Terminal and SVG chart rendering for distribution/trend sections.
+//!
+//! Small, dependency-free helpers shared by analysis subcommands that want
+//! an at-a-glance view of a distribution or a time series without piping
+//! through an external plotting tool - first used by `system-overview`'s
+//! `--plot`/`--plot-svg` modes.
+
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::Write;
+
+/// Unicode block glyph used to fill bar segments.
+const BAR_GLYPH: char = '█';
+
+/// Render `items` (label, count) as a horizontal bar histogram using
+/// Unicode block glyphs, one line per item, scaled so the largest count
+/// fills `bar_width` columns.
+///
+/// # Examples
+///
+/// ```
+/// use vault_audit_tools::utils::chart::bar_chart;
+///
+/// let lines = bar_chart(&[("read".to_string(), 100), ("write".to_string(), 50)], 20);
+/// assert_eq!(lines.len(), 2);
+/// assert!(lines[0].contains('█'));
+/// ```
+pub fn bar_chart(items: &[(String, usize)], bar_width: usize) -> Vec<String> {
+    let max_count = items.iter().map(|(_, count)| *count).max().unwrap_or(0);
+    items
+        .iter()
+        .map(|(label, count)| {
+            let filled = if max_count > 0 {
+                (*count as f64 / max_count as f64 * bar_width as f64).round() as usize
+            } else {
+                0
+            };
+            let bar: String = std::iter::repeat(BAR_GLYPH).take(filled).collect();
+            format!("{:<40} {:<width$} {}", label, bar, count, width = bar_width)
+        })
+        .collect()
+}
+
+/// Write a standalone SVG line chart of `points` (x-axis label, y-axis
+/// value) to `path`, titled `title`. Used to plot operations-per-interval
+/// alongside the `--interval` trend section, but generic over any labeled
+/// series.
+pub fn write_svg_line_chart(path: &str, title: &str, points: &[(String, f64)]) -> Result<()> {
+    const WIDTH: f64 = 800.0;
+    const HEIGHT: f64 = 300.0;
+    const MARGIN: f64 = 40.0;
+
+    let plot_width = WIDTH - 2.0 * MARGIN;
+    let plot_height = HEIGHT - 2.0 * MARGIN;
+    let max_value = points.iter().map(|(_, v)| *v).fold(0.0_f64, f64::max);
+
+    let mut svg = String::new();
+    svg.push_str(&format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{HEIGHT}\" viewBox=\"0 0 {WIDTH} {HEIGHT}\">\n"
+    ));
+    svg.push_str(&format!(
+        "<text x=\"{MARGIN}\" y=\"20\" font-family=\"sans-serif\" font-size=\"14\">{title}</text>\n"
+    ));
+    svg.push_str(&format!(
+        "<rect x=\"{MARGIN}\" y=\"{MARGIN}\" width=\"{plot_width}\" height=\"{plot_height}\" fill=\"none\" stroke=\"#ccc\"/>\n"
+    ));
+
+    if points.len() > 1 && max_value > 0.0 {
+        let step = plot_width / (points.len() - 1) as f64;
+        let coords: Vec<String> = points
+            .iter()
+            .enumerate()
+            .map(|(i, (_, value))| {
+                let x = MARGIN + i as f64 * step;
+                let y = MARGIN + plot_height - (value / max_value * plot_height);
+                format!("{:.2},{:.2}", x, y)
+            })
+            .collect();
+        svg.push_str(&format!(
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"#2563eb\" stroke-width=\"2\"/>\n",
+            coords.join(" ")
+        ));
+    }
+
+    svg.push_str("</svg>\n");
+
+    let mut file =
+        File::create(path).with_context(|| format!("Failed to create SVG file: {}", path))?;
+    file.write_all(svg.as_bytes())
+        .with_context(|| format!("Failed to write SVG file: {}", path))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_chart_scales_to_max() {
+        let items = vec![("a".to_string(), 50), ("b".to_string(), 100)];
+        let lines = bar_chart(&items, 10);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].matches(BAR_GLYPH).count(), 10);
+        assert_eq!(lines[0].matches(BAR_GLYPH).count(), 5);
+    }
+
+    #[test]
+    fn test_bar_chart_empty_is_blank() {
+        let items = vec![("a".to_string(), 0)];
+        let lines = bar_chart(&items, 10);
+        assert_eq!(lines[0].matches(BAR_GLYPH).count(), 0);
+    }
+
+    #[test]
+    fn test_write_svg_line_chart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trend.svg");
+        let points = vec![
+            ("2025-01-01T00:00:00Z".to_string(), 10.0),
+            ("2025-01-01T01:00:00Z".to_string(), 25.0),
+        ];
+        write_svg_line_chart(path.to_str().unwrap(), "Ops/Interval", &points).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.starts_with("<svg"));
+        assert!(contents.contains("polyline"));
+        assert!(contents.contains("Ops/Interval"));
+    }
+}
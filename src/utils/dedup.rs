@@ -0,0 +1,183 @@
+//! Duplicate detection for rotated audit-log file lists.
+//!
+//! Rotated Vault audit logs frequently overlap - the same events can appear
+//! in both `vault_audit.log` and `vault_audit.log.1` after a rotation races
+//! with a read - which silently double-counts every downstream metric if
+//! both are handed to [`crate::utils::parallel::process_files_parallel`].
+//!
+//! [`dedup_files`] runs a size -> head-sample-hash -> full-content-hash
+//! funnel so files that can't possibly match never pay for a full read:
+//! most file lists are size-unique and exit after stage 1.
+
+use anyhow::{Context, Result};
+use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::io::BufRead;
+
+/// Size of the head sample read in stage 2 of [`dedup_files`].
+const HEAD_SAMPLE_BYTES: usize = 8 * 1024;
+
+/// One file [`dedup_files`] dropped because its contents were byte-identical
+/// to another file that appeared earlier in the input list.
+#[derive(Debug, Clone)]
+pub struct DuplicateFile {
+    /// Path that was dropped.
+    pub path: String,
+    /// Path it's byte-identical to (the one kept in the deduplicated list).
+    pub kept_as: String,
+}
+
+/// Report of what [`dedup_files`] found, in input order.
+#[derive(Debug, Clone, Default)]
+pub struct DedupReport {
+    pub duplicates: Vec<DuplicateFile>,
+}
+
+impl DedupReport {
+    /// Print one line per dropped duplicate; a no-op when nothing was dropped.
+    pub fn report(&self) {
+        if self.duplicates.is_empty() {
+            return;
+        }
+        eprintln!(
+            "Dropped {} duplicate rotated log file(s):",
+            self.duplicates.len()
+        );
+        for dup in &self.duplicates {
+            eprintln!("  {} (identical to {})", dup.path, dup.kept_as);
+        }
+    }
+}
+
+/// Filters `files` down to one path per distinct byte-identical content,
+/// keeping each duplicate group's first occurrence and dropping the rest.
+///
+/// Stage 1 groups by exact byte length from [`std::fs::metadata`] - a
+/// size-unique file is immediately kept. Stage 2 hashes a fixed
+/// [`HEAD_SAMPLE_BYTES`] head sample for files sharing a size - a
+/// head-unique file is kept without reading the rest of it. Stage 3 computes
+/// a full streaming content hash only for files that survived both, and
+/// declares files sharing a digest duplicates. Each stage fans out over
+/// `par_iter`.
+pub fn dedup_files(files: &[String]) -> Result<(Vec<String>, DedupReport)> {
+    if files.len() < 2 {
+        return Ok((files.to_vec(), DedupReport::default()));
+    }
+
+    // Stage 1: group indices by file size.
+    let sizes: Vec<u64> = files
+        .par_iter()
+        .map(|path| std::fs::metadata(path).map(|m| m.len()).unwrap_or(0))
+        .collect();
+
+    let mut by_size: HashMap<u64, Vec<usize>> = HashMap::new();
+    for (idx, &size) in sizes.iter().enumerate() {
+        by_size.entry(size).or_default().push(idx);
+    }
+
+    // Stage 2: within each same-size group, hash a head sample.
+    let mut by_head_sample: HashMap<(u64, u64), Vec<usize>> = HashMap::new();
+    for indices in by_size.values().filter(|indices| indices.len() >= 2) {
+        let heads: Vec<(usize, u64)> = indices
+            .par_iter()
+            .map(|&idx| (idx, head_sample_hash(&files[idx]).unwrap_or(0)))
+            .collect();
+        for (idx, head_hash) in heads {
+            by_head_sample
+                .entry((sizes[idx], head_hash))
+                .or_default()
+                .push(idx);
+        }
+    }
+
+    // Stage 3: within each surviving group, a full content hash settles it.
+    let mut dropped: HashSet<usize> = HashSet::new();
+    let mut duplicates = Vec::new();
+    for indices in by_head_sample.values().filter(|indices| indices.len() >= 2) {
+        let digests: Vec<(usize, Result<String>)> = indices
+            .par_iter()
+            .map(|&idx| (idx, content_hash(&files[idx])))
+            .collect();
+
+        // Settle ties in input order so "kept" is always the first occurrence.
+        let mut digests = digests;
+        digests.sort_by_key(|(idx, _)| *idx);
+
+        let mut first_seen: HashMap<String, usize> = HashMap::new();
+        for (idx, digest) in digests {
+            let Ok(digest) = digest else { continue };
+            match first_seen.get(&digest) {
+                Some(&kept_idx) => {
+                    duplicates.push(DuplicateFile {
+                        path: files[idx].clone(),
+                        kept_as: files[kept_idx].clone(),
+                    });
+                    dropped.insert(idx);
+                }
+                None => {
+                    first_seen.insert(digest, idx);
+                }
+            }
+        }
+    }
+
+    duplicates.sort_by_key(|dup| files.iter().position(|f| *f == dup.path).unwrap_or(usize::MAX));
+
+    let deduped = files
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| !dropped.contains(idx))
+        .map(|(_, path)| path.clone())
+        .collect();
+
+    Ok((deduped, DedupReport { duplicates }))
+}
+
+/// Hashes the first [`HEAD_SAMPLE_BYTES`] of `path` with a fast
+/// non-cryptographic hash, for stage 2 of [`dedup_files`].
+fn head_sample_hash(path: &str) -> Result<u64> {
+    use std::io::Read;
+
+    let file =
+        crate::utils::reader::open_file(path).with_context(|| format!("Failed to open {path}"))?;
+    let mut reader = std::io::BufReader::new(file);
+    let mut buf = vec![0u8; HEAD_SAMPLE_BYTES];
+    let mut total = 0;
+    while total < buf.len() {
+        let n = reader.read(&mut buf[total..])?;
+        if n == 0 {
+            break;
+        }
+        total += n;
+    }
+    buf.truncate(total);
+
+    let mut hasher = DefaultHasher::new();
+    buf.hash(&mut hasher);
+    Ok(hasher.finish())
+}
+
+/// Computes a full streaming SHA-256 over `path`'s contents, line by line
+/// (the same `BufReader` loop shape as `parallel::read_file_entries`), for
+/// stage 3 of [`dedup_files`].
+fn content_hash(path: &str) -> Result<String> {
+    let file =
+        crate::utils::reader::open_file(path).with_context(|| format!("Failed to open {path}"))?;
+    let reader = std::io::BufReader::new(file);
+
+    let mut hasher = Sha256::new();
+    for line in reader.lines() {
+        let line = line.with_context(|| format!("Failed to read line from {path}"))?;
+        hasher.update(line.as_bytes());
+        hasher.update(b"\n");
+    }
+
+    Ok(hex_encode(&hasher.finalize()))
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
@@ -0,0 +1,195 @@
+//! Elastic Common Schema (ECS) bulk export (`export-elastic`).
+//!
+//! This tool's analysis commands already normalize Vault audit data into
+//! rows (entity churn records, token-abuse flags, KV-access hotspots, ...).
+//! `export-elastic` reads one of those JSON exports back in, maps each row
+//! onto a handful of well-known [ECS](https://www.elastic.co/guide/en/ecs/current/index.html)
+//! fields, and ships the result to an Elasticsearch `_bulk` endpoint so
+//! teams can keep existing Beats/Elastic dashboards fed from this tool's
+//! enriched output instead of raw audit lines.
+//!
+//! Documents are batched into newline-delimited `{"index":{...}}\n{doc}\n`
+//! payloads of `batch_size` (default ~500) and POSTed with `reqwest`, the
+//! same HTTP client already used by [`crate::vault_api`]. Elasticsearch's
+//! bulk API reports failures per item rather than failing the whole
+//! request, so [`BulkSender::send_all`] re-batches and retries only the
+//! items a response actually rejected, up to a small retry budget.
+
+use anyhow::{bail, Context, Result};
+use reqwest::Client;
+use serde_json::Value;
+use std::time::Duration;
+
+/// Number of times a batch with partial item failures is retried before
+/// its remaining failures are reported and left unsent.
+const MAX_RETRIES: u32 = 3;
+
+/// Known field names (in the order they're checked) that map onto ECS's
+/// `@timestamp`, `user.id`, `user.name`, `url.path`, and `event.action`
+/// respectively. Source rows from different commands use slightly
+/// different names for the same concept, so each maps to the first match.
+const TIMESTAMP_FIELDS: &[&str] = &["@timestamp", "timestamp", "creation_time", "time"];
+const USER_ID_FIELDS: &[&str] = &["entity_id", "user_id", "token_accessor"];
+const USER_NAME_FIELDS: &[&str] = &["entity_name", "display_name", "alias_name"];
+const PATH_FIELDS: &[&str] = &["mount_path", "path", "url_path"];
+const ACTION_FIELDS: &[&str] = &["operation", "vault_operation", "pattern", "event_action"];
+
+/// Builds an ECS document from one exported row, setting
+/// `event.dataset: "vault.audit"` and carrying every field that isn't
+/// folded into an ECS field over under a `vault.*` namespace, per ECS's
+/// convention for vendor-specific fields.
+pub fn row_to_ecs_doc(row: &serde_json::Map<String, Value>) -> Value {
+    let mut doc = serde_json::Map::new();
+    doc.insert(
+        "event".to_string(),
+        serde_json::json!({ "dataset": "vault.audit", "action": first_present(row, ACTION_FIELDS) }),
+    );
+    if let Some(ts) = first_present(row, TIMESTAMP_FIELDS) {
+        doc.insert("@timestamp".to_string(), ts);
+    }
+    if let Some(id) = first_present(row, USER_ID_FIELDS) {
+        doc.insert("user".to_string(), serde_json::json!({ "id": id }));
+    }
+    if let Some(name) = first_present(row, USER_NAME_FIELDS) {
+        let user = doc
+            .entry("user".to_string())
+            .or_insert_with(|| serde_json::json!({}));
+        user["name"] = name;
+    }
+    if let Some(path) = first_present(row, PATH_FIELDS) {
+        doc.insert("url".to_string(), serde_json::json!({ "path": path }));
+    }
+
+    let mapped_fields: Vec<&str> = TIMESTAMP_FIELDS
+        .iter()
+        .chain(USER_ID_FIELDS)
+        .chain(USER_NAME_FIELDS)
+        .chain(PATH_FIELDS)
+        .chain(ACTION_FIELDS)
+        .copied()
+        .collect();
+    let vendor: serde_json::Map<String, Value> = row
+        .iter()
+        .filter(|(k, _)| !mapped_fields.contains(&k.as_str()))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    if !vendor.is_empty() {
+        doc.insert("vault".to_string(), Value::Object(vendor));
+    }
+
+    Value::Object(doc)
+}
+
+fn first_present(row: &serde_json::Map<String, Value>, candidates: &[&str]) -> Option<Value> {
+    candidates
+        .iter()
+        .find_map(|&field| row.get(field))
+        .filter(|v| !v.is_null())
+        .cloned()
+}
+
+/// Ships batches of ECS documents to one Elasticsearch index via the
+/// `_bulk` API.
+pub struct BulkSender {
+    client: Client,
+    bulk_url: String,
+    index: String,
+    batch_size: usize,
+}
+
+impl BulkSender {
+    pub fn new(elastic_url: &str, index: &str, batch_size: usize) -> Self {
+        Self {
+            client: Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .expect("reqwest client builder should not fail"),
+            bulk_url: format!("{}/_bulk", elastic_url.trim_end_matches('/')),
+            index: index.to_string(),
+            batch_size: batch_size.max(1),
+        }
+    }
+
+    /// Sends every document in `docs`, chunked into `batch_size`-sized
+    /// bulk requests. Returns the total number of documents Elasticsearch
+    /// ultimately rejected after retries.
+    pub async fn send_all(&self, docs: &[Value]) -> Result<usize> {
+        let mut failed = 0;
+        for chunk in docs.chunks(self.batch_size) {
+            failed += self.send_batch_with_retry(chunk.to_vec()).await?;
+        }
+        Ok(failed)
+    }
+
+    async fn send_batch_with_retry(&self, mut batch: Vec<Value>) -> Result<usize> {
+        for attempt in 0..=MAX_RETRIES {
+            if batch.is_empty() {
+                return Ok(0);
+            }
+            let failed_docs = self.send_batch(&batch).await?;
+            if failed_docs.is_empty() {
+                return Ok(0);
+            }
+            if attempt == MAX_RETRIES {
+                return Ok(failed_docs.len());
+            }
+            batch = failed_docs;
+        }
+        Ok(batch.len())
+    }
+
+    /// POSTs one `_bulk` request and returns the subset of `batch` whose
+    /// items Elasticsearch reported as failed, for the caller to retry.
+    async fn send_batch(&self, batch: &[Value]) -> Result<Vec<Value>> {
+        let mut body = String::new();
+        for doc in batch {
+            let action = serde_json::json!({ "index": { "_index": self.index } });
+            body.push_str(&action.to_string());
+            body.push('\n');
+            body.push_str(&doc.to_string());
+            body.push('\n');
+        }
+
+        let response = self
+            .client
+            .post(&self.bulk_url)
+            .header("Content-Type", "application/x-ndjson")
+            .body(body)
+            .send()
+            .await
+            .context("Failed to send bulk request to Elasticsearch")?;
+
+        if !response.status().is_success() {
+            bail!(
+                "Elasticsearch bulk request failed with status {}",
+                response.status()
+            );
+        }
+
+        let body: Value = response
+            .json()
+            .await
+            .context("Failed to parse Elasticsearch bulk response")?;
+
+        let Some(true) = body.get("errors").and_then(Value::as_bool) else {
+            return Ok(vec![]);
+        };
+
+        let items = body
+            .get("items")
+            .and_then(Value::as_array)
+            .context("Bulk response reported errors but has no items array")?;
+
+        Ok(items
+            .iter()
+            .zip(batch)
+            .filter(|(item, _)| {
+                item.get("index")
+                    .and_then(|i| i.get("status"))
+                    .and_then(Value::as_u64)
+                    .is_some_and(|status| status >= 300)
+            })
+            .map(|(_, doc)| doc.clone())
+            .collect())
+    }
+}
@@ -0,0 +1,163 @@
+//! Content-addressed, zero-copy cache for built entity maps.
+//!
+//! The auto-preprocess path in [`crate::commands::entity_analysis::run_churn`]
+//! and [`run_creation`](crate::commands::entity_analysis::run_creation) rebuilds
+//! the full entity map from scratch on every invocation. For repeated runs over
+//! the same logs (e.g. `churn` then `creation` over the same week), that's a
+//! full re-scan each time for no reason.
+//!
+//! This module hashes the input log paths together with their size and
+//! modification time into a cache key, then archives the built map with
+//! [`rkyv`] under a cache directory. On a cache hit, the archive is `mmap`ed
+//! and accessed directly via [`rkyv::check_archived_root`] - no
+//! deserialization of the full map is needed unless the caller wants an
+//! owned copy (which callers here do, since [`crate::commands::entity_creation`]
+//! and [`crate::commands::entity_churn`] both expect an owned
+//! `HashMap<String, EntityMapping>` today).
+//!
+//! # Cache directory
+//!
+//! Defaults to `<tmp>/vault-audit-entity-cache`; override with the
+//! `VAULT_AUDIT_CACHE_DIR` environment variable.
+
+use crate::commands::preprocess_entities::EntityMapping;
+use anyhow::{Context, Result};
+use rkyv::{Archive, Deserialize as RkyvDeserialize, Serialize as RkyvSerialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// `rkyv`-archivable mirror of [`EntityMapping`] (which stays plain `serde`
+/// for its existing JSON/CSV export paths).
+#[derive(Archive, RkyvDeserialize, RkyvSerialize, Debug, Clone)]
+#[archive(check_bytes)]
+struct CachedEntityMapping {
+    display_name: String,
+    mount_path: String,
+    mount_accessor: String,
+    username: Option<String>,
+    login_count: u64,
+    first_seen: String,
+    last_seen: String,
+}
+
+impl From<&EntityMapping> for CachedEntityMapping {
+    fn from(m: &EntityMapping) -> Self {
+        Self {
+            display_name: m.display_name.clone(),
+            mount_path: m.mount_path.clone(),
+            mount_accessor: m.mount_accessor.clone(),
+            username: m.username.clone(),
+            login_count: m.login_count as u64,
+            first_seen: m.first_seen.clone(),
+            last_seen: m.last_seen.clone(),
+        }
+    }
+}
+
+impl From<&CachedEntityMapping> for EntityMapping {
+    fn from(m: &CachedEntityMapping) -> Self {
+        Self {
+            display_name: m.display_name.clone(),
+            mount_path: m.mount_path.clone(),
+            mount_accessor: m.mount_accessor.clone(),
+            username: m.username.clone(),
+            login_count: m.login_count as usize,
+            first_seen: m.first_seen.clone(),
+            last_seen: m.last_seen.clone(),
+        }
+    }
+}
+
+#[derive(Archive, RkyvDeserialize, RkyvSerialize, Debug)]
+#[archive(check_bytes)]
+struct CachedEntityMap {
+    entries: Vec<(String, CachedEntityMapping)>,
+}
+
+fn cache_dir() -> PathBuf {
+    std::env::var("VAULT_AUDIT_CACHE_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| std::env::temp_dir().join("vault-audit-entity-cache"))
+}
+
+/// Fingerprints `log_files` by path + size + modification time (not
+/// contents - hashing gigabytes of audit logs on every run would defeat the
+/// point of caching) into a stable cache key.
+fn cache_key(log_files: &[String]) -> String {
+    let mut fingerprints: Vec<String> = log_files
+        .iter()
+        .map(|path| {
+            let meta = std::fs::metadata(path).ok();
+            let len = meta.as_ref().map(std::fs::Metadata::len).unwrap_or(0);
+            let mtime = meta
+                .and_then(|m| m.modified().ok())
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            format!("{path}:{len}:{mtime}")
+        })
+        .collect();
+    fingerprints.sort();
+
+    let mut hasher = Sha256::new();
+    for fingerprint in &fingerprints {
+        hasher.update(fingerprint.as_bytes());
+        hasher.update(b"\n");
+    }
+    hex_encode(&hasher.finalize())
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn cache_path(key: &str) -> PathBuf {
+    cache_dir().join(format!("{key}.rkyv"))
+}
+
+/// Loads a cached entity map for `log_files`, if an archive for the current
+/// (path, size, mtime) fingerprint exists and validates. Returns `None` on
+/// any cache miss or validation failure - callers should treat that exactly
+/// like a cold cache and fall back to [`crate::commands::preprocess_entities::build_entity_map`].
+pub fn load(log_files: &[String]) -> Option<HashMap<String, EntityMapping>> {
+    let path = cache_path(&cache_key(log_files));
+    let file = std::fs::File::open(&path).ok()?;
+    let mmap = unsafe { memmap2::Mmap::map(&file).ok()? };
+
+    let archived = rkyv::check_archived_root::<CachedEntityMap>(&mmap).ok()?;
+    let cached: CachedEntityMap = archived.deserialize(&mut rkyv::Infallible).ok()?;
+
+    Some(
+        cached
+            .entries
+            .iter()
+            .map(|(entity_id, mapping)| (entity_id.clone(), EntityMapping::from(mapping)))
+            .collect(),
+    )
+}
+
+/// Archives `entity_map` under `log_files`'s cache key, so a later [`load`]
+/// with the same (unchanged) input set can skip rebuilding it.
+pub fn store(log_files: &[String], entity_map: &HashMap<String, EntityMapping>) -> Result<()> {
+    let dir = cache_dir();
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create entity-map cache dir: {}", dir.display()))?;
+
+    let cached = CachedEntityMap {
+        entries: entity_map
+            .iter()
+            .map(|(entity_id, mapping)| (entity_id.clone(), CachedEntityMapping::from(mapping)))
+            .collect(),
+    };
+
+    let bytes =
+        rkyv::to_bytes::<_, 4096>(&cached).context("Failed to archive entity map for cache")?;
+
+    let path = cache_path(&cache_key(log_files));
+    let mut file = std::fs::File::create(&path)
+        .with_context(|| format!("Failed to write entity-map cache file: {}", path.display()))?;
+    file.write_all(&bytes)?;
+    Ok(())
+}
@@ -26,10 +26,65 @@ pub fn format_number(n: usize) -> String {
     result.chars().rev().collect()
 }
 
+/// Formats a byte count as a human-readable size (KiB/MiB/GiB/TiB, binary units).
+///
+/// # Examples
+///
+/// ```
+/// use vault_audit_tools::utils::format::format_bytes;
+///
+/// assert_eq!(format_bytes(512), "512 B");
+/// assert_eq!(format_bytes(2048), "2.00 KiB");
+/// assert_eq!(format_bytes(5 * 1024 * 1024), "5.00 MiB");
+/// ```
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[unit])
+    } else {
+        format!("{:.2} {}", value, UNITS[unit])
+    }
+}
+
+/// Formats a duration in seconds as `mm:ss`.
+///
+/// # Examples
+///
+/// ```
+/// use vault_audit_tools::utils::format::format_duration_mmss;
+///
+/// assert_eq!(format_duration_mmss(5), "00:05");
+/// assert_eq!(format_duration_mmss(65), "01:05");
+/// assert_eq!(format_duration_mmss(3661), "61:01");
+/// ```
+pub fn format_duration_mmss(total_secs: u64) -> String {
+    let minutes = total_secs / 60;
+    let seconds = total_secs % 60;
+    format!("{minutes:02}:{seconds:02}")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1024), "1.00 KiB");
+        assert_eq!(format_bytes(2048), "2.00 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.00 MiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.00 GiB");
+    }
+
     #[test]
     fn test_format_number() {
         assert_eq!(format_number(0), "0");
@@ -45,6 +100,15 @@ mod tests {
         assert_eq!(format_number(1_000_000_000), "1,000,000,000");
     }
 
+    #[test]
+    fn test_format_duration_mmss() {
+        assert_eq!(format_duration_mmss(0), "00:00");
+        assert_eq!(format_duration_mmss(5), "00:05");
+        assert_eq!(format_duration_mmss(59), "00:59");
+        assert_eq!(format_duration_mmss(60), "01:00");
+        assert_eq!(format_duration_mmss(3661), "61:01");
+    }
+
     #[test]
     fn test_format_number_large() {
         assert_eq!(
@@ -0,0 +1,127 @@
+//! Reusable output key-casing adapter.
+//!
+//! Several inventory commands emit the same data to multiple downstream
+//! consumers: this crate's own snake_case, camelCase for JS/TS tooling, and
+//! a shape that matches Vault's own `/sys/*` API responses so the exported
+//! JSON can be diffed directly against an API capture. Rather than forking
+//! the serde derive per format, commands serialize to a `serde_json::Value`
+//! as usual and run it through [`recase`] before printing.
+
+use serde_json::{Map, Value};
+
+/// Output key-casing convention for JSON serialization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum KeyCase {
+    /// This crate's native snake_case field names (default, unchanged).
+    #[default]
+    Snake,
+    /// camelCase field names, for JS/TS-style consumers.
+    Camel,
+    /// Field names/shape matching Vault's own `/sys/auth` response.
+    Vault,
+}
+
+/// Convert a JSON value serialized with this crate's native snake_case
+/// field names into the requested key-casing convention.
+pub fn recase(value: Value, case: KeyCase) -> Value {
+    match case {
+        KeyCase::Snake => value,
+        KeyCase::Camel => camelize(value),
+        KeyCase::Vault => vaultize(value),
+    }
+}
+
+fn camelize(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(k, v)| (to_camel_case(&k), camelize(v)))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(camelize).collect()),
+        other => other,
+    }
+}
+
+fn to_camel_case(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut upper_next = false;
+    for ch in s.chars() {
+        if ch == '_' {
+            upper_next = true;
+        } else if upper_next {
+            out.extend(ch.to_uppercase());
+            upper_next = false;
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+/// Rename/reshape fields to match Vault's own `/v1/sys/auth` response: the
+/// `type` field instead of `auth_type`, and lease TTLs nested under a
+/// `config` object instead of flattened at the top level.
+fn vaultize(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut out = Map::new();
+            let mut config = Map::new();
+            for (key, val) in map {
+                let val = vaultize(val);
+                match key.as_str() {
+                    "auth_type" => {
+                        out.insert("type".to_string(), val);
+                    }
+                    "default_lease_ttl" | "max_lease_ttl" => {
+                        config.insert(key, val);
+                    }
+                    other => {
+                        out.insert(other.to_string(), val);
+                    }
+                }
+            }
+            if !config.is_empty() {
+                out.insert("config".to_string(), Value::Object(config));
+            }
+            Value::Object(out)
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(vaultize).collect()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_snake_is_noop() {
+        let value = json!({"auth_type": "kubernetes", "default_lease_ttl": "0s"});
+        assert_eq!(recase(value.clone(), KeyCase::Snake), value);
+    }
+
+    #[test]
+    fn test_camel_renames_keys() {
+        let value = json!({"auth_type": "kubernetes", "seal_wrap": false});
+        let recased = recase(value, KeyCase::Camel);
+        assert_eq!(recased["authType"], json!("kubernetes"));
+        assert_eq!(recased["sealWrap"], json!(false));
+    }
+
+    #[test]
+    fn test_vault_nests_lease_ttls_under_config() {
+        let value = json!({
+            "auth_type": "kubernetes",
+            "default_lease_ttl": "0s",
+            "max_lease_ttl": "0s",
+            "accessor": "auth_kubernetes_123",
+        });
+        let recased = recase(value, KeyCase::Vault);
+        assert_eq!(recased["type"], json!("kubernetes"));
+        assert_eq!(recased["config"]["default_lease_ttl"], json!("0s"));
+        assert_eq!(recased["config"]["max_lease_ttl"], json!("0s"));
+        assert_eq!(recased["accessor"], json!("auth_kubernetes_123"));
+    }
+}
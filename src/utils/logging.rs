@@ -0,0 +1,118 @@
+//! Structured logging subsystem.
+//!
+//! Operator-facing progress/diagnostic messages go through `tracing` instead
+//! of bare `eprintln!`, so they can be timestamped, leveled, and redirected
+//! independently of the command's machine-readable output (CSV/JSON/stdout
+//! tree), which always stays on `stdout` untouched.
+//!
+//! - Without `--log-file`, events are formatted to stderr.
+//! - With `--log-file <path>`, the same events are additionally written as
+//!   timestamped structured lines to the given file.
+//! - With the `enable_syslog` feature compiled in, events are also forwarded
+//!   to the local syslog daemon, for unattended audit runs that don't retain
+//!   their own log files.
+//!
+//! Verbosity is controlled by the existing [`crate::utils::progress::Verbosity`]
+//! enum, and can be overridden with the `RUST_LOG` environment variable.
+
+use crate::utils::progress::Verbosity;
+use anyhow::{Context, Result};
+use std::fs::OpenOptions;
+use tracing_subscriber::{fmt, prelude::*, EnvFilter};
+
+/// Initialize the global `tracing` subscriber for this process.
+///
+/// Must be called once, near the top of `main`/`run`, before any `tracing::*!`
+/// macros are used. Returns an error if `log_file` can't be opened or a
+/// subscriber is already installed.
+pub fn init(log_file: Option<&str>, verbosity: Verbosity) -> Result<()> {
+    let default_level = match verbosity {
+        Verbosity::Quiet => "warn",
+        Verbosity::Normal => "info",
+        Verbosity::Verbose => "debug",
+    };
+    let env_filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(default_level));
+
+    let stderr_layer = fmt::layer().with_writer(std::io::stderr).with_target(false);
+
+    let file_layer = log_file
+        .map(|path| {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .with_context(|| format!("Failed to open log file: {}", path))?;
+            Ok::<_, anyhow::Error>(
+                fmt::layer()
+                    .with_writer(file)
+                    .with_ansi(false)
+                    .with_target(false),
+            )
+        })
+        .transpose()?;
+
+    let registry = tracing_subscriber::registry()
+        .with(env_filter)
+        .with(stderr_layer)
+        .with(file_layer);
+
+    #[cfg(feature = "enable_syslog")]
+    {
+        registry.with(fmt::layer().with_writer(SyslogWriter::connect()?).with_ansi(false).with_target(false)).try_init().ok();
+    }
+    #[cfg(not(feature = "enable_syslog"))]
+    {
+        registry.try_init().ok();
+    }
+
+    Ok(())
+}
+
+/// A `tracing_subscriber` writer that forwards each formatted line to the
+/// local syslog daemon instead of a file or terminal. Only compiled with
+/// `--features enable_syslog`.
+#[cfg(feature = "enable_syslog")]
+struct SyslogWriter {
+    logger: std::sync::Mutex<syslog::Logger<syslog::LoggerBackend, syslog::Formatter3164>>,
+}
+
+#[cfg(feature = "enable_syslog")]
+impl SyslogWriter {
+    fn connect() -> Result<Self> {
+        let formatter = syslog::Formatter3164 {
+            facility: syslog::Facility::LOG_USER,
+            hostname: None,
+            process: "vault-audit".into(),
+            pid: std::process::id(),
+        };
+        let logger = syslog::unix(formatter).context("Failed to connect to local syslog")?;
+        Ok(Self {
+            logger: std::sync::Mutex::new(logger),
+        })
+    }
+}
+
+#[cfg(feature = "enable_syslog")]
+impl std::io::Write for &SyslogWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let line = String::from_utf8_lossy(buf);
+        if let Ok(mut logger) = self.logger.lock() {
+            let _ = logger.info(line.trim_end());
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "enable_syslog")]
+impl<'a> fmt::MakeWriter<'a> for SyslogWriter {
+    type Writer = &'a SyslogWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self
+    }
+}
@@ -0,0 +1,187 @@
+//! Swappable backend for entity-mapping accumulation.
+//!
+//! `preprocess-entities` and `entity-creation` each fold one record per
+//! entity into an in-memory `HashMap` as they scan audit logs, which OOMs on
+//! multi-terabyte, multi-year corpora with millions of distinct entities.
+//! [`MappingStore`] abstracts over where those records actually live, so a
+//! command can swap [`InMemoryStore`] (the default, unbounded by disk but
+//! bounded by RAM) for [`SledStore`] (an embedded on-disk key-value store,
+//! bounded by disk instead) via `--store-backend`/`--store-path`.
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Backend an entity-mapping command accumulates per-entity records into.
+pub trait MappingStore<V> {
+    /// Look up the current record for `entity_id`, if any.
+    fn get(&self, entity_id: &str) -> Result<Option<V>>;
+
+    /// Apply `update` to the record for `entity_id`, starting from
+    /// `V::default()` the first time `entity_id` is seen.
+    fn upsert_with<F>(&mut self, entity_id: &str, update: F) -> Result<()>
+    where
+        F: FnOnce(&mut V);
+
+    /// Iterate all `(entity_id, record)` pairs in `entity_id` order, for a
+    /// final aggregation/export pass.
+    fn iter(&self) -> Result<Box<dyn Iterator<Item = (String, V)> + '_>>;
+
+    /// Number of distinct entities tracked.
+    fn len(&self) -> Result<usize>;
+
+    /// Whether any entities have been tracked yet.
+    fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+}
+
+/// Default backend - a plain in-memory `HashMap`.
+#[derive(Debug, Default)]
+pub struct InMemoryStore<V> {
+    map: HashMap<String, V>,
+}
+
+impl<V> InMemoryStore<V> {
+    pub fn new() -> Self {
+        Self {
+            map: HashMap::new(),
+        }
+    }
+}
+
+impl<V: Clone + Default> MappingStore<V> for InMemoryStore<V> {
+    fn get(&self, entity_id: &str) -> Result<Option<V>> {
+        Ok(self.map.get(entity_id).cloned())
+    }
+
+    fn upsert_with<F>(&mut self, entity_id: &str, update: F) -> Result<()>
+    where
+        F: FnOnce(&mut V),
+    {
+        update(self.map.entry(entity_id.to_string()).or_default());
+        Ok(())
+    }
+
+    fn iter(&self) -> Result<Box<dyn Iterator<Item = (String, V)> + '_>> {
+        let mut entries: Vec<(String, V)> = self
+            .map
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(Box::new(entries.into_iter()))
+    }
+
+    fn len(&self) -> Result<usize> {
+        Ok(self.map.len())
+    }
+}
+
+/// Embedded on-disk backend, for corpora with more distinct entities than
+/// fit in RAM. Each record is JSON-serialized under its `entity_id` as the
+/// key in a `sled` tree rooted at `--store-path`.
+pub struct SledStore<V> {
+    db: sled::Db,
+    _marker: std::marker::PhantomData<V>,
+}
+
+impl<V> SledStore<V> {
+    /// Open (or create) a sled store at `path`.
+    pub fn open(path: &Path) -> Result<Self> {
+        let db = sled::open(path)
+            .with_context(|| format!("Failed to open sled store at {}", path.display()))?;
+        Ok(Self {
+            db,
+            _marker: std::marker::PhantomData,
+        })
+    }
+}
+
+impl<V: Serialize + DeserializeOwned + Default> MappingStore<V> for SledStore<V> {
+    fn get(&self, entity_id: &str) -> Result<Option<V>> {
+        match self
+            .db
+            .get(entity_id.as_bytes())
+            .context("Failed to read from sled store")?
+        {
+            Some(bytes) => Ok(Some(
+                serde_json::from_slice(&bytes).context("Failed to deserialize stored record")?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    fn upsert_with<F>(&mut self, entity_id: &str, update: F) -> Result<()>
+    where
+        F: FnOnce(&mut V),
+    {
+        let mut record = self.get(entity_id)?.unwrap_or_default();
+        update(&mut record);
+        let bytes = serde_json::to_vec(&record).context("Failed to serialize record")?;
+        self.db
+            .insert(entity_id.as_bytes(), bytes)
+            .context("Failed to write to sled store")?;
+        Ok(())
+    }
+
+    fn iter(&self) -> Result<Box<dyn Iterator<Item = (String, V)> + '_>> {
+        let mut entries: Vec<(String, V)> = Vec::new();
+        for item in self.db.iter() {
+            let (key, value) = item.context("Failed to read sled entry")?;
+            let entity_id = String::from_utf8_lossy(&key).into_owned();
+            let record: V =
+                serde_json::from_slice(&value).context("Failed to deserialize stored record")?;
+            entries.push((entity_id, record));
+        }
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(Box::new(entries.into_iter()))
+    }
+
+    fn len(&self) -> Result<usize> {
+        Ok(self.db.len())
+    }
+}
+
+/// Which [`MappingStore`] backend a command should use, selected by
+/// `--store-backend`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StoreBackend {
+    /// Plain `HashMap`, bounded by RAM. The default.
+    #[default]
+    Memory,
+    /// Embedded on-disk `sled` store, bounded by disk - see `--store-path`.
+    Sled,
+}
+
+/// `clap` `value_parser` for `--store-backend` - matches the repo's
+/// `utils::time::parse_duration` convention of a plain parsing function
+/// rather than a derived `ValueEnum`.
+pub fn parse_store_backend(s: &str) -> Result<StoreBackend> {
+    match s.to_lowercase().as_str() {
+        "memory" => Ok(StoreBackend::Memory),
+        "sled" => Ok(StoreBackend::Sled),
+        other => anyhow::bail!("Invalid --store-backend '{}'. Use 'memory' or 'sled'", other),
+    }
+}
+
+/// Open the `MappingStore` backend selected by `backend`. `Memory` ignores
+/// `store_path`; `Sled` requires it.
+pub fn open_store<V>(
+    backend: StoreBackend,
+    store_path: Option<&str>,
+) -> Result<Box<dyn MappingStore<V>>>
+where
+    V: Serialize + DeserializeOwned + Default + Clone + 'static,
+{
+    match backend {
+        StoreBackend::Memory => Ok(Box::new(InMemoryStore::new())),
+        StoreBackend::Sled => {
+            let path = store_path
+                .context("--store-backend sled requires --store-path <DIRECTORY>")?;
+            Ok(Box::new(SledStore::open(Path::new(path))?))
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! Prometheus metrics export for analysis commands.
+//!
+//! Analysis commands normally hand-inspect their CSV/JSON reports. This
+//! module lets a command additionally render its aggregate results as
+//! [Prometheus exposition format](https://prometheus.io/docs/instrumenting/exposition_formats/),
+//! either as a node_exporter-style textfile (`--metrics-file`) or served
+//! live over HTTP (`--metrics-listen addr:port`) so the run can be scraped
+//! into existing dashboards.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use vault_audit_tools::utils::metrics::MetricsExporter;
+//!
+//! let mut exporter = MetricsExporter::new();
+//! exporter.gauge("vault_kv_lines_total", "Total audit log lines processed", &[], 1234.0);
+//! exporter.counter(
+//!     "vault_kv_operations_total",
+//!     "Total KV read/list operations per path",
+//!     &[("kv_path", "secret/myapp/config")],
+//!     42.0,
+//! );
+//! exporter.write_textfile("kv_usage.prom").unwrap();
+//! ```
+//!
+//! `--metrics-listen` serves the same rendered text at `GET /metrics` via a
+//! small hand-rolled HTTP responder (matching the rest of this crate's
+//! preference for hand-written parsers over pulling in a full HTTP-server
+//! dependency for one endpoint). Since these commands do a single analysis
+//! pass rather than running continuously, the served text is a fixed
+//! snapshot of that pass, not a live-updating gauge.
+
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::io::{Read, Write as _};
+use std::net::TcpListener;
+
+/// Accumulates Prometheus exposition-format text for one command's run.
+#[derive(Debug, Default)]
+pub struct MetricsExporter {
+    buf: String,
+    emitted_headers: HashSet<String>,
+}
+
+impl MetricsExporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one gauge sample, writing the `# HELP`/`# TYPE` header the
+    /// first time `name` is seen.
+    pub fn gauge(&mut self, name: &str, help: &str, labels: &[(&str, &str)], value: f64) {
+        self.write_sample(name, help, "gauge", labels, value);
+    }
+
+    /// Record one counter sample, writing the `# HELP`/`# TYPE` header the
+    /// first time `name` is seen.
+    pub fn counter(&mut self, name: &str, help: &str, labels: &[(&str, &str)], value: f64) {
+        self.write_sample(name, help, "counter", labels, value);
+    }
+
+    fn write_sample(
+        &mut self,
+        name: &str,
+        help: &str,
+        metric_type: &str,
+        labels: &[(&str, &str)],
+        value: f64,
+    ) {
+        if self.emitted_headers.insert(name.to_string()) {
+            let _ = writeln!(self.buf, "# HELP {name} {help}");
+            let _ = writeln!(self.buf, "# TYPE {name} {metric_type}");
+        }
+        if labels.is_empty() {
+            let _ = writeln!(self.buf, "{name} {value}");
+        } else {
+            let label_str = labels
+                .iter()
+                .map(|(k, v)| format!("{k}=\"{}\"", escape_label_value(v)))
+                .collect::<Vec<_>>()
+                .join(",");
+            let _ = writeln!(self.buf, "{name}{{{label_str}}} {value}");
+        }
+    }
+
+    /// The full exposition-format document built so far.
+    pub fn render(&self) -> &str {
+        &self.buf
+    }
+
+    /// Write the rendered metrics to `path`, via a temp-file-then-rename so a
+    /// node_exporter textfile collector never observes a half-written file.
+    pub fn write_textfile(&self, path: &str) -> Result<()> {
+        let tmp_path = format!("{path}.tmp");
+        std::fs::write(&tmp_path, &self.buf)
+            .with_context(|| format!("Failed to write metrics textfile: {tmp_path}"))?;
+        std::fs::rename(&tmp_path, path)
+            .with_context(|| format!("Failed to finalize metrics textfile: {path}"))?;
+        Ok(())
+    }
+
+    /// Serve the already-rendered metrics text at `GET /metrics` until the
+    /// process is killed. Blocks the calling thread; call this after the
+    /// report has otherwise been written out.
+    pub fn serve_blocking(&self, addr: &str) -> Result<()> {
+        let listener = TcpListener::bind(addr)
+            .with_context(|| format!("Failed to bind metrics listener on {addr}"))?;
+        eprintln!("Serving Prometheus metrics on http://{addr}/metrics (Ctrl+C to stop)");
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { continue };
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request);
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                self.buf.len(),
+                self.buf,
+            );
+            if let Err(e) = stream.write_all(response.as_bytes()) {
+                eprintln!("Warning: failed to write metrics response: {e}");
+            }
+        }
+        Ok(())
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
@@ -5,6 +5,19 @@
 //! - [`progress`] - Progress tracking and display utilities
 //! - [`time`] - Timestamp parsing and formatting helpers
 //! - [`reader`] - Smart file reader with automatic decompression
+//! - [`chart`] - Terminal bar-histogram and SVG line chart rendering
+//! - [`dedup`] - Byte-identical duplicate detection for rotated log file lists
+//! - [`logging`] - Structured logging (stderr, `--log-file`, optional syslog)
+//! - [`key_case`] - Output key-casing adapter (snake/camel/Vault-native)
+//! - [`otel`] - Optional OpenTelemetry trace/metric export (`--otel-endpoint`)
+//! - [`metrics`] - Prometheus metrics export (`--metrics-file`/`--metrics-listen`)
+//! - [`s3`] - Authenticated S3-compatible object storage reads (`s3://...`)
+//! - [`entity_cache`] - Content-addressed `rkyv` cache for built entity maps
+//! - [`mapping_store`] - Swappable in-memory/on-disk backend for entity-mapping accumulation
+//! - [`report`] - Shared `--format {table,json,ndjson}` rendering for command results
+//! - [`parallel`] - Legacy file-level parallel processing (`process_files_parallel`)
+//! - [`sandbox`] - Opt-in OS-level process hardening (`--sandbox`)
+//! - [`elastic`] - ECS document mapping and `_bulk` shipping for `export-elastic`
 //!
 //! # Examples
 //!
@@ -28,6 +41,19 @@
 //! let buf_reader = BufReader::new(reader);
 //! ```
 
+pub mod chart;
+pub mod dedup;
+pub mod elastic;
+pub mod entity_cache;
+pub mod key_case;
+pub mod logging;
+pub mod mapping_store;
+pub mod metrics;
+pub mod otel;
+pub mod parallel;
 pub mod progress;
 pub mod reader;
+pub mod report;
+pub mod s3;
+pub mod sandbox;
 pub mod time;
@@ -0,0 +1,256 @@
+//! Optional OpenTelemetry instrumentation for analysis commands.
+//!
+//! Disabled by default. Passing `--otel-endpoint <url>` to a command that
+//! supports it emits an OTLP trace for the run (one span per input file,
+//! nested under a root command span - see [`run_context`]/[`file_span`] for
+//! how that nesting is threaded through) plus a set of
+//! gauges/counters/histograms summarizing the aggregate results, to any
+//! OTLP-compatible backend (Tempo, Honeycomb, Jaeger, ...).
+//!
+//! Only compiled in with the `enable_otel` feature. Without it, [`init`]
+//! returns an error if an endpoint was actually requested, so a build that
+//! can't honor `--otel-endpoint` doesn't silently do nothing; every other
+//! helper in this module becomes a no-op so call sites never need their own
+//! `#[cfg(feature = "enable_otel")]`.
+//!
+//! The exporter is meant to be initialized once near the top of a command's
+//! `run`, the same way [`crate::utils::logging::init`] is, and reused across
+//! every span/metric recorded during that run.
+
+use anyhow::Result;
+
+/// Per-run aggregate results recorded as OpenTelemetry metrics once a run
+/// completes. Field names mirror the output fields they summarize.
+pub struct ChurnRunStats<'a> {
+    pub daily_new_vs_returning: &'a [(u32, usize, usize)],
+    pub total_logins: usize,
+    pub lifecycle_counts: &'a std::collections::HashMap<String, usize>,
+    pub activity_pattern_counts: &'a std::collections::HashMap<String, usize>,
+    pub ephemeral_confidences: &'a [f32],
+}
+
+#[cfg(feature = "enable_otel")]
+mod imp {
+    use super::ChurnRunStats;
+    use anyhow::{Context, Result};
+    use opentelemetry::trace::{Span, Tracer, TracerProvider as _};
+    use opentelemetry::{global, KeyValue};
+    use opentelemetry_otlp::WithExportConfig;
+    use opentelemetry_sdk::metrics::SdkMeterProvider;
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+
+    const INSTRUMENTATION_NAME: &str = "vault-audit-tools";
+
+    /// Holds the provider handles alive for the lifetime of a run. Dropping
+    /// it flushes and shuts down both the trace and metric pipelines.
+    pub struct OtelHandle {
+        tracer_provider: SdkTracerProvider,
+        meter_provider: SdkMeterProvider,
+    }
+
+    impl Drop for OtelHandle {
+        fn drop(&mut self) {
+            let _ = self.tracer_provider.shutdown();
+            let _ = self.meter_provider.shutdown();
+        }
+    }
+
+    pub fn init(endpoint: &str) -> Result<OtelHandle> {
+        let span_exporter = opentelemetry_otlp::SpanExporter::builder()
+            .with_tonic()
+            .with_endpoint(endpoint)
+            .build()
+            .context("Failed to build OTLP span exporter")?;
+        let tracer_provider = SdkTracerProvider::builder()
+            .with_batch_exporter(span_exporter)
+            .build();
+        global::set_tracer_provider(tracer_provider.clone());
+
+        let metric_exporter = opentelemetry_otlp::MetricExporter::builder()
+            .with_tonic()
+            .with_endpoint(endpoint)
+            .build()
+            .context("Failed to build OTLP metric exporter")?;
+        let meter_provider = SdkMeterProvider::builder()
+            .with_periodic_exporter(metric_exporter)
+            .build();
+        global::set_meter_provider(meter_provider.clone());
+
+        Ok(OtelHandle {
+            tracer_provider,
+            meter_provider,
+        })
+    }
+
+    pub struct RunSpanGuard(Option<opentelemetry_sdk::trace::Span>);
+
+    impl Drop for RunSpanGuard {
+        fn drop(&mut self) {
+            if let Some(mut span) = self.0.take() {
+                span.end();
+            }
+        }
+    }
+
+    pub fn run_span(command: &str) -> RunSpanGuard {
+        let span = global::tracer(INSTRUMENTATION_NAME).start(command.to_string());
+        RunSpanGuard(Some(span))
+    }
+
+    /// A snapshot of a run span's identity, captured by value so it can be
+    /// handed into each `rayon` worker closure explicitly - `Context::current()`
+    /// is thread-local and does not follow a span across the pool's worker
+    /// threads, so per-file spans only nest correctly if the parent is passed
+    /// in rather than looked up.
+    pub struct RunContext(Option<opentelemetry::Context>);
+
+    pub fn run_context(guard: &RunSpanGuard) -> RunContext {
+        use opentelemetry::trace::TraceContextExt;
+        RunContext(
+            guard
+                .0
+                .as_ref()
+                .map(|span| opentelemetry::Context::new().with_remote_span_context(span.span_context().clone())),
+        )
+    }
+
+    pub fn file_span<F: FnOnce() -> R, R>(parent: &RunContext, file_idx: usize, file_name: &str, f: F) -> R {
+        let tracer = global::tracer(INSTRUMENTATION_NAME);
+        let mut span = match &parent.0 {
+            Some(cx) => tracer.start_with_context(format!("file[{}]", file_idx), cx),
+            None => tracer.start(format!("file[{}]", file_idx)),
+        };
+        span.set_attribute(KeyValue::new("file.name", file_name.to_string()));
+        let result = f();
+        span.end();
+        result
+    }
+
+    /// Record a handful of ad-hoc named counters for a completed run, for
+    /// commands whose results don't warrant their own typed stats struct
+    /// (see [`record_churn_metrics`] for one that does). Each counter is
+    /// published as `"<command>.<name>"`.
+    pub fn record_run_metrics(command: &str, counters: &[(&str, u64)]) {
+        let meter = global::meter(INSTRUMENTATION_NAME);
+        for (name, value) in counters {
+            meter
+                .u64_counter(format!("{command}.{name}"))
+                .build()
+                .add(*value, &[]);
+        }
+    }
+
+    pub fn record_churn_metrics(stats: &ChurnRunStats<'_>) {
+        let meter = global::meter(INSTRUMENTATION_NAME);
+
+        let new_entities = meter.u64_counter("churn.entities.new").build();
+        let returning_entities = meter.u64_counter("churn.entities.returning").build();
+        for (day_index, new, returning) in stats.daily_new_vs_returning {
+            let attrs = [KeyValue::new("day_index", i64::from(*day_index))];
+            new_entities.add(*new as u64, &attrs);
+            returning_entities.add(*returning as u64, &attrs);
+        }
+
+        meter
+            .u64_counter("churn.logins.total")
+            .build()
+            .add(stats.total_logins as u64, &[]);
+
+        let lifecycle_gauge = meter.u64_counter("churn.entities.by_lifecycle").build();
+        for (bucket, count) in stats.lifecycle_counts {
+            lifecycle_gauge.add(*count as u64, &[KeyValue::new("lifecycle", bucket.clone())]);
+        }
+
+        let activity_gauge = meter
+            .u64_counter("churn.entities.by_activity_pattern")
+            .build();
+        for (bucket, count) in stats.activity_pattern_counts {
+            activity_gauge.add(
+                *count as u64,
+                &[KeyValue::new("activity_pattern", bucket.clone())],
+            );
+        }
+
+        let confidence_histogram = meter.f64_histogram("churn.ephemeral_confidence").build();
+        for confidence in stats.ephemeral_confidences {
+            confidence_histogram.record(f64::from(*confidence), &[]);
+        }
+    }
+}
+
+#[cfg(not(feature = "enable_otel"))]
+mod imp {
+    use super::ChurnRunStats;
+    use anyhow::{anyhow, Result};
+
+    pub struct OtelHandle;
+
+    pub fn init(_endpoint: &str) -> Result<OtelHandle> {
+        Err(anyhow!(
+            "--otel-endpoint was given but this build was compiled without the `enable_otel` feature"
+        ))
+    }
+
+    pub struct RunSpanGuard;
+
+    pub fn run_span(_command: &str) -> RunSpanGuard {
+        RunSpanGuard
+    }
+
+    pub struct RunContext;
+
+    pub fn run_context(_guard: &RunSpanGuard) -> RunContext {
+        RunContext
+    }
+
+    pub fn file_span<F: FnOnce() -> R, R>(_parent: &RunContext, _file_idx: usize, _file_name: &str, f: F) -> R {
+        f()
+    }
+
+    pub fn record_churn_metrics(_stats: &ChurnRunStats<'_>) {}
+
+    pub fn record_run_metrics(_command: &str, _counters: &[(&str, u64)]) {}
+}
+
+pub use imp::{OtelHandle, RunContext, RunSpanGuard};
+
+/// Initialize OpenTelemetry tracing/metrics export if `endpoint` is given.
+/// Returns `Ok(None)` when `endpoint` is `None`, without touching any global
+/// state. The returned handle must be kept alive for the duration of the run;
+/// dropping it flushes and shuts down the exporters.
+pub fn init(endpoint: Option<&str>) -> Result<Option<OtelHandle>> {
+    endpoint.map(imp::init).transpose()
+}
+
+/// Start the root span for a command run. Ends the span when the returned
+/// guard is dropped. A no-op when `enable_otel` isn't compiled in.
+pub fn run_span(command: &str) -> RunSpanGuard {
+    imp::run_span(command)
+}
+
+/// Snapshot the run span's identity so it can be passed explicitly into
+/// [`file_span`] calls made from other threads (e.g. a `rayon` worker pool),
+/// where `Context::current()` wouldn't otherwise follow it. A no-op when
+/// `enable_otel` isn't compiled in.
+pub fn run_context(guard: &RunSpanGuard) -> RunContext {
+    imp::run_context(guard)
+}
+
+/// Wrap `f` in a child span describing the processing of one input file,
+/// nested under `parent` (see [`run_context`]). A no-op passthrough when
+/// `enable_otel` isn't compiled in.
+pub fn file_span<F: FnOnce() -> R, R>(parent: &RunContext, file_idx: usize, file_name: &str, f: F) -> R {
+    imp::file_span(parent, file_idx, file_name, f)
+}
+
+/// Record the aggregate gauges/counters/histogram for a completed churn run.
+/// A no-op when `enable_otel` isn't compiled in.
+pub fn record_churn_metrics(stats: &ChurnRunStats<'_>) {
+    imp::record_churn_metrics(stats)
+}
+
+/// Record a handful of named `u64` counters for a completed run. A no-op
+/// when `enable_otel` isn't compiled in.
+pub fn record_run_metrics(command: &str, counters: &[(&str, u64)]) {
+    imp::record_run_metrics(command, counters)
+}
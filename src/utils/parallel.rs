@@ -5,13 +5,14 @@
 //! progress tracking and error handling.
 
 use crate::audit::types::AuditEntry;
-use crate::utils::progress::ProgressBar;
+use crate::utils::progress::{Progress, ProgressBar};
 use crate::utils::reader::open_file;
 use anyhow::{Context, Result};
 use rayon::prelude::*;
 use std::io::{BufRead, BufReader};
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Result of processing a single file
 #[derive(Debug)]
@@ -22,117 +23,269 @@ pub struct FileProcessResult<T> {
     pub data: T,
 }
 
+/// Per-file counts a [`process_files_parallel`] processor reports alongside
+/// its own result, so the aggregate [`Metrics`] can show how much of a run
+/// went to successfully parsed entries versus silently skipped bad lines.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileMetrics {
+    /// Bytes read from disk for this file (post-decompression).
+    pub bytes_read: u64,
+    /// Lines read (including blank and unparsable lines).
+    pub lines_read: usize,
+    /// Lines successfully parsed as an [`AuditEntry`].
+    pub entries_parsed: usize,
+    /// Lines where `serde_json::from_str::<AuditEntry>` returned `Err`.
+    pub parse_failures: usize,
+}
+
+impl FileMetrics {
+    fn merge(&mut self, other: Self) {
+        self.bytes_read += other.bytes_read;
+        self.lines_read += other.lines_read;
+        self.entries_parsed += other.entries_parsed;
+        self.parse_failures += other.parse_failures;
+    }
+}
+
+/// Per-stage instrumentation collected by [`process_files_parallel`],
+/// distinguishing wall-clock time (how long the run actually took) from
+/// summed per-thread processing time (how much CPU it burned), so an
+/// operator can tell a slow disk from a genuinely CPU-bound run. Printed via
+/// [`Metrics::report`] behind a command's `--stats` flag.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Metrics {
+    /// Wall-clock time spent in the byte-size scan phase (an `fs::metadata`
+    /// stat per file, not a read).
+    pub wall_scan: Duration,
+    /// Summed per-thread time spent in the scan phase.
+    pub cpu_scan: Duration,
+    /// Wall-clock time spent in the processing phase.
+    pub wall_process: Duration,
+    /// Summed per-thread time spent in the processing phase.
+    pub cpu_process: Duration,
+    /// Number of files considered.
+    pub files_considered: usize,
+    /// Total bytes read across all files.
+    pub bytes_read: u64,
+    /// Total lines read across all files.
+    pub lines_read: usize,
+    /// Total lines successfully parsed as an [`AuditEntry`].
+    pub entries_parsed: usize,
+    /// Total lines where JSON parsing failed and were skipped.
+    pub parse_failures: usize,
+}
+
+impl Metrics {
+    /// Processing-phase throughput in lines/sec (0 if the phase took no measurable time).
+    pub fn lines_per_sec(&self) -> f64 {
+        let secs = self.wall_process.as_secs_f64();
+        if secs > 0.0 {
+            self.lines_read as f64 / secs
+        } else {
+            0.0
+        }
+    }
+
+    /// Processing-phase throughput in MB/sec (0 if the phase took no measurable time).
+    pub fn mb_per_sec(&self) -> f64 {
+        let secs = self.wall_process.as_secs_f64();
+        if secs > 0.0 {
+            (self.bytes_read as f64 / (1024.0 * 1024.0)) / secs
+        } else {
+            0.0
+        }
+    }
+
+    /// Print a summary of the collected metrics, for commands' `--stats` flag.
+    pub fn report(&self) {
+        eprintln!("\nMetrics:");
+        eprintln!("  Files considered: {}", self.files_considered);
+        eprintln!(
+            "  Bytes read: {}",
+            crate::utils::format::format_bytes(self.bytes_read)
+        );
+        eprintln!(
+            "  Lines read: {}",
+            crate::utils::format::format_number(self.lines_read)
+        );
+        eprintln!(
+            "  Entries parsed: {}",
+            crate::utils::format::format_number(self.entries_parsed)
+        );
+        if self.parse_failures > 0 {
+            let skip_percentage =
+                (self.parse_failures as f64 / self.lines_read.max(1) as f64) * 100.0;
+            eprintln!(
+                "  Parse failures: {} ({:.2}%)",
+                crate::utils::format::format_number(self.parse_failures),
+                skip_percentage
+            );
+        }
+        eprintln!(
+            "  Scan phase:    wall {:.2}s, cpu {:.2}s",
+            self.wall_scan.as_secs_f64(),
+            self.cpu_scan.as_secs_f64()
+        );
+        eprintln!(
+            "  Process phase: wall {:.2}s, cpu {:.2}s",
+            self.wall_process.as_secs_f64(),
+            self.cpu_process.as_secs_f64()
+        );
+        eprintln!(
+            "  Throughput: {:.1} lines/sec, {:.2} MB/sec",
+            self.lines_per_sec(),
+            self.mb_per_sec()
+        );
+    }
+}
+
 /// Process multiple files in parallel with memory-efficient streaming
 ///
 /// This function processes files concurrently using a streaming approach that
 /// processes entries line-by-line without loading entire files into memory.
 /// This prevents memory allocation failures on large files.
 ///
+/// Byte-identical duplicates (e.g. overlapping rotated logs like
+/// `vault_audit.log` and `vault_audit.log.1`) are dropped up front via
+/// [`crate::utils::dedup::dedup_files`] so they can't double-count.
+///
+/// Progress is sized from on-disk byte sizes (`fs::metadata`, no read) and
+/// driven by `processor` reporting bytes consumed through the `&dyn Progress`
+/// it's handed - the same byte-based model [`crate::utils::processor::FileProcessor`]
+/// uses - so a file is read at most once instead of once to count lines,
+/// again to process, and a third time for the completion message.
+///
 /// # Arguments
 /// * `files` - List of file paths to process
-/// * `processor` - Function that processes a single file with streaming callback
+/// * `processor` - Function that processes a single file with streaming callback,
+///   reporting bytes consumed via the `&dyn Progress` it's given
 /// * `combiner` - Function that combines results from all files
 ///
 /// # Returns
-/// Combined result from all files plus total lines processed
+/// Combined result from all files, total lines processed, and per-stage
+/// [`Metrics`] (wall-clock vs. summed per-thread time, parse success/failure
+/// counts, derived throughput) for a `--stats` flag to print.
 pub fn process_files_parallel<T, F, C, R>(
     files: &[String],
     processor: F,
     combiner: C,
-) -> Result<(R, usize)>
+) -> Result<(R, usize, Metrics)>
 where
     T: Send + 'static,
     R: Send + 'static,
-    F: Fn(&str) -> Result<T> + Send + Sync,
+    F: Fn(&str, &dyn Progress) -> Result<(T, FileMetrics)> + Send + Sync,
     C: Fn(Vec<FileProcessResult<T>>) -> R + Send + Sync,
 {
     if files.is_empty() {
         return Err(anyhow::anyhow!("No files provided for processing"));
     }
 
+    // Drop byte-identical rotated-log duplicates before they can double-count
+    // every metric downstream.
+    let (files, dedup_report) = crate::utils::dedup::dedup_files(files)?;
+    dedup_report.report();
+    let files = files.as_slice();
+
     eprintln!("Processing {} files in parallel...", files.len());
 
-    // First pass: count total lines across all files for accurate progress
-    eprintln!("Scanning files to determine total work...");
-    let total_lines_to_process: usize = files
+    // Size progress from on-disk byte sizes - a `stat()` per file rather than
+    // the full read-through-and-count-lines pre-scan this used to do - so the
+    // total is known instantly instead of after a full file read.
+    let scan_cpu_nanos = AtomicU64::new(0);
+    let wall_scan_start = Instant::now();
+    let total_bytes: u64 = files
         .par_iter()
-        .map(|file_path| count_file_lines(file_path).unwrap_or(0))
+        .map(|file_path| {
+            let task_start = Instant::now();
+            let bytes = std::fs::metadata(file_path).map(|m| m.len()).unwrap_or(0);
+            scan_cpu_nanos.fetch_add(task_start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+            bytes
+        })
         .sum();
+    let wall_scan = wall_scan_start.elapsed();
 
     eprintln!(
-        "Total lines to process: {}",
-        crate::utils::format::format_number(total_lines_to_process)
+        "Total to process: {}",
+        crate::utils::format::format_bytes(total_bytes)
     );
 
-    let processed_lines = Arc::new(AtomicUsize::new(0));
-    let progress = Arc::new(Mutex::new(ProgressBar::new(
-        total_lines_to_process,
-        "Processing",
-    )));
-
-    // Initialize global progress for system_overview streaming
-    crate::commands::system_overview::init_parallel_progress(
-        processed_lines.clone(),
-        progress.clone(),
-    );
+    let progress: Arc<dyn Progress> = Arc::new(ProgressBar::new(total_bytes as usize, "Processing"));
 
     // Process files in parallel
+    let process_cpu_nanos = AtomicU64::new(0);
+    let wall_process_start = Instant::now();
     let results: Result<Vec<_>> = files
         .par_iter()
         .enumerate()
-        .map(|(idx, file_path)| -> Result<FileProcessResult<T>> {
+        .map(|(idx, file_path)| -> Result<(FileProcessResult<T>, FileMetrics)> {
             // Don't print starting messages to avoid interfering with progress bar
 
-            // Process file using streaming approach (progress updated internally)
-            let data = processor(file_path)
+            // Process file using streaming approach; `processor` reports bytes
+            // consumed through `progress` as it goes.
+            let task_start = Instant::now();
+            let (data, file_metrics) = processor(file_path, progress.as_ref())
                 .with_context(|| format!("Failed to process file: {}", file_path))?;
-
-            // Count lines for completion message
-            let lines_count = count_file_lines(file_path)?;
-
-            // Print completion message without interfering with progress
-            if let Ok(mut progress) = progress.lock() {
-                eprint!("\r"); // Clear current line
-                eprint!("{}", " ".repeat(100)); // Clear with spaces
-                eprint!("\r"); // Return to start
-                eprintln!(
-                    "[{}/{}] âœ“ Completed: {} ({} lines)",
-                    idx + 1,
-                    files.len(),
-                    file_path.split('/').next_back().unwrap_or(file_path),
-                    crate::utils::format::format_number(lines_count)
-                );
-                // Re-render progress bar on new line
-                progress.render();
-            }
-
-            Ok(FileProcessResult {
-                file_path: file_path.clone(),
-                lines_processed: lines_count,
-                data,
-            })
+            process_cpu_nanos.fetch_add(task_start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+
+            progress.message(&format!(
+                "[{}/{}] ✓ Completed: {} ({} lines)",
+                idx + 1,
+                files.len(),
+                file_path.split('/').next_back().unwrap_or(file_path),
+                crate::utils::format::format_number(file_metrics.lines_read)
+            ));
+
+            Ok((
+                FileProcessResult {
+                    file_path: file_path.clone(),
+                    lines_processed: file_metrics.lines_read,
+                    data,
+                },
+                file_metrics,
+            ))
         })
         .collect();
 
     let results = results?;
-    let total_lines_processed = processed_lines.load(Ordering::Relaxed);
-
-    if let Ok(mut progress) = progress.lock() {
-        // Clear the progress line before final message
-        eprint!("\r");
-        eprint!("{}", " ".repeat(80));
-        eprint!("\r");
-        progress.finish_with_message(&format!("Processed {} total lines", total_lines_processed));
+    let wall_process = wall_process_start.elapsed();
+
+    let mut combined_file_metrics = FileMetrics::default();
+    let (file_results, file_metrics): (Vec<_>, Vec<_>) = results.into_iter().unzip();
+    for metrics in file_metrics {
+        combined_file_metrics.merge(metrics);
     }
 
+    progress.finish(&format!(
+        "Processed {} total lines",
+        crate::utils::format::format_number(combined_file_metrics.lines_read)
+    ));
+
+    let metrics = Metrics {
+        wall_scan,
+        cpu_scan: Duration::from_nanos(scan_cpu_nanos.load(Ordering::Relaxed)),
+        wall_process,
+        cpu_process: Duration::from_nanos(process_cpu_nanos.load(Ordering::Relaxed)),
+        files_considered: files.len(),
+        bytes_read: combined_file_metrics.bytes_read,
+        lines_read: combined_file_metrics.lines_read,
+        entries_parsed: combined_file_metrics.entries_parsed,
+        parse_failures: combined_file_metrics.parse_failures,
+    };
+
+    let total_lines_processed = combined_file_metrics.lines_read;
+
     // Combine results
-    let result = combiner(results);
+    let result = combiner(file_results);
 
-    Ok((result, total_lines_processed))
+    Ok((result, total_lines_processed, metrics))
 }
 
-/// Count lines in a file for progress tracking (lightweight)
-fn count_file_lines(file_path: &str) -> Result<usize> {
+/// Count lines in a file for progress tracking (lightweight).
+///
+/// [`process_files_parallel`] no longer pre-scans this way (it sizes progress
+/// from `fs::metadata` byte sizes instead), but sequential callers that want
+/// an accurate total before a single-file streaming pass still use this.
+pub(crate) fn count_file_lines(file_path: &str) -> Result<usize> {
     let file =
         open_file(file_path).with_context(|| format!("Failed to open file: {}", file_path))?;
     let reader = BufReader::new(file);
@@ -218,14 +371,15 @@ pub fn process_files_aggregate<T, F, A>(
 ) -> Result<(T, usize)>
 where
     T: Send + Clone + Sync + 'static,
-    F: Fn(&str) -> Result<T> + Send + Sync,
+    F: Fn(&str, &dyn Progress) -> Result<(T, FileMetrics)> + Send + Sync,
     A: Fn(T, T) -> T + Send + Sync,
 {
-    process_files_parallel(files, processor, |results| {
+    let (result, total_lines, _metrics) = process_files_parallel(files, processor, |results| {
         results
             .into_iter()
             .fold(initial.clone(), |acc, result| aggregator(acc, result.data))
-    })
+    })?;
+    Ok((result, total_lines))
 }
 
 #[cfg(test)]
@@ -248,29 +402,36 @@ mod tests {
         }).collect();
 
         // Process files to count entries per file
-        let (results, _total_lines) = process_files_parallel(
+        let (results, total_lines, metrics) = process_files_parallel(
             &files,
-            |file_path| -> Result<usize> {
+            |file_path, progress| -> Result<(usize, FileMetrics)> {
                 let file = open_file(file_path)?;
                 let reader = BufReader::new(file);
                 let mut count = 0;
+                let mut file_metrics = FileMetrics::default();
                 for line_result in reader.lines() {
                     let line = line_result?;
+                    progress.inc(line.len() + 1);
                     if line.trim().is_empty() {
                         continue;
                     }
+                    file_metrics.lines_read += 1;
                     if serde_json::from_str::<AuditEntry>(&line).is_ok() {
                         count += 1;
+                        file_metrics.entries_parsed += 1;
+                    } else {
+                        file_metrics.parse_failures += 1;
                     }
                 }
-                Ok(count)
+                Ok((count, file_metrics))
             },
             |results| results.into_iter().map(|r| r.data).sum::<usize>(),
         )
         .unwrap();
 
         assert_eq!(results, 6); // 2 entries per file * 3 files
-                                // Note: total_lines from atomic counter is only updated by streaming processors
-                                // that explicitly call the global progress tracker
+        assert_eq!(total_lines, 6);
+        assert_eq!(metrics.entries_parsed, 6);
+        assert_eq!(metrics.files_considered, 3);
     }
 }
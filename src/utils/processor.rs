@@ -5,13 +5,17 @@
 //! parallel and sequential processing modes.
 
 use crate::audit::types::AuditEntry;
-use crate::utils::progress::ProgressBar;
+use crate::utils::progress::{Progress, ProgressBar};
 use crate::utils::reader::open_file;
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use rayon::prelude::*;
-use std::io::{BufRead, BufReader};
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::{Arc, Mutex};
+use std::io::{BufRead, BufReader, Read};
+use std::sync::mpsc;
+use std::sync::Arc;
+
+/// Size of each chunk the reader thread reads from disk and hands across the
+/// channel in [`FileProcessor::process_single_file_streaming`].
+const READ_CHUNK_SIZE: usize = 1024 * 1024;
 
 /// Processing mode for file processing
 #[derive(Debug, Clone, Copy)]
@@ -24,33 +28,117 @@ pub enum ProcessingMode {
     Auto,
 }
 
+/// How [`FileProcessor`] responds to a line that fails JSON parsing.
+#[derive(Debug, Clone)]
+pub enum OnParseError {
+    /// Skip the bad line and keep going. The default.
+    Skip,
+    /// Abort the whole run with an error identifying the offending line.
+    Fail,
+    /// Keep going, but remember up to `max` samples (line number, truncated
+    /// content, and the serde error message) per file, surfaced later via
+    /// [`ProcessStats::parse_error_samples`] / [`ProcessStats::report`].
+    Collect {
+        /// Maximum number of samples to retain per file.
+        max: usize,
+    },
+}
+
+impl Default for OnParseError {
+    fn default() -> Self {
+        Self::Skip
+    }
+}
+
+/// Minimum lines read from a file before `abort_error_rate` starts evaluating
+/// the running skip ratio, so a short burst of bad lines doesn't trip the
+/// circuit breaker before there's a meaningful sample to judge it against.
+const MIN_SAMPLE_LINES_FOR_ABORT: usize = 100;
+
 /// Configuration for file processing
-#[derive(Debug)]
 pub struct ProcessorConfig {
     /// Processing mode to use
     pub mode: ProcessingMode,
-    /// Progress update frequency (lines)
+    /// Progress update frequency, in bytes consumed since the last flush to
+    /// the shared progress bar (progress is tracked by bytes, not lines -
+    /// see [`FileProcessor::process_single_file_streaming`])
     pub progress_frequency: usize,
     /// Whether to show detailed per-file completion messages
     pub show_file_completion: bool,
     /// Custom progress label
     pub progress_label: String,
-    /// Whether to use strict JSON parsing (fail on any parse error)
-    pub strict_parsing: bool,
+    /// How to respond to a line that fails JSON parsing
+    pub on_parse_error: OnParseError,
+    /// Once `skipped_lines / total_lines` for a file exceeds this ratio
+    /// (after [`MIN_SAMPLE_LINES_FOR_ABORT`] lines), abort that file with an
+    /// error instead of silently grinding through what's likely the wrong
+    /// file format entirely.
+    pub abort_error_rate: Option<f64>,
+    /// When `true`, [`FileProcessor::process_files_streaming`] guarantees
+    /// `aggregator(acc, file_n)` is applied in the caller's original file
+    /// order (`file_0, file_1, ..., file_n`) even when files are processed
+    /// in parallel. Needed for analyses that fold time-ordered sequences or
+    /// track first/last-seen state across files. Leave `false` (the
+    /// default) for purely commutative aggregators - there's no reordering
+    /// cost to pay for those.
+    pub ordered_aggregation: bool,
+    /// Where progress is reported. `None` (the default) builds a terminal
+    /// [`ProgressBar`] sized to the actual total once it's known; set this
+    /// to report through something else instead - e.g. [`JsonProgress`] for
+    /// scripts/CI, or [`NoopProgress`] under `-q`.
+    ///
+    /// [`JsonProgress`]: crate::utils::progress::JsonProgress
+    /// [`NoopProgress`]: crate::utils::progress::NoopProgress
+    pub progress_sink: Option<Arc<dyn Progress>>,
+}
+
+impl std::fmt::Debug for ProcessorConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProcessorConfig")
+            .field("mode", &self.mode)
+            .field("progress_frequency", &self.progress_frequency)
+            .field("show_file_completion", &self.show_file_completion)
+            .field("progress_label", &self.progress_label)
+            .field("on_parse_error", &self.on_parse_error)
+            .field("abort_error_rate", &self.abort_error_rate)
+            .field("ordered_aggregation", &self.ordered_aggregation)
+            .field("progress_sink", &self.progress_sink.is_some())
+            .finish()
+    }
 }
 
 impl Default for ProcessorConfig {
     fn default() -> Self {
         Self {
             mode: ProcessingMode::Auto,
-            progress_frequency: 2000,
+            progress_frequency: 256 * 1024,
             show_file_completion: true,
             progress_label: "Processing".to_string(),
-            strict_parsing: false,
+            on_parse_error: OnParseError::Skip,
+            abort_error_rate: None,
+            ordered_aggregation: false,
+            progress_sink: None,
         }
     }
 }
 
+/// One sample of a line that failed JSON parsing, retained only when
+/// [`OnParseError::Collect`] is configured.
+#[derive(Debug, Clone)]
+pub struct ParseErrorSample {
+    /// File the bad line was read from
+    pub file_path: String,
+    /// 1-based line number within that file
+    pub line_number: usize,
+    /// Line content, truncated to keep the sample small
+    pub content: String,
+    /// The serde error message
+    pub message: String,
+}
+
+/// Line content longer than this is truncated when stored in a [`ParseErrorSample`].
+const PARSE_ERROR_SAMPLE_CONTENT_LIMIT: usize = 200;
+
 /// Statistics collected during processing
 #[derive(Debug, Default, Clone)]
 pub struct ProcessStats {
@@ -62,6 +150,13 @@ pub struct ProcessStats {
     pub skipped_lines: usize,
     /// Number of files processed
     pub files_processed: usize,
+    /// Total bytes read from disk across all files (post-decompression if
+    /// the source was compressed), used to drive byte-based progress
+    /// instead of a full pre-scan line count
+    pub bytes_read: u64,
+    /// Sampled parse errors, populated only when `on_parse_error` is
+    /// [`OnParseError::Collect`]
+    pub parse_error_samples: Vec<ParseErrorSample>,
 }
 
 impl ProcessStats {
@@ -71,6 +166,9 @@ impl ProcessStats {
         self.parsed_entries += other.parsed_entries;
         self.skipped_lines += other.skipped_lines;
         self.files_processed += other.files_processed;
+        self.bytes_read += other.bytes_read;
+        self.parse_error_samples
+            .extend(other.parse_error_samples.iter().cloned());
     }
 
     /// Print a summary of processing statistics
@@ -85,6 +183,10 @@ impl ProcessStats {
             "  Parsed entries: {}",
             crate::utils::format::format_number(self.parsed_entries)
         );
+        eprintln!(
+            "  Bytes read: {}",
+            crate::utils::format::format_bytes(self.bytes_read)
+        );
         if self.skipped_lines > 0 {
             let skip_percentage = (self.skipped_lines as f64 / self.total_lines as f64) * 100.0;
             eprintln!(
@@ -93,6 +195,15 @@ impl ProcessStats {
                 skip_percentage
             );
         }
+        if !self.parse_error_samples.is_empty() {
+            eprintln!("  Parse error samples:");
+            for sample in &self.parse_error_samples {
+                eprintln!(
+                    "    {}:{} - {} ({})",
+                    sample.file_path, sample.line_number, sample.message, sample.content
+                );
+            }
+        }
     }
 }
 
@@ -160,6 +271,12 @@ impl FileProcessor {
             return Ok((initial, ProcessStats::default()));
         }
 
+        // Resolve any `s3://bucket/prefix/` or glob (`s3://bucket/.../*.log`)
+        // entries down to concrete per-object keys up front, so the
+        // size-probing/parallel-chunking logic below sees one real source per file.
+        let files = crate::utils::reader::expand_sources(files)?;
+        let files = files.as_slice();
+
         let mode = self.determine_processing_mode(files);
 
         match mode {
@@ -231,72 +348,81 @@ impl FileProcessor {
     {
         eprintln!("Processing {} files in parallel...", files.len());
 
-        // Pre-scan to determine total work
-        eprintln!("Scanning files to determine total work...");
-        let total_lines: usize = files
+        // Sum file sizes for the progress total - a `stat()` per file rather
+        // than the full read-through-and-count-lines pre-scan this used to
+        // do, so large multi-file runs don't pay for reading every file twice.
+        let total_bytes: u64 = files
             .par_iter()
-            .map(|file_path| count_file_lines(file_path).unwrap_or(0))
+            .map(|file_path| std::fs::metadata(file_path).map(|m| m.len()).unwrap_or(0))
             .sum();
 
         eprintln!(
-            "Total lines to process: {}",
-            crate::utils::format::format_number(total_lines)
+            "Total to process: {}",
+            crate::utils::format::format_bytes(total_bytes)
         );
 
-        let processed_lines = Arc::new(AtomicUsize::new(0));
-        let progress = Arc::new(Mutex::new(ProgressBar::new(
-            total_lines,
-            &self.config.progress_label,
-        )));
+        let progress: Arc<dyn Progress> = match &self.config.progress_sink {
+            Some(sink) => sink.clone(),
+            None => Arc::new(ProgressBar::new(
+                total_bytes as usize,
+                &self.config.progress_label,
+            )),
+        };
+        progress.set_total(total_bytes as usize);
 
         // Process files in parallel
         let results: Result<Vec<_>> = files
             .par_iter()
             .enumerate()
-            .map(|(idx, file_path)| -> Result<(T, ProcessStats)> {
+            .map(|(idx, file_path)| -> Result<(usize, T, ProcessStats)> {
                 let mut file_state = initial.clone();
                 let mut local_processor = line_processor.clone();
 
-                let progress_ref = (processed_lines.clone(), progress.clone());
                 let stats = self.process_single_file_streaming(
                     file_path,
                     &mut local_processor,
                     &mut file_state,
-                    Some(&progress_ref),
+                    Some(&progress),
                 )?;
 
                 if self.config.show_file_completion {
-                    let lines_count = count_file_lines(file_path)?;
-                    if let Ok(progress) = progress.lock() {
-                        progress.println(format!(
-                            "[{}/{}] ✓ Completed: {} ({} lines)",
-                            idx + 1,
-                            files.len(),
-                            file_path.split('/').next_back().unwrap_or(file_path),
-                            crate::utils::format::format_number(lines_count)
-                        ));
-                    }
+                    progress.message(&format!(
+                        "[{}/{}] ✓ Completed: {} ({} lines, {})",
+                        idx + 1,
+                        files.len(),
+                        file_path.split('/').next_back().unwrap_or(file_path),
+                        crate::utils::format::format_number(stats.total_lines),
+                        crate::utils::format::format_bytes(stats.bytes_read)
+                    ));
                 }
 
-                Ok((file_state, stats))
+                Ok((idx, file_state, stats))
             })
             .collect();
 
-        let results = results?;
-
-        // Finish progress bar with final message
-        if let Ok(progress) = progress.lock() {
-            progress.finish_with_message(&format!(
-                "Processed {} total lines",
-                crate::utils::format::format_number(processed_lines.load(Ordering::Relaxed))
-            ));
+        let mut results = results?;
+
+        // Finish progress reporting with a final summary message
+        progress.finish(&format!(
+            "Processed {}",
+            crate::utils::format::format_bytes(total_bytes)
+        ));
+
+        // `ordered_aggregation` guarantees the fold below sees files in the
+        // caller's original order regardless of which thread finished first.
+        // Rayon's `collect` already happens to preserve input order today,
+        // but sorting explicitly makes that a guarantee callers can rely on
+        // rather than an incidental side effect of the current collection
+        // strategy.
+        if self.config.ordered_aggregation {
+            results.sort_by_key(|(idx, _, _)| *idx);
         }
 
         // Aggregate all results
         let mut combined_stats = ProcessStats::default();
         let final_result = results
             .into_iter()
-            .fold(initial, |acc, (file_result, file_stats)| {
+            .fold(initial, |acc, (_, file_result, file_stats)| {
                 combined_stats.merge(&file_stats);
                 aggregator(acc, file_result)
             });
@@ -343,11 +469,12 @@ impl FileProcessor {
 
             if self.config.show_file_completion {
                 eprintln!(
-                    "[{}/{}] ✓ Completed: {} ({} lines)",
+                    "[{}/{}] ✓ Completed: {} ({} lines, {})",
                     file_idx + 1,
                     files.len(),
                     file_path.split('/').next_back().unwrap_or(file_path),
-                    crate::utils::format::format_number(single_file_stats.total_lines)
+                    crate::utils::format::format_number(single_file_stats.total_lines),
+                    crate::utils::format::format_bytes(single_file_stats.bytes_read)
                 );
             }
         }
@@ -384,6 +511,8 @@ impl FileProcessor {
             parsed_entries: 0, // Unknown for collected results
             skipped_lines: 0,
             files_processed: files.len(),
+            bytes_read: 0,
+            parse_error_samples: Vec::new(),
         };
 
         Ok((results?, stats))
@@ -434,58 +563,155 @@ impl FileProcessor {
             parsed_entries: 0, // Unknown for collected results
             skipped_lines: 0,
             files_processed: files.len(),
+            bytes_read: 0,
+            parse_error_samples: Vec::new(),
         };
 
         Ok((results, stats))
     }
 
-    /// Process a single file with streaming and optional progress tracking
+    /// Process a single file with streaming and optional progress tracking.
+    ///
+    /// Reading happens on a dedicated thread that pushes fixed-size
+    /// [`READ_CHUNK_SIZE`] byte chunks across a small bounded channel, so I/O
+    /// overlaps JSON parsing on this thread instead of alternating with it.
+    /// Lines are split as `&[u8]` slices directly out of each chunk - no
+    /// per-line `String` allocation - with any line straddling a chunk
+    /// boundary stitched together via a small carry-over buffer.
     fn process_single_file_streaming<T, F>(
         &self,
         file_path: &str,
         line_processor: &mut F,
         state: &mut T,
-        progress: Option<&(Arc<AtomicUsize>, Arc<Mutex<ProgressBar>>)>,
+        progress: Option<&Arc<dyn Progress>>,
     ) -> Result<ProcessStats>
     where
         F: FnMut(&AuditEntry, &mut T),
     {
         let file =
             open_file(file_path).with_context(|| format!("Failed to open file: {}", file_path))?;
-        let reader = BufReader::new(file);
+
+        let (chunk_tx, chunk_rx) = mpsc::sync_channel::<Result<Vec<u8>>>(2);
+        let owned_path = file_path.to_string();
+        let reader_thread = std::thread::spawn(move || {
+            let mut file = file;
+            loop {
+                let mut chunk = vec![0u8; READ_CHUNK_SIZE];
+                match file.read(&mut chunk) {
+                    Ok(0) => break,
+                    Ok(n) => {
+                        chunk.truncate(n);
+                        if chunk_tx.send(Ok(chunk)).is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        let _ = chunk_tx.send(Err(anyhow!(e)
+                            .context(format!("Failed to read from {}", owned_path))));
+                        break;
+                    }
+                }
+            }
+        });
 
         let mut file_stats = ProcessStats::default();
+        let mut carry: Vec<u8> = Vec::new();
+        // Bytes consumed since the last progress-bar flush. Chunk boundaries,
+        // not line boundaries, are the natural unit for byte-based progress -
+        // one flush per chunk keeps this independent of line length/density.
+        let mut bytes_since_update: usize = 0;
+
+        for chunk in &chunk_rx {
+            let chunk = chunk?;
+            bytes_since_update += chunk.len();
+            file_stats.bytes_read += chunk.len() as u64;
+            carry.extend_from_slice(&chunk);
+
+            let mut start = 0;
+            while let Some(offset) = carry[start..].iter().position(|&b| b == b'\n') {
+                let line_end = start + offset;
+                self.process_line_bytes(
+                    &carry[start..line_end],
+                    file_path,
+                    line_processor,
+                    state,
+                    &mut file_stats,
+                )?;
+                start = line_end + 1;
+            }
+            carry.drain(..start);
 
-        for line_result in reader.lines() {
-            let line =
-                line_result.with_context(|| format!("Failed to read line from {}", file_path))?;
+            if bytes_since_update >= self.config.progress_frequency {
+                if let Some(progress) = &progress {
+                    progress.inc(bytes_since_update);
+                }
+                bytes_since_update = 0;
+            }
+        }
 
-            file_stats.total_lines += 1;
+        reader_thread
+            .join()
+            .map_err(|_| anyhow!("Reader thread panicked while reading {}", file_path))?;
 
-            // Update progress if in parallel mode
-            if file_stats.total_lines % self.config.progress_frequency == 0 {
-                if let Some((processed_lines, progress_bar)) = &progress {
-                    processed_lines.fetch_add(self.config.progress_frequency, Ordering::Relaxed);
-                    if let Ok(p) = progress_bar.lock() {
-                        p.update(processed_lines.load(Ordering::Relaxed));
-                    }
-                }
+        // A file not ending in a trailing newline leaves one last line in `carry`.
+        if !carry.is_empty() {
+            self.process_line_bytes(
+                &carry,
+                file_path,
+                line_processor,
+                state,
+                &mut file_stats,
+            )?;
+        }
+
+        // Flush any bytes consumed since the last progress update.
+        if bytes_since_update > 0 {
+            if let Some(progress) = &progress {
+                progress.inc(bytes_since_update);
             }
+        }
 
-            // Skip empty lines
-            if line.trim().is_empty() {
-                continue;
+        file_stats.files_processed = 1;
+        Ok(file_stats)
+    }
+
+    /// Parses and processes one line, given as a byte slice borrowed from the
+    /// chunk buffer (with any trailing `\r` from a CRLF file already possibly
+    /// present - stripped here rather than by the caller). Shared by
+    /// [`Self::process_single_file_streaming`] for both complete,
+    /// newline-terminated lines and the final unterminated line, if any.
+    fn process_line_bytes<T, F>(
+        &self,
+        line_bytes: &[u8],
+        file_path: &str,
+        line_processor: &mut F,
+        state: &mut T,
+        file_stats: &mut ProcessStats,
+    ) -> Result<()>
+    where
+        F: FnMut(&AuditEntry, &mut T),
+    {
+        let line_bytes = line_bytes
+            .strip_suffix(b"\r")
+            .unwrap_or(line_bytes);
+
+        file_stats.total_lines += 1;
+
+        // Skip empty (or whitespace-only) lines
+        if line_bytes.iter().all(u8::is_ascii_whitespace) {
+            return Ok(());
+        }
+
+        match serde_json::from_slice::<AuditEntry>(line_bytes) {
+            Ok(entry) => {
+                file_stats.parsed_entries += 1;
+                line_processor(&entry, state);
             }
+            Err(e) => {
+                file_stats.skipped_lines += 1;
 
-            // Parse and process entry
-            match serde_json::from_str::<AuditEntry>(&line) {
-                Ok(entry) => {
-                    file_stats.parsed_entries += 1;
-                    line_processor(&entry, state);
-                }
-                Err(e) => {
-                    file_stats.skipped_lines += 1;
-                    if self.config.strict_parsing {
+                match &self.config.on_parse_error {
+                    OnParseError::Fail => {
                         return Err(e).with_context(|| {
                             format!(
                                 "Failed to parse JSON at line {} in {}",
@@ -493,24 +719,41 @@ impl FileProcessor {
                             )
                         });
                     }
-                    // Skip invalid lines and continue in non-strict mode
+                    OnParseError::Collect { max } => {
+                        if file_stats.parse_error_samples.len() < *max {
+                            let mut content = String::from_utf8_lossy(line_bytes).into_owned();
+                            content.truncate(PARSE_ERROR_SAMPLE_CONTENT_LIMIT);
+                            file_stats.parse_error_samples.push(ParseErrorSample {
+                                file_path: file_path.to_string(),
+                                line_number: file_stats.total_lines,
+                                content,
+                                message: e.to_string(),
+                            });
+                        }
+                    }
+                    OnParseError::Skip => {}
                 }
-            }
-        }
 
-        // Update progress for remaining lines
-        if let Some((processed_lines, progress_bar)) = &progress {
-            let remaining = file_stats.total_lines % self.config.progress_frequency;
-            if remaining > 0 {
-                processed_lines.fetch_add(remaining, Ordering::Relaxed);
-                if let Ok(p) = progress_bar.lock() {
-                    p.update(processed_lines.load(Ordering::Relaxed));
+                if let Some(threshold) = self.config.abort_error_rate {
+                    if file_stats.total_lines >= MIN_SAMPLE_LINES_FOR_ABORT {
+                        let skip_ratio =
+                            file_stats.skipped_lines as f64 / file_stats.total_lines as f64;
+                        if skip_ratio > threshold {
+                            anyhow::bail!(
+                                "Parse error rate {:.1}% in {} exceeds abort_error_rate threshold \
+                                 {:.1}% after {} lines — this may not be a JSON audit log",
+                                skip_ratio * 100.0,
+                                file_path,
+                                threshold * 100.0,
+                                file_stats.total_lines
+                            );
+                        }
+                    }
                 }
             }
         }
 
-        file_stats.files_processed = 1;
-        Ok(file_stats)
+        Ok(())
     }
 }
 
@@ -555,7 +798,7 @@ impl ProcessorBuilder {
         self
     }
 
-    /// Set progress update frequency
+    /// Set progress update frequency, in bytes consumed
     #[must_use]
     #[allow(dead_code)]
     pub const fn progress_frequency(mut self, frequency: usize) -> Self {
@@ -578,11 +821,38 @@ impl ProcessorBuilder {
         self
     }
 
-    /// Enable strict JSON parsing
+    /// Set the parse-error policy (default: [`OnParseError::Skip`])
+    #[must_use]
+    #[allow(dead_code)]
+    pub fn on_parse_error(mut self, policy: OnParseError) -> Self {
+        self.config.on_parse_error = policy;
+        self
+    }
+
+    /// Abort a file once its skipped/total line ratio exceeds `rate` (after
+    /// a minimum sample of lines) — see [`ProcessorConfig::abort_error_rate`]
+    #[must_use]
+    #[allow(dead_code)]
+    pub const fn abort_error_rate(mut self, rate: f64) -> Self {
+        self.config.abort_error_rate = Some(rate);
+        self
+    }
+
+    /// Guarantee `aggregator` sees files in their original order even under
+    /// parallel processing — see [`ProcessorConfig::ordered_aggregation`]
+    #[must_use]
+    #[allow(dead_code)]
+    pub const fn ordered_aggregation(mut self, ordered: bool) -> Self {
+        self.config.ordered_aggregation = ordered;
+        self
+    }
+
+    /// Report progress through `sink` instead of building a terminal
+    /// [`ProgressBar`] — see [`ProcessorConfig::progress_sink`]
     #[must_use]
     #[allow(dead_code)]
-    pub const fn strict_parsing(mut self, strict: bool) -> Self {
-        self.config.strict_parsing = strict;
+    pub fn progress_sink(mut self, sink: Arc<dyn Progress>) -> Self {
+        self.config.progress_sink = Some(sink);
         self
     }
 
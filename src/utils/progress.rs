@@ -1,30 +1,402 @@
 //! Progress bar utilities using indicatif
 //!
 //! Provides a wrapper around indicatif's `ProgressBar` for consistent
-//! progress reporting across all commands.
+//! progress reporting across all commands, plus the [`Progress`] trait that
+//! lets callers swap in a different reporting sink entirely (NDJSON for
+//! scripts/CI, or nothing at all under `-q`). [`MultiProgress`] coordinates
+//! several bars sharing one terminal block (e.g. one per worker plus an
+//! aggregate total) so they don't garble each other's output.
+//! [`ProgressBar::new_bytes`] switches position/rate formatting to
+//! human-readable sizes for byte-oriented work like scanning raw log files.
+//! [`ProgressIteratorExt`]/[`ProgressReader`] drive a bar automatically from
+//! an iterator or a `Read`, so a caller can't forget to call `inc()`/`update()`
+//! on an early return. [`ProgressBar::enable_steady_tick`] keeps a spinner
+//! animating across long blocking calls with no progress updates of their own.
+//! [`BarStyle`]/[`ProgressBar::with_style`] make the template and glyphs
+//! configurable instead of hardcoded, for narrow terminals or redirected
+//! (non-color) output.
 
-use indicatif::{ProgressBar as IndicatifBar, ProgressStyle};
+use crate::utils::format::{format_bytes, format_duration_mmss};
+use indicatif::{ProgressBar as IndicatifBar, ProgressDrawTarget, ProgressStyle};
+use std::io::{IsTerminal, Read, Write};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Braille spinner frames, cycled by [`ProgressBar::render_line`] to drive
+/// the `{spinner}` placeholder - the same glyph set indicatif's own default
+/// spinner style uses.
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Fallback bar width (in characters) when [`BarStyle::width`] wasn't set
+/// and no terminal width could be determined - matches the width this bar
+/// hardcoded before rendering became configurable.
+const DEFAULT_BAR_WIDTH: usize = 40;
+
+/// Smoothing factor for the exponential moving average used to turn a
+/// jumpy instantaneous rate into a usable throughput/ETA estimate.
+const RATE_EMA_ALPHA: f64 = 0.3;
+
+/// Minimum gap between two actual terminal redraws of a [`ProgressBar`],
+/// whether triggered manually (`update()`/`render()`) or by
+/// [`ProgressBar::enable_steady_tick`]. Mirrors the refresh-rate limiting
+/// pbr and similar progress bars apply, so a tight update loop or a short
+/// steady-tick interval doesn't flicker the terminal or burn syscalls.
+const MIN_REDRAW_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Shared by every [`ProgressBar`] (and its steady-tick thread, if any) to
+/// enforce [`MIN_REDRAW_INTERVAL`]. Returns `true` - and records `now` - if
+/// enough time has passed since the last redraw that this caller should
+/// actually draw; `false` if the draw should be skipped.
+fn throttled_redraw(last_redraw: &Mutex<Option<Instant>>) -> bool {
+    let mut last = last_redraw.lock().expect("last redraw mutex poisoned");
+    let now = Instant::now();
+    let should_draw = match *last {
+        Some(prev) => now.duration_since(prev) >= MIN_REDRAW_INTERVAL,
+        None => true,
+    };
+    if should_draw {
+        *last = Some(now);
+    }
+    should_draw
+}
+
+/// Tracks the state needed to compute a smoothed (EMA) processing rate
+/// across successive [`ProgressBar::update`]/[`ProgressBar::inc`] calls.
+struct RateTracker {
+    last_instant: Instant,
+    last_pos: u64,
+    ema: f64,
+}
+
+impl RateTracker {
+    fn new() -> Self {
+        Self {
+            last_instant: Instant::now(),
+            last_pos: 0,
+            ema: 0.0,
+        }
+    }
+
+    /// Folds in the instantaneous rate since the last call and returns the
+    /// updated smoothed rate (lines/sec).
+    fn observe(&mut self, pos: u64) -> f64 {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_instant).as_secs_f64();
+        let delta = pos.saturating_sub(self.last_pos);
+        let instantaneous = if elapsed > 0.0 {
+            delta as f64 / elapsed
+        } else {
+            0.0
+        };
+        self.ema = RATE_EMA_ALPHA * instantaneous + (1.0 - RATE_EMA_ALPHA) * self.ema;
+        self.last_instant = now;
+        self.last_pos = pos;
+        self.ema
+    }
+
+    /// The current smoothed rate (lines/sec), without taking a new sample.
+    fn current_rate(&self) -> f64 {
+        self.ema
+    }
+}
+
+/// How a [`ProgressBar`] formats its position/total/rate - plain counts, or
+/// (like pbr's `Units::Bytes`) human-readable sizes for byte-oriented work
+/// such as scanning raw log files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Units {
+    /// Plain integer counts (e.g. lines).
+    #[default]
+    Count,
+    /// SI/binary-prefixed byte sizes, via [`format_bytes`].
+    Bytes,
+}
+
+/// Output verbosity level for progress and interim status reporting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    /// Suppress the progress display entirely (`-q`)
+    Quiet,
+    /// Default progress bar behavior
+    #[default]
+    Normal,
+    /// Print periodic interim rates in addition to the progress bar (`-v`)
+    Verbose,
+}
+
+/// Background thread spawned by [`ProgressBar::enable_steady_tick`], kept
+/// around only so it can be stopped and joined cleanly - from
+/// `enable_steady_tick` replacing a previous one, or from `finish()`.
+/// Dropping it (via [`Mutex::take`][Option::take]) signals the thread to
+/// stop and blocks until it exits.
+struct SteadyTick {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for SteadyTick {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Configurable rendering for a [`ProgressBar`] - inspired by indicatif's
+/// own `ProgressStyle` and pbr's `Style`/`FORMAT`, but owned by this crate
+/// so [`ProgressBar::render_line`] (the hand-rolled renderer [`MultiProgress`]
+/// depends on, since it hides the bar's own indicatif draw target) can honor
+/// the same template and glyphs as the directly-drawn case.
+///
+/// The template is a plain string with these placeholders: `{bar}`, `{pos}`,
+/// `{total}`, `{percent}`, `{eta}`, `{rate}`, `{msg}`, `{spinner}`. `{msg}`
+/// is this bar's label plus its computed rate/ETA (see
+/// [`ProgressBar::update_rate_message`]) - a caller who wants full control
+/// over layout can omit `{msg}` and compose `{rate}`/`{eta}`/`{pos}`/
+/// `{total}` directly instead.
+#[derive(Debug, Clone)]
+pub struct BarStyle {
+    template: String,
+    fill: char,
+    head: Option<char>,
+    empty: char,
+    width: Option<usize>,
+}
+
+impl BarStyle {
+    /// Starts from [`BarStyle::default`].
+    #[allow(dead_code)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the template string - see [`BarStyle`] for the placeholders.
+    #[allow(dead_code)]
+    pub fn template(mut self, template: &str) -> Self {
+        self.template = template.to_string();
+        self
+    }
+
+    /// Sets the bar's fill glyphs from a 2- or 3-character string, as with
+    /// indicatif's and pbr's own `progress_chars`/`FORMAT`: 2 characters are
+    /// `"<fill><empty>"`; 3 are `"<fill><head><empty>"`, where `head` marks
+    /// the bar's leading edge (e.g. pbr's `"=>-"`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chars` is not 2 or 3 characters.
+    #[allow(dead_code)]
+    pub fn progress_chars(mut self, chars: &str) -> Self {
+        let glyphs: Vec<char> = chars.chars().collect();
+        match glyphs.as_slice() {
+            [fill, empty] => {
+                self.fill = *fill;
+                self.head = None;
+                self.empty = *empty;
+            }
+            [fill, head, empty] => {
+                self.fill = *fill;
+                self.head = Some(*head);
+                self.empty = *empty;
+            }
+            _ => panic!("progress_chars expects 2 or 3 characters, got {chars:?}"),
+        }
+        self
+    }
+
+    /// Fixes the bar's width in characters instead of deriving it from the
+    /// terminal width (see [`bar_width`]).
+    #[allow(dead_code)]
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    fn progress_chars_string(&self) -> String {
+        let mut chars = String::new();
+        chars.push(self.fill);
+        if let Some(head) = self.head {
+            chars.push(head);
+        }
+        chars.push(self.empty);
+        chars
+    }
+
+    /// Renders the `{bar}` placeholder itself: `width` fill/head/empty
+    /// glyphs proportional to `percent` (0.0-100.0).
+    fn render_bar(&self, width: usize, percent: f64) -> String {
+        let filled = (((percent.clamp(0.0, 100.0) / 100.0) * width as f64).round() as usize).min(width);
+        match self.head {
+            Some(head) if filled > 0 && filled < width => {
+                let full = self.fill.to_string().repeat(filled - 1);
+                let empty = self.empty.to_string().repeat(width - filled);
+                format!("{full}{head}{empty}")
+            }
+            _ => {
+                let full = self.fill.to_string().repeat(filled);
+                let empty = self.empty.to_string().repeat(width - filled);
+                format!("{full}{empty}")
+            }
+        }
+    }
+}
+
+impl Default for BarStyle {
+    fn default() -> Self {
+        Self {
+            template: "{msg} [{bar}] {percent}% ({pos}/{total})".to_string(),
+            fill: '█',
+            head: None,
+            empty: '░',
+            width: None,
+        }
+    }
+}
+
+/// Whether bar glyphs should be colored, honoring a `CLICOLOR_FORCE`/
+/// `NO_COLOR` toggle so output stays clean when redirected to a file:
+/// `CLICOLOR_FORCE` (set to anything but `"0"`) forces color on even off a
+/// TTY; otherwise `NO_COLOR` (any value) forces it off; otherwise color
+/// follows whether stderr is a terminal.
+fn colors_enabled() -> bool {
+    if let Ok(val) = std::env::var("CLICOLOR_FORCE") {
+        if val != "0" {
+            return true;
+        }
+    }
+    if std::env::var_os("NO_COLOR").is_some() {
+        return false;
+    }
+    std::io::stderr().is_terminal()
+}
+
+/// The bar width (in characters) to render at: `style`'s fixed width if
+/// set, else the terminal width (via `$COLUMNS`, minus a rough budget for
+/// the surrounding label/percent/position text) when stderr is a TTY, else
+/// [`DEFAULT_BAR_WIDTH`] as a non-TTY fallback.
+fn bar_width(style: &BarStyle) -> usize {
+    const NON_BAR_OVERHEAD: usize = 30;
+    const MIN_WIDTH: usize = 10;
+
+    if let Some(width) = style.width {
+        return width;
+    }
+    if !std::io::stderr().is_terminal() {
+        return DEFAULT_BAR_WIDTH;
+    }
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse::<usize>().ok())
+        .map(|columns| columns.saturating_sub(NON_BAR_OVERHEAD).max(MIN_WIDTH))
+        .unwrap_or(DEFAULT_BAR_WIDTH)
+}
+
+/// Expands `template` by replacing each `(placeholder, value)` pair in turn.
+fn expand_template(template: &str, pairs: &[(&str, String)]) -> String {
+    let mut rendered = template.to_string();
+    for (placeholder, value) in pairs {
+        rendered = rendered.replace(placeholder, value);
+    }
+    rendered
+}
+
+/// Builds the indicatif-native style for the direct (non-[`MultiProgress`])
+/// rendering path from a [`BarStyle`]'s glyphs/width, honoring the
+/// `CLICOLOR_FORCE`/`NO_COLOR` toggle. The template skeleton itself mirrors
+/// [`BarStyle::default`]'s layout (`{bar}` plus percent and, in
+/// [`Units::Count`] mode, `{pos}/{len}`) - full placeholder reordering is
+/// owned by [`ProgressBar::render_line`], since indicatif never sees this
+/// bar's draw target once [`MultiProgress`] hides it.
+fn indicatif_style(units: Units, style: &BarStyle) -> ProgressStyle {
+    let width = bar_width(style);
+    let bar_spec = if colors_enabled() {
+        format!("{{bar:{width}.cyan/blue}}")
+    } else {
+        format!("{{bar:{width}}}")
+    };
+    let template = match units {
+        Units::Count => format!("{{msg}} [{bar_spec}] {{percent:>3}}% ({{pos}}/{{len}})"),
+        Units::Bytes => format!("{{msg}} [{bar_spec}] {{percent:>3}}%"),
+    };
+    ProgressStyle::default_bar()
+        .template(&template)
+        .expect("Invalid progress bar template")
+        .progress_chars(&style.progress_chars_string())
+}
 
 /// Progress bar wrapper for displaying processing status
 pub struct ProgressBar {
     bar: IndicatifBar,
+    label: String,
+    rate: Mutex<RateTracker>,
+    started_at: Instant,
+    units: Units,
+    /// Mirrors whatever was last passed to `bar.set_message()` - indicatif
+    /// has no getter for it, but [`MultiProgress`] needs the current text to
+    /// render this bar's row itself instead of letting indicatif draw it.
+    current_message: Mutex<String>,
+    /// Last time this bar actually redrew. Wrapped in an `Arc` so the
+    /// steady-tick background thread can share the same gate as manual
+    /// `update()`/`render()` calls without needing `self` to be `'static`.
+    last_redraw: Arc<Mutex<Option<Instant>>>,
+    /// The background thread spawned by `enable_steady_tick`, if active.
+    steady_tick: Mutex<Option<SteadyTick>>,
+    /// Template, glyphs and width this bar renders with - see [`BarStyle`].
+    style: BarStyle,
+    /// Frame index for the `{spinner}` placeholder in [`ProgressBar::render_line`],
+    /// advanced once per call - independent of indicatif's own spinner frame,
+    /// which still animates the direct (non-`MultiProgress`) rendering path.
+    spinner_frame: AtomicU64,
 }
 
 impl ProgressBar {
     /// Create a new progress bar with known total
     pub fn new(total: usize, label: &str) -> Self {
+        let style = BarStyle::default();
         let bar = IndicatifBar::new(total as u64);
-        bar.set_style(
-            ProgressStyle::default_bar()
-                .template(
-                    "{msg} [{bar:40.cyan/blue}] {percent:>3}% ({pos}/{len}) ({per_sec}) {eta}",
-                )
-                .expect("Invalid progress bar template")
-                .progress_chars("█░"),
-        );
+        bar.set_style(indicatif_style(Units::Count, &style));
+        bar.set_message(label.to_string());
+
+        Self {
+            bar,
+            label: label.to_string(),
+            rate: Mutex::new(RateTracker::new()),
+            started_at: Instant::now(),
+            units: Units::Count,
+            current_message: Mutex::new(label.to_string()),
+            last_redraw: Arc::new(Mutex::new(None)),
+            steady_tick: Mutex::new(None),
+            style,
+            spinner_frame: AtomicU64::new(0),
+        }
+    }
+
+    /// Create a new progress bar with a known total measured in bytes. Both
+    /// the position/total and the throughput are rendered with human
+    /// readable binary-prefixed sizes (see [`format_bytes`]) instead of raw
+    /// counts, e.g. `scanning audit.log 450.20 MiB / 1.30 GiB (22.40 MiB/s)`.
+    #[allow(dead_code)]
+    pub fn new_bytes(total_bytes: usize, label: &str) -> Self {
+        let style = BarStyle::default();
+        let bar = IndicatifBar::new(total_bytes as u64);
+        bar.set_style(indicatif_style(Units::Bytes, &style));
         bar.set_message(label.to_string());
 
-        Self { bar }
+        Self {
+            bar,
+            label: label.to_string(),
+            rate: Mutex::new(RateTracker::new()),
+            started_at: Instant::now(),
+            units: Units::Bytes,
+            current_message: Mutex::new(label.to_string()),
+            last_redraw: Arc::new(Mutex::new(None)),
+            steady_tick: Mutex::new(None),
+            style,
+            spinner_frame: AtomicU64::new(0),
+        }
     }
 
     /// Create a new progress bar with unknown total (spinner mode)
@@ -38,34 +410,259 @@ impl ProgressBar {
         );
         bar.set_message(label.to_string());
 
-        Self { bar }
+        Self {
+            bar,
+            label: label.to_string(),
+            rate: Mutex::new(RateTracker::new()),
+            started_at: Instant::now(),
+            units: Units::Count,
+            current_message: Mutex::new(label.to_string()),
+            last_redraw: Arc::new(Mutex::new(None)),
+            steady_tick: Mutex::new(None),
+            style: BarStyle::default(),
+            spinner_frame: AtomicU64::new(0),
+        }
+    }
+
+    /// Replaces this bar's rendering style (template, glyphs, width) - see
+    /// [`BarStyle`]. Re-applies the indicatif-native style for the direct
+    /// (non-[`MultiProgress`]) drawing path too, so a bar constructed via
+    /// `new`/`new_bytes` with a sensible default style keeps working
+    /// unchanged until a caller opts into a custom one.
+    #[allow(dead_code)]
+    pub fn with_style(mut self, style: BarStyle) -> Self {
+        self.bar.set_style(indicatif_style(self.units, &style));
+        self.style = style;
+        self
+    }
+
+    /// Time elapsed since this bar was created.
+    #[allow(dead_code)]
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// The current smoothed (EMA) processing rate, in units/sec, without
+    /// folding in a new sample.
+    #[allow(dead_code)]
+    pub fn rate_per_sec(&self) -> f64 {
+        self.rate
+            .lock()
+            .expect("rate tracker mutex poisoned")
+            .current_rate()
+    }
+
+    /// Estimated time remaining, from the current smoothed rate and however
+    /// much of the bar's total is left. Zero if the total is unknown
+    /// (spinner mode) or the rate hasn't warmed up yet.
+    #[allow(dead_code)]
+    pub fn eta(&self) -> Duration {
+        let rate = self.rate_per_sec();
+        match self.bar.length() {
+            Some(total) if rate > 0.0 => {
+                let remaining = total.saturating_sub(self.bar.position());
+                Duration::from_secs_f64(remaining as f64 / rate)
+            }
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// Renders `rate` (units/sec) for the display message, as `{rate:.1}
+    /// lines/s` in [`Units::Count`] mode or a [`format_bytes`]-formatted
+    /// `{rate}/s` in [`Units::Bytes`] mode, so the throughput estimator's
+    /// single EMA serves both unit systems.
+    fn format_rate(&self, rate: f64) -> String {
+        match self.units {
+            Units::Count => format!("{rate:.1} lines/s"),
+            Units::Bytes => format!("{}/s", format_bytes(rate.round() as u64)),
+        }
+    }
+
+    /// Renders `current` (and `total`, if known) for the display message -
+    /// plain counts in [`Units::Count`] mode, human-readable sizes via
+    /// [`format_bytes`] in [`Units::Bytes`] mode (e.g. `450.20 MiB / 1.30
+    /// GiB`).
+    fn format_position(&self, current: u64, total: Option<u64>) -> String {
+        match self.units {
+            Units::Count => match total {
+                Some(total) => format!("{current}/{total}"),
+                None => current.to_string(),
+            },
+            Units::Bytes => match total {
+                Some(total) => format!("{} / {}", format_bytes(current), format_bytes(total)),
+                None => format_bytes(current),
+            },
+        }
+    }
+
+    /// Recomputes the smoothed rate for `current` and updates the displayed
+    /// message, folding the instantaneous rate since the last call into the
+    /// EMA (alpha = 0.3). In [`Units::Count`] mode this is `{label} (rate,
+    /// ETA mm:ss)` - position/total are left to the template's own
+    /// `{pos}/{len}` placeholder. In [`Units::Bytes`] mode the template has
+    /// no such placeholder (binary-prefixed sizes don't fit it), so the
+    /// formatted position is folded into the message itself: `{label}
+    /// {pos} / {total} (rate, ETA mm:ss)`. The ETA is only shown once the
+    /// bar's total is known.
+    fn update_rate_message(&self, current: u64) {
+        let ema = self
+            .rate
+            .lock()
+            .expect("rate tracker mutex poisoned")
+            .observe(current);
+
+        let rate = self.format_rate(ema);
+        let prefix = match self.units {
+            Units::Count => self.label.clone(),
+            Units::Bytes => format!(
+                "{} {}",
+                self.label,
+                self.format_position(current, self.bar.length())
+            ),
+        };
+
+        let message = match self.bar.length() {
+            Some(_) if ema > 0.0 => format!(
+                "{prefix} ({rate}, ETA {})",
+                format_duration_mmss(self.eta().as_secs())
+            ),
+            _ => format!("{prefix} ({rate})"),
+        };
+        *self
+            .current_message
+            .lock()
+            .expect("current message mutex poisoned") = message.clone();
+        if throttled_redraw(&self.last_redraw) {
+            self.bar.set_message(message);
+        }
+    }
+
+    /// Renders this bar's current state as one plain-text line, for
+    /// [`MultiProgress`] to print itself instead of relying on indicatif's
+    /// own (hidden, in that case) draw target. Expands `self.style.template`
+    /// - see [`BarStyle`] for the placeholders.
+    fn render_line(&self) -> String {
+        let pos = self.bar.position();
+        let total = self.bar.length();
+        let message = self
+            .current_message
+            .lock()
+            .expect("current message mutex poisoned")
+            .clone();
+
+        let percent = match total {
+            Some(t) if t > 0 => ((pos as f64 / t as f64) * 100.0).clamp(0.0, 100.0),
+            _ => 0.0,
+        };
+        let width = bar_width(&self.style);
+        let bar_str = self.style.render_bar(width, percent);
+        let pos_str = match self.units {
+            Units::Count => pos.to_string(),
+            Units::Bytes => format_bytes(pos),
+        };
+        let total_str = match (self.units, total) {
+            (_, None) => "?".to_string(),
+            (Units::Count, Some(t)) => t.to_string(),
+            (Units::Bytes, Some(t)) => format_bytes(t),
+        };
+        let frame = self.spinner_frame.fetch_add(1, Ordering::Relaxed);
+        let spinner_str = SPINNER_FRAMES[frame as usize % SPINNER_FRAMES.len()].to_string();
+
+        expand_template(
+            &self.style.template,
+            &[
+                ("{msg}", message),
+                ("{bar}", bar_str),
+                ("{pos}", pos_str),
+                ("{total}", total_str),
+                ("{percent}", format!("{percent:>3.0}")),
+                ("{eta}", format_duration_mmss(self.eta().as_secs())),
+                ("{rate}", self.format_rate(self.rate_per_sec())),
+                ("{spinner}", spinner_str),
+            ],
+        )
     }
 
     /// Update progress
     pub fn update(&self, current: usize) {
         self.bar.set_position(current as u64);
+        self.update_rate_message(current as u64);
     }
 
     /// Increment progress by 1
     #[allow(dead_code)]
     pub fn inc(&self) {
         self.bar.inc(1);
+        self.update_rate_message(self.bar.position());
     }
 
-    /// Force render (indicatif handles this automatically)
+    /// Force a redraw, subject to the same [`MIN_REDRAW_INTERVAL`] gate as
+    /// everything else (indicatif otherwise handles rendering automatically).
     #[allow(dead_code)]
     pub fn render(&self) {
-        // indicatif handles rendering automatically
-        self.bar.tick();
+        if throttled_redraw(&self.last_redraw) {
+            self.bar.tick();
+        }
+    }
+
+    /// Spawns a background thread that redraws this bar - in particular,
+    /// advances the spinner frame in `new_spinner` mode - every `interval`,
+    /// independent of `update()`/`inc()` calls. Without this, a spinner
+    /// freezes for the duration of a long blocking call between updates.
+    /// Mirrors indicatif's own `steady.rs`. Every redraw, steady-ticked or
+    /// manual, still passes through the shared [`MIN_REDRAW_INTERVAL`] gate,
+    /// so a short `interval` can't flood the terminal.
+    ///
+    /// Calling this again replaces (stopping and joining) any previously
+    /// running tick thread. [`ProgressBar::finish`] and
+    /// [`ProgressBar::finish_with_message`] stop it automatically.
+    #[allow(dead_code)]
+    pub fn enable_steady_tick(&self, interval: Duration) {
+        self.stop_steady_tick();
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let bar = self.bar.clone();
+        let last_redraw = Arc::clone(&self.last_redraw);
+        let thread_stop = Arc::clone(&stop);
+        let thread = std::thread::spawn(move || {
+            while !thread_stop.load(Ordering::Relaxed) {
+                std::thread::sleep(interval);
+                if thread_stop.load(Ordering::Relaxed) {
+                    break;
+                }
+                if throttled_redraw(&last_redraw) {
+                    bar.tick();
+                }
+            }
+        });
+
+        *self
+            .steady_tick
+            .lock()
+            .expect("steady tick mutex poisoned") = Some(SteadyTick {
+            stop,
+            thread: Some(thread),
+        });
+    }
+
+    /// Stops and joins the steady-tick thread, if one is running. A no-op
+    /// otherwise.
+    fn stop_steady_tick(&self) {
+        *self
+            .steady_tick
+            .lock()
+            .expect("steady tick mutex poisoned") = None;
     }
 
     /// Finish the progress bar
     pub fn finish(&self) {
+        self.stop_steady_tick();
         self.bar.finish();
     }
 
     /// Finish with custom message
     pub fn finish_with_message(&self, message: &str) {
+        self.stop_steady_tick();
         self.bar.finish_with_message(message.to_string());
     }
 
@@ -74,3 +671,334 @@ impl ProgressBar {
         self.bar.println(msg.as_ref());
     }
 }
+
+/// Drives a [`ProgressBar`] from an iterator automatically, so a caller
+/// can't forget to call `inc()` on an early `break`/`return` - mirrors
+/// indicatif's own `ProgressIterator` adapter (and the `zzz` crate's). Calls
+/// `inc()` once per yielded item and `finish()` on [`Drop`], whether the
+/// iterator was exhausted or merely dropped early.
+#[allow(dead_code)]
+pub struct ProgressIter<I> {
+    iter: I,
+    bar: ProgressBar,
+}
+
+impl<I: Iterator> Iterator for ProgressIter<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.iter.next();
+        if next.is_some() {
+            self.bar.inc();
+        }
+        next
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<I> Drop for ProgressIter<I> {
+    fn drop(&mut self) {
+        self.bar.finish();
+    }
+}
+
+/// Extension trait adding [`ProgressBar`]-driving adapters to any iterator,
+/// so progress updates happen as a side effect of iteration instead of a
+/// manual `inc()`/`update()` call the caller has to remember on every loop
+/// body (and every early return out of it).
+#[allow(dead_code)]
+pub trait ProgressIteratorExt: Iterator + Sized {
+    /// Wraps `self` so each yielded item calls `pb.inc()`. If `pb` has no
+    /// known total yet (e.g. built with [`ProgressBar::new_spinner`]) and
+    /// `self`'s `size_hint` is exact, the total is filled in from that.
+    fn progress_with(self, pb: ProgressBar) -> ProgressIter<Self> {
+        if pb.bar.length().is_none() {
+            let (lower, upper) = self.size_hint();
+            if upper == Some(lower) {
+                pb.set_total(lower);
+            }
+        }
+        ProgressIter { iter: self, bar: pb }
+    }
+
+    /// Convenience for the common case: build a bar with a known `total`
+    /// and wrap `self` with it.
+    fn progress_count(self, total: u64) -> ProgressIter<Self> {
+        self.progress_with(ProgressBar::new(total as usize, "Progress"))
+    }
+}
+
+impl<I: Iterator> ProgressIteratorExt for I {}
+
+/// Wraps a [`Read`] implementation so each `read()` call reports the bytes
+/// it actually read through the wrapped [`ProgressBar`] - streaming an
+/// audit file through one drives a [`ProgressBar::new_bytes`] bar with no
+/// manual bookkeeping. Calls `finish()` on [`Drop`].
+#[allow(dead_code)]
+pub struct ProgressReader<R: Read> {
+    inner: R,
+    bar: ProgressBar,
+}
+
+impl<R: Read> ProgressReader<R> {
+    /// Wrap `inner`, reporting bytes read through `bar` as they're read.
+    pub fn new(inner: R, bar: ProgressBar) -> Self {
+        Self { inner, bar }
+    }
+}
+
+impl<R: Read> Read for ProgressReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        Progress::inc(&self.bar, n);
+        Ok(n)
+    }
+}
+
+impl<R: Read> Drop for ProgressReader<R> {
+    fn drop(&mut self) {
+        self.bar.finish();
+    }
+}
+
+/// Destination for progress/status reporting during file processing.
+///
+/// Lets [`crate::utils::processor::FileProcessor`] report through something
+/// other than an interactive terminal bar - e.g. NDJSON for scripts and CI,
+/// or nothing at all under `-q`. Implementations must be safely shareable
+/// across the worker threads that drive parallel processing.
+pub trait Progress: Send + Sync {
+    /// Set (or update) the known total amount of work, once it's known.
+    fn set_total(&self, total: usize);
+    /// Advance progress by `n` units (bytes, for [`FileProcessor`]'s callers).
+    ///
+    /// [`FileProcessor`]: crate::utils::processor::FileProcessor
+    fn inc(&self, n: usize);
+    /// Report a status line - e.g. a per-file completion message - without
+    /// disturbing an interactive display.
+    fn message(&self, msg: &str);
+    /// Mark processing as finished with a final summary message.
+    fn finish(&self, msg: &str);
+}
+
+impl Progress for ProgressBar {
+    fn set_total(&self, total: usize) {
+        self.bar.set_length(total as u64);
+    }
+
+    fn inc(&self, n: usize) {
+        self.bar.inc(n as u64);
+        self.update_rate_message(self.bar.position());
+    }
+
+    fn message(&self, msg: &str) {
+        self.println(msg);
+    }
+
+    fn finish(&self, msg: &str) {
+        self.finish_with_message(msg);
+    }
+}
+
+/// Coordinates redraws of several [`ProgressBar`]s sharing one terminal
+/// block, so workers running in parallel (e.g. one bar per file, plus an
+/// aggregate total) don't interleave and garble each other's lines the way
+/// unsynchronized bars would. Inspired by indicatif's own `MultiProgress`
+/// and Deno's global `DrawThread`, but hand-rolled rather than reused: each
+/// added bar's own indicatif draw target is hidden, and `MultiProgress`
+/// renders the whole block itself, clearing and reprinting every row
+/// through one locked `stderr` handle on each update by moving the cursor
+/// back up with `\x1b[{n}A` first.
+pub struct MultiProgress {
+    state: Mutex<MultiProgressState>,
+}
+
+#[derive(Default)]
+struct MultiProgressState {
+    bars: Vec<Arc<ProgressBar>>,
+    lines_drawn: usize,
+}
+
+impl MultiProgress {
+    /// Create an empty manager with no rows yet.
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(MultiProgressState::default()),
+        }
+    }
+
+    /// Adopts `bar` as a new row at the bottom of the managed block. Hides
+    /// `bar`'s own indicatif draw target, since from this point on only
+    /// `MultiProgress` is allowed to write to the terminal for it, and
+    /// returns a handle whose `update`/`inc`/`finish`/`message` calls
+    /// trigger a coordinated redraw of the whole block rather than drawing
+    /// just this row.
+    pub fn add(&self, bar: ProgressBar) -> ProgressBarHandle<'_> {
+        bar.bar.set_draw_target(ProgressDrawTarget::hidden());
+        let bar = Arc::new(bar);
+        {
+            let mut state = self.state.lock().expect("multi-progress mutex poisoned");
+            state.bars.push(bar.clone());
+        }
+        self.redraw(None);
+        ProgressBarHandle { bar, manager: self }
+    }
+
+    /// Drops `bar`'s row from the managed block, reflowing the rows below
+    /// it up by one, and redraws the (now shorter) block.
+    fn remove(&self, bar: &Arc<ProgressBar>) {
+        {
+            let mut state = self.state.lock().expect("multi-progress mutex poisoned");
+            state.bars.retain(|b| !Arc::ptr_eq(b, bar));
+        }
+        self.redraw(None);
+    }
+
+    /// Clears the previously drawn block and reprints every live bar's
+    /// current line, optionally preceded by one `leading` status line
+    /// printed just above the block (used by [`Progress::message`] so a
+    /// completion note doesn't land mid-block). Holds `stderr`'s lock for
+    /// the whole redraw so no other thread's write can land in the middle
+    /// of it.
+    fn redraw(&self, leading: Option<&str>) {
+        let mut state = self.state.lock().expect("multi-progress mutex poisoned");
+        let stderr = std::io::stderr();
+        let mut handle = stderr.lock();
+
+        if state.lines_drawn > 0 {
+            let _ = write!(handle, "\x1b[{}A", state.lines_drawn);
+        }
+        if let Some(msg) = leading {
+            let _ = writeln!(handle, "\x1b[2K{msg}");
+        }
+        for bar in &state.bars {
+            let _ = writeln!(handle, "\x1b[2K{}", bar.render_line());
+        }
+        let _ = handle.flush();
+
+        state.lines_drawn = state.bars.len();
+    }
+}
+
+impl Default for MultiProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A [`MultiProgress`]-managed bar's fixed row. Behaves like using the
+/// underlying [`ProgressBar`] directly, except every update also triggers a
+/// coordinated redraw of the whole managed block instead of the bar
+/// drawing (and potentially garbling) its own line independently.
+pub struct ProgressBarHandle<'a> {
+    bar: Arc<ProgressBar>,
+    manager: &'a MultiProgress,
+}
+
+impl<'a> ProgressBarHandle<'a> {
+    /// Update progress, then redraw the managed block.
+    pub fn update(&self, current: usize) {
+        self.bar.update(current);
+        self.manager.redraw(None);
+    }
+
+    /// Drops this bar's row from the managed block and reflows the rows
+    /// below it upward.
+    pub fn remove(self) {
+        self.manager.remove(&self.bar);
+    }
+}
+
+impl<'a> Progress for ProgressBarHandle<'a> {
+    fn set_total(&self, total: usize) {
+        Progress::set_total(self.bar.as_ref(), total);
+        self.manager.redraw(None);
+    }
+
+    fn inc(&self, n: usize) {
+        Progress::inc(self.bar.as_ref(), n);
+        self.manager.redraw(None);
+    }
+
+    fn message(&self, msg: &str) {
+        self.manager.redraw(Some(msg));
+    }
+
+    fn finish(&self, msg: &str) {
+        Progress::finish(self.bar.as_ref(), msg);
+        self.manager.redraw(None);
+    }
+}
+
+/// Emits periodic NDJSON progress records to a writer instead of rendering
+/// a terminal bar, for consumers (scripts, CI dashboards) that want
+/// machine-readable status rather than an interactive display.
+///
+/// `files_done` increments once per [`Progress::message`] call, since this
+/// crate's processing paths call `message` exactly once per completed file.
+pub struct JsonProgress<W: Write + Send> {
+    writer: Mutex<W>,
+    total: AtomicU64,
+    processed: AtomicU64,
+    files_done: AtomicU64,
+}
+
+impl<W: Write + Send> JsonProgress<W> {
+    /// Create a new JSON progress sink writing NDJSON records to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+            total: AtomicU64::new(0),
+            processed: AtomicU64::new(0),
+            files_done: AtomicU64::new(0),
+        }
+    }
+
+    fn emit(&self) {
+        let record = format!(
+            "{{\"processed\":{},\"total\":{},\"files_done\":{}}}",
+            self.processed.load(Ordering::Relaxed),
+            self.total.load(Ordering::Relaxed),
+            self.files_done.load(Ordering::Relaxed)
+        );
+        if let Ok(mut writer) = self.writer.lock() {
+            let _ = writeln!(writer, "{record}");
+        }
+    }
+}
+
+impl<W: Write + Send> Progress for JsonProgress<W> {
+    fn set_total(&self, total: usize) {
+        self.total.store(total as u64, Ordering::Relaxed);
+        self.emit();
+    }
+
+    fn inc(&self, n: usize) {
+        self.processed.fetch_add(n as u64, Ordering::Relaxed);
+        self.emit();
+    }
+
+    fn message(&self, _msg: &str) {
+        self.files_done.fetch_add(1, Ordering::Relaxed);
+        self.emit();
+    }
+
+    fn finish(&self, _msg: &str) {
+        self.emit();
+    }
+}
+
+/// Discards all progress reporting - used under `-q`, or anywhere output
+/// must stay free of interleaved status text.
+#[allow(dead_code)]
+pub struct NoopProgress;
+
+impl Progress for NoopProgress {
+    fn set_total(&self, _total: usize) {}
+    fn inc(&self, _n: usize) {}
+    fn message(&self, _msg: &str) {}
+    fn finish(&self, _msg: &str) {}
+}
@@ -0,0 +1,252 @@
+//! Shared machine-readable output support for commands with a `--format
+//! {table,json,ndjson}` flag.
+//!
+//! Most commands in this crate still print fixed-width tables straight to
+//! stdout via `println!`, which is awkward to consume from a pipeline.
+//! Rather than hand-rolling a JSON/NDJSON branch per command (as
+//! [`crate::commands::token_analysis`]'s `--export-format` does today), a
+//! command can implement [`Report`] for its result type and call [`emit`]:
+//! `table` keeps the existing human-readable rendering as the default,
+//! `json` serializes the whole result as one document, and `ndjson` streams
+//! one record per row so results flow into `jq`/log shippers without
+//! buffering the full result set in memory.
+//!
+//! [`crate::commands::threat_scan`] is the first command built against this;
+//! the rest of the crate's commands each predate it with their own
+//! `--format`/`--export-format` conventions (`csv`/`json`/`parquet`/...) and
+//! haven't been migrated, so this module is the shared mechanism new
+//! commands should adopt going forward rather than a retrofit of every
+//! existing one.
+//!
+//! `json` mode wraps [`Report::rows`] in a stable [`Envelope`] -
+//! `{ schema_version, command, results }` - rather than dumping the
+//! report struct as-is, so downstream tooling can validate shape once
+//! ([`validate_output`] against [`schema_json`]'s JSON Schema) instead of
+//! per-command. `ndjson` mode stays unwrapped: it exists specifically so
+//! large result sets stream without buffering, and wrapping would require
+//! buffering the whole array first.
+
+use anyhow::Result;
+use serde::Serialize;
+use std::io::Write;
+
+/// Version of the [`Envelope`] shape `json` mode emits. Bump this if the
+/// envelope's own fields change; per-command `results` shapes are
+/// versioned by the command itself, not by this constant.
+pub const SCHEMA_VERSION: &str = "1.0";
+
+/// The stable `--format json` wrapper: `results` is always
+/// [`Report::rows`] for the command named in `command`, so a consumer that
+/// only understands the envelope shape can still extract structured data
+/// from a command it's never seen before.
+#[derive(Debug, Serialize)]
+struct Envelope<'a, T: Serialize> {
+    schema_version: &'static str,
+    command: &'static str,
+    results: &'a [T],
+}
+
+/// JSON Schema (draft 2020-12) for the [`Envelope`] shape. Deliberately
+/// validates only the envelope itself - `results` items are `{}` (any) -
+/// since each command's row shape is its own contract; see
+/// [`crate::commands::schema`] for the `schema` subcommand that prints this.
+pub const ENVELOPE_SCHEMA: &str = r#"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "title": "vault-audit-tools output envelope",
+  "type": "object",
+  "required": ["schema_version", "command", "results"],
+  "properties": {
+    "schema_version": { "type": "string" },
+    "command": { "type": "string" },
+    "results": { "type": "array", "items": {} }
+  }
+}"#;
+
+/// Returns [`ENVELOPE_SCHEMA`], the document `vault-audit schema` prints.
+pub fn schema_json() -> &'static str {
+    ENVELOPE_SCHEMA
+}
+
+/// Validates `value` against `schema`, returning every violation found
+/// rather than stopping at the first. Supports the subset of JSON Schema
+/// this crate's own schemas use - `type`, `required`, `properties`,
+/// `items` - rather than pulling in a full JSON Schema implementation for
+/// one envelope shape.
+pub fn validate_output(value: &serde_json::Value, schema: &serde_json::Value) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    validate_at("$", value, schema, &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn json_type_name(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "boolean",
+        serde_json::Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        serde_json::Value::Number(_) => "number",
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Array(_) => "array",
+        serde_json::Value::Object(_) => "object",
+    }
+}
+
+fn validate_at(path: &str, value: &serde_json::Value, schema: &serde_json::Value, errors: &mut Vec<String>) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+
+    if let Some(expected_type) = schema.get("type").and_then(|t| t.as_str()) {
+        let actual_type = json_type_name(value);
+        let matches = actual_type == expected_type
+            || (expected_type == "number" && actual_type == "integer");
+        if !matches {
+            errors.push(format!(
+                "{}: expected type '{}', found '{}'",
+                path, expected_type, actual_type
+            ));
+            return;
+        }
+    }
+
+    if let Some(required) = schema.get("required").and_then(|r| r.as_array()) {
+        if let Some(object) = value.as_object() {
+            for field in required {
+                if let Some(field) = field.as_str() {
+                    if !object.contains_key(field) {
+                        errors.push(format!("{}: missing required field '{}'", path, field));
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(properties) = schema.get("properties").and_then(|p| p.as_object()) {
+        if let Some(object) = value.as_object() {
+            for (field, field_schema) in properties {
+                if let Some(field_value) = object.get(field) {
+                    validate_at(&format!("{}.{}", path, field), field_value, field_schema, errors);
+                }
+            }
+        }
+    }
+
+    if let Some(items_schema) = schema.get("items") {
+        if let Some(array) = value.as_array() {
+            for (idx, item) in array.iter().enumerate() {
+                validate_at(&format!("{}[{}]", path, idx), item, items_schema, errors);
+            }
+        }
+    }
+}
+
+/// Output format shared across commands that support `--format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Fixed-width human-readable table (the default).
+    Table,
+    /// One pretty-printed JSON document for the whole result.
+    Json,
+    /// One JSON object per line, one per row.
+    Ndjson,
+}
+
+impl OutputFormat {
+    /// `clap` `value_parser` for `--format table|json|ndjson`.
+    ///
+    /// `text` is accepted as a synonym for `table` - some commands (e.g.
+    /// `entity-analysis timeline`) call their human-readable mode `text`
+    /// rather than `table`; both select the same rendering.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "table" | "text" => Ok(Self::Table),
+            "json" => Ok(Self::Json),
+            "ndjson" => Ok(Self::Ndjson),
+            other => Err(format!(
+                "invalid --format '{}': expected 'table', 'json', or 'ndjson'",
+                other
+            )),
+        }
+    }
+}
+
+/// A command result that can render itself as a human table, or be
+/// serialized whole (`json`) or row-by-row (`ndjson`).
+pub trait Report: Serialize {
+    /// Row type streamed one-per-line in `ndjson` mode, and wrapped as
+    /// `results` in `json` mode's [`Envelope`].
+    type Row: Serialize;
+
+    /// The CLI verb this report's data came from (e.g. `"path-hotspots"`),
+    /// carried as `json` mode's `Envelope.command` field.
+    fn command_name(&self) -> &'static str;
+
+    /// Render the existing human-readable table to `w`. Unchanged behavior
+    /// from before `--format` existed, aside from taking a writer instead of
+    /// assuming stdout - `table` is still the default, and [`emit`] targets
+    /// stdout for it same as always.
+    fn render_table(&self, w: &mut dyn Write) -> std::io::Result<()>;
+
+    /// Rows to stream in `ndjson` mode.
+    fn rows(&self) -> &[Self::Row];
+}
+
+/// Writes `report` in the requested `format`: the table via
+/// [`Report::render_table`], `json` as a [`SCHEMA_VERSION`]-tagged
+/// [`Envelope`] around [`Report::rows`], or `ndjson` as one unwrapped
+/// record per row.
+pub fn emit<R: Report>(report: &R, format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Table => {
+            let stdout = std::io::stdout();
+            report.render_table(&mut stdout.lock())?;
+        }
+        OutputFormat::Json => {
+            let envelope = Envelope {
+                schema_version: SCHEMA_VERSION,
+                command: report.command_name(),
+                results: report.rows(),
+            };
+            println!("{}", serde_json::to_string_pretty(&envelope)?);
+        }
+        OutputFormat::Ndjson => {
+            let stdout = std::io::stdout();
+            let mut handle = stdout.lock();
+            for row in report.rows() {
+                serde_json::to_writer(&mut handle, row)?;
+                writeln!(handle)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Same rendering as [`emit`], but buffered into a `String` instead of
+/// written to stdout - for commands that support writing their report to a
+/// file (e.g. `entity-analysis timeline --output-dir`).
+pub fn render_to_string<R: Report>(report: &R, format: OutputFormat) -> Result<String> {
+    let mut buf: Vec<u8> = Vec::new();
+    match format {
+        OutputFormat::Table => {
+            report.render_table(&mut buf)?;
+        }
+        OutputFormat::Json => {
+            let envelope = Envelope {
+                schema_version: SCHEMA_VERSION,
+                command: report.command_name(),
+                results: report.rows(),
+            };
+            buf = serde_json::to_vec_pretty(&envelope)?;
+        }
+        OutputFormat::Ndjson => {
+            for row in report.rows() {
+                serde_json::to_writer(&mut buf, row)?;
+                writeln!(buf)?;
+            }
+        }
+    }
+    Ok(String::from_utf8(buf)?)
+}
@@ -0,0 +1,135 @@
+//! OS-level process hardening (`--sandbox`).
+//!
+//! Commands that hold a privileged `VAULT_TOKEN` in memory and write
+//! exports to disk can opt into this right after CLI argument parsing and
+//! before any Vault I/O, so that a compromised dependency or a malformed
+//! API response can't read arbitrary files or open unexpected network
+//! connections. On OpenBSD this is `pledge`/`unveil`; on Linux it's a
+//! seccomp-bpf filter. Other platforms get a best-effort no-op with a
+//! warning, since neither mechanism exists there.
+
+use anyhow::Result;
+use std::path::Path;
+
+/// Drop the process to the minimal OS capabilities needed to talk to
+/// Vault over HTTPS and write a single output file.
+///
+/// Call this once, immediately after argument parsing and before any
+/// network or file I/O. `output_path` is the only path the process will
+/// be allowed to create/write afterward.
+pub fn harden(output_path: Option<&Path>) -> Result<()> {
+    imp::harden(output_path)
+}
+
+#[cfg(target_os = "openbsd")]
+mod imp {
+    use super::*;
+    use anyhow::Context;
+
+    /// `stdio` for normal I/O, `rpath`/`wpath`/`cpath` for reading the TLS
+    /// trust store and creating the output file, `inet`/`dns` for the
+    /// Vault connection itself.
+    const PROMISES: &str = "stdio rpath wpath cpath inet dns";
+
+    pub fn harden(output_path: Option<&Path>) -> Result<()> {
+        if let Some(path) = output_path {
+            pledge::unveil(path, "rwc").context("unveil output path")?;
+        }
+        pledge::unveil("/etc/ssl", "r").context("unveil TLS trust store")?;
+        pledge::unveil_none().context("close unveil")?;
+
+        pledge::pledge(PROMISES, None).context("pledge")?;
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod imp {
+    use super::*;
+    use anyhow::Context;
+    use seccompiler::{BpfProgram, SeccompAction, SeccompFilter, SeccompRule};
+    use std::collections::BTreeMap;
+
+    /// Syscalls `entity-list`'s Vault HTTPS client and output-file write
+    /// actually make. Anything outside this set kills the process.
+    ///
+    /// `harden()` runs right after argument parsing, before the first
+    /// Vault request, so this has to cover more than steady-state
+    /// read/write/connect: the `#[tokio::main]` runtime and reqwest's
+    /// DNS/TLS setup spawn blocking-pool threads on demand
+    /// (`clone`/`clone3`), which in turn need their own stacks and
+    /// thread-local state (`mprotect`, `madvise`, `rseq`, `sigaltstack`,
+    /// `set_robust_list`) and may query `prctl`/`getpid`/`gettid`. Tokio's
+    /// reactor also creates its epoll instance with `epoll_create1`,
+    /// distinct from the `epoll_wait`/`epoll_ctl` already allowed below.
+    const ALLOWED_SYSCALLS: &[i64] = &[
+        libc::SYS_read,
+        libc::SYS_write,
+        libc::SYS_open,
+        libc::SYS_openat,
+        libc::SYS_close,
+        libc::SYS_socket,
+        libc::SYS_connect,
+        libc::SYS_sendto,
+        libc::SYS_recvfrom,
+        libc::SYS_poll,
+        libc::SYS_epoll_create1,
+        libc::SYS_epoll_wait,
+        libc::SYS_epoll_ctl,
+        libc::SYS_futex,
+        libc::SYS_mmap,
+        libc::SYS_mprotect,
+        libc::SYS_madvise,
+        libc::SYS_munmap,
+        libc::SYS_brk,
+        libc::SYS_clone,
+        libc::SYS_clone3,
+        libc::SYS_rseq,
+        libc::SYS_sigaltstack,
+        libc::SYS_set_robust_list,
+        libc::SYS_prctl,
+        libc::SYS_rt_sigaction,
+        libc::SYS_rt_sigprocmask,
+        libc::SYS_fcntl,
+        libc::SYS_fstat,
+        libc::SYS_stat,
+        libc::SYS_getrandom,
+        libc::SYS_clock_gettime,
+        libc::SYS_getpid,
+        libc::SYS_gettid,
+        libc::SYS_ioctl,
+        libc::SYS_exit,
+        libc::SYS_exit_group,
+    ];
+
+    pub fn harden(_output_path: Option<&Path>) -> Result<()> {
+        let rules: BTreeMap<i64, Vec<SeccompRule>> = ALLOWED_SYSCALLS
+            .iter()
+            .map(|&syscall| (syscall, vec![]))
+            .collect();
+
+        let filter = SeccompFilter::new(
+            rules,
+            SeccompAction::Kill,
+            SeccompAction::Allow,
+            std::env::consts::ARCH
+                .try_into()
+                .context("unsupported seccomp architecture")?,
+        )
+        .context("build seccomp filter")?;
+
+        let program: BpfProgram = filter.try_into().context("compile seccomp filter")?;
+        seccompiler::apply_filter(&program).context("apply seccomp filter")?;
+        Ok(())
+    }
+}
+
+#[cfg(not(any(target_os = "openbsd", target_os = "linux")))]
+mod imp {
+    use super::*;
+
+    pub fn harden(_output_path: Option<&Path>) -> Result<()> {
+        eprintln!("warning: --sandbox has no backend on this platform; running unsandboxed");
+        Ok(())
+    }
+}
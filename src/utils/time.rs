@@ -1,6 +1,100 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Utc};
 
+/// Parse a human-friendly duration string into total seconds.
+///
+/// Accepts compact unit suffixes (`"30s"`, `"15m"`, `"2h"`, `"7d"`, `"1w"`),
+/// compound forms that sum multiple segments (`"1h30m"`), bare integers
+/// (treated as seconds, for backward compatibility with raw `u64` flags),
+/// and named aliases (`"hourly"`, `"twice-daily"`, `"daily"`, `"weekly"`).
+///
+/// # Examples
+///
+/// ```
+/// use vault_audit_tools::utils::time::parse_duration;
+///
+/// assert_eq!(parse_duration("30s").unwrap(), 30);
+/// assert_eq!(parse_duration("1h30m").unwrap(), 5400);
+/// assert_eq!(parse_duration("daily").unwrap(), 86400);
+/// assert_eq!(parse_duration("7200").unwrap(), 7200);
+/// ```
+pub fn parse_duration(s: &str) -> Result<u64> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(anyhow!("duration string is empty"));
+    }
+
+    if let Ok(seconds) = s.parse::<u64>() {
+        return Ok(seconds);
+    }
+
+    match s.to_lowercase().as_str() {
+        "hourly" => return Ok(3600),
+        "twice-daily" => return Ok(43_200),
+        "daily" => return Ok(86_400),
+        "weekly" => return Ok(604_800),
+        _ => {}
+    }
+
+    let mut total_seconds: u64 = 0;
+    let mut chars = s.char_indices().peekable();
+    let mut matched_any = false;
+
+    while let Some((start, c)) = chars.peek().copied() {
+        if !c.is_ascii_digit() {
+            return Err(anyhow!(
+                "unrecognized duration token starting at '{}' in '{}'",
+                &s[start..],
+                s
+            ));
+        }
+
+        let mut end = start;
+        while let Some((idx, c)) = chars.peek().copied() {
+            if c.is_ascii_digit() {
+                end = idx;
+                chars.next();
+            } else {
+                break;
+            }
+        }
+
+        let Some((unit_start, unit_char)) = chars.next() else {
+            return Err(anyhow!("duration '{}' is missing a unit after '{}'", s, &s[start..=end]));
+        };
+
+        let number: u64 = s[start..=end]
+            .parse()
+            .with_context(|| format!("invalid number in duration token '{}'", &s[start..=end]))?;
+
+        let multiplier = match unit_char {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            'd' => 86_400,
+            'w' => 604_800,
+            other => {
+                return Err(anyhow!(
+                    "unrecognized duration unit '{}' in token '{}{}'",
+                    other,
+                    number,
+                    other
+                ));
+            }
+        };
+
+        let _ = unit_start;
+        total_seconds += number * multiplier;
+        matched_any = true;
+    }
+
+    if !matched_any {
+        return Err(anyhow!("could not parse duration from '{}'", s));
+    }
+
+    Ok(total_seconds)
+}
+
 /// Parse a timestamp string from Vault audit logs
 #[allow(dead_code)]
 pub fn parse_timestamp(ts: &str) -> Result<DateTime<Utc>> {
@@ -9,6 +103,34 @@ pub fn parse_timestamp(ts: &str) -> Result<DateTime<Utc>> {
         .map(|dt| dt.with_timezone(&Utc))
 }
 
+/// Resolve a `--since`/`--until` argument to an absolute instant.
+///
+/// Accepts either an RFC3339 timestamp (parsed via [`parse_timestamp`]) or a
+/// relative duration understood by [`parse_duration`] (`"7d"`, `"24h"`,
+/// `"90m"`, `"30s"`, ...), interpreted as "`now` minus that duration".
+///
+/// # Examples
+///
+/// ```
+/// use chrono::Utc;
+/// use vault_audit_tools::utils::time::resolve_time_bound;
+///
+/// let now = Utc::now();
+/// let since = resolve_time_bound("7d", now).unwrap();
+/// assert_eq!((now - since).num_days(), 7);
+///
+/// let absolute = resolve_time_bound("2025-10-06T07:26:03Z", now).unwrap();
+/// assert_eq!(absolute.timestamp(), 1759734363);
+/// ```
+pub fn resolve_time_bound(s: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    if let Ok(ts) = parse_timestamp(s) {
+        return Ok(ts);
+    }
+    let seconds_ago = parse_duration(s)
+        .with_context(|| format!("'{}' is neither an RFC3339 timestamp nor a duration like '7d'", s))?;
+    Ok(now - chrono::Duration::seconds(seconds_ago as i64))
+}
+
 /// Format a timestamp for display
 #[allow(dead_code)]
 pub fn format_timestamp(dt: &DateTime<Utc>) -> String {
@@ -53,4 +175,59 @@ mod tests {
         let duration = duration_human(&start, &end);
         assert!(duration.contains("1.0 hours"));
     }
+
+    #[test]
+    fn test_parse_duration_units() {
+        assert_eq!(parse_duration("30s").unwrap(), 30);
+        assert_eq!(parse_duration("15m").unwrap(), 900);
+        assert_eq!(parse_duration("2h").unwrap(), 7200);
+        assert_eq!(parse_duration("7d").unwrap(), 604_800);
+        assert_eq!(parse_duration("1w").unwrap(), 604_800);
+    }
+
+    #[test]
+    fn test_parse_duration_compound() {
+        assert_eq!(parse_duration("1h30m").unwrap(), 5400);
+    }
+
+    #[test]
+    fn test_parse_duration_aliases() {
+        assert_eq!(parse_duration("hourly").unwrap(), 3600);
+        assert_eq!(parse_duration("twice-daily").unwrap(), 43_200);
+        assert_eq!(parse_duration("daily").unwrap(), 86_400);
+        assert_eq!(parse_duration("weekly").unwrap(), 604_800);
+    }
+
+    #[test]
+    fn test_parse_duration_bare_number() {
+        assert_eq!(parse_duration("7200").unwrap(), 7200);
+    }
+
+    #[test]
+    fn test_resolve_time_bound_relative() {
+        let now = parse_timestamp("2025-10-06T07:26:03Z").unwrap();
+        let since = resolve_time_bound("24h", now).unwrap();
+        assert_eq!(since, parse_timestamp("2025-10-05T07:26:03Z").unwrap());
+    }
+
+    #[test]
+    fn test_resolve_time_bound_absolute() {
+        let now = parse_timestamp("2025-10-06T07:26:03Z").unwrap();
+        let absolute = resolve_time_bound("2025-09-01T00:00:00Z", now).unwrap();
+        assert_eq!(absolute, parse_timestamp("2025-09-01T00:00:00Z").unwrap());
+    }
+
+    #[test]
+    fn test_resolve_time_bound_rejects_garbage() {
+        let now = parse_timestamp("2025-10-06T07:26:03Z").unwrap();
+        assert!(resolve_time_bound("not-a-time", now).is_err());
+    }
+
+    #[test]
+    fn test_parse_duration_rejects_empty_and_garbage() {
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("  ").is_err());
+        assert!(parse_duration("soon").is_err());
+        assert!(parse_duration("3x").is_err());
+    }
 }
@@ -0,0 +1,745 @@
+//! Vault API client used by the mount/entity enumeration commands.
+//!
+//! This mirrors the lighter-weight client in `vault-audit-tools`, but adds
+//! namespace support (`X-Vault-Namespace`) and `LIST`/`POST` helpers, since
+//! the enumeration commands in this crate need both. Every request goes
+//! through a shared retry policy: HTTP 429/5xx responses are retried with
+//! exponential backoff and jitter (honoring a `Retry-After` header verbatim
+//! when present), reusing the single pooled `reqwest::Client` across calls.
+//!
+//! # DNS resolution
+//!
+//! By default the client uses the system resolver. Two overrides are
+//! available for environments with split-horizon DNS, internal-only Vault
+//! clusters, or a bastion that can't see the cluster's DNS at all:
+//! curl-style `--resolve host:port:ip` static pins (see
+//! [`parse_resolve_override`]), applied directly on the `reqwest::ClientBuilder`;
+//! and `--dns-server <addr>`, which routes every other lookup through a
+//! specific nameserver via [`CustomDnsResolver`]. Static pins take priority
+//! per-host; `--dns-server` only affects hosts that aren't pinned.
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use hickory_resolver::config::{NameServerConfigGroup, ResolverConfig, ResolverOpts};
+use hickory_resolver::TokioAsyncResolver;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::env;
+use std::fs;
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// Maximum number of attempts (including the first) before a retryable
+/// (HTTP 429/5xx) response is surfaced as an error.
+const MAX_RETRY_ATTEMPTS: u32 = 5;
+
+/// Base delay for the exponential backoff between retries; doubles each
+/// attempt, capped at [`MAX_RETRY_DELAY`].
+const BASE_RETRY_DELAY: Duration = Duration::from_millis(200);
+
+/// Upper bound on the backoff delay between retries.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Cheap, dependency-free jitter source: the low bits of the current time's
+/// subsecond nanoseconds. Good enough to avoid a thundering herd of retrying
+/// clients without pulling in a `rand` dependency.
+fn jitter_millis(bound_ms: u64) -> u64 {
+    if bound_ms == 0 {
+        return 0;
+    }
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    u64::from(nanos) % bound_ms
+}
+
+/// Check if TLS verification should be skipped based on environment or flag
+pub fn should_skip_verify(insecure_flag: bool) -> bool {
+    if insecure_flag {
+        return true;
+    }
+
+    env::var("VAULT_SKIP_VERIFY")
+        .ok()
+        .and_then(|v| {
+            v.parse::<bool>().ok().or_else(|| match v.to_lowercase().as_str() {
+                "1" | "true" | "yes" => Some(true),
+                _ => Some(false),
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// Normalizes a Vault Enterprise namespace path the same way `addr` is
+/// trimmed: surrounding whitespace and leading/trailing slashes, e.g.
+/// `"/admin/team-a/"` -> `"admin/team-a"`. Returns `None` if nothing is left,
+/// so an empty `--vault-namespace`/`VAULT_NAMESPACE` is treated as unset.
+fn normalize_namespace(raw: &str) -> Option<String> {
+    let trimmed = raw.trim().trim_matches('/');
+    if trimmed.is_empty() {
+        None
+    } else {
+        Some(trimmed.to_string())
+    }
+}
+
+/// Parse a curl-style `--resolve host:port:ip` override into a `(host,
+/// socket_addr)` pair suitable for [`VaultClient::from_options`]. The port
+/// is validated but otherwise only used to build the overridden socket
+/// address; TLS verification still validates against `host`, not the IP.
+pub fn parse_resolve_override(s: &str) -> Result<(String, SocketAddr)> {
+    let mut parts = s.splitn(3, ':');
+    let host = parts
+        .next()
+        .filter(|h| !h.is_empty())
+        .ok_or_else(|| anyhow!("invalid --resolve override '{}': missing host", s))?;
+    let port: u16 = parts
+        .next()
+        .ok_or_else(|| anyhow!("invalid --resolve override '{}': missing port", s))?
+        .parse()
+        .with_context(|| format!("invalid --resolve override '{}': bad port", s))?;
+    let ip: std::net::IpAddr = parts
+        .next()
+        .ok_or_else(|| anyhow!("invalid --resolve override '{}': missing IP", s))?
+        .parse()
+        .with_context(|| format!("invalid --resolve override '{}': bad IP address", s))?;
+
+    Ok((host.to_string(), SocketAddr::new(ip, port)))
+}
+
+/// A `reqwest::dns::Resolve` implementation that routes lookups through a
+/// specific nameserver (`--dns-server`) instead of the system resolver, for
+/// Vault clusters whose names only resolve via an internal/split-horizon
+/// DNS server. Hosts pinned via `--resolve` bypass this entirely - reqwest
+/// only consults the configured `Resolve` for hosts without a static
+/// override.
+#[derive(Clone)]
+struct CustomDnsResolver {
+    resolver: Arc<TokioAsyncResolver>,
+}
+
+impl CustomDnsResolver {
+    fn new(dns_server: SocketAddr) -> Self {
+        let group = NameServerConfigGroup::from_ips_clear(
+            &[dns_server.ip()],
+            dns_server.port(),
+            true, /* trust_negative_responses */
+        );
+        let config = ResolverConfig::from_parts(None, vec![], group);
+        let resolver = TokioAsyncResolver::tokio(config, ResolverOpts::default());
+        Self {
+            resolver: Arc::new(resolver),
+        }
+    }
+}
+
+impl Resolve for CustomDnsResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let resolver = Arc::clone(&self.resolver);
+        Box::pin(async move {
+            let lookup = resolver.lookup_ip(name.as_str()).await?;
+            let addrs: Addrs = Box::new(lookup.into_iter().map(|ip| SocketAddr::new(ip, 0)));
+            Ok(addrs)
+        })
+    }
+}
+
+/// A Vault auth method `VaultClient::login` can exchange for a client token,
+/// instead of requiring a pre-provisioned `VAULT_TOKEN`.
+#[derive(Debug, Clone)]
+pub enum AuthMethod {
+    /// A pre-issued token, routed through the same login path as the other
+    /// variants purely so callers can treat all three uniformly.
+    Token(String),
+    /// AppRole auth (`POST /v1/auth/approle/login`).
+    AppRole { role_id: String, secret_id: String },
+    /// Kubernetes auth (`POST /v1/auth/kubernetes/login`), reading the
+    /// service account JWT from `jwt_path` (typically
+    /// `/var/run/secrets/kubernetes.io/serviceaccount/token`).
+    Kubernetes { role: String, jwt_path: String },
+}
+
+/// A client token and the lease metadata needed to renew it. `lease_duration_secs
+/// == u64::MAX` marks a token with no known expiry (e.g. a raw `VAULT_TOKEN`
+/// supplied directly rather than minted via [`VaultClient::login`]), which
+/// [`VaultClient::renew_if_needed`] treats as a no-op.
+#[derive(Debug, Clone)]
+struct TokenState {
+    token: String,
+    issued_at: DateTime<Utc>,
+    lease_duration_secs: u64,
+}
+
+impl TokenState {
+    fn unmanaged(token: String) -> Self {
+        Self {
+            token,
+            issued_at: Utc::now(),
+            lease_duration_secs: u64::MAX,
+        }
+    }
+}
+
+/// Vault API client configuration
+#[derive(Debug, Clone)]
+pub struct VaultClient {
+    addr: String,
+    token: Arc<RwLock<TokenState>>,
+    namespace: Option<String>,
+    client: Client,
+}
+
+impl VaultClient {
+    /// Create a new Vault client from address and token
+    #[allow(dead_code)]
+    pub fn new(addr: String, token: String) -> Result<Self> {
+        Self::new_with_skip_verify(addr, token, false, None)
+    }
+
+    /// Create a new Vault client with option to skip TLS verification and
+    /// target a Vault Enterprise namespace
+    pub fn new_with_skip_verify(
+        addr: String,
+        token: String,
+        skip_verify: bool,
+        namespace: Option<String>,
+    ) -> Result<Self> {
+        let client = Self::build_client(skip_verify, &[], None)?;
+
+        Ok(Self {
+            addr: addr.trim_end_matches('/').to_string(),
+            token: Arc::new(RwLock::new(TokenState::unmanaged(token))),
+            namespace: namespace.as_deref().and_then(normalize_namespace),
+            client,
+        })
+    }
+
+    /// Build the underlying `reqwest` client, applying any curl-style
+    /// `--resolve host:port:ip` overrides so DNS resolution for `host` is
+    /// pinned to `ip` while TLS SNI and the `Host` header (and therefore
+    /// certificate validation) still use `host`, and, if `dns_server` is
+    /// given, routing every other host's lookups through it via
+    /// [`CustomDnsResolver`] instead of the system resolver.
+    fn build_client(
+        skip_verify: bool,
+        resolve_overrides: &[(String, SocketAddr)],
+        dns_server: Option<SocketAddr>,
+    ) -> Result<Client> {
+        let mut builder = Client::builder().danger_accept_invalid_certs(skip_verify);
+        if let Some(dns_server) = dns_server {
+            builder = builder.dns_resolver(Arc::new(CustomDnsResolver::new(dns_server)));
+        }
+        for (host, addr) in resolve_overrides {
+            builder = builder.resolve(host, *addr);
+        }
+        builder.build().context("Failed to create HTTP client")
+    }
+
+    /// Create a client with optional parameters (for CLI)
+    ///
+    /// `resolve_overrides` implements curl-style `--resolve host:port:ip`
+    /// host pinning: the given host resolves to the given IP instead of
+    /// going through system DNS, while TLS SNI and certificate validation
+    /// still use the original hostname. `dns_server`, if given, routes
+    /// lookups for every other host through that nameserver instead of the
+    /// system resolver.
+    pub fn from_options(
+        vault_addr: Option<&str>,
+        vault_token: Option<&str>,
+        vault_namespace: Option<&str>,
+        skip_verify: bool,
+        resolve_overrides: &[(String, SocketAddr)],
+        dns_server: Option<SocketAddr>,
+    ) -> Result<Self> {
+        let addr = vault_addr
+            .map(|s| s.to_string())
+            .or_else(|| env::var("VAULT_ADDR").ok())
+            .unwrap_or_else(|| "http://127.0.0.1:8200".to_string());
+
+        let token = if let Some(t) = vault_token {
+            t.to_string()
+        } else if let Ok(t) = env::var("VAULT_TOKEN") {
+            t
+        } else if let Ok(token_file) = env::var("VAULT_TOKEN_FILE") {
+            fs::read_to_string(&token_file)
+                .with_context(|| format!("Failed to read token from file: {}", token_file))?
+                .trim()
+                .to_string()
+        } else {
+            return Err(anyhow!(
+                "VAULT_TOKEN or VAULT_TOKEN_FILE must be set. Provide a token via:\n\
+                 - Command-line: --vault-token hvs.xxxxx\n\
+                 - Environment variable: export VAULT_TOKEN=hvs.xxxxx\n\
+                 - Token file: export VAULT_TOKEN_FILE=/path/to/token"
+            ));
+        };
+
+        let namespace = vault_namespace
+            .map(|s| s.to_string())
+            .or_else(|| env::var("VAULT_NAMESPACE").ok())
+            .and_then(|ns| normalize_namespace(&ns));
+
+        let client = Self::build_client(skip_verify, resolve_overrides, dns_server)?;
+
+        Ok(Self {
+            addr: addr.trim_end_matches('/').to_string(),
+            token: Arc::new(RwLock::new(TokenState::unmanaged(token))),
+            namespace,
+            client,
+        })
+    }
+
+    /// Resolves an AppRole credential from, in order, the CLI flag, a
+    /// same-named environment variable, or a `_FILE`-suffixed environment
+    /// variable naming a file to read it from - the same fallback chain
+    /// [`VaultClient::from_options`] uses for `VAULT_TOKEN`/
+    /// `VAULT_TOKEN_FILE`, parameterized over the variable name so it can
+    /// be reused for both `VAULT_ROLE_ID` and `VAULT_SECRET_ID`.
+    fn read_credential(flag_value: Option<&str>, env_var: &str) -> Result<Option<String>> {
+        if let Some(value) = flag_value {
+            return Ok(Some(value.to_string()));
+        }
+        if let Ok(value) = env::var(env_var) {
+            return Ok(Some(value));
+        }
+        let file_var = format!("{}_FILE", env_var);
+        if let Ok(path) = env::var(&file_var) {
+            let contents = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {} from file: {}", env_var, path))?
+                .trim()
+                .to_string();
+            return Ok(Some(contents));
+        }
+        Ok(None)
+    }
+
+    /// Builds a client the same way [`VaultClient::from_options`] does,
+    /// except that when `role_id`/`secret_id` are available (via flag,
+    /// `VAULT_ROLE_ID`/`VAULT_SECRET_ID`, or `VAULT_ROLE_ID_FILE`/
+    /// `VAULT_SECRET_ID_FILE`) it logs in via AppRole through
+    /// [`VaultClient::login`] instead, so CI/automation can run without a
+    /// pre-provisioned long-lived `VAULT_TOKEN`.
+    pub async fn connect(
+        vault_addr: Option<&str>,
+        vault_token: Option<&str>,
+        vault_namespace: Option<&str>,
+        role_id: Option<&str>,
+        secret_id: Option<&str>,
+        skip_verify: bool,
+        resolve_overrides: &[(String, SocketAddr)],
+        dns_server: Option<SocketAddr>,
+    ) -> Result<Self> {
+        let role_id = Self::read_credential(role_id, "VAULT_ROLE_ID")?;
+        let secret_id = Self::read_credential(secret_id, "VAULT_SECRET_ID")?;
+
+        match (role_id, secret_id) {
+            (Some(role_id), Some(secret_id)) => {
+                let addr = vault_addr
+                    .map(|s| s.to_string())
+                    .or_else(|| env::var("VAULT_ADDR").ok())
+                    .unwrap_or_else(|| "http://127.0.0.1:8200".to_string());
+                Self::login(
+                    &addr,
+                    AuthMethod::AppRole { role_id, secret_id },
+                    vault_namespace,
+                    skip_verify,
+                    resolve_overrides,
+                    dns_server,
+                )
+                .await
+            }
+            (None, None) => Self::from_options(
+                vault_addr,
+                vault_token,
+                vault_namespace,
+                skip_verify,
+                resolve_overrides,
+                dns_server,
+            ),
+            _ => Err(anyhow!(
+                "AppRole login requires both --role-id and --secret-id (or their \
+                 VAULT_ROLE_ID/VAULT_SECRET_ID equivalents)"
+            )),
+        }
+    }
+
+    /// Authenticate to Vault via `auth_method` and build a client from the
+    /// resulting `auth.client_token`/`auth.lease_duration`, instead of
+    /// requiring a pre-provisioned `VAULT_TOKEN`. Call
+    /// [`VaultClient::renew_if_needed`] periodically during long-running
+    /// enumeration so the token doesn't expire mid-run.
+    pub async fn login(
+        addr: &str,
+        auth_method: AuthMethod,
+        namespace: Option<&str>,
+        skip_verify: bool,
+        resolve_overrides: &[(String, SocketAddr)],
+        dns_server: Option<SocketAddr>,
+    ) -> Result<Self> {
+        let client = Self::build_client(skip_verify, resolve_overrides, dns_server)?;
+        let addr = addr.trim_end_matches('/').to_string();
+        let namespace = namespace.and_then(normalize_namespace);
+
+        let token_state = match auth_method {
+            AuthMethod::Token(token) => TokenState::unmanaged(token),
+            AuthMethod::AppRole { role_id, secret_id } => {
+                let (token, lease_duration_secs) = Self::login_via(
+                    &client,
+                    &addr,
+                    namespace.as_deref(),
+                    "auth/approle/login",
+                    serde_json::json!({ "role_id": role_id, "secret_id": secret_id }),
+                )
+                .await?;
+                TokenState {
+                    token,
+                    issued_at: Utc::now(),
+                    lease_duration_secs,
+                }
+            }
+            AuthMethod::Kubernetes { role, jwt_path } => {
+                let jwt = fs::read_to_string(&jwt_path)
+                    .with_context(|| {
+                        format!("Failed to read Kubernetes service account JWT from {}", jwt_path)
+                    })?
+                    .trim()
+                    .to_string();
+                let (token, lease_duration_secs) = Self::login_via(
+                    &client,
+                    &addr,
+                    namespace.as_deref(),
+                    "auth/kubernetes/login",
+                    serde_json::json!({ "role": role, "jwt": jwt }),
+                )
+                .await?;
+                TokenState {
+                    token,
+                    issued_at: Utc::now(),
+                    lease_duration_secs,
+                }
+            }
+        };
+
+        Ok(Self {
+            addr,
+            token: Arc::new(RwLock::new(token_state)),
+            namespace,
+            client,
+        })
+    }
+
+    /// POSTs to a Vault auth-method login endpoint and extracts
+    /// `auth.client_token`/`auth.lease_duration` from the response.
+    async fn login_via(
+        client: &Client,
+        addr: &str,
+        namespace: Option<&str>,
+        login_path: &str,
+        body: Value,
+    ) -> Result<(String, u64)> {
+        let url = format!("{}/v1/{}", addr, login_path);
+        let mut req = client.post(url).json(&body);
+        if let Some(namespace) = namespace {
+            req = req.header("X-Vault-Namespace", namespace);
+        }
+
+        let response = req.send().await.context("Failed to send Vault login request")?;
+        let status = response.status();
+        let body_text = response
+            .text()
+            .await
+            .context("Failed to read Vault login response")?;
+
+        if !status.is_success() {
+            return Err(anyhow!(
+                "Vault login to {} failed with status {}: {}",
+                login_path,
+                status,
+                body_text
+            ));
+        }
+
+        let value: Value = serde_json::from_str(&body_text)
+            .with_context(|| format!("Failed to parse JSON response from {}", login_path))?;
+        let auth = value
+            .get("auth")
+            .ok_or_else(|| anyhow!("Vault login response from {} has no \"auth\" block", login_path))?;
+        let token = auth
+            .get("client_token")
+            .and_then(Value::as_str)
+            .ok_or_else(|| {
+                anyhow!("Vault login response from {} has no auth.client_token", login_path)
+            })?
+            .to_string();
+        let lease_duration_secs = auth.get("lease_duration").and_then(Value::as_u64).unwrap_or(0);
+
+        Ok((token, lease_duration_secs))
+    }
+
+    /// Renews the current token via `/v1/auth/token/renew-self` once it is
+    /// within `renew_fraction` of its lease expiry (e.g. `0.5` renews at the
+    /// halfway point), so a long-running enumeration command doesn't die
+    /// mid-run with a 403. A no-op for tokens with no known lease duration,
+    /// i.e. anything not minted via [`VaultClient::login`].
+    pub async fn renew_if_needed(&self, renew_fraction: f64) -> Result<()> {
+        let (token, lease_duration_secs, elapsed_secs) = {
+            let state = self.token.read().expect("token lock poisoned");
+            if state.lease_duration_secs == 0 || state.lease_duration_secs == u64::MAX {
+                return Ok(());
+            }
+            let elapsed_secs = Utc::now()
+                .signed_duration_since(state.issued_at)
+                .num_seconds()
+                .max(0) as u64;
+            (state.token.clone(), state.lease_duration_secs, elapsed_secs)
+        };
+
+        if (elapsed_secs as f64) < (lease_duration_secs as f64) * renew_fraction {
+            return Ok(());
+        }
+
+        let url = format!("{}/v1/auth/token/renew-self", self.addr);
+        let mut req = self.client.post(url).header("X-Vault-Token", &token);
+        if let Some(namespace) = &self.namespace {
+            req = req.header("X-Vault-Namespace", namespace);
+        }
+
+        let response = req
+            .send()
+            .await
+            .context("Failed to send Vault token renewal request")?;
+        let status = response.status();
+        let body_text = response
+            .text()
+            .await
+            .context("Failed to read Vault renewal response")?;
+
+        if !status.is_success() {
+            return Err(anyhow!(
+                "Vault token renewal failed with status {}: {}",
+                status,
+                body_text
+            ));
+        }
+
+        let value: Value = serde_json::from_str(&body_text)
+            .context("Failed to parse JSON response from auth/token/renew-self")?;
+        let auth = value
+            .get("auth")
+            .ok_or_else(|| anyhow!("Vault renew-self response has no \"auth\" block"))?;
+        let new_token = auth
+            .get("client_token")
+            .and_then(Value::as_str)
+            .unwrap_or(&token)
+            .to_string();
+        let new_lease = auth
+            .get("lease_duration")
+            .and_then(Value::as_u64)
+            .unwrap_or(lease_duration_secs);
+
+        let mut state = self.token.write().expect("token lock poisoned");
+        state.token = new_token;
+        state.lease_duration_secs = new_lease;
+        state.issued_at = Utc::now();
+
+        Ok(())
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let url = format!("{}{}", self.addr, path);
+        let token = self.token.read().expect("token lock poisoned").token.clone();
+        let mut req = self.client.request(method, url).header("X-Vault-Token", &token);
+        if let Some(namespace) = &self.namespace {
+            req = req.header("X-Vault-Namespace", namespace);
+        }
+        req
+    }
+
+    async fn send_json(&self, method: reqwest::Method, path: &str) -> Result<Value> {
+        self.send_json_with_body(method, path, None).await
+    }
+
+    /// Send a request, retrying with exponential backoff + jitter on HTTP
+    /// 429 or 5xx responses (honoring a `Retry-After` header verbatim when
+    /// present), up to [`MAX_RETRY_ATTEMPTS`]. The underlying `reqwest::Client`
+    /// is shared across all calls, so retries and bulk enumeration alike
+    /// reuse pooled connections instead of opening new sockets.
+    async fn send_json_with_body(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<Value> {
+        let mut attempt = 0u32;
+        loop {
+            attempt += 1;
+            let mut req = self.request(method.clone(), path);
+            if let Some(body) = body {
+                req = req.json(body);
+            }
+
+            let response = req.send().await.context("Failed to send request to Vault")?;
+            let status = response.status();
+
+            let retryable = status.as_u16() == 429 || status.is_server_error();
+            if retryable && attempt < MAX_RETRY_ATTEMPTS {
+                let retry_after = response
+                    .headers()
+                    .get(reqwest::header::RETRY_AFTER)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .map(Duration::from_secs);
+
+                let delay = retry_after.unwrap_or_else(|| {
+                    let exponential = BASE_RETRY_DELAY
+                        .saturating_mul(1u32 << (attempt - 1).min(31))
+                        .min(MAX_RETRY_DELAY);
+                    exponential + Duration::from_millis(jitter_millis(100))
+                });
+
+                tokio::time::sleep(delay).await;
+                continue;
+            }
+
+            let body_text = response
+                .text()
+                .await
+                .context("Failed to read response body")?;
+
+            if !status.is_success() {
+                return Err(anyhow!(
+                    "Vault API request failed with status {}: {}",
+                    status,
+                    body_text
+                ));
+            }
+
+            if body_text.trim().is_empty() {
+                return Ok(Value::Null);
+            }
+
+            return serde_json::from_str(&body_text)
+                .with_context(|| format!("Failed to parse JSON response from {}", path));
+        }
+    }
+
+    /// Make a GET request to a Vault API endpoint
+    pub async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let value = self.send_json(reqwest::Method::GET, path).await?;
+        serde_json::from_value(value)
+            .with_context(|| format!("Failed to deserialize JSON response from {}", path))
+    }
+
+    /// Make a GET request and return raw JSON Value
+    pub async fn get_json(&self, path: &str) -> Result<Value> {
+        self.send_json(reqwest::Method::GET, path).await
+    }
+
+    /// Make a GET request and return the raw response body, for endpoints
+    /// like `/sys/metrics?format=prometheus` that don't return JSON.
+    pub async fn get_text(&self, path: &str) -> Result<String> {
+        let response = self
+            .request(reqwest::Method::GET, path)
+            .send()
+            .await
+            .context("Failed to send request to Vault")?;
+        let status = response.status();
+        let body_text = response.text().await.context("Failed to read response body")?;
+        if !status.is_success() {
+            return Err(anyhow!(
+                "Vault API request failed with status {}: {}",
+                status,
+                body_text
+            ));
+        }
+        Ok(body_text)
+    }
+
+    /// Make a Vault `LIST` request and return raw JSON Value
+    pub async fn list_json(&self, path: &str) -> Result<Value> {
+        let list_method = reqwest::Method::from_bytes(b"LIST").expect("LIST is a valid method token");
+        self.send_json(list_method, path).await
+    }
+
+    /// Make a Vault `LIST` request and deserialize into `T`
+    pub async fn list<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let value = self.list_json(path).await?;
+        serde_json::from_value(value)
+            .with_context(|| format!("Failed to deserialize JSON response from {}", path))
+    }
+
+    /// Make a POST request with a JSON body and return raw JSON Value
+    pub async fn post_json(&self, path: &str, body: &Value) -> Result<Value> {
+        self.send_json_with_body(reqwest::Method::POST, path, Some(body))
+            .await
+    }
+
+    /// Make a POST request with a JSON body and deserialize the response into `T`
+    pub async fn post<T: DeserializeOwned>(&self, path: &str, body: &Value) -> Result<T> {
+        let value = self.post_json(path, body).await?;
+        serde_json::from_value(value)
+            .with_context(|| format!("Failed to deserialize JSON response from {}", path))
+    }
+
+    /// Make a DELETE request, for endpoints like `/sys/audit/:path` that
+    /// tear down a config object and respond `204 No Content`.
+    pub async fn delete(&self, path: &str) -> Result<()> {
+        self.send_json_with_body(reqwest::Method::DELETE, path, None).await?;
+        Ok(())
+    }
+
+    /// Get the Vault address
+    pub fn addr(&self) -> &str {
+        &self.addr
+    }
+}
+
+/// Helper to extract data from Vault response wrapper
+pub fn extract_data<T: DeserializeOwned>(value: Value) -> Result<T> {
+    if let Some(data) = value.get("data") {
+        serde_json::from_value(data.clone())
+            .context("Failed to deserialize data from Vault response")
+    } else {
+        serde_json::from_value(value).context("Failed to deserialize Vault response")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_creation() {
+        let client = VaultClient::new(
+            "http://127.0.0.1:8200".to_string(),
+            "test-token".to_string(),
+        );
+        assert!(client.is_ok());
+    }
+
+    #[test]
+    fn test_addr_trimming() {
+        let client = VaultClient::new(
+            "http://127.0.0.1:8200/".to_string(),
+            "test-token".to_string(),
+        )
+        .unwrap();
+        assert_eq!(client.addr(), "http://127.0.0.1:8200");
+    }
+
+    #[test]
+    fn test_parse_resolve_override_valid() {
+        let (host, addr) = parse_resolve_override("vault.internal:8200:10.0.0.5").unwrap();
+        assert_eq!(host, "vault.internal");
+        assert_eq!(addr.to_string(), "10.0.0.5:8200");
+    }
+
+    #[test]
+    fn test_parse_resolve_override_rejects_bad_ip() {
+        assert!(parse_resolve_override("vault.internal:8200:not-an-ip").is_err());
+    }
+}
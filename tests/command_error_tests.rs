@@ -10,6 +10,9 @@ fn test_preprocess_entities_with_invalid_file() {
         &["/nonexistent/file.log".to_string()],
         "output.json",
         "json",
+        vault_audit_tools::utils::mapping_store::StoreBackend::Memory,
+        None,
+        None,
     );
 
     assert!(result.is_err());
@@ -29,6 +32,9 @@ fn test_preprocess_entities_with_empty_file() {
         &[log_path.to_str().unwrap().to_string()],
         output_path.to_str().unwrap(),
         "json",
+        vault_audit_tools::utils::mapping_store::StoreBackend::Memory,
+        None,
+        None,
     );
 
     // Should succeed even with empty file
@@ -49,6 +55,9 @@ fn test_preprocess_entities_invalid_format() {
         &[log_path.to_str().unwrap().to_string()],
         output_path.to_str().unwrap(),
         "invalid_format",
+        vault_audit_tools::utils::mapping_store::StoreBackend::Memory,
+        None,
+        None,
     );
 
     assert!(result.is_err());
@@ -73,6 +82,9 @@ fn test_preprocess_entities_json_format() {
         &[log_path.to_str().unwrap().to_string()],
         output_path.to_str().unwrap(),
         "json",
+        vault_audit_tools::utils::mapping_store::StoreBackend::Memory,
+        None,
+        None,
     );
 
     assert!(result.is_ok());
@@ -97,6 +109,9 @@ fn test_preprocess_entities_csv_format() {
         &[log_path.to_str().unwrap().to_string()],
         output_path.to_str().unwrap(),
         "csv",
+        vault_audit_tools::utils::mapping_store::StoreBackend::Memory,
+        None,
+        None,
     );
 
     assert!(result.is_ok());
@@ -171,7 +186,7 @@ fn test_entity_gaps_empty_log() {
     let log_path = temp_dir.path().join("empty.log");
     File::create(&log_path).unwrap();
 
-    let result = entity_gaps::run(&[log_path.to_str().unwrap().to_string()], 3600);
+    let result = entity_gaps::run(&[log_path.to_str().unwrap().to_string()], 3600, None, "table", None);
     assert!(result.is_ok());
 }
 
@@ -179,7 +194,7 @@ fn test_entity_gaps_empty_log() {
 fn test_entity_gaps_invalid_file() {
     use vault_audit_tools::commands::entity_gaps;
 
-    let result = entity_gaps::run(&["/nonexistent/file.log".to_string()], 3600);
+    let result = entity_gaps::run(&["/nonexistent/file.log".to_string()], 3600, None, "table", None);
     assert!(result.is_err());
 }
 
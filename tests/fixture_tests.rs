@@ -0,0 +1,26 @@
+/// Golden-fixture regression tests for `--format json` output.
+///
+/// Unlike `integration_tests.rs`, which only asserts `result.is_ok()`,
+/// these compare a command's output byte-for-byte against a checked-in
+/// `expected.json` - see `vault_audit_tools::testing` for the harness and
+/// `tests/fixtures/<name>/` for the fixture pairs. Run with
+/// `VAULT_AUDIT_RECORD_FIXTURES=1 cargo test --test fixture_tests` to
+/// regenerate `expected.json` after an intentional behavior change.
+use vault_audit_tools::commands::{anomaly_detect, findings};
+use vault_audit_tools::testing::run_against_fixture;
+
+#[test]
+fn test_findings_fixture() {
+    run_against_fixture("findings", |input| {
+        findings::run_to_string(&[input.to_string_lossy().into_owned()], 2, 3, 1, "json")
+    })
+    .unwrap();
+}
+
+#[test]
+fn test_anomaly_detect_fixture() {
+    run_against_fixture("anomaly_detect", |input| {
+        anomaly_detect::run_to_string(&[input.to_string_lossy().into_owned()], 60, 0.3, 3.0, 3, 50, "json")
+    })
+    .unwrap();
+}
@@ -76,7 +76,7 @@ fn test_token_lookup_abuse_command() {
 
     use vault_audit_tools::commands::token_lookup_abuse;
     let log_files = vec![log_path.to_str().unwrap().to_string()];
-    let result = token_lookup_abuse::run(&log_files, 2);
+    let result = token_lookup_abuse::run(&log_files, 2, false, "table");
 
     assert!(result.is_ok());
 }
@@ -130,7 +130,7 @@ fn test_entity_gaps_command() {
 
     use vault_audit_tools::commands::entity_gaps;
     let log_files = vec![log_path.to_str().unwrap().to_string()];
-    let result = entity_gaps::run(&log_files, 300);
+    let result = entity_gaps::run(&log_files, 300, None, "table", None);
 
     assert!(result.is_ok());
 }
@@ -171,6 +171,17 @@ fn test_kv_analyzer_command() {
     assert!(output_path.exists());
 }
 
+#[test]
+fn test_findings_command() {
+    let (_dir, log_path) = create_sample_audit_log();
+
+    use vault_audit_tools::commands::findings;
+    let log_files = vec![log_path.to_str().unwrap().to_string()];
+    let result = findings::run(&log_files, 5, 10, 3, "table");
+
+    assert!(result.is_ok());
+}
+
 #[test]
 fn test_token_export_command() {
     let (_dir, log_path) = create_sample_audit_log();
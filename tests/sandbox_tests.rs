@@ -0,0 +1,95 @@
+/// Smoke test for `--sandbox`: drives the real `vault-audit entity-list
+/// --sandbox` binary end-to-end against a minimal local Vault stub, so a
+/// seccomp/pledge allowlist that's too tight for the Vault HTTPS client
+/// (thread creation during DNS/TLS setup, etc.) fails this test instead of
+/// shipping broken.
+///
+/// The hardening only ever runs inside the spawned child process, never in
+/// the test harness itself, since it permanently restricts the calling
+/// process's syscalls for its remaining lifetime.
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::process::Command;
+
+/// Reads one HTTP/1.1 request line + headers off `stream` and writes back
+/// a canned JSON body keyed on the request path. Good enough to stand in
+/// for Vault's `/v1/sys/auth` and `/v1/identity/entity/id[/*]` endpoints.
+fn handle_request(mut stream: TcpStream) {
+    let mut buf = [0u8; 4096];
+    let mut request = Vec::new();
+    loop {
+        let n = match stream.read(&mut buf) {
+            Ok(n) => n,
+            Err(_) => return,
+        };
+        request.extend_from_slice(&buf[..n]);
+        if request.windows(4).any(|w| w == b"\r\n\r\n") || n == 0 {
+            break;
+        }
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let path = request
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .unwrap_or("/");
+
+    let body = if path.starts_with("/v1/sys/auth") {
+        r#"{"data":{}}"#.to_string()
+    } else if path.starts_with("/v1/identity/entity/id?list=true") {
+        r#"{"data":{"keys":["entity-1"]}}"#.to_string()
+    } else if path.starts_with("/v1/identity/entity/id/") {
+        r#"{"data":{"id":"entity-1","name":"test-user","disabled":false,"creation_time":"2025-01-01T00:00:00Z","last_update_time":"2025-01-01T00:00:00Z","aliases":[]}}"#.to_string()
+    } else {
+        r#"{"data":{}}"#.to_string()
+    };
+
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes());
+}
+
+/// Spawns a stub Vault server on an OS-assigned loopback port and serves
+/// requests on a background thread for the lifetime of the test process.
+fn spawn_stub_vault() -> std::net::SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind stub vault listener");
+    let addr = listener.local_addr().expect("stub vault local addr");
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            handle_request(stream);
+        }
+    });
+
+    addr
+}
+
+#[test]
+fn test_entity_list_sandbox_smoke() {
+    let addr = spawn_stub_vault();
+    let output_dir = tempfile::TempDir::new().unwrap();
+    let output_path = output_dir.path().join("entities.csv");
+
+    let status = Command::new(env!("CARGO_BIN_EXE_vault-audit"))
+        .arg("entity-list")
+        .arg("--vault-addr")
+        .arg(format!("http://{addr}"))
+        .arg("--vault-token")
+        .arg("test-token")
+        .arg("--output")
+        .arg(&output_path)
+        .arg("--sandbox")
+        .status()
+        .expect("spawn vault-audit entity-list --sandbox");
+
+    assert!(
+        status.success(),
+        "entity-list --sandbox exited with {status}; the seccomp/pledge \
+         allowlist is likely missing a syscall the Vault HTTPS client needs"
+    );
+    assert!(output_path.exists());
+}
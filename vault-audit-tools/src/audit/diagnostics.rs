@@ -0,0 +1,112 @@
+//! Opt-in parse diagnostics for [`AuditLogReader`](super::parser::AuditLogReader).
+//!
+//! By default, [`AuditLogReader::next_entry`](super::parser::AuditLogReader::next_entry)
+//! silently skips any line that doesn't deserialize into [`AuditEntry`](super::types::AuditEntry).
+//! That hides truncation, schema drift, and partial-write corruption that's
+//! common at the tail of a rotated log. Calling
+//! [`AuditLogReader::with_diagnostics`](super::parser::AuditLogReader::with_diagnostics)
+//! turns on tracking of every skipped line - its line number, byte offset,
+//! and whether it failed as invalid JSON or as JSON missing a required field
+//! (`type`/`time`) - into a [`ParseReport`] retrievable after iteration.
+
+/// Why a line failed to deserialize into an [`AuditEntry`](super::types::AuditEntry).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorCategory {
+    /// The line isn't valid JSON at all (truncated write, binary garbage, ...).
+    InvalidJson,
+    /// The line is valid JSON but is missing a field `AuditEntry` requires
+    /// (e.g. `type`/`time`), or a field has the wrong type - schema drift.
+    MissingFields,
+}
+
+/// One line that failed to parse, captured when diagnostics are enabled.
+#[derive(Debug, Clone)]
+pub struct SkippedLine {
+    /// 1-based line number within the source.
+    pub line_number: u64,
+    /// Byte offset of the start of this line within the source.
+    pub byte_offset: u64,
+    pub category: ParseErrorCategory,
+    /// The underlying `serde_json` error message.
+    pub message: String,
+}
+
+/// Default cap on how many [`SkippedLine`] samples a [`ParseReport`] retains.
+/// Bounded so a log that's mostly corrupt doesn't itself blow out memory.
+pub const DEFAULT_MAX_SAMPLES: usize = 20;
+
+/// Accumulated parse outcomes for one [`AuditLogReader`](super::parser::AuditLogReader)
+/// run with diagnostics enabled.
+#[derive(Debug, Clone)]
+pub struct ParseReport {
+    /// Total lines that deserialized successfully.
+    pub parsed: u64,
+    /// Total lines that were skipped (blank lines don't count).
+    pub skipped: u64,
+    /// The first `max_samples` skipped lines, for inspection.
+    pub samples: Vec<SkippedLine>,
+    max_samples: usize,
+}
+
+impl ParseReport {
+    pub(super) fn new(max_samples: usize) -> Self {
+        Self {
+            parsed: 0,
+            skipped: 0,
+            samples: Vec::new(),
+            max_samples,
+        }
+    }
+
+    pub(super) fn record_parsed(&mut self) {
+        self.parsed += 1;
+    }
+
+    pub(super) fn record_skipped(
+        &mut self,
+        line_number: u64,
+        byte_offset: u64,
+        error: &serde_json::Error,
+    ) {
+        self.skipped += 1;
+
+        let category = if error.is_data() {
+            ParseErrorCategory::MissingFields
+        } else {
+            ParseErrorCategory::InvalidJson
+        };
+
+        if self.samples.len() < self.max_samples {
+            self.samples.push(SkippedLine {
+                line_number,
+                byte_offset,
+                category,
+                message: error.to_string(),
+            });
+        }
+    }
+
+    /// Fraction of lines seen (parsed + skipped) that were skipped, in `[0.0, 1.0]`.
+    /// `0.0` if no lines have been seen yet.
+    pub fn skip_rate(&self) -> f64 {
+        let total = self.parsed + self.skipped;
+        if total == 0 {
+            0.0
+        } else {
+            self.skipped as f64 / total as f64
+        }
+    }
+
+    /// A short human-readable summary, e.g.
+    /// `"12,483 parsed, 37 skipped (0.3%)"`, suitable for printing at the
+    /// end of a run so operators can tell a healthy log from one that's
+    /// quietly losing entries.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} parsed, {} skipped ({:.1}%)",
+            self.parsed,
+            self.skipped,
+            self.skip_rate() * 100.0
+        )
+    }
+}
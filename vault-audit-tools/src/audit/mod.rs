@@ -6,6 +6,9 @@
 //! ## Key Components
 //!
 //! - [`types`] - Data structures representing audit log entries
+//! - [`parser`] - Streaming, decompressing line-by-line log reader
+//! - [`transaction`] - Pairs request/response entries into unified transactions
+//! - [`diagnostics`] - Opt-in tracking of skipped/malformed lines
 //!
 //! ## Example
 //!
@@ -29,4 +32,11 @@
 //! }
 //! ```
 
+pub mod diagnostics;
+pub mod parser;
+pub mod transaction;
 pub mod types;
+
+pub use diagnostics::ParseReport;
+pub use parser::AuditLogReader;
+pub use transaction::{Transaction, TransactionJoiner};
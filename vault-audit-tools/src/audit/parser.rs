@@ -4,12 +4,31 @@
 //! by reading and parsing one line at a time, rather than loading
 //! the entire file into memory.
 //!
+//! [`AuditLogReader`] reads from anything that implements [`BufRead`], so a
+//! file need not be plain text: [`AuditLogReader::new`] peeks the first few
+//! bytes of the file and dispatches on compression magic numbers (gzip,
+//! zstd), falling back to the `.gz`/`.zst` extension and then plain text
+//! when nothing matches - so a rotated file like `audit.log.gz.2024-01-02`
+//! still decompresses even though its extension no longer ends in `.gz`.
+//! [`AuditLogReader::from_stdin`] reads `-` for piping in a live
+//! `tail -f`-style feed instead of a static path.
+//!
+//! Invalid lines are skipped silently by default. Call
+//! [`AuditLogReader::with_diagnostics`] to instead accumulate a
+//! [`ParseReport`](super::diagnostics::ParseReport) of what was skipped and
+//! why - see [`super::diagnostics`].
+//!
+//! For long-running scrapes, [`AuditLogReader::with_metrics`] attaches a
+//! [`RunMetrics`](crate::utils::metrics::RunMetrics) handle that's updated
+//! atomically as entries are parsed or skipped - see
+//! [`crate::utils::metrics`].
+//!
 //! # Example
 //!
 //! ```no_run
 //! use vault_audit_tools::audit::parser::AuditLogReader;
 //!
-//! let mut reader = AuditLogReader::new("audit.log").unwrap();
+//! let mut reader = AuditLogReader::new("audit.log.gz").unwrap();
 //! while let Some(entry) = reader.next_entry().unwrap() {
 //!     if let Some(auth) = &entry.auth {
 //!         if let Some(entity_id) = &auth.entity_id {
@@ -19,28 +38,88 @@
 //! }
 //! ```
 
+use super::diagnostics::{ParseReport, DEFAULT_MAX_SAMPLES};
 use super::types::AuditEntry;
+use crate::utils::metrics::RunMetrics;
 use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Read};
 use std::path::Path;
+use std::sync::Arc;
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+const XZ_MAGIC: &[u8] = &[0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00];
+const BZIP2_MAGIC: &[u8] = b"BZh";
+const MAX_MAGIC_LEN: usize = XZ_MAGIC.len();
+
+/// What compression, if any, a sniffed byte prefix indicates.
+enum SniffedCompression {
+    Gzip,
+    Zstd,
+    Xz,
+    Bzip2,
+    None,
+}
 
-/// Iterator over audit log entries from a file.
+/// Identifies compression from the leading bytes of a file, independent of
+/// its extension - see the module docs.
+fn sniff_compression(header: &[u8]) -> SniffedCompression {
+    if header.starts_with(GZIP_MAGIC) {
+        SniffedCompression::Gzip
+    } else if header.starts_with(ZSTD_MAGIC) {
+        SniffedCompression::Zstd
+    } else if header.starts_with(XZ_MAGIC) {
+        SniffedCompression::Xz
+    } else if header.starts_with(BZIP2_MAGIC) {
+        SniffedCompression::Bzip2
+    } else {
+        SniffedCompression::None
+    }
+}
+
+/// Peeks up to `MAX_MAGIC_LEN` bytes off `file` and returns a reader that
+/// re-presents those bytes before the rest of the file, so sniffing the
+/// magic number doesn't consume any data the decoder needs.
+fn peek_and_rewrap(mut file: File) -> Result<(Vec<u8>, impl Read)> {
+    let mut header = [0u8; MAX_MAGIC_LEN];
+    let read = file.read(&mut header)?;
+    let peeked = header[..read].to_vec();
+    Ok((peeked.clone(), std::io::Cursor::new(peeked).chain(file)))
+}
+
+/// Iterator over audit log entries from any buffered byte source.
 ///
 /// This reader provides streaming access to audit log entries,
 /// parsing them one line at a time to minimize memory usage.
 /// Invalid JSON lines are automatically skipped.
 pub struct AuditLogReader {
-    reader: BufReader<File>,
+    reader: Box<dyn BufRead>,
     line_buffer: String,
+    line_number: u64,
+    byte_offset: u64,
+    diagnostics: Option<ParseReport>,
+    metrics: Option<Arc<RunMetrics>>,
 }
 
 impl AuditLogReader {
-    /// Create a new audit log reader from a file path.
+    /// Create a new audit log reader from a file path, or `"-"` for stdin
+    /// (equivalent to [`Self::from_stdin`] - no decompression is attempted
+    /// on stdin, since a shipper can just as easily pipe through `zcat`).
+    ///
+    /// Detects compression by peeking the file's leading bytes for a known
+    /// magic number, falling back to its extension and then plain text:
+    /// - Gzip magic (or `.gz` extension) -> Gzip
+    /// - Zstandard magic (or `.zst` extension) -> Zstandard
+    /// - xz magic (or `.xz`/`.lzma` extension) -> xz/lzma
+    /// - bzip2 magic (or `.bz2` extension) -> Bzip2
+    /// - Anything else -> plain text
     ///
     /// # Arguments
     ///
-    /// * `path` - Path to the audit log file
+    /// * `path` - Path to the audit log file (compressed or uncompressed),
+    ///   or `"-"` for stdin
     ///
     /// # Returns
     ///
@@ -52,21 +131,106 @@ impl AuditLogReader {
     /// ```no_run
     /// use vault_audit_tools::audit::parser::AuditLogReader;
     ///
-    /// let reader = AuditLogReader::new("audit.log").unwrap();
+    /// let reader = AuditLogReader::new("audit.log.gz").unwrap();
     /// ```
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let file = File::open(path).context("Failed to open audit log file")?;
-        Ok(Self {
-            reader: BufReader::new(file),
+        let path = path.as_ref();
+        if path == Path::new("-") {
+            return Ok(Self::from_stdin());
+        }
+
+        let file =
+            File::open(path).with_context(|| format!("Failed to open file: {}", path.display()))?;
+
+        let (peeked, rewrapped) = peek_and_rewrap(file)?;
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+
+        let reader: Box<dyn BufRead> = match (sniff_compression(&peeked), extension) {
+            (SniffedCompression::Gzip, _) | (SniffedCompression::None, "gz") => {
+                Box::new(BufReader::new(GzDecoder::new(rewrapped)))
+            }
+            (SniffedCompression::Zstd, _) | (SniffedCompression::None, "zst") => {
+                Box::new(BufReader::new(zstd::Decoder::new(rewrapped).with_context(
+                    || format!("Failed to create zstd decoder for: {}", path.display()),
+                )?))
+            }
+            (SniffedCompression::Xz, _) | (SniffedCompression::None, "xz" | "lzma") => {
+                Box::new(BufReader::new(xz2::read::XzDecoder::new(rewrapped)))
+            }
+            (SniffedCompression::Bzip2, _) | (SniffedCompression::None, "bz2") => {
+                Box::new(BufReader::new(bzip2::read::BzDecoder::new(rewrapped)))
+            }
+            (SniffedCompression::None, _) => Box::new(BufReader::new(rewrapped)),
+        };
+
+        Ok(Self::from_reader(reader))
+    }
+
+    /// Create a reader over stdin, for `vault-audit <cmd> -` pipelines (e.g.
+    /// `tail -f audit.log | vault-audit ... -`). Stdin has no natural EOF
+    /// when following a live stream, so callers loop [`next_entry`](Self::next_entry)
+    /// the same way they would for a socket stream rather than expecting it
+    /// to return `None`.
+    pub fn from_stdin() -> Self {
+        Self::from_reader(Box::new(BufReader::new(std::io::stdin())))
+    }
+
+    /// Wrap an already-open buffered reader, e.g. one produced by
+    /// [`crate::utils::reader`]-style decompression or a socket connection.
+    pub fn from_reader(reader: Box<dyn BufRead>) -> Self {
+        Self {
+            reader,
             line_buffer: String::new(),
-        })
+            line_number: 0,
+            byte_offset: 0,
+            diagnostics: None,
+            metrics: None,
+        }
+    }
+
+    /// Attach a [`RunMetrics`] handle so every line yielded from here on
+    /// updates its counters atomically - see [`crate::utils::metrics`].
+    pub fn with_metrics(mut self, metrics: Arc<RunMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Enable parse diagnostics: every line that fails to deserialize is
+    /// recorded into a [`ParseReport`] (line number, byte offset, and
+    /// whether it was invalid JSON or valid JSON missing a required field),
+    /// up to [`DEFAULT_MAX_SAMPLES`] samples. Retrieve the report with
+    /// [`Self::diagnostics`] or [`Self::take_diagnostics`] once done reading.
+    pub fn with_diagnostics(self) -> Self {
+        self.with_diagnostics_sampled(DEFAULT_MAX_SAMPLES)
+    }
+
+    /// Same as [`Self::with_diagnostics`], but caps the number of
+    /// [`SkippedLine`](super::diagnostics::SkippedLine) samples retained at
+    /// `max_samples` instead of the default.
+    pub fn with_diagnostics_sampled(mut self, max_samples: usize) -> Self {
+        self.diagnostics = Some(ParseReport::new(max_samples));
+        self
+    }
+
+    /// The accumulated [`ParseReport`], if [`Self::with_diagnostics`] was
+    /// called. Updated after every call to [`Self::next_entry`].
+    pub fn diagnostics(&self) -> Option<&ParseReport> {
+        self.diagnostics.as_ref()
+    }
+
+    /// Takes the accumulated [`ParseReport`] out of this reader, leaving
+    /// diagnostics tracking disabled. Useful once iteration is done and the
+    /// report is about to be printed or returned.
+    pub fn take_diagnostics(&mut self) -> Option<ParseReport> {
+        self.diagnostics.take()
     }
 
     /// Read the next valid audit entry, skipping invalid lines.
     ///
-    /// Invalid JSON lines are silently skipped and do not cause errors.
-    /// This allows processing of audit logs that may contain corrupted
-    /// or malformed entries.
+    /// Invalid JSON lines are silently skipped and do not cause errors. This
+    /// allows processing of audit logs that may contain corrupted or
+    /// malformed entries. Call [`Self::with_diagnostics`] first to instead
+    /// accumulate what's being skipped and why.
     ///
     /// # Returns
     ///
@@ -76,11 +240,14 @@ impl AuditLogReader {
     pub fn next_entry(&mut self) -> Result<Option<AuditEntry>> {
         loop {
             self.line_buffer.clear();
+            let line_start_offset = self.byte_offset;
             let bytes_read = self.reader.read_line(&mut self.line_buffer)?;
 
             if bytes_read == 0 {
                 return Ok(None); // EOF
             }
+            self.byte_offset += bytes_read as u64;
+            self.line_number += 1;
 
             let line = self.line_buffer.trim();
             if line.is_empty() {
@@ -88,9 +255,24 @@ impl AuditLogReader {
             }
 
             match serde_json::from_str(line) {
-                Ok(entry) => return Ok(Some(entry)),
-                Err(_) => {
-                    // Skip invalid lines silently (common in audit logs)
+                Ok(entry) => {
+                    if let Some(report) = &mut self.diagnostics {
+                        report.record_parsed();
+                    }
+                    if let Some(metrics) = &self.metrics {
+                        metrics.record_line(bytes_read as u64);
+                    }
+                    return Ok(Some(entry));
+                }
+                Err(err) => {
+                    // Skip invalid lines silently (common in audit logs),
+                    // but record why when diagnostics are enabled.
+                    if let Some(report) = &mut self.diagnostics {
+                        report.record_skipped(self.line_number, line_start_offset, &err);
+                    }
+                    if let Some(metrics) = &self.metrics {
+                        metrics.record_skipped();
+                    }
                     continue;
                 }
             }
@@ -0,0 +1,208 @@
+//! Pairs request/response audit-log entries into unified transaction records.
+//!
+//! Every Vault operation writes two audit log lines sharing the same
+//! `request.id`: a `request` entry when the operation starts, and a
+//! `response` entry once it completes (or a `response` entry with `error`
+//! set if it failed). [`AuditEntry`] models these lines separately;
+//! [`TransactionJoiner`] buffers them by `request.id` and yields a merged
+//! [`Transaction`] once both halves of a pair have been seen.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use vault_audit_tools::audit::parser::AuditLogReader;
+//! use vault_audit_tools::audit::transaction::TransactionJoiner;
+//!
+//! let reader = AuditLogReader::new("audit.log").unwrap();
+//! for transaction in TransactionJoiner::new(reader, 10_000) {
+//!     let transaction = transaction.unwrap();
+//!     if let Some(latency) = transaction.latency_ms() {
+//!         println!("{} took {}ms", transaction.request_id, latency);
+//!     }
+//! }
+//! ```
+
+use super::parser::AuditLogReader;
+use super::types::AuditEntry;
+use anyhow::Result;
+use std::collections::{HashMap, VecDeque};
+
+/// A request entry merged with its matching response entry (if one has been
+/// seen yet), keyed by `request.id`.
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    /// `request.id` shared by both halves of this pair.
+    pub request_id: String,
+    /// The `request`-type entry that opened this operation.
+    pub request: AuditEntry,
+    /// The matching `response`-type entry, or `None` if the log was
+    /// truncated (or the in-flight window evicted it) before one arrived.
+    pub response: Option<AuditEntry>,
+}
+
+impl Transaction {
+    /// True once a response entry has been paired with the request.
+    pub fn is_complete(&self) -> bool {
+        self.response.is_some()
+    }
+
+    /// Time elapsed between the request and response entries, in
+    /// milliseconds. `None` if there's no response yet, or either entry's
+    /// `time` field fails to parse as RFC 3339.
+    pub fn latency_ms(&self) -> Option<i64> {
+        let response = self.response.as_ref()?;
+        let start = chrono::DateTime::parse_from_rfc3339(&self.request.time).ok()?;
+        let end = chrono::DateTime::parse_from_rfc3339(&response.time).ok()?;
+        Some(end.signed_duration_since(start).num_milliseconds())
+    }
+
+    /// The operation's final error, if it failed. Vault surfaces this on the
+    /// response entry; falls back to the request entry for a truncated
+    /// transaction that never got a response.
+    pub fn error(&self) -> Option<&str> {
+        self.response
+            .as_ref()
+            .and_then(|r| r.error.as_deref())
+            .or(self.request.error.as_deref())
+    }
+
+    /// Warnings attached to the response, if any.
+    pub fn warnings(&self) -> Option<&[String]> {
+        self.response
+            .as_ref()?
+            .response
+            .as_ref()?
+            .warnings
+            .as_deref()
+    }
+
+    /// True if the response carried any `data` payload.
+    pub fn has_response_data(&self) -> bool {
+        self.response
+            .as_ref()
+            .and_then(|r| r.response.as_ref())
+            .and_then(|r| r.data.as_ref())
+            .is_some_and(|data| !data.is_empty())
+    }
+}
+
+/// Buffers request/response entries by `request.id` and yields merged
+/// [`Transaction`]s, so callers can analyze complete operations instead of
+/// half-entries.
+///
+/// The in-flight map is bounded by `max_in_flight`: once it would grow past
+/// that size, the oldest unmatched request is evicted and yielded as an
+/// incomplete [`Transaction`] (`response: None`) rather than held onto
+/// forever. This keeps memory bounded when a log is truncated mid-request or
+/// a response entry is simply missing. Any entries still in flight when the
+/// underlying reader reaches EOF are flushed the same way.
+pub struct TransactionJoiner {
+    reader: AuditLogReader,
+    max_in_flight: usize,
+    /// Requests awaiting a response, keyed by `request.id`.
+    in_flight: HashMap<String, AuditEntry>,
+    /// Insertion order of `in_flight`, oldest first, for FIFO eviction.
+    order: VecDeque<String>,
+    /// Completed or evicted transactions ready to be yielded.
+    ready: VecDeque<Transaction>,
+    /// Set once the underlying reader has returned EOF.
+    exhausted: bool,
+}
+
+impl TransactionJoiner {
+    /// Wrap `reader`, holding at most `max_in_flight` unmatched requests
+    /// before evicting the oldest one.
+    pub fn new(reader: AuditLogReader, max_in_flight: usize) -> Self {
+        Self {
+            reader,
+            max_in_flight: max_in_flight.max(1),
+            in_flight: HashMap::new(),
+            order: VecDeque::new(),
+            ready: VecDeque::new(),
+            exhausted: false,
+        }
+    }
+
+    /// Evicts the oldest in-flight request (if any) as an incomplete
+    /// transaction.
+    fn evict_oldest(&mut self) {
+        while let Some(request_id) = self.order.pop_front() {
+            if let Some(request) = self.in_flight.remove(&request_id) {
+                self.ready.push_back(Transaction {
+                    request_id,
+                    request,
+                    response: None,
+                });
+                return;
+            }
+        }
+    }
+
+    /// Pulls entries from the underlying reader until either a transaction
+    /// is ready or the reader is exhausted.
+    fn fill(&mut self) -> Result<()> {
+        while self.ready.is_empty() && !self.exhausted {
+            let Some(entry) = self.reader.next_entry()? else {
+                self.exhausted = true;
+                break;
+            };
+
+            let Some(request_id) = entry.request.as_ref().and_then(|r| r.id.clone()) else {
+                // No request.id to correlate on - nothing to pair, drop it.
+                continue;
+            };
+
+            match entry.entry_type.as_str() {
+                "response" => {
+                    if let Some(request) = self.in_flight.remove(&request_id) {
+                        self.order.retain(|id| id != &request_id);
+                        self.ready.push_back(Transaction {
+                            request_id,
+                            request,
+                            response: Some(entry),
+                        });
+                    }
+                    // No matching request in flight (evicted, or the log
+                    // started mid-transaction) - nothing to pair this
+                    // response with, so it's dropped.
+                }
+                _ => {
+                    if self.in_flight.len() >= self.max_in_flight {
+                        self.evict_oldest();
+                    }
+                    self.in_flight.insert(request_id.clone(), entry);
+                    self.order.push_back(request_id);
+                }
+            }
+        }
+
+        if self.exhausted {
+            // Flush every request that never got a response.
+            while let Some(request_id) = self.order.pop_front() {
+                if let Some(request) = self.in_flight.remove(&request_id) {
+                    self.ready.push_back(Transaction {
+                        request_id,
+                        request,
+                        response: None,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Iterator for TransactionJoiner {
+    type Item = Result<Transaction>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.ready.is_empty() {
+            if let Err(err) = self.fill() {
+                return Some(Err(err));
+            }
+        }
+
+        self.ready.pop_front().map(Ok)
+    }
+}
@@ -3,9 +3,88 @@
 //! These types closely mirror the JSON structure of Vault audit logs,
 //! enabling efficient deserialization with serde.
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
 
+/// The `hmac-sha256:` prefix Vault writes in front of a hashed audit field
+/// when `log_raw` is disabled for the audit device.
+const HMAC_PREFIX: &str = "hmac-sha256:";
+
+/// A Vault audit field (token, accessor, request data, ...) that may be
+/// HMAC-obfuscated. When the audit device's `log_raw` option is off (the
+/// default, and strongly recommended), Vault replaces sensitive values with
+/// `hmac-sha256:<hex digest>` before writing the log line; with `log_raw` on,
+/// the cleartext value is written instead.
+///
+/// Two entries whose [`HmacValue`] fields carry the same digest came from
+/// the same underlying secret (token, accessor, ...) even though the log
+/// never reveals what that secret was - see [`AuditEntry::correlate_by_token`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HmacValue {
+    /// `hmac-sha256:<digest>` - the hex digest, without the prefix.
+    Hashed(String),
+    /// The field as Vault wrote it, unobfuscated (`log_raw` was enabled).
+    Cleartext(String),
+}
+
+impl HmacValue {
+    /// True if this value is an HMAC digest rather than cleartext.
+    pub fn is_hashed(&self) -> bool {
+        matches!(self, Self::Hashed(_))
+    }
+
+    /// The raw hex digest, if this value is hashed.
+    pub fn digest(&self) -> Option<&str> {
+        match self {
+            Self::Hashed(digest) => Some(digest),
+            Self::Cleartext(_) => None,
+        }
+    }
+
+    /// The cleartext value, if `log_raw` was enabled for this log.
+    pub fn cleartext(&self) -> Option<&str> {
+        match self {
+            Self::Cleartext(value) => Some(value),
+            Self::Hashed(_) => None,
+        }
+    }
+
+    /// The value's identity for correlation purposes: the digest when
+    /// hashed, the cleartext value otherwise. Two [`AuditEntry`]s with equal
+    /// `correlation_key()`s for the same field came from the same secret.
+    pub fn correlation_key(&self) -> &str {
+        match self {
+            Self::Hashed(digest) => digest,
+            Self::Cleartext(value) => value,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for HmacValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        Ok(match raw.strip_prefix(HMAC_PREFIX) {
+            Some(digest) => Self::Hashed(digest.to_string()),
+            None => Self::Cleartext(raw),
+        })
+    }
+}
+
+impl Serialize for HmacValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Hashed(digest) => serializer.serialize_str(&format!("{HMAC_PREFIX}{digest}")),
+            Self::Cleartext(value) => serializer.serialize_str(value),
+        }
+    }
+}
+
 /// Top-level audit log entry.
 ///
 /// Each line in a Vault audit log is a JSON object that deserializes
@@ -36,8 +115,8 @@ pub struct AuditEntry {
 /// including the associated entity, policies, and metadata.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AuthInfo {
-    pub accessor: Option<String>,
-    pub client_token: Option<String>,
+    pub accessor: Option<HmacValue>,
+    pub client_token: Option<HmacValue>,
     pub display_name: Option<String>,
     /// Vault identity entity ID that made this request
     pub entity_id: Option<String>,
@@ -70,8 +149,8 @@ pub struct RequestInfo {
     pub namespace: Option<Namespace>,
     pub remote_address: Option<String>,
     pub remote_port: Option<u16>,
-    pub client_token: Option<String>,
-    pub client_token_accessor: Option<String>,
+    pub client_token: Option<HmacValue>,
+    pub client_token_accessor: Option<HmacValue>,
 }
 
 /// Response information from the audit log.
@@ -146,4 +225,35 @@ impl AuditEntry {
     pub fn is_token_operation(&self) -> bool {
         self.path_starts_with("auth/token/")
     }
+
+    /// The client token's [`HmacValue::correlation_key`] for this entry, if
+    /// one is present. Prefers `auth.client_token` (present on both request
+    /// and response entries); falls back to `request.client_token` for
+    /// entries where `auth` itself is `None` (e.g. unauthenticated requests
+    /// that still carry a token on the request envelope).
+    pub fn token_correlation_key(&self) -> Option<&str> {
+        self.auth
+            .as_ref()
+            .and_then(|auth| auth.client_token.as_ref())
+            .or_else(|| {
+                self.request
+                    .as_ref()
+                    .and_then(|request| request.client_token.as_ref())
+            })
+            .map(HmacValue::correlation_key)
+    }
+
+    /// Groups `entries` by [`token_correlation_key`](Self::token_correlation_key),
+    /// so every entry made by the same token - identified by its HMAC digest
+    /// when the log is obfuscated, or its cleartext value otherwise - ends up
+    /// in the same bucket. Entries with no client token are omitted.
+    pub fn correlate_by_token(entries: &[AuditEntry]) -> HashMap<&str, Vec<&AuditEntry>> {
+        let mut by_token: HashMap<&str, Vec<&AuditEntry>> = HashMap::new();
+        for entry in entries {
+            if let Some(key) = entry.token_correlation_key() {
+                by_token.entry(key).or_default().push(entry);
+            }
+        }
+        by_token
+    }
 }
@@ -1,9 +1,12 @@
+use crate::utils::hyperloglog::HyperLogLog;
 use crate::vault_api::{extract_data, should_skip_verify, VaultClient};
 use anyhow::{Context, Result};
+use futures::TryStreamExt;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
+use tokio::io::AsyncBufReadExt;
 
 #[derive(Debug, Deserialize)]
 struct MountInfo {
@@ -52,6 +55,40 @@ struct MountActivity {
     non_entity: usize,
 }
 
+/// A set of observed client IDs, either tracked exactly or via an
+/// approximate-cardinality sketch to bound memory on huge exports.
+#[derive(Debug)]
+enum ClientSet {
+    Exact(std::collections::HashSet<String>),
+    Approx(HyperLogLog),
+}
+
+impl ClientSet {
+    fn new(approximate: bool) -> Self {
+        if approximate {
+            Self::Approx(HyperLogLog::new())
+        } else {
+            Self::Exact(std::collections::HashSet::new())
+        }
+    }
+
+    fn insert(&mut self, client_id: &str) {
+        match self {
+            Self::Exact(set) => {
+                set.insert(client_id.to_string());
+            }
+            Self::Approx(hll) => hll.insert(&client_id),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Exact(set) => set.len(),
+            Self::Approx(hll) => hll.estimate() as usize,
+        }
+    }
+}
+
 fn format_number(n: usize) -> String {
     let s = n.to_string();
     let mut result = String::new();
@@ -74,9 +111,11 @@ pub async fn run(
     group_by_role: bool,
     entity_map_path: Option<&str>,
     output: Option<&str>,
+    approximate: bool,
+    resolve: &[(String, std::net::SocketAddr)],
 ) -> Result<()> {
     let skip_verify = should_skip_verify(insecure);
-    let client = VaultClient::from_options(vault_addr, vault_token, skip_verify)?;
+    let client = VaultClient::from_options(vault_addr, vault_token, skip_verify, resolve)?;
 
     eprintln!("=== Vault Client Activity Analysis ===");
     eprintln!("Vault Address: {}", client.addr());
@@ -113,35 +152,15 @@ pub async fn run(
         start_time, end_time
     );
 
-    let export_text = client.get_text(&export_path).await?;
-
-    // Parse NDJSON (newline-delimited JSON) or regular JSON
-    let records: Vec<ActivityRecord> = if export_text.trim().starts_with('[') {
-        // Regular JSON array
-        serde_json::from_str(&export_text)?
-    } else {
-        // NDJSON - parse line by line
-        export_text
-            .lines()
-            .filter(|line| !line.trim().is_empty())
-            .filter_map(|line| serde_json::from_str(line).ok())
-            .collect()
-    };
-
-    if records.is_empty() {
-        eprintln!("No activity data found for the specified time range.");
-        return Ok(());
-    }
-
-    eprintln!(
-        "Processing {} activity records...",
-        format_number(records.len())
-    );
-
-    // Group by mount and count unique clients
+    // Group by mount and count unique clients. Each record is folded directly
+    // into these accumulators so peak memory is bounded by the number of
+    // distinct mount/role groups rather than the total export size.
     let mut mount_activities: HashMap<String, MountActivityData> = HashMap::new();
+    let mut records_seen = 0usize;
+
+    let mut fold_record = |record: &ActivityRecord| {
+        records_seen += 1;
 
-    for record in &records {
         let accessor = record
             .mount_accessor
             .as_deref()
@@ -200,20 +219,69 @@ pub async fn run(
                 mount_type,
                 accessor,
                 role: role.clone(),
-                total_clients: std::collections::HashSet::new(),
-                entity_clients: std::collections::HashSet::new(),
-                non_entity_clients: std::collections::HashSet::new(),
+                total_clients: ClientSet::new(approximate),
+                entity_clients: ClientSet::new(approximate),
+                non_entity_clients: ClientSet::new(approximate),
             });
 
-        activity.total_clients.insert(record.client_id.clone());
+        activity.total_clients.insert(&record.client_id);
 
         if record.client_type.as_deref() == Some("entity") {
-            activity.entity_clients.insert(record.client_id.clone());
+            activity.entity_clients.insert(&record.client_id);
         } else {
-            activity.non_entity_clients.insert(record.client_id.clone());
+            activity.non_entity_clients.insert(&record.client_id);
+        }
+    };
+
+    let response = client.get_response(&export_path).await?;
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_string();
+
+    if content_type.contains("application/json") {
+        // Regular JSON array: fall back to the in-memory path, since the
+        // whole array must be parsed before we know it's an array at all.
+        let body = response
+            .text()
+            .await
+            .context("Failed to read response body")?;
+        let records: Vec<ActivityRecord> =
+            serde_json::from_str(&body).context("Failed to parse activity export JSON array")?;
+        for record in &records {
+            fold_record(record);
+        }
+    } else {
+        // NDJSON: stream the body line-by-line, deserializing and folding
+        // one record at a time instead of buffering the whole export.
+        let byte_stream = response
+            .bytes_stream()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e));
+        let stream_reader = tokio_util::io::StreamReader::new(byte_stream);
+        let mut lines = tokio::io::BufReader::new(stream_reader).lines();
+
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Ok(record) = serde_json::from_str::<ActivityRecord>(&line) {
+                fold_record(&record);
+            }
         }
     }
 
+    if records_seen == 0 {
+        eprintln!("No activity data found for the specified time range.");
+        return Ok(());
+    }
+
+    eprintln!(
+        "Processed {} activity records...",
+        format_number(records_seen)
+    );
+
     // Convert to output format
     let mut results: Vec<MountActivity> = mount_activities
         .into_values()
@@ -287,9 +355,9 @@ struct MountActivityData {
     mount_type: String,
     accessor: String,
     role: Option<String>,
-    total_clients: std::collections::HashSet<String>,
-    entity_clients: std::collections::HashSet<String>,
-    non_entity_clients: std::collections::HashSet<String>,
+    total_clients: ClientSet,
+    entity_clients: ClientSet,
+    non_entity_clients: ClientSet,
 }
 
 async fn fetch_mount_map(client: &VaultClient) -> Result<HashMap<String, (String, String)>> {
@@ -1,4 +1,5 @@
 use crate::audit::parser::AuditLogReader;
+use crate::utils::metrics::RunMetrics;
 use anyhow::Result;
 use std::collections::HashMap;
 
@@ -14,10 +15,18 @@ fn format_number(n: usize) -> String {
     result.chars().rev().collect()
 }
 
-pub fn run(log_file: &str, _window_seconds: u64) -> Result<()> {
+pub fn run(log_file: &str, _window_seconds: u64, metrics_addr: Option<&str>) -> Result<()> {
     println!("Analyzing no-entity operations in {}...", log_file);
 
     let mut reader = AuditLogReader::new(log_file)?;
+    let metrics = if let Some(addr) = metrics_addr {
+        let metrics = RunMetrics::new();
+        crate::utils::metrics::serve(addr, metrics.clone())?;
+        reader = reader.with_metrics(metrics.clone());
+        Some(metrics)
+    } else {
+        None
+    };
     let mut operations_by_type: HashMap<String, usize> = HashMap::new();
     let mut paths_accessed: HashMap<String, usize> = HashMap::new();
     let mut display_names: HashMap<String, usize> = HashMap::new();
@@ -40,6 +49,10 @@ pub fn run(log_file: &str, _window_seconds: u64) -> Result<()> {
 
         no_entity_operations += 1;
 
+        if let (Some(metrics), Some(mount_type)) = (&metrics, entry.mount_type()) {
+            metrics.record_operation(mount_type);
+        }
+
         // Track data
         if let Some(op) = entry.operation() {
             *operations_by_type.entry(op.to_string()).or_insert(0) += 1;
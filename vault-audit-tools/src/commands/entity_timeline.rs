@@ -7,6 +7,9 @@
 //!
 //! ```bash
 //! vault-audit entity-timeline audit.log --entity-id abc-123-def
+//!
+//! # Structured output for dashboards / downstream tooling
+//! vault-audit entity-timeline audit.log --entity-id abc-123-def --format json
 //! ```
 //!
 //! # Output
@@ -22,14 +25,35 @@
 //! - Time-based patterns (hourly distribution)
 //! - Mount point usage
 //! - First and last seen timestamps
+//!
+//! `--format json` emits the same computed summary, type/path breakdowns,
+//! hourly rollups, hour-of-day distribution, peak windows, and behavioral
+//! warnings as a single JSON object instead of the fixed-width text report,
+//! for feeding into dashboards or other post-processing.
+//!
+//! `log_file` may also be a directory of rotated logs or a glob
+//! (`logs/audit.log.*`); see [`crate::utils::reader`]. Files are processed
+//! in rotation order (parsed timestamp suffix, falling back to mtime), so
+//! first/last-seen and peak-window math stays correct across rotation
+//! boundaries.
 
 use crate::audit::types::AuditEntry;
 use crate::utils::progress::ProgressBar;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Timelike, Utc};
+use serde::Serialize;
 use std::collections::HashMap;
-use std::fs::File;
 use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Rounds `ts` down to the start of its `bucket_seconds`-wide window,
+/// anchored at the Unix epoch (so `--bucket 1h` reproduces the previous
+/// fixed hourly rollup, and `--bucket 5m` the previous fixed peak window).
+fn bucket_start(ts: DateTime<Utc>, bucket_seconds: i64) -> DateTime<Utc> {
+    let epoch_seconds = ts.timestamp();
+    let start = epoch_seconds - epoch_seconds.rem_euclid(bucket_seconds);
+    DateTime::from_timestamp(start, 0).unwrap_or(ts)
+}
 
 fn format_number(n: usize) -> String {
     let s = n.to_string();
@@ -51,23 +75,82 @@ struct Operation {
     operation: String,
 }
 
-pub fn run(log_file: &str, entity_id: &str, display_name: &Option<String>) -> Result<()> {
-    println!("Analyzing timeline for entity: {}", entity_id);
-    if let Some(name) = display_name {
-        println!("Display name: {}", name);
+/// Structured `--format json` report - see the module docs.
+#[derive(Serialize)]
+struct TimelineReport {
+    entity_id: String,
+    display_name: Option<String>,
+    summary: TimelineSummary,
+    operations_by_type: HashMap<String, usize>,
+    top_paths: Vec<PathCount>,
+    operations_by_hour: HashMap<String, HashMap<String, usize>>,
+    hour_of_day_stats: HashMap<u32, usize>,
+    peak_windows: Vec<PeakWindow>,
+    warnings: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct TimelineSummary {
+    total_operations: usize,
+    time_span_hours: f64,
+    avg_ops_per_hour: f64,
+    avg_ops_per_minute: f64,
+    first_operation: String,
+    last_operation: String,
+}
+
+#[derive(Serialize)]
+struct PathCount {
+    path: String,
+    count: usize,
+}
+
+#[derive(Serialize)]
+struct PeakWindow {
+    window_start: String,
+    operations: usize,
+    rate_per_sec: f64,
+}
+
+pub fn run(
+    log_file: &str,
+    entity_id: &str,
+    display_name: &Option<String>,
+    format: &str,
+    bucket: chrono::Duration,
+) -> Result<()> {
+    let is_json = format == "json";
+    let bucket_seconds = bucket.num_seconds().max(1);
+
+    if !is_json {
+        println!("Analyzing timeline for entity: {}", entity_id);
+        if let Some(name) = display_name {
+            println!("Display name: {}", name);
+        }
+        println!();
     }
-    println!();
 
-    // Get file size for progress tracking
-    let file_size = std::fs::metadata(log_file).ok().map(|m| m.len() as usize);
+    // `log_file` may be a single file, a directory of rotated logs, or a
+    // glob - expand it up front so progress tracking covers the whole set.
+    let files = crate::utils::reader::expand_log_files(Path::new(log_file))?;
+    let file_size: Option<usize> = files
+        .iter()
+        .map(|f| std::fs::metadata(f).ok().map(|m| m.len() as usize))
+        .collect::<Option<Vec<_>>>()
+        .map(|sizes| sizes.iter().sum());
     let mut progress = if let Some(size) = file_size {
         ProgressBar::new(size, "Processing")
     } else {
         ProgressBar::new_spinner("Processing")
     };
 
-    let file = File::open(log_file)?;
-    let reader = BufReader::new(file);
+    let mut chained: Box<dyn std::io::Read> = Box::new(std::io::empty());
+    for file_path in &files {
+        let file = std::fs::File::open(file_path)
+            .with_context(|| format!("Failed to open file: {}", file_path.display()))?;
+        chained = Box::new(std::io::Read::chain(chained, file));
+    }
+    let reader = BufReader::new(chained);
     let mut operations_by_hour: HashMap<String, HashMap<String, usize>> = HashMap::new();
     let mut operations_by_type: HashMap<String, usize> = HashMap::new();
     let mut paths_accessed: HashMap<String, usize> = HashMap::new();
@@ -126,9 +209,11 @@ pub fn run(log_file: &str, entity_id: &str, display_name: &Option<String>) -> Re
         if let Ok(ts) = chrono::DateTime::parse_from_rfc3339(&entry.time) {
             let ts_utc = ts.with_timezone(&Utc);
 
-            // Track by hour
-            let hour_key = ts_utc.format("%Y-%m-%d %H:00").to_string();
-            let hour_ops = operations_by_hour.entry(hour_key).or_default();
+            // Track by bucket
+            let bucket_key = bucket_start(ts_utc, bucket_seconds)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string();
+            let hour_ops = operations_by_hour.entry(bucket_key).or_default();
             *hour_ops.entry("total".to_string()).or_insert(0) += 1;
             *hour_ops.entry(operation.clone()).or_insert(0) += 1;
 
@@ -152,15 +237,21 @@ pub fn run(log_file: &str, entity_id: &str, display_name: &Option<String>) -> Re
         progress.update(size);
     }
 
-    progress.finish_with_message(&format!(
-        "Processed {} lines, found {} operations for entity: {}",
-        format_number(total_lines),
-        format_number(entity_operations),
-        entity_id
-    ));
+    if !is_json {
+        progress.finish_with_message(&format!(
+            "Processed {} lines, found {} operations for entity: {}",
+            format_number(total_lines),
+            format_number(entity_operations),
+            entity_id
+        ));
+    }
 
     if entity_operations == 0 {
-        println!("\nNo operations found for this entity!");
+        if !is_json {
+            println!("\nNo operations found for this entity!");
+        } else {
+            println!("{}", serde_json::json!({"entity_id": entity_id, "total_operations": 0}));
+        }
         return Ok(());
     }
 
@@ -177,6 +268,120 @@ pub fn run(log_file: &str, entity_id: &str, display_name: &Option<String>) -> Re
         return Ok(());
     };
 
+    let mut sorted_ops: Vec<_> = operations_by_type.iter().collect();
+    sorted_ops.sort_by(|a, b| b.1.cmp(a.1));
+
+    let mut sorted_paths: Vec<_> = paths_accessed.iter().collect();
+    sorted_paths.sort_by(|a, b| b.1.cmp(a.1));
+
+    let mut sorted_hours: Vec<_> = operations_by_hour.iter().collect();
+    sorted_hours.sort_by(|a, b| {
+        let a_total = a.1.get("total").unwrap_or(&0);
+        let b_total = b.1.get("total").unwrap_or(&0);
+        b_total.cmp(a_total)
+    });
+
+    let mut hour_of_day_stats: HashMap<u32, usize> = HashMap::new();
+    for op in &operations_timeline {
+        let hour = op.timestamp.hour();
+        *hour_of_day_stats.entry(hour).or_insert(0) += 1;
+    }
+
+    let mut window_counts: HashMap<DateTime<Utc>, usize> = HashMap::new();
+    for op in &operations_timeline {
+        let window_start = bucket_start(op.timestamp, bucket_seconds);
+        *window_counts.entry(window_start).or_insert(0) += 1;
+    }
+    let mut sorted_windows: Vec<_> = window_counts.iter().collect();
+    sorted_windows.sort_by(|a, b| b.1.cmp(a.1));
+
+    // Behavioral warnings, computed once and either printed or serialized.
+    let mut warnings: Vec<String> = Vec::new();
+    if time_span_hours > 1.0 {
+        let ops_per_hour = entity_operations as f64 / time_span_hours;
+        if ops_per_hour > 100.0 {
+            warnings.push(format!(
+                "HIGH FREQUENCY: {:.0} operations/hour suggests automated polling",
+                ops_per_hour
+            ));
+        }
+
+        let token_lookup_paths: Vec<_> = paths_accessed
+            .keys()
+            .filter(|p| p.contains("token/lookup"))
+            .collect();
+        let total_token_lookups: usize = token_lookup_paths
+            .iter()
+            .map(|p| paths_accessed.get(*p).unwrap_or(&0))
+            .sum();
+
+        if total_token_lookups > 1000 {
+            warnings.push(format!(
+                "TOKEN LOOKUP ABUSE: {} token lookups detected ({:.1}/hour)",
+                format_number(total_token_lookups),
+                total_token_lookups as f64 / time_span_hours
+            ));
+        }
+
+        if let Some((top_path, top_count)) = sorted_paths.first() {
+            let top_path_pct = (**top_count as f64 / entity_operations as f64) * 100.0;
+            if top_path_pct > 30.0 {
+                warnings.push(format!(
+                    "PATH CONCENTRATION: {:.1}% of operations on single path ({})",
+                    top_path_pct, top_path
+                ));
+            }
+        }
+
+        let hours_with_activity = (0..24)
+            .filter(|h| hour_of_day_stats.contains_key(h))
+            .count();
+        if hours_with_activity >= 20 {
+            warnings.push(format!(
+                "24/7 ACTIVITY: Active in {}/24 hours",
+                hours_with_activity
+            ));
+        }
+    }
+
+    if is_json {
+        let report = TimelineReport {
+            entity_id: entity_id.to_string(),
+            display_name: display_name.clone(),
+            summary: TimelineSummary {
+                total_operations: entity_operations,
+                time_span_hours,
+                avg_ops_per_hour: entity_operations as f64 / time_span_hours,
+                avg_ops_per_minute: entity_operations as f64 / time_span_hours / 60.0,
+                first_operation: first_op.to_rfc3339(),
+                last_operation: last_op.to_rfc3339(),
+            },
+            operations_by_type,
+            top_paths: sorted_paths
+                .iter()
+                .take(30)
+                .map(|(path, count)| PathCount {
+                    path: path.to_string(),
+                    count: **count,
+                })
+                .collect(),
+            operations_by_hour,
+            hour_of_day_stats,
+            peak_windows: sorted_windows
+                .iter()
+                .take(20)
+                .map(|(window, count)| PeakWindow {
+                    window_start: window.to_rfc3339(),
+                    operations: **count,
+                    rate_per_sec: **count as f64 / bucket_seconds as f64,
+                })
+                .collect(),
+            warnings,
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
     // Analysis and reporting
     println!("\n{}", "=".repeat(100));
     println!("TIMELINE ANALYSIS FOR: {}", entity_id);
@@ -205,15 +410,12 @@ pub fn run(log_file: &str, entity_id: &str, display_name: &Option<String>) -> Re
     println!("{:<30} {:<15} {:<15}", "Operation", "Count", "Percentage");
     println!("{}", "-".repeat(100));
 
-    let mut sorted_ops: Vec<_> = operations_by_type.iter().collect();
-    sorted_ops.sort_by(|a, b| b.1.cmp(a.1));
-
-    for (op, count) in sorted_ops {
-        let percentage = (*count as f64 / entity_operations as f64) * 100.0;
+    for (op, count) in &sorted_ops {
+        let percentage = (**count as f64 / entity_operations as f64) * 100.0;
         println!(
             "{:<30} {:<15} {:<15.2}%",
             op,
-            format_number(*count),
+            format_number(**count),
             percentage
         );
     }
@@ -224,9 +426,6 @@ pub fn run(log_file: &str, entity_id: &str, display_name: &Option<String>) -> Re
     println!("{:<70} {:<15} {:<15}", "Path", "Count", "Percentage");
     println!("{}", "-".repeat(100));
 
-    let mut sorted_paths: Vec<_> = paths_accessed.iter().collect();
-    sorted_paths.sort_by(|a, b| b.1.cmp(a.1));
-
     for (path, count) in sorted_paths.iter().take(30) {
         let percentage = (**count as f64 / entity_operations as f64) * 100.0;
         let display_path = if path.len() > 68 {
@@ -242,22 +441,15 @@ pub fn run(log_file: &str, entity_id: &str, display_name: &Option<String>) -> Re
         );
     }
 
-    // 4. Hourly activity pattern
-    println!("\n4. HOURLY ACTIVITY PATTERN (Top 30 Hours)");
+    // 4. Bucketed activity pattern
+    println!("\n4. ACTIVITY BY {}-SECOND BUCKET (Top 30)", bucket_seconds);
     println!("{}", "-".repeat(100));
     println!(
         "{:<20} {:<12} {:<10} {:<10} {:<10} {:<10}",
-        "Hour", "Total Ops", "read", "update", "list", "Other"
+        "Bucket Start", "Total Ops", "read", "update", "list", "Other"
     );
     println!("{}", "-".repeat(100));
 
-    let mut sorted_hours: Vec<_> = operations_by_hour.iter().collect();
-    sorted_hours.sort_by(|a, b| {
-        let a_total = a.1.get("total").unwrap_or(&0);
-        let b_total = b.1.get("total").unwrap_or(&0);
-        b_total.cmp(a_total)
-    });
-
     for (hour, ops) in sorted_hours.iter().take(30) {
         let total = *ops.get("total").unwrap_or(&0);
         let read = *ops.get("read").unwrap_or(&0);
@@ -280,12 +472,6 @@ pub fn run(log_file: &str, entity_id: &str, display_name: &Option<String>) -> Re
     println!("\n5. ACTIVITY DISTRIBUTION BY HOUR OF DAY");
     println!("{}", "-".repeat(100));
 
-    let mut hour_of_day_stats: HashMap<u32, usize> = HashMap::new();
-    for op in &operations_timeline {
-        let hour = op.timestamp.hour();
-        *hour_of_day_stats.entry(hour).or_insert(0) += 1;
-    }
-
     println!("{:<10} {:<15} {:<50}", "Hour", "Operations", "Bar Chart");
     println!("{}", "-".repeat(100));
 
@@ -306,33 +492,16 @@ pub fn run(log_file: &str, entity_id: &str, display_name: &Option<String>) -> Re
     println!("\n6. PEAK ACTIVITY WINDOWS");
     println!("{}", "-".repeat(100));
 
-    let mut window_counts: HashMap<DateTime<Utc>, usize> = HashMap::new();
-
-    for op in &operations_timeline {
-        // Round to 5-minute window
-        let minute = (op.timestamp.minute() / 5) * 5;
-        let window_start = op
-            .timestamp
-            .with_minute(minute)
-            .unwrap()
-            .with_second(0)
-            .unwrap()
-            .with_nanosecond(0)
-            .unwrap();
-        *window_counts.entry(window_start).or_insert(0) += 1;
-    }
-
-    let mut sorted_windows: Vec<_> = window_counts.iter().collect();
-    sorted_windows.sort_by(|a, b| b.1.cmp(a.1));
-
     println!(
         "{:<25} {:<15} {:<20}",
-        "5-Minute Window", "Operations", "Rate (ops/sec)"
+        format!("{}s Window", bucket_seconds),
+        "Operations",
+        "Rate (ops/sec)"
     );
     println!("{}", "-".repeat(100));
 
     for (window, count) in sorted_windows.iter().take(20) {
-        let rate = **count as f64 / 300.0;
+        let rate = **count as f64 / bucket_seconds as f64;
         println!(
             "{:<25} {:<15} {:<20.3}",
             window.format("%Y-%m-%d %H:%M"),
@@ -345,66 +514,8 @@ pub fn run(log_file: &str, entity_id: &str, display_name: &Option<String>) -> Re
     println!("\n7. BEHAVIORAL PATTERNS");
     println!("{}", "-".repeat(100));
 
-    if time_span_hours > 1.0 {
-        let ops_per_hour = entity_operations as f64 / time_span_hours;
-        if ops_per_hour > 100.0 {
-            println!(
-                "⚠️  HIGH FREQUENCY: {:.0} operations/hour suggests automated polling",
-                ops_per_hour
-            );
-            println!("   Recommended action: Implement caching or increase polling interval");
-        }
-
-        // Check for token lookup abuse
-        let token_lookup_paths: Vec<_> = paths_accessed
-            .keys()
-            .filter(|p| p.contains("token/lookup"))
-            .collect();
-        let total_token_lookups: usize = token_lookup_paths
-            .iter()
-            .map(|p| paths_accessed.get(*p).unwrap_or(&0))
-            .sum();
-
-        if total_token_lookups > 1000 {
-            println!(
-                "⚠️  TOKEN LOOKUP ABUSE: {} token lookups detected",
-                format_number(total_token_lookups)
-            );
-            println!(
-                "   Rate: {:.1} lookups/hour = {:.2} lookups/second",
-                total_token_lookups as f64 / time_span_hours,
-                total_token_lookups as f64 / time_span_hours / 3600.0
-            );
-            println!("   Recommended action: Implement client-side token TTL tracking");
-        }
-
-        // Check for path concentration
-        if let Some((top_path, top_count)) = sorted_paths.first() {
-            let top_path_pct = (**top_count as f64 / entity_operations as f64) * 100.0;
-            if top_path_pct > 30.0 {
-                println!(
-                    "⚠️  PATH CONCENTRATION: {:.1}% of operations on single path",
-                    top_path_pct
-                );
-                println!("   Path: {}", top_path);
-                println!(
-                    "   Recommended action: Review why this path is accessed {} times",
-                    format_number(**top_count)
-                );
-            }
-        }
-
-        // Check for 24/7 activity
-        let hours_with_activity = (0..24)
-            .filter(|h| hour_of_day_stats.contains_key(h))
-            .count();
-        if hours_with_activity >= 20 {
-            println!(
-                "⚠️  24/7 ACTIVITY: Active in {}/24 hours",
-                hours_with_activity
-            );
-            println!("   Suggests automated system or background process");
-        }
+    for warning in &warnings {
+        println!("⚠️  {}", warning);
     }
 
     println!("\n{}", "=".repeat(100));
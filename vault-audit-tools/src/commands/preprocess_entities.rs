@@ -102,7 +102,11 @@ pub fn run(log_file: &str, output: &str) -> Result<()> {
             .trim_end_matches(&format!("/{}", display_name))
             .to_string();
 
-        let mount_accessor = auth.accessor.clone().unwrap_or_default();
+        let mount_accessor = auth
+            .accessor
+            .as_ref()
+            .map(|a| a.correlation_key().to_string())
+            .unwrap_or_default();
         let username = auth
             .metadata
             .as_ref()
@@ -1,4 +1,4 @@
-use crate::audit::types::AuditEntry;
+use crate::audit::types::{AuditEntry, HmacValue};
 use crate::utils::progress::ProgressBar;
 use anyhow::{Context, Result};
 use std::collections::HashMap;
@@ -118,7 +118,8 @@ pub fn run(log_file: &str, output: &str, min_lookups: usize) -> Result<()> {
         let accessor = entry
             .auth
             .as_ref()
-            .and_then(|a| a.accessor.as_deref())
+            .and_then(|a| a.accessor.as_ref())
+            .map(HmacValue::correlation_key)
             .unwrap_or("unknown")
             .to_string();
 
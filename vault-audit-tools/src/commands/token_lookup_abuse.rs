@@ -71,13 +71,14 @@ pub fn run(log_file: &str, threshold: usize) -> Result<()> {
         let Some(entity_id) = entry.entity_id() else { continue };
         let Some(auth) = &entry.auth else { continue };
         let Some(accessor) = &auth.accessor else { continue };
+        let accessor = accessor.correlation_key().to_string();
 
         lookup_lines += 1;
 
         let entity_map = patterns.entry(entity_id.to_string()).or_insert_with(HashMap::new);
-        
+
         entity_map
-            .entry(accessor.clone())
+            .entry(accessor)
             .and_modify(|data| {
                 data.lookups += 1;
                 data.last_seen = entry.time.clone();
@@ -101,10 +101,15 @@ enum Commands {
     EntityGaps {
         /// Path to audit log file
         log_file: String,
-        
+
         /// Time window in seconds for gap detection
         #[arg(long, default_value = "300")]
         window_seconds: u64,
+
+        /// Serve live Prometheus metrics at this address (e.g. 127.0.0.1:9898)
+        /// while processing. Requires the `metrics` feature.
+        #[arg(long)]
+        metrics_addr: Option<String>,
     },
 
     /// Show timeline of operations for a specific entity
@@ -119,6 +124,16 @@ enum Commands {
         /// Display name (optional)
         #[arg(long)]
         display_name: Option<String>,
+
+        /// Output format: "text" (the default report) or "json" (a single
+        /// structured document with the same summary/breakdowns/warnings)
+        #[arg(long, default_value = "text")]
+        format: String,
+
+        /// Rollup/peak-window bucket width: "30s", "5m", "1h", "1d", or the
+        /// shortcuts "hourly"/"daily"
+        #[arg(long, default_value = "1h")]
+        bucket: String,
     },
 
     /// Identify path access hotspots
@@ -177,11 +192,12 @@ fn main() -> Result<()> {
         Commands::TokenLookupAbuse { log_file, threshold } => {
             commands::token_lookup_abuse::run(&log_file, threshold)
         }
-        Commands::EntityGaps { log_file, window_seconds } => {
-            commands::entity_gaps::run(&log_file, window_seconds)
+        Commands::EntityGaps { log_file, window_seconds, metrics_addr } => {
+            commands::entity_gaps::run(&log_file, window_seconds, metrics_addr.as_deref())
         }
-        Commands::EntityTimeline { log_file, entity_id, display_name } => {
-            commands::entity_timeline::run(&log_file, &entity_id, &display_name)
+        Commands::EntityTimeline { log_file, entity_id, display_name, format, bucket } => {
+            let bucket = utils::duration::parse_bucket_duration(&bucket)?;
+            commands::entity_timeline::run(&log_file, &entity_id, &display_name, &format, bucket)
         }
         Commands::PathHotspots { log_file, top } => {
             commands::path_hotspots::run(&log_file, top)
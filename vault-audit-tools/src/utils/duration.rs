@@ -0,0 +1,45 @@
+//! Human-friendly duration parsing for time-bucketed output.
+//!
+//! Accepts forms like `30s`, `5m`, `1h`, `1d`, plus the shortcuts
+//! `hourly`/`daily`, so commands can expose a single `--bucket <dur>` flag
+//! that zooms from second-level burst detection to daily trend buckets
+//! without code changes.
+
+use anyhow::{anyhow, Result};
+use chrono::Duration;
+
+/// Parses a duration string - `30s`, `5m`, `1h`, `1d`, or the shortcuts
+/// `hourly`/`daily` - into a [`chrono::Duration`].
+pub fn parse_bucket_duration(s: &str) -> Result<Duration> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(anyhow!("duration string is empty"));
+    }
+
+    match s.to_lowercase().as_str() {
+        "hourly" => return Ok(Duration::hours(1)),
+        "daily" => return Ok(Duration::days(1)),
+        _ => {}
+    }
+
+    let unit_start = s
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| anyhow!("duration '{}' is missing a unit (s/m/h/d)", s))?;
+    let (value, unit) = s.split_at(unit_start);
+
+    let amount: i64 = value
+        .parse()
+        .map_err(|_| anyhow!("invalid duration amount in '{}'", s))?;
+
+    match unit {
+        "s" => Ok(Duration::seconds(amount)),
+        "m" => Ok(Duration::minutes(amount)),
+        "h" => Ok(Duration::hours(amount)),
+        "d" => Ok(Duration::days(amount)),
+        other => Err(anyhow!(
+            "unrecognized duration unit '{}' in '{}' (expected s/m/h/d)",
+            other,
+            s
+        )),
+    }
+}
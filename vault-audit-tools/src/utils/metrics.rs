@@ -0,0 +1,181 @@
+//! Live Prometheus metrics export for long-running analysis runs.
+//!
+//! Commands that stream a multi-gigabyte log through
+//! [`AuditLogReader`](crate::audit::parser::AuditLogReader) can take minutes
+//! to produce a final report. [`RunMetrics`] gives operators something to
+//! scrape in the meantime: lines parsed, bytes consumed, entries skipped,
+//! per-mount-type operation counts, and per-worker progress, all updated
+//! atomically as entries are yielded.
+//!
+//! Real collection and the HTTP responder only exist when this crate is
+//! built with the `metrics` feature, so the default CLI stays
+//! dependency-light. With the feature off, [`RunMetrics`] is a zero-cost
+//! no-op and [`serve`] errors out rather than silently doing nothing, so a
+//! `--metrics-addr` flag never looks like it worked when it didn't.
+
+#[cfg(feature = "metrics")]
+mod imp {
+    use anyhow::Result;
+    use std::collections::HashMap;
+    use std::io::Write;
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    /// Atomic counters for one analysis run, safe to share across workers.
+    ///
+    /// Cheap to update from the hot path: the scalar counters are plain
+    /// atomics, and the per-mount-type / per-worker maps are only touched
+    /// once per entry, not once per byte.
+    #[derive(Default)]
+    pub struct RunMetrics {
+        lines_parsed: AtomicU64,
+        bytes_consumed: AtomicU64,
+        entries_skipped: AtomicU64,
+        operations_by_mount_type: Mutex<HashMap<String, u64>>,
+        worker_progress: Mutex<HashMap<usize, u64>>,
+    }
+
+    impl RunMetrics {
+        pub fn new() -> Arc<Self> {
+            Arc::new(Self::default())
+        }
+
+        /// Record one successfully parsed line of `bytes` length.
+        pub fn record_line(&self, bytes: u64) {
+            self.lines_parsed.fetch_add(1, Ordering::Relaxed);
+            self.bytes_consumed.fetch_add(bytes, Ordering::Relaxed);
+        }
+
+        /// Record one line that failed to parse.
+        pub fn record_skipped(&self) {
+            self.entries_skipped.fetch_add(1, Ordering::Relaxed);
+        }
+
+        /// Record one operation against `mount_type` (e.g. `"kv"`, `"database"`).
+        pub fn record_operation(&self, mount_type: &str) {
+            let mut by_mount = self.operations_by_mount_type.lock().unwrap();
+            *by_mount.entry(mount_type.to_string()).or_insert(0) += 1;
+        }
+
+        /// Record that `worker_id` has processed `lines` lines so far.
+        pub fn record_worker_progress(&self, worker_id: usize, lines: u64) {
+            self.worker_progress.lock().unwrap().insert(worker_id, lines);
+        }
+
+        /// Render current counters as Prometheus exposition text.
+        pub fn render(&self) -> String {
+            let mut out = String::new();
+
+            out.push_str("# HELP vault_audit_lines_parsed_total Audit log lines successfully parsed.\n");
+            out.push_str("# TYPE vault_audit_lines_parsed_total counter\n");
+            out.push_str(&format!(
+                "vault_audit_lines_parsed_total {}\n",
+                self.lines_parsed.load(Ordering::Relaxed)
+            ));
+
+            out.push_str("# HELP vault_audit_bytes_consumed_total Input bytes read from audit logs.\n");
+            out.push_str("# TYPE vault_audit_bytes_consumed_total counter\n");
+            out.push_str(&format!(
+                "vault_audit_bytes_consumed_total {}\n",
+                self.bytes_consumed.load(Ordering::Relaxed)
+            ));
+
+            out.push_str("# HELP vault_audit_entries_skipped_total Lines skipped because they failed to parse.\n");
+            out.push_str("# TYPE vault_audit_entries_skipped_total counter\n");
+            out.push_str(&format!(
+                "vault_audit_entries_skipped_total {}\n",
+                self.entries_skipped.load(Ordering::Relaxed)
+            ));
+
+            out.push_str("# HELP vault_audit_operations_by_mount_total Operations observed per mount type.\n");
+            out.push_str("# TYPE vault_audit_operations_by_mount_total counter\n");
+            for (mount_type, count) in self.operations_by_mount_type.lock().unwrap().iter() {
+                out.push_str(&format!(
+                    "vault_audit_operations_by_mount_total{{mount_type=\"{}\"}} {}\n",
+                    mount_type, count
+                ));
+            }
+
+            out.push_str("# HELP vault_audit_worker_lines_total Lines processed per worker.\n");
+            out.push_str("# TYPE vault_audit_worker_lines_total gauge\n");
+            for (worker_id, lines) in self.worker_progress.lock().unwrap().iter() {
+                out.push_str(&format!(
+                    "vault_audit_worker_lines_total{{worker=\"{}\"}} {}\n",
+                    worker_id, lines
+                ));
+            }
+
+            out
+        }
+    }
+
+    fn handle_connection(mut stream: TcpStream, metrics: &Arc<RunMetrics>) {
+        // We only serve `GET /metrics`; the request itself is otherwise
+        // ignored since no other endpoint exists to route to.
+        let mut discard = [0u8; 1024];
+        let _ = std::io::Read::read(&mut stream, &mut discard);
+
+        let body = metrics.render();
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let _ = stream.write_all(response.as_bytes());
+    }
+
+    /// Serve `metrics` as Prometheus exposition text at `http://<addr>/metrics`
+    /// from a background thread, for the lifetime of the process.
+    pub fn serve(addr: &str, metrics: Arc<RunMetrics>) -> Result<()> {
+        let listener = TcpListener::bind(addr)?;
+        eprintln!("Serving metrics on http://{}/metrics", addr);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => handle_connection(stream, &metrics),
+                    Err(_) => continue,
+                }
+            }
+        });
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "metrics"))]
+mod imp {
+    use anyhow::{anyhow, Result};
+    use std::sync::Arc;
+
+    /// No-op stand-in for [`RunMetrics`](super::RunMetrics) when this crate
+    /// is built without the `metrics` feature. All recording methods are
+    /// true no-ops so call sites don't need to be feature-gated themselves.
+    #[derive(Default)]
+    pub struct RunMetrics;
+
+    impl RunMetrics {
+        pub fn new() -> Arc<Self> {
+            Arc::new(Self)
+        }
+
+        pub fn record_line(&self, _bytes: u64) {}
+        pub fn record_skipped(&self) {}
+        pub fn record_operation(&self, _mount_type: &str) {}
+        pub fn record_worker_progress(&self, _worker_id: usize, _lines: u64) {}
+
+        pub fn render(&self) -> String {
+            String::new()
+        }
+    }
+
+    /// Errors out: a `--metrics-addr` was given but this build was compiled
+    /// without the `metrics` feature, so nothing would actually be served.
+    pub fn serve(_addr: &str, _metrics: Arc<RunMetrics>) -> Result<()> {
+        Err(anyhow!(
+            "--metrics-addr was given but this build was compiled without the metrics feature"
+        ))
+    }
+}
+
+pub use imp::{serve, RunMetrics};
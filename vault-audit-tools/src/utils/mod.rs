@@ -4,6 +4,10 @@
 //!
 //! - [`progress`] - Progress tracking and display utilities
 //! - [`time`] - Timestamp parsing and formatting helpers
+//! - [`hyperloglog`] - Approximate cardinality estimation for bounded-memory unique counting
+//! - [`metrics`] - Live Prometheus metrics export for long-running runs (behind the `metrics` feature)
+//! - [`reader`] - Multi-file ingestion for rotated audit logs (directory/glob expansion, chained reads)
+//! - [`duration`] - Human-friendly duration parsing for `--bucket`-style flags
 //!
 //! # Examples
 //!
@@ -24,5 +28,9 @@
 //! assert_eq!(format_number(1000000), "1,000,000");
 //! ```
 
+pub mod duration;
+pub mod hyperloglog;
+pub mod metrics;
 pub mod progress;
+pub mod reader;
 pub mod time;
@@ -4,6 +4,31 @@ use serde::de::DeserializeOwned;
 use serde_json::Value;
 use std::env;
 use std::fs;
+use std::net::SocketAddr;
+
+/// Parse a curl-style `--resolve host:port:ip` override into a `(host,
+/// socket_addr)` pair suitable for [`VaultClient::from_options`]. The port
+/// is validated but otherwise only used to build the overridden socket
+/// address; TLS verification still validates against `host`, not the IP.
+pub fn parse_resolve_override(s: &str) -> Result<(String, SocketAddr)> {
+    let mut parts = s.splitn(3, ':');
+    let host = parts
+        .next()
+        .filter(|h| !h.is_empty())
+        .ok_or_else(|| anyhow!("invalid --resolve override '{}': missing host", s))?;
+    let port: u16 = parts
+        .next()
+        .ok_or_else(|| anyhow!("invalid --resolve override '{}': missing port", s))?
+        .parse()
+        .with_context(|| format!("invalid --resolve override '{}': bad port", s))?;
+    let ip: std::net::IpAddr = parts
+        .next()
+        .ok_or_else(|| anyhow!("invalid --resolve override '{}': missing IP", s))?
+        .parse()
+        .with_context(|| format!("invalid --resolve override '{}': bad IP address", s))?;
+
+    Ok((host.to_string(), SocketAddr::new(ip, port)))
+}
 
 /// Check if TLS verification should be skipped based on environment or flag
 pub fn should_skip_verify(insecure_flag: bool) -> bool {
@@ -40,10 +65,7 @@ impl VaultClient {
 
     /// Create a new Vault client with option to skip TLS verification
     pub fn new_with_skip_verify(addr: String, token: String, skip_verify: bool) -> Result<Self> {
-        let client = Client::builder()
-            .danger_accept_invalid_certs(skip_verify)
-            .build()
-            .context("Failed to create HTTP client")?;
+        let client = Self::build_client(skip_verify, &[])?;
 
         Ok(Self {
             addr: addr.trim_end_matches('/').to_string(),
@@ -52,6 +74,18 @@ impl VaultClient {
         })
     }
 
+    /// Build the underlying `reqwest` client, applying any curl-style
+    /// `--resolve host:port:ip` overrides so DNS resolution for `host` is
+    /// pinned to `ip` while TLS SNI and the `Host` header (and therefore
+    /// certificate validation) still use `host`.
+    fn build_client(skip_verify: bool, resolve_overrides: &[(String, SocketAddr)]) -> Result<Client> {
+        let mut builder = Client::builder().danger_accept_invalid_certs(skip_verify);
+        for (host, addr) in resolve_overrides {
+            builder = builder.resolve(host, *addr);
+        }
+        builder.build().context("Failed to create HTTP client")
+    }
+
     /// Create a client from environment variables
     #[allow(dead_code)]
     pub fn from_env() -> Result<Self> {
@@ -76,10 +110,16 @@ impl VaultClient {
     }
 
     /// Create a client with optional parameters (for CLI)
+    ///
+    /// `resolve_overrides` implements curl-style `--resolve host:port:ip`
+    /// host pinning: the given host resolves to the given IP instead of
+    /// going through system DNS, while TLS SNI and certificate validation
+    /// still use the original hostname.
     pub fn from_options(
         vault_addr: Option<&str>,
         vault_token: Option<&str>,
         skip_verify: bool,
+        resolve_overrides: &[(String, SocketAddr)],
     ) -> Result<Self> {
         let addr = vault_addr
             .map(|s| s.to_string())
@@ -104,7 +144,13 @@ impl VaultClient {
             ));
         };
 
-        Self::new_with_skip_verify(addr, token, skip_verify)
+        let client = Self::build_client(skip_verify, resolve_overrides)?;
+
+        Ok(Self {
+            addr: addr.trim_end_matches('/').to_string(),
+            token,
+            client,
+        })
     }
 
     /// Make a GET request to a Vault API endpoint
@@ -171,6 +217,36 @@ impl VaultClient {
         Ok(body)
     }
 
+    /// Make a GET request and return the raw streaming response, for callers
+    /// that want to fold over the body line-by-line (e.g. NDJSON exports)
+    /// instead of buffering it into a `String`.
+    pub async fn get_response(&self, path: &str) -> Result<reqwest::Response> {
+        let url = format!("{}{}", self.addr, path);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("X-Vault-Token", &self.token)
+            .send()
+            .await
+            .context("Failed to send request to Vault")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response
+                .text()
+                .await
+                .context("Failed to read response body")?;
+            return Err(anyhow!(
+                "Vault API request failed with status {}: {}",
+                status,
+                body
+            ));
+        }
+
+        Ok(response)
+    }
+
     /// Get the Vault address
     pub fn addr(&self) -> &str {
         &self.addr
@@ -200,6 +276,23 @@ mod tests {
         assert!(client.is_ok());
     }
 
+    #[test]
+    fn test_parse_resolve_override_valid() {
+        let (host, addr) = parse_resolve_override("vault.internal:8200:10.0.0.5").unwrap();
+        assert_eq!(host, "vault.internal");
+        assert_eq!(addr.to_string(), "10.0.0.5:8200");
+    }
+
+    #[test]
+    fn test_parse_resolve_override_rejects_bad_ip() {
+        assert!(parse_resolve_override("vault.internal:8200:not-an-ip").is_err());
+    }
+
+    #[test]
+    fn test_parse_resolve_override_rejects_missing_parts() {
+        assert!(parse_resolve_override("vault.internal:8200").is_err());
+    }
+
     #[test]
     fn test_addr_trimming() {
         let client = VaultClient::new(
@@ -0,0 +1,93 @@
+use std::io::Write;
+use tempfile::NamedTempFile;
+use vault_audit_tools::audit::diagnostics::ParseErrorCategory;
+use vault_audit_tools::audit::parser::AuditLogReader;
+
+fn write_log(lines: &[&str]) -> NamedTempFile {
+    let mut temp_file = NamedTempFile::new().unwrap();
+    for line in lines {
+        writeln!(temp_file, "{}", line).unwrap();
+    }
+    temp_file.flush().unwrap();
+    temp_file
+}
+
+#[test]
+fn test_diagnostics_disabled_by_default() {
+    let temp = write_log(&[
+        "{not json",
+        r#"{"type":"response","time":"2025-10-07T10:30:00Z","auth":{"entity_id":"test-123"},"request":{"path":"test","operation":"read"},"response":{}}"#,
+    ]);
+
+    let mut reader = AuditLogReader::new(temp.path()).unwrap();
+    let entry = reader.next_entry().unwrap().unwrap();
+    assert_eq!(entry.entity_id(), Some("test-123"));
+    assert!(reader.diagnostics().is_none());
+}
+
+#[test]
+fn test_diagnostics_tracks_invalid_json() {
+    let temp = write_log(&[
+        "{not json at all",
+        r#"{"type":"response","time":"2025-10-07T10:30:00Z","auth":{"entity_id":"test-123"},"request":{"path":"test","operation":"read"},"response":{}}"#,
+    ]);
+
+    let mut reader = AuditLogReader::new(temp.path()).unwrap().with_diagnostics();
+    while reader.next_entry().unwrap().is_some() {}
+
+    let report = reader.diagnostics().unwrap();
+    assert_eq!(report.parsed, 1);
+    assert_eq!(report.skipped, 1);
+    assert_eq!(report.samples.len(), 1);
+    assert_eq!(report.samples[0].line_number, 1);
+    assert_eq!(report.samples[0].category, ParseErrorCategory::InvalidJson);
+}
+
+#[test]
+fn test_diagnostics_distinguishes_missing_fields() {
+    // Valid JSON, but missing the required `time` field.
+    let temp = write_log(&[r#"{"type":"response"}"#]);
+
+    let mut reader = AuditLogReader::new(temp.path()).unwrap().with_diagnostics();
+    while reader.next_entry().unwrap().is_some() {}
+
+    let report = reader.diagnostics().unwrap();
+    assert_eq!(report.skipped, 1);
+    assert_eq!(
+        report.samples[0].category,
+        ParseErrorCategory::MissingFields
+    );
+}
+
+#[test]
+fn test_diagnostics_summary_and_skip_rate() {
+    let temp = write_log(&[
+        "garbage",
+        r#"{"type":"response","time":"2025-10-07T10:30:00Z","auth":{"entity_id":"test-123"},"request":{"path":"test","operation":"read"},"response":{}}"#,
+        r#"{"type":"response","time":"2025-10-07T10:30:01Z","auth":{"entity_id":"test-124"},"request":{"path":"test","operation":"read"},"response":{}}"#,
+    ]);
+
+    let mut reader = AuditLogReader::new(temp.path()).unwrap().with_diagnostics();
+    while reader.next_entry().unwrap().is_some() {}
+
+    let report = reader.diagnostics().unwrap();
+    assert!((report.skip_rate() - (1.0 / 3.0)).abs() < 1e-9);
+    assert!(report.summary().contains("2 parsed"));
+    assert!(report.summary().contains("1 skipped"));
+}
+
+#[test]
+fn test_diagnostics_sample_cap() {
+    let lines: Vec<String> = (0..5).map(|_| "not json".to_string()).collect();
+    let line_refs: Vec<&str> = lines.iter().map(String::as_str).collect();
+    let temp = write_log(&line_refs);
+
+    let mut reader = AuditLogReader::new(temp.path())
+        .unwrap()
+        .with_diagnostics_sampled(2);
+    while reader.next_entry().unwrap().is_some() {}
+
+    let report = reader.diagnostics().unwrap();
+    assert_eq!(report.skipped, 5);
+    assert_eq!(report.samples.len(), 2);
+}
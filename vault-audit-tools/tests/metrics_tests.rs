@@ -0,0 +1,15 @@
+use vault_audit_tools::utils::metrics::RunMetrics;
+
+#[test]
+fn test_no_op_without_metrics_feature() {
+    // Without the `metrics` feature, recording is a true no-op and render()
+    // returns nothing to scrape - it must never panic either way.
+    let metrics = RunMetrics::new();
+    metrics.record_line(128);
+    metrics.record_skipped();
+    metrics.record_operation("kv");
+    metrics.record_worker_progress(0, 10);
+
+    #[cfg(not(feature = "metrics"))]
+    assert_eq!(metrics.render(), "");
+}
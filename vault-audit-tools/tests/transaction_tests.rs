@@ -0,0 +1,87 @@
+use std::io::Write;
+use tempfile::NamedTempFile;
+use vault_audit_tools::audit::parser::AuditLogReader;
+use vault_audit_tools::audit::transaction::TransactionJoiner;
+
+fn write_log(lines: &[&str]) -> NamedTempFile {
+    let mut temp_file = NamedTempFile::new().unwrap();
+    for line in lines {
+        writeln!(temp_file, "{}", line).unwrap();
+    }
+    temp_file.flush().unwrap();
+    temp_file
+}
+
+#[test]
+fn test_pairs_request_and_response() {
+    let temp = write_log(&[
+        r#"{"type":"request","time":"2025-10-07T10:30:00.000000Z","request":{"id":"req-1","path":"secret/data/test","operation":"read"}}"#,
+        r#"{"type":"response","time":"2025-10-07T10:30:00.250000Z","request":{"id":"req-1"},"response":{"data":{"key":"value"}}}"#,
+    ]);
+
+    let reader = AuditLogReader::new(temp.path()).unwrap();
+    let transactions: Vec<_> = TransactionJoiner::new(reader, 100)
+        .map(|t| t.unwrap())
+        .collect();
+
+    assert_eq!(transactions.len(), 1);
+    let transaction = &transactions[0];
+    assert_eq!(transaction.request_id, "req-1");
+    assert!(transaction.is_complete());
+    assert_eq!(transaction.latency_ms(), Some(250));
+    assert!(transaction.has_response_data());
+    assert!(transaction.error().is_none());
+}
+
+#[test]
+fn test_unmatched_request_flushed_at_eof() {
+    let temp = write_log(&[
+        r#"{"type":"request","time":"2025-10-07T10:30:00.000000Z","request":{"id":"req-1","path":"secret/data/test","operation":"read"}}"#,
+    ]);
+
+    let reader = AuditLogReader::new(temp.path()).unwrap();
+    let transactions: Vec<_> = TransactionJoiner::new(reader, 100)
+        .map(|t| t.unwrap())
+        .collect();
+
+    assert_eq!(transactions.len(), 1);
+    assert!(!transactions[0].is_complete());
+    assert_eq!(transactions[0].latency_ms(), None);
+}
+
+#[test]
+fn test_bounded_in_flight_evicts_oldest() {
+    let temp = write_log(&[
+        r#"{"type":"request","time":"2025-10-07T10:30:00.000000Z","request":{"id":"req-1","path":"secret/data/a","operation":"read"}}"#,
+        r#"{"type":"request","time":"2025-10-07T10:30:01.000000Z","request":{"id":"req-2","path":"secret/data/b","operation":"read"}}"#,
+        r#"{"type":"request","time":"2025-10-07T10:30:02.000000Z","request":{"id":"req-3","path":"secret/data/c","operation":"read"}}"#,
+    ]);
+
+    let reader = AuditLogReader::new(temp.path()).unwrap();
+    let joiner = TransactionJoiner::new(reader, 2);
+    let transactions: Vec<_> = joiner.map(|t| t.unwrap()).collect();
+
+    // req-1 gets evicted once req-3 would push the in-flight map past its
+    // bound of 2, and is yielded as an incomplete transaction immediately;
+    // req-2 and req-3 are flushed incomplete at EOF.
+    assert_eq!(transactions.len(), 3);
+    assert_eq!(transactions[0].request_id, "req-1");
+    assert!(!transactions[0].is_complete());
+}
+
+#[test]
+fn test_ignores_entries_without_request_id() {
+    let temp = write_log(&[
+        r#"{"type":"request","time":"2025-10-07T10:30:00.000000Z","request":{"path":"secret/data/test","operation":"read"}}"#,
+        r#"{"type":"request","time":"2025-10-07T10:30:01.000000Z","request":{"id":"req-1","path":"secret/data/test","operation":"read"}}"#,
+        r#"{"type":"response","time":"2025-10-07T10:30:01.100000Z","request":{"id":"req-1"},"response":{}}"#,
+    ]);
+
+    let reader = AuditLogReader::new(temp.path()).unwrap();
+    let transactions: Vec<_> = TransactionJoiner::new(reader, 100)
+        .map(|t| t.unwrap())
+        .collect();
+
+    assert_eq!(transactions.len(), 1);
+    assert_eq!(transactions[0].request_id, "req-1");
+}
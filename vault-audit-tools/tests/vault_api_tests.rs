@@ -74,9 +74,10 @@ fn test_vault_client_from_options_with_all_params() {
         Some("https://vault.example.com:8200"),
         Some("hvs.test-token"),
         false,
+        &[],
     );
     assert!(client.is_ok());
-    
+
     let client = client.unwrap();
     assert_eq!(client.addr(), "https://vault.example.com:8200");
 }
@@ -98,11 +99,23 @@ fn test_vault_client_from_options_no_token_fails() {
         Some("https://vault.example.com:8200"),
         None,
         false,
+        &[],
     );
     // Might pass or fail depending on environment, so just test it runs
     let _ = client;
 }
 
+#[test]
+fn test_vault_client_from_options_with_resolve_override() {
+    let client = VaultClient::from_options(
+        Some("https://vault.example.com:8200"),
+        Some("hvs.test-token"),
+        false,
+        &[("vault.example.com".to_string(), "10.0.0.5:8200".parse().unwrap())],
+    );
+    assert!(client.is_ok());
+}
+
 #[test]
 fn test_vault_client_from_options_param_overrides_env() {
     // Skip env-based tests due to test isolation issues